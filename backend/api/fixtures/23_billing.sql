@@ -0,0 +1,6 @@
+-- individual account for user 0, with a stripe customer
+insert into account (account_id, account_type, stripe_customer_id, created_at)
+values ('b8a5bb6e-6e5f-11ee-b962-0242ac120002', 0, 'cus_OsEbJa4aYk1dVZ', '2023-10-19T00:00:00Z'::timestamptz);
+
+insert into user_account (user_id, account_id, admin, verified)
+values ('1f241e1b-b537-493f-a230-075cb16315be', 'b8a5bb6e-6e5f-11ee-b962-0242ac120002', true, true);
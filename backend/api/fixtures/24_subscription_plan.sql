@@ -0,0 +1,8 @@
+-- plans priced by the fake payment provider the tests run with
+insert into subscription_plan (plan_id, price_id, plan_type, created_at)
+values ('7e1a2b3c-6e5f-11ee-b962-0242ac120002', 'price_test_basic_monthly', 0, '2023-10-19T00:00:00Z'::timestamptz),
+       ('7e1a2e8e-6e5f-11ee-b962-0242ac120002', 'price_test_pro_monthly', 2, '2023-10-19T00:00:00Z'::timestamptz),
+       ('7e1a2fec-6e5f-11ee-b962-0242ac120002', 'price_test_pro_annually', 3, '2023-10-19T00:00:00Z'::timestamptz);
+
+insert into subscription_plan (plan_id, price_id, plan_type, created_at)
+values ('7e1a3150-6e5f-11ee-b962-0242ac120002', 'price_test_school_level_1_monthly', 4, '2023-10-19T00:00:00Z'::timestamptz);
@@ -0,0 +1,23 @@
+-- level 1 school (5 seats) administered by user 0, with user 1 as a teacher and two pending invites
+insert into account (account_id, account_type, stripe_customer_id, created_at)
+values ('c4b2f5a0-6e5f-11ee-b962-0242ac120002', 1, 'cus_school_fixture', '2023-10-19T00:00:00Z'::timestamptz);
+
+insert into user_account (user_id, account_id, admin, verified)
+values ('1f241e1b-b537-493f-a230-075cb16315be', 'c4b2f5a0-6e5f-11ee-b962-0242ac120002', true, true),
+       ('7b96a41c-e406-11eb-8176-efd86dd7f444', 'c4b2f5a0-6e5f-11ee-b962-0242ac120002', false, true);
+
+insert into school (school_id, email, account_id, school_name, verified, created_at)
+values ('c4b2f80c-6e5f-11ee-b962-0242ac120002', 'school@test.test', 'c4b2f5a0-6e5f-11ee-b962-0242ac120002',
+        'Fixture School', true, '2023-10-19T00:00:00Z'::timestamptz);
+
+insert into subscription (subscription_id, stripe_subscription_id, subscription_plan_id, status, current_period_end,
+                          account_id, price, created_at)
+values ('c4b2f938-6e5f-11ee-b962-0242ac120002', 'sub_school_fixture', '7e1a3150-6e5f-11ee-b962-0242ac120002', 1,
+        '2030-01-01T00:00:00Z'::timestamptz, 'c4b2f5a0-6e5f-11ee-b962-0242ac120002', 11500,
+        '2023-10-19T00:00:00Z'::timestamptz);
+
+insert into school_invite (invite_id, account_id, email, invited_by, created_at)
+values ('c4b2fa64-6e5f-11ee-b962-0242ac120002', 'c4b2f5a0-6e5f-11ee-b962-0242ac120002', 'pending1@test.test',
+        '1f241e1b-b537-493f-a230-075cb16315be', '2023-10-20T00:00:00Z'::timestamptz),
+       ('c4b2fb90-6e5f-11ee-b962-0242ac120002', 'c4b2f5a0-6e5f-11ee-b962-0242ac120002', 'pending2@test.test',
+        '1f241e1b-b537-493f-a230-075cb16315be', '2023-10-21T00:00:00Z'::timestamptz);
@@ -0,0 +1,20 @@
+-- level 1 school administered by user 1, which has invited user 0
+insert into account (account_id, account_type, stripe_customer_id, created_at)
+values ('d0e1a2b2-6e5f-11ee-b962-0242ac120002', 1, 'cus_school_invite_fixture', '2023-10-19T00:00:00Z'::timestamptz);
+
+insert into user_account (user_id, account_id, admin, verified)
+values ('7b96a41c-e406-11eb-8176-efd86dd7f444', 'd0e1a2b2-6e5f-11ee-b962-0242ac120002', true, true);
+
+insert into school (school_id, email, account_id, school_name, verified, created_at)
+values ('d0e1a4f6-6e5f-11ee-b962-0242ac120002', 'invite-school@test.test', 'd0e1a2b2-6e5f-11ee-b962-0242ac120002',
+        'Inviting School', true, '2023-10-19T00:00:00Z'::timestamptz);
+
+insert into subscription (subscription_id, stripe_subscription_id, subscription_plan_id, status, current_period_end,
+                          account_id, price, created_at)
+values ('d0e1a622-6e5f-11ee-b962-0242ac120002', 'sub_school_invite_fixture', '7e1a3150-6e5f-11ee-b962-0242ac120002',
+        1, '2030-01-01T00:00:00Z'::timestamptz, 'd0e1a2b2-6e5f-11ee-b962-0242ac120002', 11500,
+        '2023-10-19T00:00:00Z'::timestamptz);
+
+insert into school_invite (invite_id, account_id, email, invited_by, created_at)
+values ('d0e1a74e-6e5f-11ee-b962-0242ac120002', 'd0e1a2b2-6e5f-11ee-b962-0242ac120002', 'test@test.test',
+        '7b96a41c-e406-11eb-8176-efd86dd7f444', '2023-10-20T00:00:00Z'::timestamptz);
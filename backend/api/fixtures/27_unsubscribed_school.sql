@@ -0,0 +1,11 @@
+-- school administered by user 0 which has never subscribed or been billed
+insert into account (account_id, account_type, created_at)
+values ('e2a7c3f0-6e5f-11ee-b962-0242ac120002', 1, '2023-10-19T00:00:00Z'::timestamptz);
+
+insert into user_account (user_id, account_id, admin, verified)
+values ('1f241e1b-b537-493f-a230-075cb16315be', 'e2a7c3f0-6e5f-11ee-b962-0242ac120002', true, true);
+
+insert into school (school_id, email, account_id, school_name, verified, created_at)
+values ('e2a7c5a8-6e5f-11ee-b962-0242ac120002', 'unsubscribed-school@test.test',
+        'e2a7c3f0-6e5f-11ee-b962-0242ac120002', 'Unsubscribed School', true,
+        '2023-10-19T00:00:00Z'::timestamptz);
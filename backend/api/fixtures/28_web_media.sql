@@ -0,0 +1,10 @@
+insert into web_media_library (id, hash, kind, created_at, created_by)
+values ('8d7e1d02-e4a2-11eb-8176-2b0d5e4d4e21', '\x01', 0, '2021-07-01T00:00:00Z'::timestamptz, '1f241e1b-b537-493f-a230-075cb16315be'), -- png sticker
+       ('8d7e1d03-e4a2-11eb-8176-2b0d5e4d4e21', '\x02', 1, '2021-07-02T00:00:00Z'::timestamptz, '1f241e1b-b537-493f-a230-075cb16315be'), -- gif
+       ('8d7e1d04-e4a2-11eb-8176-2b0d5e4d4e21', '\x03', 5, '2021-07-03T00:00:00Z'::timestamptz, null); -- svg, uploader unknown
+
+insert into web_media_library_url (media_id, media_url, created_at)
+values ('8d7e1d02-e4a2-11eb-8176-2b0d5e4d4e21', 'https://example.com/apple.png', '2021-07-01T00:00:00Z'::timestamptz),
+       ('8d7e1d02-e4a2-11eb-8176-2b0d5e4d4e21', 'https://example.org/apple.png', '2021-07-04T00:00:00Z'::timestamptz), -- same bytes, different url
+       ('8d7e1d03-e4a2-11eb-8176-2b0d5e4d4e21', 'https://example.com/dance.gif', '2021-07-02T00:00:00Z'::timestamptz),
+       ('8d7e1d04-e4a2-11eb-8176-2b0d5e4d4e21', 'https://example.com/star.svg', '2021-07-03T00:00:00Z'::timestamptz);
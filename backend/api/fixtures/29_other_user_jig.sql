@@ -0,0 +1,18 @@
+-- a private, unpublished JIG of another user, which the test user can't use
+insert into "user" (id, created_at)
+values ('7b5c1c32-3a54-11ef-9c0c-6b3c3e5a2d11', '2020-08-08T00:11:21Z'::timestamptz);
+
+insert into jig_data (id, display_name, created_at, updated_at, language, last_synced_at, description, theme,
+                      audio_background, audio_feedback_negative, audio_feedback_positive, direction, privacy_level, scoring,
+                      drag_assist, draft_or_live, translated_description)
+values ('7b5c2010-3a54-11ef-9c0c-bf5b0a6a41c2', 'private', '2021-03-04 00:46:26.134651+00', -- live
+        '2021-03-04 00:46:26.134651+00', 'en', '2021-03-04 00:46:26.134651+00', 'private description', 0, null,
+        array [0, 1], array [0, 1, 2], 0, 2, true, true, 1, '{}'::jsonb),
+       ('7b5c2204-3a54-11ef-9c0c-1f2d6a9c1e53', 'private', '2021-03-04 00:46:26.134651+00', -- draft
+        '2021-03-04 00:46:26.134651+00', 'en', '2021-03-04 00:46:26.134651+00', 'private description', 0, null,
+        array [0, 1], array [0, 1, 2], 0, 2, true, true, 0, '{}'::jsonb);
+
+insert into jig (id, creator_id, author_id, live_id, draft_id, published_at)
+values ('7b5c23e4-3a54-11ef-9c0c-0b7f3c0e8a64', '7b5c1c32-3a54-11ef-9c0c-6b3c3e5a2d11',
+        '7b5c1c32-3a54-11ef-9c0c-6b3c3e5a2d11', '7b5c2010-3a54-11ef-9c0c-bf5b0a6a41c2',
+        '7b5c2204-3a54-11ef-9c0c-1f2d6a9c1e53', null);
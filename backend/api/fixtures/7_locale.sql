@@ -19,12 +19,12 @@ values
 
 -- unlike with everything else, the sequential ids mean that we can't just insert with the ids, since that doesn't increment the sequence.
 -- luckily, ids are deterministic.
-insert into locale_entry (bundle_id, section, item_kind_id, english, status, in_app, in_element, in_mock, created_at)
+insert into locale_entry (bundle_id, section, item_kind_id, key, translations, status, in_app, in_element, in_mock, created_at)
 values
-    ('7c11d04e-7c67-11eb-a0d7-9b5fb8f29de9', 'A', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', 'Entry #1', 0, true, true, false, '2021-03-03 21:41:17.146015+00'),
-    ('8359a48a-7c67-11eb-a0d7-0fd74777a62c', 'A', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', 'Entry #2', 0, true, true, false, '2021-03-03 21:41:33.251747+00'),
-    ('7c11d04e-7c67-11eb-a0d7-9b5fb8f29de9', 'A', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', 'Entry #3', 0, true, true, false, '2021-03-03 21:41:36.928568+00'),
-    ('7c11d04e-7c67-11eb-a0d7-9b5fb8f29de9', 'B', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', 'Entry #B-1', 0, true, true, false, '2021-03-03 21:41:48.042122+00'),
-    ('7c11d04e-7c67-11eb-a0d7-9b5fb8f29de9', 'C', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', 'Entry #B-1', 0, true, true, false, '2021-03-03 21:41:53.888321+00');
+    ('7c11d04e-7c67-11eb-a0d7-9b5fb8f29de9', 'A', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', 'entry-1', '{"en": "Entry #1"}', 0, true, true, false, '2021-03-03 21:41:17.146015+00'),
+    ('8359a48a-7c67-11eb-a0d7-0fd74777a62c', 'A', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', 'entry-2', '{"en": "Entry #2"}', 0, true, true, false, '2021-03-03 21:41:33.251747+00'),
+    ('7c11d04e-7c67-11eb-a0d7-9b5fb8f29de9', 'A', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', 'entry-3', '{"en": "Entry #3"}', 0, true, true, false, '2021-03-03 21:41:36.928568+00'),
+    ('7c11d04e-7c67-11eb-a0d7-9b5fb8f29de9', 'B', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', 'entry-b-1', '{"en": "Entry #B-1", "he": "רשומה #B-1"}', 0, true, true, false, '2021-03-03 21:41:48.042122+00'),
+    ('7c11d04e-7c67-11eb-a0d7-9b5fb8f29de9', 'C', '1623eab4-7c68-11eb-a0d7-5f27277abc1b', null, '{"en": "Entry #B-1"}', 0, true, true, false, '2021-03-03 21:41:53.888321+00');
 
 
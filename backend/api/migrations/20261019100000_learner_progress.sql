@@ -0,0 +1,31 @@
+create table course_unit_progress
+(
+    user_id         uuid        not null references "user" (id) on delete cascade,
+    course_id       uuid        not null references course (id) on delete cascade,
+    unit_id         uuid        not null,
+    status          smallint    not null default 0,
+    score_earned    real,
+    score_available real,
+    started_at      timestamptz not null default now(),
+    completed_at    timestamptz,
+    updated_at      timestamptz not null default now(),
+    primary key (user_id, course_id, unit_id)
+);
+
+create index course_unit_progress_user_updated_at_idx on course_unit_progress (user_id, updated_at desc);
+
+create table playlist_jig_progress
+(
+    user_id         uuid        not null references "user" (id) on delete cascade,
+    playlist_id     uuid        not null references playlist (id) on delete cascade,
+    jig_id          uuid        not null references jig (id) on delete cascade,
+    status          smallint    not null default 0,
+    score_earned    real,
+    score_available real,
+    started_at      timestamptz not null default now(),
+    completed_at    timestamptz,
+    updated_at      timestamptz not null default now(),
+    primary key (user_id, playlist_id, jig_id)
+);
+
+create index playlist_jig_progress_user_updated_at_idx on playlist_jig_progress (user_id, updated_at desc);
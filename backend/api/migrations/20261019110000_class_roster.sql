@@ -0,0 +1,53 @@
+create table class
+(
+    id           uuid        not null default uuid_generate_v1mc() primary key,
+    owner_id     uuid        not null references "user" (id) on delete cascade,
+    account_id   uuid references account (account_id) on delete set null,
+    display_name text        not null,
+    login_code   text        not null unique,
+    created_at   timestamptz not null default now(),
+    updated_at   timestamptz
+);
+
+create index class_owner_id_idx on class (owner_id);
+
+create table class_student
+(
+    id               uuid        not null default uuid_generate_v1mc() primary key,
+    class_id         uuid        not null references class (id) on delete cascade,
+    display_name     text        not null,
+    login_code       text        not null unique,
+    picture_password smallint[]  not null,
+    created_at       timestamptz not null default now(),
+    updated_at       timestamptz
+);
+
+create index class_student_class_id_idx on class_student (class_id);
+
+create table class_assignment
+(
+    id          uuid        not null default uuid_generate_v1mc() primary key,
+    class_id    uuid        not null references class (id) on delete cascade,
+    jig_id      uuid references jig (id) on delete cascade,
+    playlist_id uuid references playlist (id) on delete cascade,
+    due_at      timestamptz,
+    created_at  timestamptz not null default now(),
+    updated_at  timestamptz,
+    check (num_nonnulls(jig_id, playlist_id) = 1)
+);
+
+create index class_assignment_class_id_idx on class_assignment (class_id);
+
+-- the jig code that sessions of an assignment's jig are recorded under
+create table class_assignment_code
+(
+    assignment_id uuid    not null references class_assignment (id) on delete cascade,
+    jig_id        uuid    not null references jig (id) on delete cascade,
+    code          integer not null references jig_code (code) on delete cascade,
+    primary key (assignment_id, jig_id)
+);
+
+alter table jig_code_session
+    add column student_id uuid references class_student (id) on delete set null;
+
+create index jig_code_session_student_id_idx on jig_code_session (student_id) where student_id is not null;
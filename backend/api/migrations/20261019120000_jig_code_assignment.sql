@@ -0,0 +1,5 @@
+alter table jig_code
+    add column opens_at     timestamptz,
+    add column due_at       timestamptz,
+    add column max_attempts smallint check (max_attempts > 0),
+    add column hide_score   boolean not null default false;
@@ -0,0 +1,21 @@
+-- every transactional email is queued here before it's sent, so failed sends can be retried
+-- and what was sent to whom can be audited
+create table email_outbox
+(
+    id              uuid        not null default uuid_generate_v1mc() primary key,
+    template        smallint    not null,
+    to_email        text        not null,
+    locale          text        not null,
+    subject         text        not null,
+    -- template variables, cleared once sent since they can contain login links
+    data            jsonb,
+    status          smallint    not null default 0,
+    attempts        smallint    not null default 0,
+    last_error      text,
+    next_attempt_at timestamptz not null default now(),
+    created_at      timestamptz not null default now(),
+    sent_at         timestamptz
+);
+
+create index email_outbox_pending_idx on email_outbox (next_attempt_at) where status = 0;
+create index email_outbox_to_email_idx on email_outbox (to_email);
@@ -0,0 +1,38 @@
+create table notification
+(
+    id         uuid        not null default uuid_generate_v1mc() primary key,
+    -- who the notification is for
+    user_id    uuid        not null references "user" (id) on delete cascade,
+    kind       smallint    not null,
+    -- who caused it, if anyone
+    actor_id   uuid references "user" (id) on delete cascade,
+    jig_id     uuid references jig (id) on delete cascade,
+    circle_id  uuid references circle (id) on delete cascade,
+    comment_id uuid references jig_curation_comment (id) on delete cascade,
+    -- events grouped into this notification while it's unread, e.g. anonymous plays of a jig
+    count      integer     not null default 1 check (count > 0),
+    read_at    timestamptz,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now()
+);
+
+create index notification_user_id_idx on notification (user_id, updated_at desc);
+create index notification_unread_idx on notification (user_id) where read_at is null;
+-- plays of a jig are grouped into one notification until it's read
+create unique index notification_unread_play_idx on notification (user_id, jig_id) where kind = 1 and read_at is null;
+
+-- kinds a user has changed from the default of being notified both in app and by email
+create table notification_preference
+(
+    user_id uuid     not null references "user" (id) on delete cascade,
+    kind    smallint not null,
+    in_app  boolean  not null,
+    email   boolean  not null,
+    primary key (user_id, kind)
+);
+
+create table notification_digest
+(
+    user_id uuid        not null primary key references "user" (id) on delete cascade,
+    sent_at timestamptz not null
+);
@@ -0,0 +1,15 @@
+-- every stripe webhook event we've received, so redeliveries can be skipped and failures replayed
+create table stripe_webhook_event
+(
+    id           text        not null primary key,
+    event_type   text        not null,
+    payload      jsonb       not null,
+    -- 0 pending, 1 processed, 2 failed, 3 ignored
+    status       smallint    not null default 0,
+    attempts     integer     not null default 0,
+    error        text,
+    created_at   timestamptz not null default now(),
+    processed_at timestamptz
+);
+
+create index stripe_webhook_event_status_idx on stripe_webhook_event (status, created_at desc);
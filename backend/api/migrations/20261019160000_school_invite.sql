@@ -0,0 +1,16 @@
+-- invites to join a school account. pending invites hold a seat on the school's plan until they're
+-- accepted, declined or revoked.
+create table school_invite
+(
+    invite_id   uuid        not null primary key default uuid_generate_v1mc(),
+    account_id  uuid        not null references account (account_id) on delete cascade,
+    email       citext      not null,
+    invited_by  uuid        references "user" (id) on delete set null,
+    created_at  timestamptz not null default now(),
+    accepted_at timestamptz,
+    accepted_by uuid        references "user" (id) on delete set null
+);
+
+create unique index school_invite_pending_idx on school_invite (account_id, email) where accepted_at is null;
+
+create index school_invite_email_idx on school_invite (email) where accepted_at is null;
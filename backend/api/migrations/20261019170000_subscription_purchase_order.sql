@@ -0,0 +1,6 @@
+-- Schools which pay by invoice against a purchase order instead of by card.
+alter table subscription
+    add column purchase_order_number text,
+    add column days_until_due        int4,
+    add constraint subscription_purchase_order_check
+        check ((purchase_order_number is null) = (days_until_due is null));
@@ -0,0 +1,4 @@
+-- filled in when the uploaded audio is processed
+alter table user_audio_library
+    add column duration_ms integer check (duration_ms >= 0),
+    add column waveform    bytea;
@@ -0,0 +1,6 @@
+-- Page counts, previews and text extracted from PDFs on upload.
+-- `preview_page_count` is how many pages (from the first) have a preview image stored.
+alter table user_pdf_library
+    add column page_count         int,
+    add column preview_page_count int  not null default 0,
+    add column content_text       text not null default '';
@@ -0,0 +1,14 @@
+-- bytes stored for each uploaded file, including all of its renditions, for storage quotas.
+-- files uploaded before this are counted as 0 bytes.
+alter table user_image_library
+    add column size_bytes bigint not null default 0 check (size_bytes >= 0);
+
+alter table user_audio_library
+    add column size_bytes bigint not null default 0 check (size_bytes >= 0);
+
+alter table user_pdf_library
+    add column size_bytes bigint not null default 0 check (size_bytes >= 0);
+
+create index on user_image_library (user_id);
+create index on user_audio_library (user_id);
+create index on user_pdf_library (user_id);
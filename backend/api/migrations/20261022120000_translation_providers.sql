@@ -0,0 +1,201 @@
+-- terms which translation providers tend to get wrong, with how to write them in each language.
+-- `terms` maps a language code to its spellings, the first of which is used in translations.
+create table translation_glossary
+(
+    id         uuid primary key     default uuid_generate_v1mc(),
+    terms      jsonb       not null check (jsonb_typeof(terms) = 'object'),
+    created_at timestamptz not null default now(),
+    updated_at timestamptz
+);
+
+insert into translation_glossary (terms)
+values ('{"en": ["Shabbat", "Shabbos", "Sabbath"], "iw": ["שבת"], "yi": ["שבת"], "es": ["Shabat"], "fr": ["Chabbat"], "ru": ["Шаббат"], "de": ["Schabbat"], "pt": ["Shabat"]}'),
+       ('{"en": ["Rosh Hashanah", "Rosh Hashana"], "iw": ["ראש השנה"], "yi": ["ראש השנה"], "es": ["Rosh Hashaná"], "fr": ["Roch Hachana"], "ru": ["Рош ха-Шана"], "de": ["Rosch ha-Schana"], "pt": ["Rosh Hashaná"]}'),
+       ('{"en": ["Yom Kippur"], "iw": ["יום כיפור"], "yi": ["יום כיפור"], "es": ["Yom Kipur"], "fr": ["Yom Kippour"], "ru": ["Йом Кипур"], "de": ["Jom Kippur"], "pt": ["Yom Kipur"]}'),
+       ('{"en": ["Sukkot", "Sukkos"], "iw": ["סוכות"], "yi": ["סוכות"], "es": ["Sucot"], "fr": ["Souccot"], "ru": ["Суккот"], "de": ["Sukkot"], "pt": ["Sucot"]}'),
+       ('{"en": ["Simchat Torah", "Simchas Torah"], "iw": ["שמחת תורה"], "yi": ["שמחת תורה"], "es": ["Simjat Torá"], "fr": ["Sim’hat Torah"], "ru": ["Симхат Тора"], "de": ["Simchat Tora"], "pt": ["Simchat Torá"]}'),
+       ('{"en": ["Hanukkah", "Chanukah", "Hanukah", "Chanuka"], "iw": ["חנוכה"], "yi": ["חנוכה"], "es": ["Janucá"], "fr": ["Hanoucca"], "ru": ["Ханука"], "de": ["Chanukka"], "pt": ["Chanucá"]}'),
+       ('{"en": ["Tu BiShvat", "Tu B''Shvat"], "iw": ["ט״ו בשבט", "טו בשבט"], "yi": ["חמשה עשר בשבט"], "es": ["Tu Bishvat"], "fr": ["Tou Bichvat"], "ru": ["Ту би-Шват"], "de": ["Tu Bischwat"], "pt": ["Tu Bishvat"]}'),
+       ('{"en": ["Purim"], "iw": ["פורים"], "yi": ["פורים"], "es": ["Purim"], "fr": ["Pourim"], "ru": ["Пурим"], "de": ["Purim"], "pt": ["Purim"]}'),
+       ('{"en": ["Passover", "Pesach"], "iw": ["פסח"], "yi": ["פסח"], "es": ["Pésaj"], "fr": ["Pessa’h"], "ru": ["Песах"], "de": ["Pessach"], "pt": ["Pessach"]}'),
+       ('{"en": ["Lag BaOmer", "Lag B''Omer"], "iw": ["ל״ג בעומר", "לג בעומר"], "yi": ["ל״ג בעומר"], "es": ["Lag BaÓmer"], "fr": ["Lag Baomer"], "ru": ["Лаг ба-Омер"], "de": ["Lag baOmer"], "pt": ["Lag BaÔmer"]}'),
+       ('{"en": ["Shavuot", "Shavuos"], "iw": ["שבועות"], "yi": ["שבועות"], "es": ["Shavuot"], "fr": ["Chavouot"], "ru": ["Шавуот"], "de": ["Schawuot"], "pt": ["Shavuot"]}'),
+       ('{"en": ["Tisha B''Av", "Tisha BeAv"], "iw": ["תשעה באב"], "yi": ["תשעה באב"], "es": ["Tishá BeAv"], "fr": ["Tisha Beav"], "ru": ["Тиша бе-Ав"], "de": ["Tischa beAw"], "pt": ["Tishá BeAv"]}'),
+       ('{"en": ["Yom Ha''atzmaut"], "iw": ["יום העצמאות"], "es": ["Yom Haatzmaut"], "fr": ["Yom Haatsmaout"], "ru": ["Йом ха-Ацмаут"], "de": ["Jom haAtzma’ut"], "pt": ["Yom Haatzmaut"]}'),
+       ('{"en": ["Torah"], "iw": ["תורה"], "yi": ["תורה"], "es": ["Torá"], "fr": ["Torah"], "ru": ["Тора"], "de": ["Tora"], "pt": ["Torá"]}'),
+       ('{"en": ["Parashah", "Parsha", "Parashat"], "iw": ["פרשה", "פרשת"], "yi": ["סדרה"], "es": ["Parashá"], "fr": ["Paracha"], "ru": ["Недельная глава"], "de": ["Parascha"], "pt": ["Parashá"]}'),
+       ('{"en": ["Havdalah"], "iw": ["הבדלה"], "yi": ["הבדלה"], "es": ["Havdalá"], "fr": ["Havdala"], "ru": ["Авдала"], "de": ["Hawdala"], "pt": ["Havdalá"]}'),
+       ('{"en": ["Kiddush"], "iw": ["קידוש"], "yi": ["קידוש"], "es": ["Kidush"], "fr": ["Kiddouch"], "ru": ["Кидуш"], "de": ["Kiddusch"], "pt": ["Kidush"]}'),
+       ('{"en": ["Seder"], "iw": ["סדר"], "yi": ["סדר"], "es": ["Séder"], "fr": ["Seder"], "ru": ["Седер"], "de": ["Seder"], "pt": ["Seder"]}'),
+       ('{"en": ["Megillah"], "iw": ["מגילה"], "yi": ["מגילה"], "es": ["Meguilá"], "fr": ["Méguila"], "ru": ["Мегила"], "de": ["Megilla"], "pt": ["Meguilá"]}'),
+       ('{"en": ["Aleph-Bet", "Alef-Bet", "Aleph Bet"], "iw": ["אלף-בית", "אלף בית"], "yi": ["אלף-בית"], "es": ["Alef-Bet"], "fr": ["Aleph-Beth"], "ru": ["Алеф-бет"], "de": ["Alef-Bet"], "pt": ["Alef-Bet"]}');
+
+-- translation status of each field, e.g. `{"name": "success", "description": "partial"}`.
+-- fields which haven't been translated (yet) are missing.
+alter table image_metadata
+    add column translation_status jsonb not null default '{}';
+
+alter table jig
+    add column translation_status jsonb not null default '{}';
+
+alter table playlist
+    add column translation_status jsonb not null default '{}';
+
+alter table resource
+    add column translation_status jsonb not null default '{}';
+
+alter table course
+    add column translation_status jsonb not null default '{}';
+
+create function translation_status_name(status smallint) returns text
+    language sql
+    immutable
+as
+$$
+select case status when 0 then 'undefined' when 1 then 'noTranslation' when 2 then 'success' end
+$$;
+
+create function translation_status_from_columns(name_status smallint, description_status smallint) returns jsonb
+    language sql
+    immutable
+as
+$$
+select jsonb_strip_nulls(jsonb_build_object('name', translation_status_name(name_status),
+                                            'description', translation_status_name(description_status)))
+$$;
+
+update image_metadata
+set translation_status = translation_status_from_columns(name_translate_status, description_translate_status);
+
+update jig
+set translation_status = translation_status_from_columns(name_translate_status, description_translate_status);
+
+update playlist
+set translation_status = translation_status_from_columns(name_translate_status, description_translate_status);
+
+update resource
+set translation_status = translation_status_from_columns(name_translate_status, description_translate_status);
+
+drop function translation_status_from_columns(smallint, smallint);
+drop function translation_status_name(smallint);
+
+drop trigger image_translate_des_status on image_metadata;
+drop trigger image_translate_name_status on image_metadata;
+drop trigger jig_translate_des_status on jig_data;
+drop trigger jig_translate_name_status on jig_data;
+drop trigger playlist_translate_des_status on playlist_data;
+drop trigger playlist_translate_name_status on playlist_data;
+drop trigger resource_translate_des_status on resource_data;
+drop trigger resource_translate_name_status on resource_data;
+
+drop function image_translate_des_status();
+drop function image_translate_name_status();
+drop function jig_translate_des_status();
+drop function jig_translate_name_status();
+drop function playlist_translate_des_status();
+drop function playlist_translate_name_status();
+drop function resource_translate_des_status();
+drop function resource_translate_name_status();
+
+alter table image_metadata
+    drop column name_translate_status,
+    drop column description_translate_status;
+
+alter table jig
+    drop column name_translate_status,
+    drop column description_translate_status;
+
+alter table playlist
+    drop column name_translate_status,
+    drop column description_translate_status;
+
+alter table resource
+    drop column name_translate_status,
+    drop column description_translate_status;
+
+--
+-- Reset the status of a field when it changes, so that it gets translated again
+--
+
+-- arguments: the field's key in `translation_status`
+create function reset_image_translation_status() returns trigger
+    language plpgsql
+as
+$$
+begin
+    new.translation_status = new.translation_status - TG_ARGV[0];
+    return new;
+end;
+$$;
+
+create trigger image_reset_name_translation_status
+    before update of name
+    on image_metadata
+    for each row
+execute procedure reset_image_translation_status('name');
+
+create trigger image_reset_description_translation_status
+    before update of description
+    on image_metadata
+    for each row
+execute procedure reset_image_translation_status('description');
+
+-- arguments: the asset table, and the field's key in its `translation_status`
+create function reset_asset_translation_status() returns trigger
+    language plpgsql
+as
+$$
+begin
+    execute format('update %I set translation_status = translation_status - %L where live_id = $1',
+                   TG_ARGV[0], TG_ARGV[1]) using new.id;
+    return new;
+end;
+$$;
+
+create trigger jig_reset_name_translation_status
+    after update of display_name
+    on jig_data
+    for each row
+execute procedure reset_asset_translation_status('jig', 'name');
+
+create trigger jig_reset_description_translation_status
+    after update of description
+    on jig_data
+    for each row
+execute procedure reset_asset_translation_status('jig', 'description');
+
+create trigger playlist_reset_name_translation_status
+    after update of display_name
+    on playlist_data
+    for each row
+execute procedure reset_asset_translation_status('playlist', 'name');
+
+create trigger playlist_reset_description_translation_status
+    after update of description
+    on playlist_data
+    for each row
+execute procedure reset_asset_translation_status('playlist', 'description');
+
+create trigger resource_reset_name_translation_status
+    after update of display_name
+    on resource_data
+    for each row
+execute procedure reset_asset_translation_status('resource', 'name');
+
+create trigger resource_reset_description_translation_status
+    after update of description
+    on resource_data
+    for each row
+execute procedure reset_asset_translation_status('resource', 'description');
+
+create trigger course_reset_name_translation_status
+    after update of display_name
+    on course_data
+    for each row
+execute procedure reset_asset_translation_status('course', 'name');
+
+create trigger course_reset_description_translation_status
+    after update of description
+    on course_data
+    for each row
+execute procedure reset_asset_translation_status('course', 'description');
@@ -0,0 +1,25 @@
+-- languages which ui strings are translated into.
+create table locale_language
+(
+    code         text primary key,
+    display_name text        not null,
+    created_at   timestamptz not null default now()
+);
+
+insert into locale_language (code, display_name)
+values ('en', 'English'),
+       ('he', 'Hebrew');
+
+-- `key` identifies an entry in its bundle's Fluent file, so it has to be a Fluent identifier.
+-- `translations` maps a language's code to the entry's text in that language.
+alter table locale_entry
+    add column key          text check (key ~ '^[a-zA-Z][a-zA-Z0-9_-]*$'),
+    add column translations jsonb not null default '{}' check (jsonb_typeof(translations) = 'object'),
+    add constraint locale_entry_bundle_key unique (bundle_id, key);
+
+update locale_entry
+set translations = jsonb_strip_nulls(jsonb_build_object('en', english, 'he', hebrew));
+
+alter table locale_entry
+    drop column english,
+    drop column hebrew;
@@ -0,0 +1,13 @@
+-- results of web image searches, merged across every provider, so that repeated searches don't go
+-- out to the providers again until they expire.
+create table web_image_search_cache
+(
+    query      text        not null,
+    -- `ImageType`, or -1 when searching every type.
+    image_type smallint    not null,
+    results    jsonb       not null check (jsonb_typeof(results) = 'array'),
+    created_at timestamptz not null default now(),
+    primary key (query, image_type)
+);
+
+create index web_image_search_cache_created_at on web_image_search_cache (created_at);
@@ -0,0 +1,6 @@
+-- who first added each piece of web media, so that the library can be browsed by uploader.
+alter table web_media_library
+    add column created_by uuid references "user" (id) on delete set null;
+
+create index web_media_library_created_at on web_media_library (created_at);
+create index web_media_library_created_by on web_media_library (created_by);
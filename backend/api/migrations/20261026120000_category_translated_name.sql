@@ -0,0 +1,15 @@
+-- category names per language, keyed by language code.
+alter table category
+    add column translated_name jsonb not null default '{}';
+
+-- a jig can only be in a category once, as is already the case for the other assets.
+delete
+from jig_data_category
+where ctid in (select ctid
+               from (select ctid,
+                            row_number() over (partition by jig_data_id, category_id) as n
+                     from jig_data_category) as numbered
+               where n > 1);
+
+alter table jig_data_category
+    add unique (jig_data_id, category_id);
@@ -0,0 +1,24 @@
+-- bulk edits of the image library, with what each image looked like before so they can be undone.
+create table image_bulk_update
+(
+    id         uuid primary key     default uuid_generate_v1mc(),
+    created_by uuid references "user" (id) on delete set null,
+    changes    jsonb       not null,
+    created_at timestamptz not null default now(),
+    undone_at  timestamptz
+);
+
+create table image_bulk_update_image
+(
+    bulk_update_id uuid    not null references image_bulk_update (id) on delete cascade,
+    image_id       uuid    not null references image_metadata (id) on delete cascade,
+    styles         uuid[]  not null,
+    tags           int2[]  not null,
+    age_ranges     uuid[]  not null,
+    affiliations   uuid[]  not null,
+    is_premium     boolean not null,
+    publish_at     timestamptz,
+    primary key (bulk_update_id, image_id)
+);
+
+create index image_bulk_update_created_at_idx on image_bulk_update (created_at desc);
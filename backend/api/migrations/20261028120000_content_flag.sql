@@ -0,0 +1,31 @@
+-- items flagged by the content safety checks, waiting for an admin to review them.
+-- kind: 0 = user image, 1 = user pdf, 2 = jig, 3 = circle
+-- status: 0 = pending, 1 = approved, 2 = rejected
+create table content_flag
+(
+    id          uuid primary key     default uuid_generate_v1mc(),
+    kind        int2        not null,
+    item_id     uuid        not null,
+    owner_id    uuid references "user" (id) on delete set null,
+    reasons     jsonb       not null,
+    status      int2        not null default 0,
+    reviewed_by uuid references "user" (id) on delete set null,
+    reviewed_at timestamptz,
+    created_at  timestamptz not null default now(),
+    updated_at  timestamptz
+);
+
+-- an item has at most one flag waiting for review, later checks add to its reasons.
+create unique index content_flag_pending_idx on content_flag (kind, item_id) where status = 0;
+
+create index content_flag_created_at_idx on content_flag (created_at desc);
+
+-- flagged items are held back until they're approved.
+alter table user_image_library
+    add column quarantined_at timestamptz;
+
+alter table user_pdf_library
+    add column quarantined_at timestamptz;
+
+alter table circle
+    add column quarantined_at timestamptz;
@@ -0,0 +1,9 @@
+-- child privacy mode for school accounts: players of their teachers' codes are only stored under a
+-- pseudonym, and code sessions are purged after `session_retention_days`. A null retention keeps
+-- sessions indefinitely, unless the school is in child privacy mode.
+alter table school
+    add column child_privacy_mode     boolean not null default false,
+    add column session_retention_days integer check (session_retention_days > 0);
+
+-- for purging old sessions
+create index jig_code_session_started_at_idx on jig_code_session (started_at);
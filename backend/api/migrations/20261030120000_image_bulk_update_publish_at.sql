@@ -0,0 +1,4 @@
+-- when a bulk update published its images, resolved from the requested `Publish`, so that undoing
+-- it only reverts images which haven't been republished since.
+alter table image_bulk_update
+    add column publish_at timestamptz;
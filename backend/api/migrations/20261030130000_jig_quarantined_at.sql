@@ -0,0 +1,11 @@
+-- flagged jigs are quarantined on their own, rather than through `jig_admin_data.blocked`, so that
+-- reviewing a flag doesn't change whether an admin blocked the jig.
+alter table jig
+    add column quarantined_at timestamptz;
+
+update jig
+set quarantined_at = content_flag.created_at
+from content_flag
+where content_flag.kind = 2
+  and content_flag.status = 0
+  and content_flag.item_id = jig.id;
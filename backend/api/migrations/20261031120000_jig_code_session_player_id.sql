@@ -0,0 +1,6 @@
+-- identifies a player who isn't logged in as a student by a random id kept on their device, so
+-- that players sharing an IP address, e.g. a classroom behind one NAT, get their own attempts
+alter table jig_code_session
+    add column player_id uuid;
+
+create index jig_code_session_code_player_id_idx on jig_code_session (code, player_id);
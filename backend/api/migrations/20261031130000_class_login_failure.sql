@@ -0,0 +1,13 @@
+-- failed class logins and roster lookups, for throttling guesses of login codes and picture
+-- passwords. Rows are only kept for as long as they count towards a limit.
+create table class_login_failure
+(
+    id         bigint generated always as identity primary key,
+    ip_address text,
+    student_id uuid references class_student (id) on delete cascade,
+    failed_at  timestamptz not null default now()
+);
+
+create index class_login_failure_ip_address_idx on class_login_failure (ip_address, failed_at);
+create index class_login_failure_student_id_idx on class_login_failure (student_id, failed_at);
+create index class_login_failure_failed_at_idx on class_login_failure (failed_at);
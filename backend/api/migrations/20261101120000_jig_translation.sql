@@ -0,0 +1,17 @@
+-- translated copies of jigs, which are made in the background.
+create table jig_translation
+(
+    id                uuid primary key     default uuid_generate_v1mc(),
+    jig_id            uuid        not null references jig (id) on delete cascade,
+    language          text        not null,
+    created_by        uuid        not null references "user" (id) on delete cascade,
+    -- 0 = pending, 1 = finished, 2 = failed
+    status            int2        not null default 0,
+    translated_jig_id uuid references jig (id) on delete set null,
+    untranslated      jsonb       not null default '[]',
+    created_at        timestamptz not null default now(),
+    started_at        timestamptz,
+    finished_at       timestamptz
+);
+
+create index jig_translation_pending_idx on jig_translation (created_at) where status = 0;
@@ -0,0 +1,25 @@
+-- when the WebP and AVIF renditions of an image were stored, null for images which were uploaded
+-- before renditions were stored until they're backfilled.
+alter table image_upload
+    add column renditions_at timestamptz;
+
+alter table user_image_upload
+    add column renditions_at timestamptz;
+
+alter table web_media_upload
+    add column renditions_at timestamptz;
+
+-- svg images have always been stored with renditions
+update image_upload
+set renditions_at = processed_at
+where kind = 2;
+
+update user_image_upload
+set renditions_at = processed_at
+where kind = 2;
+
+update web_media_upload
+set renditions_at = processed_at
+from web_media_library
+where web_media_library.id = web_media_upload.media_id
+  and web_media_library.kind = 5;
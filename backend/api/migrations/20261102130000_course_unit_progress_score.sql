@@ -0,0 +1,4 @@
+-- course units are images, audio, links, pdfs and videos, none of which are scored.
+alter table course_unit_progress
+    drop column score_earned,
+    drop column score_available;
@@ -0,0 +1,20 @@
+-- whether size_bytes is the stored size of the upload. uploads stored before storage quotas were
+-- counted as 0 bytes, their size is summed from s3 by the media size backfill.
+alter table user_image_library
+    add column size_counted boolean not null default false;
+
+alter table user_audio_library
+    add column size_counted boolean not null default false;
+
+alter table user_pdf_library
+    add column size_counted boolean not null default false;
+
+-- uploads from now on are counted as they're stored
+alter table user_image_library
+    alter column size_counted set default true;
+
+alter table user_audio_library
+    alter column size_counted set default true;
+
+alter table user_pdf_library
+    alter column size_counted set default true;
@@ -15,6 +15,7 @@ select
     distinct id,
     category.parent_id,
     name,
+    translated_name as "translated_name!: Json<HashMap<String, String>>",
     category.index,
     created_at,
     updated_at,
@@ -13,6 +13,7 @@ select distinct id as "id!",
        path.index::int2 as "index!",
        path.parent_id,
        name,
+       translated_name as "translated_name!: Json<HashMap<String, String>>",
        created_at,
        updated_at,
        user_scopes
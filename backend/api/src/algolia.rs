@@ -41,6 +41,9 @@ const HAS_AUTHOR_TAG: &'static str = "hasAuthor";
 const HAS_RATING_TAG: &'static str = "isRated";
 const HAS_NO_RATING_TAG: &'static str = "isNotRated";
 
+/// How many characters of the text of a resource's PDFs get indexed, to keep records small.
+const PDF_SEARCH_TEXT_LEN: i32 = 5000;
+
 #[derive(Serialize)]
 struct BatchJig<'a> {
     name: &'a str,
@@ -97,6 +100,7 @@ struct BatchResource<'a> {
     translated_name: &'a Vec<String>,
     translated_description: &'a Vec<String>,
     blocked: &'a bool,
+    pdf_text: &'a str,
 }
 
 #[derive(Serialize)]
@@ -619,7 +623,7 @@ select jig.id,
             where jpc.jig_id = jig.id
         )                                                                                                           as "plays!",
         published_at                                                                                                as "published_at",
-        blocked                                                                                                     as "blocked!"
+        blocked or jig.quarantined_at is not null                                                                   as "blocked!"
 from jig
          inner join jig_data on live_id = jig_data.id
          inner join jig_admin_data "jad" on jad.jig_id = jig.id
@@ -790,7 +794,12 @@ select resource.id,
         likes                                                                                                       as "likes!",
         views                                                                                                       as "views!",
         published_at                                                                                                as "published_at",
-        blocked                                                                                                     as "blocked!"
+        blocked                                                                                                     as "blocked!",
+        (select left(string_agg(content_text, ' '), $1)
+         from user_pdf_library
+         where user_pdf_library.id::text in (select resource_content ->> 'pdfId'
+                                             from resource_data_resource
+                                             where resource_data_id = resource_data.id))                           as "pdf_text"
 from resource
          inner join resource_data on live_id = resource_data.id
          inner join resource_admin_data "rad" on rad.resource_id = resource.id
@@ -798,7 +807,8 @@ where ((last_synced_at is null and published_at is not null)
    or (updated_at is not null and last_synced_at < updated_at)
     or (published_at < now() is true and last_synced_at < published_at))
 limit 100 for no key update skip locked;
-     "#
+     "#,
+            PDF_SEARCH_TEXT_LEN
         )
         .fetch(&mut txn)
         .map_ok(|row| {
@@ -853,7 +863,8 @@ limit 100 for no key update skip locked;
                 published_at: row.published_at,
                 translated_name: &translation_name,
                 translated_description: &translation_description,
-                blocked: &row.blocked
+                blocked: &row.blocked,
+                pdf_text: row.pdf_text.as_deref().unwrap_or_default(),
             })
             .expect("failed to serialize BatchResource to json")
             {
@@ -1403,6 +1414,7 @@ limit 100 for no key update skip locked;
     from circle
 where (last_synced_at is null or
        (updated_at is not null and last_synced_at < updated_at))
+  and quarantined_at is null
 limit 100 for no key update skip locked;
      "#
         )
@@ -261,6 +261,7 @@ pub(crate) async fn resource_index(
                 .single(Attribute("language".to_owned()))
                 .single(Attribute("other_keywords".to_owned()))
                 .single(Attribute("translated_name".to_owned()))
+                .single(Attribute("pdf_text".to_owned()))
                 .finish(),
         ),
         attributes_for_faceting: Some(vec![
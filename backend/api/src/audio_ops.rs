@@ -0,0 +1,283 @@
+//! Audio processing, done by running `ffmpeg` (which needs `libmp3lame` and `libopus`).
+
+use std::{path::Path, process::Stdio};
+
+use anyhow::Context;
+use ji_core::config::AUDIO_MAX_DURATION_SECS;
+use tokio::{process::Command, sync::Semaphore};
+
+use crate::{
+    error,
+    media_type::{MediaError, MediaType},
+    temp_dir::TempDir,
+};
+
+/// Most uploads processed at once. Each runs `ffmpeg`, which uses a core and some memory, so
+/// further uploads wait their turn.
+const MAX_CONCURRENT_PROCESSING: usize = 4;
+
+static PROCESSING_PERMITS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_PROCESSING);
+
+/// Sample rate of the PCM decoded for analysis, plenty to find silence and draw a waveform.
+const ANALYSIS_SAMPLE_RATE: u32 = 8000;
+
+/// Samples quieter than this (about -50 dBFS) count as silence when trimming.
+const SILENCE_THRESHOLD: u16 = 104;
+
+/// Silence kept before the first and after the last sound, so the start of speech isn't clipped.
+const SILENCE_PADDING_MS: u32 = 100;
+
+/// Number of waveform peaks for each second of audio.
+pub const WAVEFORM_PEAKS_PER_SECOND: u32 = 20;
+
+/// EBU R128 normalization: integrated loudness, true peak and loudness range targets.
+const LOUDNORM_FILTER: &str = "loudnorm=I=-16:TP=-1.5:LRA=11";
+
+/// The files and metadata stored for uploaded audio.
+pub struct ProcessedAudio {
+    pub mp3: Vec<u8>,
+    pub opus: Vec<u8>,
+    pub duration_ms: u32,
+    pub peaks: Vec<u8>,
+}
+
+/// Decodes uploaded audio in any of [`MediaType::AUDIO`], trims leading and trailing silence,
+/// normalizes its loudness and encodes it as MP3 and Opus.
+///
+/// The encoded files carry none of the upload's metadata (tags, cover art, ...).
+pub async fn process_audio(data: Vec<u8>) -> Result<ProcessedAudio, error::Upload> {
+    MediaType::expect(&data, MediaType::AUDIO)?;
+
+    let _permit = PROCESSING_PERMITS
+        .acquire()
+        .await
+        .context("audio processing semaphore closed")?;
+
+    // ffmpeg needs to seek around some containers (M4A), so it reads from a file rather than a pipe.
+    let dir = TempDir::new("ji-audio").await?;
+    let input = dir.path.join("input");
+    tokio::fs::write(&input, data).await?;
+
+    let samples = decode_for_analysis(&input).await?;
+    let analysis = analyze(&samples)?;
+
+    let mp3 = dir.path.join("audio.mp3");
+    let opus = dir.path.join("audio.opus");
+    encode(&input, &analysis, &mp3, &opus).await?;
+
+    Ok(ProcessedAudio {
+        mp3: tokio::fs::read(&mp3).await?,
+        opus: tokio::fs::read(&opus).await?,
+        duration_ms: analysis.duration_ms(),
+        peaks: analysis.peaks,
+    })
+}
+
+fn ffmpeg() -> Command {
+    let mut command = Command::new("ffmpeg");
+    command
+        .args(["-hide_banner", "-loglevel", "error", "-nostdin"])
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    command
+}
+
+/// Decodes the first audio stream to mono 16 bit PCM at [`ANALYSIS_SAMPLE_RATE`].
+async fn decode_for_analysis(input: &Path) -> Result<Vec<i16>, error::Upload> {
+    // one second past the limit, to tell audio that's too long from audio right at the limit
+    let max_secs = (AUDIO_MAX_DURATION_SECS + 1).to_string();
+    let sample_rate = ANALYSIS_SAMPLE_RATE.to_string();
+
+    let output = ffmpeg()
+        .args(["-t", max_secs.as_str(), "-i"])
+        .arg(input)
+        .args(["-map", "0:a:0", "-ac", "1", "-ar", sample_rate.as_str()])
+        .args(["-f", "s16le", "pipe:1"])
+        .output()
+        .await
+        .context("failed to run ffmpeg")?;
+
+    if !output.status.success() {
+        log::debug!(
+            "ffmpeg couldn't decode audio: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return Err(MediaError::Malformed.into());
+    }
+
+    let samples: Vec<i16> = output
+        .stdout
+        .chunks_exact(2)
+        .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+        .collect();
+
+    if samples.len() > (AUDIO_MAX_DURATION_SECS * ANALYSIS_SAMPLE_RATE) as usize {
+        return Err(MediaError::AudioTooLong.into());
+    }
+
+    Ok(samples)
+}
+
+/// Normalizes the non-silent part of `input`, and encodes it to both `mp3` and `opus`.
+async fn encode(
+    input: &Path,
+    analysis: &Analysis,
+    mp3: &Path,
+    opus: &Path,
+) -> Result<(), error::Upload> {
+    let start = timestamp(analysis.start);
+    let length = timestamp(analysis.end - analysis.start);
+    let filter = format!("[0:a:0]{},asplit=2[mp3][opus]", LOUDNORM_FILTER);
+
+    let output = ffmpeg()
+        .args(["-ss", start.as_str(), "-t", length.as_str(), "-i"])
+        .arg(input)
+        .args(["-filter_complex", filter.as_str()])
+        .args([
+            "-map",
+            "[mp3]",
+            "-map_metadata",
+            "-1",
+            "-id3v2_version",
+            "0",
+        ])
+        .args([
+            "-ar",
+            "44100",
+            "-c:a",
+            "libmp3lame",
+            "-q:a",
+            "4",
+            "-f",
+            "mp3",
+        ])
+        .arg(mp3)
+        .args(["-map", "[opus]", "-map_metadata", "-1"])
+        .args([
+            "-ar", "48000", "-c:a", "libopus", "-b:a", "48k", "-f", "ogg",
+        ])
+        .arg(opus)
+        .output()
+        .await
+        .context("failed to run ffmpeg")?;
+
+    // the input already decoded fine, so this isn't the upload's fault
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "ffmpeg failed to encode audio: {}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Formats a position in the analysis PCM as seconds, for ffmpeg.
+fn timestamp(samples: usize) -> String {
+    let ms = samples as u64 * 1000 / u64::from(ANALYSIS_SAMPLE_RATE);
+    format!("{}.{:03}", ms / 1000, ms % 1000)
+}
+
+/// The non-silent range of some audio, in samples, and its waveform.
+#[derive(Debug)]
+struct Analysis {
+    start: usize,
+    end: usize,
+    peaks: Vec<u8>,
+}
+
+impl Analysis {
+    fn duration_ms(&self) -> u32 {
+        ((self.end - self.start) as u64 * 1000 / u64::from(ANALYSIS_SAMPLE_RATE)) as u32
+    }
+}
+
+/// Finds the non-silent range of `samples`.
+///
+/// Audio with nothing above [`SILENCE_THRESHOLD`] could still be a very quiet recording, so it's
+/// kept whole rather than rejected.
+fn analyze(samples: &[i16]) -> Result<Analysis, MediaError> {
+    if samples.is_empty() {
+        return Err(MediaError::Malformed);
+    }
+
+    let is_sound = |sample: &i16| sample.unsigned_abs() >= SILENCE_THRESHOLD;
+
+    let (start, end) = match (
+        samples.iter().position(is_sound),
+        samples.iter().rposition(is_sound),
+    ) {
+        (Some(first), Some(last)) => {
+            let padding = (ANALYSIS_SAMPLE_RATE * SILENCE_PADDING_MS / 1000) as usize;
+            (
+                first.saturating_sub(padding),
+                (last + 1 + padding).min(samples.len()),
+            )
+        }
+        _ => {
+            log::warn!("uploaded audio is quieter than the silence threshold, keeping all of it");
+            (0, samples.len())
+        }
+    };
+
+    let samples_per_peak = (ANALYSIS_SAMPLE_RATE / WAVEFORM_PEAKS_PER_SECOND) as usize;
+    let maxima: Vec<u16> = samples[start..end]
+        .chunks(samples_per_peak)
+        .map(|chunk| chunk.iter().map(|it| it.unsigned_abs()).max().unwrap_or(0))
+        .collect();
+
+    let loudest = maxima.iter().copied().max().unwrap_or(0).max(1);
+    let peaks = maxima
+        .into_iter()
+        .map(|peak| (u32::from(peak) * 255 / u32::from(loudest)) as u8)
+        .collect();
+
+    Ok(Analysis { start, end, peaks })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_silence_with_padding() {
+        // 1s of silence, 2s of sound, 1s of silence
+        let rate = ANALYSIS_SAMPLE_RATE as usize;
+        let mut samples = vec![0_i16; rate * 4];
+        samples[rate..rate * 3].fill(8000);
+
+        let analysis = analyze(&samples).unwrap();
+
+        assert_eq!(rate - 800, analysis.start);
+        assert_eq!(rate * 3 + 800, analysis.end);
+        assert_eq!(2200, analysis.duration_ms());
+        assert_eq!("0.900", timestamp(analysis.start));
+    }
+
+    #[test]
+    fn peaks_are_scaled_to_the_loudest() {
+        let rate = ANALYSIS_SAMPLE_RATE as usize;
+        let mut samples = vec![4000_i16; rate];
+        samples[rate / 2..].fill(-16000);
+
+        let analysis = analyze(&samples).unwrap();
+
+        assert_eq!(WAVEFORM_PEAKS_PER_SECOND as usize, analysis.peaks.len());
+        assert_eq!(63, analysis.peaks[0]);
+        assert_eq!(255, *analysis.peaks.last().unwrap());
+    }
+
+    #[test]
+    fn keeps_quiet_audio_whole() {
+        let analysis = analyze(&[0, 3, -50, 100]).unwrap();
+
+        assert_eq!(0, analysis.start);
+        assert_eq!(4, analysis.end);
+    }
+
+    #[test]
+    fn rejects_empty_audio() {
+        assert!(matches!(analyze(&[]), Err(MediaError::Malformed)));
+    }
+}
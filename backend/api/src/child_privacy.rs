@@ -0,0 +1,109 @@
+//! Pseudonymous identities for players of JIG codes created by teachers of a school in child
+//! privacy mode.
+//!
+//! Their IP addresses and names aren't stored. Players who aren't logged in as a student are told
+//! apart by the random id their device keeps, and reported under a pseudonym derived from it. The
+//! pseudonym stays the same for a code so that a player's attempts are grouped in the code's
+//! report, but can't be linked to the player's id or across codes without the key.
+//!
+//! The class login screen, which anyone with the class code can open, only shows as much of the
+//! students' names as they need to recognise themselves.
+
+use hmac::{Hmac, Mac, NewMac};
+use sha2::Sha256;
+use shared::domain::jig::codes::JigCode;
+use uuid::Uuid;
+
+/// Number of hex characters of the player's tag shown in their pseudonym.
+const PSEUDONYM_TAG_LENGTH: usize = 6;
+
+/// Name a player is reported under, derived from the code they're playing and their id.
+pub(crate) fn pseudonym(key: &[u8], code: JigCode, player_id: Uuid) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+
+    mac.update(&code.0.to_be_bytes());
+    mac.update(player_id.as_bytes());
+
+    let tag: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect();
+
+    format!("Player {}", &tag[..PSEUDONYM_TAG_LENGTH])
+}
+
+/// Shortens the names of a class's students for its login screen to their first name and the
+/// initial of their last, e.g. `Noa L.`. Students whose short names would clash keep their full
+/// name, so that they can still tell themselves apart.
+pub(crate) fn roster_names(names: &[String]) -> Vec<String> {
+    let short_names: Vec<String> = names.iter().map(|name| short_name(name)).collect();
+
+    short_names
+        .iter()
+        .zip(names)
+        .map(|(short_name, name)| {
+            let clashes = short_names.iter().filter(|it| *it == short_name).count() > 1;
+
+            if clashes {
+                name.trim().to_owned()
+            } else {
+                short_name.clone()
+            }
+        })
+        .collect()
+}
+
+fn short_name(name: &str) -> String {
+    let mut words = name.split_whitespace();
+    let first = words.next().unwrap_or_default();
+
+    match words.last().and_then(|last| last.chars().next()) {
+        Some(initial) => format!("{} {}.", first, initial),
+        None => first.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = &[7; 32];
+
+    #[test]
+    fn pseudonym_is_stable_per_code() {
+        let player_id = Uuid::from_u128(1);
+        let name = pseudonym(KEY, JigCode(1234), player_id);
+
+        assert_eq!(name, pseudonym(KEY, JigCode(1234), player_id));
+        assert_ne!(name, pseudonym(KEY, JigCode(1235), player_id));
+        assert_ne!(name, pseudonym(KEY, JigCode(1234), Uuid::from_u128(2)));
+        assert_ne!(name, pseudonym(&[8; 32], JigCode(1234), player_id));
+    }
+
+    #[test]
+    fn pseudonym_format() {
+        let name = pseudonym(KEY, JigCode(1234), Uuid::from_u128(1));
+
+        assert_eq!(name.len(), "Player ".len() + PSEUDONYM_TAG_LENGTH);
+        assert!(name["Player ".len()..]
+            .chars()
+            .all(|c| c.is_ascii_hexdigit() && !c.is_ascii_lowercase()));
+    }
+
+    #[test]
+    fn roster_names_are_shortened() {
+        let names = |names: &[&str]| names.iter().map(|it| it.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            roster_names(&names(&["Noa Levi", "Ari", "Dana Bat Cohen", "  "])),
+            names(&["Noa L.", "Ari", "Dana C.", ""]),
+        );
+
+        assert_eq!(
+            roster_names(&names(&["Noa Levi", "Noa Lavie", "Noa Cohen"])),
+            names(&["Noa Levi", "Noa Lavie", "Noa C."]),
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Automated checks on content which users publish, before anyone else sees it.
+//!
+//! Text is checked for profanity and personal information by [`text`], and images are sent to an
+//! [`ImageClassifier`]. Items with any [`FlagReason`] are quarantined and sent to the admin review
+//! queue by [`crate::db::moderation::flag`], instead of waiting to be reported.
+//!
+//! Quarantined items are left out of listings and search. The files of quarantined uploads are
+//! moved out of the media bucket, so that JIGs using them can't load them either, see
+//! [`crate::service::s3::Client::quarantine`].
+
+use std::sync::Arc;
+
+use shared::domain::moderation::FlagReason;
+
+pub mod image;
+pub mod text;
+
+pub use image::ImageClassifier;
+
+/// The content safety checks, shared by every handler which accepts user content.
+pub struct ContentSafety {
+    images: Arc<dyn ImageClassifier>,
+}
+
+impl ContentSafety {
+    pub fn new(images: Arc<dyn ImageClassifier>) -> Self {
+        Self { images }
+    }
+
+    /// Checks text fields, given as `(field, text)` pairs.
+    pub fn check_text(&self, fields: &[(&str, &str)]) -> Vec<FlagReason> {
+        fields
+            .iter()
+            .flat_map(|(field, text)| text::check(field, text))
+            .collect()
+    }
+
+    /// Checks an image.
+    ///
+    /// If the classifier fails the image isn't flagged, so that uploads don't depend on it being
+    /// up. Admins can still act on reports.
+    pub async fn check_image(&self, image: &[u8]) -> Vec<FlagReason> {
+        match self.images.classify(image).await {
+            Ok(reasons) => reasons,
+            Err(e) => {
+                log::warn!(
+                    "image classifier `{}` failed, not flagging: {:?}",
+                    self.images.name(),
+                    e
+                );
+                vec![]
+            }
+        }
+    }
+}
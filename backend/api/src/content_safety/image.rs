@@ -0,0 +1,61 @@
+//! Classifying images as safe or not.
+
+use async_trait::async_trait;
+use shared::domain::moderation::FlagReason;
+
+/// A service which labels unsafe images.
+#[async_trait]
+pub trait ImageClassifier: Send + Sync {
+    /// Name of the classifier, for logs.
+    fn name(&self) -> &'static str;
+
+    /// Classifies an encoded image, returning a [`FlagReason::Image`] for each unsafe label.
+    async fn classify(&self, image: &[u8]) -> anyhow::Result<Vec<FlagReason>>;
+}
+
+/// Doesn't flag any image, for when no classifier is configured.
+pub struct NoopImageClassifier;
+
+#[async_trait]
+impl ImageClassifier for NoopImageClassifier {
+    fn name(&self) -> &'static str {
+        "noop"
+    }
+
+    async fn classify(&self, _image: &[u8]) -> anyhow::Result<Vec<FlagReason>> {
+        Ok(vec![])
+    }
+}
+
+/// Flags every image with a fixed label, or none, for tests.
+#[derive(Default)]
+pub struct StubImageClassifier {
+    label: Option<String>,
+}
+
+impl StubImageClassifier {
+    /// Flags every image with `label`.
+    pub fn flagging(label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl ImageClassifier for StubImageClassifier {
+    fn name(&self) -> &'static str {
+        "stub"
+    }
+
+    async fn classify(&self, _image: &[u8]) -> anyhow::Result<Vec<FlagReason>> {
+        Ok(self
+            .label
+            .iter()
+            .map(|label| FlagReason::Image {
+                label: label.clone(),
+                confidence: 1.0,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,366 @@
+//! Checking text for profanity and personal information.
+//!
+//! Text is split into words, which are compared against the profanity list of every language,
+//! since text isn't always in the language of the site it's written on. Personal information is
+//! found by its shape, without looking anything up.
+
+use shared::domain::moderation::{FlagReason, PersonalInfoKind};
+
+/// Terms which are flagged, by language code. Codes are Google Translate's, like the ones
+/// translations use.
+///
+/// Words which are also everyday words (such as "כוס", a cup, or "זין", the letter zayin) are only
+/// listed as part of the phrases they're rude in.
+const PROFANITY: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "fuck",
+            "fucking",
+            "fucker",
+            "motherfucker",
+            "shit",
+            "bullshit",
+            "cunt",
+            "bitch",
+            "asshole",
+            "dickhead",
+            "wanker",
+            "twat",
+            "slut",
+            "whore",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "puta",
+            "puto",
+            "mierda",
+            "cabron",
+            "cabrón",
+            "coño",
+            "gilipollas",
+            "pendejo",
+            "chingada",
+            "verga",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "merde", "putain", "salope", "connard", "connasse", "enculé", "encule", "pute",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "scheiße",
+            "scheisse",
+            "fotze",
+            "arschloch",
+            "wichser",
+            "hurensohn",
+            "ficken",
+        ],
+    ),
+    (
+        "pt",
+        &["porra", "caralho", "merda", "puta", "foda", "buceta"],
+    ),
+    (
+        "it",
+        &[
+            "cazzo",
+            "stronzo",
+            "puttana",
+            "vaffanculo",
+            "merda",
+            "troia",
+        ],
+    ),
+    ("nl", &["kut", "klootzak", "godverdomme", "hoer"]),
+    (
+        "ru",
+        &["блядь", "бля", "хуй", "пизда", "ебать", "сука", "мудак"],
+    ),
+    (
+        "iw",
+        &[
+            "זונה",
+            "מניאק",
+            "שרמוטה",
+            "בן זונה",
+            "כוסאמק",
+            "כוס אמק",
+            "כוס אמא שלך",
+            "תזדיין",
+        ],
+    ),
+];
+
+/// Checks one text field, returning why it should be flagged. Each term or kind of information is
+/// reported once.
+pub fn check(field: &str, text: &str) -> Vec<FlagReason> {
+    let mut reasons = Vec::new();
+
+    for (language, term) in profanity(text) {
+        let reason = FlagReason::Profanity {
+            field: field.to_owned(),
+            language: language.to_owned(),
+            term: term.to_owned(),
+        };
+
+        if !reasons.contains(&reason) {
+            reasons.push(reason);
+        }
+    }
+
+    for info in personal_info(text) {
+        let reason = FlagReason::PersonalInfo {
+            field: field.to_owned(),
+            info,
+        };
+
+        if !reasons.contains(&reason) {
+            reasons.push(reason);
+        }
+    }
+
+    reasons
+}
+
+/// Finds the profane terms in `text`, with the language of the list they're from.
+fn profanity(text: &str) -> Vec<(&'static str, &'static str)> {
+    let text = text.to_lowercase();
+
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let folded: Vec<String> = words.iter().map(|word| fold_leetspeak(word)).collect();
+    let folded: Vec<&str> = folded.iter().map(String::as_str).collect();
+
+    let mut found = Vec::new();
+
+    for (language, terms) in PROFANITY {
+        for term in *terms {
+            let term_words: Vec<&str> = term.split(' ').collect();
+
+            let matches = |candidates: &[&str]| {
+                candidates
+                    .windows(term_words.len())
+                    .any(|window| window == term_words.as_slice())
+            };
+
+            if matches(&words) || matches(&folded) {
+                found.push((*language, *term));
+            }
+        }
+    }
+
+    found
+}
+
+/// Undoes the usual digit for letter swaps, like `sh1t`.
+fn fold_leetspeak(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '1' => 'i',
+            '3' => 'e',
+            '4' => 'a',
+            '5' => 's',
+            '7' => 't',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Finds the kinds of personal information in `text`.
+fn personal_info(text: &str) -> Vec<PersonalInfoKind> {
+    let mut found = Vec::new();
+
+    if text.split_whitespace().any(is_email) {
+        found.push(PersonalInfoKind::Email);
+    }
+
+    for run in number_runs(text) {
+        let digits: Vec<u32> = run.chars().filter_map(|c| c.to_digit(10)).collect();
+
+        let kind = if (13..=19).contains(&digits.len()) && passes_luhn(&digits) {
+            PersonalInfoKind::CardNumber
+        } else if (9..=15).contains(&digits.len()) && looks_like_phone_number(run) {
+            PersonalInfoKind::PhoneNumber
+        } else {
+            continue;
+        };
+
+        if !found.contains(&kind) {
+            found.push(kind);
+        }
+    }
+
+    found
+}
+
+fn is_email(word: &str) -> bool {
+    let word = word.trim_matches(|c: char| !c.is_alphanumeric());
+
+    let Some((local, domain)) = word.split_once('@') else {
+        return false;
+    };
+
+    !local.is_empty()
+        && domain
+            .split_once('.')
+            .map_or(false, |(name, tld)| !name.is_empty() && tld.len() >= 2)
+}
+
+/// Splits `text` into runs of digits and the characters which separate the digits of phone and
+/// card numbers.
+fn number_runs(text: &str) -> Vec<&str> {
+    let is_number_char = |c: char| c.is_ascii_digit() || " -.()+".contains(c);
+
+    let mut runs = Vec::new();
+    let mut start = None;
+
+    for (index, c) in text.char_indices() {
+        match (start, is_number_char(c)) {
+            (None, true) => start = Some(index),
+            (Some(from), false) => {
+                runs.push(text[from..index].trim());
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some(from) = start {
+        runs.push(text[from..].trim());
+    }
+
+    runs.into_iter()
+        .filter(|run| run.chars().any(|c| c.is_ascii_digit()))
+        .collect()
+}
+
+/// Whether a run of digits is written like a phone number, rather than e.g. a list of years.
+fn looks_like_phone_number(run: &str) -> bool {
+    let groups = run
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|group| !group.is_empty())
+        .count();
+
+    run.starts_with('+')
+        || run.starts_with('(')
+        || run.starts_with('0')
+        || run.contains('-')
+        || groups == 1
+}
+
+fn passes_luhn(digits: &[u32]) -> bool {
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(index, &digit)| {
+            if index % 2 == 1 {
+                let doubled = digit * 2;
+                if doubled > 9 {
+                    doubled - 9
+                } else {
+                    doubled
+                }
+            } else {
+                digit
+            }
+        })
+        .sum();
+
+    sum % 10 == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(text: &str) -> Vec<PersonalInfoKind> {
+        personal_info(text)
+    }
+
+    #[test]
+    fn clean_text() {
+        assert!(check(
+            "description",
+            "Learn the Hebrew alphabet, class of 2021-2022!"
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn profanity_in_any_language() {
+        assert_eq!(profanity("What the FUCK"), vec![("en", "fuck")]);
+        assert_eq!(profanity("quelle merde."), vec![("fr", "merde")]);
+        assert_eq!(profanity("ты сука"), vec![("ru", "сука")]);
+        assert_eq!(
+            profanity("בן זונה"),
+            vec![("iw", "זונה"), ("iw", "בן זונה")]
+        );
+    }
+
+    #[test]
+    fn everyday_hebrew_words() {
+        assert!(profanity("כוס מים ואות זין").is_empty());
+        assert_eq!(profanity("כוס אמק"), vec![("iw", "כוס אמק")]);
+    }
+
+    #[test]
+    fn profanity_is_whole_words() {
+        assert!(profanity("a classic assessment of Scunthorpe").is_empty());
+    }
+
+    #[test]
+    fn profanity_with_digits() {
+        assert_eq!(profanity("sh1t"), vec![("en", "shit")]);
+    }
+
+    #[test]
+    fn emails() {
+        assert_eq!(
+            kinds("write to me at kid@example.com."),
+            vec![PersonalInfoKind::Email]
+        );
+        assert!(kinds("@jigzi on social media").is_empty());
+    }
+
+    #[test]
+    fn phone_numbers() {
+        assert_eq!(
+            kinds("call 054-123-4567"),
+            vec![PersonalInfoKind::PhoneNumber]
+        );
+        assert_eq!(
+            kinds("+1 (555) 123 4567"),
+            vec![PersonalInfoKind::PhoneNumber]
+        );
+        assert!(kinds("years 2020 2021 2022").is_empty());
+    }
+
+    #[test]
+    fn card_numbers() {
+        assert_eq!(
+            kinds("4111 1111 1111 1111"),
+            vec![PersonalInfoKind::CardNumber]
+        );
+        assert!(kinds("4111 1111 1111 1112").is_empty());
+    }
+
+    #[test]
+    fn reasons_are_reported_once() {
+        let reasons = check("name", "shit shit a@b.co c@d.co");
+
+        assert_eq!(reasons.len(), 2);
+    }
+}
@@ -4,12 +4,16 @@ pub(crate) mod audio;
 pub(crate) mod billing;
 pub(crate) mod category;
 pub(crate) mod circle;
+pub(crate) mod class;
 pub(crate) mod course;
+pub(crate) mod email;
 pub(crate) mod image;
 pub(crate) mod jig;
 pub(crate) mod locale;
 pub(crate) mod media;
 pub(crate) mod meta;
+pub(crate) mod moderation;
+pub(crate) mod notification;
 pub(crate) mod pdf;
 pub(crate) mod playlist;
 pub(crate) mod resource;
@@ -186,6 +190,9 @@ fn generate_metadata_insert(base_table: &str, meta_kind: &str, binds: usize) ->
         write!(s, ", ($1, ${})", i + 2).expect("write to String shouldn't fail");
     }
 
+    // repeated metadata is only stored once
+    s.push_str(" on conflict do nothing");
+
     s
 }
 
@@ -236,6 +243,9 @@ fn generate_tag_insert(base_table: &str, binds: usize) -> String {
         write!(s, ", ($1, ${})", i + 2).expect("write to String shouldn't fail");
     }
 
+    // repeated metadata is only stored once
+    s.push_str(" on conflict do nothing");
+
     s
 }
 
@@ -1,16 +1,16 @@
 use crate::db;
 use anyhow::anyhow;
-use futures::future::join_all;
 use shared::domain::admin::SearchSchoolsParams;
 use shared::domain::billing::{
-    Account, AccountId, AccountType, AccountUser, AdminSchool, CreateSchoolAccountRequest,
-    CustomerId, PaymentMethod, PlanTier, PlanType, School, SchoolId, SchoolName, SchoolNameId,
-    SchoolNameValue, SubscriptionStatus, UpdateSchoolAccountRequest, UserAccountSummary,
+    Account, AccountId, AccountLimit, AccountSeats, AccountType, AccountUser, AdminSchool,
+    CreateSchoolAccountRequest, CustomerId, PaymentMethod, PlanTier, PlanType, School, SchoolId,
+    SchoolInvite, SchoolInviteId, SchoolName, SchoolNameId, SchoolNameValue, SubscriptionStatus,
+    UpdateSchoolAccountRequest, UserAccountSummary, UserSchoolInvite,
 };
 use shared::domain::image::ImageId;
 use shared::domain::user::UserId;
 use shared::domain::{ItemCount, UpdateNullable};
-use sqlx::{Executor, PgPool, Postgres};
+use sqlx::{Executor, PgConnection, PgPool, Postgres};
 use tracing::{instrument, Instrument};
 
 #[instrument(skip(pool))]
@@ -227,7 +227,9 @@ update school
         description = case when $6 then $7 else description end,
         profile_image_id = case when $8 then $9 else profile_image_id end,
         website = case when $10 then $11 else website end,
-        organization_type = case when $12 then $13 else organization_type end
+        organization_type = case when $12 then $13 else organization_type end,
+        child_privacy_mode = coalesce($14, child_privacy_mode),
+        session_retention_days = case when $15 then $16 else session_retention_days end
 where school_id = $1
 "#,
         school_id as &SchoolId,
@@ -243,6 +245,9 @@ where school_id = $1
         update.website.into_option(),
         update.organization_type.is_change(),
         update.organization_type.into_option(),
+        update.child_privacy_mode.into_option(),
+        update.session_retention_days.is_change(),
+        update.session_retention_days.into_option().map(i32::from),
     )
     .execute(pool)
     .await?;
@@ -379,6 +384,10 @@ where account_id = $1
             .await?,
             created_at: account.created_at,
             updated_at: account.updated_at,
+            seats: match account.account_type {
+                AccountType::School => Some(get_account_seats(pool, &account.account_id).await?),
+                AccountType::Individual => None,
+            },
         })),
         None => Ok(None),
     }
@@ -424,6 +433,10 @@ where user_account.user_id = $1
             .await?,
             created_at: account.created_at,
             updated_at: account.updated_at,
+            seats: match account.account_type {
+                AccountType::School => Some(get_account_seats(pool, &account.account_id).await?),
+                AccountType::Individual => None,
+            },
         })),
         None => Ok(None),
     }
@@ -520,6 +533,8 @@ select
     website,
     organization_type,
     account_id as "account_id!: AccountId",
+    child_privacy_mode,
+    session_retention_days,
     created_at,
     updated_at
 from school
@@ -542,6 +557,8 @@ where account_id = $1
                 website: record.website,
                 organization_type: record.organization_type,
                 account_id: record.account_id,
+                child_privacy_mode: record.child_privacy_mode,
+                session_retention_days: record.session_retention_days.map(|days| days as u16),
                 created_at: record.created_at,
                 updated_at: record.updated_at,
             };
@@ -605,6 +622,8 @@ select
     website,
     organization_type,
     account_id as "account_id!: AccountId",
+    child_privacy_mode,
+    session_retention_days,
     created_at,
     updated_at
 from school
@@ -627,6 +646,8 @@ where school_id = $1
                 website: record.website,
                 organization_type: record.organization_type,
                 account_id: record.account_id,
+                child_privacy_mode: record.child_privacy_mode,
+                session_retention_days: record.session_retention_days.map(|days| days as u16),
                 created_at: record.created_at,
                 updated_at: record.updated_at,
             };
@@ -916,75 +937,343 @@ where account_id = $1
     Ok(())
 }
 
-#[instrument(skip(pool))]
-pub async fn delete_account_for_user(pool: &PgPool, user_id: &UserId) -> anyhow::Result<()> {
+#[instrument(skip(conn))]
+pub async fn delete_account_for_user(
+    conn: &mut PgConnection,
+    user_id: &UserId,
+) -> anyhow::Result<()> {
     let account_id = sqlx::query_scalar!(
         // language=SQL
         r#"select account_id as "account_id!: AccountId" from user_account where user_id = $1"#,
         user_id as &UserId,
     )
-    .fetch_optional(pool)
+    .fetch_optional(&mut *conn)
     .await?;
 
     let Some(account_id) = account_id else {
         return Err(anyhow!("Could not find user"));
     };
 
-    let delete_queries = vec![
-        sqlx::query!(
-            r#"delete from subscription where account_id = $1;"#,
-            account_id as AccountId,
-        ),
-        sqlx::query!(
-            r#"delete from user_account where account_id = $1;"#,
-            account_id as AccountId,
-        ),
-        sqlx::query!(
-            r#"delete from school where account_id = $1;"#,
-            account_id as AccountId,
-        ),
-        sqlx::query!(
-            r#"delete from account where account_id = $1;"#,
-            account_id as AccountId,
-        ),
-    ];
-
-    join_all(
-        delete_queries
-            .into_iter()
-            .map(|q| q.execute(pool))
-            .collect::<Vec<_>>(),
+    sqlx::query!(
+        r#"delete from subscription where account_id = $1;"#,
+        account_id as AccountId,
     )
-    .await
-    .into_iter()
-    .collect::<Result<Vec<_>, _>>()?;
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"delete from user_account where account_id = $1;"#,
+        account_id as AccountId,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"delete from school where account_id = $1;"#,
+        account_id as AccountId,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"delete from account where account_id = $1;"#,
+        account_id as AccountId,
+    )
+    .execute(&mut *conn)
+    .await?;
 
     Ok(())
 }
 
-#[instrument(skip(pool))]
-pub async fn delete_user_from_school(
-    pool: &PgPool,
+/// Removes a user from a school, freeing up their seat. Returns whether they were a member.
+#[instrument(skip(executor))]
+pub async fn delete_user_from_school<'c, E: Executor<'c, Database = Postgres>>(
+    executor: E,
     school_id: &SchoolId,
     user_id: &UserId,
-) -> sqlx::Result<()> {
-    let account_id = sqlx::query_scalar!(
+) -> sqlx::Result<bool> {
+    let deleted = sqlx::query!(
         // language=SQL
-        r#"select account_id as "account_id!: AccountId" from school where school_id = $1"#,
+        r#"
+delete from user_account
+using school
+where school.school_id = $1
+    and user_account.account_id = school.account_id
+    and user_account.user_id = $2
+"#,
         school_id as &SchoolId,
+        user_id as &UserId,
     )
-    .fetch_optional(pool)
+    .execute(executor)
+    .await?
+    .rows_affected();
+
+    Ok(deleted > 0)
+}
+
+/// Locks an account until the end of the transaction, so that its seats can be handed out without
+/// racing other requests.
+pub async fn lock_account(txn: &mut PgConnection, account_id: &AccountId) -> sqlx::Result<()> {
+    sqlx::query!(
+        // language=SQL
+        r#"select 1 as "locked" from account where account_id = $1 for update"#,
+        account_id as &AccountId,
+    )
+    .fetch_optional(txn)
     .await?;
 
-    if let Some(account_id) = account_id {
-        sqlx::query!(
-            r#"delete from user_account where account_id = $1 and user_id = $2;"#,
-            account_id as AccountId,
-            user_id as &UserId,
-        )
-        .execute(pool)
-        .await?;
-    }
+    Ok(())
+}
+
+#[instrument(skip(executor))]
+pub async fn get_account_seats<'c, E: Executor<'c, Database = Postgres>>(
+    executor: E,
+    account_id: &AccountId,
+) -> sqlx::Result<AccountSeats> {
+    let record = sqlx::query!(
+        // language=SQL
+        r#"
+select
+    (select count(*) from user_account where account_id = $1) as "members!",
+    (select count(*) from school_invite where account_id = $1 and accepted_at is null) as "pending_invites!",
+    tier_override is not null as "overridden!",
+    subscription.plan_type as "plan_type?: PlanType",
+    subscription.status as "status?: SubscriptionStatus"
+from account
+left join lateral (
+    select plan_type, status
+    from subscription
+    inner join subscription_plan on subscription_plan.plan_id = subscription.subscription_plan_id
+    where subscription.account_id = account.account_id
+    order by subscription.created_at desc
+    limit 1
+) as subscription on true
+where account.account_id = $1
+"#,
+        account_id as &AccountId,
+    )
+    .fetch_one(executor)
+    .await?;
+
+    let limit = match (record.plan_type, record.status) {
+        _ if record.overridden => None,
+        (Some(plan_type), Some(status)) if status.is_valid() => plan_type.account_limit(),
+        _ => Some(AccountLimit::from(0)),
+    };
+
+    Ok(AccountSeats {
+        members: record.members,
+        pending_invites: record.pending_invites,
+        limit,
+    })
+}
+
+/// Whether a user with this email is already a member of the account
+pub async fn is_account_member_by_email<'c, E: Executor<'c, Database = Postgres>>(
+    executor: E,
+    account_id: &AccountId,
+    email: &str,
+) -> sqlx::Result<bool> {
+    sqlx::query_scalar!(
+        // language=SQL
+        r#"
+select exists(
+    select 1
+    from user_account
+    inner join user_email using (user_id)
+    where account_id = $1 and user_email.email = $2::text::citext
+) as "exists!"
+"#,
+        account_id as &AccountId,
+        email,
+    )
+    .fetch_one(executor)
+    .await
+}
+
+pub async fn has_pending_school_invite<'c, E: Executor<'c, Database = Postgres>>(
+    executor: E,
+    account_id: &AccountId,
+    email: &str,
+) -> sqlx::Result<bool> {
+    sqlx::query_scalar!(
+        // language=SQL
+        r#"
+select exists(
+    select 1 from school_invite where account_id = $1 and email = $2::text::citext and accepted_at is null
+) as "exists!"
+"#,
+        account_id as &AccountId,
+        email,
+    )
+    .fetch_one(executor)
+    .await
+}
+
+#[instrument(skip(executor))]
+pub async fn create_school_invite<'c, E: Executor<'c, Database = Postgres>>(
+    executor: E,
+    account_id: &AccountId,
+    email: &str,
+    invited_by: &UserId,
+) -> sqlx::Result<SchoolInvite> {
+    sqlx::query_as!(
+        SchoolInvite,
+        // language=SQL
+        r#"
+insert into school_invite (account_id, email, invited_by)
+values ($1, $2::text::citext, $3)
+returning
+    invite_id as "id!: SchoolInviteId",
+    email::text as "email!",
+    invited_by as "invited_by?: UserId",
+    created_at
+"#,
+        account_id as &AccountId,
+        email,
+        invited_by as &UserId,
+    )
+    .fetch_one(executor)
+    .await
+}
+
+#[instrument(skip(pool))]
+pub async fn get_pending_school_invites(
+    pool: &PgPool,
+    account_id: &AccountId,
+) -> sqlx::Result<Vec<SchoolInvite>> {
+    sqlx::query_as!(
+        SchoolInvite,
+        // language=SQL
+        r#"
+select
+    invite_id as "id!: SchoolInviteId",
+    email::text as "email!",
+    invited_by as "invited_by?: UserId",
+    created_at
+from school_invite
+where account_id = $1 and accepted_at is null
+order by created_at
+"#,
+        account_id as &AccountId,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Deletes a pending invite to the account. Returns whether there was one.
+#[instrument(skip(pool))]
+pub async fn delete_school_invite(
+    pool: &PgPool,
+    account_id: &AccountId,
+    invite_id: &SchoolInviteId,
+) -> sqlx::Result<bool> {
+    let deleted = sqlx::query!(
+        // language=SQL
+        r#"delete from school_invite where invite_id = $1 and account_id = $2 and accepted_at is null"#,
+        invite_id as &SchoolInviteId,
+        account_id as &AccountId,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(deleted > 0)
+}
+
+/// Pending invites sent to the user's email address
+#[instrument(skip(pool))]
+pub async fn get_user_school_invites(
+    pool: &PgPool,
+    user_id: &UserId,
+) -> sqlx::Result<Vec<UserSchoolInvite>> {
+    sqlx::query_as!(
+        UserSchoolInvite,
+        // language=SQL
+        r#"
+select
+    invite_id as "id!: SchoolInviteId",
+    school_id as "school_id!: SchoolId",
+    school_name::text as "school_name!",
+    school_invite.created_at
+from school_invite
+inner join user_email using (email)
+inner join school using (account_id)
+where user_email.user_id = $1 and accepted_at is null
+order by school_invite.created_at
+"#,
+        user_id as &UserId,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Account of a pending invite sent to the user's email address, if there is one
+pub async fn get_user_school_invite_account<'c, E: Executor<'c, Database = Postgres>>(
+    executor: E,
+    user_id: &UserId,
+    invite_id: &SchoolInviteId,
+) -> sqlx::Result<Option<AccountId>> {
+    sqlx::query_scalar!(
+        // language=SQL
+        r#"
+select account_id as "account_id!: AccountId"
+from school_invite
+inner join user_email using (email)
+where invite_id = $1 and user_email.user_id = $2 and accepted_at is null
+"#,
+        invite_id as &SchoolInviteId,
+        user_id as &UserId,
+    )
+    .fetch_optional(executor)
+    .await
+}
+
+/// Marks an invite as accepted by the user, which releases the seat it was holding.
+pub async fn accept_school_invite<'c, E: Executor<'c, Database = Postgres>>(
+    executor: E,
+    invite_id: &SchoolInviteId,
+    user_id: &UserId,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        // language=SQL
+        r#"
+update school_invite
+set accepted_at = now(), accepted_by = $2
+where invite_id = $1
+"#,
+        invite_id as &SchoolInviteId,
+        user_id as &UserId,
+    )
+    .execute(executor)
+    .await?;
 
     Ok(())
 }
+
+/// Deletes a pending invite sent to the user's email address. Returns whether there was one.
+#[instrument(skip(pool))]
+pub async fn decline_school_invite(
+    pool: &PgPool,
+    user_id: &UserId,
+    invite_id: &SchoolInviteId,
+) -> sqlx::Result<bool> {
+    let deleted = sqlx::query!(
+        // language=SQL
+        r#"
+delete from school_invite
+using user_email
+where invite_id = $1
+    and user_email.user_id = $2
+    and school_invite.email = user_email.email
+    and accepted_at is null
+"#,
+        invite_id as &SchoolInviteId,
+        user_id as &UserId,
+    )
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(deleted > 0)
+}
@@ -1,16 +1,24 @@
 pub mod user {
-    use futures::stream::BoxStream;
-    use shared::domain::audio::{user::UserAudio, AudioId};
+    use futures::{stream::BoxStream, StreamExt, TryStreamExt};
+    use shared::domain::{
+        audio::{
+            user::{UserAudio, UserAudioWaveformResponse},
+            AudioId,
+        },
+        user::UserId,
+    };
     use sqlx::PgPool;
 
-    pub async fn create(db: &PgPool) -> sqlx::Result<AudioId> {
+    pub async fn create(db: &PgPool, user_id: UserId) -> sqlx::Result<AudioId> {
         let mut txn = db.begin().await?;
 
         let id: AudioId = sqlx::query!(
             r#"
-insert into user_audio_library default values
+insert into user_audio_library(user_id)
+values($1)
 returning id as "id: AudioId"
 "#,
+            user_id.0
         )
         .fetch_one(db)
         .await?
@@ -33,20 +41,47 @@ returning id as "id: AudioId"
     }
 
     pub async fn get(db: &PgPool, image: AudioId) -> sqlx::Result<Option<UserAudio>> {
-        sqlx::query_as!(
-            UserAudio,
-            r#"select id as "id: AudioId" from user_audio_library where id = $1"#,
+        let audio = sqlx::query!(
+            r#"select id as "id: AudioId", duration_ms from user_audio_library where id = $1"#,
             image.0
         )
         .fetch_optional(db)
-        .await
+        .await?
+        .map(|row| UserAudio {
+            id: row.id,
+            duration_ms: row.duration_ms.map(|it| it as u32),
+        });
+
+        Ok(audio)
     }
 
     pub fn list(db: &PgPool) -> BoxStream<'_, sqlx::Result<UserAudio>> {
-        sqlx::query_as!(
-            UserAudio,
-            r#"select id as "id: AudioId" from user_audio_library order by created_at desc"#,
+        sqlx::query!(
+            r#"select id as "id: AudioId", duration_ms from user_audio_library order by created_at desc"#,
         )
         .fetch(db)
+        .map_ok(|row| UserAudio {
+            id: row.id,
+            duration_ms: row.duration_ms.map(|it| it as u32),
+        })
+        .boxed()
+    }
+
+    pub async fn get_waveform(
+        db: &PgPool,
+        audio: AudioId,
+    ) -> sqlx::Result<Option<UserAudioWaveformResponse>> {
+        let peaks = sqlx::query!(
+            r#"select waveform as "waveform!" from user_audio_library where id = $1 and waveform is not null"#,
+            audio.0
+        )
+        .fetch_optional(db)
+        .await?
+        .map(|it| it.waveform);
+
+        Ok(peaks.map(|peaks| UserAudioWaveformResponse {
+            peaks_per_second: crate::audio_ops::WAVEFORM_PEAKS_PER_SECOND,
+            peaks,
+        }))
     }
 }
@@ -1,10 +1,11 @@
 use chrono::{DateTime, Utc};
 use shared::domain::billing::{
-    AccountId, AmountInCents, AppliedCoupon, CreateSubscriptionRecord, PlanId, PlanType,
-    StripeInvoiceId, StripePriceId, StripeSubscriptionId, Subscription, SubscriptionId,
-    SubscriptionPlan, SubscriptionStatus, UpdateSubscriptionRecord,
+    AccountId, AmountInCents, AppliedCoupon, CreateSubscriptionRecord, ListWebhookEventsQuery,
+    PlanId, PlanType, PurchaseOrder, StripeEventId, StripeInvoiceId, StripePriceId,
+    StripeSubscriptionId, Subscription, SubscriptionId, SubscriptionPlan, SubscriptionStatus,
+    UpdateSubscriptionRecord, WebhookEvent, WebhookEventStatus,
 };
-use shared::domain::Percent;
+use shared::domain::{ItemCount, Percent};
 use sqlx::types::BigDecimal;
 use sqlx::PgPool;
 use tracing::{instrument, Instrument};
@@ -103,10 +104,12 @@ insert into subscription
         account_id,
         latest_invoice_id,
         amount_due,
-        price
+        price,
+        purchase_order_number,
+        days_until_due
     )
 values
-    ($1, $2, $3, $4, $5, $6, $7, $8)
+    ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
 returning subscription_id as "id!: SubscriptionId"
 "#,
         subscription.stripe_subscription_id as StripeSubscriptionId,
@@ -119,16 +122,29 @@ returning subscription_id as "id!: SubscriptionId"
             .map(|invoice_id| invoice_id.inner()),
         subscription.amount_due_in_cents.map(|due| due.inner()),
         subscription.price.inner(),
+        subscription
+            .purchase_order
+            .as_ref()
+            .map(|purchase_order| purchase_order.purchase_order_number.as_str()),
+        subscription
+            .purchase_order
+            .as_ref()
+            .map(|purchase_order| purchase_order.days_until_due as i32),
     )
     .fetch_one(pool)
     .await
     .map(|res| res.id)
 }
 
+/// Saves changes to a subscription.
+///
+/// `as_of` is when Stripe took the snapshot of the subscription the changes come from. Snapshots
+/// older than the last update of the subscription are skipped.
 #[instrument(skip(pool))]
 pub async fn save_subscription(
     pool: &PgPool,
     subscription: UpdateSubscriptionRecord,
+    as_of: Option<DateTime<Utc>>,
 ) -> sqlx::Result<()> {
     sqlx::query!(
         //language=SQL
@@ -138,7 +154,7 @@ set
     subscription_plan_id = coalesce($2, subscription_plan_id),
     status = coalesce($3, status),
     current_period_end = coalesce($4, current_period_end),
-    updated_at = now(),
+    updated_at = coalesce($17, now()),
     latest_invoice_id = case when $5 then $6 else latest_invoice_id end,
     is_trial = coalesce($7, is_trial),
     price = coalesce($8, price),
@@ -147,6 +163,7 @@ set
     coupon_from = case when $13 then $14 else coupon_from end,
     coupon_to = case when $15 then $16 else coupon_to end
 where stripe_subscription_id = $1
+  and ($17::timestamptz is null or updated_at is null or updated_at <= $17)
 "#,
         subscription.stripe_subscription_id as StripeSubscriptionId,
         subscription.subscription_plan_id.into_option() as Option<PlanId>,
@@ -173,6 +190,7 @@ where stripe_subscription_id = $1
         subscription.coupon_from.into_option(),
         subscription.coupon_to.is_change(),
         subscription.coupon_to.into_option(),
+        as_of,
     )
     .execute(pool)
     .await?;
@@ -226,6 +244,8 @@ select
     coupon_from as "coupon_from?: DateTime<Utc>",
     coupon_to as "coupon_to?: DateTime<Utc>",
     coupon_percent as "coupon_percent?",
+    purchase_order_number as "purchase_order_number?",
+    days_until_due as "days_until_due?",
     subscription.created_at as "created_at!: DateTime<Utc>",
     subscription.updated_at as "updated_at?: DateTime<Utc>"
 from subscription
@@ -245,6 +265,13 @@ where subscription_id = $1
             coupon_to: row.coupon_to,
         });
 
+        let purchase_order = row.purchase_order_number.zip(row.days_until_due).map(
+            |(purchase_order_number, days_until_due)| PurchaseOrder {
+                purchase_order_number,
+                days_until_due: days_until_due as u32,
+            },
+        );
+
         Subscription {
             subscription_id: row.subscription_id,
             stripe_subscription_id: row.stripe_subscription_id,
@@ -258,6 +285,7 @@ where subscription_id = $1
             amount_due_in_cents: row.amount_due_in_cents,
             price: row.price,
             applied_coupon,
+            purchase_order,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
@@ -289,6 +317,8 @@ select
     coupon_from as "coupon_from?: DateTime<Utc>",
     coupon_to as "coupon_to?: DateTime<Utc>",
     coupon_percent as "coupon_percent?",
+    purchase_order_number as "purchase_order_number?",
+    days_until_due as "days_until_due?",
     subscription.created_at as "created_at!: DateTime<Utc>",
     subscription.updated_at as "updated_at?: DateTime<Utc>"
 from subscription
@@ -310,6 +340,13 @@ limit 1
             coupon_to: row.coupon_to,
         });
 
+        let purchase_order = row.purchase_order_number.zip(row.days_until_due).map(
+            |(purchase_order_number, days_until_due)| PurchaseOrder {
+                purchase_order_number,
+                days_until_due: days_until_due as u32,
+            },
+        );
+
         Subscription {
             subscription_id: row.subscription_id,
             stripe_subscription_id: row.stripe_subscription_id,
@@ -323,6 +360,7 @@ limit 1
             amount_due_in_cents: row.amount_due_in_cents,
             price: row.price,
             applied_coupon,
+            purchase_order,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
@@ -344,3 +382,160 @@ pub async fn get_stripe_subscription_id_by_invoice_id(
     .fetch_optional(pool)
     .await
 }
+
+/// Saves a webhook event to the event log. Returns `false` if the event has already been
+/// processed or ignored, in which case it shouldn't be handled again.
+#[instrument(skip(pool, payload))]
+pub async fn save_webhook_event(
+    pool: &PgPool,
+    id: &StripeEventId,
+    event_type: &str,
+    payload: &serde_json::Value,
+) -> sqlx::Result<bool> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into stripe_webhook_event (id, event_type, payload)
+values ($1, $2, $3)
+on conflict (id) do update set payload = excluded.payload
+where stripe_webhook_event.status not in (1, 3)
+returning id
+"#,
+        id as &StripeEventId,
+        event_type,
+        payload,
+    )
+    .fetch_optional(pool)
+    .await
+    .map(|row| row.is_some())
+}
+
+/// Records the outcome of handling a webhook event.
+#[instrument(skip(pool))]
+pub async fn record_webhook_event_attempt(
+    pool: &PgPool,
+    id: &StripeEventId,
+    status: WebhookEventStatus,
+    error: Option<&str>,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update stripe_webhook_event
+set status       = $2,
+    attempts     = attempts + 1,
+    error        = $3,
+    processed_at = now()
+where id = $1
+"#,
+        id as &StripeEventId,
+        status as i16,
+        error,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_webhook_event(
+    pool: &PgPool,
+    id: &StripeEventId,
+) -> sqlx::Result<Option<WebhookEvent>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id!: StripeEventId",
+       event_type,
+       payload,
+       status as "status!: WebhookEventStatus",
+       attempts,
+       error,
+       created_at,
+       processed_at
+from stripe_webhook_event
+where id = $1
+"#,
+        id as &StripeEventId,
+    )
+    .fetch_optional(pool)
+    .await
+    .map(|row| {
+        row.map(|row| WebhookEvent {
+            id: row.id,
+            event_type: row.event_type,
+            payload: row.payload,
+            status: row.status,
+            attempts: row.attempts as u16,
+            error: row.error,
+            created_at: row.created_at,
+            processed_at: row.processed_at,
+        })
+    })
+}
+
+pub async fn list_webhook_events(
+    pool: &PgPool,
+    query: &ListWebhookEventsQuery,
+) -> sqlx::Result<Vec<WebhookEvent>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id!: StripeEventId",
+       event_type,
+       payload,
+       status as "status!: WebhookEventStatus",
+       attempts,
+       error,
+       created_at,
+       processed_at
+from stripe_webhook_event
+where ($1::smallint is null or status = $1)
+  and ($2::text is null or event_type = $2)
+order by created_at desc
+limit $3
+offset $4
+"#,
+        query.status.map(|status| status as i16),
+        query.event_type,
+        i64::from(query.page_limit),
+        query.page_limit.offset(query.page),
+    )
+    .fetch_all(pool)
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .map(|row| WebhookEvent {
+                id: row.id,
+                event_type: row.event_type,
+                payload: row.payload,
+                status: row.status,
+                attempts: row.attempts as u16,
+                error: row.error,
+                created_at: row.created_at,
+                processed_at: row.processed_at,
+            })
+            .collect()
+    })
+}
+
+pub async fn count_webhook_events(
+    pool: &PgPool,
+    query: &ListWebhookEventsQuery,
+) -> sqlx::Result<ItemCount> {
+    let count = sqlx::query_scalar!(
+        //language=SQL
+        r#"
+select count(*) as "count!"
+from stripe_webhook_event
+where ($1::smallint is null or status = $1)
+  and ($2::text is null or event_type = $2)
+"#,
+        query.status.map(|status| status as i16),
+        query.event_type,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((count as usize).into())
+}
@@ -3,9 +3,15 @@ use crate::{
     error::{self, Delete},
 };
 use futures::TryStreamExt;
-use shared::domain::category::{Category, CategoryId};
+use shared::domain::asset::{AssetId, AssetType};
+use shared::domain::category::{Category, CategoryId, CategoryUsage, RecategorizedAsset};
+use shared::domain::course::CourseId;
+use shared::domain::jig::JigId;
+use shared::domain::playlist::PlaylistId;
+use shared::domain::resource::ResourceId;
 use shared::domain::user::UserScope;
-use sqlx::{Executor, PgPool};
+use sqlx::{types::Json, Executor, PgPool};
+use std::collections::HashMap;
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -15,6 +21,7 @@ pub async fn get_top_level(db: &sqlx::PgPool) -> anyhow::Result<Vec<Category>> {
         r#"
 select id                                                                 as "id: CategoryId",
        name,
+       translated_name                                                    as "translated_name: Json<HashMap<String, String>>",
        created_at,
        updated_at,
        user_scopes
@@ -29,6 +36,7 @@ order by index
         created_at: it.created_at,
         updated_at: it.updated_at,
         name: it.name,
+        translated_name: it.translated_name.0,
         children: vec![],
         user_scopes: {
             let scopes = it
@@ -52,6 +60,7 @@ pub async fn get_exact(db: &sqlx::PgPool, ids: &[Uuid]) -> sqlx::Result<Vec<Cate
         r#"
 select id                                                                 as "id!: CategoryId",
        name                                                               as "name!",
+       translated_name                                                    as "translated_name!: Json<HashMap<String, String>>",
        created_at                                                         as "created_at!",
        updated_at,
        user_scopes                                                        as "user_scopes!"
@@ -65,6 +74,7 @@ order by t.ord
     .map_ok(|it| Category {
         id: it.id,
         name: it.name,
+        translated_name: it.translated_name.0,
         created_at: it.created_at,
         updated_at: it.updated_at,
         children: vec![],
@@ -95,6 +105,7 @@ pub async fn get_tree(db: &sqlx::PgPool) -> sqlx::Result<Vec<Category>> {
 select id,
        parent_id,
        name,
+       translated_name as "translated_name: Json<HashMap<String, String>>",
        index,
        created_at,
        updated_at,
@@ -187,6 +198,7 @@ async fn update_slow(
     id: Uuid,
     new_parent: Option<Option<Uuid>>,
     name: Option<&str>,
+    translated_name: Option<&HashMap<String, String>>,
     index: Option<i16>,
 ) -> Result<(), UpdateLoopError> {
     let mut txn = db.begin().await?;
@@ -211,6 +223,16 @@ select parent_id, index from category where id = $1 for update
             .await?;
     }
 
+    if let Some(translated_name) = translated_name {
+        sqlx::query!(
+            "update category set translated_name = $1 where id = $2",
+            Json(translated_name) as _,
+            id
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
     let mut current_index = category_info.index;
     if let Some(parent_id) = new_parent {
         if parent_id != category_info.parent_id {
@@ -295,6 +317,7 @@ pub async fn update(
     CategoryId(id): CategoryId,
     parent_id: Option<Option<CategoryId>>,
     name: Option<&str>,
+    translated_name: Option<HashMap<String, String>>,
     index: Option<i16>,
     user_scopes: Option<Vec<UserScope>>,
 ) -> Result<(), error::CategoryUpdate> {
@@ -320,6 +343,23 @@ pub async fn update(
             }
         }
 
+        if let Some(translated_name) = &translated_name {
+            let rows_updated = sqlx::query!(
+                "update category set translated_name = $1, updated_at = now() where id = $2",
+                Json(translated_name) as _,
+                id
+            )
+            .execute(db)
+            .await?
+            .rows_affected();
+
+            match rows_updated {
+                0 => return Err(error::CategoryUpdate::CategoryNotFound),
+                1 => {}
+                _ => unreachable!(),
+            }
+        }
+
         if let Some(user_scopes) = user_scopes {
             let scopes: Vec<i16> = user_scopes.iter().map(|x| *x as i16).collect();
 
@@ -350,7 +390,7 @@ pub async fn update(
 
     let new_parent = parent_id.map(|id| id.map(|it| it.0));
     loop {
-        match update_slow(db, id, new_parent, name, index).await {
+        match update_slow(db, id, new_parent, name, translated_name.as_ref(), index).await {
             Ok(()) => return Ok(()),
             Err(UpdateLoopError::UpdateError(e)) => return Err(e),
             Err(UpdateLoopError::Sqlx(sqlx::Error::Database(e)))
@@ -414,3 +454,512 @@ pub async fn delete(db: &PgPool, id: CategoryId) -> Result<(), Delete> {
         }
     }
 }
+
+#[instrument(skip_all)]
+pub async fn usage(db: &PgPool) -> sqlx::Result<Vec<CategoryUsage>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select id                                                                          as "id!: CategoryId",
+       (select count(distinct jig.id)
+        from jig
+                 inner join jig_data_category on jig_data_id in (jig.draft_id, jig.live_id)
+        where category_id = category.id)                                           as "jigs!",
+       (select count(distinct resource.id)
+        from resource
+                 inner join resource_data_category on resource_data_id in (resource.draft_id, resource.live_id)
+        where category_id = category.id)                                           as "resources!",
+       (select count(distinct playlist.id)
+        from playlist
+                 inner join playlist_data_category on playlist_data_id in (playlist.draft_id, playlist.live_id)
+        where category_id = category.id)                                           as "playlists!",
+       (select count(distinct course.id)
+        from course
+                 inner join course_data_category on course_data_id in (course.draft_id, course.live_id)
+        where category_id = category.id)                                           as "courses!",
+       (select count(*) from image_category where category_id = category.id)       as "images!"
+from category
+"#
+    )
+    .fetch(db)
+    .map_ok(|it| CategoryUsage {
+        id: it.id,
+        jigs: it.jigs as u64,
+        resources: it.resources as u64,
+        playlists: it.playlists as u64,
+        courses: it.courses as u64,
+        images: it.images as u64,
+    })
+    .try_collect()
+    .await
+}
+
+/// Moves everything in the category `id` to `into`, then deletes it.
+///
+/// Assets and images which are moved are marked for reindexing, and `id`'s children are appended to
+/// `into`'s children in their current order.
+#[instrument(skip(db))]
+pub async fn merge(
+    db: &PgPool,
+    CategoryId(id): CategoryId,
+    CategoryId(into): CategoryId,
+) -> Result<(), error::CategoryMerge> {
+    async fn inner(db: &PgPool, id: Uuid, into: Uuid) -> Result<(), MergeLoopError> {
+        let mut txn = db.begin().await?;
+
+        txn.execute("set transaction isolation level repeatable read")
+            .await?;
+
+        let category_info = sqlx::query!(
+            r#"
+select parent_id, index from category where id = $1 for update
+    "#,
+            id
+        )
+        .fetch_optional(&mut txn)
+        .await?
+        .ok_or(error::CategoryMerge::CategoryNotFound)?;
+
+        let into_exists = sqlx::query!(
+            r#"select exists(select 1 from category where id = $1) as "exists!""#,
+            into
+        )
+        .fetch_one(&mut txn)
+        .await?
+        .exists;
+
+        if !into_exists {
+            return Err(error::CategoryMerge::TargetCategoryNotFound.into());
+        }
+
+        // merging into a descendant would leave it as its own ancestor once the children move up.
+        if would_cycle(&mut txn, id, into).await? {
+            return Err(error::CategoryMerge::Cycle.into());
+        }
+
+        sqlx::query!(
+            //language=SQL
+            r#"
+with moved as (
+    delete from jig_data_category where category_id = $1 returning jig_data_id
+), added as (
+    insert into jig_data_category (jig_data_id, category_id)
+    select jig_data_id, $2::uuid
+    from moved
+    on conflict do nothing
+)
+update jig_data
+set last_synced_at = null
+where id in (select jig_data_id from moved)
+"#,
+            id,
+            into
+        )
+        .execute(&mut txn)
+        .await?;
+
+        sqlx::query!(
+            //language=SQL
+            r#"
+with moved as (
+    delete from resource_data_category where category_id = $1 returning resource_data_id
+), added as (
+    insert into resource_data_category (resource_data_id, category_id)
+    select resource_data_id, $2::uuid
+    from moved
+    on conflict do nothing
+)
+update resource_data
+set last_synced_at = null
+where id in (select resource_data_id from moved)
+"#,
+            id,
+            into
+        )
+        .execute(&mut txn)
+        .await?;
+
+        sqlx::query!(
+            //language=SQL
+            r#"
+with moved as (
+    delete from playlist_data_category where category_id = $1 returning playlist_data_id
+), added as (
+    insert into playlist_data_category (playlist_data_id, category_id)
+    select playlist_data_id, $2::uuid
+    from moved
+    on conflict do nothing
+)
+update playlist_data
+set last_synced_at = null
+where id in (select playlist_data_id from moved)
+"#,
+            id,
+            into
+        )
+        .execute(&mut txn)
+        .await?;
+
+        sqlx::query!(
+            //language=SQL
+            r#"
+with moved as (
+    delete from course_data_category where category_id = $1 returning course_data_id
+), added as (
+    insert into course_data_category (course_data_id, category_id)
+    select course_data_id, $2::uuid
+    from moved
+    on conflict do nothing
+)
+update course_data
+set last_synced_at = null
+where id in (select course_data_id from moved)
+"#,
+            id,
+            into
+        )
+        .execute(&mut txn)
+        .await?;
+
+        sqlx::query!(
+            //language=SQL
+            r#"
+with moved as (
+    delete from image_category where category_id = $1 returning image_id
+), added as (
+    insert into image_category (image_id, category_id)
+    select image_id, $2::uuid
+    from moved
+    on conflict do nothing
+)
+update image_metadata
+set last_synced_at = null
+where id in (select image_id from moved)
+"#,
+            id,
+            into
+        )
+        .execute(&mut txn)
+        .await?;
+
+        sqlx::query!(
+            r#"
+update category
+set parent_id = $2,
+    updated_at = now(),
+    index = index + (select count(*)::int2 from category where parent_id = $2)
+where parent_id = $1
+"#,
+            id,
+            into
+        )
+        .execute(&mut txn)
+        .await?;
+
+        sqlx::query!("delete from category where id = $1", id)
+            .execute(&mut txn)
+            .await?;
+
+        backshift(&mut txn, category_info.parent_id, category_info.index, None).await?;
+
+        txn.commit().await?;
+
+        Ok(())
+    }
+
+    loop {
+        match inner(db, id, into).await {
+            Ok(()) => return Ok(()),
+            Err(MergeLoopError::MergeError(e)) => return Err(e),
+            Err(MergeLoopError::Sqlx(sqlx::Error::Database(e)))
+                if e.code().as_deref() == Some("40001") => {}
+            Err(MergeLoopError::Sqlx(e)) => {
+                return Err(error::CategoryMerge::InternalServerError(e.into()))
+            }
+        }
+    }
+}
+
+enum MergeLoopError {
+    MergeError(error::CategoryMerge),
+    Sqlx(sqlx::Error),
+}
+
+impl From<sqlx::Error> for MergeLoopError {
+    fn from(err: sqlx::Error) -> Self {
+        Self::Sqlx(err)
+    }
+}
+
+impl From<error::CategoryMerge> for MergeLoopError {
+    fn from(err: error::CategoryMerge) -> Self {
+        Self::MergeError(err)
+    }
+}
+
+/// Adds and removes categories on the draft and live data of the given assets, and of every
+/// asset in `in_category`.
+///
+/// Returns the assets which changed, or would change if `preview` is set. Data which changes is
+/// marked for reindexing.
+#[instrument(skip(db))]
+pub async fn recategorize(
+    db: &PgPool,
+    assets: &[AssetId],
+    in_category: Option<CategoryId>,
+    add: &[CategoryId],
+    remove: &[CategoryId],
+    preview: bool,
+) -> sqlx::Result<Vec<RecategorizedAsset>> {
+    let ids_of = |asset_type: AssetType| -> Vec<Uuid> {
+        assets
+            .iter()
+            .filter(|asset_id| asset_id.asset_type() == asset_type)
+            .map(|asset_id| *asset_id.uuid())
+            .collect()
+    };
+
+    let jig_ids = ids_of(AssetType::Jig);
+    let resource_ids = ids_of(AssetType::Resource);
+    let playlist_ids = ids_of(AssetType::Playlist);
+    let course_ids = ids_of(AssetType::Course);
+
+    let mut txn = db.begin().await?;
+
+    // every version of the selected assets, along with its current categories.
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select 'jig'                                                                           as "kind!",
+       jig.id                                                                          as "asset_id!",
+       data_id                                                                         as "data_id!",
+       array(select category_id from jig_data_category where jig_data_id = data_id)    as "categories!"
+from jig
+         cross join unnest(array [jig.draft_id, jig.live_id]) as data_id
+where jig.id = any ($1)
+   or exists(select 1
+             from jig_data_category
+             where jig_data_id in (jig.draft_id, jig.live_id)
+               and category_id = $5)
+union all
+select 'resource',
+       resource.id,
+       data_id,
+       array(select category_id from resource_data_category where resource_data_id = data_id)
+from resource
+         cross join unnest(array [resource.draft_id, resource.live_id]) as data_id
+where resource.id = any ($2)
+   or exists(select 1
+             from resource_data_category
+             where resource_data_id in (resource.draft_id, resource.live_id)
+               and category_id = $5)
+union all
+select 'playlist',
+       playlist.id,
+       data_id,
+       array(select category_id from playlist_data_category where playlist_data_id = data_id)
+from playlist
+         cross join unnest(array [playlist.draft_id, playlist.live_id]) as data_id
+where playlist.id = any ($3)
+   or exists(select 1
+             from playlist_data_category
+             where playlist_data_id in (playlist.draft_id, playlist.live_id)
+               and category_id = $5)
+union all
+select 'course',
+       course.id,
+       data_id,
+       array(select category_id from course_data_category where course_data_id = data_id)
+from course
+         cross join unnest(array [course.draft_id, course.live_id]) as data_id
+where course.id = any ($4)
+   or exists(select 1
+             from course_data_category
+             where course_data_id in (course.draft_id, course.live_id)
+               and category_id = $5)
+"#,
+        &jig_ids[..],
+        &resource_ids[..],
+        &playlist_ids[..],
+        &course_ids[..],
+        in_category.map(|it| it.0),
+    )
+    .fetch_all(&mut txn)
+    .await?;
+
+    let mut changed: Vec<RecategorizedAsset> = Vec::new();
+    let mut data_ids: HashMap<String, Vec<Uuid>> = HashMap::new();
+
+    for row in rows {
+        let asset_id = match row.kind.as_str() {
+            "jig" => AssetId::JigId(JigId(row.asset_id)),
+            "resource" => AssetId::ResourceId(ResourceId(row.asset_id)),
+            "playlist" => AssetId::PlaylistId(PlaylistId(row.asset_id)),
+            _ => AssetId::CourseId(CourseId(row.asset_id)),
+        };
+
+        let added: Vec<_> = add
+            .iter()
+            .filter(|id| !row.categories.contains(&id.0))
+            .copied()
+            .collect();
+
+        let removed: Vec<_> = remove
+            .iter()
+            .filter(|id| row.categories.contains(&id.0))
+            .copied()
+            .collect();
+
+        if added.is_empty() && removed.is_empty() {
+            continue;
+        }
+
+        data_ids.entry(row.kind).or_default().push(row.data_id);
+
+        // the draft and live rows of an asset are reported together.
+        let asset = match changed.iter_mut().find(|it| it.asset_id == asset_id) {
+            Some(asset) => asset,
+            None => {
+                changed.push(RecategorizedAsset {
+                    asset_id,
+                    added: vec![],
+                    removed: vec![],
+                });
+                changed.last_mut().expect("an asset was just pushed")
+            }
+        };
+
+        for id in added {
+            if !asset.added.contains(&id) {
+                asset.added.push(id);
+            }
+        }
+
+        for id in removed {
+            if !asset.removed.contains(&id) {
+                asset.removed.push(id);
+            }
+        }
+    }
+
+    if preview || changed.is_empty() {
+        return Ok(changed);
+    }
+
+    let add: Vec<Uuid> = add.iter().map(|it| it.0).collect();
+    let remove: Vec<Uuid> = remove.iter().map(|it| it.0).collect();
+
+    let mut data_ids_of = |kind: &str| data_ids.remove(kind).unwrap_or_default();
+    let jig_data_ids = data_ids_of("jig");
+    let resource_data_ids = data_ids_of("resource");
+    let playlist_data_ids = data_ids_of("playlist");
+    let course_data_ids = data_ids_of("course");
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+with added as (
+    insert into jig_data_category (jig_data_id, category_id)
+    select data.id, cat.id
+    from unnest($1::uuid[]) as data(id)
+             cross join unnest($2::uuid[]) as cat(id)
+    on conflict do nothing
+    returning jig_data_id
+), removed as (
+    delete from jig_data_category
+    where jig_data_id = any ($1) and category_id = any ($3)
+    returning jig_data_id
+)
+update jig_data
+set last_synced_at = null
+where id in (select jig_data_id from added union select jig_data_id from removed)
+"#,
+        &jig_data_ids[..],
+        &add[..],
+        &remove[..],
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+with added as (
+    insert into resource_data_category (resource_data_id, category_id)
+    select data.id, cat.id
+    from unnest($1::uuid[]) as data(id)
+             cross join unnest($2::uuid[]) as cat(id)
+    on conflict do nothing
+    returning resource_data_id
+), removed as (
+    delete from resource_data_category
+    where resource_data_id = any ($1) and category_id = any ($3)
+    returning resource_data_id
+)
+update resource_data
+set last_synced_at = null
+where id in (select resource_data_id from added union select resource_data_id from removed)
+"#,
+        &resource_data_ids[..],
+        &add[..],
+        &remove[..],
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+with added as (
+    insert into playlist_data_category (playlist_data_id, category_id)
+    select data.id, cat.id
+    from unnest($1::uuid[]) as data(id)
+             cross join unnest($2::uuid[]) as cat(id)
+    on conflict do nothing
+    returning playlist_data_id
+), removed as (
+    delete from playlist_data_category
+    where playlist_data_id = any ($1) and category_id = any ($3)
+    returning playlist_data_id
+)
+update playlist_data
+set last_synced_at = null
+where id in (select playlist_data_id from added union select playlist_data_id from removed)
+"#,
+        &playlist_data_ids[..],
+        &add[..],
+        &remove[..],
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+with added as (
+    insert into course_data_category (course_data_id, category_id)
+    select data.id, cat.id
+    from unnest($1::uuid[]) as data(id)
+             cross join unnest($2::uuid[]) as cat(id)
+    on conflict do nothing
+    returning course_data_id
+), removed as (
+    delete from course_data_category
+    where course_data_id = any ($1) and category_id = any ($3)
+    returning course_data_id
+)
+update course_data
+set last_synced_at = null
+where id in (select course_data_id from added union select course_data_id from removed)
+"#,
+        &course_data_ids[..],
+        &add[..],
+        &remove[..],
+    )
+    .execute(&mut txn)
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(changed)
+}
@@ -205,6 +205,7 @@ pub async fn browse(
             left join circle_member "cm" on cm.id = circle.id
             where (creator_id = $1 or $1 is null)
             and (cm.user_id = any($2) or $2 = array[]::uuid[])
+            and quarantined_at is null
             group by circle.created_at, member_count
             order by case when $5 = 0 then member_count
                 else extract(epoch from created_at)
@@ -433,6 +434,7 @@ select count(distinct circle.id) as "count!: i64"
     left join circle_member "cm" on cm.id = circle.id
     where (creator_id = $1 or $1 is null)
     and (cm.user_id = any($2) or $2 = array[]::uuid[])
+    and quarantined_at is null
 "#,
         creator_id.map(|x| x.0),
         &user_ids[..]
@@ -0,0 +1,1136 @@
+use chrono::{DateTime, Utc};
+use rand::{seq::SliceRandom, Rng};
+use shared::{
+    config::{CLASS_LOGIN_CODE_LENGTH, PICTURE_PASSWORD_CHOICES, PICTURE_PASSWORD_LENGTH},
+    domain::{
+        asset::{AssetId, PrivacyLevel},
+        billing::AccountId,
+        class::{
+            Class, ClassAssignment, ClassAssignmentCode, ClassAssignmentId, ClassId,
+            ClassLoginRosterResponse, LoginCode, PicturePassword, Student, StudentAssignment,
+            StudentAssignmentJig, StudentDataResponse, StudentId, StudentLoginRequest,
+            StudentProfile, StudentRecord,
+        },
+        jig::{
+            codes::{JigCode, JigCodeSessionResponse, JigPlayerSessionCreateRequest},
+            JigId, JigPlayerSettings, TextDirection,
+        },
+        playlist::PlaylistId,
+        user::UserId,
+        UpdateNullable,
+    },
+};
+use sqlx::{PgConnection, PgPool};
+
+use crate::{child_privacy, db, error};
+
+/// Generates a login code which isn't used by any class or student.
+async fn generate_login_code(conn: &mut PgConnection) -> sqlx::Result<LoginCode> {
+    loop {
+        let code: String = {
+            let mut rng = rand::thread_rng();
+
+            (0..CLASS_LOGIN_CODE_LENGTH)
+                .map(|_| *LoginCode::ALPHABET.choose(&mut rng).unwrap() as char)
+                .collect()
+        };
+
+        let exists = sqlx::query!(
+            //language=SQL
+            r#"
+select exists(select 1 from class where login_code = $1)
+    or exists(select 1 from class_student where login_code = $1) as "exists!"
+"#,
+            code
+        )
+        .fetch_one(&mut *conn)
+        .await?
+        .exists;
+
+        if !exists {
+            return Ok(LoginCode(code));
+        }
+    }
+}
+
+fn generate_picture_password() -> PicturePassword {
+    let mut rng = rand::thread_rng();
+
+    PicturePassword(
+        (0..PICTURE_PASSWORD_LENGTH)
+            .map(|_| rng.gen_range(0..PICTURE_PASSWORD_CHOICES as i16))
+            .collect(),
+    )
+}
+
+pub async fn create(
+    db: &PgPool,
+    owner_id: UserId,
+    display_name: &str,
+    account_id: Option<AccountId>,
+) -> sqlx::Result<ClassId> {
+    let mut txn = db.begin().await?;
+
+    let login_code = generate_login_code(&mut txn).await?;
+
+    let id = sqlx::query!(
+        //language=SQL
+        r#"
+insert into class (owner_id, account_id, display_name, login_code)
+values ($1, $2, $3, $4)
+returning id as "id!: ClassId"
+"#,
+        owner_id.0,
+        account_id.map(|id| id.0),
+        display_name,
+        login_code.0,
+    )
+    .fetch_one(&mut txn)
+    .await?
+    .id;
+
+    txn.commit().await?;
+
+    Ok(id)
+}
+
+/// Makes sure the class exists and is owned by the user.
+pub async fn authz(db: &PgPool, user_id: UserId, class_id: ClassId) -> Result<(), error::Class> {
+    let authed = sqlx::query!(
+        //language=SQL
+        r#"
+select exists(select 1 from class where id = $1 and owner_id = $2) as "authed!"
+"#,
+        class_id.0,
+        user_id.0,
+    )
+    .fetch_one(db)
+    .await?
+    .authed;
+
+    if !authed {
+        return Err(error::Class::ResourceNotFound);
+    }
+
+    Ok(())
+}
+
+pub async fn list(db: &PgPool, owner_id: UserId) -> sqlx::Result<Vec<Class>> {
+    let classes = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                                            as "id!: ClassId",
+       display_name,
+       owner_id                                                      as "owner_id!: UserId",
+       account_id                                                    as "account_id?: AccountId",
+       login_code                                                    as "login_code!: LoginCode",
+       (select count(*) from class_student where class_id = class.id) as "student_count!",
+       created_at,
+       updated_at
+from class
+where owner_id = $1
+order by created_at desc
+"#,
+        owner_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| Class {
+        id: row.id,
+        display_name: row.display_name,
+        owner_id: row.owner_id,
+        account_id: row.account_id,
+        login_code: row.login_code,
+        student_count: row.student_count as u32,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+    .collect();
+
+    Ok(classes)
+}
+
+pub async fn get(db: &PgPool, id: ClassId) -> sqlx::Result<Option<Class>> {
+    let class = sqlx::query!(
+        //language=SQL
+        r#"
+select id                                                            as "id!: ClassId",
+       display_name,
+       owner_id                                                      as "owner_id!: UserId",
+       account_id                                                    as "account_id?: AccountId",
+       login_code                                                    as "login_code!: LoginCode",
+       (select count(*) from class_student where class_id = class.id) as "student_count!",
+       created_at,
+       updated_at
+from class
+where id = $1
+"#,
+        id.0,
+    )
+    .fetch_optional(db)
+    .await?
+    .map(|row| Class {
+        id: row.id,
+        display_name: row.display_name,
+        owner_id: row.owner_id,
+        account_id: row.account_id,
+        login_code: row.login_code,
+        student_count: row.student_count as u32,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    });
+
+    Ok(class)
+}
+
+pub async fn update(
+    db: &PgPool,
+    id: ClassId,
+    display_name: Option<&str>,
+    reset_login_code: bool,
+) -> sqlx::Result<()> {
+    let mut txn = db.begin().await?;
+
+    let login_code = match reset_login_code {
+        true => Some(generate_login_code(&mut txn).await?),
+        false => None,
+    };
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+update class
+set display_name = coalesce($2, display_name),
+    login_code   = coalesce($3, login_code),
+    updated_at   = now()
+where id = $1
+"#,
+        id.0,
+        display_name,
+        login_code.map(|code| code.0),
+    )
+    .execute(&mut txn)
+    .await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+pub async fn delete(db: &PgPool, id: ClassId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+delete from class
+where id = $1
+"#,
+        id.0,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn list_students(db: &PgPool, class_id: ClassId) -> sqlx::Result<Vec<Student>> {
+    let students = sqlx::query!(
+        //language=SQL
+        r#"
+select id         as "id!: StudentId",
+       class_id   as "class_id!: ClassId",
+       display_name,
+       login_code as "login_code!: LoginCode",
+       picture_password,
+       created_at
+from class_student
+where class_id = $1
+order by lower(display_name)
+"#,
+        class_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| Student {
+        id: row.id,
+        class_id: row.class_id,
+        display_name: row.display_name,
+        login_code: row.login_code,
+        picture_password: PicturePassword(row.picture_password),
+        created_at: row.created_at,
+    })
+    .collect();
+
+    Ok(students)
+}
+
+async fn insert_student(
+    conn: &mut PgConnection,
+    class_id: ClassId,
+    display_name: &str,
+) -> sqlx::Result<Student> {
+    let login_code = generate_login_code(&mut *conn).await?;
+    let picture_password = generate_picture_password();
+
+    let row = sqlx::query!(
+        //language=SQL
+        r#"
+insert into class_student (class_id, display_name, login_code, picture_password)
+values ($1, $2, $3, $4)
+returning id as "id!: StudentId", created_at
+"#,
+        class_id.0,
+        display_name,
+        login_code.0,
+        &picture_password.0,
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    Ok(Student {
+        id: row.id,
+        class_id,
+        display_name: display_name.to_owned(),
+        login_code,
+        picture_password,
+        created_at: row.created_at,
+    })
+}
+
+pub async fn create_student(
+    db: &PgPool,
+    class_id: ClassId,
+    display_name: &str,
+) -> sqlx::Result<Student> {
+    let mut txn = db.begin().await?;
+
+    let student = insert_student(&mut txn, class_id, display_name).await?;
+
+    txn.commit().await?;
+
+    Ok(student)
+}
+
+/// Adds the students in a roster to a class, skipping names which are already in the class.
+///
+/// Returns the added students and the skipped names.
+pub async fn import_students(
+    db: &PgPool,
+    class_id: ClassId,
+    names: Vec<String>,
+) -> sqlx::Result<(Vec<Student>, Vec<String>)> {
+    let mut txn = db.begin().await?;
+
+    let mut existing: Vec<String> = sqlx::query!(
+        //language=SQL
+        r#"
+select lower(display_name) as "name!"
+from class_student
+where class_id = $1
+"#,
+        class_id.0,
+    )
+    .fetch_all(&mut txn)
+    .await?
+    .into_iter()
+    .map(|row| row.name)
+    .collect();
+
+    let mut students = Vec::new();
+    let mut skipped = Vec::new();
+
+    for name in names {
+        let lower = name.to_lowercase();
+
+        if existing.contains(&lower) {
+            skipped.push(name);
+            continue;
+        }
+
+        students.push(insert_student(&mut txn, class_id, &name).await?);
+        existing.push(lower);
+    }
+
+    txn.commit().await?;
+
+    Ok((students, skipped))
+}
+
+/// Updates a student. Returns `false` if the student isn't in the class.
+pub async fn update_student(
+    db: &PgPool,
+    class_id: ClassId,
+    student_id: StudentId,
+    display_name: Option<&str>,
+    reset_login: bool,
+) -> sqlx::Result<bool> {
+    let mut txn = db.begin().await?;
+
+    let (login_code, picture_password) = match reset_login {
+        true => (
+            Some(generate_login_code(&mut txn).await?),
+            Some(generate_picture_password()),
+        ),
+        false => (None, None),
+    };
+
+    let updated = sqlx::query!(
+        //language=SQL
+        r#"
+update class_student
+set display_name     = coalesce($3, display_name),
+    login_code       = coalesce($4, login_code),
+    picture_password = coalesce($5, picture_password),
+    updated_at       = now()
+where class_id = $1
+  and id = $2
+"#,
+        class_id.0,
+        student_id.0,
+        display_name,
+        login_code.map(|code| code.0),
+        picture_password.map(|password| password.0).as_deref(),
+    )
+    .execute(&mut txn)
+    .await?
+    .rows_affected()
+        > 0;
+
+    txn.commit().await?;
+
+    Ok(updated)
+}
+
+/// Removes a student from a class. Returns `false` if the student isn't in the class.
+pub async fn delete_student(
+    db: &PgPool,
+    class_id: ClassId,
+    student_id: StudentId,
+) -> sqlx::Result<bool> {
+    let deleted = sqlx::query!(
+        //language=SQL
+        r#"
+delete from class_student
+where class_id = $1
+  and id = $2
+"#,
+        class_id.0,
+        student_id.0,
+    )
+    .execute(db)
+    .await?
+    .rows_affected()
+        > 0;
+
+    Ok(deleted)
+}
+
+/// Everything stored about a student. Returns `None` if the student isn't in the class.
+pub async fn student_data(
+    db: &PgPool,
+    class_id: ClassId,
+    student_id: StudentId,
+) -> Result<Option<StudentDataResponse>, error::Class> {
+    let student = sqlx::query!(
+        //language=SQL
+        r#"
+select display_name,
+       created_at
+from class_student
+where class_id = $1
+  and id = $2
+"#,
+        class_id.0,
+        student_id.0,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    let student = match student {
+        Some(row) => StudentRecord {
+            id: student_id,
+            class_id,
+            display_name: row.display_name,
+            created_at: row.created_at,
+        },
+        None => return Ok(None),
+    };
+
+    let sessions = sqlx::query!(
+        //language=SQL
+        r#"
+select code as "code!: JigCode",
+       started_at,
+       finished_at,
+       info
+from jig_code_session
+where student_id = $1
+order by started_at
+"#,
+        student_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| {
+        Ok(JigCodeSessionResponse {
+            code: row.code,
+            players_name: Some(student.display_name.clone()),
+            student_id: Some(student_id),
+            started_at: row.started_at,
+            finished_at: row.finished_at,
+            info: row.info.map(serde_json::from_value).transpose()?,
+        })
+    })
+    .collect::<Result<_, serde_json::Error>>()?;
+
+    Ok(Some(StudentDataResponse { student, sessions }))
+}
+
+/// Removes a student from a class along with the JIG code sessions they played. Returns `false` if
+/// the student isn't in the class.
+pub async fn delete_student_data(
+    db: &PgPool,
+    class_id: ClassId,
+    student_id: StudentId,
+) -> sqlx::Result<bool> {
+    let mut txn = db.begin().await?;
+
+    // deleted first, as deleting the student only unlinks their sessions
+    sqlx::query!(
+        //language=SQL
+        r#"
+delete from jig_code_session
+using class_student
+where class_student.id = jig_code_session.student_id
+  and class_student.class_id = $1
+  and class_student.id = $2
+"#,
+        class_id.0,
+        student_id.0,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    let deleted = sqlx::query!(
+        //language=SQL
+        r#"
+delete from class_student
+where class_id = $1
+  and id = $2
+"#,
+        class_id.0,
+        student_id.0,
+    )
+    .execute(&mut txn)
+    .await?
+    .rows_affected()
+        > 0;
+
+    txn.commit().await?;
+
+    Ok(deleted)
+}
+
+fn assignment_asset_id(jig_id: Option<JigId>, playlist_id: Option<PlaylistId>) -> AssetId {
+    match (jig_id, playlist_id) {
+        (Some(jig_id), _) => jig_id.into(),
+        (None, Some(playlist_id)) => playlist_id.into(),
+        // enforced by a check constraint
+        (None, None) => unreachable!("assignment without a jig or playlist"),
+    }
+}
+
+pub async fn list_assignments(
+    db: &PgPool,
+    class_id: ClassId,
+) -> sqlx::Result<Vec<ClassAssignment>> {
+    let assignments = sqlx::query!(
+        //language=SQL
+        r#"
+select id          as "id!: ClassAssignmentId",
+       class_id    as "class_id!: ClassId",
+       jig_id      as "jig_id?: JigId",
+       playlist_id as "playlist_id?: PlaylistId",
+       due_at,
+       created_at,
+       array(
+           select row (jig_id, code)
+           from class_assignment_code
+           where assignment_id = class_assignment.id
+       )           as "codes!: Vec<(JigId, i32)>"
+from class_assignment
+where class_id = $1
+order by due_at nulls last, created_at
+"#,
+        class_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| ClassAssignment {
+        id: row.id,
+        class_id: row.class_id,
+        asset_id: assignment_asset_id(row.jig_id, row.playlist_id),
+        due_at: row.due_at,
+        codes: row
+            .codes
+            .into_iter()
+            .map(|(jig_id, code)| ClassAssignmentCode {
+                jig_id,
+                code: JigCode(code),
+            })
+            .collect(),
+        created_at: row.created_at,
+    })
+    .collect();
+
+    Ok(assignments)
+}
+
+pub async fn create_assignment(
+    db: &PgPool,
+    user_id: UserId,
+    class_id: ClassId,
+    asset_id: AssetId,
+    due_at: Option<DateTime<Utc>>,
+) -> Result<ClassAssignmentId, error::Class> {
+    let (jig_id, playlist_id) = match asset_id {
+        AssetId::JigId(jig_id) => (Some(jig_id.0), None),
+        AssetId::PlaylistId(playlist_id) => (None, Some(playlist_id.0)),
+        AssetId::ResourceId(_) | AssetId::CourseId(_) => {
+            return Err(error::Class::BadRequest(
+                "Only JIGs and Playlists can be assigned".to_owned(),
+            ))
+        }
+    };
+
+    // teachers can assign their own JIGs and Playlists, and ones which are published and shared
+    let access = sqlx::query!(
+        //language=SQL
+        r#"
+select exists(select 1 from jig where id = $1)
+    or exists(select 1 from playlist where id = $2) as "exists!",
+       exists(select 1
+              from jig
+              join jig_data on jig_data.id = jig.live_id
+              where jig.id = $1
+                and (jig.creator_id = $3
+                     or (jig.published_at is not null and jig_data.privacy_level <> $4)))
+    or exists(select 1
+              from playlist
+              join playlist_data on playlist_data.id = playlist.live_id
+              where playlist.id = $2
+                and (playlist.creator_id = $3
+                     or (playlist.published_at is not null and playlist_data.privacy_level <> $4)))
+           as "accessible!"
+"#,
+        jig_id,
+        playlist_id,
+        user_id.0,
+        PrivacyLevel::Private as i16,
+    )
+    .fetch_one(db)
+    .await?;
+
+    if !access.exists {
+        return Err(error::Class::ResourceNotFound);
+    }
+
+    if !access.accessible {
+        return Err(error::Class::Forbidden);
+    }
+
+    let id = sqlx::query!(
+        //language=SQL
+        r#"
+insert into class_assignment (class_id, jig_id, playlist_id, due_at)
+values ($1, $2, $3, $4)
+returning id as "id!: ClassAssignmentId"
+"#,
+        class_id.0,
+        jig_id,
+        playlist_id,
+        due_at,
+    )
+    .fetch_one(db)
+    .await?
+    .id;
+
+    Ok(id)
+}
+
+/// Updates an assignment. Returns `false` if the assignment isn't given to the class.
+pub async fn update_assignment(
+    db: &PgPool,
+    class_id: ClassId,
+    assignment_id: ClassAssignmentId,
+    due_at: UpdateNullable<DateTime<Utc>>,
+) -> sqlx::Result<bool> {
+    // codes already created for the assignment follow its due date
+    let updated = sqlx::query!(
+        //language=SQL
+        r#"
+with updated as (
+    update class_assignment
+    set due_at     = case when $3 then $4 else due_at end,
+        updated_at = now()
+    where class_id = $1
+      and id = $2
+    returning id, due_at
+),
+codes as (
+    update jig_code
+    set due_at = updated.due_at
+    from updated
+    join class_assignment_code on class_assignment_code.assignment_id = updated.id
+    where jig_code.code = class_assignment_code.code
+      and $3
+)
+select exists(select 1 from updated) as "updated!"
+"#,
+        class_id.0,
+        assignment_id.0,
+        !due_at.is_keep(),
+        due_at.into_option(),
+    )
+    .fetch_one(db)
+    .await?
+    .updated;
+
+    Ok(updated)
+}
+
+/// Removes an assignment from a class. Returns `false` if the assignment isn't given to the class.
+///
+/// The codes created for the assignment are kept so that the sessions recorded under them can
+/// still be reviewed.
+pub async fn delete_assignment(
+    db: &PgPool,
+    class_id: ClassId,
+    assignment_id: ClassAssignmentId,
+) -> sqlx::Result<bool> {
+    let deleted = sqlx::query!(
+        //language=SQL
+        r#"
+delete from class_assignment
+where class_id = $1
+  and id = $2
+"#,
+        class_id.0,
+        assignment_id.0,
+    )
+    .execute(db)
+    .await?
+    .rows_affected()
+        > 0;
+
+    Ok(deleted)
+}
+
+/// Finds the class with the login code, and the names its students pick from on the login screen,
+/// which are shortened by [`child_privacy::roster_names`].
+pub async fn roster_by_login_code(
+    db: &PgPool,
+    code: &LoginCode,
+) -> sqlx::Result<Option<ClassLoginRosterResponse>> {
+    let class = sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id!: ClassId", display_name
+from class
+where login_code = $1
+"#,
+        code.0,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    let class = match class {
+        Some(class) => class,
+        None => return Ok(None),
+    };
+
+    let students = sqlx::query_as!(
+        StudentProfile,
+        //language=SQL
+        r#"
+select id as "id!: StudentId", display_name
+from class_student
+where class_id = $1
+order by lower(display_name)
+"#,
+        class.id.0,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let names: Vec<String> = students
+        .iter()
+        .map(|student| student.display_name.clone())
+        .collect();
+
+    let students = students
+        .into_iter()
+        .zip(child_privacy::roster_names(&names))
+        .map(|(student, display_name)| StudentProfile {
+            id: student.id,
+            display_name,
+        })
+        .collect();
+
+    Ok(Some(ClassLoginRosterResponse {
+        display_name: class.display_name,
+        students,
+    }))
+}
+
+/// A student who logged in, along with their class.
+pub struct StudentLogin {
+    pub student: StudentProfile,
+    pub class_id: ClassId,
+    pub class_name: String,
+}
+
+/// Finds the student matching the login request, if any.
+pub async fn login(db: &PgPool, req: &StudentLoginRequest) -> sqlx::Result<Option<StudentLogin>> {
+    let (student_code, class_code, student_id, picture_password) = match req {
+        StudentLoginRequest::Code { code } => (Some(&code.0), None, None, None),
+        StudentLoginRequest::Picture {
+            class_code,
+            student_id,
+            picture_password,
+        } => (
+            None,
+            Some(&class_code.0),
+            Some(student_id.0),
+            Some(&picture_password.0),
+        ),
+    };
+
+    let login = sqlx::query!(
+        //language=SQL
+        r#"
+select class_student.id           as "id!: StudentId",
+       class_student.display_name,
+       class.id                   as "class_id!: ClassId",
+       class.display_name         as "class_name"
+from class_student
+join class on class.id = class_student.class_id
+where ($1::text is not null and class_student.login_code = $1)
+   or ($2::text is not null
+       and class.login_code = $2
+       and class_student.id = $3
+       and class_student.picture_password = $4)
+"#,
+        student_code.map(String::as_str),
+        class_code.map(String::as_str),
+        student_id,
+        picture_password.map(Vec::as_slice),
+    )
+    .fetch_optional(db)
+    .await?
+    .map(|row| StudentLogin {
+        student: StudentProfile {
+            id: row.id,
+            display_name: row.display_name,
+        },
+        class_id: row.class_id,
+        class_name: row.class_name,
+    });
+
+    Ok(login)
+}
+
+/// How long failed logins count towards the limits below.
+const LOGIN_FAILURE_WINDOW_MINUTES: i32 = 15;
+
+/// How many failed logins and roster lookups an IP address can make within the window. Only
+/// failures count, so a classroom sharing an IP address can still log in.
+const MAX_LOGIN_FAILURES_PER_IP: i64 = 30;
+
+/// How many wrong picture passwords a student can be tried with within the window before they're
+/// locked out.
+const MAX_LOGIN_FAILURES_PER_STUDENT: i64 = 5;
+
+/// Whether logins from `ip_address`, or of the student, are throttled after too many failures.
+pub async fn login_throttled(
+    db: &PgPool,
+    ip_address: Option<&str>,
+    student_id: Option<StudentId>,
+) -> sqlx::Result<bool> {
+    let failures = sqlx::query!(
+        //language=SQL
+        r#"
+select count(*) filter (where ip_address = $1)  as "ip!",
+       count(*) filter (where student_id = $2) as "student!"
+from class_login_failure
+where failed_at > now() - make_interval(mins => $3)
+  and (ip_address = $1 or student_id = $2)
+"#,
+        ip_address,
+        student_id.map(|id| id.0),
+        LOGIN_FAILURE_WINDOW_MINUTES,
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok(failures.ip >= MAX_LOGIN_FAILURES_PER_IP
+        || failures.student >= MAX_LOGIN_FAILURES_PER_STUDENT)
+}
+
+/// Records a failed login or roster lookup, and forgets failures which no longer count.
+pub async fn record_login_failure(
+    db: &PgPool,
+    ip_address: Option<&str>,
+    student_id: Option<StudentId>,
+) -> sqlx::Result<()> {
+    let mut txn = db.begin().await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+delete from class_login_failure
+where failed_at <= now() - make_interval(mins => $1)
+"#,
+        LOGIN_FAILURE_WINDOW_MINUTES,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into class_login_failure (ip_address, student_id)
+values ($1, (select id from class_student where id = $2))
+"#,
+        ip_address,
+        student_id.map(|id| id.0),
+    )
+    .execute(&mut txn)
+    .await?;
+
+    txn.commit().await
+}
+
+/// Forgets a student's failed logins once they've logged in.
+pub async fn clear_login_failures(db: &PgPool, student_id: StudentId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+delete from class_login_failure
+where student_id = $1
+"#,
+        student_id.0,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Lists the assignments of the student's class, with the JIGs the student has finished.
+pub async fn student_assignments(
+    db: &PgPool,
+    student_id: StudentId,
+) -> sqlx::Result<Vec<StudentAssignment>> {
+    let assignments = sqlx::query!(
+        //language=SQL
+        r#"
+select class_assignment.id          as "id!: ClassAssignmentId",
+       class_assignment.class_id    as "class_id!: ClassId",
+       class_assignment.jig_id      as "jig_id?: JigId",
+       class_assignment.playlist_id as "playlist_id?: PlaylistId",
+       class_assignment.due_at,
+       class_assignment.created_at,
+       coalesce(
+           (select display_name
+            from jig_data
+            join jig on jig.live_id = jig_data.id
+            where jig.id = class_assignment.jig_id),
+           (select display_name
+            from playlist_data
+            join playlist on playlist.live_id = playlist_data.id
+            where playlist.id = class_assignment.playlist_id),
+           ''
+       )                            as "display_name!",
+       array(
+           select row (jig.id, jig_data.display_name, exists(
+               select 1
+               from class_assignment_code
+               join jig_code_session using (code)
+               where class_assignment_code.assignment_id = class_assignment.id
+                 and class_assignment_code.jig_id = jig.id
+                 and jig_code_session.student_id = class_student.id
+                 and jig_code_session.finished_at is not null
+           ))
+           from jig
+           join jig_data on jig_data.id = jig.live_id
+           left join playlist_data_jig
+               on playlist_data_jig.jig_id = jig.id
+              and playlist_data_jig.playlist_data_id = (
+                  select live_id from playlist where playlist.id = class_assignment.playlist_id
+              )
+           where jig.id = class_assignment.jig_id
+              or playlist_data_jig.playlist_data_id is not null
+           order by playlist_data_jig.index
+       )                            as "jigs!: Vec<(JigId, String, bool)>",
+       array(
+           select row (class_assignment_code.jig_id, class_assignment_code.code)
+           from class_assignment_code
+           where class_assignment_code.assignment_id = class_assignment.id
+       )                            as "codes!: Vec<(JigId, i32)>"
+from class_student
+join class_assignment on class_assignment.class_id = class_student.class_id
+where class_student.id = $1
+order by class_assignment.due_at nulls last, class_assignment.created_at
+"#,
+        student_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| StudentAssignment {
+        assignment: ClassAssignment {
+            id: row.id,
+            class_id: row.class_id,
+            asset_id: assignment_asset_id(row.jig_id, row.playlist_id),
+            due_at: row.due_at,
+            codes: row
+                .codes
+                .into_iter()
+                .map(|(jig_id, code)| ClassAssignmentCode {
+                    jig_id,
+                    code: JigCode(code),
+                })
+                .collect(),
+            created_at: row.created_at,
+        },
+        display_name: row.display_name,
+        jigs: row
+            .jigs
+            .into_iter()
+            .map(|(jig_id, display_name, completed)| StudentAssignmentJig {
+                jig_id,
+                display_name,
+                completed,
+            })
+            .collect(),
+    })
+    .collect();
+
+    Ok(assignments)
+}
+
+/// Gets the code sessions of an assignment's JIG are recorded under, creating it on first play.
+///
+/// The code is owned by the class's teacher and named after the class, so that it shows up with
+/// the teacher's other codes. Returns `None` if the assignment isn't given to the student's class
+/// or the JIG isn't part of it.
+pub async fn assignment_jig_code(
+    db: &PgPool,
+    student_id: StudentId,
+    assignment_id: ClassAssignmentId,
+    jig_id: JigId,
+) -> Result<Option<JigCode>, error::Class> {
+    let assignment = sqlx::query!(
+        //language=SQL
+        r#"
+select class.owner_id   as "owner_id!: UserId",
+       class.display_name,
+       class_assignment.due_at,
+       (select code
+        from class_assignment_code
+        where assignment_id = class_assignment.id
+          and jig_id = $3) as "code?",
+       (select row (direction, scoring, drag_assist)
+        from jig_data
+        join jig on jig.live_id = jig_data.id
+        where jig.id = $3) as "settings?: (TextDirection, bool, bool)"
+from class_student
+join class on class.id = class_student.class_id
+join class_assignment on class_assignment.class_id = class.id
+where class_student.id = $1
+  and class_assignment.id = $2
+  and (class_assignment.jig_id = $3
+    or exists(
+        select 1
+        from playlist_data_jig
+        join playlist on playlist.live_id = playlist_data_jig.playlist_data_id
+        where playlist.id = class_assignment.playlist_id
+          and playlist_data_jig.jig_id = $3
+    ))
+"#,
+        student_id.0,
+        assignment_id.0,
+        jig_id.0,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    let assignment = match assignment {
+        Some(assignment) => assignment,
+        None => return Ok(None),
+    };
+
+    if let Some(code) = assignment.code {
+        return Ok(Some(JigCode(code)));
+    }
+
+    let (direction, scoring, drag_assist) =
+        assignment.settings.ok_or(error::Class::ResourceNotFound)?;
+
+    let code = db::jig::codes::create(
+        db,
+        assignment.owner_id,
+        &JigPlayerSessionCreateRequest {
+            jig_id,
+            name: Some(assignment.display_name),
+            settings: JigPlayerSettings {
+                direction,
+                scoring,
+                drag_assist,
+                due_at: assignment.due_at,
+                ..Default::default()
+            },
+        },
+    )
+    .await?
+    .index;
+
+    // another student may have played first, in which case their code is used
+    let assignment_code = sqlx::query!(
+        //language=SQL
+        r#"
+with inserted as (
+    insert into class_assignment_code (assignment_id, jig_id, code)
+    values ($1, $2, $3)
+    on conflict (assignment_id, jig_id) do nothing
+    returning code
+)
+select code as "code!" from inserted
+union all
+select code from class_assignment_code where assignment_id = $1 and jig_id = $2
+limit 1
+"#,
+        assignment_id.0,
+        jig_id.0,
+        code.0,
+    )
+    .fetch_one(db)
+    .await?
+    .code;
+
+    if assignment_code != code.0 {
+        sqlx::query!(
+            //language=SQL
+            "delete from jig_code where code = $1",
+            code.0
+        )
+        .execute(db)
+        .await?;
+    }
+
+    Ok(Some(JigCode(assignment_code)))
+}
@@ -1,4 +1,4 @@
-use crate::translate::translate_text;
+use crate::translate::TranslationProvider;
 use anyhow::Context;
 use serde_json::value::Value;
 use shared::domain::course::{CourseAdminData, CourseRating, CourseUpdateAdminDataRequest};
@@ -23,6 +23,7 @@ use crate::error;
 
 pub(crate) mod additional_resource;
 pub(crate) mod module;
+pub(crate) mod progress;
 pub(crate) mod unit;
 
 pub async fn create(
@@ -610,7 +611,7 @@ limit $6
 
 pub async fn update_draft(
     pool: &PgPool,
-    api_key: &Option<String>,
+    translator: Option<&dyn TranslationProvider>,
     id: CourseId,
     display_name: Option<&str>,
     categories: Option<&[CategoryId]>,
@@ -665,10 +666,13 @@ where id = $1 and $2 is distinct from description"#,
     }
 
     if let Some(other_keywords) = other_keywords {
-        let translate_text = match &api_key {
-            Some(key) => translate_text(&other_keywords, "he", "en", key)
-                .await
-                .context("could not translate text")?,
+        let translate_text = match translator {
+            Some(translator) => Some(
+                translator
+                    .translate(&other_keywords, "en", "iw")
+                    .await
+                    .context("could not translate text")?,
+            ),
             None => None,
         };
 
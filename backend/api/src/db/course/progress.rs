@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use shared::domain::{
+    asset::{DraftOrLive, ProgressStatus, ProgressSummary},
+    course::{
+        progress::{CourseInProgress, CourseProgressResponse, CourseUnitProgress},
+        unit::CourseUnitId,
+        CourseId,
+    },
+    user::UserId,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db;
+
+/// Whether the unit belongs to either the draft or live data of the course.
+pub async fn unit_in_course(
+    db: &PgPool,
+    course_id: CourseId,
+    unit_id: CourseUnitId,
+) -> sqlx::Result<bool> {
+    let exists = sqlx::query!(
+        //language=SQL
+        r#"
+select exists (
+    select 1
+    from course_data_unit
+    join course on course_data_id in (course.draft_id, course.live_id)
+    where course.id = $1
+      and unit_id = $2
+) as "exists!"
+"#,
+        course_id.0,
+        unit_id.0,
+    )
+    .fetch_one(db)
+    .await?
+    .exists;
+
+    Ok(exists)
+}
+
+/// Records progress on a unit. A unit never goes back from completed to started.
+pub async fn upsert_unit(
+    db: &PgPool,
+    user_id: UserId,
+    course_id: CourseId,
+    unit_id: CourseUnitId,
+    status: ProgressStatus,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into course_unit_progress (user_id, course_id, unit_id, status, completed_at)
+values ($1, $2, $3, $4, case when $4 = 1::int2 then now() end)
+on conflict (user_id, course_id, unit_id) do update
+set status       = greatest(course_unit_progress.status, excluded.status),
+    completed_at = coalesce(course_unit_progress.completed_at, excluded.completed_at),
+    updated_at   = now()
+"#,
+        user_id.0,
+        course_id.0,
+        unit_id.0,
+        status as i16,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Gets the user's progress measured against the live units of the course.
+pub async fn get(
+    db: &PgPool,
+    user_id: UserId,
+    course_id: CourseId,
+) -> sqlx::Result<Option<CourseProgressResponse>> {
+    Ok(get_many(db, user_id, &[course_id])
+        .await?
+        .remove(&course_id))
+}
+
+/// Lists the courses the user has made progress in, most recently played first.
+pub async fn list_in_progress(
+    db: &PgPool,
+    user_id: UserId,
+    include_completed: bool,
+    page: u32,
+    page_limit: u32,
+) -> sqlx::Result<Vec<CourseInProgress>> {
+    let ids: Vec<CourseId> = sqlx::query!(
+        //language=SQL
+        r#"
+select progress.course_id as "course_id!: CourseId"
+from course_unit_progress "progress"
+join course on course.id = progress.course_id
+where progress.user_id = $1
+group by progress.course_id, course.live_id
+having $2 or count(*) filter (
+    where progress.status = 1
+      and progress.unit_id in (select unit_id from course_data_unit where course_data_id = course.live_id)
+) < (select count(*) from course_data_unit where course_data_id = course.live_id)
+order by max(progress.updated_at) desc
+offset $3
+limit $4
+"#,
+        user_id.0,
+        include_completed,
+        (page * page_limit) as i32,
+        page_limit as i32,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| row.course_id)
+    .collect();
+
+    let uuids: Vec<Uuid> = ids.iter().map(|id| id.0).collect();
+
+    let mut courses: HashMap<CourseId, _> = db::course::get_by_ids(db, &uuids, DraftOrLive::Live)
+        .await?
+        .into_iter()
+        .map(|course| (course.id, course))
+        .collect();
+
+    let mut progress = get_many(db, user_id, &ids).await?;
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| {
+            Some(CourseInProgress {
+                course: courses.remove(id)?,
+                progress: progress.remove(id)?,
+            })
+        })
+        .collect())
+}
+
+/// Gets the user's progress through each of the courses which exist, measured against their
+/// live units.
+async fn get_many(
+    db: &PgPool,
+    user_id: UserId,
+    ids: &[CourseId],
+) -> sqlx::Result<HashMap<CourseId, CourseProgressResponse>> {
+    let uuids: Vec<Uuid> = ids.iter().map(|id| id.0).collect();
+
+    let mut live_units: HashMap<CourseId, Vec<CourseUnitId>> = HashMap::new();
+
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select course.id as "course_id!: CourseId",
+       unit_id   as "unit_id?: CourseUnitId"
+from course
+left join course_data_unit on course_data_unit.course_data_id = course.live_id
+where course.id = any($1)
+order by course.id, course_data_unit.index
+"#,
+        &uuids,
+    )
+    .fetch_all(db)
+    .await?;
+
+    for row in rows {
+        let units = live_units.entry(row.course_id).or_default();
+        units.extend(row.unit_id);
+    }
+
+    let mut progress: HashMap<CourseId, Vec<CourseUnitProgress>> = HashMap::new();
+
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select course_id    as "course_id!: CourseId",
+       unit_id      as "unit_id!: CourseUnitId",
+       status       as "status!: ProgressStatus",
+       started_at,
+       completed_at,
+       updated_at
+from course_unit_progress
+where user_id = $1
+  and course_id = any($2)
+"#,
+        user_id.0,
+        &uuids,
+    )
+    .fetch_all(db)
+    .await?;
+
+    for row in rows {
+        progress
+            .entry(row.course_id)
+            .or_default()
+            .push(CourseUnitProgress {
+                unit_id: row.unit_id,
+                status: row.status,
+                started_at: row.started_at,
+                completed_at: row.completed_at,
+                updated_at: row.updated_at,
+            });
+    }
+
+    Ok(live_units
+        .into_iter()
+        .map(|(course_id, unit_ids)| {
+            let summary =
+                ProgressSummary::new(&unit_ids, progress.remove(&course_id).unwrap_or_default());
+
+            let response = CourseProgressResponse {
+                course_id,
+                units: summary.items,
+                unit_count: summary.item_count,
+                completed_count: summary.completed_count,
+                percent_complete: summary.percent_complete,
+                continue_unit_id: summary.continue_from,
+                last_played_at: summary.last_played_at,
+            };
+
+            (course_id, response)
+        })
+        .collect())
+}
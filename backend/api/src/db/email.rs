@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use shared::domain::{
+    email::{
+        EmailOutboxEntry, EmailOutboxId, EmailOutboxStatus, EmailTemplateKind, ListEmailOutboxQuery,
+    },
+    ItemCount,
+};
+use sqlx::{PgConnection, PgPool};
+
+/// Name of the locale bundle holding the strings emails are rendered from.
+const EMAIL_BUNDLE: &str = "email";
+
+/// How many times an email is tried before it's marked as failed.
+pub const MAX_ATTEMPTS: i16 = 5;
+
+/// Approved strings for a template, as `(subject, body)`.
+pub struct LocalizedStrings {
+    /// The English strings, which are used when a template hasn't been translated.
+    pub english: (Option<String>, Option<String>),
+    /// The strings in the requested locale.
+    pub localized: (Option<String>, Option<String>),
+    /// Other strings of the template by their item kind, as `(english, localized)`.
+    pub parts: HashMap<String, (Option<String>, Option<String>)>,
+}
+
+/// Gets a template's strings in `locale` from the `email` locale bundle.
+///
+/// Entries are found by their section, which is the template's key, and their item kind, which
+/// is `subject`, `body` or the key of another part of the template, like a line of the
+/// notification digest.
+pub async fn template_strings(
+    db: &mut PgConnection,
+    template: EmailTemplateKind,
+    locale: &str,
+) -> sqlx::Result<LocalizedStrings> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select locale_item_kind.display_name as "part!",
+       translations ->> 'en' as english,
+       translations ->> $3 as localized
+from locale_entry
+         inner join locale_bundle on locale_bundle.id = locale_entry.bundle_id
+         inner join locale_item_kind on locale_item_kind.id = locale_entry.item_kind_id
+where locale_bundle.display_name = $1
+  and section = $2
+  and status = 0
+order by locale_entry.id
+"#,
+        EMAIL_BUNDLE,
+        template.as_str(),
+        locale,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut strings = LocalizedStrings {
+        english: (None, None),
+        localized: (None, None),
+        parts: HashMap::new(),
+    };
+
+    for row in rows {
+        let (english, localized) = match row.part.as_str() {
+            "subject" => (&mut strings.english.0, &mut strings.localized.0),
+            "body" => (&mut strings.english.1, &mut strings.localized.1),
+            _ => {
+                let (english, localized) = strings.parts.entry(row.part).or_default();
+                (english, localized)
+            }
+        };
+
+        *english = english.take().or(row.english);
+        *localized = localized.take().or(row.localized);
+    }
+
+    Ok(strings)
+}
+
+pub async fn insert(
+    db: &mut PgConnection,
+    template: EmailTemplateKind,
+    to_email: &str,
+    locale: &str,
+    subject: &str,
+    data: serde_json::Value,
+) -> sqlx::Result<EmailOutboxId> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into email_outbox (template, to_email, locale, subject, data)
+values ($1, $2, $3, $4, $5)
+returning id as "id!: EmailOutboxId"
+"#,
+        template as i16,
+        to_email,
+        locale,
+        subject,
+        data,
+    )
+    .fetch_one(db)
+    .await
+    .map(|it| it.id)
+}
+
+/// Records a delivery attempt, `error` being `None` if it succeeded.
+///
+/// Failed emails are retried with an exponential backoff until they've been tried [`MAX_ATTEMPTS`]
+/// times. The template data of sent emails is cleared since it can hold login links.
+pub async fn record_attempt(
+    db: &mut PgConnection,
+    id: EmailOutboxId,
+    error: Option<&str>,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update email_outbox
+set attempts        = attempts + 1,
+    status          = case
+                          when $2::text is null then 1
+                          when attempts + 1 >= $3 then 2
+                          else 0
+        end::smallint,
+    last_error      = coalesce($2, last_error),
+    next_attempt_at = now() + interval '1 minute' * power(2, attempts),
+    sent_at         = case when $2::text is null then now() end,
+    data            = case when $2::text is null then null else data end
+where id = $1
+"#,
+        id as EmailOutboxId,
+        error,
+        MAX_ATTEMPTS,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+pub struct PendingEmail {
+    pub id: EmailOutboxId,
+    pub to_email: String,
+    pub locale: String,
+    pub data: serde_json::Value,
+}
+
+/// Finds up to `limit` emails which are due to be retried.
+pub async fn due(pool: &PgPool, limit: i64) -> sqlx::Result<Vec<EmailOutboxId>> {
+    sqlx::query_scalar!(
+        //language=SQL
+        r#"
+select id as "id!: EmailOutboxId"
+from email_outbox
+where status = 0
+  and next_attempt_at <= now()
+  and data is not null
+order by next_attempt_at
+limit $1
+"#,
+        limit,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Locks an email which hasn't been sent yet. Returns `None` if it's been sent, has failed for
+/// good or is locked by someone else sending it.
+pub async fn lock_unsent(
+    db: &mut PgConnection,
+    id: EmailOutboxId,
+) -> sqlx::Result<Option<PendingEmail>> {
+    sqlx::query_as!(
+        PendingEmail,
+        //language=SQL
+        r#"
+select id as "id!: EmailOutboxId",
+       to_email,
+       locale,
+       data as "data!"
+from email_outbox
+where id = $1
+  and status = 0
+  and data is not null
+for update skip locked
+"#,
+        id as EmailOutboxId,
+    )
+    .fetch_optional(db)
+    .await
+}
+
+pub async fn list(
+    pool: &PgPool,
+    query: &ListEmailOutboxQuery,
+) -> sqlx::Result<Vec<EmailOutboxEntry>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id!: EmailOutboxId",
+       template as "template!: EmailTemplateKind",
+       to_email,
+       locale,
+       subject,
+       status as "status!: EmailOutboxStatus",
+       attempts,
+       last_error,
+       created_at,
+       sent_at
+from email_outbox
+where ($1::smallint is null or status = $1)
+  and ($2::text is null or to_email ilike $2)
+order by created_at desc
+limit $3
+offset $4
+"#,
+        query.status.map(|status| status as i16),
+        query.to_email,
+        i64::from(query.page_limit),
+        query.page_limit.offset(query.page),
+    )
+    .fetch_all(pool)
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .map(|row| EmailOutboxEntry {
+                id: row.id,
+                template: row.template,
+                to_email: row.to_email,
+                locale: row.locale,
+                subject: row.subject,
+                status: row.status,
+                attempts: row.attempts as u16,
+                last_error: row.last_error,
+                created_at: row.created_at,
+                sent_at: row.sent_at,
+            })
+            .collect()
+    })
+}
+
+pub async fn count(pool: &PgPool, query: &ListEmailOutboxQuery) -> sqlx::Result<ItemCount> {
+    let count = sqlx::query_scalar!(
+        //language=SQL
+        r#"
+select count(*) as "count!"
+from email_outbox
+where ($1::smallint is null or status = $1)
+  and ($2::text is null or to_email ilike $2)
+"#,
+        query.status.map(|status| status as i16),
+        query.to_email,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((count as usize).into())
+}
+
+/// Queues an email which hasn't been sent to be tried again. Returns `false` if there's no such
+/// email.
+pub async fn retry(pool: &PgPool, id: EmailOutboxId) -> sqlx::Result<bool> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update email_outbox
+set status          = 0,
+    attempts        = 0,
+    next_attempt_at = now()
+where id = $1
+  and status <> 1
+"#,
+        id as EmailOutboxId,
+    )
+    .execute(pool)
+    .await
+    .map(|res| res.rows_affected() > 0)
+}
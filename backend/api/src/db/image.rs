@@ -9,6 +9,7 @@ use shared::domain::{
 use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
+pub mod bulk;
 pub mod recent;
 pub mod tag;
 pub mod user;
@@ -0,0 +1,499 @@
+use chrono::{DateTime, Utc};
+use shared::domain::{
+    image::{
+        bulk::{
+            ImageBulkChanges, ImageBulkListQuery, ImageBulkQuery, ImageBulkUpdate,
+            ImageBulkUpdateId,
+        },
+        ImageId,
+    },
+    meta::ImageTagIndex,
+    user::UserId,
+    ItemCount,
+};
+use sqlx::{types::Json, PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::db::Metadata;
+
+/// Finds and locks every image matching `query`.
+pub async fn select(conn: &mut PgConnection, query: &ImageBulkQuery) -> sqlx::Result<Vec<ImageId>> {
+    let ids: Vec<Uuid> = query.ids.iter().map(|id| id.0).collect();
+    let styles: Vec<Uuid> = query.styles.iter().map(|id| id.0).collect();
+    let age_ranges: Vec<Uuid> = query.age_ranges.iter().map(|id| id.0).collect();
+    let affiliations: Vec<Uuid> = query.affiliations.iter().map(|id| id.0).collect();
+    let categories: Vec<Uuid> = query.categories.iter().map(|id| id.0).collect();
+    let tags: Vec<i16> = query.tags.iter().map(|it| it.0).collect();
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id!: ImageId"
+from image_metadata
+where (cardinality($1::uuid[]) = 0 or id = any($1))
+  and ($2::text is null or name ilike '%' || $2 || '%')
+  and ($3::bool is null or coalesce(publish_at <= now(), false) = $3)
+  and ($4::bool is null or is_premium = $4)
+  and ($5::int2 is null or size = $5)
+  and (cardinality($6::uuid[]) = 0 or exists(select 1 from image_style where image_id = id and style_id = any($6)))
+  and (cardinality($7::uuid[]) = 0 or exists(select 1 from image_age_range where image_id = id and age_range_id = any($7)))
+  and (cardinality($8::uuid[]) = 0 or exists(select 1 from image_affiliation where image_id = id and affiliation_id = any($8)))
+  and (cardinality($9::uuid[]) = 0 or exists(select 1 from image_category where image_id = id and category_id = any($9)))
+  and (cardinality($10::int2[]) = 0 or exists(select 1 from image_tag_join where image_id = id and tag_index = any($10)))
+order by id
+for update
+"#,
+        &ids,
+        query.q.as_deref(),
+        query.is_published,
+        query.is_premium,
+        query.size.map(|it| it as i16),
+        &styles,
+        &age_ranges,
+        &affiliations,
+        &categories,
+        &tags,
+    )
+    .fetch_all(conn)
+    .await
+    .map(|rows| rows.into_iter().map(|row| row.id).collect())
+}
+
+/// Records a bulk update of `images`, along with what each of them looks like now.
+///
+/// `publish_at` is the time [`ImageBulkChanges::publish_at`] resolves to.
+pub async fn create(
+    conn: &mut PgConnection,
+    created_by: UserId,
+    changes: &ImageBulkChanges,
+    publish_at: Option<DateTime<Utc>>,
+    images: &[ImageId],
+) -> anyhow::Result<ImageBulkUpdateId> {
+    let ids: Vec<Uuid> = images.iter().map(|id| id.0).collect();
+
+    let id = sqlx::query!(
+        r#"
+insert into image_bulk_update (created_by, changes, publish_at)
+values ($1, $2, $3)
+returning id as "id!: ImageBulkUpdateId"
+"#,
+        created_by.0,
+        serde_json::to_value(changes)?,
+        publish_at,
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .id;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into image_bulk_update_image (bulk_update_id, image_id, styles, tags, age_ranges, affiliations, is_premium, publish_at)
+select $1,
+       id,
+       array(select style_id from image_style where image_id = id),
+       array(select tag_index from image_tag_join where image_id = id),
+       array(select age_range_id from image_age_range where image_id = id),
+       array(select affiliation_id from image_affiliation where image_id = id),
+       is_premium,
+       publish_at
+from image_metadata
+where id = any($2)
+"#,
+        id.0,
+        &ids,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(id)
+}
+
+/// Applies `changes` to every image in `images` and queues them to be reindexed.
+pub async fn apply(
+    conn: &mut PgConnection,
+    images: &[ImageId],
+    changes: &ImageBulkChanges,
+    publish_at: Option<Option<DateTime<Utc>>>,
+) -> sqlx::Result<()> {
+    let ids: Vec<Uuid> = images.iter().map(|id| id.0).collect();
+
+    remove_metadata(&mut *conn, &ids, &changes.remove_styles).await?;
+    add_metadata(&mut *conn, &ids, &changes.add_styles).await?;
+    remove_metadata(&mut *conn, &ids, &changes.remove_age_ranges).await?;
+    add_metadata(&mut *conn, &ids, &changes.add_age_ranges).await?;
+    remove_metadata(&mut *conn, &ids, &changes.remove_affiliations).await?;
+    add_metadata(&mut *conn, &ids, &changes.add_affiliations).await?;
+    remove_tags(&mut *conn, &ids, &changes.remove_tags).await?;
+    add_tags(&mut *conn, &ids, &changes.add_tags).await?;
+
+    if let Some(is_premium) = changes.is_premium {
+        sqlx::query!(
+            "update image_metadata set is_premium = $2 where id = any($1) and is_premium is distinct from $2",
+            &ids,
+            is_premium,
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    if let Some(publish_at) = publish_at {
+        sqlx::query!(
+            "update image_metadata set publish_at = $2 where id = any($1) and publish_at is distinct from $2",
+            &ids,
+            publish_at,
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    sqlx::query!(
+        "update image_metadata set last_synced_at = null where id = any($1)",
+        &ids,
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Locks a bulk update, returning its changes and when it was undone.
+pub async fn get_for_undo(
+    conn: &mut PgConnection,
+    id: ImageBulkUpdateId,
+) -> sqlx::Result<Option<(ImageBulkChanges, Option<DateTime<Utc>>)>> {
+    sqlx::query!(
+        r#"
+select changes as "changes!: Json<ImageBulkChanges>",
+       undone_at
+from image_bulk_update
+where id = $1
+for update
+"#,
+        id.0,
+    )
+    .fetch_optional(conn)
+    .await
+    .map(|it| it.map(|row| (row.changes.0, row.undone_at)))
+}
+
+/// Reverts what a bulk update changed, leaving everything else about its images alone.
+///
+/// `is_premium` and `publish_at` are only restored for images which still have the value the bulk
+/// update gave them, so that later edits aren't overwritten.
+///
+/// Returns how many images were restored.
+pub async fn undo(
+    conn: &mut PgConnection,
+    id: ImageBulkUpdateId,
+    changes: &ImageBulkChanges,
+) -> sqlx::Result<u64> {
+    restore_removed_metadata(&mut *conn, id, "styles", &changes.remove_styles).await?;
+    undo_added_metadata(&mut *conn, id, "styles", &changes.add_styles).await?;
+    restore_removed_metadata(&mut *conn, id, "age_ranges", &changes.remove_age_ranges).await?;
+    undo_added_metadata(&mut *conn, id, "age_ranges", &changes.add_age_ranges).await?;
+    restore_removed_metadata(&mut *conn, id, "affiliations", &changes.remove_affiliations).await?;
+    undo_added_metadata(&mut *conn, id, "affiliations", &changes.add_affiliations).await?;
+
+    let removed_tags: Vec<i16> = changes.remove_tags.iter().map(|it| it.0).collect();
+    let added_tags: Vec<i16> = changes.add_tags.iter().map(|it| it.0).collect();
+
+    if !removed_tags.is_empty() {
+        sqlx::query!(
+            //language=SQL
+            r#"
+insert into image_tag_join (image_id, tag_index)
+select distinct b.image_id, tag.index
+from image_bulk_update_image b
+inner join image_tag tag on tag.index = any($2::int2[]) and tag.index = any(b.tags)
+where b.bulk_update_id = $1
+  and not exists(select 1 from image_tag_join j where j.image_id = b.image_id and j.tag_index = tag.index)
+"#,
+            id.0,
+            &removed_tags,
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    if !added_tags.is_empty() {
+        sqlx::query!(
+            r#"
+delete from image_tag_join j
+using image_bulk_update_image b
+where b.bulk_update_id = $1
+  and j.image_id = b.image_id
+  and j.tag_index = any($2::int2[])
+  and not (j.tag_index = any(b.tags))
+"#,
+            id.0,
+            &added_tags,
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    if let Some(is_premium) = changes.is_premium {
+        sqlx::query!(
+            r#"
+update image_metadata
+set is_premium = b.is_premium
+from image_bulk_update_image b
+where b.bulk_update_id = $1
+  and b.image_id = image_metadata.id
+  and image_metadata.is_premium = $2
+  and image_metadata.is_premium is distinct from b.is_premium
+"#,
+            id.0,
+            is_premium,
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    if changes.publish_at.is_some() {
+        sqlx::query!(
+            r#"
+update image_metadata
+set publish_at = b.publish_at
+from image_bulk_update_image b
+inner join image_bulk_update u on u.id = b.bulk_update_id
+where b.bulk_update_id = $1
+  and b.image_id = image_metadata.id
+  and image_metadata.publish_at is not distinct from u.publish_at
+  and image_metadata.publish_at is distinct from b.publish_at
+"#,
+            id.0,
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    let restored = sqlx::query!(
+        r#"
+update image_metadata
+set last_synced_at = null
+from image_bulk_update_image b
+where b.bulk_update_id = $1
+  and b.image_id = image_metadata.id
+"#,
+        id.0,
+    )
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    sqlx::query!(
+        "update image_bulk_update set undone_at = now() where id = $1",
+        id.0
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(restored)
+}
+
+pub async fn list(db: &PgPool, query: &ImageBulkListQuery) -> sqlx::Result<Vec<ImageBulkUpdate>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id!: ImageBulkUpdateId",
+       created_by as "created_by: UserId",
+       changes as "changes!: Json<ImageBulkChanges>",
+       (select count(*) from image_bulk_update_image where bulk_update_id = id) as "image_count!",
+       created_at,
+       undone_at
+from image_bulk_update
+order by created_at desc
+limit $1
+offset $2
+"#,
+        i64::from(query.page_limit),
+        query.page_limit.offset(query.page),
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .map(|row| ImageBulkUpdate {
+                id: row.id,
+                created_by: row.created_by,
+                changes: row.changes.0,
+                image_count: row.image_count as u64,
+                created_at: row.created_at,
+                undone_at: row.undone_at,
+            })
+            .collect()
+    })
+}
+
+pub async fn count(db: &PgPool) -> sqlx::Result<ItemCount> {
+    let count = sqlx::query_scalar!(r#"select count(*) as "count!" from image_bulk_update"#)
+        .fetch_one(db)
+        .await?;
+
+    Ok((count as usize).into())
+}
+
+async fn add_metadata<T: Metadata>(
+    conn: &mut PgConnection,
+    images: &[Uuid],
+    meta: &[T],
+) -> sqlx::Result<()> {
+    if meta.is_empty() {
+        return Ok(());
+    }
+
+    let meta: Vec<Uuid> = meta.iter().map(|it| (*it).into()).collect();
+
+    sqlx::query(&format!(
+        "insert into image_{0} (image_id, {0}_id) select i, m from unnest($1::uuid[]) i, unnest($2::uuid[]) m on conflict do nothing",
+        T::TABLE
+    ))
+    .bind(images)
+    .bind(&meta)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn remove_metadata<T: Metadata>(
+    conn: &mut PgConnection,
+    images: &[Uuid],
+    meta: &[T],
+) -> sqlx::Result<()> {
+    if meta.is_empty() {
+        return Ok(());
+    }
+
+    let meta: Vec<Uuid> = meta.iter().map(|it| (*it).into()).collect();
+
+    sqlx::query(&format!(
+        "delete from image_{0} where image_id = any($1) and {0}_id = any($2)",
+        T::TABLE
+    ))
+    .bind(images)
+    .bind(&meta)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn add_tags(
+    conn: &mut PgConnection,
+    images: &[Uuid],
+    tags: &[ImageTagIndex],
+) -> sqlx::Result<()> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    let tags: Vec<i16> = tags.iter().map(|it| it.0).collect();
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into image_tag_join (image_id, tag_index)
+select distinct i, t
+from unnest($1::uuid[]) i, unnest($2::int2[]) t
+where not exists(select 1 from image_tag_join where image_id = i and tag_index = t)
+"#,
+        images,
+        &tags,
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+async fn remove_tags(
+    conn: &mut PgConnection,
+    images: &[Uuid],
+    tags: &[ImageTagIndex],
+) -> sqlx::Result<()> {
+    if tags.is_empty() {
+        return Ok(());
+    }
+
+    let tags: Vec<i16> = tags.iter().map(|it| it.0).collect();
+
+    sqlx::query!(
+        "delete from image_tag_join where image_id = any($1) and tag_index = any($2)",
+        images,
+        &tags,
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Gives images back the items of `meta` which they had before the bulk update, skipping items
+/// which have been deleted since.
+async fn restore_removed_metadata<T: Metadata>(
+    conn: &mut PgConnection,
+    id: ImageBulkUpdateId,
+    snapshot_column: &str,
+    meta: &[T],
+) -> sqlx::Result<()> {
+    if meta.is_empty() {
+        return Ok(());
+    }
+
+    let meta: Vec<Uuid> = meta.iter().map(|it| (*it).into()).collect();
+
+    sqlx::query(&format!(
+        r#"
+insert into image_{0} (image_id, {0}_id)
+select b.image_id, m.id
+from image_bulk_update_image b
+inner join {0} m on m.id = any($2) and m.id = any(b.{1})
+where b.bulk_update_id = $1
+on conflict do nothing
+"#,
+        T::TABLE,
+        snapshot_column,
+    ))
+    .bind(id.0)
+    .bind(&meta)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Takes away the items of `meta` which images didn't have before the bulk update.
+async fn undo_added_metadata<T: Metadata>(
+    conn: &mut PgConnection,
+    id: ImageBulkUpdateId,
+    snapshot_column: &str,
+    meta: &[T],
+) -> sqlx::Result<()> {
+    if meta.is_empty() {
+        return Ok(());
+    }
+
+    let meta: Vec<Uuid> = meta.iter().map(|it| (*it).into()).collect();
+
+    sqlx::query(&format!(
+        r#"
+delete from image_{0} t
+using image_bulk_update_image b
+where b.bulk_update_id = $1
+  and t.image_id = b.image_id
+  and t.{0}_id = any($2)
+  and not (t.{0}_id = any(b.{1}))
+"#,
+        T::TABLE,
+        snapshot_column,
+    ))
+    .bind(id.0)
+    .bind(&meta)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
@@ -84,6 +84,7 @@ from user_image_library
               on user_image_library.id = user_image_upload.image_id
 where processing_result is true
   and user_id = $1
+  and quarantined_at is null
   and (size is not distinct from $2 or $2 is null)
 order by created_at desc
 "#,
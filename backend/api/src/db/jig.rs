@@ -1,8 +1,8 @@
-use crate::translate::translate_text;
+use crate::translate::{self, TranslationProvider};
 use anyhow::Context;
 use serde_json::value::Value;
 use shared::domain::jig::codes::JigCode;
-use shared::domain::jig::{AdminJigExport, JigUpdateAdminDataRequest};
+use shared::domain::jig::{AdminJigExport, JigUpdateAdminDataRequest, UntranslatedText};
 use shared::domain::module::StableModuleId;
 use shared::domain::playlist::{PlaylistAdminData, PlaylistRating};
 use shared::domain::{
@@ -14,12 +14,12 @@ use shared::domain::{
         JigData, JigId, JigPlayerSettings, JigRating, JigResponse, TextDirection,
     },
     meta::{AffiliationId, AgeRangeId, ResourceTypeId as TypeId},
-    module::{body::ThemeId, LiteModule, ModuleId, ModuleKind},
+    module::{body::ThemeId, LiteModule, ModuleBody, ModuleId, ModuleKind},
     playlist::{PlaylistData, PlaylistId, PlaylistResponse},
     user::{UserId, UserScope},
 };
 use sqlx::{types::Json, PgConnection, PgPool};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::{instrument, Instrument};
 use uuid::Uuid;
 
@@ -30,6 +30,7 @@ pub(crate) mod codes;
 pub(crate) mod curation;
 pub(crate) mod module;
 pub(crate) mod report;
+pub(crate) mod translation;
 
 pub async fn create(
     pool: &PgPool,
@@ -306,6 +307,7 @@ from jig_data
                 direction: row.direction,
                 scoring: row.scoring,
                 drag_assist: row.drag_assist,
+                ..Default::default()
             },
             theme: row.theme,
             age_ranges: row.age_ranges.into_iter().map(|(it,)| it).collect(),
@@ -520,6 +522,7 @@ order by ord asc
                     direction: jig_data_row.direction,
                     scoring: jig_data_row.scoring,
                     drag_assist: jig_data_row.drag_assist,
+                    ..Default::default()
                 },
                 theme: jig_data_row.theme,
                 age_ranges: jig_data_row
@@ -609,7 +612,7 @@ with cte as (
           left join jig_data_additional_resource "resource" on jd.id = resource.jig_data_id
     where (author_id = $1 or $1 is null)
         and (jd.draft_or_live = $2 or $2 is null)
-        and (blocked = $3 or $3 is null)
+        and ((blocked or jig.quarantined_at is not null) = $3 or $3 is null)
         and (jd.privacy_level = any($4) or $4 = array[]::smallint[])
         and (resource.resource_type_id = any($5) or $5 = array[]::uuid[])
     group by updated_at, created_at, jig.published_at, admin.jig_id
@@ -768,6 +771,7 @@ limit $8
                     direction: jig_data_row.direction,
                     scoring: jig_data_row.scoring,
                     drag_assist: jig_data_row.drag_assist,
+                    ..Default::default()
                 },
                 theme: jig_data_row.theme,
                 age_ranges: jig_data_row
@@ -831,7 +835,7 @@ limit $8
 
 pub async fn update_draft(
     pool: &PgPool,
-    api_key: &Option<String>,
+    translator: Option<&dyn TranslationProvider>,
     id: JigId,
     display_name: Option<&str>,
     categories: Option<&[CategoryId]>,
@@ -959,10 +963,13 @@ where id = $1 and $2 is distinct from description"#,
     }
 
     if let Some(other_keywords) = other_keywords {
-        let translate_text = match &api_key {
-            Some(key) => translate_text(&other_keywords, "he", "en", key)
-                .await
-                .context("could not translate text")?,
+        let translate_text = match translator {
+            Some(translator) => Some(
+                translator
+                    .translate(&other_keywords, "en", "iw")
+                    .await
+                    .context("could not translate text")?,
+            ),
             None => None,
         };
 
@@ -1181,7 +1188,7 @@ pub async fn filtered_count(
                   left join jig_data_additional_resource "resource" on jd.id = resource.jig_data_id
             where (jd.draft_or_live = $1 or $1 is null)
                 and (author_id = $2 or $2 is null)
-                and (blocked = $3 or $3 is null)
+                and ((blocked or jig.quarantined_at is not null) = $3 or $3 is null)
                 and (jd.privacy_level = any($4) or $4 = array[]::smallint[])
                 and (resource.resource_type_id = any($5) or $5 = array[]::uuid[])
             group by updated_at, created_at, jig.published_at, admin.jig_id, jig_id
@@ -1210,7 +1217,7 @@ pub async fn filtered_count(
                   left join jig_data_additional_resource "resource" on jd.id = resource.jig_data_id
             where (jd.draft_or_live = $1 or $1 is null)
               and (author_id = $2 or $2 is null)
-              and (blocked = $3 or $3 is null)
+              and ((blocked or jig.quarantined_at is not null) = $3 or $3 is null)
               and (jd.privacy_level = any($4) or $4 = array[]::smallint[])
               and (resource.resource_type_id = any($5) or $5 = array[]::uuid[])
             group by updated_at, created_at, jig.published_at, admin.jig_id, jig_id
@@ -1261,6 +1268,23 @@ select draft_id, live_id from jig where id = $1
     .map(|it| (it.draft_id, it.live_id))
 }
 
+/// Gets the display name and description of the live jig data.
+pub async fn get_live_text(db: &PgPool, jig_id: JigId) -> sqlx::Result<Option<(String, String)>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select display_name, description
+from jig
+         inner join jig_data on jig_data.id = jig.live_id
+where jig.id = $1
+"#,
+        jig_id.0
+    )
+    .fetch_optional(db)
+    .await
+    .map(|row| row.map(|it| (it.display_name, it.description)))
+}
+
 /// Clones a copy of the jig data and modules
 async fn clone_data(
     txn: &mut PgConnection,
@@ -1404,13 +1428,113 @@ pub async fn clone_jig(
 ) -> Result<JigId, error::CloneDraft> {
     let mut txn = db.begin().await?;
 
+    let (id, _, _) = clone_jig_in(&mut txn, parent, user_id).await?;
+
+    txn.commit().await?;
+
+    Ok(id)
+}
+
+/// Clones a jig like [`clone_jig`], replacing its texts with their `translations` and setting its
+/// language and text direction.
+///
+/// Returns the texts of the copy's draft which had no translation.
+pub async fn clone_translated_jig(
+    txn: &mut PgConnection,
+    parent: JigId,
+    user_id: UserId,
+    language: &str,
+    direction: TextDirection,
+    translations: &HashMap<String, String>,
+) -> Result<(JigId, Vec<UntranslatedText>), error::CloneDraft> {
+    let (id, draft_id, live_id) = clone_jig_in(&mut *txn, parent, user_id).await?;
+
+    let untranslated =
+        translate_data(&mut *txn, draft_id, language, direction, translations).await?;
+    translate_data(&mut *txn, live_id, language, direction, translations).await?;
+
+    Ok((id, untranslated))
+}
+
+/// The language of a jig's draft, `None` if the jig doesn't exist.
+pub async fn language(db: &PgPool, jig_id: JigId) -> sqlx::Result<Option<String>> {
+    sqlx::query_scalar!(
+        //language=SQL
+        r#"
+select language
+from jig_data
+where id = (select draft_id from jig where id = $1)
+"#,
+        jig_id.0,
+    )
+    .fetch_optional(db)
+    .await
+}
+
+/// The texts of a jig's draft and live data (name, description and module contents).
+pub async fn texts(db: &PgPool, jig_id: JigId) -> sqlx::Result<HashSet<String>> {
+    let data = sqlx::query!(
+        //language=SQL
+        r#"
+select display_name, description
+from jig_data
+inner join jig on jig_data.id in (jig.draft_id, jig.live_id)
+where jig.id = $1
+"#,
+        jig_id.0,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut texts: HashSet<String> = data
+        .into_iter()
+        .flat_map(|data| [data.display_name, data.description])
+        .filter(|text| !text.trim().is_empty())
+        .collect();
+
+    let modules = sqlx::query!(
+        //language=SQL
+        r#"
+select jig_data_module.id as "id: ModuleId",
+       kind               as "kind: ModuleKind",
+       contents
+from jig_data_module
+inner join jig on jig_data_id in (jig.draft_id, jig.live_id)
+where jig.id = $1
+"#,
+        jig_id.0,
+    )
+    .fetch_all(db)
+    .await?;
+
+    for module in modules {
+        match ModuleBody::transform_response_kind(module.contents, module.kind) {
+            Ok(body) => texts.extend(translate::module::texts(&body)),
+            Err(e) => log::warn!(
+                "Couldn't read module {} to translate it: {:?}",
+                module.id.0,
+                e
+            ),
+        }
+    }
+
+    Ok(texts)
+}
+
+/// Clones a jig's draft and live data into a new jig owned by `user_id`, returning the IDs of the
+/// jig and its draft and live data.
+async fn clone_jig_in(
+    txn: &mut PgConnection,
+    parent: JigId,
+    user_id: UserId,
+) -> Result<(JigId, Uuid, Uuid), error::CloneDraft> {
     let (draft_id, live_id) = get_draft_and_live_ids(&mut *txn, parent)
         .await
         .ok_or(error::CloneDraft::ResourceNotFound)?;
 
     let mut stable_id_map = HashMap::new();
 
-    let new_draft_id = clone_data(&mut txn, &draft_id, DraftOrLive::Draft, |old_stable_id| {
+    let new_draft_id = clone_data(&mut *txn, &draft_id, DraftOrLive::Draft, |old_stable_id| {
         let new_stable_id: StableModuleId = StableModuleId(Uuid::new_v4());
         stable_id_map.insert(old_stable_id, new_stable_id);
         new_stable_id
@@ -1419,7 +1543,7 @@ pub async fn clone_jig(
 
     let new_live_id =
         clone_data(
-            &mut txn,
+            &mut *txn,
             &live_id,
             DraftOrLive::Live,
             |old_stable_id| match stable_id_map.get(&old_stable_id) {
@@ -1443,7 +1567,7 @@ returning id as "id!: JigId"
         new_live_id,
         new_draft_id,
     )
-    .fetch_one(&mut txn)
+    .fetch_one(&mut *txn)
     .await?;
 
     sqlx::query!(
@@ -1454,12 +1578,106 @@ values ($1, 0)
         "#,
         new_jig.id.0
     )
-    .execute(&mut txn)
+    .execute(&mut *txn)
     .await?;
 
-    txn.commit().await?;
+    Ok((new_jig.id, new_draft_id, new_live_id))
+}
+
+/// Replaces the texts of cloned jig data with their `translations`, returning those which had
+/// none.
+async fn translate_data(
+    txn: &mut PgConnection,
+    data_id: Uuid,
+    language: &str,
+    direction: TextDirection,
+    translations: &HashMap<String, String>,
+) -> anyhow::Result<Vec<UntranslatedText>> {
+    let mut untranslated = Vec::new();
+
+    let mut translate = |text: &mut String, module_id: Option<ModuleId>| {
+        if text.trim().is_empty() {
+            return;
+        }
+
+        match translations.get(text.as_str()) {
+            Some(translation) => *text = translation.clone(),
+            None => untranslated.push(UntranslatedText {
+                module_id,
+                text: text.clone(),
+            }),
+        }
+    };
+
+    let data = sqlx::query!(
+        "select display_name, description from jig_data where id = $1",
+        data_id
+    )
+    .fetch_one(&mut *txn)
+    .await?;
+
+    let mut display_name = data.display_name;
+    let mut description = data.description;
+    translate(&mut display_name, None);
+    translate(&mut description, None);
+
+    // the copy's name and description get translated into other languages again once it's published
+    sqlx::query!(
+        //language=SQL
+        r#"
+update jig_data
+set display_name           = $2,
+    description            = $3,
+    language               = $4,
+    direction              = $5,
+    translated_name        = '{}',
+    translated_description = '{}'
+where id = $1
+"#,
+        data_id,
+        display_name,
+        description,
+        language,
+        direction as i16,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    let modules = sqlx::query!(
+        r#"select id as "id: ModuleId", kind as "kind: ModuleKind", contents from jig_data_module where jig_data_id = $1"#,
+        data_id
+    )
+    .fetch_all(&mut *txn)
+    .await?;
+
+    for module in modules {
+        // modules which can't be read are copied as they were
+        let mut body = match ModuleBody::transform_response_kind(module.contents, module.kind) {
+            Ok(body) => body,
+            Err(e) => {
+                log::warn!(
+                    "Couldn't read module {} to translate it: {:?}",
+                    module.id.0,
+                    e
+                );
+                continue;
+            }
+        };
+
+        translate::module::visit_texts(&mut body, &mut |text| translate(text, Some(module.id)));
+
+        let (_, contents) = ModuleBody::map_module_contents(&body)?;
+
+        sqlx::query!(
+            "update jig_data_module set contents = $2 where id = $1",
+            module.id.0,
+            contents,
+        )
+        .execute(&mut *txn)
+        .await?;
+    }
 
-    Ok(new_jig.id)
+    Ok(untranslated)
 }
 
 pub async fn jig_play(db: &PgPool, jig_id: JigId) -> anyhow::Result<()> {
@@ -2,11 +2,16 @@ use chrono::{DateTime, Duration, Utc};
 use futures::future::join_all;
 use rand::{rngs::ThreadRng, Rng};
 use serde_json::value::Value;
-use shared::config::{JIG_PLAYER_SESSION_CODE_MAX, JIG_PLAYER_SESSION_VALID_DURATION_SECS};
+use shared::config::{
+    CHILD_PRIVACY_SESSION_RETENTION_DAYS, JIG_PLAYER_SESSION_CODE_MAX,
+    JIG_PLAYER_SESSION_VALID_DURATION_SECS,
+};
 use shared::domain::additional_resource::{AdditionalResource, ResourceContent};
 use shared::domain::asset::DraftOrLive;
+use shared::domain::class::StudentId;
 use shared::domain::jig::codes::{
-    JigCodeListRequest, JigCodeSessionResponse, JigCodeUpdateRequest,
+    JigCodeCompletionStatus, JigCodeListRequest, JigCodePlayerReport, JigCodeReportResponse,
+    JigCodeSessionResponse, JigCodeUpdateRequest, JigPlaySessionModuleGetPointsEarned,
     JigPlayerSessionCreateRequest, JigWithCodes,
 };
 use shared::domain::jig::{
@@ -31,8 +36,8 @@ use uuid::Uuid;
 
 use shared::domain::jig::{AudioEffects, JigAdminData, JigData, JigResponse, TextDirection};
 
-use crate::error;
 use crate::extractor::IPAddress;
+use crate::{child_privacy, error};
 
 pub async fn create(
     db: &PgPool,
@@ -51,8 +56,9 @@ pub async fn create(
         match sqlx::query!(
             //language=SQL
             r#"
-insert into jig_code (jig_id, creator_id, name, code, direction, scoring, drag_assist, expires_at)
-values ($1, $2, $3, $4, $5, $6, $7, $8)
+insert into jig_code (jig_id, creator_id, name, code, direction, scoring, drag_assist, expires_at,
+                      opens_at, due_at, max_attempts, hide_score)
+values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
 returning created_at as "created_at: DateTime<Utc>"
 "#,
             opts.jig_id.0,
@@ -63,6 +69,10 @@ returning created_at as "created_at: DateTime<Utc>"
             opts.settings.scoring,
             opts.settings.drag_assist,
             expires_at,
+            opts.settings.opens_at,
+            opts.settings.due_at,
+            opts.settings.max_attempts.map(|max| max as i16),
+            opts.settings.hide_score,
         )
         .fetch_one(db)
         .await
@@ -100,6 +110,7 @@ pub async fn update(
     let direction = opts.settings.as_ref().map(|opts| opts.direction);
     let scoring = opts.settings.as_ref().map(|opts| opts.scoring);
     let drag_assist = opts.settings.as_ref().map(|opts| opts.drag_assist);
+    let hide_score = opts.settings.as_ref().map(|opts| opts.hide_score);
 
     sqlx::query!(
         //language=SQL
@@ -108,7 +119,11 @@ pub async fn update(
             set name = case when $2 then $3 else name end,
                 direction = coalesce($4, direction),
                 scoring = coalesce($5, scoring),
-                drag_assist = coalesce($6, drag_assist)
+                drag_assist = coalesce($6, drag_assist),
+                hide_score = coalesce($7, hide_score),
+                opens_at = case when $8 then $9 else opens_at end,
+                due_at = case when $10 then $11 else due_at end,
+                max_attempts = case when $12 then $13 else max_attempts end
             where code = $1
         "#,
         code.0,
@@ -117,6 +132,13 @@ pub async fn update(
         direction.map(|d| d as i16),
         scoring,
         drag_assist,
+        hide_score,
+        opts.opens_at.is_some(),
+        opts.opens_at.flatten(),
+        opts.due_at.is_some(),
+        opts.due_at.flatten(),
+        opts.max_attempts.is_some(),
+        opts.max_attempts.flatten().map(|max| max as i16),
     )
     .execute(db)
     .await?;
@@ -155,6 +177,10 @@ pub async fn get_code(db: &PgPool, code: JigCode) -> sqlx::Result<JigCodeRespons
                 direction as "direction: TextDirection",
                 scoring,
                 drag_assist,
+                opens_at,
+                due_at,
+                max_attempts,
+                hide_score,
                 name as "name?",
                 created_at as "created_at: DateTime<Utc>",
                 expires_at as "expires_at: DateTime<Utc>"
@@ -174,6 +200,10 @@ pub async fn get_code(db: &PgPool, code: JigCode) -> sqlx::Result<JigCodeRespons
             direction: row.direction,
             scoring: row.scoring,
             drag_assist: row.drag_assist,
+            opens_at: row.opens_at,
+            due_at: row.due_at,
+            max_attempts: row.max_attempts.map(|max| max as u16),
+            hide_score: row.hide_score,
         },
         created_at: row.created_at,
         expires_at: row.expires_at,
@@ -195,6 +225,10 @@ select code     as "code!: i32",
        direction as "direction: TextDirection",
        scoring,
        drag_assist,
+       opens_at,
+       due_at,
+       max_attempts,
+       hide_score,
        name as "name?",
        created_at as "created_at: DateTime<Utc>",
        expires_at as "expires_at: DateTime<Utc>"
@@ -216,6 +250,10 @@ order by created_at desc
             direction: it.direction,
             scoring: it.scoring,
             drag_assist: it.drag_assist,
+            opens_at: it.opens_at,
+            due_at: it.due_at,
+            max_attempts: it.max_attempts.map(|max| max as u16),
+            hide_score: it.hide_score,
         },
         created_at: it.created_at,
         expires_at: it.expires_at,
@@ -344,6 +382,10 @@ pub async fn jigs_with_codes(db: &PgPool, user_id: UserId) -> sqlx::Result<Vec<J
                         direction as "direction: TextDirection",
                         scoring,
                         drag_assist,
+                        opens_at,
+                        due_at,
+                        max_attempts,
+                        hide_score,
                         name as "name?",
                         created_at as "created_at: DateTime<Utc>",
                         expires_at as "expires_at: DateTime<Utc>"
@@ -366,6 +408,10 @@ pub async fn jigs_with_codes(db: &PgPool, user_id: UserId) -> sqlx::Result<Vec<J
                         direction: row.direction,
                         scoring: row.scoring,
                         drag_assist: row.drag_assist,
+                        opens_at: row.opens_at,
+                        due_at: row.due_at,
+                        max_attempts: row.max_attempts.map(|max| max as u16),
+                        hide_score: row.hide_score,
                     },
                     created_at: row.created_at,
                     expires_at: row.expires_at,
@@ -404,6 +450,7 @@ pub async fn jigs_with_codes(db: &PgPool, user_id: UserId) -> sqlx::Result<Vec<J
                         direction: row.direction,
                         scoring: row.scoring,
                         drag_assist: row.drag_assist,
+                        ..Default::default()
                     },
                     theme: row.theme,
                     age_ranges: row.age_ranges.into_iter().map(|(it,)| it).collect(),
@@ -486,13 +533,14 @@ pub async fn list_code_sessions(
         //language=SQL
         r#"
             SELECT
-                id,
-                code,
-                players_name,
+                jig_code_session.code,
+                coalesce(class_student.display_name, players_name) as players_name,
+                student_id as "student_id: StudentId",
                 started_at,
                 finished_at,
                 info
             FROM jig_code_session
+            LEFT JOIN class_student ON class_student.id = jig_code_session.student_id
             WHERE code = $1 AND finished_at IS NOT NULL
             ORDER BY started_at;
         "#,
@@ -505,6 +553,7 @@ pub async fn list_code_sessions(
         Ok(JigCodeSessionResponse {
             code: JigCode(it.code),
             players_name: it.players_name,
+            student_id: it.student_id,
             started_at: it.started_at,
             finished_at: it.finished_at,
             info: match it.info {
@@ -518,23 +567,213 @@ pub async fn list_code_sessions(
     Ok(sessions)
 }
 
-/// Creates new jig player session for a player
+/// Reports whether each player completed a code on time, late, or not at all.
+pub async fn code_report(
+    db: &PgPool,
+    user_id: UserId,
+    code: JigCode,
+) -> Result<JigCodeReportResponse, error::JigCode> {
+    let due_at = sqlx::query!(
+        //language=SQL
+        r#"
+            select due_at
+            from jig_code
+            where creator_id = $1 and code = $2
+        "#,
+        user_id.0,
+        code.0
+    )
+    .fetch_optional(db)
+    .await?
+    .ok_or(error::JigCode::ResourceNotFound)?
+    .due_at;
+
+    let sessions = sqlx::query!(
+        //language=SQL
+        r#"
+            select coalesce(class_student.display_name, players_name) as players_name,
+                student_id as "student_id: StudentId",
+                player_id,
+                finished_at,
+                info
+            from jig_code_session
+            left join class_student on class_student.id = jig_code_session.student_id
+            where code = $1
+            order by started_at
+        "#,
+        code.0
+    )
+    .fetch_all(db)
+    .await?;
+
+    // students of classes the code is assigned to, so that the ones who never played are listed
+    let students = sqlx::query!(
+        //language=SQL
+        r#"
+            select distinct class_student.id as "id: StudentId",
+                class_student.display_name
+            from class_assignment_code
+            join class_assignment on class_assignment.id = class_assignment_code.assignment_id
+            join class_student on class_student.class_id = class_assignment.class_id
+            where class_assignment_code.code = $1
+            order by class_student.display_name
+        "#,
+        code.0
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut players: Vec<JigCodePlayerReport> = Vec::new();
+    let mut player_indices: HashMap<String, usize> = HashMap::new();
+
+    for session in sessions {
+        // anonymous players are told apart by their device, like their attempts are counted.
+        // sessions from before devices were recorded only have the name the player typed.
+        let key = match (
+            &session.student_id,
+            &session.player_id,
+            &session.players_name,
+        ) {
+            (Some(student_id), _, _) => Some(student_id.to_string()),
+            (None, Some(player_id), _) => Some(format!("player:{}", player_id)),
+            (None, None, Some(name)) => Some(format!("name:{}", name.trim().to_lowercase())),
+            (None, None, None) => None,
+        };
+
+        let index = match key.as_ref().and_then(|key| player_indices.get(key)) {
+            Some(index) => *index,
+            None => {
+                players.push(JigCodePlayerReport {
+                    players_name: session.players_name.clone(),
+                    student_id: session.student_id,
+                    status: JigCodeCompletionStatus::NotCompleted,
+                    attempts: 0,
+                    completed_at: None,
+                    points: None,
+                });
+                if let Some(key) = key {
+                    player_indices.insert(key, players.len() - 1);
+                }
+                players.len() - 1
+            }
+        };
+
+        let player = &mut players[index];
+        player.attempts += 1;
+
+        // the name a player typed last
+        if session.players_name.is_some() {
+            player.players_name = session.players_name;
+        }
+
+        let finished_at = match session.finished_at {
+            Some(finished_at) => finished_at,
+            None => continue,
+        };
+
+        if player
+            .completed_at
+            .map_or(true, |completed_at| finished_at < completed_at)
+        {
+            player.completed_at = Some(finished_at);
+        }
+
+        let info: Option<JigPlaySession> = match session.info {
+            Some(info) => serde_json::from_value(info)?,
+            None => None,
+        };
+        let points = info
+            .filter(|info| !info.modules.is_empty())
+            .map(|info| info.get_points_earned());
+        if let Some(points) = points {
+            if player
+                .points
+                .as_ref()
+                .map_or(true, |best| points.earned > best.earned)
+            {
+                player.points = Some(points);
+            }
+        }
+    }
+
+    for player in &mut players {
+        player.status = JigCodeCompletionStatus::new(player.completed_at, due_at);
+    }
+
+    players.extend(
+        students
+            .into_iter()
+            .filter(|student| !player_indices.contains_key(&student.id.to_string()))
+            .map(|student| JigCodePlayerReport {
+                players_name: Some(student.display_name),
+                student_id: Some(student.id),
+                status: JigCodeCompletionStatus::NotCompleted,
+                attempts: 0,
+                completed_at: None,
+                points: None,
+            }),
+    );
+
+    Ok(JigCodeReportResponse {
+        code,
+        due_at,
+        players,
+    })
+}
+
+/// A player session which was started by [`start_session`].
+pub struct StartedSession {
+    pub jig_id: JigId,
+    pub settings: JigPlayerSettings,
+    pub instance_id: Uuid,
+    /// The player's id, which is new if they didn't have one.
+    pub player_id: Uuid,
+    pub pseudonym: Option<String>,
+}
+
+/// Creates new jig player session for a player, linked to the student if they're logged in to
+/// their class
+///
+/// Fails if the code isn't open yet or the player has used all their attempts. Players are told
+/// apart by the student they're logged in as, or otherwise by the `player_id` their device keeps,
+/// so that players sharing an IP address each get their own attempts. If the code's school is in
+/// child privacy mode, the IP address isn't stored and players who aren't logged in get the
+/// pseudonym which is returned, derived from their id with `privacy_key`. Without a
+/// `privacy_key`, only logged in students can start sessions of such codes.
 pub async fn start_session(
     db: &PgPool,
     code: JigCode,
     ip_address: IPAddress,
-) -> Result<(JigId, JigPlayerSettings, Uuid), error::JigCode> {
+    student_id: Option<StudentId>,
+    player_id: Option<Uuid>,
+    privacy_key: Option<&[u8]>,
+) -> Result<StartedSession, error::JigCode> {
+    let player_id = player_id.unwrap_or_else(Uuid::new_v4);
+
     let mut txn = db.begin().await?;
 
+    // locked so that concurrent starts by the same player can't go over their attempts
     let session_info = sqlx::query!(
         //language=SQL
         r#"
         select jig_id as "jig_id: JigId", 
                direction as "direction: TextDirection", 
                scoring,
-               drag_assist
+               drag_assist,
+               opens_at,
+               due_at,
+               max_attempts,
+               hide_score,
+               exists(
+                   select 1
+                   from user_account
+                   join school using (account_id)
+                   where user_account.user_id = jig_code.creator_id
+                     and school.child_privacy_mode
+               ) as "child_privacy_mode!"
         from jig_code
         where code=$1
+        for update of jig_code
         "#,
         code.0
     )
@@ -542,16 +781,70 @@ pub async fn start_session(
     .await?
     .ok_or(error::JigCode::ResourceNotFound)?;
 
+    if matches!(session_info.opens_at, Some(opens_at) if opens_at > Utc::now()) {
+        return Err(error::JigCode::NotOpen);
+    }
+
+    let (ip_address, pseudonym) = if session_info.child_privacy_mode {
+        let pseudonym = match student_id {
+            // reported under their name in the class roster, which isn't copied to the session
+            Some(_) => None,
+            None => {
+                let privacy_key = privacy_key.ok_or_else(|| {
+                    anyhow::anyhow!("child privacy mode requires `CHILD_PRIVACY_SECRET` to be set")
+                })?;
+
+                Some(child_privacy::pseudonym(privacy_key, code, player_id))
+            }
+        };
+
+        (None, pseudonym)
+    } else {
+        (ip_address.0, None)
+    };
+
+    if let Some(max_attempts) = session_info.max_attempts {
+        let attempts = sqlx::query!(
+            //language=SQL
+            r#"
+            select count(*) as "count!"
+            from jig_code_session
+            where code = $1
+              and case when $2::uuid is null
+                  then student_id is null and player_id = $3
+                  else student_id = $2
+              end
+            "#,
+            code.0,
+            student_id.map(|id| id.0),
+            player_id,
+        )
+        .fetch_one(&mut txn)
+        .await?
+        .count;
+
+        if attempts >= max_attempts as i64 {
+            return Err(error::JigCode::NoAttemptsLeft);
+        }
+    }
+
     // insert into the jig_code_session table returning the instance_id
     let instance_id = sqlx::query!(
         //language=SQL
         r#"
-        insert into jig_code_session (code, started_at, ip_address)
-        values ($1, current_timestamp, $2)
+        insert into jig_code_session (code, started_at, ip_address, student_id, player_id, players_name)
+        values ($1, current_timestamp, $2, $3, $4, case
+            when $5 then $6
+            else (select display_name from class_student where id = $3)
+        end)
         returning id as "id: Uuid"
         "#,
         code.0,
-        ip_address.0,
+        ip_address,
+        student_id.map(|id| id.0),
+        player_id,
+        session_info.child_privacy_mode,
+        pseudonym,
     )
     .fetch_one(&mut txn)
     .await?
@@ -559,18 +852,27 @@ pub async fn start_session(
 
     txn.commit().await?;
 
-    Ok((
-        session_info.jig_id,
-        JigPlayerSettings {
+    Ok(StartedSession {
+        jig_id: session_info.jig_id,
+        settings: JigPlayerSettings {
             direction: session_info.direction,
             scoring: session_info.scoring,
             drag_assist: session_info.drag_assist,
+            opens_at: session_info.opens_at,
+            due_at: session_info.due_at,
+            max_attempts: session_info.max_attempts.map(|max| max as u16),
+            hide_score: session_info.hide_score,
         },
         instance_id,
-    ))
+        player_id,
+        pseudonym,
+    })
 }
 
 /// Completes a jig player session for a player and updates play count
+///
+/// The player's name and IP address are ignored if the code's school is in child privacy mode,
+/// see [`start_session`].
 pub async fn complete_session(
     db: &PgPool,
     session: JigPlaySession,
@@ -578,21 +880,76 @@ pub async fn complete_session(
     instance_id: Uuid,
     ip_address: IPAddress,
 ) -> Result<(), error::JigCode> {
+    let code_info = sqlx::query!(
+        //language=SQL
+        r#"
+            select exists(
+                    select 1
+                    from user_account
+                    join school using (account_id)
+                    where user_account.user_id = jig_code.creator_id
+                      and school.child_privacy_mode
+                ) as "child_privacy_mode!"
+            from jig_code_session
+            join jig_code on jig_code.code = jig_code_session.code
+            where jig_code_session.id = $1
+        "#,
+        instance_id,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    let code_info = match code_info {
+        Some(code_info) => code_info,
+        None => return Ok(()),
+    };
+
+    let (ip_address, players_name) = if code_info.child_privacy_mode {
+        (None, None)
+    } else {
+        (ip_address.0, players_name)
+    };
+
     let session = serde_json::to_value(&session)?;
     sqlx::query!(
         //language=SQL
         r#"
             UPDATE jig_code_session
-            SET finished_at = current_timestamp, info=$1, players_name=$2
-            WHERE id = $3 and ip_address = $4 and finished_at is null;
+            SET finished_at = current_timestamp, info=$1, players_name=coalesce($2, players_name)
+            WHERE id = $3 and ip_address is not distinct from $4 and finished_at is null;
         "#,
         session,
         players_name,
         instance_id,
-        ip_address.0,
+        ip_address,
     )
     .execute(db)
     .await?;
 
     Ok(())
 }
+
+/// Deletes the sessions of codes created by teachers of a school which are older than the school's
+/// retention period, returning how many were deleted.
+pub async fn purge_expired_sessions(db: &PgPool) -> sqlx::Result<u64> {
+    let deleted = sqlx::query!(
+        //language=SQL
+        r#"
+            delete from jig_code_session
+            using jig_code, user_account, school
+            where jig_code.code = jig_code_session.code
+              and user_account.user_id = jig_code.creator_id
+              and school.account_id = user_account.account_id
+              and jig_code_session.started_at < now() - make_interval(days => coalesce(
+                  school.session_retention_days,
+                  case when school.child_privacy_mode then $1::int end
+              ))
+        "#,
+        i32::from(CHILD_PRIVACY_SESSION_RETENTION_DAYS),
+    )
+    .execute(db)
+    .await?
+    .rows_affected();
+
+    Ok(deleted)
+}
@@ -0,0 +1,153 @@
+//! Translated copies of JIGs, which are made in the background by the
+//! [`Translator`](crate::translate::Translator).
+
+use shared::domain::{
+    jig::{JigId, JigTranslation, JigTranslationId, JigTranslationStatus, UntranslatedText},
+    user::UserId,
+};
+use sqlx::{types::Json, PgConnection, PgPool};
+
+/// Minutes after which a translation which was started but hasn't finished is taken to have been
+/// interrupted, and is started again.
+const INTERRUPTED_AFTER_MINUTES: i32 = 30;
+
+pub async fn create(
+    db: &PgPool,
+    jig_id: JigId,
+    user_id: UserId,
+    language: &str,
+) -> sqlx::Result<JigTranslationId> {
+    sqlx::query_scalar!(
+        //language=SQL
+        r#"
+insert into jig_translation (jig_id, created_by, language)
+values ($1, $2, $3)
+returning id as "id: JigTranslationId"
+"#,
+        jig_id.0,
+        user_id.0,
+        language,
+    )
+    .fetch_one(db)
+    .await
+}
+
+/// Gets a translation which was started by `user_id`.
+pub async fn get(
+    db: &PgPool,
+    id: JigTranslationId,
+    user_id: UserId,
+) -> sqlx::Result<Option<JigTranslation>> {
+    let translation = sqlx::query!(
+        //language=SQL
+        r#"
+select id                as "id: JigTranslationId",
+       jig_id            as "jig_id: JigId",
+       language,
+       status            as "status: JigTranslationStatus",
+       translated_jig_id as "translated_jig_id: JigId",
+       untranslated      as "untranslated: Json<Vec<UntranslatedText>>"
+from jig_translation
+where id = $1
+  and created_by = $2
+"#,
+        id.0,
+        user_id.0,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(translation.map(|it| JigTranslation {
+        id: it.id,
+        jig_id: it.jig_id,
+        language: it.language,
+        status: it.status,
+        translated_jig_id: it.translated_jig_id,
+        untranslated: it.untranslated.0,
+    }))
+}
+
+/// Marks a pending translation as started, returning the JIG, who started it and the language.
+///
+/// Returns `None` if the translation isn't pending, or is already being made.
+pub async fn start(
+    db: &PgPool,
+    id: JigTranslationId,
+) -> sqlx::Result<Option<(JigId, UserId, String)>> {
+    let translation = sqlx::query!(
+        //language=SQL
+        r#"
+update jig_translation
+set started_at = now()
+where id = $1
+  and status = $2
+  and (started_at is null or started_at < now() - make_interval(mins => $3))
+returning jig_id as "jig_id: JigId", created_by as "created_by: UserId", language
+"#,
+        id.0,
+        JigTranslationStatus::Pending as i16,
+        INTERRUPTED_AFTER_MINUTES,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(translation.map(|it| (it.jig_id, it.created_by, it.language)))
+}
+
+/// Saves the translated copy, as part of the transaction which made it.
+pub async fn finish(
+    txn: &mut PgConnection,
+    id: JigTranslationId,
+    translated_jig_id: JigId,
+    untranslated: &[UntranslatedText],
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update jig_translation
+set status            = $2,
+    translated_jig_id = $3,
+    untranslated      = $4,
+    finished_at       = now()
+where id = $1
+"#,
+        id.0,
+        JigTranslationStatus::Finished as i16,
+        translated_jig_id.0,
+        Json(untranslated) as _,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn fail(db: &PgPool, id: JigTranslationId) -> sqlx::Result<()> {
+    sqlx::query!(
+        "update jig_translation set status = $2, finished_at = now() where id = $1",
+        id.0,
+        JigTranslationStatus::Failed as i16,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Pending translations which haven't been started, or were interrupted.
+pub async fn list_pending(db: &PgPool) -> sqlx::Result<Vec<JigTranslationId>> {
+    sqlx::query_scalar!(
+        //language=SQL
+        r#"
+select id as "id: JigTranslationId"
+from jig_translation
+where status = $1
+  and (started_at is null or started_at < now() - make_interval(mins => $2))
+order by created_at
+"#,
+        JigTranslationStatus::Pending as i16,
+        INTERRUPTED_AFTER_MINUTES,
+    )
+    .fetch_all(db)
+    .await
+}
@@ -1,8 +1,71 @@
-use shared::domain::locale::UpdateEntryRequest;
-use sqlx::PgConnection;
+use std::collections::BTreeMap;
+
+use shared::domain::locale::{is_valid_key, UpdateEntryRequest};
+use sqlx::{postgres::PgDatabaseError, Executor, PgConnection, PgPool, Postgres};
+use uuid::Uuid;
 
 use crate::error;
 
+/// An entry of a bundle, with its text in the language being exported.
+pub struct ExportEntry {
+    pub key: String,
+    pub section: Option<String>,
+    pub comments: Option<String>,
+    pub text: String,
+}
+
+pub fn handle_entry_err(err: sqlx::Error) -> error::NotFound {
+    match err {
+        sqlx::Error::Database(err)
+            if err.downcast_ref::<PgDatabaseError>().constraint()
+                == Some("locale_entry_bundle_key") =>
+        {
+            error::NotFound::BadRequest
+        }
+        err => err.into(),
+    }
+}
+
+/// Whether every language in `codes` has been added.
+pub async fn languages_exist<'c, E: Executor<'c, Database = Postgres>>(
+    db: E,
+    codes: &[String],
+) -> sqlx::Result<bool> {
+    sqlx::query!(
+        r#"
+select not exists(
+    select 1 from unnest($1::text[]) as code
+    where code not in (select code from locale_language)
+) as "exists!"
+"#,
+        codes
+    )
+    .fetch_one(db)
+    .await
+    .map(|it| it.exists)
+}
+
+/// The keyed entries of a bundle which have been translated into `language`, grouped by section.
+pub async fn export_entries(
+    db: &PgPool,
+    bundle_id: Uuid,
+    language: &str,
+) -> sqlx::Result<Vec<ExportEntry>> {
+    sqlx::query_as!(
+        ExportEntry,
+        r#"
+select key as "key!", section, comments, translations ->> $2 as "text!"
+from locale_entry
+where bundle_id = $1 and key is not null and translations ? $2
+order by section nulls first, id
+"#,
+        bundle_id,
+        language
+    )
+    .fetch_all(db)
+    .await
+}
+
 pub async fn update_entry(
     db: &mut PgConnection,
     id: i32,
@@ -19,6 +82,30 @@ pub async fn update_entry(
         return Err(error::NotFound::ResourceNotFound);
     }
 
+    let key = (req.key.is_some(), req.key.flatten());
+    if !key.1.as_deref().is_none_or(is_valid_key) {
+        return Err(error::NotFound::BadRequest);
+    }
+
+    let (set, removed): (BTreeMap<_, _>, Vec<_>) = req.translations.into_iter().fold(
+        (BTreeMap::new(), Vec::new()),
+        |(mut set, mut removed), (language, text)| {
+            match text {
+                Some(text) => {
+                    set.insert(language, text);
+                }
+                None => removed.push(language),
+            }
+
+            (set, removed)
+        },
+    );
+
+    let languages: Vec<_> = set.keys().cloned().collect();
+    if !languages_exist(&mut *db, &languages).await? {
+        return Err(error::NotFound::BadRequest);
+    }
+
     let section = (req.section.is_some(), req.section.flatten());
 
     let zeplin_reference = (
@@ -34,21 +121,23 @@ update locale_entry
 set
     bundle_id = coalesce(bundle_id, $2),
     item_kind_id = coalesce($3, item_kind_id),
-    english = coalesce($4, english),
-    hebrew = coalesce($5, hebrew),
-    status = coalesce($6, status),
-    in_app = coalesce($7, in_app),
-    in_element = coalesce($8, in_element),
-    in_mock = coalesce($9, in_mock),
-    section = case when $10 then $11 else section end,
-    zeplin_reference = case when $12 then $13 else zeplin_reference end,
-    comments = case when $14 then $15 else comments end
+    key = case when $4 then $5 else key end,
+    translations = (translations || $6::jsonb) - $7::text[],
+    status = coalesce($8, status),
+    in_app = coalesce($9, in_app),
+    in_element = coalesce($10, in_element),
+    in_mock = coalesce($11, in_mock),
+    section = case when $12 then $13 else section end,
+    zeplin_reference = case when $14 then $15 else zeplin_reference end,
+    comments = case when $16 then $17 else comments end
 where id = $1"#,
         id,
         req.bundle_id,
         req.item_kind_id,
-        req.english,
-        req.hebrew,
+        key.0,
+        key.1.as_deref(),
+        serde_json::to_value(&set)?,
+        &removed,
         req.status.map(|status| status as i16),
         req.in_app,
         req.in_element,
@@ -61,7 +150,8 @@ where id = $1"#,
         comments.1.as_deref(),
     )
     .execute(db)
-    .await?;
+    .await
+    .map_err(handle_entry_err)?;
 
     Ok(())
 }
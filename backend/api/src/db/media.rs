@@ -5,10 +5,16 @@ use anyhow::Context;
 use ji_core::config::{ANIMATION_BODY_SIZE_LIMIT, IMAGE_BODY_SIZE_LIMIT};
 use sha2::Digest;
 use shared::{
-    domain::image::ImageSize,
+    domain::{
+        image::ImageSize,
+        media::{WebMediaKind, WebMediaListQuery, WebMediaMetadataResponse},
+        user::UserId,
+        ItemCount,
+    },
     media::{FileKind, MediaLibrary},
 };
 use sqlx::{PgConnection, PgPool};
+use url::Url;
 use uuid::Uuid;
 
 #[inline]
@@ -24,6 +30,7 @@ pub async fn create(
     pool: &PgPool,
     s3: &ServiceData<s3::Client>,
     url_string: &String,
+    created_by: UserId,
 ) -> anyhow::Result<(Uuid, MediaKind, StatusCode)> {
     // If we can already find the image, return early.
     if let Some(record) = sqlx::query!(
@@ -63,18 +70,39 @@ where media_url = $1"#,
         let data = data.clone();
         move || crate::image_ops::detect_image_kind(&data)
     })
-    .await??;
+    .await?
+    .map_err(anyhow::Error::msg)?;
 
     log::debug!("detected image kind as: {:?}", kind);
 
     let id = sqlx::query!(
-        r#"insert into web_media_library ("hash", kind) values($1, $2) returning id"#,
+        r#"
+insert into web_media_library ("hash", kind, created_by)
+values ($1, $2, $3)
+on conflict ("hash") do nothing
+returning id
+"#,
         &hash,
-        kind as i16
+        kind as i16,
+        created_by as UserId,
     )
-    .fetch_one(&mut txn)
+    .fetch_optional(&mut txn)
     .await?
-    .id;
+    .map(|it| it.id);
+
+    // the same bytes were added from another url while these were being downloaded.
+    let id = match id {
+        Some(id) => id,
+        None => {
+            let (_, id, kind) = hash_media_file(url_string.to_string(), &mut txn, &data).await?;
+            txn.commit().await?;
+
+            let (id, kind) = id
+                .zip(kind)
+                .context("media with a conflicting hash is missing")?;
+            return Ok((id, kind, StatusCode::OK));
+        }
+    };
 
     sqlx::query!(
         "insert into web_media_library_url (media_id, media_url) values ($1, $2)",
@@ -96,8 +124,9 @@ where media_url = $1"#,
     .await?;
 
     sqlx::query!(
-        "update web_media_upload set processed_at = now(), processing_result = true where media_id = $1",
-        id
+        "update web_media_upload set processed_at = now(), processing_result = true, renditions_at = case when $2 then now() end where media_id = $1",
+        id,
+        matches!(kind, MediaKind::PngStickerImage | MediaKind::SvgStickerImage),
     )
     .execute(&mut txn)
     .await?;
@@ -107,6 +136,109 @@ where media_url = $1"#,
     Ok((id, kind, StatusCode::CREATED))
 }
 
+/// The stored kinds of media which are listed for a [`WebMediaKind`] filter.
+fn filter_kinds(kind: Option<WebMediaKind>) -> Option<Vec<i16>> {
+    let kinds: &[MediaKind] = match kind? {
+        WebMediaKind::Image => &[MediaKind::PngStickerImage, MediaKind::PngCanvasImage],
+        WebMediaKind::SvgImage => &[MediaKind::SvgStickerImage],
+        WebMediaKind::Animation => &[MediaKind::GifAnimation, MediaKind::SpritesheetAnimation],
+    };
+
+    Some(kinds.iter().map(|kind| *kind as i16).collect())
+}
+
+/// Lists the library's media, newest first.
+pub async fn list(
+    pool: &PgPool,
+    query: &WebMediaListQuery,
+) -> anyhow::Result<Vec<WebMediaMetadataResponse>> {
+    let rows = sqlx::query!(
+        r#"
+select id,
+       kind as "kind: MediaKind",
+       created_at,
+       updated_at,
+       created_by as "created_by?: UserId",
+       array(select media_url from web_media_library_url where media_id = id order by created_at) as "urls!"
+from web_media_library
+where ($1::int2[] is null or kind = any($1))
+  and ($2::timestamptz is null or created_at >= $2)
+  and ($3::timestamptz is null or created_at < $3)
+  and ($4::uuid is null or created_by = $4)
+order by created_at desc, id
+limit $5
+offset $6
+"#,
+        filter_kinds(query.kind).as_deref(),
+        query.from_date,
+        query.to_date,
+        query.created_by as Option<UserId>,
+        i64::from(query.page_limit),
+        query.page_limit.offset(query.page),
+    )
+    .fetch_all(pool)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(WebMediaMetadataResponse {
+                id: row.id,
+                kind: row.kind.to_shared(),
+                urls: row
+                    .urls
+                    .iter()
+                    .map(|it| Url::parse(it))
+                    .collect::<Result<Vec<_>, _>>()?,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+                created_by: row.created_by,
+            })
+        })
+        .collect()
+}
+
+/// Counts the library's media matching `query`, ignoring its pagination.
+pub async fn list_count(pool: &PgPool, query: &WebMediaListQuery) -> anyhow::Result<ItemCount> {
+    let count = sqlx::query_scalar!(
+        r#"
+select count(*) as "count!"
+from web_media_library
+where ($1::int2[] is null or kind = any($1))
+  and ($2::timestamptz is null or created_at >= $2)
+  and ($3::timestamptz is null or created_at < $3)
+  and ($4::uuid is null or created_by = $4)
+"#,
+        filter_kinds(query.kind).as_deref(),
+        query.from_date,
+        query.to_date,
+        query.created_by as Option<UserId>,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((count as usize).into())
+}
+
+/// Deletes media from the library along with its urls, returning the kind of each deleted media.
+pub async fn delete(txn: &mut PgConnection, ids: &[Uuid]) -> sqlx::Result<Vec<(Uuid, MediaKind)>> {
+    // the upload table restricts deletes
+    sqlx::query!("delete from web_media_upload where media_id = any($1)", ids)
+        .execute(&mut *txn)
+        .await?;
+
+    let deleted = sqlx::query!(
+        r#"delete from web_media_library where id = any($1) returning id, kind as "kind: MediaKind""#,
+        ids
+    )
+    .fetch_all(&mut *txn)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.kind))
+    .collect();
+
+    Ok(deleted)
+}
+
 async fn process_web_media_bytes(
     s3: &s3::Client,
     id: Uuid,
@@ -119,14 +251,23 @@ async fn process_web_media_bytes(
                 .await?;
         }
         MediaKind::PngStickerImage => {
-            let (original, resized, thumbnail) = actix_web::web::block(move || {
-                let original = image::load_from_memory(&data)?;
+            let files = actix_web::web::block(move || {
+                let original = crate::image_ops::load_image(&data).map_err(anyhow::Error::msg)?;
                 crate::image_ops::generate_images(&original, ImageSize::Sticker)
             })
             .await??;
 
-            s3.upload_png_images(MediaLibrary::Web, id, original, resized, thumbnail)
-                .await?;
+            s3.upload_images(MediaLibrary::Web, id, files).await?;
+        }
+        MediaKind::SvgStickerImage => {
+            let files = actix_web::web::block(move || {
+                let (svg, raster) = crate::image_ops::load_svg(&data, ImageSize::Sticker)
+                    .map_err(anyhow::Error::msg)?;
+                crate::image_ops::generate_svg_images(svg, &raster, ImageSize::Sticker)
+            })
+            .await??;
+
+            s3.upload_images(MediaLibrary::Web, id, files).await?;
         }
         kind => return Err(anyhow::anyhow!("unsupported media kind {:?}", kind)),
     }
@@ -0,0 +1,315 @@
+use shared::domain::{
+    moderation::{
+        ContentFlag, ContentFlagId, ContentFlagListQuery, ContentFlagStatus, FlagReason,
+        FlaggedContentKind,
+    },
+    user::UserId,
+    ItemCount,
+};
+use sqlx::{types::Json, PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Quarantines an item and adds it to the review queue, merging `reasons` into its pending flag if
+/// it already has one.
+///
+/// Items which an admin already approved with the same reasons aren't flagged again, so that
+/// e.g. republishing a JIG doesn't send it back to the queue. Returns whether the item was
+/// flagged.
+pub async fn flag(
+    conn: &mut PgConnection,
+    kind: FlaggedContentKind,
+    item_id: Uuid,
+    reasons: &[FlagReason],
+) -> sqlx::Result<bool> {
+    if reasons.is_empty() {
+        return Ok(false);
+    }
+
+    let reasons = serde_json::to_value(reasons).expect("flag reasons are always serializable");
+
+    let approved = sqlx::query_scalar!(
+        r#"
+select exists(
+    select 1
+    from content_flag
+    where kind = $1
+      and item_id = $2
+      and status = $3
+      and reasons @> $4
+) as "exists!"
+"#,
+        kind as i16,
+        item_id,
+        ContentFlagStatus::Approved as i16,
+        reasons,
+    )
+    .fetch_one(&mut *conn)
+    .await?;
+
+    if approved {
+        return Ok(false);
+    }
+
+    let owner_id = set_quarantined(&mut *conn, kind, item_id, true).await?;
+
+    sqlx::query!(
+        r#"
+insert into content_flag (kind, item_id, owner_id, reasons)
+values ($1, $2, $3, $4)
+on conflict (kind, item_id) where status = 0
+do update set reasons = content_flag.reasons || excluded.reasons,
+              updated_at = now()
+"#,
+        kind as i16,
+        item_id,
+        owner_id,
+        reasons,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(true)
+}
+
+/// Releases an item from quarantine.
+pub async fn release(
+    conn: &mut PgConnection,
+    kind: FlaggedContentKind,
+    item_id: Uuid,
+) -> sqlx::Result<()> {
+    set_quarantined(conn, kind, item_id, false).await?;
+
+    Ok(())
+}
+
+/// Whether an item is quarantined.
+pub async fn is_quarantined(
+    conn: &mut PgConnection,
+    kind: FlaggedContentKind,
+    item_id: Uuid,
+) -> sqlx::Result<bool> {
+    match kind {
+        FlaggedContentKind::UserImage => {
+            sqlx::query_scalar!(
+                r#"select quarantined_at is not null as "quarantined!" from user_image_library where id = $1"#,
+                item_id,
+            )
+            .fetch_optional(conn)
+            .await
+        }
+        FlaggedContentKind::UserPdf => {
+            sqlx::query_scalar!(
+                r#"select quarantined_at is not null as "quarantined!" from user_pdf_library where id = $1"#,
+                item_id,
+            )
+            .fetch_optional(conn)
+            .await
+        }
+        FlaggedContentKind::Circle => {
+            sqlx::query_scalar!(
+                r#"select quarantined_at is not null as "quarantined!" from circle where id = $1"#,
+                item_id,
+            )
+            .fetch_optional(conn)
+            .await
+        }
+        FlaggedContentKind::Jig => {
+            sqlx::query_scalar!(
+                r#"select quarantined_at is not null as "quarantined!" from jig where id = $1"#,
+                item_id,
+            )
+            .fetch_optional(conn)
+            .await
+        }
+    }
+    .map(|quarantined| quarantined.unwrap_or(false))
+}
+
+/// Sets whether an item is quarantined, returning its owner. Items which get indexed are queued to
+/// be reindexed.
+async fn set_quarantined(
+    conn: &mut PgConnection,
+    kind: FlaggedContentKind,
+    item_id: Uuid,
+    quarantined: bool,
+) -> sqlx::Result<Option<Uuid>> {
+    match kind {
+        FlaggedContentKind::UserImage => {
+            sqlx::query_scalar!(
+                r#"
+update user_image_library
+set quarantined_at = case when $2 then now() end
+where id = $1
+returning user_id
+"#,
+                item_id,
+                quarantined,
+            )
+            .fetch_optional(conn)
+            .await
+        }
+        FlaggedContentKind::UserPdf => {
+            sqlx::query_scalar!(
+                r#"
+update user_pdf_library
+set quarantined_at = case when $2 then now() end
+where id = $1
+returning user_id
+"#,
+                item_id,
+                quarantined,
+            )
+            .fetch_optional(conn)
+            .await
+        }
+        FlaggedContentKind::Circle => {
+            sqlx::query_scalar!(
+                r#"
+update circle
+set quarantined_at = case when $2 then now() end,
+    last_synced_at = null
+where id = $1
+returning creator_id as "creator_id?"
+"#,
+                item_id,
+                quarantined,
+            )
+            .fetch_optional(conn)
+            .await
+        }
+        FlaggedContentKind::Jig => {
+            let creator_id = sqlx::query_scalar!(
+                r#"
+update jig
+set quarantined_at = case when $2 then now() end
+where id = $1
+returning creator_id as "creator_id?"
+"#,
+                item_id,
+                quarantined,
+            )
+            .fetch_optional(&mut *conn)
+            .await?;
+
+            // quarantined jigs are indexed as blocked, which filters them out of search
+            sqlx::query!(
+                r#"
+update jig_data
+set last_synced_at = null
+from jig
+where jig.id = $1 and jig_data.id = jig.live_id
+"#,
+                item_id,
+            )
+            .execute(conn)
+            .await?;
+
+            Ok(creator_id)
+        }
+    }
+    .map(Option::flatten)
+}
+
+pub async fn list(db: &PgPool, query: &ContentFlagListQuery) -> sqlx::Result<Vec<ContentFlag>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id!: ContentFlagId",
+       kind as "kind!: FlaggedContentKind",
+       item_id,
+       owner_id as "owner_id: UserId",
+       reasons as "reasons!: Json<Vec<FlagReason>>",
+       status as "status!: ContentFlagStatus",
+       reviewed_by as "reviewed_by: UserId",
+       reviewed_at,
+       created_at
+from content_flag
+where ($1::int2 is null or status = $1)
+  and ($2::int2 is null or kind = $2)
+order by created_at desc
+limit $3
+offset $4
+"#,
+        query.status.map(|it| it as i16),
+        query.kind.map(|it| it as i16),
+        i64::from(query.page_limit),
+        query.page_limit.offset(query.page),
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .map(|row| ContentFlag {
+                id: row.id,
+                kind: row.kind,
+                item_id: row.item_id,
+                owner_id: row.owner_id,
+                reasons: row.reasons.0,
+                status: row.status,
+                reviewed_by: row.reviewed_by,
+                reviewed_at: row.reviewed_at,
+                created_at: row.created_at,
+            })
+            .collect()
+    })
+}
+
+pub async fn count(db: &PgPool, query: &ContentFlagListQuery) -> sqlx::Result<ItemCount> {
+    let count = sqlx::query_scalar!(
+        r#"
+select count(*) as "count!"
+from content_flag
+where ($1::int2 is null or status = $1)
+  and ($2::int2 is null or kind = $2)
+"#,
+        query.status.map(|it| it as i16),
+        query.kind.map(|it| it as i16),
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok((count as usize).into())
+}
+
+/// Finds and locks a flag, returning the item it's on and its status.
+pub async fn get_for_review(
+    conn: &mut PgConnection,
+    id: ContentFlagId,
+) -> sqlx::Result<Option<(FlaggedContentKind, Uuid, ContentFlagStatus)>> {
+    sqlx::query!(
+        r#"
+select kind as "kind: FlaggedContentKind",
+       item_id,
+       status as "status: ContentFlagStatus"
+from content_flag
+where id = $1
+for update
+"#,
+        id.0,
+    )
+    .fetch_optional(conn)
+    .await
+    .map(|row| row.map(|row| (row.kind, row.item_id, row.status)))
+}
+
+pub async fn review(
+    conn: &mut PgConnection,
+    id: ContentFlagId,
+    reviewer: UserId,
+    status: ContentFlagStatus,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"
+update content_flag
+set status = $3, reviewed_by = $2, reviewed_at = now(), updated_at = now()
+where id = $1
+"#,
+        id.0,
+        reviewer.0,
+        status as i16,
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
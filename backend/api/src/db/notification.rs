@@ -0,0 +1,435 @@
+use std::collections::HashMap;
+
+use shared::domain::{
+    circle::CircleId,
+    jig::{curation::CommentId, JigId},
+    notification::{
+        ListNotificationsQuery, Notification, NotificationActor, NotificationId, NotificationKind,
+        NotificationPreference,
+    },
+    user::UserId,
+    ItemCount,
+};
+use sqlx::{PgConnection, PgPool};
+
+// Notifications aren't created for kinds a user has turned off both in app and by email, or for
+// things users do to their own content.
+
+/// Notifies the author of a JIG that `actor_id` liked it.
+pub async fn jig_liked(db: &PgPool, jig_id: JigId, actor_id: UserId) -> sqlx::Result<()> {
+    notify_jig_author(db, NotificationKind::JigLiked, jig_id, actor_id, None).await
+}
+
+/// Notifies the author of a JIG that a curator commented on it.
+pub async fn curation_comment(
+    db: &PgPool,
+    jig_id: JigId,
+    comment_id: CommentId,
+    actor_id: UserId,
+) -> sqlx::Result<()> {
+    notify_jig_author(
+        db,
+        NotificationKind::CurationComment,
+        jig_id,
+        actor_id,
+        Some(comment_id),
+    )
+    .await
+}
+
+async fn notify_jig_author(
+    db: &PgPool,
+    kind: NotificationKind,
+    jig_id: JigId,
+    actor_id: UserId,
+    comment_id: Option<CommentId>,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into notification (user_id, kind, actor_id, jig_id, comment_id)
+select recipient, $1, $2, $3, $4
+from (select coalesce(author_id, creator_id) as recipient from jig where id = $3) jig
+where recipient is not null
+  and recipient <> $2
+  and not exists(select 1
+                 from notification_preference
+                 where user_id = recipient
+                   and kind = $1
+                   and not in_app
+                   and not email)
+"#,
+        kind as i16,
+        actor_id.0,
+        jig_id.0,
+        comment_id.map(|it| it.0),
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Notifies the author of a JIG that it was played.
+///
+/// Plays are counted on the author's unread play notification for the JIG, if there is one.
+pub async fn jig_played(db: &PgPool, jig_id: JigId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into notification (user_id, kind, jig_id)
+select recipient, $2, $1
+from (select coalesce(author_id, creator_id) as recipient from jig where id = $1) jig
+where recipient is not null
+  and not exists(select 1
+                 from notification_preference
+                 where user_id = recipient
+                   and kind = $2
+                   and not in_app
+                   and not email)
+-- the kind has to be written out to match the unread play index, it's `NotificationKind::JigPlayed`
+on conflict (user_id, jig_id) where kind = 1 and read_at is null
+    do update set count      = notification.count + 1,
+                  updated_at = now()
+"#,
+        jig_id.0,
+        NotificationKind::JigPlayed as i16,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Notifies `user_id` that `follower_id` followed them.
+pub async fn followed(db: &PgPool, user_id: UserId, follower_id: UserId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into notification (user_id, kind, actor_id)
+select $1::uuid, $3, $2::uuid
+where $1 <> $2
+  and not exists(select 1
+                 from notification_preference
+                 where user_id = $1
+                   and kind = $3
+                   and not in_app
+                   and not email)
+"#,
+        user_id.0,
+        follower_id.0,
+        NotificationKind::Followed as i16,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Notifies the creator of a circle that `member_id` joined it.
+pub async fn circle_joined(
+    db: &PgPool,
+    circle_id: CircleId,
+    member_id: UserId,
+) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into notification (user_id, kind, actor_id, circle_id)
+select creator_id, $3, $2, id
+from circle
+where id = $1
+  and creator_id <> $2
+  and not exists(select 1
+                 from notification_preference
+                 where user_id = creator_id
+                   and kind = $3
+                   and not in_app
+                   and not email)
+"#,
+        circle_id.0,
+        member_id.0,
+        NotificationKind::CircleJoined as i16,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Lists a user's notifications, leaving out kinds they've turned off in app.
+pub async fn list(
+    db: &PgPool,
+    user_id: UserId,
+    query: &ListNotificationsQuery,
+) -> sqlx::Result<Vec<Notification>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select notification.id                                  as "id!: NotificationId",
+       notification.kind                                as "kind!: NotificationKind",
+       notification.actor_id                            as "actor_id?: UserId",
+       actor.given_name || ' '::text || actor.family_name as "actor_name?",
+       notification.jig_id                              as "jig_id?: JigId",
+       jig_data.display_name                            as "jig_name?",
+       notification.circle_id                           as "circle_id?: CircleId",
+       circle.display_name                              as "circle_name?",
+       notification.comment_id                          as "comment_id?: CommentId",
+       notification.count,
+       notification.read_at is not null                 as "read!",
+       notification.created_at,
+       notification.updated_at
+from notification
+         left join user_profile actor on actor.user_id = notification.actor_id
+         left join jig on jig.id = notification.jig_id
+         left join jig_data on jig_data.id = jig.live_id
+         left join circle on circle.id = notification.circle_id
+where notification.user_id = $1
+  and (not $2 or notification.read_at is null)
+  and not exists(select 1
+                 from notification_preference
+                 where notification_preference.user_id = notification.user_id
+                   and notification_preference.kind = notification.kind
+                   and not in_app)
+order by notification.updated_at desc
+limit $3
+offset $4
+"#,
+        user_id.0,
+        query.unread_only,
+        i64::from(query.page_limit),
+        query.page_limit.offset(query.page),
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .map(|row| Notification {
+                id: row.id,
+                kind: row.kind,
+                actor: row.actor_id.map(|id| NotificationActor {
+                    id,
+                    name: row.actor_name.unwrap_or_default(),
+                }),
+                jig_id: row.jig_id,
+                jig_name: row.jig_name,
+                circle_id: row.circle_id,
+                circle_name: row.circle_name,
+                comment_id: row.comment_id,
+                count: row.count as u32,
+                read: row.read,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+            .collect()
+    })
+}
+
+/// Counts a user's notifications, leaving out kinds they've turned off in app.
+pub async fn count(db: &PgPool, user_id: UserId, unread_only: bool) -> sqlx::Result<ItemCount> {
+    let count = sqlx::query_scalar!(
+        //language=SQL
+        r#"
+select count(*) as "count!"
+from notification
+where user_id = $1
+  and (not $2 or read_at is null)
+  and not exists(select 1
+                 from notification_preference
+                 where notification_preference.user_id = notification.user_id
+                   and notification_preference.kind = notification.kind
+                   and not in_app)
+"#,
+        user_id.0,
+        unread_only,
+    )
+    .fetch_one(db)
+    .await?;
+
+    Ok((count as usize).into())
+}
+
+/// Marks one of a user's notifications as read. Returns `false` if they don't have such a
+/// notification.
+pub async fn mark_read(db: &PgPool, user_id: UserId, id: NotificationId) -> sqlx::Result<bool> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update notification
+set read_at = coalesce(read_at, now())
+where id = $1
+  and user_id = $2
+"#,
+        id.0,
+        user_id.0,
+    )
+    .execute(db)
+    .await
+    .map(|res| res.rows_affected() > 0)
+}
+
+pub async fn mark_all_read(db: &PgPool, user_id: UserId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+update notification
+set read_at = now()
+where user_id = $1
+  and read_at is null
+"#,
+        user_id.0,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Gets a user's preference for every kind of notification.
+pub async fn get_preferences(
+    db: &PgPool,
+    user_id: UserId,
+) -> sqlx::Result<Vec<NotificationPreference>> {
+    let saved: HashMap<NotificationKind, NotificationPreference> = sqlx::query!(
+        //language=SQL
+        r#"
+select kind as "kind!: NotificationKind",
+       in_app,
+       email
+from notification_preference
+where user_id = $1
+"#,
+        user_id.0,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| {
+        (
+            row.kind,
+            NotificationPreference {
+                kind: row.kind,
+                in_app: row.in_app,
+                email: row.email,
+            },
+        )
+    })
+    .collect();
+
+    Ok(NotificationKind::ALL
+        .iter()
+        .map(|kind| {
+            saved
+                .get(kind)
+                .copied()
+                .unwrap_or_else(|| NotificationPreference::default_for(*kind))
+        })
+        .collect())
+}
+
+pub async fn update_preferences(
+    db: &PgPool,
+    user_id: UserId,
+    preferences: &[NotificationPreference],
+) -> sqlx::Result<()> {
+    let kinds: Vec<i16> = preferences.iter().map(|it| it.kind as i16).collect();
+    let in_app: Vec<bool> = preferences.iter().map(|it| it.in_app).collect();
+    let email: Vec<bool> = preferences.iter().map(|it| it.email).collect();
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into notification_preference (user_id, kind, in_app, email)
+select $1, kind, in_app, email
+from unnest($2::smallint[], $3::bool[], $4::bool[]) as t(kind, in_app, email)
+on conflict (user_id, kind) do update set in_app = excluded.in_app,
+                                          email  = excluded.email
+"#,
+        user_id.0,
+        &kinds,
+        &in_app,
+        &email,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// A user who's due a digest email.
+pub struct Digest {
+    pub user_id: UserId,
+    pub email: String,
+    pub given_name: String,
+    pub language: String,
+    /// Count of unread events by kind since the last digest.
+    pub counts: Vec<(NotificationKind, u32)>,
+}
+
+/// Finds users who haven't had a digest in the last week and have unread notifications they want
+/// emailed about since their last one.
+pub async fn due_digests(db: &PgPool) -> sqlx::Result<Vec<Digest>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select notification.user_id         as "user_id!: UserId",
+       user_email.email::text       as "email!",
+       user_profile.given_name,
+       user_profile.language_emails,
+       notification.kind            as "kind!: NotificationKind",
+       sum(notification.count)      as "count!"
+from notification
+         inner join user_email on user_email.user_id = notification.user_id
+         inner join user_profile on user_profile.user_id = notification.user_id
+         left join notification_digest on notification_digest.user_id = notification.user_id
+where notification.read_at is null
+  and (notification_digest.sent_at is null or notification_digest.sent_at <= now() - interval '7 days')
+  and notification.updated_at > coalesce(notification_digest.sent_at, now() - interval '7 days')
+  and not exists(select 1
+                 from notification_preference
+                 where notification_preference.user_id = notification.user_id
+                   and notification_preference.kind = notification.kind
+                   and not notification_preference.email)
+group by notification.user_id, user_email.email, user_profile.given_name,
+         user_profile.language_emails, notification.kind
+order by notification.user_id, notification.kind
+"#,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut digests: Vec<Digest> = Vec::new();
+
+    for row in rows {
+        match digests.last_mut() {
+            Some(digest) if digest.user_id == row.user_id => {
+                digest.counts.push((row.kind, row.count as u32))
+            }
+            _ => digests.push(Digest {
+                user_id: row.user_id,
+                email: row.email,
+                given_name: row.given_name,
+                language: row.language_emails,
+                counts: vec![(row.kind, row.count as u32)],
+            }),
+        }
+    }
+
+    Ok(digests)
+}
+
+pub async fn record_digest(db: &mut PgConnection, user_id: UserId) -> sqlx::Result<()> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into notification_digest (user_id, sent_at)
+values ($1, now())
+on conflict (user_id) do update set sent_at = excluded.sent_at
+"#,
+        user_id.0,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 pub mod user {
-    use futures::stream::BoxStream;
+    use futures::{stream::BoxStream, StreamExt, TryStreamExt};
     use shared::domain::{
         pdf::{user::UserPdf, PdfId},
         user::UserId,
@@ -30,28 +30,43 @@ returning id as "id: PdfId"
         Ok(id)
     }
 
-    pub async fn delete(db: &PgPool, pdf: PdfId) -> sqlx::Result<()> {
-        sqlx::query!("delete from user_pdf_library where id = $1", pdf.0)
-            .execute(db)
-            .await
-            .map(drop)
+    /// Deletes a pdf, returning how many page previews it had (if it existed).
+    pub async fn delete(db: &PgPool, pdf: PdfId) -> sqlx::Result<Option<u32>> {
+        sqlx::query!(
+            "delete from user_pdf_library where id = $1 returning preview_page_count",
+            pdf.0
+        )
+        .fetch_optional(db)
+        .await
+        .map(|row| row.map(|row| row.preview_page_count as u32))
     }
 
     pub async fn get(db: &PgPool, pdf: PdfId) -> sqlx::Result<Option<UserPdf>> {
-        sqlx::query_as!(
-            UserPdf,
-            r#"select id as "id: PdfId" from user_pdf_library where id = $1"#,
+        let pdf = sqlx::query!(
+            r#"select id as "id: PdfId", page_count, preview_page_count from user_pdf_library where id = $1"#,
             pdf.0
         )
         .fetch_optional(db)
-        .await
+        .await?
+        .map(|row| UserPdf {
+            id: row.id,
+            page_count: row.page_count.map(|it| it as u32),
+            preview_page_count: row.preview_page_count as u32,
+        });
+
+        Ok(pdf)
     }
 
     pub fn list(db: &PgPool) -> BoxStream<'_, sqlx::Result<UserPdf>> {
-        sqlx::query_as!(
-            UserPdf,
-            r#"select id as "id: PdfId" from user_pdf_library order by created_at desc"#,
+        sqlx::query!(
+            r#"select id as "id: PdfId", page_count, preview_page_count from user_pdf_library where quarantined_at is null order by created_at desc"#,
         )
         .fetch(db)
+        .map_ok(|row| UserPdf {
+            id: row.id,
+            page_count: row.page_count.map(|it| it as u32),
+            preview_page_count: row.preview_page_count as u32,
+        })
+        .boxed()
     }
 }
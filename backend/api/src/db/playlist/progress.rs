@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use shared::domain::{
+    asset::{DraftOrLive, ProgressStatus, ProgressSummary},
+    jig::{codes::PointsEarned, JigId},
+    playlist::{
+        progress::{PlaylistInProgress, PlaylistItemProgress, PlaylistProgressResponse},
+        PlaylistId,
+    },
+    user::UserId,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::db;
+
+/// Whether the JIG is an item of either the draft or live data of the playlist.
+pub async fn jig_in_playlist(
+    db: &PgPool,
+    playlist_id: PlaylistId,
+    jig_id: JigId,
+) -> sqlx::Result<bool> {
+    let exists = sqlx::query!(
+        //language=SQL
+        r#"
+select exists (
+    select 1
+    from playlist_data_jig
+    join playlist on playlist_data_id in (playlist.draft_id, playlist.live_id)
+    where playlist.id = $1
+      and jig_id = $2
+) as "exists!"
+"#,
+        playlist_id.0,
+        jig_id.0,
+    )
+    .fetch_one(db)
+    .await?
+    .exists;
+
+    Ok(exists)
+}
+
+/// Records progress on a JIG in the playlist. A JIG never goes back from completed to started,
+/// and the score is only replaced when a new one is given.
+pub async fn upsert_item(
+    db: &PgPool,
+    user_id: UserId,
+    playlist_id: PlaylistId,
+    jig_id: JigId,
+    status: ProgressStatus,
+    points_earned: Option<PointsEarned>,
+) -> sqlx::Result<()> {
+    let (earned, available) = points_earned
+        .map(|points| (Some(points.earned), Some(points.available)))
+        .unwrap_or_default();
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+insert into playlist_jig_progress (user_id, playlist_id, jig_id, status, score_earned, score_available, completed_at)
+values ($1, $2, $3, $4, $5, $6, case when $4 = 1::int2 then now() end)
+on conflict (user_id, playlist_id, jig_id) do update
+set status          = greatest(playlist_jig_progress.status, excluded.status),
+    score_earned    = coalesce(excluded.score_earned, playlist_jig_progress.score_earned),
+    score_available = coalesce(excluded.score_available, playlist_jig_progress.score_available),
+    completed_at    = coalesce(playlist_jig_progress.completed_at, excluded.completed_at),
+    updated_at      = now()
+"#,
+        user_id.0,
+        playlist_id.0,
+        jig_id.0,
+        status as i16,
+        earned,
+        available,
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Gets the user's progress measured against the live items of the playlist.
+pub async fn get(
+    db: &PgPool,
+    user_id: UserId,
+    playlist_id: PlaylistId,
+) -> sqlx::Result<Option<PlaylistProgressResponse>> {
+    Ok(get_many(db, user_id, &[playlist_id])
+        .await?
+        .remove(&playlist_id))
+}
+
+/// Lists the playlists the user has made progress in, most recently played first.
+pub async fn list_in_progress(
+    db: &PgPool,
+    user_id: UserId,
+    include_completed: bool,
+    page: u32,
+    page_limit: u32,
+) -> sqlx::Result<Vec<PlaylistInProgress>> {
+    let ids: Vec<PlaylistId> = sqlx::query!(
+        //language=SQL
+        r#"
+select progress.playlist_id as "playlist_id!: PlaylistId"
+from playlist_jig_progress "progress"
+join playlist on playlist.id = progress.playlist_id
+where progress.user_id = $1
+group by progress.playlist_id, playlist.live_id
+having $2 or count(*) filter (
+    where progress.status = 1
+      and progress.jig_id in (select jig_id from playlist_data_jig where playlist_data_id = playlist.live_id)
+) < (select count(*) from playlist_data_jig where playlist_data_id = playlist.live_id)
+order by max(progress.updated_at) desc
+offset $3
+limit $4
+"#,
+        user_id.0,
+        include_completed,
+        (page * page_limit) as i32,
+        page_limit as i32,
+    )
+    .fetch_all(db)
+    .await?
+    .into_iter()
+    .map(|row| row.playlist_id)
+    .collect();
+
+    let mut playlists: HashMap<PlaylistId, _> =
+        db::playlist::get_by_ids(db, &ids, DraftOrLive::Live, Some(user_id))
+            .await?
+            .into_iter()
+            .map(|playlist| (playlist.id, playlist))
+            .collect();
+
+    let mut progress = get_many(db, user_id, &ids).await?;
+
+    Ok(ids
+        .iter()
+        .filter_map(|id| {
+            Some(PlaylistInProgress {
+                playlist: playlists.remove(id)?,
+                progress: progress.remove(id)?,
+            })
+        })
+        .collect())
+}
+
+/// Gets the user's progress through each of the playlists which exist, measured against their
+/// live items.
+async fn get_many(
+    db: &PgPool,
+    user_id: UserId,
+    ids: &[PlaylistId],
+) -> sqlx::Result<HashMap<PlaylistId, PlaylistProgressResponse>> {
+    let uuids: Vec<Uuid> = ids.iter().map(|id| id.0).collect();
+
+    let mut live_items: HashMap<PlaylistId, Vec<JigId>> = HashMap::new();
+
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select playlist.id as "playlist_id!: PlaylistId",
+       jig_id      as "jig_id?: JigId"
+from playlist
+left join playlist_data_jig on playlist_data_jig.playlist_data_id = playlist.live_id
+where playlist.id = any($1)
+order by playlist.id, playlist_data_jig.index
+"#,
+        &uuids,
+    )
+    .fetch_all(db)
+    .await?;
+
+    for row in rows {
+        let items = live_items.entry(row.playlist_id).or_default();
+        items.extend(row.jig_id);
+    }
+
+    let mut progress: HashMap<PlaylistId, Vec<PlaylistItemProgress>> = HashMap::new();
+
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select playlist_id     as "playlist_id!: PlaylistId",
+       jig_id          as "jig_id!: JigId",
+       status          as "status!: ProgressStatus",
+       score_earned,
+       score_available,
+       started_at,
+       completed_at,
+       updated_at
+from playlist_jig_progress
+where user_id = $1
+  and playlist_id = any($2)
+"#,
+        user_id.0,
+        &uuids,
+    )
+    .fetch_all(db)
+    .await?;
+
+    for row in rows {
+        progress
+            .entry(row.playlist_id)
+            .or_default()
+            .push(PlaylistItemProgress {
+                jig_id: row.jig_id,
+                status: row.status,
+                points_earned: points_earned(row.score_earned, row.score_available),
+                started_at: row.started_at,
+                completed_at: row.completed_at,
+                updated_at: row.updated_at,
+            });
+    }
+
+    Ok(live_items
+        .into_iter()
+        .map(|(playlist_id, jig_ids)| {
+            let summary =
+                ProgressSummary::new(&jig_ids, progress.remove(&playlist_id).unwrap_or_default());
+
+            let response = PlaylistProgressResponse {
+                playlist_id,
+                items: summary.items,
+                item_count: summary.item_count,
+                completed_count: summary.completed_count,
+                percent_complete: summary.percent_complete,
+                continue_jig_id: summary.continue_from,
+                last_played_at: summary.last_played_at,
+            };
+
+            (playlist_id, response)
+        })
+        .collect())
+}
+
+fn points_earned(earned: Option<f32>, available: Option<f32>) -> Option<PointsEarned> {
+    match (earned, available) {
+        (Some(earned), Some(available)) => Some(PointsEarned { available, earned }),
+        _ => None,
+    }
+}
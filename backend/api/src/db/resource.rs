@@ -1,4 +1,4 @@
-use crate::translate::translate_text;
+use crate::translate::TranslationProvider;
 use anyhow::Context;
 use serde_json::value::Value;
 use shared::domain::resource::ResourceUpdateAdminDataRequest;
@@ -639,7 +639,7 @@ limit $8
 
 pub async fn update_draft(
     pool: &PgPool,
-    api_key: &Option<String>,
+    translator: Option<&dyn TranslationProvider>,
     id: ResourceId,
     display_name: Option<&str>,
     categories: Option<&[CategoryId]>,
@@ -696,10 +696,13 @@ where id = $1 and $2 is distinct from description"#,
     }
 
     if let Some(other_keywords) = other_keywords {
-        let translate_text = match &api_key {
-            Some(key) => translate_text(&other_keywords, "he", "en", key)
-                .await
-                .context("could not translate text")?,
+        let translate_text = match translator {
+            Some(translator) => Some(
+                translator
+                    .translate(&other_keywords, "en", "iw")
+                    .await
+                    .context("could not translate text")?,
+            ),
             None => None,
         };
 
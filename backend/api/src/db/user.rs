@@ -24,6 +24,7 @@ use std::{convert::TryFrom, fmt::Debug, str::FromStr};
 use tracing::{instrument, Instrument};
 use uuid::Uuid;
 
+pub(crate) mod media;
 pub(crate) mod public_user;
 
 use super::{nul_if_empty, recycle_metadata};
@@ -1254,6 +1255,26 @@ where user_id = $1
     Ok(given_name)
 }
 
+/// The language a user wants their emails in, if they've created a profile.
+pub async fn get_email_language(
+    txn: &mut PgConnection,
+    user_id: UserId,
+) -> sqlx::Result<Option<String>> {
+    let language = sqlx::query!(
+        r#"
+select language_emails
+from user_profile
+where user_id = $1
+        "#,
+        user_id.0
+    )
+    .fetch_optional(txn)
+    .await?
+    .map(|it| it.language_emails);
+
+    Ok(language)
+}
+
 // keeping the struct here because it's so far only used here
 pub struct Location {
     pub city: Option<String>,
@@ -0,0 +1,321 @@
+//! A user's uploads across the user image, audio and pdf libraries.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use futures::TryStreamExt;
+use shared::domain::{
+    jig::JigId,
+    user::{UserId, UserMedia, UserMediaKind},
+};
+use sqlx::{Executor, PgConnection, PgPool, Postgres};
+use uuid::Uuid;
+
+use crate::service::upload::references::LiveMedia;
+
+/// Bytes stored for all of the user's uploads, not counting `excluding` (a file being replaced).
+pub async fn storage_used<'c, E: Executor<'c, Database = Postgres>>(
+    db: E,
+    user_id: UserId,
+    excluding: Option<Uuid>,
+) -> sqlx::Result<u64> {
+    let used = sqlx::query!(
+        //language=SQL
+        r#"
+select coalesce(sum(size_bytes), 0)::bigint as "used!"
+from (select id, size_bytes from user_image_library where user_id = $1
+      union all
+      select id, size_bytes from user_audio_library where user_id = $1
+      union all
+      select id, size_bytes from user_pdf_library where user_id = $1) as media
+where id is distinct from $2
+"#,
+        user_id.0,
+        excluding,
+    )
+    .fetch_one(db)
+    .await?
+    .used;
+
+    Ok(used as u64)
+}
+
+/// Locks the user, so that their uploads are stored one at a time and can't go over their quota
+/// together.
+pub async fn lock_storage(txn: &mut PgConnection, user_id: UserId) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"select 1 as "locked!" from "user" where id = $1 for no key update"#,
+        user_id.0
+    )
+    .fetch_optional(&mut *txn)
+    .await?;
+
+    Ok(())
+}
+
+/// Lists the user's uploads (only those in `ids`, if given), most recent first.
+///
+/// References are found by scanning the contents of the user's own JIGs, playlists, resources and
+/// courses for the IDs they contain, see [`LiveMedia`].
+pub async fn list(
+    db: &PgPool,
+    user_id: UserId,
+    ids: Option<&[Uuid]>,
+) -> sqlx::Result<Vec<UserMedia>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id!", kind as "kind!", size_bytes as "size_bytes!", created_at as "created_at!"
+from (select id, 'image' as kind, size_bytes, created_at from user_image_library where user_id = $1
+      union all
+      select id, 'audio' as kind, size_bytes, created_at from user_audio_library where user_id = $1
+      union all
+      select id, 'pdf' as kind, size_bytes, created_at from user_pdf_library where user_id = $1) as media
+where $2::uuid[] is null or id = any($2)
+order by created_at desc
+"#,
+        user_id.0,
+        ids,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let listed: HashSet<Uuid> = rows.iter().map(|row| row.id).collect();
+
+    let mut referenced_by: HashMap<Uuid, BTreeSet<Uuid>> = HashMap::new();
+    let mut used_elsewhere = HashSet::new();
+
+    // content of the user's JIGs comes with the JIG's ID, anything else without one
+    let mut content = sqlx::query!(
+        //language=SQL
+        r#"
+select jig.id as "jig_id?", contents as "content!"
+from jig
+inner join jig_data_module on jig_data_id in (jig.draft_id, jig.live_id)
+where jig.creator_id = $1
+union all
+select jig.id, resource_content
+from jig
+inner join jig_data_additional_resource on jig_data_id in (jig.draft_id, jig.live_id)
+where jig.creator_id = $1
+union all
+select null, contents
+from resource
+inner join resource_data_module on resource_data_id in (resource.draft_id, resource.live_id)
+where resource.creator_id = $1
+union all
+select null, resource_content
+from resource
+inner join resource_data_resource on resource_data_id in (resource.draft_id, resource.live_id)
+where resource.creator_id = $1
+union all
+select null, contents
+from playlist
+inner join playlist_data_module on playlist_data_id in (playlist.draft_id, playlist.live_id)
+where playlist.creator_id = $1
+union all
+select null, resource_content
+from playlist
+inner join playlist_data_resource on playlist_data_id in (playlist.draft_id, playlist.live_id)
+where playlist.creator_id = $1
+union all
+select null, contents
+from course
+inner join course_data_module on course_data_id in (course.draft_id, course.live_id)
+where course.creator_id = $1
+union all
+select null, resource_content
+from course
+inner join course_data_resource on course_data_id in (course.draft_id, course.live_id)
+where course.creator_id = $1
+union all
+select null, value
+from course
+inner join course_data_unit on course_data_id in (course.draft_id, course.live_id)
+where course.creator_id = $1
+union all
+select null, to_jsonb(profile_image_id)
+from user_profile
+where user_id = $1 and profile_image_id is not null
+union all
+select null, to_jsonb(profile_image_id)
+from school
+where profile_image_id in (select id from user_image_library where user_id = $1)
+"#,
+        user_id.0,
+    )
+    .fetch(db);
+
+    while let Some(row) = content.try_next().await? {
+        let found = LiveMedia::of(&row.content);
+
+        for id in found.iter().filter(|id| listed.contains(*id)) {
+            match row.jig_id {
+                Some(jig_id) => {
+                    referenced_by.entry(*id).or_default().insert(jig_id);
+                }
+                None => {
+                    used_elsewhere.insert(*id);
+                }
+            }
+        }
+    }
+
+    let media = rows
+        .into_iter()
+        .map(|row| UserMedia {
+            id: row.id,
+            kind: match row.kind.as_str() {
+                "image" => UserMediaKind::Image,
+                "audio" => UserMediaKind::Audio,
+                _ => UserMediaKind::Pdf,
+            },
+            size_bytes: row.size_bytes as u64,
+            created_at: row.created_at,
+            referenced_by: referenced_by
+                .remove(&row.id)
+                .unwrap_or_default()
+                .into_iter()
+                .map(JigId)
+                .collect(),
+            used_elsewhere: used_elsewhere.contains(&row.id),
+        })
+        .collect();
+
+    Ok(media)
+}
+
+/// Deletes uploads of the user from the library tables, returning how many pdf page previews each
+/// deleted pdf had.
+///
+/// Callers are expected to have checked that nothing uses the uploads.
+pub async fn delete(
+    txn: &mut PgConnection,
+    user_id: UserId,
+    ids: &[Uuid],
+) -> sqlx::Result<Vec<(Uuid, u32)>> {
+    sqlx::query!(
+        "delete from user_image_library where user_id = $1 and id = any($2)",
+        user_id.0,
+        ids,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    // the upload tables restrict deletes of audio and pdfs
+    sqlx::query!(
+        r#"
+delete from user_audio_upload
+where audio_id = any($2) and audio_id in (select id from user_audio_library where user_id = $1)
+"#,
+        user_id.0,
+        ids,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    sqlx::query!(
+        "delete from user_audio_library where user_id = $1 and id = any($2)",
+        user_id.0,
+        ids,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+delete from user_pdf_upload
+where pdf_id = any($2) and pdf_id in (select id from user_pdf_library where user_id = $1)
+"#,
+        user_id.0,
+        ids,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    let pdfs = sqlx::query!(
+        "delete from user_pdf_library where user_id = $1 and id = any($2) returning id, preview_page_count",
+        user_id.0,
+        ids,
+    )
+    .fetch_all(&mut *txn)
+    .await?
+    .into_iter()
+    .map(|row| (row.id, row.preview_page_count as u32))
+    .collect();
+
+    Ok(pdfs)
+}
+
+/// Lists up to `limit` uploads whose size wasn't counted when they were stored.
+pub async fn list_uncounted(db: &PgPool, limit: i64) -> sqlx::Result<Vec<(Uuid, UserMediaKind)>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id!", kind as "kind!"
+from (select id, 'image' as kind from user_image_library where not size_counted
+      union all
+      select id, 'audio' as kind from user_audio_library where not size_counted
+      union all
+      select id, 'pdf' as kind from user_pdf_library where not size_counted) as media
+limit $1
+"#,
+        limit,
+    )
+    .fetch_all(db)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let kind = match row.kind.as_str() {
+                "image" => UserMediaKind::Image,
+                "audio" => UserMediaKind::Audio,
+                _ => UserMediaKind::Pdf,
+            };
+
+            (row.id, kind)
+        })
+        .collect())
+}
+
+/// Sets the stored size of an upload which wasn't counted, see [`list_uncounted`].
+pub async fn set_counted_size(
+    db: &PgPool,
+    id: Uuid,
+    kind: UserMediaKind,
+    size_bytes: u64,
+) -> sqlx::Result<()> {
+    let size_bytes = size_bytes as i64;
+
+    match kind {
+        UserMediaKind::Image => {
+            sqlx::query!(
+                "update user_image_library set size_bytes = $2, size_counted = true where id = $1",
+                id,
+                size_bytes,
+            )
+            .execute(db)
+            .await?;
+        }
+        UserMediaKind::Audio => {
+            sqlx::query!(
+                "update user_audio_library set size_bytes = $2, size_counted = true where id = $1",
+                id,
+                size_bytes,
+            )
+            .execute(db)
+            .await?;
+        }
+        UserMediaKind::Pdf => {
+            sqlx::query!(
+                "update user_pdf_library set size_bytes = $2, size_counted = true where id = $1",
+                id,
+                size_bytes,
+            )
+            .execute(db)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -7,14 +7,15 @@ use actix_web::{
 use crate::db::{self, account::AccountMember};
 use chrono::{DateTime, Utc};
 use http::StatusCode;
-use shared::domain::billing::AccountId;
+use shared::domain::admin::InviteFailedReason;
+use shared::domain::billing::{AccountId, SubscriptionStatus};
 use shared::domain::user::{UserId, UserScope};
 use shared::domain::{
     category::{Category, CategoryId},
     session::AUTH_COOKIE_NAME,
 };
 use shared::error::{AccountError, IntoAnyhow};
-use sqlx::PgPool;
+use sqlx::{types::Json, PgPool};
 use std::{cell::RefCell, convert::TryFrom, rc::Rc};
 use std::{collections::HashMap, fmt};
 use tracing::instrument;
@@ -25,6 +26,7 @@ pub struct RawCategory {
     pub id: Uuid,
     pub parent_id: Option<Uuid>,
     pub name: String,
+    pub translated_name: Json<HashMap<String, String>>,
     pub index: i16,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
@@ -35,6 +37,7 @@ pub struct RawCategory {
 struct CategoryNode {
     pub id: Uuid,
     pub name: String,
+    pub translated_name: HashMap<String, String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
     pub user_scopes: Vec<i16>,
@@ -46,6 +49,7 @@ impl From<CategoryNode> for Category {
         Self {
             id: CategoryId(category_node.id),
             name: category_node.name,
+            translated_name: category_node.translated_name,
             created_at: category_node.created_at,
             updated_at: category_node.updated_at,
             user_scopes: {
@@ -81,6 +85,7 @@ pub fn build_tree(categories: Vec<RawCategory>) -> Vec<Category> {
             Rc::new(RefCell::new(CategoryNode {
                 id: raw.id.clone(),
                 name: raw.name.clone(),
+                translated_name: raw.translated_name.0.clone(),
                 created_at: raw.created_at,
                 updated_at: raw.updated_at,
                 user_scopes: raw.user_scopes.clone(),
@@ -221,3 +226,45 @@ pub async fn user_authorization(
         },
     )
 }
+
+/// Whether a user is able to join a school account.
+#[derive(Debug)]
+pub enum SchoolEligibility {
+    /// The user doesn't have an account yet
+    Eligible,
+    /// The user has an individual account without a valid subscription, which has to be deleted
+    /// before they join the school
+    ReplacesIndividualAccount,
+    /// The user can't join the school
+    Ineligible(InviteFailedReason),
+}
+
+/// Checks whether a user can join a school, whether through an invite or being added by an admin.
+pub async fn school_eligibility(
+    db: &PgPool,
+    user_id: &UserId,
+) -> anyhow::Result<SchoolEligibility> {
+    if db::user::get_profile(db, user_id).await?.is_none() {
+        // Incomplete profiles cause a bunch of queries to fail, so we prevent them from being added
+        // to schools until their profile is complete.
+        return Ok(SchoolEligibility::Ineligible(
+            InviteFailedReason::IncompleteProfile,
+        ));
+    }
+
+    let Some(account_summary) = db::account::get_user_account_summary(db, user_id).await? else {
+        return Ok(SchoolEligibility::Eligible);
+    };
+
+    Ok(match account_summary.school_id {
+        Some(_) => SchoolEligibility::Ineligible(InviteFailedReason::AssociatedWithSchool),
+        None => match account_summary.subscription_status {
+            // If they have an account with an expired subscription or no subscription, then that
+            // account can be deleted so that they can be added to a school account.
+            Some(SubscriptionStatus::Expired) | None => {
+                SchoolEligibility::ReplacesIndividualAccount
+            }
+            _ => SchoolEligibility::Ineligible(InviteFailedReason::HasIndividualAccount),
+        },
+    })
+}
@@ -12,6 +12,7 @@ use shared::error::{EmptyError, MetadataNotFound};
 use shared::error::{ServiceError, ServiceKindError};
 
 use crate::db::meta::MetaWrapperError;
+use crate::media_type::MediaError;
 
 mod oauth;
 pub use oauth::{GoogleOAuth, OAuth};
@@ -321,14 +322,210 @@ impl Into<actix_web::Error> for CategoryUpdate {
     }
 }
 
+pub enum CategoryMerge {
+    CategoryNotFound,
+    TargetCategoryNotFound,
+    Cycle,
+    InternalServerError(anyhow::Error),
+}
+
+impl<T: Into<anyhow::Error>> From<T> for CategoryMerge {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for CategoryMerge {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::CategoryNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Category Not Found".to_owned(),
+            )
+            .into(),
+
+            Self::TargetCategoryNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Target Category Not Found".to_owned(),
+            )
+            .into(),
+
+            Self::Cycle => BasicError::with_message(
+                http::StatusCode::UNPROCESSABLE_ENTITY,
+                "Would cause a cycle".to_owned(),
+            )
+            .into(),
+
+            Self::InternalServerError(e) => ise(e),
+        }
+    }
+}
+
+pub enum Recategorize {
+    CategoryNotFound,
+    AddedAndRemoved,
+    InternalServerError(anyhow::Error),
+}
+
+impl<T: Into<anyhow::Error>> From<T> for Recategorize {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for Recategorize {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::CategoryNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Category Not Found".to_owned(),
+            )
+            .into(),
+
+            Self::AddedAndRemoved => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "A category can't be both added and removed".to_owned(),
+            )
+            .into(),
+
+            Self::InternalServerError(e) => ise(e),
+        }
+    }
+}
+
+pub enum ImageBulk {
+    EmptyQuery,
+    AddedAndRemoved,
+    NotFound,
+    AlreadyUndone,
+    MissingMetadata(MetadataNotFound),
+    InternalServerError(anyhow::Error),
+}
+
+impl<T: Into<anyhow::Error>> From<T> for ImageBulk {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl From<MetaWrapperError> for ImageBulk {
+    fn from(e: MetaWrapperError) -> Self {
+        match e {
+            MetaWrapperError::Sqlx(e) => Self::InternalServerError(e.into()),
+            MetaWrapperError::MissingMetadata { id, kind } => {
+                Self::MissingMetadata(MetadataNotFound {
+                    id,
+                    index: None,
+                    kind,
+                    media_group_kind: None,
+                })
+            }
+            MetaWrapperError::MissingTag {
+                index,
+                media_group_kind,
+            } => Self::MissingMetadata(MetadataNotFound {
+                id: None,
+                index,
+                kind: MetaKind::Tag,
+                media_group_kind: Some(media_group_kind),
+            }),
+        }
+    }
+}
+
+impl Into<actix_web::Error> for ImageBulk {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::EmptyQuery => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "At least one filter must be set".to_owned(),
+            )
+            .into(),
+
+            Self::AddedAndRemoved => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Metadata can't be both added and removed".to_owned(),
+            )
+            .into(),
+
+            Self::NotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Bulk Update Not Found".to_owned(),
+            )
+            .into(),
+
+            Self::AlreadyUndone => BasicError::with_message(
+                http::StatusCode::CONFLICT,
+                "Bulk Update Already Undone".to_owned(),
+            )
+            .into(),
+
+            Self::MissingMetadata(data) => ApiResponseError {
+                code: http::StatusCode::UNPROCESSABLE_ENTITY,
+                message: "Metadata not Found".to_owned(),
+                extra: data,
+            }
+            .into(),
+
+            Self::InternalServerError(e) => ise(e),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ContentFlag {
+    NotFound,
+    AlreadyReviewed,
+    InternalServerError(anyhow::Error),
+}
+
+impl<T: Into<anyhow::Error>> From<T> for ContentFlag {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for ContentFlag {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::NotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Content Flag Not Found".to_owned(),
+            )
+            .into(),
+
+            Self::AlreadyReviewed => BasicError::with_message(
+                http::StatusCode::CONFLICT,
+                "Content Flag Already Reviewed".to_owned(),
+            )
+            .into(),
+
+            Self::InternalServerError(e) => ise(e),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Upload {
     ResourceNotFound,
     InvalidMedia,
     FileTooLarge,
+    /// The file's contents were rejected, see [`MediaError`].
+    Media(MediaError),
+    /// Storing the file would take the user past their plan's storage quota.
+    QuotaExceeded {
+        used: u64,
+        quota: u64,
+    },
     InternalServerError(anyhow::Error),
 }
 
+impl From<MediaError> for Upload {
+    fn from(e: MediaError) -> Self {
+        Self::Media(e)
+    }
+}
+
 impl<T: Into<anyhow::Error>> From<T> for Upload {
     fn from(e: T) -> Self {
         Self::InternalServerError(e.into())
@@ -353,6 +550,26 @@ impl Into<actix_web::Error> for Upload {
                 "File Exceeds Upload Limit".to_owned(),
             )
             .into(),
+            Self::Media(e) => {
+                let status = match e {
+                    MediaError::Unsupported { .. } => http::StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    MediaError::TooLarge { .. }
+                    | MediaError::AudioTooLong
+                    | MediaError::Encrypted
+                    | MediaError::Malformed => http::StatusCode::UNPROCESSABLE_ENTITY,
+                };
+
+                BasicError::with_message(status, e.to_string()).into()
+            }
+            Self::QuotaExceeded { used, quota } => BasicError::with_message(
+                http::StatusCode::INSUFFICIENT_STORAGE,
+                format!(
+                    "Storage quota exceeded, {} MB of {} MB used",
+                    used / (1024 * 1024),
+                    quota / (1024 * 1024)
+                ),
+            )
+            .into(),
             Self::InternalServerError(e) => ise(e),
         }
     }
@@ -563,6 +780,7 @@ pub enum CloneDraft {
     UnprocessableEntity,
     IncompleteModules,
     Conflict,
+    UnsupportedLanguage,
     Forbidden,
     InternalServerError(anyhow::Error),
 }
@@ -610,6 +828,12 @@ impl Into<actix_web::Error> for CloneDraft {
             )
             .into(),
 
+            Self::UnsupportedLanguage => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Can't translate into this language".to_owned(),
+            )
+            .into(),
+
             Self::Forbidden => BasicError::new(http::StatusCode::FORBIDDEN).into(),
 
             Self::InternalServerError(e) => ise(e),
@@ -679,6 +903,8 @@ pub enum JigCode {
     Conflict,
     AllCodesUsed,
     Forbidden,
+    NotOpen,
+    NoAttemptsLeft,
 }
 
 impl<T: Into<anyhow::Error>> From<T> for JigCode {
@@ -715,6 +941,18 @@ impl Into<actix_web::Error> for JigCode {
                 "User does not have permissions for this jig".to_owned(),
             )
             .into(),
+
+            Self::NotOpen => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                "This code isn't open yet".to_owned(),
+            )
+            .into(),
+
+            Self::NoAttemptsLeft => BasicError::with_message(
+                http::StatusCode::FORBIDDEN,
+                "No attempts left for this code".to_owned(),
+            )
+            .into(),
         }
     }
 }
@@ -728,3 +966,63 @@ impl From<Auth> for JigCode {
         }
     }
 }
+
+pub enum Class {
+    InternalServerError(anyhow::Error),
+    ResourceNotFound,
+    Forbidden,
+    BadRequest(String),
+    InvalidLogin,
+    TooManyAttempts,
+}
+
+impl<T: Into<anyhow::Error>> From<T> for Class {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for Class {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InternalServerError(e) => ise(e),
+
+            Self::ResourceNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Resource not found".to_owned(),
+            )
+            .into(),
+
+            Self::Forbidden => BasicError::new(http::StatusCode::FORBIDDEN).into(),
+
+            Self::BadRequest(message) => {
+                BasicError::with_message(http::StatusCode::BAD_REQUEST, message).into()
+            }
+
+            Self::InvalidLogin => BasicError::with_message(
+                http::StatusCode::UNAUTHORIZED,
+                "Invalid login code or picture password".to_owned(),
+            )
+            .into(),
+
+            Self::TooManyAttempts => BasicError::with_message(
+                http::StatusCode::TOO_MANY_REQUESTS,
+                "Too many failed attempts, try again later".to_owned(),
+            )
+            .into(),
+        }
+    }
+}
+
+impl From<JigCode> for Class {
+    fn from(err: JigCode) -> Self {
+        match err {
+            JigCode::InternalServerError(e) => Self::InternalServerError(e),
+            JigCode::ResourceNotFound => Self::ResourceNotFound,
+            JigCode::Forbidden | JigCode::NotOpen | JigCode::NoAttemptsLeft => Self::Forbidden,
+            JigCode::Conflict | JigCode::AllCodesUsed => Self::InternalServerError(
+                anyhow::anyhow!("Failed to create a code for the assignment"),
+            ),
+        }
+    }
+}
@@ -0,0 +1,155 @@
+//! Writing locale bundles as [Fluent](https://projectfluent.org) (`.ftl`) files.
+//!
+//! Entries are written as messages, with their key as the message's id. Their text is written as
+//! is, so that translators can use Fluent's syntax, like `{ $name }` for variables.
+
+/// An entry of a bundle, translated into the language being exported.
+pub struct Message<'a> {
+    pub key: &'a str,
+    pub section: Option<&'a str>,
+    pub comments: Option<&'a str>,
+    pub text: &'a str,
+}
+
+/// Writes `messages` as a Fluent file, grouped by their section in the order they're given.
+pub fn write(messages: &[Message<'_>]) -> String {
+    let mut ftl = String::new();
+    let mut section = None;
+
+    for message in messages {
+        if message.text.trim().is_empty() {
+            continue;
+        }
+
+        if message.section != section {
+            section = message.section;
+
+            if let Some(section) = section {
+                push_separator(&mut ftl);
+                push_comment(&mut ftl, "## ", section);
+            }
+        }
+
+        push_separator(&mut ftl);
+
+        if let Some(comments) = message.comments.filter(|it| !it.trim().is_empty()) {
+            push_comment(&mut ftl, "# ", comments);
+        }
+
+        ftl.push_str(message.key);
+        ftl.push_str(" =");
+        push_pattern(&mut ftl, message.text);
+        ftl.push('\n');
+    }
+
+    ftl
+}
+
+fn push_separator(ftl: &mut String) {
+    if !ftl.is_empty() {
+        ftl.push('\n');
+    }
+}
+
+fn push_comment(ftl: &mut String, prefix: &str, comment: &str) {
+    for line in comment.lines() {
+        ftl.push_str(prefix.trim_end());
+        if !line.trim().is_empty() {
+            ftl.push(' ');
+            ftl.push_str(line.trim_end());
+        }
+        ftl.push('\n');
+    }
+}
+
+/// Writes the text of a message. Text spanning multiple lines goes on the lines after the key,
+/// indented, and lines starting with characters which Fluent gives a meaning to there are escaped.
+fn push_pattern(ftl: &mut String, text: &str) {
+    let text = text.trim_end();
+
+    if !text.contains('\n') {
+        ftl.push(' ');
+        ftl.push_str(text.trim_start());
+        return;
+    }
+
+    for line in text.lines() {
+        ftl.push('\n');
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            continue;
+        }
+
+        ftl.push_str("    ");
+
+        let start = line.trim_start();
+        match start.chars().next() {
+            Some(c @ ('[' | '*' | '.')) => {
+                ftl.push_str(&line[..line.len() - start.len()]);
+                ftl.push_str(&format!("{{\"{c}\"}}"));
+                ftl.push_str(&start[c.len_utf8()..]);
+            }
+            _ => ftl.push_str(line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message<'a>(key: &'a str, section: Option<&'a str>, text: &'a str) -> Message<'a> {
+        Message {
+            key,
+            section,
+            comments: None,
+            text,
+        }
+    }
+
+    #[test]
+    fn writes_messages_by_section() {
+        let messages = [
+            message("title", None, "Jigzi"),
+            message("login-title", Some("Login"), "Log in"),
+            Message {
+                comments: Some("Shown under the title\nKeep it short"),
+                ..message("login-subtitle", Some("Login"), "Welcome back, { $name }!")
+            },
+            message("signup-title", Some("Sign up"), "  Sign up "),
+            message("signup-empty", Some("Sign up"), " "),
+        ];
+
+        assert_eq!(
+            "title = Jigzi\n\
+             \n\
+             ## Login\n\
+             \n\
+             login-title = Log in\n\
+             \n\
+             # Shown under the title\n\
+             # Keep it short\n\
+             login-subtitle = Welcome back, { $name }!\n\
+             \n\
+             ## Sign up\n\
+             \n\
+             signup-title = Sign up\n",
+            write(&messages)
+        );
+    }
+
+    #[test]
+    fn writes_multiline_messages() {
+        let messages = [message(
+            "help",
+            None,
+            "Steps:\n* open the JIG\n\n  [optional] share it\n. done",
+        )];
+
+        assert_eq!(
+            "help =\n    Steps:\n    {\"*\"} open the JIG\n\n      {\"[\"}optional] share it\n    {\".\"} done\n",
+            write(&messages)
+        );
+    }
+}
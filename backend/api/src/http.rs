@@ -22,8 +22,9 @@ use tracing::Span;
 use tracing_actix_web::{root_span, DefaultRootSpanBuilder, RootSpanBuilder, TracingLogger};
 
 use crate::{
+    content_safety::ContentSafety,
     error::BasicError,
-    service::{self, mail, s3, upload::cleaner, ServiceData},
+    service::{self, mail, payment::PaymentProvider, s3, upload::cleaner, ServiceData},
     translate,
 };
 
@@ -113,7 +114,9 @@ pub async fn build_and_run(
     mail_client: Option<mail::Client>,
     algolia_manager: Option<crate::algolia::Manager>,
     media_upload_cleaner: Option<cleaner::UploadCleaner>,
-    google_translate: Option<translate::GoogleTranslate>,
+    translator: Option<translate::Translator>,
+    payment_provider: Option<Arc<dyn PaymentProvider>>,
+    content_safety: Arc<ContentSafety>,
 ) -> anyhow::Result<()> {
     let app = build(
         pool,
@@ -126,7 +129,9 @@ pub async fn build_and_run(
         mail_client,
         algolia_manager,
         media_upload_cleaner,
-        google_translate,
+        translator,
+        payment_provider,
+        content_safety,
     )?;
     app.run_until_stopped().await?;
 
@@ -144,7 +149,9 @@ pub fn build(
     mail_client: Option<mail::Client>,
     algolia_manager: Option<crate::algolia::Manager>,
     media_upload_cleaner: Option<cleaner::UploadCleaner>,
-    google_translate: Option<translate::GoogleTranslate>,
+    translator: Option<translate::Translator>,
+    payment_provider: Option<Arc<dyn PaymentProvider>>,
+    content_safety: Arc<ContentSafety>,
 ) -> anyhow::Result<Application> {
     let local_insecure = settings.is_local();
     let api_port = settings.api_port;
@@ -156,7 +163,8 @@ pub fn build(
     let mail_client = mail_client.map(ServiceData::new);
     let algolia_manager = algolia_manager.map(ServiceData::new);
     let media_upload_cleaner = media_upload_cleaner.map(ServiceData::new);
-    let google_translate = google_translate.map(ServiceData::new);
+    let translator = translator.map(ServiceData::new);
+    let payment_provider = payment_provider.map(ServiceData::from);
 
     let server = actix_web::HttpServer::new(move || {
         let app = actix_web::App::new()
@@ -197,14 +205,20 @@ pub fn build(
             Some(media_upload_cleaner) => app.app_data(media_upload_cleaner),
             None => app,
         };
-        let app = match google_translate.clone() {
-            Some(google_translate) => app.app_data(google_translate),
+        let app = match translator.clone() {
+            Some(translator) => app.app_data(translator),
+            None => app,
+        };
+
+        let app = match payment_provider.clone() {
+            Some(payment_provider) => app.app_data(payment_provider),
             None => app,
         };
 
         let enable_tracing_logs = env_bool("ENABLE_TRACING_LOGS");
 
         app.app_data(Data::from(jwk_verifier.clone()))
+            .app_data(Data::from(content_safety.clone()))
             .wrap(cors::get(local_insecure))
             .wrap(Condition::new(
                 enable_tracing_logs,
@@ -243,8 +257,10 @@ pub fn build(
             .configure(endpoints::resource::curation::configure)
             .configure(endpoints::resource::report::configure)
             .configure(endpoints::playlist::configure)
+            .configure(endpoints::playlist::progress::configure)
             .configure(endpoints::course::configure)
             .configure(endpoints::course::unit::configure)
+            .configure(endpoints::course::progress::configure)
             .configure(endpoints::admin::configure)
             .configure(endpoints::animation::configure)
             .configure(endpoints::search::configure)
@@ -254,6 +270,9 @@ pub fn build(
             .configure(endpoints::scheduler::configure)
             .configure(endpoints::pdf::configure)
             .configure(endpoints::circle::configure)
+            .configure(endpoints::class::configure)
+            .configure(endpoints::notification::configure)
+            .configure(endpoints::moderation::configure)
             .route("/", method(http::Method::GET).to(no_content_response))
     });
 
@@ -1,27 +1,31 @@
 use crate::db;
-use crate::domain::{user_authorization, UserAuthorization};
+use crate::domain::{self, user_authorization, SchoolEligibility, UserAuthorization};
 use crate::extractor::{ScopeAdmin, TokenUser, TokenUserWithScope};
-use crate::stripe::create_stripe_client;
+use crate::service::{payment::PaymentProvider, ServiceData};
 use actix_web::web::{Data, Json, Path, ServiceConfig};
 use actix_web::HttpResponse;
 use anyhow::anyhow;
 use futures::try_join;
-use ji_core::settings::RuntimeSettings;
 use shared::api::endpoints::account::{
-    DeleteSchoolAccount, GetIndividualAccount, GetSchoolAccount, UpdateSchoolAccount,
+    AcceptSchoolInvite, DeclineSchoolInvite, DeleteSchoolAccount, GetIndividualAccount,
+    GetSchoolAccount, InviteSchoolMembers, ListSchoolInvites, ListUserSchoolInvites,
+    RemoveSchoolMember, RevokeSchoolInvite, UpdateSchoolAccount,
 };
 use shared::api::endpoints::admin::{GetAdminSchoolAccount, SetAccountTierOverride};
 use shared::api::{endpoints::account::CreateSchoolAccount, ApiEndpoint, PathParts};
-use shared::domain::admin::GetAdminSchoolAccountResponse;
+use shared::domain::admin::{
+    GetAdminSchoolAccountResponse, InviteFailedReason, InviteSchoolUserFailure,
+};
 use shared::domain::billing::{
-    AccountIfAuthorized, CreateSchoolAccountRequest, GetSchoolAccountResponse,
-    IndividualAccountResponse, SchoolId, SubscriptionStatus, UpdateSchoolAccountRequest,
-    UserAccountSummary,
+    AccountId, AccountIfAuthorized, CreateSchoolAccountRequest, GetSchoolAccountResponse,
+    IndividualAccountResponse, InviteSchoolMembersResponse, ListSchoolInvitesResponse,
+    ListUserSchoolInvitesResponse, SchoolId, SchoolInviteId, SubscriptionStatus,
+    UpdateSchoolAccountRequest, UserAccountSummary,
 };
 use shared::domain::user::UserId;
-use shared::domain::UpdateNonNullable;
-use shared::error::{AccountError, IntoAnyhow};
-use sqlx::PgPool;
+use shared::domain::{UpdateNonNullable, UpdateNullable};
+use shared::error::{AccountError, IntoAnyhow, ServiceError, ServiceKindError};
+use sqlx::{PgConnection, PgPool};
 use tracing::instrument;
 
 #[instrument(skip_all)]
@@ -55,7 +59,9 @@ async fn create_school_account(
                 Some(SubscriptionStatus::Expired) | None => {
                     // If they have an account with an expired subscription or no subscription,
                     // then delete that account so that they can be added to a school account.
-                    db::account::delete_account_for_user(db.as_ref(), &user_id).await?;
+                    let mut txn = db.begin().await.into_anyhow()?;
+                    db::account::delete_account_for_user(&mut txn, &user_id).await?;
+                    txn.commit().await.into_anyhow()?;
                 }
                 _ => return Err(AccountError::UserHasAccount),
             }
@@ -80,7 +86,7 @@ async fn create_school_account(
 async fn update_school_account(
     auth: TokenUser,
     db: Data<PgPool>,
-    settings: Data<RuntimeSettings>,
+    payments: Option<ServiceData<dyn PaymentProvider>>,
     path: Path<SchoolId>,
     req: Json<<UpdateSchoolAccount as ApiEndpoint>::Req>,
 ) -> Result<HttpResponse, <UpdateSchoolAccount as ApiEndpoint>::Err> {
@@ -98,21 +104,24 @@ async fn update_school_account(
 
     let req: UpdateSchoolAccountRequest = req.into_inner();
 
-    let mut should_update_stripe = false;
-    let mut update_customer = stripe::UpdateCustomer::default();
-
-    if let UpdateNonNullable::Change(email) = &req.email {
-        should_update_stripe = true;
-        update_customer.email = Some(email.as_ref());
+    if matches!(req.session_retention_days, UpdateNullable::Change(0)) {
+        return Err(AccountError::BadRequest(
+            "Session retention must be at least one day".into(),
+        ));
     }
 
-    if let UpdateNonNullable::Change(school_name) = &req.school_name {
-        should_update_stripe = true;
-        update_customer.name = Some(school_name.as_ref());
-    }
+    let email = match &req.email {
+        UpdateNonNullable::Change(email) => Some(email.as_str()),
+        UpdateNonNullable::Keep => None,
+    };
 
-    if should_update_stripe {
-        let client = create_stripe_client(&settings)?;
+    let school_name = match &req.school_name {
+        UpdateNonNullable::Change(school_name) => Some(school_name.as_str()),
+        UpdateNonNullable::Keep => None,
+    };
+
+    if email.is_some() || school_name.is_some() {
+        let payments = payments.ok_or(ServiceError::DisabledService(ServiceKindError::Stripe))?;
         let account_id = db::account::get_account_id_by_school_id(db.as_ref(), &school_id)
             .await?
             .ok_or(anyhow!("Missing account for school {school_id}"))?;
@@ -121,10 +130,10 @@ async fn update_school_account(
             .await?
             .ok_or(anyhow!("Missing account {account_id}"))?;
 
-        let stripe_customer_id =
-            stripe::CustomerId::from(account.stripe_customer_id.unwrap().clone());
-
-        stripe::Customer::update(&client, &stripe_customer_id, update_customer).await?;
+        payments
+            .update_customer(&account.stripe_customer_id.unwrap(), email, school_name)
+            .await
+            .into_anyhow()?;
     }
 
     db::account::update_school_account(db.as_ref(), &school_id, req)
@@ -326,6 +335,324 @@ async fn get_individual_account(
     Ok(Json(IndividualAccountResponse { account }))
 }
 
+/// Why an email can't be invited to a school, if it can't. Doesn't account for seats.
+async fn school_invite_failure(
+    db: &PgPool,
+    txn: &mut PgConnection,
+    account_id: &AccountId,
+    email: &str,
+) -> anyhow::Result<Option<InviteFailedReason>> {
+    if db::account::is_account_member_by_email(&mut *txn, account_id, email).await? {
+        return Ok(Some(InviteFailedReason::AlreadyMember));
+    }
+
+    if db::account::has_pending_school_invite(&mut *txn, account_id, email).await? {
+        return Ok(Some(InviteFailedReason::AlreadyInvited));
+    }
+
+    // Teachers who haven't signed up yet can still be invited, they just can't accept until they
+    // have.
+    let Some(user_id) = db::user::get_user_id_by_email(db, email).await? else {
+        return Ok(None);
+    };
+
+    Ok(match domain::school_eligibility(db, &user_id).await? {
+        SchoolEligibility::Ineligible(InviteFailedReason::IncompleteProfile) => None,
+        SchoolEligibility::Ineligible(reason) => Some(reason),
+        SchoolEligibility::Eligible | SchoolEligibility::ReplacesIndividualAccount => None,
+    })
+}
+
+#[instrument(skip_all)]
+async fn invite_school_members(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<SchoolId>,
+    req: Json<<InviteSchoolMembers as ApiEndpoint>::Req>,
+) -> Result<
+    Json<<InviteSchoolMembers as ApiEndpoint>::Res>,
+    <InviteSchoolMembers as ApiEndpoint>::Err,
+> {
+    let user_id = auth.user_id();
+    let school_id = path.into_inner();
+
+    let school = db::account::get_school_account_by_id(db.as_ref(), &school_id)
+        .await
+        .into_anyhow()?
+        .ok_or(AccountError::NotFound("School not found".into()))?;
+
+    user_authorization(db.as_ref(), &user_id, &school.account_id)
+        .await?
+        .test_authorized(true)?;
+
+    let mut txn = db.begin().await.into_anyhow()?;
+
+    db::account::lock_account(&mut txn, &school.account_id)
+        .await
+        .into_anyhow()?;
+
+    let mut seats = db::account::get_account_seats(&mut *txn, &school.account_id)
+        .await
+        .into_anyhow()?;
+
+    let mut invites = vec![];
+    let mut failures = vec![];
+
+    for email in req.into_inner().emails {
+        let email = email.trim();
+        if email.is_empty() {
+            continue;
+        }
+
+        let failure =
+            school_invite_failure(db.as_ref(), &mut txn, &school.account_id, email).await?;
+
+        match failure {
+            Some(reason) => failures.push(InviteSchoolUserFailure {
+                email: email.to_owned(),
+                reason,
+            }),
+            None if seats.is_full() => failures.push(InviteSchoolUserFailure {
+                email: email.to_owned(),
+                reason: InviteFailedReason::NoSeatsAvailable,
+            }),
+            None => {
+                let invite = db::account::create_school_invite(
+                    &mut *txn,
+                    &school.account_id,
+                    email,
+                    &user_id,
+                )
+                .await
+                .into_anyhow()?;
+
+                seats.pending_invites += 1;
+                invites.push(invite);
+            }
+        }
+    }
+
+    txn.commit().await.into_anyhow()?;
+
+    Ok(Json(InviteSchoolMembersResponse { invites, failures }))
+}
+
+async fn list_school_invites(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<SchoolId>,
+) -> Result<Json<<ListSchoolInvites as ApiEndpoint>::Res>, <ListSchoolInvites as ApiEndpoint>::Err>
+{
+    let school_id = path.into_inner();
+
+    let school = db::account::get_school_account_by_id(db.as_ref(), &school_id)
+        .await
+        .into_anyhow()?
+        .ok_or(AccountError::NotFound("School not found".into()))?;
+
+    user_authorization(db.as_ref(), &auth.user_id(), &school.account_id)
+        .await?
+        .test_authorized(true)?;
+
+    let invites = db::account::get_pending_school_invites(db.as_ref(), &school.account_id)
+        .await
+        .into_anyhow()?;
+
+    Ok(Json(ListSchoolInvitesResponse { invites }))
+}
+
+async fn revoke_school_invite(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<(SchoolId, SchoolInviteId)>,
+) -> Result<HttpResponse, <RevokeSchoolInvite as ApiEndpoint>::Err> {
+    let (school_id, invite_id) = path.into_inner();
+
+    let school = db::account::get_school_account_by_id(db.as_ref(), &school_id)
+        .await
+        .into_anyhow()?
+        .ok_or(AccountError::NotFound("School not found".into()))?;
+
+    user_authorization(db.as_ref(), &auth.user_id(), &school.account_id)
+        .await?
+        .test_authorized(true)?;
+
+    if !db::account::delete_school_invite(db.as_ref(), &school.account_id, &invite_id)
+        .await
+        .into_anyhow()?
+    {
+        return Err(AccountError::NotFound("Invite not found".into()));
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[instrument(skip_all)]
+async fn remove_school_member(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<(SchoolId, UserId)>,
+    req: Json<<RemoveSchoolMember as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, <RemoveSchoolMember as ApiEndpoint>::Err> {
+    let user_id = auth.user_id();
+    let (school_id, member_id) = path.into_inner();
+
+    let school = db::account::get_school_account_by_id(db.as_ref(), &school_id)
+        .await
+        .into_anyhow()?
+        .ok_or(AccountError::NotFound("School not found".into()))?;
+
+    // Teachers can leave a school themselves, only admins can remove other teachers.
+    user_authorization(db.as_ref(), &user_id, &school.account_id)
+        .await?
+        .test_authorized(member_id != user_id)?;
+
+    let users =
+        db::account::get_account_users_by_account_id(db.as_ref(), &school.account_id).await?;
+
+    let member =
+        users
+            .iter()
+            .find(|user| user.user.id == member_id)
+            .ok_or(AccountError::NotFound(
+                "User is not a member of this school".into(),
+            ))?;
+
+    if member.is_admin && users.iter().filter(|user| user.is_admin).count() == 1 {
+        return Err(AccountError::BadRequest(
+            "Cannot remove the last admin of a school".into(),
+        ));
+    }
+
+    let mut txn = db.begin().await.into_anyhow()?;
+
+    db::account::lock_account(&mut txn, &school.account_id)
+        .await
+        .into_anyhow()?;
+
+    db::account::delete_user_from_school(&mut *txn, &school_id, &member_id)
+        .await
+        .into_anyhow()?;
+
+    // The replacement takes over the seat which was just freed up, so no one else can take it in
+    // the meantime.
+    if let Some(email) = req.into_inner().replacement_email {
+        let email = email.trim();
+
+        if let Some(reason) =
+            school_invite_failure(db.as_ref(), &mut txn, &school.account_id, email).await?
+        {
+            return Err(AccountError::BadRequest(format!(
+                "Cannot invite {email}: {reason}"
+            )));
+        }
+
+        if db::account::get_account_seats(&mut *txn, &school.account_id)
+            .await
+            .into_anyhow()?
+            .is_full()
+        {
+            return Err(AccountError::NoSeatsAvailable);
+        }
+
+        db::account::create_school_invite(&mut *txn, &school.account_id, email, &user_id)
+            .await
+            .into_anyhow()?;
+    }
+
+    txn.commit().await.into_anyhow()?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn list_user_school_invites(
+    auth: TokenUser,
+    db: Data<PgPool>,
+) -> Result<
+    Json<<ListUserSchoolInvites as ApiEndpoint>::Res>,
+    <ListUserSchoolInvites as ApiEndpoint>::Err,
+> {
+    let invites = db::account::get_user_school_invites(db.as_ref(), &auth.user_id())
+        .await
+        .into_anyhow()?;
+
+    Ok(Json(ListUserSchoolInvitesResponse { invites }))
+}
+
+#[instrument(skip_all)]
+async fn accept_school_invite(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<SchoolInviteId>,
+) -> Result<HttpResponse, <AcceptSchoolInvite as ApiEndpoint>::Err> {
+    let user_id = auth.user_id();
+    let invite_id = path.into_inner();
+
+    let account_id = db::account::get_user_school_invite_account(db.as_ref(), &user_id, &invite_id)
+        .await
+        .into_anyhow()?
+        .ok_or(AccountError::NotFound("Invite not found".into()))?;
+
+    let eligibility = match domain::school_eligibility(db.as_ref(), &user_id).await? {
+        SchoolEligibility::Ineligible(InviteFailedReason::IncompleteProfile) => {
+            return Err(AccountError::BadRequest(
+                "Profile must be completed before joining a school".into(),
+            ))
+        }
+        SchoolEligibility::Ineligible(_) => return Err(AccountError::UserHasAccount),
+        eligibility => eligibility,
+    };
+
+    let mut txn = db.begin().await.into_anyhow()?;
+
+    db::account::lock_account(&mut txn, &account_id)
+        .await
+        .into_anyhow()?;
+
+    // The invite has been holding a seat, but the school's plan could have been downgraded since it
+    // was sent.
+    let seats = db::account::get_account_seats(&mut *txn, &account_id)
+        .await
+        .into_anyhow()?;
+
+    if let Some(limit) = seats.limit {
+        if seats.members >= limit.get() {
+            return Err(AccountError::NoSeatsAvailable);
+        }
+    }
+
+    if let SchoolEligibility::ReplacesIndividualAccount = eligibility {
+        db::account::delete_account_for_user(&mut txn, &user_id).await?;
+    }
+
+    db::account::associate_user_with_account(&mut *txn, &user_id, &account_id, false, true)
+        .await
+        .into_anyhow()?;
+
+    db::account::accept_school_invite(&mut *txn, &invite_id, &user_id)
+        .await
+        .into_anyhow()?;
+
+    txn.commit().await.into_anyhow()?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn decline_school_invite(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    path: Path<SchoolInviteId>,
+) -> Result<HttpResponse, <DeclineSchoolInvite as ApiEndpoint>::Err> {
+    if !db::account::decline_school_invite(db.as_ref(), &auth.user_id(), &path.into_inner())
+        .await
+        .into_anyhow()?
+    {
+        return Err(AccountError::NotFound("Invite not found".into()));
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
 pub fn configure(cfg: &mut ServiceConfig) {
     cfg.route(
         <CreateSchoolAccount as ApiEndpoint>::Path::PATH,
@@ -366,5 +693,39 @@ pub fn configure(cfg: &mut ServiceConfig) {
         GetIndividualAccount::METHOD
             .route()
             .to(get_individual_account),
+    )
+    .route(
+        <InviteSchoolMembers as ApiEndpoint>::Path::PATH,
+        InviteSchoolMembers::METHOD
+            .route()
+            .to(invite_school_members),
+    )
+    .route(
+        <ListSchoolInvites as ApiEndpoint>::Path::PATH,
+        ListSchoolInvites::METHOD.route().to(list_school_invites),
+    )
+    .route(
+        <RevokeSchoolInvite as ApiEndpoint>::Path::PATH,
+        RevokeSchoolInvite::METHOD.route().to(revoke_school_invite),
+    )
+    .route(
+        <RemoveSchoolMember as ApiEndpoint>::Path::PATH,
+        RemoveSchoolMember::METHOD.route().to(remove_school_member),
+    )
+    .route(
+        <ListUserSchoolInvites as ApiEndpoint>::Path::PATH,
+        ListUserSchoolInvites::METHOD
+            .route()
+            .to(list_user_school_invites),
+    )
+    .route(
+        <AcceptSchoolInvite as ApiEndpoint>::Path::PATH,
+        AcceptSchoolInvite::METHOD.route().to(accept_school_invite),
+    )
+    .route(
+        <DeclineSchoolInvite as ApiEndpoint>::Path::PATH,
+        DeclineSchoolInvite::METHOD
+            .route()
+            .to(decline_school_invite),
     );
 }
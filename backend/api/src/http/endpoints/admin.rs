@@ -6,25 +6,28 @@ use actix_web::{
 use anyhow::anyhow;
 use argon2::{password_hash::SaltString, Argon2, PasswordHasher};
 use chrono::{Duration, Utc};
-use futures::future::join_all;
 use futures::try_join;
 use ji_core::settings::RuntimeSettings;
 use rand::{
     distributions::{Alphanumeric, DistString},
     thread_rng,
 };
-use sendgrid::v3::Email;
 use serde::{Deserialize, Serialize};
 use shared::api::endpoints::admin::{
     AdminSendPasswordReset, AdminSwitchToBasicAuth, CreateSchoolName, DeleteUserAccount,
-    GetSchoolNames, ImportSchoolNames, InviteUsers, RemoveUserFromSchool, SearchSchools,
-    SetInternalSchoolName, UpdateSchoolName, VerifySchool,
+    GetSchoolNames, ImportSchoolNames, InviteUsers, ListEmailOutbox, PreviewEmail,
+    RemoveUserFromSchool, RetryEmail, SearchSchools, SetInternalSchoolName, UpdateSchoolName,
+    VerifySchool,
 };
 use shared::domain::admin::{
     InviteFailedReason, InviteSchoolUserFailure, InviteSchoolUsersResponse, SearchSchoolsResponse,
 };
 use shared::domain::billing::{
-    SchoolId, SchoolNameId, SchoolNameValue, SubscriptionStatus, UpdateSubscriptionPlansRequest,
+    AccountId, SchoolId, SchoolNameId, SchoolNameValue, SubscriptionStatus,
+    UpdateSubscriptionPlansRequest,
+};
+use shared::domain::email::{
+    EmailOutboxId, EmailPreviewResponse, EmailTemplateKind, ListEmailOutboxResponse,
 };
 use shared::error::AccountError;
 use shared::{
@@ -36,14 +39,20 @@ use shared::{
     error::IntoAnyhow,
 };
 use sqlx::PgPool;
+use std::str::FromStr;
 use tracing::instrument;
 
 use uuid::Uuid;
 
 use crate::{
-    db, error,
+    db,
+    domain::{self, SchoolEligibility},
+    error,
     extractor::{ScopeAdmin, TokenUserNoCsrfWithScope, TokenUserWithScope},
-    service::{mail, ServiceData},
+    service::{
+        mail::{self, template, template::EmailTemplate},
+        ServiceData,
+    },
     token::{create_auth_token, SessionMask},
 };
 
@@ -307,57 +316,48 @@ async fn set_internal_school_name(
 
 async fn invite_school_user(
     pool: &PgPool,
-    school_id: &SchoolId,
+    account_id: &AccountId,
     email: String,
 ) -> Result<Option<InviteSchoolUserFailure>, error::Server> {
     let user_id = match db::user::get_user_id_by_email(pool, &email).await? {
-        Some(user_id) => {
-            if db::user::get_profile(pool, &user_id).await?.is_none() {
-                // Incomplete profiles cause a bunch of queries to fail, so we prevent them from being added to schools until their profile is complete.
-                Err(InviteFailedReason::IncompleteProfile)
-            } else {
-                if let Some(account_summary) =
-                    db::account::get_user_account_summary(pool, &user_id).await?
-                {
-                    match account_summary.school_id {
-                        Some(_) => Err(InviteFailedReason::AssociatedWithSchool),
-                        None => {
-                            match account_summary.subscription_status {
-                                Some(SubscriptionStatus::Expired) | None => {
-                                    // If they have an account with an expired subscription or no subscription,
-                                    // then delete that account so that they can be added to a school account.
-                                    db::account::delete_account_for_user(pool, &user_id).await?;
-                                    Ok(user_id)
-                                }
-                                _ => Err(InviteFailedReason::HasIndividualAccount),
-                            }
-                        }
-                    }
-                } else {
-                    Ok(user_id)
-                }
-            }
+        Some(user_id) => user_id,
+        None => {
+            return Ok(Some(InviteSchoolUserFailure {
+                email,
+                reason: InviteFailedReason::UserNotFound,
+            }))
         }
-        None => Err(InviteFailedReason::UserNotFound),
     };
 
-    match user_id {
-        Err(reason) => return Ok(Some(InviteSchoolUserFailure { email, reason })),
-        Ok(user_id) => {
-            let school = db::account::get_school_account_by_id(pool, school_id)
-                .await?
-                .ok_or(anyhow!("School not found"))?;
-            db::account::associate_user_with_account(
-                pool,
-                &user_id,
-                &school.account_id,
-                false,
-                true,
-            )
-            .await?;
+    let eligibility = match domain::school_eligibility(pool, &user_id).await? {
+        SchoolEligibility::Ineligible(reason) => {
+            return Ok(Some(InviteSchoolUserFailure { email, reason }))
         }
+        eligibility => eligibility,
+    };
+
+    let mut txn = pool.begin().await?;
+
+    db::account::lock_account(&mut txn, account_id).await?;
+
+    if db::account::get_account_seats(&mut *txn, account_id)
+        .await?
+        .is_full()
+    {
+        return Ok(Some(InviteSchoolUserFailure {
+            email,
+            reason: InviteFailedReason::NoSeatsAvailable,
+        }));
     }
 
+    if let SchoolEligibility::ReplacesIndividualAccount = eligibility {
+        db::account::delete_account_for_user(&mut txn, &user_id).await?;
+    }
+
+    db::account::associate_user_with_account(&mut *txn, &user_id, account_id, false, true).await?;
+
+    txn.commit().await?;
+
     Ok(None)
 }
 
@@ -366,21 +366,17 @@ async fn invite_school_users(
     db: Data<PgPool>,
     Json(invite_users): Json<<InviteUsers as ApiEndpoint>::Req>,
 ) -> Result<(Json<<InviteUsers as ApiEndpoint>::Res>, http::StatusCode), error::Server> {
-    let emails: Vec<_> = invite_users
-        .data
-        .lines()
-        .map(|email| invite_school_user(db.as_ref(), &invite_users.school_id, email.into()))
-        .collect();
+    let account_id = db::account::get_account_id_by_school_id(db.as_ref(), &invite_users.school_id)
+        .await?
+        .ok_or(anyhow!("School not found"))?;
 
-    let failures: Vec<InviteSchoolUserFailure> = join_all(emails)
-        .await
-        .into_iter()
-        .filter_map(|item| match item {
-            Ok(Some(item)) => Some(Ok(item)),
-            Err(error) => Some(Err(error)),
-            _ => None,
-        })
-        .collect::<Result<Vec<_>, _>>()?;
+    // Users are added one at a time so that they take up seats in the order they were listed.
+    let mut failures = vec![];
+    for email in invite_users.data.lines() {
+        if let Some(failure) = invite_school_user(db.as_ref(), &account_id, email.into()).await? {
+            failures.push(failure);
+        }
+    }
 
     Ok((
         Json(InviteSchoolUsersResponse { failures }),
@@ -423,7 +419,9 @@ async fn delete_user_account(
                 if account_summary.is_admin {
                     // If they have an account with an expired subscription or no subscription,
                     // then delete that account so that they can be added to a school account.
-                    db::account::delete_account_for_user(db.as_ref(), &user_id).await?;
+                    let mut txn = db.begin().await.into_anyhow()?;
+                    db::account::delete_account_for_user(&mut txn, &user_id).await?;
+                    txn.commit().await.into_anyhow()?;
                     Ok(())
                 } else {
                     Err(AccountError::BadRequest("User is not an admin".into()))
@@ -534,19 +532,97 @@ async fn admin_send_password_reset(
         .await
         .into_anyhow()?;
 
-    let template = mail
-        .password_reset_template()
-        .map_err(|e| anyhow!("{}", e))?;
-
     let pages_url = config.remote_target().pages_url();
     let email_link = format!("{}/user/password-reset/{}", pages_url, session);
 
-    mail.send_password_reset(template, Email::new(profile.email), email_link, first_name)
+    let email_id = mail
+        .queue(
+            &mut txn,
+            &profile.email,
+            &template::locale_for(&profile.language_emails),
+            EmailTemplate::PasswordReset {
+                url: email_link,
+                first_name,
+            },
+        )
         .await
         .map_err(|e| anyhow!("{}", e))?;
 
     txn.commit().await.into_anyhow()?;
 
+    mail.send_queued(&db, email_id).await;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Render an email template with sample data
+async fn preview_email(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    config: Data<RuntimeSettings>,
+    db: Data<PgPool>,
+    mail: ServiceData<mail::Client>,
+    path: Path<String>,
+    Query(query): Query<<PreviewEmail as ApiEndpoint>::Req>,
+) -> Result<Json<<PreviewEmail as ApiEndpoint>::Res>, error::NotFound> {
+    let kind = EmailTemplateKind::from_str(&path.into_inner())
+        .map_err(|_| error::NotFound::ResourceNotFound)?;
+
+    let locale = match query.locale.as_deref() {
+        Some(locale) => {
+            let locale = template::locale_for(locale);
+
+            if !db::locale::languages_exist(db.as_ref(), &[locale.clone()]).await? {
+                return Err(error::NotFound::BadRequest);
+            }
+
+            locale
+        }
+        None => template::DEFAULT_LOCALE.to_owned(),
+    };
+
+    let email = EmailTemplate::sample(kind, &config.remote_target().pages_url());
+
+    let mut conn = db.acquire().await?;
+
+    let (rendered_locale, rendered) = mail.render(&mut conn, &email, &locale).await?;
+
+    Ok(Json(EmailPreviewResponse {
+        locale: rendered_locale,
+        subject: rendered.subject,
+        html: rendered.html,
+        text: rendered.text,
+        sendgrid_template_id: mail.sendgrid_template(kind, &locale).map(ToOwned::to_owned),
+    }))
+}
+
+/// List emails in the outbox
+async fn list_email_outbox(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    Query(query): Query<<ListEmailOutbox as ApiEndpoint>::Req>,
+) -> Result<Json<<ListEmailOutbox as ApiEndpoint>::Res>, error::Server> {
+    let (emails, total_count) = try_join!(
+        db::email::list(db.as_ref(), &query),
+        db::email::count(db.as_ref(), &query),
+    )?;
+
+    Ok(Json(ListEmailOutboxResponse {
+        emails,
+        pages: total_count.paged(query.page_limit),
+        total_count,
+    }))
+}
+
+/// Queue an unsent email to be retried
+async fn retry_email(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    path: Path<EmailOutboxId>,
+) -> Result<HttpResponse, error::NotFound> {
+    if !db::email::retry(db.as_ref(), path.into_inner()).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
     Ok(HttpResponse::NoContent().finish())
 }
 
@@ -630,5 +706,17 @@ pub fn configure(cfg: &mut ServiceConfig) {
         AdminSendPasswordReset::METHOD
             .route()
             .to(admin_send_password_reset),
+    )
+    .route(
+        <PreviewEmail as ApiEndpoint>::Path::PATH,
+        PreviewEmail::METHOD.route().to(preview_email),
+    )
+    .route(
+        <ListEmailOutbox as ApiEndpoint>::Path::PATH,
+        ListEmailOutbox::METHOD.route().to(list_email_outbox),
+    )
+    .route(
+        <RetryEmail as ApiEndpoint>::Path::PATH,
+        RetryEmail::METHOD.route().to(retry_email),
     );
 }
@@ -35,21 +35,25 @@ pub mod user {
     use crate::{
         db, error,
         extractor::TokenUser,
-        service::{s3, upload as upload_service, ServiceData},
+        service::{s3, storage, upload as upload_service, ServiceData},
     };
 
     /// Create a audio file in the user's audio library.
     pub(super) async fn create(
         db: Data<PgPool>,
         s3: ServiceData<s3::Client>,
-        _claims: TokenUser,
+        claims: TokenUser,
         payload: Payload,
     ) -> Result<HttpResponse, error::Upload> {
         let file = super::super::read_limited_payload(payload, FileKind::AudioMp3).await?;
-        let id = db::audio::user::create(db.as_ref()).await?;
+        let user_id = claims.user_id();
+
+        let quota = storage::check_quota(db.as_ref(), user_id, None, file.len()).await?;
+
+        let id = db::audio::user::create(db.as_ref(), user_id).await?;
 
         let mut txn = db.begin().await?;
-        upload_service::process_user_audio_bytes(&mut txn, &s3, id.0, file).await?;
+        upload_service::process_user_audio_bytes(&mut txn, &s3, &quota, id.0, file).await?;
         txn.commit().await?;
 
         Ok(HttpResponse::Created().json(CreateResponse { id }))
@@ -59,7 +63,7 @@ pub mod user {
     pub(super) async fn upload(
         db: Data<PgPool>,
         s3: ServiceData<s3::Client>,
-        _claims: TokenUser,
+        claims: TokenUser,
         id: Path<AudioId>,
         payload: Payload,
     ) -> Result<HttpResponse, error::Upload> {
@@ -80,7 +84,10 @@ pub mod user {
 
         let file = super::super::read_limited_payload(payload, FileKind::AudioMp3).await?;
 
-        upload_service::process_user_audio_bytes(&mut txn, &s3, id.0, file).await?;
+        let quota =
+            storage::check_quota(db.as_ref(), claims.user_id(), Some(id.0), file.len()).await?;
+
+        upload_service::process_user_audio_bytes(&mut txn, &s3, &quota, id.0, file).await?;
 
         txn.commit().await?;
 
@@ -102,6 +109,9 @@ pub mod user {
         s3.delete_media(MediaLibrary::User, FileKind::AudioMp3, audio.0)
             .await;
 
+        s3.delete_media(MediaLibrary::User, FileKind::AudioOpus, audio.0)
+            .await;
+
         Ok(HttpResponse::NoContent().finish())
     }
 
@@ -118,6 +128,19 @@ pub mod user {
         Ok(Json(UserAudioResponse { metadata }))
     }
 
+    /// Get the waveform peaks of an audio file from the user's audio library.
+    pub(super) async fn waveform(
+        db: Data<PgPool>,
+        _claims: TokenUser,
+        req: Path<AudioId>,
+    ) -> Result<Json<<endpoints::audio::user::Waveform as ApiEndpoint>::Res>, error::NotFound> {
+        let waveform = db::audio::user::get_waveform(&db, req.into_inner())
+            .await?
+            .ok_or(error::NotFound::ResourceNotFound)?;
+
+        Ok(Json(waveform))
+    }
+
     /// List audio files from the user's audio library.
     pub(super) async fn list(
         db: Data<PgPool>,
@@ -150,6 +173,12 @@ pub fn configure(cfg: &mut ServiceConfig) {
         <audio::user::Get as ApiEndpoint>::Path::PATH,
         audio::user::Get::METHOD.route().to(self::user::get),
     )
+    .route(
+        <audio::user::Waveform as ApiEndpoint>::Path::PATH,
+        audio::user::Waveform::METHOD
+            .route()
+            .to(self::user::waveform),
+    )
     .route(
         <audio::user::List as ApiEndpoint>::Path::PATH,
         audio::user::List::METHOD.route().to(self::user::list),
@@ -1,18 +1,21 @@
 use actix_web::{
-    web::{self, Data, Json, ServiceConfig},
+    web::{self, Data, Json, Query, ServiceConfig},
     HttpRequest, HttpResponse,
 };
 use anyhow::anyhow;
 use chrono::{TimeZone, Utc};
 use ji_core::settings::RuntimeSettings;
 use shared::api::endpoints::billing::{
-    AdminUpgradeSubscriptionPlan, CreateCustomerPortalLink, CreateSetupIntent,
-    UpdateSubscriptionCancellation, UpdateSubscriptionPaused, UpgradeSubscriptionPlan,
+    AdminCreateInvoicedSubscription, AdminListSchoolInvoices, AdminMarkInvoicePaid,
+    AdminUpgradeSubscriptionPlan, CreateCustomerPortalLink, CreateSetupIntent, ListInvoices,
+    ListWebhookEvents, ReplayWebhookEvent, UpdateSubscriptionCancellation,
+    UpdateSubscriptionPaused, UpgradeSubscriptionPlan,
 };
 use shared::domain::billing::{
-    Account, AccountType, AmountInCents, CancellationStatus, CreateSubscriptionRecord, PlanType,
-    StripeInvoiceId, StripeSubscriptionId, SubscriptionStatus, SubscriptionType,
-    UpdateSubscriptionRecord,
+    Account, AccountId, AccountType, AmountInCents, CancellationStatus, CreateSubscriptionRecord,
+    ListInvoicesResponse, ListWebhookEventsResponse, PlanType, PurchaseOrder, SchoolId,
+    StripeEventId, StripeInvoiceId, SubscriptionStatus, SubscriptionType, UpdateSubscriptionRecord,
+    WebhookEventStatus, PURCHASE_ORDER_DAYS_UNTIL_DUE,
 };
 use shared::error::BillingError;
 
@@ -29,47 +32,25 @@ use shared::{
 };
 use sqlx::PgPool;
 use std::borrow::Borrow;
-use std::str::FromStr;
-use stripe::{
-    generated::billing::subscription::SubscriptionProrationBehavior, Client, CreateCustomer,
-    CreateSubscription as CreateStripeSubscription, CreateSubscriptionItems, Customer,
-    CustomerInvoiceSettings, EventObject, EventType, List, ListPromotionCodes, PromotionCode,
-    PromotionCodeId, SetupIntent, SetupIntentId, UpdateCustomer, UpdateSubscription,
-    UpdateSubscriptionPauseCollection, UpdateSubscriptionPauseCollectionBehavior, Webhook,
-    WebhookError,
-};
+use stripe::{Event, EventObject, EventType, Webhook, WebhookError};
 use tracing::instrument;
 
 use crate::domain::user_authorization;
 use crate::extractor::{ScopeAdmin, TokenUserWithScope};
-use crate::stripe::create_stripe_client;
+use crate::service::{
+    payment::{Collection, NewSubscription, PaymentProvider, PromotionCodeId, SubscriptionChange},
+    ServiceData,
+};
 use crate::{db, extractor::TokenUser};
 
-async fn retrieve_promotion_code_id(
-    client: &Client,
+async fn find_promotion_code(
+    payments: &dyn PaymentProvider,
     promotion_code: &Option<String>,
 ) -> Result<Option<PromotionCodeId>, BillingError> {
-    Ok(if let Some(promotion_code) = &promotion_code {
-        let list_params = ListPromotionCodes {
-            active: Some(true),
-            code: Some(promotion_code),
-            ..Default::default()
-        };
-
-        let List {
-            data: mut codes, ..
-        } = PromotionCode::list(&client, &list_params).await?;
-
-        if codes.is_empty() || codes.len() > 1 {
-            return Err(BillingError::InvalidPromotionCode(
-                promotion_code.to_string(),
-            ));
-        }
-
-        Some(codes.pop().unwrap().id)
-    } else {
-        None
-    })
+    match promotion_code {
+        Some(promotion_code) => Ok(Some(payments.find_promotion_code(promotion_code).await?)),
+        None => Ok(None),
+    }
 }
 
 /// Create a new subscription for an authenticated user.
@@ -84,7 +65,7 @@ async fn retrieve_promotion_code_id(
 async fn create_subscription(
     auth: TokenUser,
     db: Data<PgPool>,
-    settings: Data<RuntimeSettings>,
+    payments: ServiceData<dyn PaymentProvider>,
     req: Json<<CreateSubscription as ApiEndpoint>::Req>,
 ) -> Result<
     (
@@ -106,134 +87,50 @@ async fn create_subscription(
         .await?
         .ok_or(BillingError::NotFound(format!("User {user_id}")))?;
 
-    let client = create_stripe_client(&settings)?;
-
-    let account = get_or_create_customer(db.as_ref(), &client, &user_profile, &plan).await?;
+    let account = get_or_create_customer(db.as_ref(), &**payments, &user_profile, &plan).await?;
 
-    let stripe_customer_id = stripe::CustomerId::from(account.stripe_customer_id.unwrap().clone());
+    let customer_id = account.stripe_customer_id.unwrap(); // get_or_create_customer guarantees that this is `Some`
 
     if let Some(setup_intent_id) = &req.setup_intent_id {
-        let setup_intent_id = SetupIntentId::from_str(setup_intent_id)
-            .map_err(|_| BillingError::InvalidSetupIntentId)?;
-        let setup_intent = SetupIntent::retrieve(&client, &setup_intent_id, &[])
-            .await
-            .map_err(|_| BillingError::InvalidSetupIntentId)?;
-
-        let payment_method_id = setup_intent
-            .payment_method
-            .ok_or(BillingError::InternalServerError(
-                anyhow!("Missing payment_method from SetupIntent").into(),
-            ))?
-            .id()
-            .to_string();
-
-        let customer_invoice_settings = CustomerInvoiceSettings {
-            default_payment_method: Some(payment_method_id),
-            ..Default::default()
-        };
-
-        let update_customer = UpdateCustomer {
-            invoice_settings: Some(customer_invoice_settings),
-            ..Default::default()
-        };
-
-        Customer::update(&client, &stripe_customer_id, update_customer).await?;
+        payments
+            .set_default_payment_method(&customer_id, setup_intent_id)
+            .await?;
     }
 
-    // Create a Stripe subscription
-    let stripe_subscription = {
-        let mut params = CreateStripeSubscription::new(stripe_customer_id);
-        params.items = Some(vec![CreateSubscriptionItems {
-            price: Some(plan.price_id.into()),
-            ..Default::default()
-        }]);
-
-        params.promotion_code = retrieve_promotion_code_id(&client, &req.promotion_code).await?;
-
-        // This will mark the subscription as incomplete until the payment intent has been
-        // confirmed.
-        params.payment_behavior = Some(stripe::SubscriptionPaymentBehavior::AllowIncomplete);
-        params.expand = &["latest_invoice.payment_intent"];
-
-        // If the user hasn't previously had a subscription, then we can set their trial period.
-        if account.subscription.is_none() {
-            params.trial_period_days = Some(plan.plan_type.trial_period().inner() as u32);
-            params.trial_settings = Some(stripe::CreateSubscriptionTrialSettings {
-                end_behavior: stripe::CreateSubscriptionTrialSettingsEndBehavior {
-                    missing_payment_method: stripe::CreateSubscriptionTrialSettingsEndBehaviorMissingPaymentMethod::Cancel,
-                },
-            });
-        }
-
-        stripe::Subscription::create(&client, params).await?
-    };
-
-    let stripe_subscription_id: StripeSubscriptionId = stripe_subscription.id.into();
-
-    let latest_invoice_id = stripe_subscription
-        .latest_invoice
-        .as_ref()
-        .map(|invoice| StripeInvoiceId::from(&invoice.id()));
-
-    let amount_due_in_cents = match stripe_subscription.latest_invoice.as_ref() {
-        Some(invoice) => invoice
-            .as_object()
-            .unwrap()
-            .amount_remaining
-            .map(AmountInCents::new),
-        None => None,
-    };
-
-    // Fetch the latest invoice so that we can retrieve the client secret. This is useful if the
-    // user doesn't get a trial, and needs to add a payment method so that the subscription can be
-    // completed.
-    let create_response = match stripe_subscription.latest_invoice {
-        Some(invoice) => {
-            invoice
-                .as_object()
-                .unwrap()
-                .payment_intent
-                .as_ref()
-                .map(|payment_intent| CreateSubscriptionResponse {
-                    subscription_id: stripe_subscription_id.clone(),
-                    client_secret: payment_intent
-                        .as_object()
-                        .unwrap()
-                        .client_secret
-                        .as_ref()
-                        .unwrap()
-                        .clone(),
-                })
-        }
-        None => None,
-    };
+    let created = payments
+        .create_subscription(NewSubscription {
+            customer_id,
+            price_id: plan.price_id,
+            promotion_code_id: find_promotion_code(&**payments, &req.promotion_code).await?,
+            // If the user hasn't previously had a subscription, then we can set their trial period.
+            trial_period_days: account
+                .subscription
+                .is_none()
+                .then(|| plan.plan_type.trial_period().inner() as u32),
+            collection: Collection::ChargeAutomatically,
+        })
+        .await?;
+
+    // The client secret is only there if the user doesn't get a trial, and needs to add a payment
+    // method so that the subscription can be completed.
+    let create_response = created
+        .client_secret
+        .map(|client_secret| CreateSubscriptionResponse {
+            subscription_id: created.subscription_id.clone(),
+            client_secret,
+        });
 
-    let price = AmountInCents::from(
-        stripe_subscription
-            .items
-            .data
-            .get(0)
-            .map(|item| item.clone())
-            .ok_or(anyhow!("Missing plan data"))?
-            .plan
-            .ok_or(anyhow!("Missing stripe subscription plan"))?
-            .amount
-            .ok_or(anyhow!("Missing subscription plan amount"))?,
-    );
-
-    // Create subscription in database
+    // Create subscription in database. Status changes after this are picked up by the webhook.
     let subscription = CreateSubscriptionRecord {
-        stripe_subscription_id,
+        stripe_subscription_id: created.subscription_id,
         subscription_plan_id: plan.plan_id,
-        status: Default::default(), // This will be updated in the webhook
-        current_period_end: Utc
-            .timestamp_opt(stripe_subscription.current_period_end, 0)
-            .latest()
-            .ok_or(anyhow::anyhow!("Invalid timestamp"))?,
+        status: created.status,
+        current_period_end: created.current_period_end,
         account_id: account.account_id,
-        latest_invoice_id,
-        amount_due_in_cents,
-        price,
+        latest_invoice_id: created.latest_invoice_id,
+        amount_due_in_cents: created.amount_due_in_cents,
+        price: created.price,
+        purchase_order: None,
     };
 
     db::billing::create_subscription(db.as_ref(), subscription)
@@ -247,7 +144,7 @@ async fn create_subscription(
 async fn update_subscription_cancel_status(
     auth: TokenUser,
     db: Data<PgPool>,
-    settings: Data<RuntimeSettings>,
+    payments: ServiceData<dyn PaymentProvider>,
     req: Json<<UpdateSubscriptionCancellation as ApiEndpoint>::Req>,
 ) -> Result<HttpResponse, <UpdateSubscriptionCancellation as ApiEndpoint>::Err> {
     let user_id = auth.user_id();
@@ -268,45 +165,30 @@ async fn update_subscription_cancel_status(
                 BillingError::NotFound("User does not have an existing subscription".into())
             })?;
 
-    let client = create_stripe_client(&settings)?;
-
-    let stripe_id: Result<stripe::SubscriptionId, anyhow::Error> =
-        subscription.stripe_subscription_id.try_into();
-
-    let stripe_id: stripe::SubscriptionId = stripe_id?;
-
-    let update_subscription = match &req.status {
+    let change = match &req.status {
         CancellationStatus::CancelAtPeriodEnd => {
             if !subscription.status.is_active() {
                 return Err(BillingError::NoActiveSubscription);
             }
 
-            UpdateSubscription {
-                cancel_at_period_end: Some(true),
-                ..Default::default()
-            }
+            SubscriptionChange::CancelAtPeriodEnd
         }
         CancellationStatus::RemoveCancellation => {
             if !subscription.status.is_canceled() {
                 return Err(BillingError::NoCanceledSubscription);
             }
 
-            UpdateSubscription {
-                cancel_at_period_end: Some(false),
-                ..Default::default()
-            }
+            SubscriptionChange::RemoveCancellation
         }
     };
 
-    let updated_subscription =
-        stripe::Subscription::update(&client, &stripe_id, update_subscription).await?;
+    let update_record = payments
+        .update_subscription(&subscription.stripe_subscription_id, change)
+        .await?;
 
-    db::billing::save_subscription(
-        db.as_ref(),
-        UpdateSubscriptionRecord::try_from(updated_subscription)?,
-    )
-    .await
-    .into_anyhow()?;
+    db::billing::save_subscription(db.as_ref(), update_record, None)
+        .await
+        .into_anyhow()?;
 
     Ok(HttpResponse::NoContent().finish())
 }
@@ -315,7 +197,7 @@ async fn update_subscription_cancel_status(
 async fn update_subscription_paused(
     auth: TokenUser,
     db: Data<PgPool>,
-    settings: Data<RuntimeSettings>,
+    payments: ServiceData<dyn PaymentProvider>,
     req: Json<<UpdateSubscriptionPaused as ApiEndpoint>::Req>,
 ) -> Result<HttpResponse, <UpdateSubscriptionPaused as ApiEndpoint>::Err> {
     let user_id = auth.user_id();
@@ -336,53 +218,30 @@ async fn update_subscription_paused(
                 BillingError::NotFound("User does not have an existing subscription".into())
             })?;
 
-    let client = create_stripe_client(&settings)?;
-
-    let stripe_id: Result<stripe::SubscriptionId, anyhow::Error> =
-        subscription.stripe_subscription_id.try_into();
-
-    let stripe_id: stripe::SubscriptionId = stripe_id?;
-
-    let update_subscription = match &req.paused {
+    let change = match &req.paused {
         true => {
             if !subscription.status.is_active() {
                 return Err(BillingError::NoActiveSubscription);
             }
 
-            UpdateSubscription {
-                pause_collection: Some(UpdateSubscriptionPauseCollection {
-                    behavior: UpdateSubscriptionPauseCollectionBehavior::Void,
-                    resumes_at: None,
-                }),
-                ..Default::default()
-            }
+            SubscriptionChange::Pause
         }
         false => {
             if !subscription.status.is_paused() {
                 return Err(BillingError::NoActiveSubscription);
             }
 
-            UpdateSubscription {
-                pause_collection: Some(UpdateSubscriptionPauseCollection {
-                    behavior: UpdateSubscriptionPauseCollectionBehavior::Void,
-                    // Resume right now. There is no way to remove the pause_collection using this crate,
-                    // so instead we just set the resumes_at time to right now.
-                    resumes_at: Some(chrono::Utc::now().timestamp()),
-                }),
-                ..Default::default()
-            }
+            SubscriptionChange::Resume
         }
     };
 
-    let updated_subscription =
-        stripe::Subscription::update(&client, &stripe_id, update_subscription).await?;
+    let update_record = payments
+        .update_subscription(&subscription.stripe_subscription_id, change)
+        .await?;
 
-    db::billing::save_subscription(
-        db.as_ref(),
-        UpdateSubscriptionRecord::try_from(updated_subscription)?,
-    )
-    .await
-    .into_anyhow()?;
+    db::billing::save_subscription(db.as_ref(), update_record, None)
+        .await
+        .into_anyhow()?;
 
     Ok(HttpResponse::NoContent().finish())
 }
@@ -392,7 +251,7 @@ async fn upgrade_subscription_plan_internal(
     plan_type: &PlanType,
     promotion_code: &Option<String>,
     db: Data<PgPool>,
-    settings: Data<RuntimeSettings>,
+    payments: ServiceData<dyn PaymentProvider>,
 ) -> Result<(), BillingError> {
     let account = db::account::get_account_by_user_id(db.as_ref(), &user_id)
         .await?
@@ -427,37 +286,17 @@ async fn upgrade_subscription_plan_internal(
         });
     }
 
-    let client = create_stripe_client(&settings)?;
-
-    let stripe_id: Result<stripe::SubscriptionId, anyhow::Error> =
-        subscription.stripe_subscription_id.try_into();
-
-    let stripe_id: stripe::SubscriptionId = stripe_id?;
-
-    let stripe_subscription = stripe::Subscription::retrieve(&client, &stripe_id, &[]).await?;
-
-    let price_item = stripe_subscription.items.data.first().ok_or(anyhow!(
-        "Expected exactly one subscription item for subscription {stripe_id}"
-    ))?;
-
-    let update_subscription = UpdateSubscription {
-        items: Some(vec![stripe::UpdateSubscriptionItems {
-            id: Some(price_item.id.to_string()),
-            price: Some(plan.price_id.into()),
-            ..Default::default()
-        }]),
-        promotion_code: retrieve_promotion_code_id(&client, &promotion_code).await?,
-        proration_behavior: Some(SubscriptionProrationBehavior::AlwaysInvoice),
-        ..Default::default()
+    let change = SubscriptionChange::ChangePrice {
+        price_id: plan.price_id,
+        promotion_code_id: find_promotion_code(&**payments, promotion_code).await?,
     };
 
-    let updated_subscription =
-        stripe::Subscription::update(&client, &stripe_id, update_subscription).await?;
-
-    let mut update_record = UpdateSubscriptionRecord::try_from(updated_subscription)?;
+    let mut update_record = payments
+        .update_subscription(&subscription.stripe_subscription_id, change)
+        .await?;
     update_record.subscription_plan_id = UpdateNonNullable::Change(plan.plan_id);
 
-    db::billing::save_subscription(db.as_ref(), update_record)
+    db::billing::save_subscription(db.as_ref(), update_record, None)
         .await
         .into_anyhow()?;
 
@@ -468,7 +307,7 @@ async fn upgrade_subscription_plan_internal(
 async fn upgrade_subscription_plan(
     auth: TokenUser,
     db: Data<PgPool>,
-    settings: Data<RuntimeSettings>,
+    payments: ServiceData<dyn PaymentProvider>,
     req: Json<<UpgradeSubscriptionPlan as ApiEndpoint>::Req>,
 ) -> Result<HttpResponse, <UpgradeSubscriptionPlan as ApiEndpoint>::Err> {
     upgrade_subscription_plan_internal(
@@ -476,7 +315,7 @@ async fn upgrade_subscription_plan(
         &req.plan_type,
         &req.promotion_code,
         db,
-        settings,
+        payments,
     )
     .await?;
 
@@ -487,10 +326,10 @@ async fn upgrade_subscription_plan(
 async fn upgrade_user_subscription_plan(
     _auth: TokenUserWithScope<ScopeAdmin>,
     db: Data<PgPool>,
-    settings: Data<RuntimeSettings>,
+    payments: ServiceData<dyn PaymentProvider>,
     req: Json<<AdminUpgradeSubscriptionPlan as ApiEndpoint>::Req>,
 ) -> Result<HttpResponse, <AdminUpgradeSubscriptionPlan as ApiEndpoint>::Err> {
-    upgrade_subscription_plan_internal(req.user_id, &req.plan_type, &None, db, settings).await?;
+    upgrade_subscription_plan_internal(req.user_id, &req.plan_type, &None, db, payments).await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
@@ -499,7 +338,7 @@ async fn upgrade_user_subscription_plan(
 async fn create_setup_intent(
     auth: TokenUser,
     db: Data<PgPool>,
-    settings: Data<RuntimeSettings>,
+    payments: ServiceData<dyn PaymentProvider>,
     req: Json<<CreateSetupIntent as ApiEndpoint>::Req>,
 ) -> Result<
     (
@@ -521,34 +360,19 @@ async fn create_setup_intent(
         .await?
         .ok_or(BillingError::NotFound(format!("User {user_id}")))?;
 
-    let client = create_stripe_client(&settings)?;
-
-    let account = get_or_create_customer(db.as_ref(), &client, &user_profile, &plan).await?;
+    let account = get_or_create_customer(db.as_ref(), &**payments, &user_profile, &plan).await?;
     let customer_id = account.stripe_customer_id.unwrap(); // get_or_create_customer guarantees that this is `Some`
 
-    let create_setup_intent = stripe::CreateSetupIntent {
-        customer: Some(customer_id.into()),
-        payment_method_types: Some(vec!["card".into(), "link".into()]),
-        // TODO need to set `automatic_payment_methods` but it isn't available in async-stripe?
-        ..Default::default()
-    };
-    let setup_intent = SetupIntent::create(&client, create_setup_intent).await?;
-
-    Ok((
-        Json(
-            setup_intent
-                .client_secret
-                .ok_or(anyhow!("Missing client secret"))?,
-        ),
-        http::StatusCode::CREATED,
-    ))
+    let client_secret = payments.create_setup_intent(&customer_id).await?;
+
+    Ok((Json(client_secret), http::StatusCode::CREATED))
 }
 
 #[instrument(skip_all)]
 async fn create_customer_portal_link(
     auth: TokenUser,
     db: Data<PgPool>,
-    settings: Data<RuntimeSettings>,
+    payments: ServiceData<dyn PaymentProvider>,
 ) -> Result<
     (
         Json<<CreateCustomerPortalLink as ApiEndpoint>::Res>,
@@ -567,19 +391,16 @@ async fn create_customer_portal_link(
         None => return Err(error::BillingError::Forbidden),
     };
 
-    let create_session = stripe::CreateBillingPortalSession::new(customer_id.into());
-
-    let client = create_stripe_client(&settings)?;
-    let session = stripe::BillingPortalSession::create(&client, create_session).await?;
+    let url = payments.create_portal_link(&customer_id).await?;
 
-    Ok((Json(session.url), http::StatusCode::CREATED))
+    Ok((Json(url), http::StatusCode::CREATED))
 }
 
 /// Get the user accounts customer ID. If they don't have one yet, then we create one here.
 #[instrument(skip_all)]
 async fn get_or_create_customer(
     db: &PgPool,
-    client: &Client,
+    payments: &dyn PaymentProvider,
     user_profile: &UserProfile,
     plan: &SubscriptionPlan,
 ) -> Result<Account, BillingError> {
@@ -627,34 +448,15 @@ async fn get_or_create_customer(
     if account.stripe_customer_id.is_none() {
         let customer_id = match account.account_type {
             AccountType::School => {
-                let school = db::account::get_school_account_by_account_id(db, &account.account_id)
-                    .await
-                    .into_anyhow()?
-                    .ok_or(BillingError::SchoolNotFound)?;
-
-                create_stripe_customer(
-                    client,
-                    CreateCustomer {
-                        email: Some(school.email.as_str()),
-                        name: Some(school.school_name.as_str()),
-                        ..Default::default()
-                    },
-                )
-                .await?
+                create_school_customer(db, payments, &account.account_id).await?
             }
             AccountType::Individual => {
-                create_stripe_customer(
-                    client,
-                    CreateCustomer {
-                        email: Some(user_profile.email.as_str()),
-                        name: Some(&format!(
-                            "{} {}",
-                            user_profile.given_name, user_profile.family_name
-                        )),
-                        ..Default::default()
-                    },
-                )
-                .await?
+                payments
+                    .create_customer(
+                        user_profile.email.as_str(),
+                        &format!("{} {}", user_profile.given_name, user_profile.family_name),
+                    )
+                    .await?
             }
         };
 
@@ -666,12 +468,196 @@ async fn get_or_create_customer(
     Ok(account)
 }
 
-#[instrument(skip(client))]
-async fn create_stripe_customer(
-    client: &Client,
-    create_customer: CreateCustomer<'_>,
+/// Create a customer for a school account, billed under the school's name and email.
+async fn create_school_customer(
+    db: &PgPool,
+    payments: &dyn PaymentProvider,
+    account_id: &AccountId,
 ) -> Result<CustomerId, BillingError> {
-    Ok(Customer::create(client, create_customer).await?.id.into())
+    let school = db::account::get_school_account_by_account_id(db, account_id)
+        .await
+        .into_anyhow()?
+        .ok_or(BillingError::SchoolNotFound)?;
+
+    payments
+        .create_customer(school.email.as_str(), school.school_name.as_str())
+        .await
+}
+
+/// List the invoices of the user's account.
+#[instrument(skip_all)]
+async fn list_invoices(
+    auth: TokenUser,
+    db: Data<PgPool>,
+    payments: ServiceData<dyn PaymentProvider>,
+) -> Result<Json<<ListInvoices as ApiEndpoint>::Res>, <ListInvoices as ApiEndpoint>::Err> {
+    let user_id = auth.user_id();
+    let account = db::account::get_account_by_user_id(db.as_ref(), &user_id)
+        .await?
+        .ok_or_else(|| BillingError::NotFound("User does not have an account".into()))?;
+
+    user_authorization(db.as_ref(), &user_id, &account.account_id)
+        .await
+        .map_err(Into::<BillingError>::into)?
+        .test_authorized(true)?;
+
+    // Without a customer nothing has ever been invoiced.
+    let invoices = match &account.stripe_customer_id {
+        Some(customer_id) => payments.list_invoices(customer_id).await?,
+        None => Vec::new(),
+    };
+
+    Ok(Json(ListInvoicesResponse { invoices }))
+}
+
+/// List the invoices of a school.
+#[instrument(skip_all)]
+async fn list_school_invoices(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    payments: ServiceData<dyn PaymentProvider>,
+    path: web::Path<SchoolId>,
+) -> Result<
+    Json<<AdminListSchoolInvoices as ApiEndpoint>::Res>,
+    <AdminListSchoolInvoices as ApiEndpoint>::Err,
+> {
+    let school_id = path.into_inner();
+
+    let school = db::account::get_school_account_by_id(db.as_ref(), &school_id)
+        .await
+        .into_anyhow()?
+        .ok_or(BillingError::SchoolNotFound)?;
+
+    let account = db::account::get_account_by_id(db.as_ref(), &school.account_id)
+        .await?
+        .ok_or_else(|| anyhow!("Missing account for school {school_id}"))?;
+
+    let invoices = match &account.stripe_customer_id {
+        Some(customer_id) => payments.list_invoices(customer_id).await?,
+        None => Vec::new(),
+    };
+
+    Ok(Json(ListInvoicesResponse { invoices }))
+}
+
+/// Subscribe a school which pays by invoice against a purchase order.
+///
+/// The subscription is active straight away, so the school gets the plan's entitlements while the
+/// invoice is open. The invoice is sent to the school's billing email by the payment provider.
+#[instrument(skip_all)]
+async fn create_invoiced_subscription(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    payments: ServiceData<dyn PaymentProvider>,
+    path: web::Path<SchoolId>,
+    req: Json<<AdminCreateInvoicedSubscription as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, <AdminCreateInvoicedSubscription as ApiEndpoint>::Err> {
+    let school_id = path.into_inner();
+    let req = req.into_inner();
+
+    let plan: SubscriptionPlan = db::billing::get_subscription_plan_by_type(&db, req.plan_type)
+        .await
+        .into_anyhow()?
+        .ok_or(BillingError::NotFound(format!("Plan {}", req.plan_type)))?;
+
+    if !matches!(plan.plan_type.subscription_type(), SubscriptionType::School) {
+        return Err(BillingError::IncorrectPlanType {
+            expected: AccountType::School,
+            found: plan.plan_type.subscription_type(),
+        });
+    }
+
+    let school = db::account::get_school_account_by_id(db.as_ref(), &school_id)
+        .await
+        .into_anyhow()?
+        .ok_or(BillingError::SchoolNotFound)?;
+
+    let account = db::account::get_account_by_id(db.as_ref(), &school.account_id)
+        .await?
+        .ok_or_else(|| anyhow!("Missing account for school {school_id}"))?;
+
+    if let Some(subscription) = &account.subscription {
+        if !matches!(subscription.status, SubscriptionStatus::Expired) {
+            return Err(BillingError::SubscriptionExists);
+        }
+    }
+
+    let customer_id = match account.stripe_customer_id {
+        Some(customer_id) => customer_id,
+        None => {
+            let customer_id =
+                create_school_customer(db.as_ref(), &**payments, &account.account_id).await?;
+
+            db::account::save_customer_id(db.as_ref(), &account.account_id, &customer_id).await?;
+
+            customer_id
+        }
+    };
+
+    let purchase_order = PurchaseOrder {
+        purchase_order_number: req.purchase_order_number,
+        days_until_due: req.days_until_due.unwrap_or(PURCHASE_ORDER_DAYS_UNTIL_DUE),
+    };
+
+    let created = payments
+        .create_subscription(NewSubscription {
+            customer_id,
+            price_id: plan.price_id,
+            promotion_code_id: None,
+            trial_period_days: None,
+            collection: Collection::SendInvoice {
+                days_until_due: purchase_order.days_until_due,
+                purchase_order_number: purchase_order.purchase_order_number.clone(),
+            },
+        })
+        .await?;
+
+    let subscription = CreateSubscriptionRecord {
+        stripe_subscription_id: created.subscription_id,
+        subscription_plan_id: plan.plan_id,
+        status: created.status,
+        current_period_end: created.current_period_end,
+        account_id: account.account_id,
+        latest_invoice_id: created.latest_invoice_id,
+        amount_due_in_cents: created.amount_due_in_cents,
+        price: created.price,
+        purchase_order: Some(purchase_order),
+    };
+
+    db::billing::create_subscription(db.as_ref(), subscription)
+        .await
+        .into_anyhow()?;
+
+    Ok(HttpResponse::Created().finish())
+}
+
+/// Mark an invoice as paid once the school has paid it outside of the payment provider.
+#[instrument(skip_all)]
+async fn mark_invoice_paid(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    payments: ServiceData<dyn PaymentProvider>,
+    path: web::Path<StripeInvoiceId>,
+) -> Result<
+    Json<<AdminMarkInvoicePaid as ApiEndpoint>::Res>,
+    <AdminMarkInvoicePaid as ApiEndpoint>::Err,
+> {
+    let invoice_id = path.into_inner();
+
+    let invoice = payments.mark_invoice_paid(&invoice_id).await?;
+
+    // The webhook catches up with this as well, but the amount due shouldn't linger until then.
+    if let Some(subscription_id) =
+        db::billing::get_stripe_subscription_id_by_invoice_id(db.as_ref(), &invoice_id)
+            .await
+            .into_anyhow()?
+    {
+        db::billing::set_subscription_amount_due(db.as_ref(), subscription_id, invoice.amount_due)
+            .await
+            .into_anyhow()?;
+    }
+
+    Ok(Json(invoice))
 }
 
 #[instrument(skip_all)]
@@ -696,77 +682,192 @@ async fn webhook(
         .ok()
         .unwrap_or_default();
 
-    match Webhook::construct_event(payload_str, stripe_signature, secret) {
-        Ok(event) => match event.type_ {
-            EventType::PaymentMethodAttached => {
-                save_payment_method(db, event.data.object, EventType::PaymentMethodAttached)
-                    .await?;
-            }
-            EventType::PaymentMethodUpdated => {
-                save_payment_method(db, event.data.object, EventType::PaymentMethodUpdated).await?;
-            }
-            EventType::PaymentMethodDetached => {
-                save_payment_method(db, event.data.object, EventType::PaymentMethodDetached)
-                    .await?;
-            }
-            _ => {
-                match event.data.object {
-                    EventObject::Subscription(subscription) => {
-                        let _span = tracing::info_span!("subscription event");
-
-                        // Save a subscription from a subscription event
-                        // Note: this will handle invoice changes on subscriptions as well since a
-                        // subscription is updated when an invoice is paid/unpaid/etc.
-                        let update_subscription = UpdateSubscriptionRecord::try_from(subscription)?;
-
-                        db::billing::save_subscription(db.as_ref(), update_subscription)
-                            .await
-                            .into_anyhow()?;
-                    }
-                    EventObject::Invoice(invoice) => {
-                        let _span = tracing::info_span!("invoice event");
-
-                        let invoice_id = StripeInvoiceId::from(&invoice.id);
-
-                        if let Some(subscription_id) =
-                            db::billing::get_stripe_subscription_id_by_invoice_id(
-                                db.as_ref(),
-                                &invoice_id,
-                            )
-                            .await
-                            .into_anyhow()?
-                        {
-                            db::billing::set_subscription_amount_due(
-                                db.as_ref(),
-                                subscription_id,
-                                AmountInCents::new(invoice.amount_remaining.unwrap_or_default()),
-                            )
-                            .await
-                            .into_anyhow()?;
-                        }
-                    }
-                    _ => {
-                        log::trace!("Unknown event encountered in webhook: {:?}", event.type_);
-                    }
-                }
-            }
-        },
+    let event = match Webhook::construct_event(payload_str, stripe_signature, secret) {
+        Ok(event) => Ok(event),
+        // The signature is checked before the event is parsed, so this is a genuine event which
+        // the crate doesn't support yet, e.g. `billing_portal.session`.
+        Err(WebhookError::BadParse(error)) => Err(error),
         Err(error) => {
-            match error {
-                WebhookError::BadParse(serde_error)
-                    if serde_error.to_string().contains("billing_portal.session") =>
-                {
-                    // TODO remove this match once the crate supports the `billing_portal.session` event.
-                    // Don't log this warning.
+            log::warn!("Failed to construct webhook event: {error:#?}");
+            return Ok(HttpResponse::Ok().finish());
+        }
+    };
+
+    let raw: serde_json::Value = serde_json::from_str(payload_str).into_anyhow()?;
+
+    let id = StripeEventId::from(raw["id"].as_str().unwrap_or_default().to_owned());
+    let event_type = raw["type"].as_str().unwrap_or_default();
+
+    // Stripe can deliver an event more than once, only handle it until it's succeeded.
+    if !db::billing::save_webhook_event(&db, &id, event_type, &raw)
+        .await
+        .into_anyhow()?
+    {
+        log::debug!("Skipping webhook event {id}, it has already been handled");
+        return Ok(HttpResponse::Ok().finish());
+    }
+
+    match event {
+        Ok(event) => {
+            process_webhook_event(&db, &id, event).await?;
+        }
+        Err(error) => {
+            db::billing::record_webhook_event_attempt(
+                &db,
+                &id,
+                WebhookEventStatus::Ignored,
+                Some(&error.to_string()),
+            )
+            .await
+            .into_anyhow()?;
+        }
+    }
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// Handles a webhook event and records the outcome in the event log.
+///
+/// A failed event is left in the log as failed and its error is returned, so that Stripe retries
+/// it as well.
+async fn process_webhook_event(
+    db: &PgPool,
+    id: &StripeEventId,
+    event: Event,
+) -> anyhow::Result<WebhookEventStatus> {
+    let res = handle_webhook_event(db, event).await;
+
+    let (status, error) = match &res {
+        Ok(true) => (WebhookEventStatus::Processed, None),
+        Ok(false) => (WebhookEventStatus::Ignored, None),
+        Err(error) => (WebhookEventStatus::Failed, Some(format!("{error:#}"))),
+    };
+
+    db::billing::record_webhook_event_attempt(db, id, status, error.as_deref()).await?;
+
+    res.map(|_| status)
+}
+
+/// Applies a webhook event. Returns `false` if it's not an event we handle.
+async fn handle_webhook_event(db: &PgPool, event: Event) -> anyhow::Result<bool> {
+    match event.type_ {
+        EventType::PaymentMethodAttached
+        | EventType::PaymentMethodUpdated
+        | EventType::PaymentMethodDetached => {
+            save_payment_method(db, event.data.object, event.type_).await?;
+        }
+        _ => {
+            match event.data.object {
+                EventObject::Subscription(subscription) => {
+                    let _span = tracing::info_span!("subscription event");
+
+                    // Save a subscription from a subscription event
+                    // Note: this will handle invoice changes on subscriptions as well since a
+                    // subscription is updated when an invoice is paid/unpaid/etc.
+                    let update_subscription = UpdateSubscriptionRecord::try_from(subscription)?;
+
+                    // Events can arrive out of order, or be replayed long after they were sent
+                    let as_of = Utc
+                        .timestamp_opt(event.created, 0)
+                        .single()
+                        .ok_or(anyhow!("Invalid event timestamp"))?;
+
+                    db::billing::save_subscription(db, update_subscription, Some(as_of)).await?;
+                }
+                EventObject::Invoice(invoice) => {
+                    let _span = tracing::info_span!("invoice event");
+
+                    let invoice_id = StripeInvoiceId::from(&invoice.id);
+
+                    if let Some(subscription_id) =
+                        db::billing::get_stripe_subscription_id_by_invoice_id(db, &invoice_id)
+                            .await?
+                    {
+                        db::billing::set_subscription_amount_due(
+                            db,
+                            subscription_id,
+                            AmountInCents::new(invoice.amount_remaining.unwrap_or_default()),
+                        )
+                        .await?;
+                    }
                 }
                 _ => {
-                    log::warn!("Failed to construct webhook event: {error:#?}");
+                    log::trace!("Unknown event encountered in webhook: {:?}", event.type_);
+                    return Ok(false);
                 }
             }
         }
     }
 
-    Ok(HttpResponse::Ok().finish())
+    Ok(true)
+}
+
+/// List Stripe webhook events, e.g. to find the ones which failed.
+async fn list_webhook_events(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    query: Option<Query<<ListWebhookEvents as ApiEndpoint>::Req>>,
+) -> Result<Json<<ListWebhookEvents as ApiEndpoint>::Res>, BillingError> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let (events, total_count) = futures::try_join!(
+        db::billing::list_webhook_events(&db, &query),
+        db::billing::count_webhook_events(&db, &query),
+    )
+    .into_anyhow()?;
+
+    Ok(Json(ListWebhookEventsResponse {
+        events,
+        pages: total_count.paged(query.page_limit),
+        total_count,
+    }))
+}
+
+/// Handle a Stripe webhook event again.
+async fn replay_webhook_event(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    path: web::Path<StripeEventId>,
+) -> Result<Json<<ReplayWebhookEvent as ApiEndpoint>::Res>, BillingError> {
+    let id = path.into_inner();
+
+    let webhook_event = db::billing::get_webhook_event(&db, &id)
+        .await
+        .into_anyhow()?
+        .ok_or_else(|| BillingError::NotFound(format!("Webhook event {id}")))?;
+
+    // Handled events may have been superseded since, handling them again could undo later changes
+    if !matches!(
+        webhook_event.status,
+        WebhookEventStatus::Failed | WebhookEventStatus::Ignored
+    ) {
+        return Err(BillingError::WebhookEventNotReplayable);
+    }
+
+    match serde_json::from_value::<Event>(webhook_event.payload) {
+        Ok(event) => {
+            if let Err(error) = process_webhook_event(&db, &id, event).await {
+                log::warn!("Replaying webhook event {id} failed: {error:#}");
+            }
+        }
+        Err(error) => {
+            db::billing::record_webhook_event_attempt(
+                &db,
+                &id,
+                WebhookEventStatus::Ignored,
+                Some(&error.to_string()),
+            )
+            .await
+            .into_anyhow()?;
+        }
+    }
+
+    let webhook_event = db::billing::get_webhook_event(&db, &id)
+        .await
+        .into_anyhow()?
+        .ok_or_else(|| BillingError::NotFound(format!("Webhook event {id}")))?;
+
+    Ok(Json(webhook_event))
 }
 
 /// Save a payment method for a customer. This will overwrite the existing payment method
@@ -774,7 +875,7 @@ async fn webhook(
 /// will be removed.
 #[instrument(skip(db, event_object))]
 async fn save_payment_method(
-    db: Data<PgPool>,
+    db: &PgPool,
     event_object: EventObject,
     event_type: EventType,
 ) -> anyhow::Result<()> {
@@ -798,10 +899,10 @@ async fn save_payment_method(
             return Ok(());
         };
 
-    match db::account::get_account_id_by_customer_id(db.as_ref(), &customer_id).await? {
+    match db::account::get_account_id_by_customer_id(db, &customer_id).await? {
         Some(account_id) => {
             db::account::save_payment_method(
-                db.as_ref(),
+                db,
                 &account_id,
                 payment_method.map(PaymentMethod::from),
             )
@@ -854,5 +955,33 @@ pub fn configure(cfg: &mut ServiceConfig) {
             .route()
             .to(create_customer_portal_link),
     )
+    .route(
+        <ListInvoices as ApiEndpoint>::Path::PATH,
+        ListInvoices::METHOD.route().to(list_invoices),
+    )
+    .route(
+        <AdminListSchoolInvoices as ApiEndpoint>::Path::PATH,
+        AdminListSchoolInvoices::METHOD
+            .route()
+            .to(list_school_invoices),
+    )
+    .route(
+        <AdminCreateInvoicedSubscription as ApiEndpoint>::Path::PATH,
+        AdminCreateInvoicedSubscription::METHOD
+            .route()
+            .to(create_invoiced_subscription),
+    )
+    .route(
+        <AdminMarkInvoicePaid as ApiEndpoint>::Path::PATH,
+        AdminMarkInvoicePaid::METHOD.route().to(mark_invoice_paid),
+    )
+    .route(
+        <ListWebhookEvents as ApiEndpoint>::Path::PATH,
+        ListWebhookEvents::METHOD.route().to(list_webhook_events),
+    )
+    .route(
+        <ReplayWebhookEvent as ApiEndpoint>::Path::PATH,
+        ReplayWebhookEvent::METHOD.route().to(replay_webhook_event),
+    )
     .route("/v1/stripe-webhook", Method::Post.route().to(webhook));
 }
@@ -5,8 +5,9 @@ use actix_web::{
 use shared::{
     api::endpoints::{category, ApiEndpoint, PathParts},
     domain::category::{
-        CategoryId, CategoryResponse, CategoryTreeScope, CreateCategoryRequest, GetCategoryRequest,
-        NewCategoryResponse, UpdateCategoryRequest,
+        CategoryId, CategoryResponse, CategoryTreeScope, CategoryUsageResponse,
+        CreateCategoryRequest, GetCategoryRequest, MergeCategoryRequest, NewCategoryResponse,
+        RecategorizeRequest, RecategorizeResponse, UpdateCategoryRequest,
     },
 };
 use sqlx::PgPool;
@@ -88,6 +89,7 @@ async fn update_category(
 ) -> actix_web::Result<HttpResponse, error::CategoryUpdate> {
     let UpdateCategoryRequest {
         name,
+        translated_name,
         parent_id,
         index,
         user_scopes,
@@ -98,6 +100,7 @@ async fn update_category(
         path.into_inner(),
         parent_id,
         name.as_deref(),
+        translated_name,
         index.map(|it| it as i16),
         user_scopes,
     )
@@ -117,6 +120,64 @@ async fn delete_category(
     Ok(HttpResponse::NoContent().finish())
 }
 
+/// Get how many assets use each category.
+async fn usage(
+    db: Data<PgPool>,
+    _claims: TokenUserWithScope<ScopeManageCategory>,
+) -> actix_web::Result<Json<<category::Usage as ApiEndpoint>::Res>, error::Server> {
+    let usage = db::category::usage(&db).await?;
+
+    Ok(Json(CategoryUsageResponse { usage }))
+}
+
+/// Merge a category into another one.
+async fn merge_category(
+    db: Data<PgPool>,
+    _claims: TokenUserWithScope<ScopeManageCategory>,
+    req: Json<<category::Merge as ApiEndpoint>::Req>,
+    path: web::Path<CategoryId>,
+) -> actix_web::Result<HttpResponse, error::CategoryMerge> {
+    let MergeCategoryRequest { into } = req.into_inner();
+
+    db::category::merge(&db, path.into_inner(), into).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Add and remove categories on many assets.
+async fn recategorize(
+    db: Data<PgPool>,
+    _claims: TokenUserWithScope<ScopeManageCategory>,
+    req: Json<<category::Recategorize as ApiEndpoint>::Req>,
+) -> actix_web::Result<Json<<category::Recategorize as ApiEndpoint>::Res>, error::Recategorize> {
+    let RecategorizeRequest {
+        assets,
+        in_category,
+        mut add,
+        mut remove,
+        preview,
+    } = req.into_inner();
+
+    add.sort_by_key(|it| it.0);
+    add.dedup();
+    remove.sort_by_key(|it| it.0);
+    remove.dedup();
+
+    if add.iter().any(|id| remove.contains(id)) {
+        return Err(error::Recategorize::AddedAndRemoved);
+    }
+
+    let ids: Vec<_> = add.iter().map(|it| it.0).collect();
+    if db::category::get_exact(&db, &ids).await?.len() != add.len() {
+        return Err(error::Recategorize::CategoryNotFound);
+    }
+
+    let changed =
+        db::category::recategorize(&db, &assets, in_category, &add, &remove, preview).await?;
+
+    Ok(Json(RecategorizeResponse { changed }))
+}
+
 pub fn configure(cfg: &mut ServiceConfig) {
     cfg.route(
         <category::Get as ApiEndpoint>::Path::PATH,
@@ -133,5 +194,17 @@ pub fn configure(cfg: &mut ServiceConfig) {
     .route(
         <category::Delete as ApiEndpoint>::Path::PATH,
         category::Delete::METHOD.route().to(delete_category),
+    )
+    .route(
+        <category::Usage as ApiEndpoint>::Path::PATH,
+        category::Usage::METHOD.route().to(usage),
+    )
+    .route(
+        <category::Merge as ApiEndpoint>::Path::PATH,
+        category::Merge::METHOD.route().to(merge_category),
+    )
+    .route(
+        <category::Recategorize as ApiEndpoint>::Path::PATH,
+        category::Recategorize::METHOD.route().to(recategorize),
     );
 }
@@ -8,6 +8,7 @@ use shared::{
     domain::{
         asset::UserOrMe,
         circle::{BrowseMembersResponse, CircleBrowseResponse, CircleId, CircleSearchResponse},
+        moderation::FlaggedContentKind,
         user::UserId,
         CreateResponse,
     },
@@ -16,6 +17,7 @@ use shared::{
 use sqlx::PgPool;
 
 use crate::{
+    content_safety::ContentSafety,
     db::{self},
     error::{self},
     extractor::{get_user_id, TokenUser},
@@ -24,8 +26,11 @@ use crate::{
 };
 
 /// Create an Circle.
+///
+/// The circle is quarantined if its name or description are flagged.
 async fn create(
     db: Data<PgPool>,
+    safety: Data<ContentSafety>,
     claims: TokenUser,
     req: Json<<circle::Create as ApiEndpoint>::Req>,
 ) -> Result<HttpResponse, error::CreateWithMetadata> {
@@ -43,17 +48,28 @@ async fn create(
     )
     .await?;
 
+    let reasons = safety.check_text(&[
+        ("displayName", req.display_name.as_str()),
+        ("description", req.description.as_str()),
+    ]);
+
+    db::moderation::flag(&mut txn, FlaggedContentKind::Circle, id.0, &reasons).await?;
+
     txn.commit().await?;
 
     Ok(HttpResponse::Created().json(CreateResponse { id }))
 }
 
 /// Update a Circle
+///
+/// The circle is quarantined and removed from search if its new name or description are flagged.
 async fn update(
     db: Data<PgPool>,
+    safety: Data<ContentSafety>,
     claims: TokenUser,
     req: Option<Json<<circle::Update as ApiEndpoint>::Req>>,
     path: Path<CircleId>,
+    algolia: Option<ServiceData<crate::algolia::Manager>>,
 ) -> Result<HttpResponse, error::UpdateWithMetadata> {
     let id = path.into_inner();
     let user_id = claims.user_id();
@@ -71,6 +87,27 @@ async fn update(
     )
     .await?;
 
+    let fields: Vec<(&str, &str)> = [
+        ("displayName", req.display_name.as_deref()),
+        ("description", req.description.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(field, text)| Some((field, text?)))
+    .collect();
+
+    let reasons = safety.check_text(&fields);
+
+    let mut txn = db.begin().await?;
+    let flagged =
+        db::moderation::flag(&mut txn, FlaggedContentKind::Circle, id.0, &reasons).await?;
+    txn.commit().await?;
+
+    if flagged {
+        if let Some(algolia) = algolia {
+            algolia.delete_circle(id).await;
+        }
+    }
+
     Ok(HttpResponse::NoContent().finish())
 }
 
@@ -123,6 +160,8 @@ async fn join(
         .await
         .map_err(|e| error::NotFound::InternalServerError(e))?;
 
+    db::notification::circle_joined(&db, id, user_id).await?;
+
     Ok(HttpResponse::NoContent().finish())
 }
 
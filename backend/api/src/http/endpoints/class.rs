@@ -0,0 +1,636 @@
+use actix_web::{
+    web::{Data, Json, Path, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::class, ApiEndpoint, PathParts},
+    domain::{
+        class::{ClassId, ClassListResponse},
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser};
+
+/// Create a Class owned by the user.
+async fn create(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<<class::Create as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Class> {
+    let req = req.into_inner();
+    let user_id = claims.user_id();
+
+    if let Some(account_id) = req.account_id {
+        db::account::user_account_membership(&db, &user_id, &account_id)
+            .await?
+            .ok_or(error::Class::Forbidden)?;
+    }
+
+    let display_name = req.display_name.trim();
+
+    if display_name.is_empty() {
+        return Err(error::Class::BadRequest(
+            "Class name can't be empty".to_owned(),
+        ));
+    }
+
+    let id = db::class::create(&db, user_id, display_name, req.account_id).await?;
+
+    Ok(HttpResponse::Created().json(CreateResponse { id }))
+}
+
+/// List the user's Classes.
+async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<class::List as ApiEndpoint>::Res>, error::Class> {
+    let classes = db::class::list(&db, claims.user_id()).await?;
+
+    Ok(Json(ClassListResponse { classes }))
+}
+
+/// Get a Class.
+async fn get(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<ClassId>,
+) -> Result<Json<<class::Get as ApiEndpoint>::Res>, error::Class> {
+    let id = path.into_inner();
+
+    db::class::authz(&db, claims.user_id(), id).await?;
+
+    let class = db::class::get(&db, id)
+        .await?
+        .ok_or(error::Class::ResourceNotFound)?;
+
+    Ok(Json(class))
+}
+
+/// Update a Class.
+async fn update(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<ClassId>,
+    req: Option<Json<<class::Update as ApiEndpoint>::Req>>,
+) -> Result<HttpResponse, error::Class> {
+    let id = path.into_inner();
+    let req = req.map_or_else(Default::default, Json::into_inner);
+
+    db::class::authz(&db, claims.user_id(), id).await?;
+
+    let display_name = req.display_name.as_deref().map(str::trim);
+
+    if display_name.map_or(false, str::is_empty) {
+        return Err(error::Class::BadRequest(
+            "Class name can't be empty".to_owned(),
+        ));
+    }
+
+    db::class::update(&db, id, display_name, req.reset_login_code).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Delete a Class.
+async fn delete(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<ClassId>,
+) -> Result<HttpResponse, error::Class> {
+    let id = path.into_inner();
+
+    db::class::authz(&db, claims.user_id(), id).await?;
+
+    db::class::delete(&db, id).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub mod student {
+    use actix_web::{
+        web::{Data, Json, Path},
+        HttpResponse,
+    };
+    use shared::{
+        api::{endpoints::class, ApiEndpoint},
+        domain::class::{
+            parse_roster_csv, ClassId, StudentId, StudentImportResponse, StudentListResponse,
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::{db, error, extractor::TokenUser};
+
+    /// List the students of a Class.
+    pub(super) async fn list(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<ClassId>,
+    ) -> Result<Json<<class::student::List as ApiEndpoint>::Res>, error::Class> {
+        let class_id = path.into_inner();
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        let students = db::class::list_students(&db, class_id).await?;
+
+        Ok(Json(StudentListResponse { students }))
+    }
+
+    /// Add a student to a Class.
+    pub(super) async fn create(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<ClassId>,
+        req: Json<<class::student::Create as ApiEndpoint>::Req>,
+    ) -> Result<HttpResponse, error::Class> {
+        let class_id = path.into_inner();
+        let req = req.into_inner();
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        let display_name = req.display_name.trim();
+
+        if display_name.is_empty() {
+            return Err(error::Class::BadRequest(
+                "Student name can't be empty".to_owned(),
+            ));
+        }
+
+        let student = db::class::create_student(&db, class_id, display_name).await?;
+
+        Ok(HttpResponse::Created().json(student))
+    }
+
+    /// Add students to a Class from a CSV roster.
+    pub(super) async fn import(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<ClassId>,
+        req: Json<<class::student::Import as ApiEndpoint>::Req>,
+    ) -> Result<HttpResponse, error::Class> {
+        let class_id = path.into_inner();
+        let req = req.into_inner();
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        let names =
+            parse_roster_csv(&req.csv).map_err(|err| error::Class::BadRequest(err.to_string()))?;
+
+        let (students, skipped) = db::class::import_students(&db, class_id, names).await?;
+
+        Ok(HttpResponse::Created().json(StudentImportResponse { students, skipped }))
+    }
+
+    /// Update a student.
+    pub(super) async fn update(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<(ClassId, StudentId)>,
+        req: Option<Json<<class::student::Update as ApiEndpoint>::Req>>,
+    ) -> Result<HttpResponse, error::Class> {
+        let (class_id, student_id) = path.into_inner();
+        let req = req.map_or_else(Default::default, Json::into_inner);
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        let display_name = req.display_name.as_deref().map(str::trim);
+
+        if display_name.map_or(false, str::is_empty) {
+            return Err(error::Class::BadRequest(
+                "Student name can't be empty".to_owned(),
+            ));
+        }
+
+        let updated =
+            db::class::update_student(&db, class_id, student_id, display_name, req.reset_login)
+                .await?;
+
+        if !updated {
+            return Err(error::Class::ResourceNotFound);
+        }
+
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// Remove a student from a Class.
+    pub(super) async fn delete(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<(ClassId, StudentId)>,
+    ) -> Result<HttpResponse, error::Class> {
+        let (class_id, student_id) = path.into_inner();
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        if !db::class::delete_student(&db, class_id, student_id).await? {
+            return Err(error::Class::ResourceNotFound);
+        }
+
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// Export everything stored about a student.
+    pub(super) async fn export_data(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<(ClassId, StudentId)>,
+    ) -> Result<Json<<class::student::ExportData as ApiEndpoint>::Res>, error::Class> {
+        let (class_id, student_id) = path.into_inner();
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        let data = db::class::student_data(&db, class_id, student_id)
+            .await?
+            .ok_or(error::Class::ResourceNotFound)?;
+
+        Ok(Json(data))
+    }
+
+    /// Remove a student from a Class along with their sessions.
+    pub(super) async fn delete_data(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<(ClassId, StudentId)>,
+    ) -> Result<HttpResponse, error::Class> {
+        let (class_id, student_id) = path.into_inner();
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        if !db::class::delete_student_data(&db, class_id, student_id).await? {
+            return Err(error::Class::ResourceNotFound);
+        }
+
+        Ok(HttpResponse::NoContent().finish())
+    }
+}
+
+pub mod assignment {
+    use actix_web::{
+        web::{Data, Json, Path},
+        HttpResponse,
+    };
+    use shared::{
+        api::{endpoints::class, ApiEndpoint},
+        domain::{
+            class::{ClassAssignmentId, ClassAssignmentListResponse, ClassId},
+            CreateResponse,
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::{db, error, extractor::TokenUser};
+
+    /// List the assignments of a Class.
+    pub(super) async fn list(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<ClassId>,
+    ) -> Result<Json<<class::assignment::List as ApiEndpoint>::Res>, error::Class> {
+        let class_id = path.into_inner();
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        let assignments = db::class::list_assignments(&db, class_id).await?;
+
+        Ok(Json(ClassAssignmentListResponse { assignments }))
+    }
+
+    /// Assign a JIG or Playlist to a Class.
+    pub(super) async fn create(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<ClassId>,
+        req: Json<<class::assignment::Create as ApiEndpoint>::Req>,
+    ) -> Result<HttpResponse, error::Class> {
+        let class_id = path.into_inner();
+        let req = req.into_inner();
+
+        let user_id = claims.user_id();
+
+        db::class::authz(&db, user_id, class_id).await?;
+
+        let id =
+            db::class::create_assignment(&db, user_id, class_id, req.asset_id, req.due_at).await?;
+
+        Ok(HttpResponse::Created().json(CreateResponse { id }))
+    }
+
+    /// Update an assignment.
+    pub(super) async fn update(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<(ClassId, ClassAssignmentId)>,
+        req: Option<Json<<class::assignment::Update as ApiEndpoint>::Req>>,
+    ) -> Result<HttpResponse, error::Class> {
+        let (class_id, assignment_id) = path.into_inner();
+        let req = req.map_or_else(Default::default, Json::into_inner);
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        if !db::class::update_assignment(&db, class_id, assignment_id, req.due_at).await? {
+            return Err(error::Class::ResourceNotFound);
+        }
+
+        Ok(HttpResponse::NoContent().finish())
+    }
+
+    /// Remove an assignment from a Class.
+    pub(super) async fn delete(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        path: Path<(ClassId, ClassAssignmentId)>,
+    ) -> Result<HttpResponse, error::Class> {
+        let (class_id, assignment_id) = path.into_inner();
+
+        db::class::authz(&db, claims.user_id(), class_id).await?;
+
+        if !db::class::delete_assignment(&db, class_id, assignment_id).await? {
+            return Err(error::Class::ResourceNotFound);
+        }
+
+        Ok(HttpResponse::NoContent().finish())
+    }
+}
+
+pub mod login {
+    use actix_web::web::{Data, Json, Path};
+    use chrono::{Duration, Utc};
+    use ji_core::settings::RuntimeSettings;
+    use shared::{
+        api::{endpoints::class, ApiEndpoint},
+        domain::{
+            class::{
+                ClassAssignmentId, LoginCode, StudentAssignmentListResponse, StudentLoginRequest,
+                StudentLoginResponse,
+            },
+            jig::codes::instance::PlayerSessionInstanceResponse,
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::{
+        db, error,
+        extractor::IPAddress,
+        token::{
+            create_player_session_instance_token, create_student_token, validate_student_token,
+        },
+    };
+
+    /// Look up a Class by its login code.
+    ///
+    /// Unknown codes count as failed logins, so that codes can't be guessed by trying them all.
+    pub(super) async fn roster(
+        db: Data<PgPool>,
+        ip_address: IPAddress,
+        path: Path<LoginCode>,
+    ) -> Result<Json<<class::login::Roster as ApiEndpoint>::Res>, error::Class> {
+        let code = LoginCode::normalized(&path.into_inner().0);
+        let ip_address = ip_address.0.as_deref();
+
+        if db::class::login_throttled(&db, ip_address, None).await? {
+            return Err(error::Class::TooManyAttempts);
+        }
+
+        let roster = match db::class::roster_by_login_code(&db, &code).await? {
+            Some(roster) => roster,
+            None => {
+                db::class::record_login_failure(&db, ip_address, None).await?;
+                return Err(error::Class::ResourceNotFound);
+            }
+        };
+
+        Ok(Json(roster))
+    }
+
+    /// Log a student in.
+    ///
+    /// Logins are throttled by IP address, and a student is locked out of picture password logins
+    /// for a while after too many wrong guesses.
+    pub(super) async fn login(
+        settings: Data<RuntimeSettings>,
+        db: Data<PgPool>,
+        ip_address: IPAddress,
+        req: Json<<class::login::Login as ApiEndpoint>::Req>,
+    ) -> Result<Json<<class::login::Login as ApiEndpoint>::Res>, error::Class> {
+        let req = match req.into_inner() {
+            StudentLoginRequest::Code { code } => StudentLoginRequest::Code {
+                code: LoginCode::normalized(&code.0),
+            },
+            StudentLoginRequest::Picture {
+                class_code,
+                student_id,
+                picture_password,
+            } => {
+                if !picture_password.is_valid() {
+                    return Err(error::Class::InvalidLogin);
+                }
+
+                StudentLoginRequest::Picture {
+                    class_code: LoginCode::normalized(&class_code.0),
+                    student_id,
+                    picture_password,
+                }
+            }
+        };
+
+        let ip_address = ip_address.0.as_deref();
+        let student_id = match &req {
+            StudentLoginRequest::Code { .. } => None,
+            StudentLoginRequest::Picture { student_id, .. } => Some(*student_id),
+        };
+
+        if db::class::login_throttled(&db, ip_address, student_id).await? {
+            return Err(error::Class::TooManyAttempts);
+        }
+
+        let login = match db::class::login(&db, &req).await? {
+            Some(login) => login,
+            None => {
+                db::class::record_login_failure(&db, ip_address, student_id).await?;
+                return Err(error::Class::InvalidLogin);
+            }
+        };
+
+        db::class::clear_login_failures(&db, login.student.id).await?;
+
+        let token = create_student_token(
+            &settings.token_secret,
+            Duration::weeks(2),
+            login.student.id,
+            Utc::now(),
+        )?;
+
+        Ok(Json(StudentLoginResponse {
+            token,
+            student: login.student,
+            class_id: login.class_id,
+            class_name: login.class_name,
+        }))
+    }
+
+    /// List the assignments of a logged in student.
+    pub(super) async fn assignments(
+        settings: Data<RuntimeSettings>,
+        db: Data<PgPool>,
+        req: Json<<class::login::Assignments as ApiEndpoint>::Req>,
+    ) -> Result<Json<<class::login::Assignments as ApiEndpoint>::Res>, error::Class> {
+        let student_id = validate_student_token(&req.token, &settings.token_secret)
+            .map_err(|_| error::Class::InvalidLogin)?;
+
+        let assignments = db::class::student_assignments(&db, student_id).await?;
+
+        Ok(Json(StudentAssignmentListResponse { assignments }))
+    }
+
+    /// Start a session of an assignment's JIG for a logged in student.
+    pub(super) async fn play(
+        settings: Data<RuntimeSettings>,
+        db: Data<PgPool>,
+        ip_address: IPAddress,
+        path: Path<ClassAssignmentId>,
+        req: Json<<class::login::Play as ApiEndpoint>::Req>,
+    ) -> Result<
+        (
+            Json<<class::login::Play as ApiEndpoint>::Res>,
+            actix_web::http::StatusCode,
+        ),
+        error::Class,
+    > {
+        let assignment_id = path.into_inner();
+        let req = req.into_inner();
+
+        let student_id = validate_student_token(&req.token, &settings.token_secret)
+            .map_err(|_| error::Class::InvalidLogin)?;
+
+        let code = db::class::assignment_jig_code(&db, student_id, assignment_id, req.jig_id)
+            .await?
+            .ok_or(error::Class::ResourceNotFound)?;
+
+        let session = db::jig::codes::start_session(
+            &db,
+            code,
+            ip_address,
+            Some(student_id),
+            None,
+            settings.child_privacy_secret.as_deref().map(|key| &key[..]),
+        )
+        .await?;
+
+        let token = create_player_session_instance_token(
+            &settings.token_secret,
+            Duration::weeks(2),
+            &session.instance_id,
+            Utc::now(),
+        )?;
+
+        Ok((
+            Json(PlayerSessionInstanceResponse {
+                jig_id: session.jig_id,
+                settings: session.settings,
+                token,
+                player_id: session.player_id,
+                pseudonym: session.pseudonym,
+            }),
+            actix_web::http::StatusCode::CREATED,
+        ))
+    }
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    // the student routes are registered first, as `/v1/class/student/assignments` would otherwise
+    // be matched as the assignments of a class with the ID `student`
+    cfg.route(
+        <class::login::Roster as ApiEndpoint>::Path::PATH,
+        class::login::Roster::METHOD.route().to(login::roster),
+    )
+    .route(
+        <class::login::Login as ApiEndpoint>::Path::PATH,
+        class::login::Login::METHOD.route().to(login::login),
+    )
+    .route(
+        <class::login::Assignments as ApiEndpoint>::Path::PATH,
+        class::login::Assignments::METHOD
+            .route()
+            .to(login::assignments),
+    )
+    .route(
+        <class::login::Play as ApiEndpoint>::Path::PATH,
+        class::login::Play::METHOD.route().to(login::play),
+    )
+    .route(
+        <class::Create as ApiEndpoint>::Path::PATH,
+        class::Create::METHOD.route().to(create),
+    )
+    .route(
+        <class::List as ApiEndpoint>::Path::PATH,
+        class::List::METHOD.route().to(list),
+    )
+    .route(
+        <class::Get as ApiEndpoint>::Path::PATH,
+        class::Get::METHOD.route().to(get),
+    )
+    .route(
+        <class::Update as ApiEndpoint>::Path::PATH,
+        class::Update::METHOD.route().to(update),
+    )
+    .route(
+        <class::Delete as ApiEndpoint>::Path::PATH,
+        class::Delete::METHOD.route().to(delete),
+    )
+    .route(
+        <class::student::List as ApiEndpoint>::Path::PATH,
+        class::student::List::METHOD.route().to(student::list),
+    )
+    .route(
+        <class::student::Create as ApiEndpoint>::Path::PATH,
+        class::student::Create::METHOD.route().to(student::create),
+    )
+    .route(
+        <class::student::Import as ApiEndpoint>::Path::PATH,
+        class::student::Import::METHOD.route().to(student::import),
+    )
+    .route(
+        <class::student::Update as ApiEndpoint>::Path::PATH,
+        class::student::Update::METHOD.route().to(student::update),
+    )
+    .route(
+        <class::student::Delete as ApiEndpoint>::Path::PATH,
+        class::student::Delete::METHOD.route().to(student::delete),
+    )
+    .route(
+        <class::student::ExportData as ApiEndpoint>::Path::PATH,
+        class::student::ExportData::METHOD
+            .route()
+            .to(student::export_data),
+    )
+    .route(
+        <class::student::DeleteData as ApiEndpoint>::Path::PATH,
+        class::student::DeleteData::METHOD
+            .route()
+            .to(student::delete_data),
+    )
+    .route(
+        <class::assignment::List as ApiEndpoint>::Path::PATH,
+        class::assignment::List::METHOD.route().to(assignment::list),
+    )
+    .route(
+        <class::assignment::Create as ApiEndpoint>::Path::PATH,
+        class::assignment::Create::METHOD
+            .route()
+            .to(assignment::create),
+    )
+    .route(
+        <class::assignment::Update as ApiEndpoint>::Path::PATH,
+        class::assignment::Update::METHOD
+            .route()
+            .to(assignment::update),
+    )
+    .route(
+        <class::assignment::Delete as ApiEndpoint>::Path::PATH,
+        class::assignment::Delete::METHOD
+            .route()
+            .to(assignment::delete),
+    );
+}
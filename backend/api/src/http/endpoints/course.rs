@@ -25,8 +25,10 @@ use crate::{
     error::{self},
     extractor::TokenUser,
     service::ServiceData,
+    translate,
 };
 
+pub mod progress;
 pub mod unit;
 
 pub const DEFAULT_PAGE_LIMIT: u32 = 20;
@@ -105,7 +107,7 @@ async fn update_draft(
     path: web::Path<CourseId>,
 ) -> Result<HttpResponse, error::UpdateWithMetadata> {
     let id = path.into_inner();
-    let api_key = &settings.google_api_key;
+    let translator = translate::provider(&settings);
     let user_id = claims.user_id();
 
     db::course::authz(&*db, user_id, Some(id)).await?;
@@ -114,7 +116,7 @@ async fn update_draft(
 
     db::course::update_draft(
         &*db,
-        api_key,
+        translator.as_deref(),
         id,
         req.display_name.as_deref(),
         req.categories.as_deref(),
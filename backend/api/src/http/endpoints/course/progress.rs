@@ -0,0 +1,82 @@
+use actix_web::{
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::course, ApiEndpoint, PathParts},
+    domain::course::{progress::CourseListInProgressResponse, unit::CourseUnitId, CourseId},
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser};
+
+/// Get the current user's progress through a Course.
+async fn get(
+    db: Data<PgPool>,
+    auth: TokenUser,
+    path: Path<CourseId>,
+) -> Result<Json<<course::progress::Get as ApiEndpoint>::Res>, error::NotFound> {
+    let progress = db::course::progress::get(&db, auth.user_id(), path.into_inner())
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(progress))
+}
+
+/// Record the current user's progress on a Course Unit.
+async fn update_unit(
+    db: Data<PgPool>,
+    auth: TokenUser,
+    path: Path<(CourseId, CourseUnitId)>,
+    req: Json<<course::progress::UpdateUnit as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::NotFound> {
+    let (course_id, unit_id) = path.into_inner();
+    let req = req.into_inner();
+
+    if !db::course::progress::unit_in_course(&db, course_id, unit_id).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    db::course::progress::upsert_unit(&db, auth.user_id(), course_id, unit_id, req.status).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// List the Courses the current user has started.
+async fn list_in_progress(
+    db: Data<PgPool>,
+    auth: TokenUser,
+    query: Option<Query<<course::progress::ListInProgress as ApiEndpoint>::Req>>,
+) -> Result<Json<<course::progress::ListInProgress as ApiEndpoint>::Res>, error::Server> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let page_limit = super::page_limit(query.page_limit).await?;
+
+    let courses = db::course::progress::list_in_progress(
+        &db,
+        auth.user_id(),
+        query.include_completed,
+        query.page.unwrap_or(0),
+        page_limit,
+    )
+    .await?;
+
+    Ok(Json(CourseListInProgressResponse { courses }))
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <course::progress::ListInProgress as ApiEndpoint>::Path::PATH,
+        course::progress::ListInProgress::METHOD
+            .route()
+            .to(list_in_progress),
+    )
+    .route(
+        <course::progress::Get as ApiEndpoint>::Path::PATH,
+        course::progress::Get::METHOD.route().to(get),
+    )
+    .route(
+        <course::progress::UpdateUnit as ApiEndpoint>::Path::PATH,
+        course::progress::UpdateUnit::METHOD.route().to(update_unit),
+    );
+}
@@ -22,6 +22,7 @@ use crate::{
     service::{s3, upload as upload_service, ServiceData},
 };
 
+pub mod bulk;
 pub mod recent;
 pub mod tag;
 pub mod user;
@@ -265,11 +266,8 @@ async fn delete(
 
     // todo: 501 when algolia is disabled.
 
-    let delete = |kind| s3.delete_media(MediaLibrary::Global, FileKind::ImagePng(kind), image.0);
-    let ((), (), (), (), ()) = futures::future::join5(
-        delete(PngImageFile::Original),
-        delete(PngImageFile::Resized),
-        delete(PngImageFile::Thumbnail),
+    let ((), (), ()) = futures::future::join3(
+        s3.delete_images(MediaLibrary::Global, image.0),
         s3.delete_media(MediaLibrary::Global, FileKind::AnimationGif, image.0),
         algolia.delete_image(image),
     )
@@ -328,6 +326,18 @@ pub fn configure(cfg: &mut ServiceConfig) {
         <image::Browse as ApiEndpoint>::Path::PATH,
         image::Browse::METHOD.route().to(browse),
     )
+    .route(
+        <image::bulk::Update as ApiEndpoint>::Path::PATH,
+        image::bulk::Update::METHOD.route().to(self::bulk::update),
+    )
+    .route(
+        <image::bulk::List as ApiEndpoint>::Path::PATH,
+        image::bulk::List::METHOD.route().to(self::bulk::list),
+    )
+    .route(
+        <image::bulk::Undo as ApiEndpoint>::Path::PATH,
+        image::bulk::Undo::METHOD.route().to(self::bulk::undo),
+    )
     .route(
         <image::Get as ApiEndpoint>::Path::PATH,
         image::Get::METHOD.route().to(get_one),
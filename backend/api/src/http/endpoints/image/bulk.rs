@@ -0,0 +1,115 @@
+//! Edits many images in the global image library at once
+use crate::{
+    db::{self, meta::handle_metadata_err},
+    error,
+    extractor::{ScopeManageImage, TokenUserWithScope},
+};
+use actix_web::web::{Data, Json, Path, Query};
+use chrono::{DateTime, Utc};
+use futures::try_join;
+use shared::{
+    api::{endpoints, ApiEndpoint},
+    domain::image::bulk::{
+        ImageBulkChanges, ImageBulkListResponse, ImageBulkUndoResponse, ImageBulkUpdateId,
+        ImageBulkUpdateResponse,
+    },
+};
+use sqlx::PgPool;
+
+pub(super) async fn update(
+    db: Data<PgPool>,
+    auth: TokenUserWithScope<ScopeManageImage>,
+    req: Json<<endpoints::image::bulk::Update as ApiEndpoint>::Req>,
+) -> Result<Json<<endpoints::image::bulk::Update as ApiEndpoint>::Res>, error::ImageBulk> {
+    let req = req.into_inner();
+
+    if req.query.is_empty() {
+        return Err(error::ImageBulk::EmptyQuery);
+    }
+
+    if adds_and_removes(&req.changes) {
+        return Err(error::ImageBulk::AddedAndRemoved);
+    }
+
+    let mut txn = db.begin().await?;
+
+    let images = db::image::bulk::select(&mut txn, &req.query).await?;
+
+    let publish_at = req
+        .changes
+        .publish_at
+        .map(|it| it.map(DateTime::<Utc>::from));
+
+    let id = db::image::bulk::create(
+        &mut txn,
+        auth.claims.user_id,
+        &req.changes,
+        publish_at.flatten(),
+        &images,
+    )
+    .await?;
+
+    db::image::bulk::apply(&mut txn, &images, &req.changes, publish_at)
+        .await
+        .map_err(handle_metadata_err)?;
+
+    txn.commit().await?;
+
+    Ok(Json(ImageBulkUpdateResponse {
+        id,
+        image_count: images.len() as u64,
+    }))
+}
+
+pub(super) async fn undo(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeManageImage>,
+    id: Path<ImageBulkUpdateId>,
+) -> Result<Json<<endpoints::image::bulk::Undo as ApiEndpoint>::Res>, error::ImageBulk> {
+    let id = id.into_inner();
+
+    let mut txn = db.begin().await?;
+
+    let (changes, undone_at) = db::image::bulk::get_for_undo(&mut txn, id)
+        .await?
+        .ok_or(error::ImageBulk::NotFound)?;
+
+    if undone_at.is_some() {
+        return Err(error::ImageBulk::AlreadyUndone);
+    }
+
+    let image_count = db::image::bulk::undo(&mut txn, id, &changes).await?;
+
+    txn.commit().await?;
+
+    Ok(Json(ImageBulkUndoResponse { image_count }))
+}
+
+pub(super) async fn list(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeManageImage>,
+    Query(query): Query<<endpoints::image::bulk::List as ApiEndpoint>::Req>,
+) -> Result<Json<<endpoints::image::bulk::List as ApiEndpoint>::Res>, error::Server> {
+    let (updates, total_count) = try_join!(
+        db::image::bulk::list(db.as_ref(), &query),
+        db::image::bulk::count(db.as_ref()),
+    )?;
+
+    Ok(Json(ImageBulkListResponse {
+        updates,
+        pages: total_count.paged(query.page_limit),
+        total_count,
+    }))
+}
+
+/// Whether any item is both added and removed, which would leave what happens to it unclear.
+fn adds_and_removes(changes: &ImageBulkChanges) -> bool {
+    fn overlap<T: PartialEq>(add: &[T], remove: &[T]) -> bool {
+        add.iter().any(|it| remove.contains(it))
+    }
+
+    overlap(&changes.add_tags, &changes.remove_tags)
+        || overlap(&changes.add_styles, &changes.remove_styles)
+        || overlap(&changes.add_age_ranges, &changes.remove_age_ranges)
+        || overlap(&changes.add_affiliations, &changes.remove_affiliations)
+}
@@ -17,15 +17,17 @@ use shared::{
 use sqlx::PgPool;
 
 use crate::{
+    content_safety::ContentSafety,
     db, error,
     extractor::TokenUser,
-    service::{s3, upload as upload_service, ServiceData},
+    service::{s3, storage, upload as upload_service, ServiceData},
 };
 
 /// Create a image in the user's image library.
 pub(super) async fn create(
     db: Data<PgPool>,
     s3: ServiceData<s3::Client>,
+    safety: Data<ContentSafety>,
     claims: TokenUser,
     query: Query<<endpoints::image::user::Create as ApiEndpoint>::Req>,
     payload: Payload,
@@ -37,10 +39,13 @@ pub(super) async fn create(
 
     let user_id = claims.user_id();
 
+    let quota = storage::check_quota(db.as_ref(), user_id, None, file.len()).await?;
+
     let id = db::image::user::create(db.as_ref(), &user_id, size).await?;
 
     let mut txn = db.begin().await?;
-    upload_service::process_user_image_bytes(&mut txn, &s3, id.0, size, file).await?;
+    upload_service::process_user_image_bytes(&mut txn, &s3, &safety, &quota, id.0, size, file)
+        .await?;
     txn.commit().await?;
 
     Ok(HttpResponse::Created().json(CreateResponse { id }))
@@ -50,6 +55,7 @@ pub(super) async fn create(
 pub(super) async fn upload(
     db: Data<PgPool>,
     s3: ServiceData<s3::Client>,
+    safety: Data<ContentSafety>,
     claims: TokenUser,
     path: Path<ImageId>,
     payload: Payload,
@@ -72,7 +78,10 @@ pub(super) async fn upload(
         super::super::read_limited_payload(payload, FileKind::ImagePng(PngImageFile::Original))
             .await?;
 
-    upload_service::process_user_image_bytes(&mut txn, &s3, id.0, size, file).await?;
+    let quota = storage::check_quota(db.as_ref(), user_id, Some(id.0), file.len()).await?;
+
+    upload_service::process_user_image_bytes(&mut txn, &s3, &safety, &quota, id.0, size, file)
+        .await?;
 
     txn.commit().await?;
 
@@ -93,12 +102,10 @@ pub(super) async fn delete(
         .await
         .map_err(super::check_conflict_delete)?;
 
-    let delete = |kind| s3.delete_media(MediaLibrary::User, FileKind::ImagePng(kind), id.0);
-    let ((), (), (), ()) = futures::future::join4(
-        delete(PngImageFile::Original),
-        delete(PngImageFile::Resized),
-        delete(PngImageFile::Thumbnail),
+    let ((), (), ()) = futures::future::join3(
+        s3.delete_images(MediaLibrary::User, id.0),
         s3.delete_media(MediaLibrary::User, FileKind::AnimationGif, id.0),
+        s3.delete_quarantined(MediaLibrary::User, id.0),
     )
     .await;
 
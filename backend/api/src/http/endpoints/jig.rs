@@ -6,6 +6,7 @@ use futures::try_join;
 use ji_core::settings::RuntimeSettings;
 use shared::domain::{
     jig::{JigFeaturedResponse, JigTrendingResponse, ListLikedResponse, ListPlayedResponse},
+    moderation::FlaggedContentKind,
     user::UserScope,
 };
 use shared::{
@@ -14,7 +15,7 @@ use shared::{
         asset::{DraftOrLive, PrivacyLevel, UserOrMe},
         jig::{
             GetJigPlaylistsResponse, JigBrowseResponse, JigCountResponse, JigCreateRequest, JigId,
-            JigLikedResponse, JigSearchResponse,
+            JigLikedResponse, JigSearchResponse, JigTranslationId,
         },
         user::UserId,
         CreateResponse,
@@ -22,15 +23,17 @@ use shared::{
     error::{IntoAnyhow, ServiceError, ServiceKindError},
 };
 use sqlx::PgPool;
+use std::sync::Arc;
 use tracing::instrument;
 use uuid::Uuid;
 
 use crate::{
+    content_safety::ContentSafety,
     db::{self, jig::CreateJigError},
     error,
     extractor::{get_user_id, ScopeAdmin, TokenUser, TokenUserWithScope},
     service::ServiceData,
-    trending,
+    translate, trending,
 };
 
 mod codes;
@@ -126,7 +129,7 @@ async fn update_draft(
     path: web::Path<JigId>,
 ) -> Result<HttpResponse, error::UpdateWithMetadata> {
     let id = path.into_inner();
-    let api_key = &settings.google_api_key;
+    let translator = translate::provider(&settings);
     let user_id = claims.user_id();
 
     db::jig::authz(&*db, user_id, Some(id)).await?;
@@ -135,7 +138,7 @@ async fn update_draft(
 
     db::jig::update_draft(
         &*db,
-        api_key,
+        translator.as_deref(),
         id,
         req.display_name.as_deref(),
         req.categories.as_deref(),
@@ -255,8 +258,11 @@ fn filters_for_ids_or<T: Into<Uuid> + Copy>(ids: &[T]) -> Vec<Uuid> {
 }
 
 /// Copies the contents of the draft jig data to live
+///
+/// The jig is quarantined if its live title or description are flagged.
 pub(super) async fn publish_draft_to_live(
     db: Data<PgPool>,
+    safety: Data<ContentSafety>,
     claims: TokenUser,
     jig_id: Path<JigId>,
 ) -> Result<HttpResponse, error::CloneDraft> {
@@ -268,6 +274,17 @@ pub(super) async fn publish_draft_to_live(
 
     db::jig::publish_draft_to_live(&*db, jig_id).await?;
 
+    if let Some((display_name, description)) = db::jig::get_live_text(&*db, jig_id).await? {
+        let reasons = safety.check_text(&[
+            ("displayName", display_name.as_str()),
+            ("description", description.as_str()),
+        ]);
+
+        let mut txn = db.begin().await?;
+        db::moderation::flag(&mut txn, FlaggedContentKind::Jig, jig_id.0, &reasons).await?;
+        txn.commit().await?;
+    }
+
     Ok(HttpResponse::NoContent().finish())
 }
 
@@ -286,6 +303,55 @@ async fn clone(
     Ok(HttpResponse::Created().json(CreateResponse { id }))
 }
 
+/// Clone a jig, translated into another language in the background
+async fn clone_translated(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    translator: ServiceData<translate::Translator>,
+    parent: web::Path<JigId>,
+    req: Json<<jig::Translate as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::CloneDraft> {
+    let user_id = claims.user_id();
+    let parent = parent.into_inner();
+    let language = req.into_inner().language;
+
+    db::jig::authz(&*db, user_id, None).await?;
+
+    let target = translate::translation_language(&language)
+        .filter(|target| translator.provider.supports(target))
+        .ok_or(error::CloneDraft::UnsupportedLanguage)?;
+
+    let source = db::jig::language(&db, parent)
+        .await?
+        .ok_or(error::CloneDraft::ResourceNotFound)?;
+
+    // JIGs without a language are assumed to be in English
+    let source = translate::translation_language(&source).unwrap_or_else(|| "en".to_owned());
+    if source == target {
+        return Err(error::CloneDraft::UnsupportedLanguage);
+    }
+
+    let id = db::jig::translation::create(&db, parent, user_id, &language).await?;
+
+    let translator = Arc::clone(&translator);
+    tokio::spawn(async move { translator.translate_jig(id).await });
+
+    Ok(HttpResponse::Created().json(CreateResponse { id }))
+}
+
+/// Get a translated copy of a jig
+async fn get_translation(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigTranslationId>,
+) -> Result<Json<<jig::GetTranslation as ApiEndpoint>::Res>, error::NotFound> {
+    let translation = db::jig::translation::get(&db, path.into_inner(), claims.user_id())
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(translation))
+}
+
 /// Search for jigs.
 #[instrument(skip_all)]
 async fn search(
@@ -433,8 +499,11 @@ async fn like(
     path: web::Path<JigId>,
 ) -> Result<HttpResponse, error::Server> {
     let user_id = claims.user_id();
+    let jig_id = path.into_inner();
+
+    db::jig::jig_like(&*db, user_id, jig_id).await?;
 
-    db::jig::jig_like(&*db, user_id, path.into_inner()).await?;
+    db::notification::jig_liked(&db, jig_id, user_id).await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
@@ -512,7 +581,11 @@ async fn list_played(
 
 /// Add a play to a jig
 async fn play(db: Data<PgPool>, path: web::Path<JigId>) -> Result<HttpResponse, error::NotFound> {
-    db::jig::jig_play(&*db, path.into_inner()).await?;
+    let jig_id = path.into_inner();
+
+    db::jig::jig_play(&*db, jig_id).await?;
+
+    db::notification::jig_played(&db, jig_id).await?;
 
     Ok(HttpResponse::NoContent().finish())
 }
@@ -651,6 +724,14 @@ pub fn configure(cfg: &mut ServiceConfig) {
         <jig::Clone as ApiEndpoint>::Path::PATH,
         jig::Clone::METHOD.route().to(clone),
     )
+    .route(
+        <jig::Translate as ApiEndpoint>::Path::PATH,
+        jig::Translate::METHOD.route().to(clone_translated),
+    )
+    .route(
+        <jig::GetTranslation as ApiEndpoint>::Path::PATH,
+        jig::GetTranslation::METHOD.route().to(get_translation),
+    )
     .route(
         <jig::Browse as ApiEndpoint>::Path::PATH,
         jig::Browse::METHOD.route().to(browse),
@@ -729,6 +810,12 @@ pub fn configure(cfg: &mut ServiceConfig) {
             .route()
             .to(codes::list_code_sessions),
     )
+    .route(
+        <jig::codes::JigCodeReport as ApiEndpoint>::Path::PATH,
+        jig::codes::JigCodeReport::METHOD
+            .route()
+            .to(codes::code_report),
+    )
     .route(
         <jig::codes::instance::Create as ApiEndpoint>::Path::PATH,
         jig::codes::instance::Create::METHOD
@@ -101,6 +101,20 @@ pub async fn list_code_sessions(
     Ok(Json(JigCodeSessionsListResponse { sessions }))
 }
 
+/// Report who completed a code on time, late, or not at all.
+pub async fn code_report(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigCode>,
+) -> Result<Json<<codes::JigCodeReport as ApiEndpoint>::Res>, error::JigCode> {
+    let code = path.into_inner();
+    let user_id = claims.user_id();
+
+    let report = db::jig::codes::code_report(&*db, user_id, code).await?;
+
+    Ok(Json(report))
+}
+
 pub mod instance {
     use actix_web::{
         web::{Data, Json},
@@ -118,7 +132,7 @@ pub mod instance {
     use crate::{
         db, error,
         extractor::IPAddress,
-        token::{create_player_session_instance_token, validate_token},
+        token::{create_player_session_instance_token, validate_student_token, validate_token},
     };
     use uuid::Uuid;
 
@@ -137,20 +151,36 @@ pub mod instance {
     > {
         let req = req.into_inner();
 
-        let resp = db::jig::codes::start_session(&*db, req.code, ip_address).await?;
+        let student_id = req
+            .student_token
+            .map(|token| validate_student_token(&token, &settings.token_secret))
+            .transpose()
+            .map_err(|_| error::JigCode::Forbidden)?;
+
+        let resp = db::jig::codes::start_session(
+            &*db,
+            req.code,
+            ip_address,
+            student_id,
+            req.player_id,
+            settings.child_privacy_secret.as_deref().map(|key| &key[..]),
+        )
+        .await?;
 
         let token: String = create_player_session_instance_token(
             &settings.token_secret,
             Duration::weeks(2),
-            &resp.2,
+            &resp.instance_id,
             Utc::now(),
         )?;
 
         Ok((
             Json(PlayerSessionInstanceResponse {
-                jig_id: resp.0,
-                settings: resp.1,
+                jig_id: resp.jig_id,
+                settings: resp.settings,
                 token,
+                player_id: resp.player_id,
+                pseudonym: resp.pseudonym,
             }),
             actix_web::http::StatusCode::CREATED,
         ))
@@ -85,6 +85,8 @@ async fn create_comment(
     let id =
         db::jig::curation::create_comment(&*db, jig_id, req.value, auth.claims.user_id).await?;
 
+    db::notification::curation_comment(&db, jig_id, id, admin_id).await?;
+
     Ok((Json(CreateResponse { id }), http::StatusCode::CREATED))
 }
 
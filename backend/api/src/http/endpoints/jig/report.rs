@@ -3,18 +3,22 @@ use ji_core::settings::RuntimeSettings;
 use shared::{
     api::{endpoints::jig::report, ApiEndpoint, PathParts},
     domain::{
+        email::EmailOutboxId,
         jig::{report::JigReportEmail, report::ReportId, JigId},
         user::UserId,
         CreateResponse,
     },
 };
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 use crate::{
     db::{self},
     error,
     extractor::{ScopeAdmin, TokenUser, TokenUserWithScope},
-    service::{mail, ServiceData},
+    service::{
+        mail::{self, template, template::EmailTemplate, template::ReportEmail},
+        ServiceData,
+    },
 };
 
 /// Create a new jig report and send the report to info@jigzi.org
@@ -43,7 +47,8 @@ async fn create(
         .await?
         .ok_or(error::ReportError::ResourceNotFound)?;
 
-    send_report(
+    let email_id = send_report(
+        &mut txn,
         &mail,
         jig_id,
         report_info,
@@ -53,6 +58,8 @@ async fn create(
 
     txn.commit().await?;
 
+    mail.send_queued(&db, email_id).await;
+
     Ok((Json(CreateResponse { id }), http::StatusCode::CREATED))
 }
 
@@ -72,16 +79,33 @@ async fn get(
 }
 
 async fn send_report(
+    txn: &mut PgConnection,
     mail: &mail::Client,
     jig_id: JigId,
     report: JigReportEmail,
     pages_url: &str,
-) -> Result<(), error::ReportError> {
+) -> Result<EmailOutboxId, error::ReportError> {
     let email_link = format!("{}/jig/play/{}", pages_url, jig_id.0);
 
-    mail.send_jig_report_email(report, email_link).await?;
-
-    Ok(())
+    let email = EmailTemplate::JigReport(ReportEmail {
+        url: email_link,
+        display_name: report.display_name,
+        report_type: report.report_type.as_str().to_owned(),
+        reporter_name: report.reporter_name,
+        reporter_email: report.reporter_email,
+        creator_name: report.creator_name,
+    });
+
+    let email_id = mail
+        .queue(
+            txn,
+            mail.jigzi_info_email(),
+            template::DEFAULT_LOCALE,
+            email,
+        )
+        .await?;
+
+    Ok(email_id)
 }
 
 pub fn configure(cfg: &mut ServiceConfig) {
@@ -1,11 +1,23 @@
-use actix_web::web::{Data, Json, ServiceConfig};
+use actix_web::{
+    http::header::ContentType,
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
 use shared::{
     api::{endpoints::locale, ApiEndpoint, PathParts},
-    domain::locale::{Bundle, ItemKind, ListBundleResponse, ListItemKindResponse},
+    domain::locale::{
+        Bundle, CreateLanguageRequest, ExportBundleQuery, ItemKind, Language, ListBundleResponse,
+        ListItemKindResponse, ListLanguageResponse,
+    },
 };
-use sqlx::PgPool;
+use sqlx::{postgres::PgDatabaseError, PgPool};
+use uuid::Uuid;
 
-use crate::error;
+use crate::{
+    db, error,
+    extractor::{ScopeManageManageEntry, TokenUserWithScope},
+    fluent,
+};
 
 async fn list_bundles(db: Data<PgPool>) -> Result<Json<ListBundleResponse>, error::Server> {
     let bundles = sqlx::query_as!(
@@ -29,6 +41,85 @@ async fn list_item_kinds(db: Data<PgPool>) -> Result<Json<ListItemKindResponse>,
     Ok(Json(ListItemKindResponse { item_kinds }))
 }
 
+async fn export_bundle(
+    db: Data<PgPool>,
+    path: Path<Uuid>,
+    query: Query<ExportBundleQuery>,
+) -> Result<HttpResponse, error::NotFound> {
+    let bundle_id = path.into_inner();
+    let language = query.into_inner().language;
+
+    let bundle_exists = sqlx::query!(
+        r#"select exists(select 1 from locale_bundle where id = $1) as "exists!""#,
+        bundle_id
+    )
+    .fetch_one(db.as_ref())
+    .await?
+    .exists;
+
+    if !bundle_exists || !db::locale::languages_exist(db.as_ref(), &[language.clone()]).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    let entries = db::locale::export_entries(db.as_ref(), bundle_id, &language).await?;
+
+    let messages: Vec<_> = entries
+        .iter()
+        .map(|entry| fluent::Message {
+            key: &entry.key,
+            section: entry.section.as_deref(),
+            comments: entry.comments.as_deref(),
+            text: &entry.text,
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok()
+        .content_type(ContentType::plaintext())
+        .body(fluent::write(&messages)))
+}
+
+async fn list_languages(db: Data<PgPool>) -> Result<Json<ListLanguageResponse>, error::Server> {
+    let languages = sqlx::query_as!(
+        Language,
+        "select code, display_name from locale_language order by created_at"
+    )
+    .fetch_all(db.as_ref())
+    .await?;
+
+    Ok(Json(ListLanguageResponse { languages }))
+}
+
+async fn create_language(
+    _user: TokenUserWithScope<ScopeManageManageEntry>,
+    db: Data<PgPool>,
+    req: Json<CreateLanguageRequest>,
+) -> Result<HttpResponse, error::NotFound> {
+    let req = req.into_inner();
+
+    if req.code.trim().is_empty() || req.display_name.trim().is_empty() {
+        return Err(error::NotFound::BadRequest);
+    }
+
+    sqlx::query!(
+        "insert into locale_language (code, display_name) values ($1, $2)",
+        req.code.trim(),
+        req.display_name.trim(),
+    )
+    .execute(db.as_ref())
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(err)
+            if err.downcast_ref::<PgDatabaseError>().constraint()
+                == Some("locale_language_pkey") =>
+        {
+            error::NotFound::BadRequest
+        }
+        err => err.into(),
+    })?;
+
+    Ok(HttpResponse::Created().finish())
+}
+
 mod entry {
     use std::collections::BTreeMap;
 
@@ -37,10 +128,10 @@ mod entry {
         HttpResponse,
     };
     use shared::domain::locale::{
-        CreateEntryRequest, CreateEntryResponse, Entry, EntryStatus, GetEntryResponse,
-        ListEntryGroupBy, ListEntryQuery, ListEntryResponse, UpdateEntryRequest,
+        is_valid_key, CreateEntryRequest, CreateEntryResponse, Entry, EntryStatus,
+        GetEntryResponse, ListEntryGroupBy, ListEntryQuery, ListEntryResponse, UpdateEntryRequest,
     };
-    use sqlx::PgPool;
+    use sqlx::{types::Json as SqlJson, PgPool};
 
     use crate::{
         db, error,
@@ -53,15 +144,26 @@ mod entry {
         req: Json<CreateEntryRequest>,
     ) -> Result<HttpResponse, error::NotFound> {
         let req = req.into_inner();
+
+        if !req.key.as_deref().is_none_or(is_valid_key) {
+            return Err(error::NotFound::BadRequest);
+        }
+
+        let languages: Vec<_> = req.translations.keys().cloned().collect();
+        if !db::locale::languages_exist(db.as_ref(), &languages).await? {
+            return Err(error::NotFound::BadRequest);
+        }
+
         let entry = sqlx::query!(
             r#"
-insert into locale_entry (bundle_id, section, item_kind_id, english, hebrew, status, zeplin_reference, comments, in_app, in_element, in_mock)
+insert into locale_entry (bundle_id, section, item_kind_id, key, translations, status, zeplin_reference, comments, in_app, in_element, in_mock)
 values ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
 returning id
-"#, req.bundle_id, req.section, req.item_kind_id, req.english, req.hebrew, req.status as i16, req.zeplin_reference, req.comments, req.in_app, req.in_element, req.in_mock
+"#, req.bundle_id, req.section, req.item_kind_id, req.key, serde_json::to_value(&req.translations)?, req.status as i16, req.zeplin_reference, req.comments, req.in_app, req.in_element, req.in_mock
         )
         .fetch_one(db.as_ref())
-        .await?;
+        .await
+        .map_err(db::locale::handle_entry_err)?;
 
         Ok(HttpResponse::Created().json(CreateEntryResponse {
             id: entry.id as u32,
@@ -81,8 +183,8 @@ select
     bundle_id,
     section,
     item_kind_id,
-    english,
-    hebrew,
+    key,
+    translations as "translations: SqlJson<BTreeMap<String, String>>",
     status as "status: EntryStatus",
     zeplin_reference,
     comments,
@@ -104,8 +206,8 @@ order by id
             bundle_id: row.bundle_id,
             section: row.section,
             item_kind_id: row.item_kind_id,
-            english: row.english,
-            hebrew: row.hebrew,
+            key: row.key,
+            translations: row.translations.0,
             status: row.status,
             zeplin_reference: row.zeplin_reference,
             comments: row.comments,
@@ -143,8 +245,8 @@ select
     bundle_id,
     section,
     item_kind_id,
-    english,
-    hebrew,
+    key,
+    translations as "translations: SqlJson<BTreeMap<String, String>>",
     status as "status: EntryStatus",
     zeplin_reference,
     comments,
@@ -163,8 +265,8 @@ where id = $1
             bundle_id: row.bundle_id,
             section: row.section,
             item_kind_id: row.item_kind_id,
-            english: row.english,
-            hebrew: row.hebrew,
+            key: row.key,
+            translations: row.translations.0,
             status: row.status,
             zeplin_reference: row.zeplin_reference,
             comments: row.comments,
@@ -215,6 +317,18 @@ pub fn configure(cfg: &mut ServiceConfig) {
         <locale::bundle::List as ApiEndpoint>::Path::PATH,
         locale::bundle::List::METHOD.route().to(list_bundles),
     )
+    .route(
+        <locale::bundle::Export as ApiEndpoint>::Path::PATH,
+        locale::bundle::Export::METHOD.route().to(export_bundle),
+    )
+    .route(
+        <locale::language::List as ApiEndpoint>::Path::PATH,
+        locale::language::List::METHOD.route().to(list_languages),
+    )
+    .route(
+        <locale::language::Create as ApiEndpoint>::Path::PATH,
+        locale::language::Create::METHOD.route().to(create_language),
+    )
     .route(
         <locale::item_kind::List as ApiEndpoint>::Path::PATH,
         locale::item_kind::List::METHOD.route().to(list_item_kinds),
@@ -1,14 +1,18 @@
 use actix_web::{
-    web::{Data, Json, Path, ServiceConfig},
+    web::{Data, Json, Path, Query, ServiceConfig},
     HttpResponse,
 };
+use futures::try_join;
 use shared::{
     api::{endpoints, ApiEndpoint, PathParts},
     domain::{
-        media::{UrlCreatedResponse, WebMediaMetadataResponse, WebMediaUrlCreateRequest},
+        media::{
+            UrlCreatedResponse, WebMediaListResponse, WebMediaMetadataResponse,
+            WebMediaUrlCreateRequest,
+        },
+        user::UserId,
         Base64,
     },
-    media::{FileKind, MediaLibrary, PngImageFile},
 };
 use sqlx::PgPool;
 use url::Url;
@@ -18,12 +22,12 @@ use crate::{
     db, error,
     extractor::{ScopeAdmin, TokenUser, TokenUserWithScope},
     image_ops::MediaKind,
-    service::{s3, ServiceData},
+    service::{s3, upload, ServiceData},
 };
 
 pub async fn create(
     pool: Data<PgPool>,
-    _claims: TokenUser,
+    claims: TokenUser,
     s3: ServiceData<s3::Client>,
     request: Json<WebMediaUrlCreateRequest>,
 ) -> Result<HttpResponse, error::Server> {
@@ -31,7 +35,8 @@ pub async fn create(
 
     let url_string = request.url.to_string();
 
-    let (id, kind, status_code) = db::media::create(&pool, &s3, &url_string).await?;
+    let (id, kind, status_code) =
+        db::media::create(&pool, &s3, &url_string, claims.user_id()).await?;
 
     Ok(HttpResponse::build(status_code).json(UrlCreatedResponse {
         id,
@@ -39,9 +44,22 @@ pub async fn create(
     }))
 }
 
-// filter by media type, etc
-// list route (with filter & pagation)
-// async fn list() {}
+async fn list(
+    pool: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    Query(query): Query<<endpoints::media::List as ApiEndpoint>::Req>,
+) -> Result<Json<<endpoints::media::List as ApiEndpoint>::Res>, error::Server> {
+    let (media, total_count) = try_join!(
+        db::media::list(pool.as_ref(), &query),
+        db::media::list_count(pool.as_ref(), &query),
+    )?;
+
+    Ok(Json(WebMediaListResponse {
+        media,
+        pages: total_count.paged(query.page_limit),
+        total_count,
+    }))
+}
 
 async fn delete_media(
     pool: Data<PgPool>,
@@ -51,34 +69,12 @@ async fn delete_media(
 ) -> Result<HttpResponse, error::Server> {
     let id = path.into_inner();
 
-    let record = sqlx::query!(
-        r#"delete from web_media_library where id = $1 returning kind as "kind: MediaKind""#,
-        id
-    )
-    .fetch_optional(pool.as_ref())
-    .await?;
+    let mut txn = pool.begin().await?;
+    let deleted = db::media::delete(&mut txn, &[id]).await?;
+    txn.commit().await?;
 
-    let kind = match record {
-        Some(record) => record.kind,
-        None => return Ok(HttpResponse::NoContent().finish()),
-    };
-
-    let delete = |file_kind| s3.delete_media(MediaLibrary::Web, file_kind, id);
-    match kind {
-        MediaKind::PngStickerImage => {
-            futures::future::join3(
-                delete(FileKind::ImagePng(PngImageFile::Original)),
-                delete(FileKind::ImagePng(PngImageFile::Resized)),
-                delete(FileKind::ImagePng(PngImageFile::Thumbnail)),
-            )
-            .await;
-        }
-
-        MediaKind::GifAnimation => {
-            delete(FileKind::AnimationGif).await;
-        }
-
-        kind => return Err(anyhow::anyhow!("unsupported media kind {:?}", kind).into()),
+    for (id, kind) in deleted {
+        upload::delete_web_media_files(&s3, id, kind).await;
     }
 
     Ok(HttpResponse::NoContent().finish())
@@ -113,6 +109,7 @@ select id,
        kind as "kind: MediaKind",
        created_at,
        updated_at,
+       created_by as "created_by?: UserId",
        array(select media_url from web_media_library_url where media_id = $1) as "urls!"
 from web_media_library
 where id = $1"#,
@@ -132,6 +129,7 @@ where id = $1"#,
             .collect::<Result<Vec<_>, _>>()?,
         created_at: media.created_at,
         updated_at: media.updated_at,
+        created_by: media.created_by,
     }))
 }
 
@@ -151,6 +149,7 @@ select id,
        kind as "kind: MediaKind",
        created_at,
        updated_at,
+       created_by as "created_by?: UserId",
        array(select media_url from web_media_library_url where media_id = id) as "urls!"
 from web_media_library
 where id = (select media_id from web_media_library_url where media_url = $1)
@@ -171,6 +170,7 @@ where id = (select media_id from web_media_library_url where media_url = $1)
             .collect::<Result<Vec<_>, _>>()?,
         created_at: media.created_at,
         updated_at: media.updated_at,
+        created_by: media.created_by,
     }))
 }
 
@@ -179,6 +179,10 @@ pub fn configure(cfg: &mut ServiceConfig) {
         <endpoints::media::Create as ApiEndpoint>::Path::PATH,
         endpoints::media::Create::METHOD.route().to(create),
     )
+    .route(
+        <endpoints::media::List as ApiEndpoint>::Path::PATH,
+        endpoints::media::List::METHOD.route().to(list),
+    )
     .route(
         <endpoints::media::GetUrl as ApiEndpoint>::Path::PATH,
         endpoints::media::GetUrl::METHOD.route().to(get_by_url),
@@ -189,10 +193,10 @@ pub fn configure(cfg: &mut ServiceConfig) {
     )
     .route(
         <endpoints::media::DeleteId as ApiEndpoint>::Path::PATH,
-        endpoints::media::DeleteId::METHOD.route().to(delete_url),
+        endpoints::media::DeleteId::METHOD.route().to(delete_media),
     )
     .route(
         <endpoints::media::DeleteUrl as ApiEndpoint>::Path::PATH,
-        endpoints::media::DeleteUrl::METHOD.route().to(delete_media),
+        endpoints::media::DeleteUrl::METHOD.route().to(delete_url),
     );
 }
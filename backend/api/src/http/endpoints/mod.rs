@@ -6,13 +6,16 @@ pub mod audio;
 pub mod billing;
 pub mod category;
 pub mod circle;
+pub mod class;
 pub mod course;
 pub mod image;
 pub mod jig;
 pub mod locale;
 pub mod media;
 pub mod meta;
+pub mod moderation;
 pub mod module;
+pub mod notification;
 pub mod pdf;
 pub mod playlist;
 pub mod resource;
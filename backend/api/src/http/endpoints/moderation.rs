@@ -0,0 +1,97 @@
+//! Review queue for items flagged by the content safety checks
+use actix_web::{
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use futures::try_join;
+use shared::{
+    api::{endpoints::moderation, ApiEndpoint, PathParts},
+    domain::moderation::{
+        ContentFlagDecision, ContentFlagId, ContentFlagListResponse, ContentFlagStatus,
+        FlaggedContentKind,
+    },
+    media::MediaLibrary,
+};
+use sqlx::PgPool;
+
+use crate::{
+    db, error,
+    extractor::{ScopeAdmin, TokenUserWithScope},
+    service::{s3, ServiceData},
+};
+
+/// List flagged items, most recent first.
+async fn list(
+    db: Data<PgPool>,
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    Query(query): Query<<moderation::List as ApiEndpoint>::Req>,
+) -> Result<Json<<moderation::List as ApiEndpoint>::Res>, error::Server> {
+    let (flags, total_count) = try_join!(
+        db::moderation::list(db.as_ref(), &query),
+        db::moderation::count(db.as_ref(), &query),
+    )?;
+
+    Ok(Json(ContentFlagListResponse {
+        flags,
+        pages: total_count.paged(query.page_limit),
+        total_count,
+    }))
+}
+
+/// Approve or reject a flagged item. Approved items are released from quarantine, rejected ones
+/// stay quarantined.
+async fn review(
+    db: Data<PgPool>,
+    s3: Option<ServiceData<s3::Client>>,
+    auth: TokenUserWithScope<ScopeAdmin>,
+    id: Path<ContentFlagId>,
+    req: Json<<moderation::Review as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::ContentFlag> {
+    let id = id.into_inner();
+
+    let mut txn = db.begin().await?;
+
+    let (kind, item_id, status) = db::moderation::get_for_review(&mut txn, id)
+        .await?
+        .ok_or(error::ContentFlag::NotFound)?;
+
+    if status != ContentFlagStatus::Pending {
+        return Err(error::ContentFlag::AlreadyReviewed);
+    }
+
+    let status = match req.decision {
+        ContentFlagDecision::Approve => {
+            db::moderation::release(&mut txn, kind, item_id).await?;
+
+            // the files of quarantined uploads aren't served until they're released
+            if matches!(
+                kind,
+                FlaggedContentKind::UserImage | FlaggedContentKind::UserPdf
+            ) {
+                if let Some(s3) = &s3 {
+                    s3.release(MediaLibrary::User, item_id).await?;
+                }
+            }
+
+            ContentFlagStatus::Approved
+        }
+        ContentFlagDecision::Reject => ContentFlagStatus::Rejected,
+    };
+
+    db::moderation::review(&mut txn, id, auth.claims.user_id, status).await?;
+
+    txn.commit().await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <moderation::List as ApiEndpoint>::Path::PATH,
+        moderation::List::METHOD.route().to(list),
+    )
+    .route(
+        <moderation::Review as ApiEndpoint>::Path::PATH,
+        moderation::Review::METHOD.route().to(review),
+    );
+}
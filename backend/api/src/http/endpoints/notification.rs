@@ -0,0 +1,121 @@
+use actix_web::{
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::notification, ApiEndpoint, PathParts},
+    domain::notification::{
+        ListNotificationsResponse, NotificationId, NotificationPreferences,
+        UnreadNotificationsCountResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser};
+
+/// List the user's notifications, latest first.
+async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    query: Option<Query<<notification::List as ApiEndpoint>::Req>>,
+) -> Result<Json<<notification::List as ApiEndpoint>::Res>, error::Server> {
+    let user_id = claims.user_id();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let (notifications, total_count, unread_count) = futures::try_join!(
+        db::notification::list(&db, user_id, &query),
+        db::notification::count(&db, user_id, query.unread_only),
+        db::notification::count(&db, user_id, true),
+    )?;
+
+    Ok(Json(ListNotificationsResponse {
+        notifications,
+        pages: total_count.paged(query.page_limit),
+        total_count,
+        unread_count,
+    }))
+}
+
+/// Count the user's unread notifications.
+async fn unread_count(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<notification::UnreadCount as ApiEndpoint>::Res>, error::Server> {
+    let unread_count = db::notification::count(&db, claims.user_id(), true).await?;
+
+    Ok(Json(UnreadNotificationsCountResponse { unread_count }))
+}
+
+/// Mark one of the user's notifications as read.
+async fn mark_read(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<NotificationId>,
+) -> Result<HttpResponse, error::NotFound> {
+    let found = db::notification::mark_read(&db, claims.user_id(), path.into_inner()).await?;
+
+    if !found {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Mark all of the user's notifications as read.
+async fn mark_all_read(db: Data<PgPool>, claims: TokenUser) -> Result<HttpResponse, error::Server> {
+    db::notification::mark_all_read(&db, claims.user_id()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Get the user's notification preferences.
+async fn get_preferences(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<notification::GetPreferences as ApiEndpoint>::Res>, error::Server> {
+    let preferences = db::notification::get_preferences(&db, claims.user_id()).await?;
+
+    Ok(Json(NotificationPreferences { preferences }))
+}
+
+/// Update the user's notification preferences.
+async fn update_preferences(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<<notification::UpdatePreferences as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::Server> {
+    db::notification::update_preferences(&db, claims.user_id(), &req.preferences).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <notification::List as ApiEndpoint>::Path::PATH,
+        notification::List::METHOD.route().to(list),
+    )
+    .route(
+        <notification::UnreadCount as ApiEndpoint>::Path::PATH,
+        notification::UnreadCount::METHOD.route().to(unread_count),
+    )
+    .route(
+        <notification::MarkAllRead as ApiEndpoint>::Path::PATH,
+        notification::MarkAllRead::METHOD.route().to(mark_all_read),
+    )
+    .route(
+        <notification::GetPreferences as ApiEndpoint>::Path::PATH,
+        notification::GetPreferences::METHOD
+            .route()
+            .to(get_preferences),
+    )
+    .route(
+        <notification::UpdatePreferences as ApiEndpoint>::Path::PATH,
+        notification::UpdatePreferences::METHOD
+            .route()
+            .to(update_preferences),
+    )
+    .route(
+        <notification::MarkRead as ApiEndpoint>::Path::PATH,
+        notification::MarkRead::METHOD.route().to(mark_read),
+    );
+}
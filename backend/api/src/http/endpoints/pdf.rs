@@ -33,23 +33,28 @@ pub mod user {
     use sqlx::PgPool;
 
     use crate::{
+        content_safety::ContentSafety,
         db, error,
         extractor::TokenUser,
-        service::{s3, upload as upload_service, ServiceData},
+        service::{s3, storage, upload as upload_service, ServiceData},
     };
 
     /// Create a pdf file in the user's pdf library.
     pub(super) async fn create(
         db: Data<PgPool>,
         s3: ServiceData<s3::Client>,
+        safety: Data<ContentSafety>,
         claims: TokenUser,
         payload: Payload,
     ) -> Result<HttpResponse, error::Upload> {
         let file = super::super::read_limited_payload(payload, FileKind::DocumentPdf).await?;
+
+        let quota = storage::check_quota(db.as_ref(), claims.0.user_id, None, file.len()).await?;
+
         let id = db::pdf::user::create(db.as_ref(), claims.0.user_id).await?;
 
         let mut txn = db.begin().await?;
-        upload_service::process_user_pdf_bytes(&mut txn, &s3, id.0, file).await?;
+        upload_service::process_user_pdf_bytes(&mut txn, &s3, &safety, &quota, id.0, file).await?;
         txn.commit().await?;
 
         Ok(HttpResponse::Created().json(CreateResponse { id }))
@@ -59,7 +64,8 @@ pub mod user {
     pub(super) async fn upload(
         db: Data<PgPool>,
         s3: ServiceData<s3::Client>,
-        _claims: TokenUser,
+        safety: Data<ContentSafety>,
+        claims: TokenUser,
         id: Path<PdfId>,
         payload: Payload,
     ) -> Result<HttpResponse, error::Upload> {
@@ -80,7 +86,10 @@ pub mod user {
 
         let file = super::super::read_limited_payload(payload, FileKind::DocumentPdf).await?;
 
-        upload_service::process_user_pdf_bytes(&mut txn, &s3, id.0, file).await?;
+        let quota =
+            storage::check_quota(db.as_ref(), claims.0.user_id, Some(id.0), file.len()).await?;
+
+        upload_service::process_user_pdf_bytes(&mut txn, &s3, &safety, &quota, id.0, file).await?;
 
         txn.commit().await?;
 
@@ -95,12 +104,19 @@ pub mod user {
         s3: ServiceData<s3::Client>,
     ) -> Result<HttpResponse, error::Delete> {
         let pdf = req.into_inner();
-        db::pdf::user::delete(&db, pdf)
+        let preview_page_count = db::pdf::user::delete(&db, pdf)
             .await
-            .map_err(super::check_conflict_delete)?;
+            .map_err(super::check_conflict_delete)?
+            .unwrap_or_default();
+
+        futures::future::join_all(
+            FileKind::pdf_files(preview_page_count)
+                .into_iter()
+                .map(|file_kind| s3.delete_media(MediaLibrary::User, file_kind, pdf.0)),
+        )
+        .await;
 
-        s3.delete_media(MediaLibrary::User, FileKind::DocumentPdf, pdf.0)
-            .await;
+        s3.delete_quarantined(MediaLibrary::User, pdf.0).await;
 
         Ok(HttpResponse::NoContent().finish())
     }
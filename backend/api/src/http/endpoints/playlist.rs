@@ -28,8 +28,11 @@ use crate::{
     error::{self},
     extractor::{get_user_id, TokenUser},
     service::ServiceData,
+    translate,
 };
 
+pub mod progress;
+
 pub const DEFAULT_PAGE_LIMIT: u32 = 20;
 pub const MAX_PAGE_LIMIT: u32 = 100;
 
@@ -116,7 +119,7 @@ async fn update_draft(
     path: web::Path<PlaylistId>,
 ) -> Result<HttpResponse, error::UpdateWithMetadata> {
     let id = path.into_inner();
-    let api_key = &settings.google_api_key;
+    let translator = translate::provider(&settings);
     let user_id = claims.user_id();
 
     db::playlist::authz(&*db, user_id, Some(id)).await?;
@@ -125,7 +128,7 @@ async fn update_draft(
 
     db::playlist::update_draft(
         &*db,
-        api_key,
+        translator.as_deref(),
         id,
         req.display_name.as_deref(),
         req.categories.as_deref(),
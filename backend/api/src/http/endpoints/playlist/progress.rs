@@ -0,0 +1,95 @@
+use actix_web::{
+    web::{Data, Json, Path, Query, ServiceConfig},
+    HttpResponse,
+};
+use shared::{
+    api::{endpoints::playlist, ApiEndpoint, PathParts},
+    domain::{
+        jig::JigId,
+        playlist::{progress::PlaylistListInProgressResponse, PlaylistId},
+    },
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser};
+
+/// Get the current user's progress through a Playlist.
+async fn get(
+    db: Data<PgPool>,
+    auth: TokenUser,
+    path: Path<PlaylistId>,
+) -> Result<Json<<playlist::progress::Get as ApiEndpoint>::Res>, error::NotFound> {
+    let progress = db::playlist::progress::get(&db, auth.user_id(), path.into_inner())
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(progress))
+}
+
+/// Record the current user's progress on a JIG within a Playlist.
+async fn update_item(
+    db: Data<PgPool>,
+    auth: TokenUser,
+    path: Path<(PlaylistId, JigId)>,
+    req: Json<<playlist::progress::UpdateItem as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::NotFound> {
+    let (playlist_id, jig_id) = path.into_inner();
+    let req = req.into_inner();
+
+    if !db::playlist::progress::jig_in_playlist(&db, playlist_id, jig_id).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    db::playlist::progress::upsert_item(
+        &db,
+        auth.user_id(),
+        playlist_id,
+        jig_id,
+        req.status,
+        req.points_earned,
+    )
+    .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// List the Playlists the current user has started.
+async fn list_in_progress(
+    db: Data<PgPool>,
+    auth: TokenUser,
+    query: Option<Query<<playlist::progress::ListInProgress as ApiEndpoint>::Req>>,
+) -> Result<Json<<playlist::progress::ListInProgress as ApiEndpoint>::Res>, error::Server> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let page_limit = super::page_limit(query.page_limit).await?;
+
+    let playlists = db::playlist::progress::list_in_progress(
+        &db,
+        auth.user_id(),
+        query.include_completed,
+        query.page.unwrap_or(0),
+        page_limit,
+    )
+    .await?;
+
+    Ok(Json(PlaylistListInProgressResponse { playlists }))
+}
+
+pub fn configure(cfg: &mut ServiceConfig) {
+    cfg.route(
+        <playlist::progress::ListInProgress as ApiEndpoint>::Path::PATH,
+        playlist::progress::ListInProgress::METHOD
+            .route()
+            .to(list_in_progress),
+    )
+    .route(
+        <playlist::progress::Get as ApiEndpoint>::Path::PATH,
+        playlist::progress::Get::METHOD.route().to(get),
+    )
+    .route(
+        <playlist::progress::UpdateItem as ApiEndpoint>::Path::PATH,
+        playlist::progress::UpdateItem::METHOD
+            .route()
+            .to(update_item),
+    );
+}
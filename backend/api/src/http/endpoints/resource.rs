@@ -28,6 +28,7 @@ use crate::{
     error::{self},
     extractor::{get_user_id, ScopeAdmin, TokenUser, TokenUserWithScope},
     service::ServiceData,
+    translate,
 };
 
 pub mod curation;
@@ -123,7 +124,7 @@ async fn update_draft(
     path: web::Path<ResourceId>,
 ) -> Result<HttpResponse, error::UpdateWithMetadata> {
     let id = path.into_inner();
-    let api_key = &settings.google_api_key;
+    let translator = translate::provider(&settings);
     let user_id = claims.user_id();
 
     db::resource::authz(&*db, user_id, Some(id)).await?;
@@ -132,7 +133,7 @@ async fn update_draft(
 
     db::resource::update_draft(
         &*db,
-        api_key,
+        translator.as_deref(),
         id,
         req.display_name.as_deref(),
         req.categories.as_deref(),
@@ -1,21 +1,24 @@
 use actix_web::web::{Data, Json, Path, ServiceConfig};
 use ji_core::settings::RuntimeSettings;
-use sendgrid::v3::Email;
 use shared::{
     api::{endpoints::resource::report, ApiEndpoint, PathParts},
     domain::{
+        email::EmailOutboxId,
         resource::{report::ReportId, report::ResourceReportEmail, ResourceId},
         user::UserId,
         CreateResponse,
     },
 };
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 
 use crate::{
     db::{self},
     error,
     extractor::{ScopeAdmin, TokenUser, TokenUserWithScope},
-    service::{mail, ServiceData},
+    service::{
+        mail::{self, template, template::EmailTemplate, template::ReportEmail},
+        ServiceData,
+    },
 };
 
 /// Create a new resource report and send the report email to admin
@@ -45,7 +48,8 @@ async fn create(
         .await?
         .ok_or(error::ReportError::ResourceNotFound)?;
 
-    send_report(
+    let email_id = send_report(
+        &mut txn,
         &mail,
         resource_id,
         report_info,
@@ -56,6 +60,8 @@ async fn create(
 
     txn.commit().await?;
 
+    mail.send_queued(&db, email_id).await;
+
     Ok((Json(CreateResponse { id }), http::StatusCode::CREATED))
 }
 
@@ -75,18 +81,29 @@ async fn get(
 }
 
 async fn send_report(
+    txn: &mut PgConnection,
     mail: &mail::Client,
     resource_id: ResourceId,
     report: ResourceReportEmail,
     email_address: &str,
     pages_url: &str,
-) -> Result<(), error::ReportError> {
+) -> Result<EmailOutboxId, error::ReportError> {
     let email_link = format!("{}/resource/play/{}", pages_url, resource_id.0);
 
-    mail.send_resource_report_email(Email::new(email_address), report, email_link)
+    let email = EmailTemplate::ResourceReport(ReportEmail {
+        url: email_link,
+        display_name: report.display_name,
+        report_type: report.report_type.as_str().to_owned(),
+        reporter_name: report.reporter_name,
+        reporter_email: report.reporter_email,
+        creator_name: report.creator_name,
+    });
+
+    let email_id = mail
+        .queue(txn, email_address, template::DEFAULT_LOCALE, email)
         .await?;
 
-    Ok(())
+    Ok(email_id)
 }
 
 pub fn configure(cfg: &mut ServiceConfig) {
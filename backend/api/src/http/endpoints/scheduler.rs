@@ -1,21 +1,29 @@
 use actix_web::{
-    web::{method, Data, ServiceConfig},
+    web::{method, Data, Json, Query, ServiceConfig},
     HttpResponse,
 };
 use actix_web_httpauth::extractors::bearer::BearerAuth;
+use ji_core::settings::RuntimeSettings;
+use serde::Deserialize;
 use sqlx::PgPool;
 
 use crate::http::endpoints::scheduler::expired_emails::delete_expired_emails;
+use crate::http::endpoints::scheduler::notification_digest::send_notification_digests;
 use crate::{
     algolia::Manager,
-    error,
+    db, error,
     extractor::UserAgent,
     jwk::{IdentityClaims, JwkVerifier},
-    service::{upload::cleaner::UploadCleaner, ServiceData},
-    translate::GoogleTranslate,
+    service::{
+        mail, s3, storage,
+        upload::{cleaner::UploadCleaner, orphans, renditions},
+        ServiceData,
+    },
+    translate::Translator,
 };
 
 pub(crate) mod expired_emails;
+pub(crate) mod notification_digest;
 
 async fn batch_update(
     algolia_manager: ServiceData<Manager>,
@@ -61,8 +69,117 @@ async fn media_clean(
     Ok(HttpResponse::Ok().finish())
 }
 
+#[derive(Deserialize)]
+struct OrphanedMediaCleanQuery {
+    /// Only report the orphaned media, without deleting it.
+    #[serde(default)]
+    dry_run: bool,
+    /// Also clean uploads to the user libraries.
+    #[serde(default)]
+    include_user_library: bool,
+}
+
+async fn orphaned_media_clean(
+    s3: ServiceData<s3::Client>,
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    user_agent: UserAgent,
+    query: Query<OrphanedMediaCleanQuery>,
+) -> Result<Json<orphans::OrphanReport>, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    let report = orphans::clean(&db, query.dry_run, query.include_user_library)
+        .await?
+        .delete_files(&s3)
+        .await;
+
+    Ok(Json(report))
+}
+
+#[derive(Deserialize)]
+struct RenditionsBackfillQuery {
+    /// The most images to store renditions for.
+    #[serde(default = "default_renditions_limit")]
+    limit: i64,
+}
+
+fn default_renditions_limit() -> i64 {
+    100
+}
+
+/// Stores the renditions of images which were uploaded before renditions were stored.
+async fn renditions_backfill(
+    s3: ServiceData<s3::Client>,
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    user_agent: UserAgent,
+    query: Query<RenditionsBackfillQuery>,
+) -> Result<Json<renditions::RenditionReport>, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    let report = renditions::backfill(&db, &s3, query.limit).await?;
+
+    Ok(Json(report))
+}
+
+#[derive(Deserialize)]
+struct MediaSizeBackfillQuery {
+    /// The most uploads to count.
+    #[serde(default = "default_media_size_limit")]
+    limit: i64,
+}
+
+fn default_media_size_limit() -> i64 {
+    500
+}
+
+/// Counts the stored size of user uploads which were stored before their size was counted.
+async fn media_size_backfill(
+    s3: ServiceData<s3::Client>,
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    user_agent: UserAgent,
+    query: Query<MediaSizeBackfillQuery>,
+) -> Result<Json<storage::SizeReport>, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    let report = storage::backfill_sizes(&db, &s3, query.limit).await?;
+
+    Ok(Json(report))
+}
+
 async fn translate_descriptions(
-    google_translate_manager: ServiceData<GoogleTranslate>,
+    translator: ServiceData<Translator>,
     bearer_auth: BearerAuth,
     jwks: Data<JwkVerifier>,
     user_agent: UserAgent,
@@ -78,7 +195,7 @@ async fn translate_descriptions(
         .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
         .await?;
 
-    google_translate_manager.spawn_cron_jobs().await?;
+    translator.spawn_cron_jobs().await?;
 
     Ok(HttpResponse::Ok().finish())
 }
@@ -105,6 +222,78 @@ async fn clean_expired_emails(
     Ok(HttpResponse::Ok().finish())
 }
 
+/// Deletes JIG code sessions which are past their school's retention period.
+async fn purge_code_sessions(
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    user_agent: UserAgent,
+) -> Result<HttpResponse, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    let deleted = db::jig::codes::purge_expired_sessions(&*db).await?;
+
+    log::info!("purged {} expired jig code sessions", deleted);
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn send_email_outbox(
+    mail: ServiceData<mail::Client>,
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    user_agent: UserAgent,
+) -> Result<HttpResponse, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    mail.send_pending(&*db).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
+async fn notification_digest(
+    mail: ServiceData<mail::Client>,
+    bearer_auth: BearerAuth,
+    jwks: Data<JwkVerifier>,
+    db: Data<PgPool>,
+    config: Data<RuntimeSettings>,
+    user_agent: UserAgent,
+) -> Result<HttpResponse, error::ServiceSession> {
+    if user_agent
+        .0
+        .map_or(true, |it| it != "Google-Cloud-Scheduler")
+    {
+        return Err(error::ServiceSession::Unauthorized);
+    }
+
+    let _claims: IdentityClaims = jwks
+        .verify_iam_api_invoker_oauth(bearer_auth.token(), 3)
+        .await?;
+
+    send_notification_digests(&*db, &mail, &config.remote_target().pages_url()).await?;
+
+    Ok(HttpResponse::Ok().finish())
+}
+
 pub fn configure(cfg: &mut ServiceConfig) {
     cfg.route(
         "/v1/scheduler/update-algolia",
@@ -114,6 +303,18 @@ pub fn configure(cfg: &mut ServiceConfig) {
         "/v1/scheduler/media-clean",
         method(http::Method::POST).to(media_clean),
     );
+    cfg.route(
+        "/v1/scheduler/orphaned-media-clean",
+        method(http::Method::POST).to(orphaned_media_clean),
+    );
+    cfg.route(
+        "/v1/scheduler/renditions-backfill",
+        method(http::Method::POST).to(renditions_backfill),
+    );
+    cfg.route(
+        "/v1/scheduler/media-size-backfill",
+        method(http::Method::POST).to(media_size_backfill),
+    );
     cfg.route(
         "/v1/scheduler/translate-descriptions",
         method(http::Method::POST).to(translate_descriptions),
@@ -122,4 +323,16 @@ pub fn configure(cfg: &mut ServiceConfig) {
         "/v1/scheduler/expired-emails-clean",
         method(http::Method::POST).to(clean_expired_emails),
     );
+    cfg.route(
+        "/v1/scheduler/code-sessions-purge",
+        method(http::Method::POST).to(purge_code_sessions),
+    );
+    cfg.route(
+        "/v1/scheduler/email-outbox",
+        method(http::Method::POST).to(send_email_outbox),
+    );
+    cfg.route(
+        "/v1/scheduler/notification-digest",
+        method(http::Method::POST).to(notification_digest),
+    );
 }
@@ -0,0 +1,57 @@
+use sqlx::PgPool;
+
+use crate::{
+    db::{self, notification::Digest},
+    service::mail::{self, template::EmailTemplate},
+};
+
+/// Emails users a summary of their unread notifications, at most once a week.
+pub(crate) async fn send_notification_digests(
+    db: &PgPool,
+    mail: &mail::Client,
+    pages_url: &str,
+) -> anyhow::Result<()> {
+    let digests = db::notification::due_digests(db).await?;
+
+    log::debug!("sending {} notification digests", digests.len());
+
+    for digest in digests {
+        let user_id = digest.user_id;
+
+        if let Err(e) = send_digest(db, mail, pages_url, digest).await {
+            log::warn!("Failed to send notification digest to {}: {:?}", user_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+async fn send_digest(
+    db: &PgPool,
+    mail: &mail::Client,
+    pages_url: &str,
+    digest: Digest,
+) -> anyhow::Result<()> {
+    let mut txn = db.begin().await?;
+
+    let email_id = mail
+        .queue(
+            &mut txn,
+            &digest.email,
+            &mail::template::locale_for(&digest.language),
+            EmailTemplate::NotificationDigest {
+                url: pages_url.to_owned(),
+                first_name: digest.given_name,
+                counts: digest.counts,
+            },
+        )
+        .await?;
+
+    db::notification::record_digest(&mut txn, digest.user_id).await?;
+
+    txn.commit().await?;
+
+    mail.send_queued(db, email_id).await;
+
+    Ok(())
+}
@@ -6,10 +6,11 @@ use ji_core::settings::RuntimeSettings;
 use shared::error::ServiceError;
 use shared::{
     api::{endpoints::search, ApiEndpoint, PathParts},
-    domain::search::{CreateSearchKeyResponse, WebImageSearchResponse},
+    domain::search::CreateSearchKeyResponse,
 };
+use sqlx::PgPool;
 
-use crate::{error, extractor::TokenUser, service::ServiceData};
+use crate::{error, extractor::TokenUser, image_search, service::ServiceData};
 
 /// Create an Algolia search key based on the user's auth. Currently expires after 15 minutes, but that number is subject to change.
 /// # Errors
@@ -28,20 +29,20 @@ async fn create_key(
     ))
 }
 
-/// Search for images over the web.
+/// Search for images over the web, on every configured stock image service.
 /// # Errors
 /// 400: request not in proper format, or invalid image type
 pub async fn search_web_images(
+    db: Data<PgPool>,
     runtime_settings: Data<RuntimeSettings>,
     _claims: TokenUser,
     query: Query<<search::WebImageSearch as ApiEndpoint>::Req>,
 ) -> Result<Json<<search::WebImageSearch as ApiEndpoint>::Res>, error::Server> {
     let query = query.into_inner();
 
-    let res = match &runtime_settings.pixabay_search_key {
-        Some(key) => crate::image_search::get_images(&query.q, query.image_type, key).await?,
-        None => WebImageSearchResponse { images: Vec::new() },
-    };
+    let providers = image_search::providers(&runtime_settings);
+
+    let res = image_search::search(&db, &providers, &query.q, query.image_type).await?;
 
     Ok(Json(res))
 }
@@ -146,6 +146,8 @@ async fn handle_google_oauth(
     .fetch_optional(&mut txn)
     .await?;
 
+    let mut verification_email = None;
+
     let (user_id, mask) = match &google_auth {
         Some(google_auth) => {
             // Check user status: blocked, has verified email, has profile
@@ -178,15 +180,17 @@ async fn handle_google_oauth(
                 .await
                 .map_err(|_| anyhow::anyhow!("Google auth record missing email"))?;
 
-                send_verification_email(
-                    &mut txn,
-                    UserId(google_auth.user_id),
-                    email,
-                    mail,
-                    &remote_target.pages_url(),
-                )
-                .await
-                .map_err(|e| error::OAuth::InternalServerError(e.into()))?;
+                verification_email = Some(
+                    send_verification_email(
+                        &mut txn,
+                        UserId(google_auth.user_id),
+                        email,
+                        mail,
+                        &remote_target.pages_url(),
+                    )
+                    .await
+                    .map_err(|e| error::OAuth::InternalServerError(e.into()))?,
+                );
 
                 SessionMask::VERIFY_EMAIL
             };
@@ -227,15 +231,17 @@ async fn handle_google_oauth(
             .await?;
 
             // Send verification email
-            send_verification_email(
-                &mut txn,
-                UserId(id),
-                claims.email.to_lowercase(),
-                mail,
-                &remote_target.pages_url(),
-            )
-            .await
-            .map_err(|e| error::OAuth::InternalServerError(e.into()))?;
+            verification_email = Some(
+                send_verification_email(
+                    &mut txn,
+                    UserId(id),
+                    claims.email.to_lowercase(),
+                    mail,
+                    &remote_target.pages_url(),
+                )
+                .await
+                .map_err(|e| error::OAuth::InternalServerError(e.into()))?,
+            );
 
             (id, SessionMask::VERIFY_EMAIL)
         }
@@ -255,6 +261,10 @@ async fn handle_google_oauth(
 
     txn.commit().await?;
 
+    if let Some(email_id) = verification_email {
+        mail.send_queued(db, email_id).await;
+    }
+
     let (csrf, cookie) = create_auth_token(token_secret, local_insecure, login_ttl, &session)?;
 
     let response = NewSessionResponse { csrf };
@@ -12,22 +12,22 @@ use rand::{
     distributions::{Alphanumeric, DistString},
     thread_rng,
 };
-use sendgrid::v3::Email;
 use serde::{Deserialize, Serialize};
 use shared::{
     api::endpoints::{
         user::{
             self, Browse, BrowseFollowers, BrowseFollowing, BrowsePlaylists, BrowsePublicUser,
             BrowseResources, BrowseUserJigs, ChangePassword, Create, CreateColor, CreateFont,
-            CreateProfile, Delete, DeleteColor, DeleteFont, Follow, GetColors, GetFonts,
-            GetPublicUser, PatchProfile, PatchProfileAdminData, Profile, ResetEmail, ResetPassword,
-            Search, SearchUser, SwitchToBasicAuth, Unfollow, UpdateColor, UpdateFont, UserLookup,
-            VerifyEmail, VerifyResetEmail,
+            CreateProfile, Delete, DeleteColor, DeleteFont, DeleteMedia, Follow, GetColors,
+            GetFonts, GetPublicUser, ListMedia, PatchProfile, PatchProfileAdminData, Profile,
+            ResetEmail, ResetPassword, Search, SearchUser, SwitchToBasicAuth, Unfollow,
+            UpdateColor, UpdateFont, UserLookup, VerifyEmail, VerifyResetEmail,
         },
         ApiEndpoint, PathParts,
     },
     domain::{
         billing::AccountType,
+        email::EmailOutboxId,
         image::{ImageId, ImageSize},
         session::{NewSessionResponse, OAuthProvider},
         user::{
@@ -48,8 +48,11 @@ use crate::{
     domain::NoContentClearAuth,
     error::{self},
     extractor::{ScopeAdmin, SessionCreateProfile, SessionDelete, TokenSessionOf, TokenUser},
-    service::{mail, s3, ServiceData},
-    stripe::create_stripe_client,
+    service::{
+        mail::{self, template, template::EmailTemplate},
+        payment::PaymentProvider,
+        s3, ServiceData,
+    },
     token::{create_auth_token, SessionMask},
 };
 use crate::{
@@ -61,8 +64,18 @@ use super::jig::page_limit;
 
 mod color;
 mod font;
+mod media;
 pub mod public_user;
 
+/// Picks the locale to send a user's emails in.
+async fn email_locale(txn: &mut PgConnection, user_id: UserId) -> sqlx::Result<String> {
+    let language = db::user::get_email_language(txn, user_id).await?;
+
+    Ok(template::locale_for(
+        language.as_deref().unwrap_or_default(),
+    ))
+}
+
 #[instrument(skip(txn, email_address, mail))]
 pub async fn send_verification_email(
     txn: &mut PgConnection,
@@ -70,7 +83,7 @@ pub async fn send_verification_email(
     email_address: String,
     mail: &mail::Client,
     pages_url: &str,
-) -> Result<(), ServiceError> {
+) -> Result<EmailOutboxId, ServiceError> {
     let session = db::session::create(
         &mut *txn,
         user_id,
@@ -81,16 +94,20 @@ pub async fn send_verification_email(
     .await
     .into_anyhow()?;
 
-    let template = mail
-        .signup_verify_template()
-        .map_err(ServiceError::DisabledService)?;
+    let locale = email_locale(&mut *txn, user_id).await.into_anyhow()?;
 
     let email_link = format!("{}/user/verify-email/{}", pages_url, session);
 
-    mail.send_signup_verify(template, Email::new(email_address), email_link)
+    let email_id = mail
+        .queue(
+            txn,
+            &email_address,
+            &locale,
+            EmailTemplate::SignupVerify { url: email_link },
+        )
         .await?;
 
-    Ok(())
+    Ok(email_id)
 }
 
 #[instrument(skip(txn, email_address, mail))]
@@ -100,24 +117,26 @@ async fn send_welcome_jigzi_email(
     email_address: String,
     mail: &mail::Client,
     pages_url: &str,
-) -> Result<(), ServiceError> {
+) -> Result<EmailOutboxId, ServiceError> {
     let first_name = db::user::get_given_name(&mut *txn, user_id)
         .await
         .into_anyhow()?;
 
-    let template = mail
-        .welcome_jigzi_template()
-        .map_err(ServiceError::DisabledService)?;
-
-    mail.send_welcome_jigzi(
-        template,
-        Email::new(email_address),
-        pages_url.to_string(),
-        first_name,
-    )
-    .await?;
+    let locale = email_locale(&mut *txn, user_id).await.into_anyhow()?;
+
+    let email_id = mail
+        .queue(
+            txn,
+            &email_address,
+            &locale,
+            EmailTemplate::WelcomeJigzi {
+                url: pages_url.to_string(),
+                first_name,
+            },
+        )
+        .await?;
 
-    Ok(())
+    Ok(email_id)
 }
 
 #[instrument(skip(txn, email_address, mail))]
@@ -128,8 +147,10 @@ async fn send_password_email(
     mail: &mail::Client,
     pages_url: &str,
     is_oauth: bool,
-) -> Result<(), ServiceError> {
-    if !is_oauth {
+) -> Result<EmailOutboxId, ServiceError> {
+    let locale = email_locale(&mut *txn, user_id).await.into_anyhow()?;
+
+    let email = if !is_oauth {
         let session = db::session::create(
             &mut *txn,
             user_id,
@@ -144,20 +165,21 @@ async fn send_password_email(
             .await
             .into_anyhow()?;
 
-        let template = mail
-            .password_reset_template()
-            .map_err(ServiceError::DisabledService)?;
-
         let email_link = format!("{}/user/password-reset/{}", pages_url, session);
 
-        mail.send_password_reset(template, Email::new(email_address), email_link, first_name)
-            .await?;
+        EmailTemplate::PasswordReset {
+            url: email_link,
+            first_name,
+        }
     } else {
-        mail.send_oauth_password_reset(Email::new(email_address), OAuthProvider::Google)
-            .await?;
-    }
+        EmailTemplate::OAuthPasswordReset {
+            provider: OAuthProvider::Google.as_str().to_owned(),
+        }
+    };
 
-    Ok(())
+    let email_id = mail.queue(txn, &email_address, &locale, email).await?;
+
+    Ok(email_id)
 }
 
 #[instrument(skip(txn, mail))]
@@ -167,7 +189,7 @@ async fn send_reset_email(
     email_address: String,
     mail: &mail::Client,
     pages_url: &str,
-) -> Result<(), ServiceError> {
+) -> Result<EmailOutboxId, ServiceError> {
     let session = db::session::create(
         &mut *txn,
         user_id,
@@ -182,17 +204,24 @@ async fn send_reset_email(
         .await
         .into_anyhow()?;
 
-    let template = mail
-        .email_reset_template()
-        .map_err(ServiceError::DisabledService)?;
+    let locale = email_locale(&mut *txn, user_id).await.into_anyhow()?;
 
+    // email_link contains the session id
     let email_link = format!("{}/user/verify-email-reset/{}", pages_url, session);
 
-    // email_link contains the session id
-    mail.send_email_reset(template, Email::new(email_address), email_link, first_name)
+    let email_id = mail
+        .queue(
+            txn,
+            &email_address,
+            &locale,
+            EmailTemplate::EmailReset {
+                url: email_link,
+                first_name,
+            },
+        )
         .await?;
 
-    Ok(())
+    Ok(email_id)
 }
 
 #[instrument(skip_all)]
@@ -280,7 +309,7 @@ async fn create_user(
     .await
     .into_anyhow()?;
 
-    send_verification_email(
+    let email_id = send_verification_email(
         &mut txn,
         UserId(user.id),
         email,
@@ -292,6 +321,8 @@ async fn create_user(
 
     txn.commit().await.into_anyhow()?;
 
+    mail.send_queued(&db, email_id).await;
+
     Ok(HttpResponse::NoContent().finish())
 }
 
@@ -338,7 +369,7 @@ where
             // make sure they can't use the old link anymore
             db::session::clear_any(&mut txn, user.id, SessionMask::VERIFY_EMAIL).await?;
 
-            send_verification_email(
+            let email_id = send_verification_email(
                 &mut txn,
                 user.id,
                 lowercase_email.to_string(),
@@ -360,6 +391,8 @@ where
 
             txn.commit().await?;
 
+            mail.send_queued(&db, email_id).await;
+
             Ok(HttpResponse::NoContent().into())
         }
 
@@ -519,7 +552,7 @@ async fn create_profile(
 
     let email = db::user::get_email(&mut txn, user_id).await?;
 
-    send_welcome_jigzi_email(
+    let email_id = send_welcome_jigzi_email(
         &mut txn,
         user_id,
         email,
@@ -536,6 +569,8 @@ async fn create_profile(
 
     txn.commit().await?;
 
+    mail.send_queued(&db, email_id).await;
+
     Ok(HttpResponse::Created()
         .cookie(cookie)
         .json(NewSessionResponse { csrf }))
@@ -579,22 +614,16 @@ async fn create_user_profile_image(
     let data = Arc::new(data);
 
     // process
-    let (original, resized, thumbnail) = actix_web::web::block(move || {
-        let original = image::load_from_memory(&data)?;
+    let files = actix_web::web::block(move || {
+        let original = crate::image_ops::load_image(&data).map_err(anyhow::Error::msg)?;
         crate::image_ops::generate_images(&original, ImageSize::Sticker)
     })
     .instrument(tracing::info_span!("process image"))
     .await??;
 
     // upload to ID
-    s3.upload_png_images(
-        MediaLibrary::User,
-        profile_image_id.0,
-        original,
-        resized,
-        thumbnail,
-    )
-    .await?;
+    s3.upload_images(MediaLibrary::User, profile_image_id.0, files)
+        .await?;
 
     sqlx::query!(
         //language=SQL
@@ -602,7 +631,8 @@ async fn create_user_profile_image(
 update user_image_upload
 set uploaded_at       = now(),
     processed_at      = now(),
-    processing_result = true
+    processing_result = true,
+    renditions_at     = now()
 where image_id = $1
 "#,
         profile_image_id.0
@@ -675,7 +705,7 @@ async fn email_reset(
     )?;
 
     // 2. Send email reset email with token
-    send_reset_email(
+    let email_id = send_reset_email(
         &mut txn,
         user_id,
         lowercase_email,
@@ -687,6 +717,8 @@ async fn email_reset(
 
     txn.commit().await.into_anyhow()?;
 
+    mail.send_queued(&db, email_id).await;
+
     Ok(Json(ResetEmailResponse { paseto_token }))
 }
 
@@ -881,7 +913,7 @@ async fn patch_profile_admin_data(
     _auth: TokenUserWithScope<ScopeAdmin>,
     req: Json<<PatchProfileAdminData as ApiEndpoint>::Req>,
     path: web::Path<UserId>,
-    settings: Data<RuntimeSettings>,
+    payments: Option<ServiceData<dyn PaymentProvider>>,
 ) -> Result<HttpResponse, error::UserUpdate> {
     let user_id: UserId = path.into_inner();
 
@@ -910,11 +942,12 @@ async fn patch_profile_admin_data(
 
         if let Some(customer_id) = stripe_id {
             if email_changed {
-                let client = create_stripe_client(&settings)?;
-                let mut update_customer = stripe::UpdateCustomer::default();
-                update_customer.email = Some(new_email.as_str());
+                let payments =
+                    payments.ok_or(ServiceError::DisabledService(ServiceKindError::Stripe))?;
 
-                stripe::Customer::update(&client, &customer_id.into(), update_customer).await?;
+                payments
+                    .update_customer(&customer_id, Some(new_email.as_str()), None)
+                    .await?;
             }
         }
     }
@@ -981,7 +1014,7 @@ async fn switch_to_basic_auth(
     .await
     .into_anyhow()?;
 
-    send_password_email(
+    let email_id = send_password_email(
         &mut txn,
         user_id,
         profile.email,
@@ -993,6 +1026,8 @@ async fn switch_to_basic_auth(
 
     txn.commit().await.into_anyhow()?;
 
+    mail.send_queued(&db, email_id).await;
+
     Ok(HttpResponse::NoContent().finish())
 }
 
@@ -1059,7 +1094,7 @@ async fn reset_password(
         return Ok(HttpResponse::NoContent().finish());
     }
 
-    send_password_email(
+    let email_id = send_password_email(
         &mut txn,
         user_id,
         email,
@@ -1071,6 +1106,8 @@ async fn reset_password(
 
     txn.commit().await.into_anyhow()?;
 
+    mail.send_queued(&db, email_id).await;
+
     Ok(HttpResponse::NoContent().finish())
 }
 
@@ -1324,6 +1361,14 @@ pub fn configure(cfg: &mut ServiceConfig) {
         <DeleteFont as ApiEndpoint>::Path::PATH,
         DeleteFont::METHOD.route().to(font::delete),
     )
+    .route(
+        <ListMedia as ApiEndpoint>::Path::PATH,
+        ListMedia::METHOD.route().to(media::list),
+    )
+    .route(
+        <DeleteMedia as ApiEndpoint>::Path::PATH,
+        DeleteMedia::METHOD.route().to(media::delete),
+    )
     .route(
         <Search as ApiEndpoint>::Path::PATH,
         Search::METHOD.route().to(public_user::search),
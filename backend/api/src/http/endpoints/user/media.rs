@@ -0,0 +1,60 @@
+use crate::{
+    db, error,
+    extractor::TokenUser,
+    service::{s3, storage, upload, ServiceData},
+};
+
+use actix_web::web::{Data, Json};
+use shared::{
+    api::endpoints::{
+        user::{DeleteMedia, ListMedia},
+        ApiEndpoint,
+    },
+    domain::user::{UserMediaDeleteRequest, UserMediaDeleteResponse, UserMediaListResponse},
+};
+use sqlx::PgPool;
+
+pub async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<ListMedia as ApiEndpoint>::Res>, error::Server> {
+    let user_id = claims.user_id();
+
+    let media = db::user::media::list(db.as_ref(), user_id, None).await?;
+    let storage = storage::usage(db.as_ref(), user_id, None).await?;
+
+    Ok(Json(UserMediaListResponse { media, storage }))
+}
+
+pub async fn delete(
+    db: Data<PgPool>,
+    s3: ServiceData<s3::Client>,
+    claims: TokenUser,
+    req: Json<UserMediaDeleteRequest>,
+) -> Result<Json<<DeleteMedia as ApiEndpoint>::Res>, error::Server> {
+    let user_id = claims.user_id();
+
+    // only the user's own files are listed, so anyone else's IDs are dropped here
+    let (unused, in_use): (Vec<_>, Vec<_>) =
+        db::user::media::list(db.as_ref(), user_id, Some(&req.ids))
+            .await?
+            .into_iter()
+            .partition(|media| media.is_unreferenced());
+
+    let ids: Vec<_> = unused.iter().map(|media| media.id).collect();
+
+    let mut txn = db.begin().await?;
+    let pdfs = db::user::media::delete(&mut txn, user_id, &ids).await?;
+    txn.commit().await?;
+
+    let media: Vec<_> = unused.iter().map(|media| (media.id, media.kind)).collect();
+    upload::delete_user_media_files(&s3, &media, &pdfs).await;
+
+    let storage = storage::usage(db.as_ref(), user_id, None).await?;
+
+    Ok(Json(UserMediaDeleteResponse {
+        deleted: ids,
+        in_use: in_use.into_iter().map(|media| media.id).collect(),
+        storage,
+    }))
+}
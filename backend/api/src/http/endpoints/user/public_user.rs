@@ -288,6 +288,8 @@ pub async fn follow(
 
     db::user::public_user::follow(&db, user_id, follower_id).await?;
 
+    db::notification::followed(&db, user_id, follower_id).await?;
+
     Ok(HttpResponse::NoContent().finish())
 }
 
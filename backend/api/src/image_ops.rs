@@ -1,9 +1,28 @@
+use std::{io::Cursor, sync::Arc};
+
 use image::{
-    codecs::gif::GifDecoder, imageops::FilterType, AnimationDecoder, DynamicImage,
-    GenericImageView, ImageOutputFormat,
+    codecs::gif::GifDecoder, imageops::FilterType, io::Reader, AnimationDecoder, DynamicImage,
+    GenericImageView, ImageDecoder, ImageOutputFormat,
 };
 use shared::domain::{animation::AnimationKind, audio::AudioKind, image::ImageSize};
-use shared::media::MediaKind as SharedMediaKind;
+use shared::media::{
+    FileKind, ImageWidth, MediaKind as SharedMediaKind, PngImageFile, RenditionSize,
+};
+
+use resvg::{tiny_skia, usvg};
+
+use crate::media_type::{check_image_dimensions, MediaError, MediaType};
+
+/// Quality of lossy WebP renditions, from 0 to 100.
+const WEBP_QUALITY: f32 = 80.0;
+
+/// Speed of the AVIF encoder, from 1 (slowest, smallest) to 10.
+#[cfg(feature = "avif")]
+const AVIF_SPEED: u8 = 6;
+
+/// Quality of AVIF renditions, from 0 to 100.
+#[cfg(feature = "avif")]
+const AVIF_QUALITY: u8 = 70;
 
 /// Kinds of media used with the web media library
 #[repr(i16)]
@@ -23,6 +42,9 @@ pub enum MediaKind {
 
     /// Media is a Mp3, and Audio
     Mp3Audio = 4,
+
+    /// Media is an Svg, and a Sticker Image
+    SvgStickerImage = 5,
 }
 
 impl MediaKind {
@@ -32,81 +54,394 @@ impl MediaKind {
             Self::GifAnimation => SharedMediaKind::Animation(AnimationKind::Gif),
             Self::SpritesheetAnimation => SharedMediaKind::Animation(AnimationKind::Spritesheet),
             Self::Mp3Audio => SharedMediaKind::Audio(AudioKind::Mp3),
+            Self::SvgStickerImage => SharedMediaKind::SvgImage,
         }
     }
 }
 
-// todo: use a better method for this
-pub fn detect_image_kind(data: &[u8]) -> anyhow::Result<MediaKind> {
-    let decoder = GifDecoder::new(&*data);
+/// Detects whether an image upload is a still image, an SVG or an animated GIF.
+pub fn detect_image_kind(data: &[u8]) -> Result<MediaKind, MediaError> {
+    match MediaType::expect(data, MediaType::IMAGE_UPLOADS)? {
+        MediaType::Gif => {}
+        MediaType::Svg => return Ok(MediaKind::SvgStickerImage),
+        _ => return Ok(MediaKind::PngStickerImage),
+    }
+
+    let decoder = GifDecoder::new(data).map_err(|_| MediaError::Malformed)?;
+    let (width, height) = decoder.dimensions();
+    check_image_dimensions(width, height)?;
 
-    let frames = match decoder {
-        Ok(decoder) => decoder.into_frames().count(),
-        Err(image::ImageError::Decoding(_)) => return Ok(MediaKind::PngStickerImage),
-        Err(e) => return Err(e.into()),
-    };
+    // only the first two frames are needed to tell
+    let frames = decoder
+        .into_frames()
+        .take(2)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| MediaError::Malformed)?;
 
-    if frames < 2 {
+    if frames.len() < 2 {
         Ok(MediaKind::PngStickerImage)
     } else {
         Ok(MediaKind::GifAnimation)
     }
 }
 
-pub fn regenerate_images(
-    original: &DynamicImage,
-    size: ImageSize,
-) -> anyhow::Result<(Vec<u8>, Vec<u8>)> {
-    let resized = {
-        let (width, height) = size.size();
-        let new_image = match size {
-            ImageSize::Canvas => original.resize_exact(width, height, FilterType::Triangle),
+/// Decodes an uploaded still image, and rotates it upright according to its EXIF orientation.
+///
+/// The dimensions are checked from the header before decoding, to avoid decompression bombs.
+/// The decoded image carries no metadata, so re-encoding it strips EXIF (including location) data.
+pub fn load_image(data: &[u8]) -> Result<DynamicImage, MediaError> {
+    let format = MediaType::expect(data, MediaType::IMAGES)?
+        .image_format()
+        .ok_or(MediaError::Malformed)?;
 
-            ImageSize::Sticker if (width >= original.width() && height >= original.height()) => {
-                original.clone()
-            }
+    let (width, height) = Reader::with_format(Cursor::new(data), format)
+        .into_dimensions()
+        .map_err(|_| MediaError::Malformed)?;
+    check_image_dimensions(width, height)?;
 
-            ImageSize::Sticker => original.resize(width, height, FilterType::Nearest),
+    let image =
+        image::load_from_memory_with_format(data, format).map_err(|_| MediaError::Malformed)?;
 
-            ImageSize::UserProfile => original.resize(width, height, FilterType::Nearest),
-        };
+    Ok(orient(image, exif_orientation(data)))
+}
 
-        let mut buffer = Vec::new();
-        new_image.write_to(&mut buffer, ImageOutputFormat::Png)?;
-        buffer
+/// Sanitizes an uploaded SVG, and rasterizes it to fit within `size` for the PNG renditions.
+///
+/// The SVG is parsed and written back out by `usvg`, which only keeps what it renders: scripts,
+/// event handlers, `foreignObject`s and links don't survive. Images (embedded or not) are dropped so
+/// nothing outside of the file is ever loaded, and text which wasn't converted to paths isn't kept.
+#[allow(clippy::cast_precision_loss)]
+pub fn load_svg(data: &[u8], size: ImageSize) -> Result<(Vec<u8>, DynamicImage), MediaError> {
+    MediaType::expect(data, &[MediaType::Svg])?;
+
+    let options = usvg::Options {
+        image_href_resolver: usvg::ImageHrefResolver {
+            resolve_data: Box::new(
+                |_: &str, _: Arc<Vec<u8>>, _: &usvg::Options<'_>| -> Option<usvg::ImageKind> {
+                    None
+                },
+            ),
+            resolve_string: Box::new(
+                |_: &str, _: &usvg::Options<'_>| -> Option<usvg::ImageKind> { None },
+            ),
+        },
+        ..usvg::Options::default()
     };
 
-    let thumbnail = {
-        let mut buffer = Vec::new();
-        let (width, height) = ImageSize::THUMBNAIL_SIZE;
-        original
-            .thumbnail(width, height)
-            .write_to(&mut buffer, ImageOutputFormat::Png)?;
-        buffer
-    };
+    let tree = usvg::Tree::from_data(data, &options).map_err(|_| MediaError::Malformed)?;
+
+    let svg_size = tree.size();
+    // usvg doesn't write a `viewBox`, which browsers need to scale the SVG to other sizes
+    let sanitized = tree.to_string(&usvg::WriteOptions::default()).replacen(
+        "<svg ",
+        &format!(
+            r#"<svg viewBox="0 0 {} {}" "#,
+            svg_size.width(),
+            svg_size.height()
+        ),
+        1,
+    );
+
+    let (max_width, max_height) = size.size();
+    let scale = (max_width as f32 / svg_size.width()).min(max_height as f32 / svg_size.height());
+    let width = (svg_size.width() * scale).round().max(1.0) as u32;
+    let height = (svg_size.height() * scale).round().max(1.0) as u32;
+
+    let mut pixmap = tiny_skia::Pixmap::new(width, height).ok_or(MediaError::Malformed)?;
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+
+    // tiny-skia uses premultiplied alpha
+    let rgba = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+
+    let raster = image::RgbaImage::from_raw(width, height, rgba).ok_or(MediaError::Malformed)?;
+
+    Ok((sanitized.into_bytes(), DynamicImage::ImageRgba8(raster)))
+}
+
+/// Checks that an animation is a GIF within the size limits, with a decodable first frame.
+pub fn validate_gif(data: &[u8]) -> Result<(), MediaError> {
+    MediaType::expect(data, MediaType::ANIMATIONS)?;
+
+    let decoder = GifDecoder::new(data).map_err(|_| MediaError::Malformed)?;
+    let (width, height) = decoder.dimensions();
+    check_image_dimensions(width, height)?;
+
+    DynamicImage::from_decoder(decoder).map_err(|_| MediaError::Malformed)?;
+
+    Ok(())
+}
+
+/// Reads the EXIF orientation tag, defaulting to 1 (upright) if there isn't one.
+fn exif_orientation(data: &[u8]) -> u32 {
+    exif::Reader::new()
+        .read_from_container(&mut Cursor::new(data))
+        .ok()
+        .and_then(|exif| {
+            exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+                .and_then(|field| field.value.get_uint(0))
+        })
+        .unwrap_or(1)
+}
+
+/// Applies an EXIF orientation, see <https://www.exif.org/Exif2-2.PDF> (page 18).
+fn orient(image: DynamicImage, orientation: u32) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+fn resize(original: &DynamicImage, size: ImageSize) -> DynamicImage {
+    let (width, height) = size.size();
+    match size {
+        ImageSize::Canvas => original.resize_exact(width, height, FilterType::Triangle),
+
+        ImageSize::Sticker if (width >= original.width() && height >= original.height()) => {
+            original.clone()
+        }
+
+        ImageSize::Sticker => original.resize(width, height, FilterType::Nearest),
+
+        ImageSize::UserProfile => original.resize(width, height, FilterType::Nearest),
+    }
+}
+
+fn thumbnail(original: &DynamicImage) -> DynamicImage {
+    let (width, height) = ImageSize::THUMBNAIL_SIZE;
+    original.thumbnail(width, height)
+}
+
+fn encode_png(image: &DynamicImage) -> anyhow::Result<Vec<u8>> {
+    let mut buffer = Vec::new();
+    image.write_to(&mut buffer, ImageOutputFormat::Png)?;
+    Ok(buffer)
+}
+
+fn encode_webp(image: &DynamicImage) -> Vec<u8> {
+    let rgba = image.to_rgba8();
+    webp::Encoder::from_rgba(rgba.as_raw(), rgba.width(), rgba.height())
+        .encode(WEBP_QUALITY)
+        .to_vec()
+}
+
+#[cfg(feature = "avif")]
+fn encode_avif(image: &DynamicImage) -> anyhow::Result<Vec<u8>> {
+    use image::ImageEncoder;
+
+    let rgba = image.to_rgba8();
+    let mut buffer = Vec::new();
+    image::codecs::avif::AvifEncoder::new_with_speed_quality(&mut buffer, AVIF_SPEED, AVIF_QUALITY)
+        .write_image(
+            rgba.as_raw(),
+            rgba.width(),
+            rgba.height(),
+            image::ColorType::Rgba8,
+        )?;
+    Ok(buffer)
+}
+
+/// Encodes `image` in every format besides PNG, at the `size` rendition.
+fn encode_variants(
+    size: RenditionSize,
+    image: &DynamicImage,
+) -> anyhow::Result<Vec<(FileKind, Vec<u8>)>> {
+    #[allow(unused_mut)]
+    let mut variants = vec![(FileKind::ImageWebp(size), encode_webp(image))];
+
+    #[cfg(feature = "avif")]
+    variants.push((FileKind::ImageAvif(size), encode_avif(image)?));
+
+    Ok(variants)
+}
+
+pub fn regenerate_images(
+    original: &DynamicImage,
+    size: ImageSize,
+) -> anyhow::Result<(Vec<u8>, Vec<u8>)> {
+    let resized = encode_png(&resize(original, size))?;
+    let thumbnail = encode_png(&thumbnail(original))?;
 
     Ok((resized, thumbnail))
 }
 
+/// Generates every file stored for an image: the original, resized and thumbnail PNGs, the same
+/// in the other formats, and the resized image at each [`ImageWidth`] for `srcset`s.
 pub fn generate_images(
     original: &DynamicImage,
     size: ImageSize,
-) -> anyhow::Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
-    let (resized, thumbnail) = regenerate_images(original, size)?;
+) -> anyhow::Result<Vec<(FileKind, Vec<u8>)>> {
+    let resized = resize(original, size);
+    let thumbnail = thumbnail(original);
+
+    let mut files = vec![
+        (
+            FileKind::ImagePng(PngImageFile::Original),
+            encode_png(original)?,
+        ),
+        (
+            FileKind::ImagePng(PngImageFile::Resized),
+            encode_png(&resized)?,
+        ),
+        (
+            FileKind::ImagePng(PngImageFile::Thumbnail),
+            encode_png(&thumbnail)?,
+        ),
+    ];
+
+    files.extend(encode_variants(
+        RenditionSize::Png(PngImageFile::Original),
+        original,
+    )?);
+    files.extend(encode_variants(
+        RenditionSize::Png(PngImageFile::Resized),
+        &resized,
+    )?);
+    files.extend(encode_variants(
+        RenditionSize::Png(PngImageFile::Thumbnail),
+        &thumbnail,
+    )?);
+
+    for width in ImageWidth::ALL {
+        let narrower = if resized.width() > width.pixels() {
+            resized.resize(width.pixels(), u32::MAX, FilterType::Triangle)
+        } else {
+            resized.clone()
+        };
 
-    let original = {
-        let mut buffer = Vec::new();
-        original.write_to(&mut buffer, ImageOutputFormat::Png)?;
-        buffer
-    };
+        files.extend(encode_variants(
+            RenditionSize::ResizedWidth(width),
+            &narrower,
+        )?);
+    }
 
-    Ok((original, resized, thumbnail))
+    Ok(files)
+}
+
+/// Generates every file stored for an SVG image, from [`load_svg`]: the sanitized SVG, and the
+/// same renditions as [`generate_images`] from the rasterized SVG.
+pub fn generate_svg_images(
+    svg: Vec<u8>,
+    raster: &DynamicImage,
+    size: ImageSize,
+) -> anyhow::Result<Vec<(FileKind, Vec<u8>)>> {
+    let mut files = generate_images(raster, size)?;
+    files.push((FileKind::ImageSvg, svg));
+
+    Ok(files)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn detect_still_image() {
+        let original = include_bytes!("../fixtures/images/city-wide.png");
+
+        assert!(matches!(
+            detect_image_kind(original),
+            Ok(MediaKind::PngStickerImage)
+        ));
+        assert!(matches!(
+            detect_image_kind(b"%PDF-1.7\n"),
+            Err(MediaError::Unsupported { .. })
+        ));
+    }
+
+    const UNSAFE_SVG: &str = r#"<?xml version="1.0"?>
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="100" height="50" onload="alert(1)">
+    <script>alert(2)</script>
+    <foreignObject width="10" height="10"><iframe xmlns="http://www.w3.org/1999/xhtml" src="https://example.com"/></foreignObject>
+    <image xlink:href="file:///etc/passwd" width="10" height="10"/>
+    <image href="https://example.com/tracker.png" width="10" height="10"/>
+    <a xlink:href="javascript:alert(3)"><rect width="100" height="50" fill="red"/></a>
+</svg>"#;
+
+    #[test]
+    fn sanitize_svg() -> anyhow::Result<()> {
+        assert!(matches!(
+            detect_image_kind(UNSAFE_SVG.as_bytes()),
+            Ok(MediaKind::SvgStickerImage)
+        ));
+
+        let (svg, raster) =
+            load_svg(UNSAFE_SVG.as_bytes(), ImageSize::Sticker).map_err(anyhow::Error::msg)?;
+        let svg = String::from_utf8(svg)?;
+
+        for unsafe_content in [
+            "script",
+            "alert",
+            "onload",
+            "foreignObject",
+            "iframe",
+            "example.com",
+            "passwd",
+            "href",
+        ] {
+            assert!(!svg.contains(unsafe_content), "{}", svg);
+        }
+        assert!(svg.contains(r#"viewBox="0 0 100 50""#), "{}", svg);
+
+        assert_eq!((1440, 720), raster.dimensions());
+        assert_eq!([255, 0, 0, 255], raster.get_pixel(700, 300).0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn orient_rotates_and_flips() {
+        let image = DynamicImage::new_rgba8(3, 2);
+
+        assert_eq!((3, 2), orient(image.clone(), 1).dimensions());
+        assert_eq!((3, 2), orient(image.clone(), 3).dimensions());
+        assert_eq!((2, 3), orient(image.clone(), 6).dimensions());
+        assert_eq!((2, 3), orient(image, 5).dimensions());
+    }
+
+    /// A 1x1 transparent GIF.
+    const PIXEL_GIF: &[u8] = b"GIF89a\x01\x00\x01\x00\x80\x00\x00\xFF\xFF\xFF\x00\x00\x00!\xF9\x04\x01\x00\x00\x00\x00,\x00\x00\x00\x00\x01\x00\x01\x00\x00\x02\x02D\x01\x00;";
+
+    #[test]
+    fn single_frame_gif_is_still() {
+        assert!(validate_gif(PIXEL_GIF).is_ok());
+        assert!(matches!(
+            detect_image_kind(PIXEL_GIF),
+            Ok(MediaKind::PngStickerImage)
+        ));
+    }
+
+    #[test]
+    fn reject_oversized_gif() {
+        // a tiny file which claims a 10000x10000 screen in its header
+        let mut data = PIXEL_GIF.to_vec();
+        data[6..10].copy_from_slice(&[0x10, 0x27, 0x10, 0x27]);
+
+        assert!(matches!(
+            validate_gif(&data),
+            Err(MediaError::TooLarge {
+                width: 10_000,
+                height: 10_000
+            })
+        ));
+    }
+
     #[ignore] // slow
     #[test]
     fn regenerate_sticker_square() -> anyhow::Result<()> {
@@ -140,4 +475,93 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn generate_sticker_widths() -> anyhow::Result<()> {
+        let original = DynamicImage::new_rgba8(600, 400);
+
+        let files = generate_images(&original, ImageSize::Sticker)?;
+
+        let dimensions = |size| -> anyhow::Result<(u32, u32)> {
+            let (_, data) = files
+                .iter()
+                .find(|(file_kind, _)| *file_kind == FileKind::ImageWebp(size))
+                .ok_or_else(|| anyhow::anyhow!("missing {:?}", size))?;
+
+            let image = webp::Decoder::new(data)
+                .decode()
+                .ok_or_else(|| anyhow::anyhow!("invalid WebP for {:?}", size))?;
+
+            Ok((image.width(), image.height()))
+        };
+
+        assert_eq!(
+            (480, 320),
+            dimensions(RenditionSize::ResizedWidth(ImageWidth::Small))?
+        );
+
+        // narrower widths are never scaled up
+        assert_eq!(
+            (600, 400),
+            dimensions(RenditionSize::ResizedWidth(ImageWidth::Medium))?
+        );
+
+        // every generated file is one which can be found again from its key
+        for (file_kind, _) in &files {
+            let key = shared::media::media_key(
+                shared::media::MediaLibrary::User,
+                uuid::Uuid::nil(),
+                *file_kind,
+            );
+            let suffix = key.rsplit('/').next().unwrap();
+
+            assert_eq!(*file_kind, suffix.parse::<FileKind>()?);
+            assert!(FileKind::image_files().contains(file_kind));
+        }
+
+        Ok(())
+    }
+
+    #[ignore] // slow
+    #[test]
+    fn generate_canvas_widths() -> anyhow::Result<()> {
+        let original = include_bytes!("../fixtures/images/city-wide.png");
+        let original = image::load_from_memory(original)?;
+
+        let files = generate_images(&original, ImageSize::Canvas)?;
+
+        let dimensions = |kind| -> anyhow::Result<(u32, u32)> {
+            let (_, data) = files
+                .iter()
+                .find(|(file_kind, _)| *file_kind == kind)
+                .ok_or_else(|| anyhow::anyhow!("missing {:?}", kind))?;
+
+            let image = webp::Decoder::new(data)
+                .decode()
+                .ok_or_else(|| anyhow::anyhow!("invalid WebP for {:?}", kind))?;
+
+            Ok((image.width(), image.height()))
+        };
+
+        assert_eq!(
+            (1920, 1080),
+            dimensions(FileKind::ImageWebp(RenditionSize::Png(
+                PngImageFile::Resized
+            )))?
+        );
+        assert_eq!(
+            (480, 270),
+            dimensions(FileKind::ImageWebp(RenditionSize::ResizedWidth(
+                ImageWidth::Small
+            )))?
+        );
+        assert_eq!(
+            (960, 540),
+            dimensions(FileKind::ImageWebp(RenditionSize::ResizedWidth(
+                ImageWidth::Medium
+            )))?
+        );
+
+        Ok(())
+    }
 }
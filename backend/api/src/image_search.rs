@@ -1,72 +1,268 @@
-use anyhow;
-use serde::Deserialize;
+//! Searching for images on stock image services.
+//!
+//! Every configured [`ImageSearchProvider`] is searched, and their results are merged into one list
+//! without duplicates. Merged results are cached by query for [`CACHE_TTL_HOURS`], since searches
+//! are made as the user types.
+
+use std::{collections::HashSet, sync::Arc};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use ji_core::settings::RuntimeSettings;
 use shared::domain::search::{ImageType, WebImageSearchItem, WebImageSearchResponse};
+use sqlx::{types::Json, PgPool};
+
+pub mod openverse;
+pub mod pixabay;
+pub mod unsplash;
+
+/// How long search results are cached for.
+const CACHE_TTL_HOURS: i32 = 24;
+
+/// How many images are returned from a search, across every provider.
+const MAX_RESULTS: usize = 100;
+
+/// How many images are asked for from each provider.
+const PER_PROVIDER: usize = 50;
 
-const QUERY_TYPE: &str = "image_type";
+/// A stock image service.
+#[async_trait]
+pub trait ImageSearchProvider: Send + Sync {
+    /// Name of the provider, for logs.
+    fn name(&self) -> &'static str;
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct Image {
-    #[serde(rename(deserialize = "previewURL"))]
-    thumbnail_url: url::Url,
-    #[serde(rename(deserialize = "largeImageURL"))]
-    content_url: url::Url,
+    /// Searches for up to [`PER_PROVIDER`] images matching `query`.
+    async fn search(
+        &self,
+        query: &str,
+        image_type: Option<ImageType>,
+    ) -> anyhow::Result<Vec<WebImageSearchItem>>;
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ImagesResponse {
-    hits: Vec<Image>,
+/// The providers to search with, in the order their results are merged in.
+///
+/// Openverse doesn't need a key, while Pixabay and Unsplash are only searched when theirs are
+/// configured.
+pub fn providers(settings: &RuntimeSettings) -> Vec<Arc<dyn ImageSearchProvider>> {
+    let mut providers: Vec<Arc<dyn ImageSearchProvider>> = Vec::new();
+
+    if let Some(key) = &settings.pixabay_search_key {
+        providers.push(Arc::new(pixabay::Pixabay::new(key.clone())));
+    }
+
+    providers.push(Arc::new(openverse::Openverse));
+
+    if let Some(access_key) = &settings.unsplash_access_key {
+        providers.push(Arc::new(unsplash::Unsplash::new(access_key.clone())));
+    }
+
+    providers
 }
 
-pub async fn get_images(
+/// Searches every provider for `query`, or returns the cached results of an earlier search.
+pub async fn search(
+    db: &PgPool,
+    providers: &[Arc<dyn ImageSearchProvider>],
     query: &str,
     image_type: Option<ImageType>,
-    key: &str,
 ) -> anyhow::Result<WebImageSearchResponse> {
-    // returns tuple if there is a valid user input,
-    // otherwise empty query for image type field
-    let image_type: (&str, String) = if let Some(image) = image_type {
-        let im_type = match image {
-            ImageType::Clipart => ImageType::Clipart.to_str().to_lowercase(),
-            ImageType::AnimatedGif => ImageType::AnimatedGif.to_str().to_lowercase(),
-            ImageType::Photo => ImageType::Photo.to_str().to_lowercase(),
-            ImageType::Line => ImageType::Line.to_str().to_lowercase(),
-            ImageType::Transparent => ImageType::Transparent.to_str().to_lowercase(),
-            ImageType::All => ImageType::All.to_str().to_lowercase(),
-            ImageType::Illustration => ImageType::Illustration.to_str().to_lowercase(),
-            ImageType::Vector => ImageType::Vector.to_str().to_lowercase(),
-        };
-
-        (QUERY_TYPE, im_type)
-    } else {
-        ("", "".to_string())
-    };
-
-    // https://pixabay.com/api/docs/#api_search_images
-    let res = reqwest::Client::new()
-        .get("https://pixabay.com/api/")
-        .query(&[("q", query)])
-        .query(&[image_type])
-        .query(&[("safesearch", "true")])
-        .query(&[("key", key)])
-        .query(&[("per_page", "200")])
-        .send()
-        .await?
-        .error_for_status()?
-        .json::<ImagesResponse>()
+    let query = normalize_query(query);
+    let cache_image_type = image_type.map_or(-1, |it| it as i16);
+
+    let cached = sqlx::query!(
+        r#"
+select results as "results: Json<Vec<WebImageSearchItem>>"
+from web_image_search_cache
+where query = $1
+  and image_type = $2
+  and created_at > now() - make_interval(hours => $3)
+"#,
+        &query,
+        cache_image_type,
+        CACHE_TTL_HOURS,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    if let Some(cached) = cached {
+        return Ok(WebImageSearchResponse {
+            images: cached.results.0,
+        });
+    }
+
+    let results = join_all(
+        providers
+            .iter()
+            .map(|provider| provider.search(&query, image_type)),
+    )
+    .await;
+
+    let mut failed = false;
+    let results: Vec<_> = providers
+        .iter()
+        .zip(results)
+        .filter_map(|(provider, result)| match result {
+            Ok(images) => Some(images),
+            Err(err) => {
+                log::warn!("Searching {} for images failed: {:?}", provider.name(), err);
+                failed = true;
+                None
+            }
+        })
+        .collect();
+
+    let images = merge(results);
+
+    // a provider failing is likely temporary, so its missing results aren't cached.
+    if !failed {
+        let mut txn = db.begin().await?;
+
+        sqlx::query!(
+            "delete from web_image_search_cache where created_at < now() - make_interval(hours => $1)",
+            CACHE_TTL_HOURS,
+        )
+        .execute(&mut txn)
         .await?;
 
-    let res = WebImageSearchResponse {
-        images: res
-            .hits
-            .into_iter()
-            .map(|it| WebImageSearchItem {
-                thumbnail_url: it.thumbnail_url,
-                url: it.content_url,
-            })
-            .collect(),
-    };
-
-    Ok(res)
+        sqlx::query!(
+            r#"
+insert into web_image_search_cache (query, image_type, results)
+values ($1, $2, $3)
+on conflict (query, image_type) do update
+set results = excluded.results,
+    created_at = now()
+"#,
+            &query,
+            cache_image_type,
+            serde_json::to_value(&images)?,
+        )
+        .execute(&mut txn)
+        .await?;
+
+        txn.commit().await?;
+    }
+
+    Ok(WebImageSearchResponse { images })
+}
+
+fn normalize_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Interleaves the providers' results, so that the first page has images from each of them, and
+/// drops images which were already found.
+fn merge(results: Vec<Vec<WebImageSearchItem>>) -> Vec<WebImageSearchItem> {
+    let mut seen = HashSet::new();
+    let mut images = Vec::new();
+
+    let mut results: Vec<_> = results.into_iter().map(Vec::into_iter).collect();
+
+    loop {
+        let mut found_any = false;
+
+        for images_of_provider in &mut results {
+            let Some(image) = images_of_provider.next() else {
+                continue;
+            };
+
+            found_any = true;
+
+            let new_url = seen.insert(image.url.clone());
+            let new_thumbnail = seen.insert(image.thumbnail_url.clone());
+
+            if new_url && new_thumbnail {
+                images.push(image);
+            }
+
+            if images.len() == MAX_RESULTS {
+                return images;
+            }
+        }
+
+        if !found_any {
+            return images;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::domain::search::{WebImageAttribution, WebImageSource};
+
+    use super::*;
+
+    fn image(source: WebImageSource, name: &str) -> WebImageSearchItem {
+        WebImageSearchItem {
+            thumbnail_url: format!("https://example.com/{name}_thumb.jpg")
+                .parse()
+                .unwrap(),
+            url: format!("https://example.com/{name}.jpg").parse().unwrap(),
+            source,
+            attribution: WebImageAttribution::default(),
+        }
+    }
+
+    #[test]
+    fn merges_results() {
+        let merged = merge(vec![
+            vec![
+                image(WebImageSource::Pixabay, "a"),
+                image(WebImageSource::Pixabay, "b"),
+                image(WebImageSource::Pixabay, "c"),
+            ],
+            vec![
+                image(WebImageSource::Openverse, "b"),
+                image(WebImageSource::Openverse, "d"),
+            ],
+            vec![],
+        ]);
+
+        let merged: Vec<_> = merged
+            .iter()
+            .map(|image| (image.source, image.url.path()))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (WebImageSource::Pixabay, "/a.jpg"),
+                (WebImageSource::Openverse, "/b.jpg"),
+                (WebImageSource::Openverse, "/d.jpg"),
+                (WebImageSource::Pixabay, "/c.jpg"),
+            ],
+            merged
+        );
+    }
+
+    #[test]
+    fn drops_images_sharing_a_thumbnail() {
+        // `a` again, with another thumbnail.
+        let mut resized = image(WebImageSource::Openverse, "a");
+        resized.thumbnail_url = "https://example.com/a_small.jpg".parse().unwrap();
+
+        // Another image which only shares that thumbnail.
+        let mut copy = image(WebImageSource::Unsplash, "a_copy");
+        copy.thumbnail_url = "https://example.com/a_small.jpg".parse().unwrap();
+
+        let merged = merge(vec![
+            vec![
+                image(WebImageSource::Pixabay, "a"),
+                image(WebImageSource::Pixabay, "b"),
+            ],
+            vec![resized],
+            vec![copy],
+        ]);
+
+        let merged: Vec<_> = merged.iter().map(|image| image.url.path()).collect();
+
+        assert_eq!(vec!["/a.jpg", "/b.jpg"], merged);
+    }
+
+    #[test]
+    fn normalizes_queries() {
+        assert_eq!("red apple", normalize_query("  Red   APPLE "));
+    }
 }
@@ -0,0 +1,131 @@
+//! [Openverse](https://api.openverse.org/v1/#tag/images), openly licensed images from across the
+//! web. Searching doesn't need a key.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use shared::domain::search::{ImageType, WebImageAttribution, WebImageSearchItem, WebImageSource};
+use url::Url;
+
+use super::{ImageSearchProvider, PER_PROVIDER};
+
+#[derive(Debug, Deserialize)]
+struct Image {
+    url: Url,
+    thumbnail: Option<Url>,
+    title: Option<String>,
+    creator: Option<String>,
+    creator_url: Option<Url>,
+    license: String,
+    license_version: Option<String>,
+    license_url: Option<Url>,
+    foreign_landing_url: Option<Url>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImagesResponse {
+    results: Vec<Image>,
+}
+
+pub struct Openverse;
+
+/// The category of images to search for, which Openverse has fewer of than [`ImageType`].
+fn category(image_type: ImageType) -> Option<&'static str> {
+    match image_type {
+        ImageType::Photo => Some("photograph"),
+        ImageType::Clipart | ImageType::Illustration | ImageType::Vector | ImageType::Line => {
+            Some("illustration")
+        }
+        ImageType::AnimatedGif | ImageType::Transparent | ImageType::All => None,
+    }
+}
+
+/// Name of a license as Openverse gives it, e.g. `by-sa` and `2.0` is `CC BY-SA 2.0`.
+fn license_name(license: &str, version: Option<&str>) -> String {
+    let name = match license {
+        "cc0" => "CC0".to_owned(),
+        "pdm" => "Public Domain Mark".to_owned(),
+        license => format!("CC {}", license.to_uppercase()),
+    };
+
+    match version {
+        Some(version) => format!("{name} {version}"),
+        None => name,
+    }
+}
+
+/// Whether images under `license` have to be credited, which is every license but the public domain
+/// ones.
+fn requires_attribution(license: &str) -> bool {
+    !matches!(license, "cc0" | "pdm")
+}
+
+#[async_trait]
+impl ImageSearchProvider for Openverse {
+    fn name(&self) -> &'static str {
+        "Openverse"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        image_type: Option<ImageType>,
+    ) -> anyhow::Result<Vec<WebImageSearchItem>> {
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut request = reqwest::Client::new()
+            .get("https://api.openverse.org/v1/images/")
+            .query(&[("q", query), ("mature", "false")])
+            .query(&[("page_size", PER_PROVIDER)]);
+
+        if let Some(category) = image_type.and_then(category) {
+            request = request.query(&[("category", category)]);
+        }
+
+        if image_type == Some(ImageType::AnimatedGif) {
+            request = request.query(&[("extension", "gif")]);
+        }
+
+        let res = request
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ImagesResponse>()
+            .await?;
+
+        Ok(res
+            .results
+            .into_iter()
+            .map(|it| WebImageSearchItem {
+                attribution: WebImageAttribution {
+                    title: it.title,
+                    creator: it.creator,
+                    creator_url: it.creator_url,
+                    license: license_name(&it.license, it.license_version.as_deref()),
+                    license_url: it.license_url,
+                    source_url: it.foreign_landing_url,
+                    required: requires_attribution(&it.license),
+                },
+                thumbnail_url: it.thumbnail.unwrap_or_else(|| it.url.clone()),
+                url: it.url,
+                source: WebImageSource::Openverse,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_licenses() {
+        assert_eq!("CC BY-SA 2.0", license_name("by-sa", Some("2.0")));
+        assert_eq!("CC0 1.0", license_name("cc0", Some("1.0")));
+        assert_eq!("Public Domain Mark", license_name("pdm", None));
+
+        assert!(requires_attribution("by-nc"));
+        assert!(!requires_attribution("cc0"));
+    }
+}
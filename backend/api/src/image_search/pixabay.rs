@@ -0,0 +1,105 @@
+//! [Pixabay](https://pixabay.com/api/docs/#api_search_images), whose images can be used without
+//! crediting them.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use shared::domain::search::{ImageType, WebImageAttribution, WebImageSearchItem, WebImageSource};
+use url::Url;
+
+use super::{ImageSearchProvider, PER_PROVIDER};
+
+const QUERY_TYPE: &str = "image_type";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Image {
+    #[serde(rename(deserialize = "previewURL"))]
+    thumbnail_url: Url,
+    #[serde(rename(deserialize = "largeImageURL"))]
+    content_url: Url,
+    #[serde(rename(deserialize = "pageURL"))]
+    page_url: Option<Url>,
+    user: Option<String>,
+    user_id: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImagesResponse {
+    hits: Vec<Image>,
+}
+
+pub struct Pixabay {
+    key: String,
+    client: reqwest::Client,
+}
+
+impl Pixabay {
+    pub fn new(key: String) -> Self {
+        Self {
+            key,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl ImageSearchProvider for Pixabay {
+    fn name(&self) -> &'static str {
+        "Pixabay"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        image_type: Option<ImageType>,
+    ) -> anyhow::Result<Vec<WebImageSearchItem>> {
+        // returns tuple if there is a valid user input,
+        // otherwise empty query for image type field
+        let image_type: (&str, String) = if let Some(image) = image_type {
+            (QUERY_TYPE, image.to_str().to_lowercase())
+        } else {
+            ("", "".to_string())
+        };
+
+        let res = self
+            .client
+            .get("https://pixabay.com/api/")
+            .query(&[("q", query)])
+            .query(&[image_type])
+            .query(&[("safesearch", "true")])
+            .query(&[("key", &self.key)])
+            .query(&[("per_page", PER_PROVIDER)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<ImagesResponse>()
+            .await?;
+
+        Ok(res
+            .hits
+            .into_iter()
+            .map(|it| WebImageSearchItem {
+                attribution: WebImageAttribution {
+                    title: None,
+                    creator_url: match (&it.user, it.user_id) {
+                        (Some(user), Some(user_id)) => {
+                            format!("https://pixabay.com/users/{user}-{user_id}/")
+                                .parse()
+                                .ok()
+                        }
+                        _ => None,
+                    },
+                    creator: it.user,
+                    license: "Pixabay Content License".to_owned(),
+                    license_url: "https://pixabay.com/service/license-summary/".parse().ok(),
+                    source_url: it.page_url,
+                    required: false,
+                },
+                thumbnail_url: it.thumbnail_url,
+                url: it.content_url,
+                source: WebImageSource::Pixabay,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,105 @@
+//! [Unsplash](https://unsplash.com/documentation#search-photos), which only has photos. Its
+//! guidelines ask for photos to be credited to their photographer.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use shared::domain::search::{ImageType, WebImageAttribution, WebImageSearchItem, WebImageSource};
+use url::Url;
+
+use super::{ImageSearchProvider, PER_PROVIDER};
+
+#[derive(Debug, Deserialize)]
+struct Urls {
+    regular: Url,
+    thumb: Url,
+}
+
+#[derive(Debug, Deserialize)]
+struct Links {
+    html: Option<Url>,
+}
+
+#[derive(Debug, Deserialize)]
+struct User {
+    name: Option<String>,
+    links: Links,
+}
+
+#[derive(Debug, Deserialize)]
+struct Photo {
+    urls: Urls,
+    links: Links,
+    user: User,
+    description: Option<String>,
+    alt_description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PhotosResponse {
+    results: Vec<Photo>,
+}
+
+pub struct Unsplash {
+    access_key: String,
+    client: reqwest::Client,
+}
+
+impl Unsplash {
+    pub fn new(access_key: String) -> Self {
+        Self {
+            access_key,
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl ImageSearchProvider for Unsplash {
+    fn name(&self) -> &'static str {
+        "Unsplash"
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        image_type: Option<ImageType>,
+    ) -> anyhow::Result<Vec<WebImageSearchItem>> {
+        if !matches!(image_type, None | Some(ImageType::All | ImageType::Photo)) || query.is_empty()
+        {
+            return Ok(Vec::new());
+        }
+
+        // at most 30 photos can be asked for at a time.
+        let res = self
+            .client
+            .get("https://api.unsplash.com/search/photos")
+            .header("Authorization", format!("Client-ID {}", self.access_key))
+            .header("Accept-Version", "v1")
+            .query(&[("query", query), ("content_filter", "high")])
+            .query(&[("per_page", PER_PROVIDER.min(30))])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<PhotosResponse>()
+            .await?;
+
+        Ok(res
+            .results
+            .into_iter()
+            .map(|it| WebImageSearchItem {
+                thumbnail_url: it.urls.thumb,
+                url: it.urls.regular,
+                source: WebImageSource::Unsplash,
+                attribution: WebImageAttribution {
+                    title: it.description.or(it.alt_description),
+                    creator: it.user.name,
+                    creator_url: it.user.links.html,
+                    license: "Unsplash License".to_owned(),
+                    license_url: "https://unsplash.com/license".parse().ok(),
+                    source_url: it.links.html,
+                    required: true,
+                },
+            })
+            .collect())
+    }
+}
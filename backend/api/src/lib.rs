@@ -31,19 +31,25 @@
 )]
 
 pub mod algolia;
+mod audio_ops;
+mod child_privacy;
+pub mod content_safety;
 pub mod db;
 mod domain;
 pub mod error;
 mod extractor;
+mod fluent;
 pub mod google_oauth;
 pub mod http;
 mod image_ops;
 pub mod image_search;
 pub mod jwk;
 pub mod logger;
+mod media_type;
 pub(crate) mod more_futures;
+mod pdf_ops;
 pub mod service;
-pub mod stripe;
+mod temp_dir;
 pub mod token;
 pub mod translate;
 pub mod trending;
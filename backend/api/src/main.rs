@@ -11,10 +11,10 @@
 #![warn(clippy::use_self)]
 #![warn(clippy::useless_let_if_seq)]
 
-use std::thread;
+use std::{sync::Arc, thread};
 
 use anyhow::Context;
-use ji_cloud_api::{algolia, db, http, jwk, logger, service, translate};
+use ji_cloud_api::{algolia, content_safety, db, http, jwk, logger, service, translate};
 use ji_core::{
     env::env_bool,
     settings::{self, SettingsManager},
@@ -38,7 +38,9 @@ async fn main() -> anyhow::Result<()> {
         jwk_verifier,
         mail_client,
         media_upload_cleaner,
-        google_translate,
+        translator,
+        payment_provider,
+        content_safety,
         // _guard,
     ) = {
         log::trace!("initializing settings and processes");
@@ -106,17 +108,35 @@ async fn main() -> anyhow::Result<()> {
         let media_upload_cleaner =
             service::upload::cleaner::UploadCleaner::new(db_pool.clone(), db::UPLOADS_DB_SCHEMA)?;
 
-        let google_translate = translate::GoogleTranslate::new(db_pool.clone(), &runtime_settings)?;
+        let translator = translate::Translator::new(db_pool.clone(), &runtime_settings)?;
 
         let jwk_verifier =
             jwk::create_verifier(settings.jwk_audience_settings(&runtime_settings).await?);
 
         let _ = jwk::run_task(jwk_verifier.clone());
 
-        let mail_client = settings
-            .email_client_settings()
-            .await?
-            .map(service::mail::Client::new);
+        let payment_provider = runtime_settings.stripe_secret_key.as_deref().map(|key| {
+            Arc::new(service::payment::stripe::StripePaymentProvider::new(key))
+                as Arc<dyn service::payment::PaymentProvider>
+        });
+
+        // no image classifier is configured yet, text is still checked
+        let content_safety = Arc::new(content_safety::ContentSafety::new(Arc::new(
+            content_safety::image::NoopImageClassifier,
+        )));
+
+        let mail_client = match settings.email_client_settings().await? {
+            Some(email_settings) => Some(service::mail::Client::new(email_settings)),
+            // without SendGrid, local emails are rendered and logged so the flows sending them still work
+            None if runtime_settings.is_local() => Some(service::mail::Client::local(
+                "local@jigzi.org".to_owned(),
+                runtime_settings
+                    .remote_target()
+                    .jigzi_info_email()
+                    .to_string(),
+            )),
+            None => None,
+        };
 
         (
             runtime_settings,
@@ -129,7 +149,9 @@ async fn main() -> anyhow::Result<()> {
             jwk_verifier,
             mail_client,
             media_upload_cleaner,
-            google_translate,
+            translator,
+            payment_provider,
+            content_safety,
             // guard,
         )
     };
@@ -146,7 +168,9 @@ async fn main() -> anyhow::Result<()> {
             mail_client,
             algolia_manager,
             media_upload_cleaner,
-            google_translate,
+            translator,
+            payment_provider,
+            content_safety,
         )
     });
 
@@ -0,0 +1,286 @@
+//! Detects the type of uploaded media from its contents, rather than trusting the client.
+
+use std::fmt;
+
+use ji_core::config::{AUDIO_MAX_DURATION_SECS, IMAGE_MAX_DIMENSION, IMAGE_MAX_PIXELS};
+
+/// How many bytes of a text file to search for an `<svg` tag.
+const SVG_SNIFF_LEN: usize = 4096;
+
+/// Major brands of ISO base media files (`ftyp` boxes) which hold MPEG-4 audio.
+///
+/// HEIF and AVIF images are ISO base media files too, but have their own brands (`heic`, `mif1`,
+/// `avif`, ...).
+const M4A_BRANDS: &[[u8; 4]] = &[
+    *b"M4A ", *b"M4B ", *b"mp41", *b"mp42", *b"isom", *b"iso2", *b"iso5", *b"dash", *b"3gp4",
+    *b"3gp5",
+];
+
+/// Types of media that can be recognized by their leading bytes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MediaType {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Svg,
+    Mp3,
+    Wav,
+    Ogg,
+    Webm,
+    M4a,
+    Pdf,
+}
+
+impl MediaType {
+    /// Still or animated images which can be decoded as raster images.
+    pub const IMAGES: &'static [Self] = &[Self::Png, Self::Jpeg, Self::Gif, Self::Webp];
+
+    /// Everything that can be uploaded as an image: the raster [`IMAGES`](Self::IMAGES) and SVG.
+    pub const IMAGE_UPLOADS: &'static [Self] =
+        &[Self::Png, Self::Jpeg, Self::Gif, Self::Webp, Self::Svg];
+
+    /// Animations, which are stored as uploaded.
+    pub const ANIMATIONS: &'static [Self] = &[Self::Gif];
+
+    /// Audio formats that can be decoded and transcoded.
+    pub const AUDIO: &'static [Self] = &[Self::Mp3, Self::Wav, Self::Ogg, Self::Webm, Self::M4a];
+
+    /// Document formats that can be stored.
+    pub const DOCUMENTS: &'static [Self] = &[Self::Pdf];
+
+    /// Detects the type of `data` from its magic bytes.
+    ///
+    /// Returns `None` if the type isn't recognized.
+    pub fn sniff(data: &[u8]) -> Option<Self> {
+        match data {
+            [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n', ..] => Some(Self::Png),
+            [0xFF, 0xD8, 0xFF, ..] => Some(Self::Jpeg),
+            [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some(Self::Gif),
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some(Self::Webp),
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => Some(Self::Wav),
+            [b'O', b'g', b'g', b'S', ..] => Some(Self::Ogg),
+            [0x1A, 0x45, 0xDF, 0xA3, ..] => Some(Self::Webm),
+            [_, _, _, _, b'f', b't', b'y', b'p', a, b, c, d, ..]
+                if M4A_BRANDS.contains(&[*a, *b, *c, *d]) =>
+            {
+                Some(Self::M4a)
+            }
+            [b'%', b'P', b'D', b'F', b'-', ..] => Some(Self::Pdf),
+            [b'I', b'D', b'3', ..] => Some(Self::Mp3),
+            [first, second, ..] if is_mp3_frame_sync(*first, *second) => Some(Self::Mp3),
+            _ if is_svg(data) => Some(Self::Svg),
+            _ => None,
+        }
+    }
+
+    /// Detects the type of `data`, and checks that it's one of `allowed`.
+    pub fn expect(data: &[u8], allowed: &'static [Self]) -> Result<Self, MediaError> {
+        match Self::sniff(data) {
+            Some(found) if allowed.contains(&found) => Ok(found),
+            found => Err(MediaError::Unsupported { found, allowed }),
+        }
+    }
+
+    /// The [`image::ImageFormat`] to decode this type with, if it's a raster image.
+    pub const fn image_format(self) -> Option<image::ImageFormat> {
+        match self {
+            Self::Png => Some(image::ImageFormat::Png),
+            Self::Jpeg => Some(image::ImageFormat::Jpeg),
+            Self::Gif => Some(image::ImageFormat::Gif),
+            Self::Webp => Some(image::ImageFormat::WebP),
+            _ => None,
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+            Self::Gif => "GIF",
+            Self::Webp => "WebP",
+            Self::Svg => "SVG",
+            Self::Mp3 => "MP3",
+            Self::Wav => "WAV",
+            Self::Ogg => "Ogg",
+            Self::Webm => "WebM",
+            Self::M4a => "M4A",
+            Self::Pdf => "PDF",
+        }
+    }
+}
+
+impl fmt::Display for MediaType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// An MPEG audio frame header starts with 11 set bits, and layer `00` is reserved.
+///
+/// This also rules out ADTS (AAC) streams, which always use layer `00`.
+const fn is_mp3_frame_sync(first: u8, second: u8) -> bool {
+    first == 0xFF && second & 0xE0 == 0xE0 && second & 0x06 != 0
+}
+
+fn is_svg(data: &[u8]) -> bool {
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    let start = match data.iter().position(|b| !b.is_ascii_whitespace()) {
+        Some(start) => start,
+        None => return false,
+    };
+
+    if data[start] != b'<' {
+        return false;
+    }
+
+    let head = &data[start..data.len().min(start + SVG_SNIFF_LEN)];
+    head.windows(4).any(|window| window == b"<svg")
+}
+
+/// Checks the dimensions of an image (from its header) before it gets decoded.
+pub fn check_image_dimensions(width: u32, height: u32) -> Result<(), MediaError> {
+    if width == 0 || height == 0 {
+        return Err(MediaError::Malformed);
+    }
+
+    if width > IMAGE_MAX_DIMENSION
+        || height > IMAGE_MAX_DIMENSION
+        || u64::from(width) * u64::from(height) > IMAGE_MAX_PIXELS
+    {
+        return Err(MediaError::TooLarge { width, height });
+    }
+
+    Ok(())
+}
+
+/// Reasons uploaded media gets rejected.
+#[derive(Debug)]
+pub enum MediaError {
+    /// The file isn't one of the types allowed for the upload.
+    Unsupported {
+        /// The type the file was detected as, if any.
+        found: Option<MediaType>,
+        allowed: &'static [MediaType],
+    },
+
+    /// The image is larger than [`IMAGE_MAX_DIMENSION`] or [`IMAGE_MAX_PIXELS`].
+    TooLarge { width: u32, height: u32 },
+
+    /// The audio is longer than [`AUDIO_MAX_DURATION_SECS`].
+    AudioTooLong,
+
+    /// The document can't be opened without a password.
+    Encrypted,
+
+    /// The file has the magic bytes of an allowed type, but can't be decoded.
+    Malformed,
+}
+
+impl fmt::Display for MediaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported { found, allowed } => {
+                let allowed: Vec<_> = allowed.iter().map(|it| it.name()).collect();
+
+                match found {
+                    Some(found) => write!(f, "Unsupported file type {}", found)?,
+                    None => f.write_str("Unrecognized file type")?,
+                }
+
+                write!(f, ", expected {}", allowed.join(", "))
+            }
+
+            Self::TooLarge { width, height } => write!(
+                f,
+                "Image is {}x{} pixels, the limit is {} pixels per side and {} pixels in total",
+                width, height, IMAGE_MAX_DIMENSION, IMAGE_MAX_PIXELS
+            ),
+
+            Self::AudioTooLong => write!(
+                f,
+                "Audio is longer than the limit of {} minutes",
+                AUDIO_MAX_DURATION_SECS / 60
+            ),
+
+            Self::Encrypted => f.write_str("Document is password protected"),
+
+            Self::Malformed => f.write_str("File is corrupt or truncated"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniff_fixtures() {
+        let png = include_bytes!("../fixtures/images/city-wide.png");
+        assert_eq!(Some(MediaType::Png), MediaType::sniff(png));
+    }
+
+    #[test]
+    fn sniff_magic_bytes() {
+        let cases: &[(&[u8], Option<MediaType>)] = &[
+            (b"\xFF\xD8\xFF\xE0\x00\x10JFIF", Some(MediaType::Jpeg)),
+            (b"GIF89a\x01\x00\x01\x00", Some(MediaType::Gif)),
+            (b"GIF87a\x01\x00\x01\x00", Some(MediaType::Gif)),
+            (b"RIFF\x24\x00\x00\x00WEBPVP8 ", Some(MediaType::Webp)),
+            (b"RIFF\x24\x00\x00\x00WAVEfmt ", Some(MediaType::Wav)),
+            (b"OggS\x00\x02", Some(MediaType::Ogg)),
+            (b"\x1A\x45\xDF\xA3\x9F\x42\x86\x81", Some(MediaType::Webm)),
+            (b"\x00\x00\x00\x1CftypM4A ", Some(MediaType::M4a)),
+            (b"\x00\x00\x00\x20ftypisom", Some(MediaType::M4a)),
+            (b"\x00\x00\x00\x18ftypheic", None),
+            (b"\x00\x00\x00\x1CftypavifmA1B", None),
+            (b"\x00\x00\x00\x1Cftypmif1", None),
+            (b"%PDF-1.7\n", Some(MediaType::Pdf)),
+            (b"ID3\x04\x00\x00\x00\x00\x00\x00", Some(MediaType::Mp3)),
+            (b"\xFF\xFB\x90\x64", Some(MediaType::Mp3)),
+            // ADTS (AAC)
+            (b"\xFF\xF1\x50\x80", None),
+            (
+                b"\xEF\xBB\xBF\n<?xml version=\"1.0\"?>\n<svg xmlns=\"http://www.w3.org/2000/svg\"/>",
+                Some(MediaType::Svg),
+            ),
+            (b"<html><body></body></html>", None),
+            (b"hello <svg>", None),
+            (b"", None),
+        ];
+
+        for (data, expected) in cases {
+            assert_eq!(*expected, MediaType::sniff(data), "{:?}", data);
+        }
+    }
+
+    #[test]
+    fn expect_rejects_other_types() {
+        let err = MediaType::expect(b"%PDF-1.7\n", MediaType::IMAGES).unwrap_err();
+
+        assert!(matches!(
+            err,
+            MediaError::Unsupported {
+                found: Some(MediaType::Pdf),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn dimension_limits() {
+        assert!(check_image_dimensions(1920, 1080).is_ok());
+        assert!(matches!(
+            check_image_dimensions(IMAGE_MAX_DIMENSION + 1, 1),
+            Err(MediaError::TooLarge { .. })
+        ));
+        assert!(matches!(
+            check_image_dimensions(8000, 8000),
+            Err(MediaError::TooLarge { .. })
+        ));
+        assert!(matches!(
+            check_image_dimensions(0, 10),
+            Err(MediaError::Malformed)
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+//! PDF processing, done by running the `poppler` tools (`pdfinfo`, `pdftoppm` and `pdftotext`).
+
+use std::{
+    path::Path,
+    process::{Output, Stdio},
+    time::Duration,
+};
+
+use anyhow::Context;
+use ji_core::config::{PDF_MAX_PREVIEW_PAGES, PDF_MAX_TEXT_LEN};
+use tokio::{process::Command, sync::Semaphore};
+
+use crate::{
+    error,
+    media_type::{MediaError, MediaType},
+    temp_dir::TempDir,
+};
+
+/// How long each tool gets to run, so that a hostile document can't tie up the server.
+const TOOL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Most uploads processed at once. Rendering a large document takes a core and plenty of memory,
+/// so further uploads wait their turn.
+const MAX_CONCURRENT_PROCESSING: usize = 4;
+
+static PROCESSING_PERMITS: Semaphore = Semaphore::const_new(MAX_CONCURRENT_PROCESSING);
+
+/// Page previews are scaled to fit in a square this many pixels wide.
+const PREVIEW_SIZE: u32 = 480;
+
+/// The previews and metadata stored for an uploaded PDF.
+pub struct ProcessedPdf {
+    pub page_count: u32,
+    /// JPEG previews of up to [`PDF_MAX_PREVIEW_PAGES`] pages, starting from the first.
+    pub previews: Vec<Vec<u8>>,
+    /// The text of every page, with whitespace collapsed, for search.
+    pub text: String,
+}
+
+/// Counts the pages of an uploaded PDF, renders previews of the first pages and extracts its text.
+pub async fn process_pdf(data: &[u8]) -> Result<ProcessedPdf, error::Upload> {
+    MediaType::expect(data, MediaType::DOCUMENTS)?;
+
+    let _permit = PROCESSING_PERMITS
+        .acquire()
+        .await
+        .context("pdf processing semaphore closed")?;
+
+    let dir = TempDir::new("ji-pdf").await?;
+    let input = dir.path.join("document.pdf");
+    tokio::fs::write(&input, data).await?;
+
+    let page_count = page_count(&input).await?;
+    let previews =
+        render_previews(&input, &dir.path, page_count.min(PDF_MAX_PREVIEW_PAGES)).await?;
+    let text = extract_text(&input).await?;
+
+    Ok(ProcessedPdf {
+        page_count,
+        previews,
+        text,
+    })
+}
+
+/// Runs `command`, treating a hang as a malformed document.
+async fn run(command: &mut Command) -> Result<Output, error::Upload> {
+    match try_run(command).await? {
+        Some(output) => Ok(output),
+        None => {
+            log::debug!("poppler timed out processing a pdf");
+            Err(MediaError::Malformed.into())
+        }
+    }
+}
+
+/// Runs `command`, `None` if it didn't finish within [`TOOL_TIMEOUT`].
+async fn try_run(command: &mut Command) -> anyhow::Result<Option<Output>> {
+    let output = command.stdin(Stdio::null()).kill_on_drop(true).output();
+
+    match tokio::time::timeout(TOOL_TIMEOUT, output).await {
+        Ok(output) => Ok(Some(output.context("failed to run poppler")?)),
+        Err(_) => Ok(None),
+    }
+}
+
+async fn page_count(input: &Path) -> Result<u32, error::Upload> {
+    let output = run(Command::new("pdfinfo").arg(input)).await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        log::debug!("pdfinfo couldn't read pdf: {}", stderr);
+
+        return Err(if stderr.contains("password") {
+            MediaError::Encrypted
+        } else {
+            MediaError::Malformed
+        }
+        .into());
+    }
+
+    match parse_page_count(&String::from_utf8_lossy(&output.stdout)) {
+        Some(count) if count > 0 => Ok(count),
+        _ => Err(MediaError::Malformed.into()),
+    }
+}
+
+/// Renders pages `1..=pages` as JPEGs into `dir`, and reads them back in order.
+async fn render_previews(
+    input: &Path,
+    dir: &Path,
+    pages: u32,
+) -> Result<Vec<Vec<u8>>, error::Upload> {
+    let last = pages.to_string();
+    let size = PREVIEW_SIZE.to_string();
+
+    let output = run(Command::new("pdftoppm")
+        .args([
+            "-jpeg",
+            "-jpegopt",
+            "quality=80",
+            "-scale-to",
+            size.as_str(),
+        ])
+        .args(["-f", "1", "-l", last.as_str()])
+        .arg(input)
+        .arg(dir.join("page")))
+    .await?;
+
+    if !output.status.success() {
+        log::debug!(
+            "pdftoppm couldn't render pdf: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return Err(MediaError::Malformed.into());
+    }
+
+    // pdftoppm pads page numbers to the width of the last one, so they get parsed rather than built
+    let mut rendered = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if let Some(page) = entry.file_name().to_str().and_then(preview_page_number) {
+            rendered.push((page, entry.path()));
+        }
+    }
+    rendered.sort_unstable();
+
+    let mut previews = Vec::with_capacity(rendered.len());
+    for (expected, (page, path)) in (1..).zip(rendered) {
+        // previews are served by page number, so there can't be gaps
+        if page != expected {
+            break;
+        }
+
+        previews.push(tokio::fs::read(path).await?);
+    }
+
+    Ok(previews)
+}
+
+/// Extracts the text of every page, failing soft since the document already rendered fine.
+async fn extract_text(input: &Path) -> Result<String, error::Upload> {
+    let output = match try_run(
+        Command::new("pdftotext")
+            .args(["-q", "-enc", "UTF-8", "-nopgbrk"])
+            .arg(input)
+            .arg("-"),
+    )
+    .await?
+    {
+        Some(output) => output,
+        None => {
+            log::warn!("pdftotext timed out extracting text");
+            return Ok(String::new());
+        }
+    };
+
+    if !output.status.success() {
+        log::warn!(
+            "pdftotext failed to extract text: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return Ok(String::new());
+    }
+
+    Ok(normalize_text(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_page_count(info: &str) -> Option<u32> {
+    info.lines()
+        .find_map(|line| line.strip_prefix("Pages:"))
+        .and_then(|count| count.trim().parse().ok())
+}
+
+/// The page number of a file written by `pdftoppm`, such as `page-07.jpg`.
+fn preview_page_number(file_name: &str) -> Option<u32> {
+    file_name
+        .strip_prefix("page-")?
+        .strip_suffix(".jpg")?
+        .parse()
+        .ok()
+}
+
+/// Collapses whitespace and control characters into single spaces, and truncates the text to
+/// [`PDF_MAX_TEXT_LEN`] bytes.
+fn normalize_text(raw: &str) -> String {
+    let mut text = String::new();
+
+    for word in raw.split(|c: char| c.is_whitespace() || c.is_control()) {
+        if word.is_empty() {
+            continue;
+        }
+
+        let separator = usize::from(!text.is_empty());
+        if text.len() + separator + word.len() > PDF_MAX_TEXT_LEN {
+            break;
+        }
+
+        if separator == 1 {
+            text.push(' ');
+        }
+        text.push_str(word);
+    }
+
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pdfinfo_pages() {
+        let info = "Producer:       LibreOffice 7.3\nTagged:         no\nPages:          12\nEncrypted:      no\n";
+
+        assert_eq!(Some(12), parse_page_count(info));
+        assert_eq!(None, parse_page_count("Encrypted:      no\n"));
+    }
+
+    #[test]
+    fn parses_preview_file_names() {
+        assert_eq!(Some(1), preview_page_number("page-1.jpg"));
+        assert_eq!(Some(7), preview_page_number("page-07.jpg"));
+        assert_eq!(None, preview_page_number("document.pdf"));
+        assert_eq!(None, preview_page_number("page-x.jpg"));
+    }
+
+    #[test]
+    fn normalizes_text() {
+        assert_eq!(
+            "Chapter 1 Shalom, world",
+            normalize_text("  Chapter 1\n\n\x0cShalom,\t\x00world \r\n")
+        );
+        assert_eq!("", normalize_text(" \n "));
+    }
+
+    #[test]
+    fn truncates_text_between_words() {
+        let word = "שלום".repeat(100);
+        let raw = vec![word.as_str(); PDF_MAX_TEXT_LEN / word.len() + 2].join(" ");
+
+        let text = normalize_text(&raw);
+
+        assert!(text.len() <= PDF_MAX_TEXT_LEN);
+        assert!(text.ends_with(&word));
+    }
+}
@@ -11,10 +11,11 @@ use crate::translate;
 use ji_core::google::GoogleAccessTokenResponse;
 use shared::error::{ServiceError, ServiceKindError};
 
-use self::translate::GoogleTranslate;
+use self::translate::Translator;
 use self::upload::cleaner::UploadCleaner;
 
 pub mod mail;
+pub mod payment;
 pub mod s3;
 pub mod storage;
 pub mod upload;
@@ -49,8 +50,8 @@ impl Service for UploadCleaner {
     const DISABLED_ERROR: ServiceKindError = ServiceKindError::UploadCleaner;
 }
 
-impl Service for GoogleTranslate {
-    const DISABLED_ERROR: ServiceKindError = ServiceKindError::GoogleTranslate;
+impl Service for Translator {
+    const DISABLED_ERROR: ServiceKindError = ServiceKindError::Translate;
 }
 #[derive(Debug)]
 pub struct ServiceData<T: ?Sized>(Arc<T>);
@@ -1,285 +1,337 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::Context;
 use ji_core::settings::EmailClientSettings;
 use sendgrid::v3::{Content, Email, Message, Personalization, SGMap, Sender};
-use shared::domain::{
-    jig::report::JigReportEmail, resource::report::ResourceReportEmail, session::OAuthProvider,
-};
+use shared::domain::email::{EmailOutboxId, EmailTemplateKind};
 use shared::error::ServiceKindError;
+use sqlx::{PgConnection, PgPool};
 use tracing::instrument;
 
 use super::Service;
+use crate::db;
 
-const SENDER_NAME: &str = "Jigzi";
+pub mod template;
 
-pub struct Client {
-    client: Sender,
+use template::{EmailTemplate, RenderedEmail, TemplateStrings, DEFAULT_LOCALE};
 
-    sender_email: Email,
+const SENDER_NAME: &str = "Jigzi";
 
-    jigzi_info_email: Email,
+/// How many pending emails are retried per run of the outbox.
+const OUTBOX_BATCH_SIZE: i64 = 50;
 
-    signup_verify_template: Option<String>,
+enum Backend {
+    SendGrid(Sender),
+    /// Logs emails instead of sending them, for running without SendGrid.
+    Local,
+}
+
+/// Sends transactional emails.
+///
+/// Every email is first queued in the outbox, in the same transaction as whatever caused it to be
+/// sent, and then sent once that transaction is committed. Emails which fail to send are retried
+/// by the scheduler.
+pub struct Client {
+    backend: Backend,
 
-    password_reset_template: Option<String>,
+    sender_email: Email,
 
-    email_reset_template: Option<String>,
+    jigzi_info_email: String,
 
-    welcome_jigzi_template: Option<String>,
+    /// SendGrid template IDs by template and locale.
+    templates: HashMap<(EmailTemplateKind, String), String>,
 }
 
 impl Client {
     pub fn new(settings: EmailClientSettings) -> Self {
-        Client {
-            client: Sender::new(settings.api_key),
+        let mut templates = HashMap::new();
+
+        for (kind, template_id) in [
+            (
+                EmailTemplateKind::SignupVerify,
+                settings.signup_verify_template,
+            ),
+            (
+                EmailTemplateKind::PasswordReset,
+                settings.password_reset_template,
+            ),
+            (EmailTemplateKind::EmailReset, settings.email_reset_template),
+            (
+                EmailTemplateKind::WelcomeJigzi,
+                settings.welcome_jigzi_template,
+            ),
+        ] {
+            if let Some(template_id) = template_id {
+                templates.insert((kind, DEFAULT_LOCALE.to_owned()), template_id);
+            }
+        }
+
+        for (key, template_id) in settings.localized_templates {
+            let parsed = key.split_once(':').and_then(|(kind, locale)| {
+                let kind = EmailTemplateKind::from_str(kind).ok()?;
+                let locale = template::locale_for(locale);
+                Some((kind, locale))
+            });
+
+            match parsed {
+                Some(key) => {
+                    templates.insert(key, template_id);
+                }
+                None => log::warn!("Ignoring unknown localized email template `{}`", key),
+            }
+        }
+
+        Self {
+            backend: Backend::SendGrid(Sender::new(settings.api_key)),
             sender_email: Email::new(settings.sender_email).set_name(SENDER_NAME),
-            jigzi_info_email: Email::new(settings.jigzi_info_email),
-            signup_verify_template: settings.signup_verify_template,
-            password_reset_template: settings.password_reset_template,
-            email_reset_template: settings.email_reset_template,
-            welcome_jigzi_template: settings.welcome_jigzi_template,
+            jigzi_info_email: settings.jigzi_info_email,
+            templates,
         }
     }
 
-    #[instrument(skip_all)]
-    pub async fn send_signup_verify(
-        &self,
-        template: SignupVerifyTemplate<'_>,
-        to: Email,
-        link: String,
-    ) -> anyhow::Result<()> {
-        let mut template_data = SGMap::new();
-        template_data.insert("url".to_string(), link);
-
-        let message = Message::new(self.sender_email.clone())
-            .set_template_id(&template.0)
-            .add_personalization(Personalization::new(to).add_dynamic_template_data(template_data));
+    /// A client which renders every email itself and logs it instead of sending it.
+    pub fn local(sender_email: String, jigzi_info_email: String) -> Self {
+        Self {
+            backend: Backend::Local,
+            sender_email: Email::new(sender_email).set_name(SENDER_NAME),
+            jigzi_info_email,
+            templates: HashMap::new(),
+        }
+    }
 
-        self.client.send(&message).await?;
+    /// Address admin alerts, such as reports, are sent to.
+    pub fn jigzi_info_email(&self) -> &str {
+        &self.jigzi_info_email
+    }
 
-        Ok(())
+    /// SendGrid template used for `kind` in `locale`, if there is one.
+    pub fn sendgrid_template(&self, kind: EmailTemplateKind, locale: &str) -> Option<&str> {
+        self.templates
+            .get(&(kind, locale.to_owned()))
+            .map(String::as_str)
     }
 
-    #[instrument(skip_all)]
-    pub async fn send_welcome_jigzi(
+    /// Renders `email` in `locale`, returning the locale it was actually rendered in.
+    ///
+    /// Falls back to English when the template hasn't been fully translated into `locale`.
+    pub async fn render(
         &self,
-        template: WelcomeJigziTemplate<'_>,
-        to: Email,
-        link: String,
-        first_name: String,
-    ) -> anyhow::Result<()> {
-        let mut template_data = SGMap::new();
-        template_data.insert("url".to_string(), link);
-        template_data.insert("firstname".to_string(), first_name);
+        db: &mut PgConnection,
+        email: &EmailTemplate,
+        locale: &str,
+    ) -> anyhow::Result<(String, RenderedEmail)> {
+        let kind = email.kind();
+        let strings = db::email::template_strings(db, kind, locale).await?;
+        let defaults = TemplateStrings::default_for(kind);
+
+        let (locale, subject, body) = match strings.localized {
+            (Some(subject), Some(body)) if locale != DEFAULT_LOCALE => {
+                (locale.to_owned(), subject, body)
+            }
+            _ => {
+                let (subject, body) = strings.english;
+
+                (
+                    DEFAULT_LOCALE.to_owned(),
+                    subject.unwrap_or(defaults.subject),
+                    body.unwrap_or(defaults.body),
+                )
+            }
+        };
+
+        // other parts which haven't been translated are left in English
+        let mut parts = defaults.parts;
+        for (part, (english, localized)) in strings.parts {
+            let text = match locale.as_str() {
+                DEFAULT_LOCALE => english,
+                _ => localized.or(english),
+            };
 
-        let message = Message::new(self.sender_email.clone())
-            .set_template_id(&template.0)
-            .add_personalization(Personalization::new(to).add_dynamic_template_data(template_data));
+            if let Some(text) = text {
+                parts.insert(part, text);
+            }
+        }
 
-        self.client.send(&message).await?;
+        let rendered = email.render(
+            &locale,
+            &TemplateStrings {
+                subject,
+                body,
+                parts,
+            },
+        );
 
-        Ok(())
+        Ok((locale, rendered))
     }
 
-    #[instrument(skip_all)]
-    pub async fn send_password_reset(
+    /// Renders `email` the way it'll be sent to someone in `locale`.
+    ///
+    /// A SendGrid template in the recipient's locale beats a translation we render ourselves, in
+    /// which case the English rendering is only kept in the outbox for auditing.
+    async fn render_for(
         &self,
-        template: PasswordResetTemplate<'_>,
-        to: Email,
-        link: String,
-        first_name: String,
-    ) -> anyhow::Result<()> {
-        let mut template_data = SGMap::new();
-        template_data.insert("url".to_string(), link);
-        template_data.insert("firstname".to_string(), first_name);
-
-        let message = Message::new(self.sender_email.clone())
-            .set_template_id(&template.0)
-            .add_personalization(Personalization::new(to).add_dynamic_template_data(template_data));
-
-        self.client.send(&message).await?;
-
-        Ok(())
+        db: &mut PgConnection,
+        email: &EmailTemplate,
+        locale: &str,
+    ) -> anyhow::Result<(String, RenderedEmail)> {
+        match self.sendgrid_template(email.kind(), locale) {
+            Some(_) => Ok((
+                locale.to_owned(),
+                self.render(db, email, DEFAULT_LOCALE).await?.1,
+            )),
+            None => self.render(db, email, locale).await,
+        }
     }
 
+    /// Queues `email` in the outbox, as part of the caller's transaction.
+    ///
+    /// Nothing is sent until the transaction is committed and the email is passed to
+    /// [`send_queued`](Self::send_queued), or the scheduler gets to it.
     #[instrument(skip_all)]
-    pub async fn send_oauth_password_reset(
+    pub async fn queue(
         &self,
-        to: Email,
-        oauth_provider: OAuthProvider,
-    ) -> anyhow::Result<()> {
-        let subject = format!("Reset your password");
-
-        let value = format!(
-            r#"
-Looks like you requested a reset password link but you didn't sign up with a password, you signed up with a {} account.
-Please try logging in with your {} account.
-            "#,
-            oauth_provider.as_str(),
-            oauth_provider.as_str()
-        );
-        let message = Message::new(self.sender_email.clone())
-            .add_personalization(Personalization::new(to))
-            .set_subject(&subject)
-            .add_content(
-                Content::new()
-                    .set_content_type("text/plain")
-                    .set_value(value),
-            );
-
-        self.client.send(&message).await?;
-
-        Ok(())
+        db: &mut PgConnection,
+        to: &str,
+        locale: &str,
+        email: EmailTemplate,
+    ) -> anyhow::Result<EmailOutboxId> {
+        let (locale, rendered) = self.render_for(&mut *db, &email, locale).await?;
+
+        let id = db::email::insert(
+            db,
+            email.kind(),
+            to,
+            &locale,
+            &rendered.subject,
+            serde_json::to_value(&email)?,
+        )
+        .await?;
+
+        Ok(id)
     }
 
-    pub async fn send_email_reset(
-        &self,
-        template: EmailResetTemplate<'_>,
-        to: Email,
-        link: String,
-        first_name: String,
-    ) -> anyhow::Result<()> {
-        let mut template_data = SGMap::new();
-        template_data.insert("url".to_string(), link);
-        template_data.insert("firstname".to_string(), first_name);
+    /// Sends an email which was queued by a committed transaction.
+    ///
+    /// Failing to send isn't an error, the email is left in the outbox to be retried.
+    #[instrument(skip(self, db))]
+    pub async fn send_queued(&self, db: &PgPool, id: EmailOutboxId) {
+        if let Err(e) = self.send_from_outbox(db, id).await {
+            log::warn!("Failed to send email {}, will retry: {:?}", id, e);
+        }
+    }
 
-        let message = Message::new(self.sender_email.clone())
-            .set_template_id(&template.0)
-            .add_personalization(Personalization::new(to).add_dynamic_template_data(template_data));
+    /// Retries emails in the outbox which are due.
+    pub async fn send_pending(&self, db: &PgPool) -> anyhow::Result<()> {
+        let pending = db::email::due(db, OUTBOX_BATCH_SIZE).await?;
 
-        self.client.send(&message).await?;
+        for id in pending {
+            self.send_from_outbox(db, id).await?;
+        }
 
         Ok(())
     }
 
-    pub async fn send_jig_report_email(
-        &self,
-        report: JigReportEmail,
-        link: String,
-    ) -> anyhow::Result<()> {
-        let subject = format!("URGENT: JIG Report '{}'", report.report_type.as_str());
-
-        let (reporter_email, reporter_name): (String, String) =
-            if let (Some(email), Some(name)) = (report.reporter_email, report.reporter_name) {
-                (email, name)
-            } else {
-                ("Unknown".to_string(), "Unknown".to_string())
-            };
-
-        let value = format!(
-            r#"{} with email {} has reported "{}" for the following reason: "{}".
-
-            URL: {}
-            Created by: {}
-               "#,
-            reporter_name,
-            reporter_email,
-            report.display_name,
-            report.report_type.as_str(),
-            link,
-            report.creator_name,
-        );
+    /// Sends an email from the outbox and records the attempt.
+    ///
+    /// Each email gets a transaction of its own, so that once it's been sent nothing can roll back
+    /// the record of it. Emails which were already sent, or are being sent by someone else, are
+    /// skipped.
+    async fn send_from_outbox(&self, db: &PgPool, id: EmailOutboxId) -> anyhow::Result<()> {
+        let mut txn = db.begin().await?;
+
+        let email = match db::email::lock_unsent(&mut txn, id).await? {
+            Some(email) => email,
+            None => return Ok(()),
+        };
+
+        let res = async {
+            let data: EmailTemplate =
+                serde_json::from_value(email.data).context("invalid template data in outbox")?;
+
+            let (locale, rendered) = self
+                .render_for(&mut txn, &data, &template::locale_for(&email.locale))
+                .await?;
+
+            self.deliver(&email.to_email, &locale, &data, &rendered)
+                .await
+        }
+        .await;
 
-        let content = Content::new();
+        if let Err(e) = &res {
+            log::warn!("Failed to send email {}: {:?}", email.id, e);
+        }
 
-        let message = Message::new(self.sender_email.clone())
-            .add_personalization(Personalization::new(self.jigzi_info_email.clone()))
-            .set_subject(&subject)
-            .add_content(content.set_content_type("text/plain").set_value(value));
+        db::email::record_attempt(
+            &mut txn,
+            email.id,
+            res.err().map(|e| format!("{:#}", e)).as_deref(),
+        )
+        .await?;
 
-        self.client.send(&message).await?;
+        txn.commit().await?;
 
         Ok(())
     }
 
-    pub async fn send_resource_report_email(
+    async fn deliver(
         &self,
-        to: Email,
-        report: ResourceReportEmail,
-        link: String,
+        to: &str,
+        locale: &str,
+        email: &EmailTemplate,
+        rendered: &RenderedEmail,
     ) -> anyhow::Result<()> {
-        let subject = format!("URGENT: Resource Report '{}'", report.report_type.as_str());
-
-        let (reporter_email, reporter_name): (String, String) =
-            if let (Some(email), Some(name)) = (report.reporter_email, report.reporter_name) {
-                (email, name)
-            } else {
-                ("Unknown".to_string(), "Unknown".to_string())
-            };
-
-        let value = format!(
-            r#"{} with email {} has reported "{}" for the following reason: "{}".
-
-            URL: {}
-            Created by: {}
-               "#,
-            reporter_name,
-            reporter_email,
-            report.display_name,
-            report.report_type.as_str(),
-            link,
-            report.creator_name,
-        );
-
-        let content = Content::new();
-
-        let message = Message::new(self.sender_email.clone())
-            .add_personalization(Personalization::new(to))
-            .set_subject(&subject)
-            .add_content(content.set_content_type("text/plain").set_value(value));
-
-        self.client.send(&message).await?;
+        let client = match &self.backend {
+            Backend::SendGrid(client) => client,
+            Backend::Local => {
+                log::info!(
+                    "Not sending email to {} ({}): {}\n{}",
+                    to,
+                    locale,
+                    rendered.subject,
+                    rendered.text
+                );
+
+                return Ok(());
+            }
+        };
+
+        let personalization = Personalization::new(Email::new(to));
+
+        let message = match self.sendgrid_template(email.kind(), locale) {
+            Some(template_id) => {
+                let mut template_data = SGMap::new();
+
+                let strings = TemplateStrings::default_for(email.kind());
+                for (name, value) in email.variables(&strings) {
+                    template_data.insert(name.to_owned(), value);
+                }
+
+                Message::new(self.sender_email.clone())
+                    .set_template_id(template_id)
+                    .add_personalization(personalization.add_dynamic_template_data(template_data))
+            }
+            None => Message::new(self.sender_email.clone())
+                .add_personalization(personalization)
+                .set_subject(&rendered.subject)
+                .add_content(
+                    Content::new()
+                        .set_content_type("text/plain")
+                        .set_value(&rendered.text),
+                )
+                .add_content(
+                    Content::new()
+                        .set_content_type("text/html")
+                        .set_value(&rendered.html),
+                ),
+        };
+
+        client.send(&message).await?.error_for_status()?;
 
         Ok(())
     }
-
-    pub fn signup_verify_template(&self) -> Result<SignupVerifyTemplate<'_>, ServiceKindError> {
-        // todo: make the error more specific?
-        self.signup_verify_template
-            .as_deref()
-            .map(SignupVerifyTemplate)
-            .ok_or(ServiceKindError::Mail)
-    }
-
-    pub fn password_reset_template(&self) -> Result<PasswordResetTemplate<'_>, ServiceKindError> {
-        // todo: make the error more specific?
-        self.password_reset_template
-            .as_deref()
-            .map(PasswordResetTemplate)
-            .ok_or(ServiceKindError::Mail)
-    }
-
-    pub fn email_reset_template(&self) -> Result<EmailResetTemplate<'_>, ServiceKindError> {
-        // todo: make the error more specific?
-        self.email_reset_template
-            .as_deref()
-            .map(EmailResetTemplate)
-            .ok_or(ServiceKindError::Mail)
-    }
-
-    pub fn welcome_jigzi_template(&self) -> Result<WelcomeJigziTemplate<'_>, ServiceKindError> {
-        // todo: make the error more specific?
-        self.welcome_jigzi_template
-            .as_deref()
-            .map(WelcomeJigziTemplate)
-            .ok_or(ServiceKindError::Mail)
-    }
 }
 
 impl Service for Client {
     const DISABLED_ERROR: ServiceKindError = ServiceKindError::Mail;
 }
-
-#[repr(transparent)]
-#[derive(Debug, Clone, Copy)]
-pub struct SignupVerifyTemplate<'a>(&'a str);
-
-#[repr(transparent)]
-#[derive(Debug, Clone, Copy)]
-pub struct PasswordResetTemplate<'a>(&'a str);
-
-#[repr(transparent)]
-#[derive(Debug, Clone, Copy)]
-pub struct EmailResetTemplate<'a>(&'a str);
-
-#[repr(transparent)]
-#[derive(Debug, Clone, Copy)]
-pub struct WelcomeJigziTemplate<'a>(&'a str);
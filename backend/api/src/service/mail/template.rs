@@ -0,0 +1,534 @@
+//! Transactional email templates.
+//!
+//! An email is sent through its SendGrid template when one is configured for the recipient's
+//! locale. Otherwise it's rendered here, from the strings in the `email` locale bundle, and sent
+//! as plain content. Strings which haven't been translated yet fall back to the English defaults.
+
+use std::{borrow::Cow, collections::HashMap};
+
+use serde::{Deserialize, Serialize};
+use shared::domain::{email::EmailTemplateKind, notification::NotificationKind};
+
+/// Locale emails are rendered in when they haven't been translated into the recipient's.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Languages which are written right to left.
+const RTL_LANGUAGES: &[&str] = &["he", "ar", "yi", "fa", "ur"];
+
+/// Gets the locale an email is rendered in from a user's email language, e.g. `he-IL` becomes
+/// `he`.
+///
+/// Any language can be picked, [`Client::render`](super::Client::render) falls back to English
+/// when a template hasn't been translated into it.
+pub fn locale_for(language: &str) -> String {
+    let language = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    match language.as_str() {
+        "" => DEFAULT_LOCALE.to_owned(),
+        // the deprecated code for Hebrew, still used by some browsers
+        "iw" => "he".to_owned(),
+        _ => language,
+    }
+}
+
+fn is_rtl(locale: &str) -> bool {
+    RTL_LANGUAGES.contains(&locale)
+}
+
+/// An email and the values to fill its template with.
+///
+/// Stored in the outbox until it's sent, so it can be rendered again when retrying.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "template", rename_all = "camelCase")]
+pub enum EmailTemplate {
+    SignupVerify {
+        url: String,
+    },
+    WelcomeJigzi {
+        url: String,
+        first_name: String,
+    },
+    PasswordReset {
+        url: String,
+        first_name: String,
+    },
+    OAuthPasswordReset {
+        provider: String,
+    },
+    EmailReset {
+        url: String,
+        first_name: String,
+    },
+    JigReport(ReportEmail),
+    ResourceReport(ReportEmail),
+    NotificationDigest {
+        url: String,
+        first_name: String,
+        /// Count of unread notifications by kind, each listed on a line of the `summary`.
+        counts: Vec<(NotificationKind, u32)>,
+    },
+}
+
+/// Values for the emails admins get when something is reported.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReportEmail {
+    pub url: String,
+    pub display_name: String,
+    pub report_type: String,
+    pub reporter_name: Option<String>,
+    pub reporter_email: Option<String>,
+    pub creator_name: String,
+}
+
+impl EmailTemplate {
+    pub fn kind(&self) -> EmailTemplateKind {
+        match self {
+            Self::SignupVerify { .. } => EmailTemplateKind::SignupVerify,
+            Self::WelcomeJigzi { .. } => EmailTemplateKind::WelcomeJigzi,
+            Self::PasswordReset { .. } => EmailTemplateKind::PasswordReset,
+            Self::OAuthPasswordReset { .. } => EmailTemplateKind::OAuthPasswordReset,
+            Self::EmailReset { .. } => EmailTemplateKind::EmailReset,
+            Self::JigReport(_) => EmailTemplateKind::JigReport,
+            Self::ResourceReport(_) => EmailTemplateKind::ResourceReport,
+            Self::NotificationDigest { .. } => EmailTemplateKind::NotificationDigest,
+        }
+    }
+
+    /// Values referenced as `{{name}}` in the template's strings.
+    ///
+    /// The names match the dynamic data of our SendGrid templates. Values which are made from the
+    /// template's own strings, like the digest's `summary`, are made from `strings`.
+    pub fn variables(&self, strings: &TemplateStrings) -> Vec<(&'static str, String)> {
+        match self {
+            Self::SignupVerify { url } => vec![("url", url.clone())],
+            Self::WelcomeJigzi { url, first_name }
+            | Self::PasswordReset { url, first_name }
+            | Self::EmailReset { url, first_name } => {
+                vec![("url", url.clone()), ("firstname", first_name.clone())]
+            }
+            Self::OAuthPasswordReset { provider } => vec![("provider", provider.clone())],
+            Self::JigReport(report) | Self::ResourceReport(report) => vec![
+                ("url", report.url.clone()),
+                ("display_name", report.display_name.clone()),
+                ("report_type", report.report_type.clone()),
+                (
+                    "reporter_name",
+                    report
+                        .reporter_name
+                        .clone()
+                        .unwrap_or_else(|| "Unknown".to_owned()),
+                ),
+                (
+                    "reporter_email",
+                    report
+                        .reporter_email
+                        .clone()
+                        .unwrap_or_else(|| "Unknown".to_owned()),
+                ),
+                ("creator_name", report.creator_name.clone()),
+            ],
+            Self::NotificationDigest {
+                url,
+                first_name,
+                counts,
+            } => {
+                let summary = counts
+                    .iter()
+                    .filter_map(|(kind, count)| {
+                        let line = strings.parts.get(kind.as_str())?;
+                        Some(substitute(line, Cow::Borrowed, |name| {
+                            (name == "count").then(|| count.to_string())
+                        }))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                // the counts are also given by kind, for SendGrid templates to word themselves
+                let mut variables = vec![
+                    ("url", url.clone()),
+                    ("firstname", first_name.clone()),
+                    ("summary", summary),
+                ];
+                variables.extend(
+                    counts
+                        .iter()
+                        .map(|(kind, count)| (kind.as_str(), count.to_string())),
+                );
+
+                variables
+            }
+        }
+    }
+
+    /// Made up values for previewing a template.
+    pub fn sample(kind: EmailTemplateKind, pages_url: &str) -> Self {
+        let url = format!("{}/user/verify-email/sample", pages_url);
+        let first_name = "Sarah".to_owned();
+
+        let report = || ReportEmail {
+            url: format!("{}/jig/play/sample", pages_url),
+            display_name: "Alef Bet Train".to_owned(),
+            report_type: "Offensive".to_owned(),
+            reporter_name: Some("Sarah Cohen".to_owned()),
+            reporter_email: Some("sarah@example.com".to_owned()),
+            creator_name: "David Levi".to_owned(),
+        };
+
+        match kind {
+            EmailTemplateKind::SignupVerify => Self::SignupVerify { url },
+            EmailTemplateKind::WelcomeJigzi => Self::WelcomeJigzi {
+                url: pages_url.to_owned(),
+                first_name,
+            },
+            EmailTemplateKind::PasswordReset => Self::PasswordReset { url, first_name },
+            EmailTemplateKind::OAuthPasswordReset => Self::OAuthPasswordReset {
+                provider: "Google".to_owned(),
+            },
+            EmailTemplateKind::EmailReset => Self::EmailReset { url, first_name },
+            EmailTemplateKind::JigReport => Self::JigReport(report()),
+            EmailTemplateKind::ResourceReport => Self::ResourceReport(report()),
+            EmailTemplateKind::NotificationDigest => Self::NotificationDigest {
+                url: pages_url.to_owned(),
+                first_name,
+                counts: vec![
+                    (NotificationKind::JigLiked, 3),
+                    (NotificationKind::JigPlayed, 12),
+                ],
+            },
+        }
+    }
+
+    /// Renders the email from `strings`.
+    pub fn render(&self, locale: &str, strings: &TemplateStrings) -> RenderedEmail {
+        let variables = self.variables(strings);
+        let value = |name: &str| {
+            variables
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        let subject = substitute(&strings.subject, Cow::Borrowed, |name| {
+            value(name).map(str::to_owned)
+        });
+
+        let text = substitute(&strings.body, Cow::Borrowed, |name| {
+            value(name).map(str::to_owned)
+        });
+
+        let paragraphs: String = strings
+            .body
+            .split("\n\n")
+            .map(str::trim)
+            .filter(|paragraph| !paragraph.is_empty())
+            .map(|paragraph| {
+                let paragraph = substitute(
+                    paragraph,
+                    |literal| Cow::Owned(escape_html(literal).replace('\n', "<br>")),
+                    |name| {
+                        value(name).map(|value| {
+                            let value = escape_html(value).replace('\n', "<br>");
+                            if name == "url" {
+                                format!(r#"<a href="{value}">{value}</a>"#)
+                            } else {
+                                value
+                            }
+                        })
+                    },
+                );
+
+                format!("<p>{}</p>\n", paragraph)
+            })
+            .collect();
+
+        let html = format!(
+            r#"<!DOCTYPE html>
+<html lang="{locale}" dir="{dir}">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+</head>
+<body style="font-family: Poppins, Arial, sans-serif; font-size: 16px; color: #383838;">
+{paragraphs}</body>
+</html>
+"#,
+            dir = if is_rtl(locale) { "rtl" } else { "ltr" },
+            title = escape_html(&subject),
+        );
+
+        RenderedEmail {
+            subject,
+            html,
+            text,
+        }
+    }
+}
+
+/// The strings an email is rendered from, with `{{name}}` placeholders for its variables.
+///
+/// Paragraphs of the body are separated by blank lines.
+#[derive(Debug, Clone)]
+pub struct TemplateStrings {
+    pub subject: String,
+    pub body: String,
+    /// Other strings of the template by their item kind, like the digest's line for each kind of
+    /// notification.
+    pub parts: HashMap<String, String>,
+}
+
+impl TemplateStrings {
+    /// The English strings used when a template hasn't been added to the locale bundle.
+    pub fn default_for(kind: EmailTemplateKind) -> Self {
+        let (subject, body) = match kind {
+            EmailTemplateKind::SignupVerify => (
+                "Verify your email",
+                "Welcome to Jigzi!\n\n\
+                 Please verify your email address by following this link:\n\n\
+                 {{url}}\n\n\
+                 The link expires in an hour.",
+            ),
+            EmailTemplateKind::WelcomeJigzi => (
+                "Welcome to Jigzi",
+                "Hi {{firstname}},\n\n\
+                 Thanks for joining Jigzi! Start creating and playing JIGs at:\n\n\
+                 {{url}}",
+            ),
+            EmailTemplateKind::PasswordReset => (
+                "Reset your password",
+                "Hi {{firstname}},\n\n\
+                 Follow this link to reset your password:\n\n\
+                 {{url}}\n\n\
+                 The link expires in an hour. If you didn't ask to reset your password you can ignore this email.",
+            ),
+            EmailTemplateKind::OAuthPasswordReset => (
+                "Reset your password",
+                "Looks like you requested a reset password link but you didn't sign up with a password, you signed up with a {{provider}} account.\n\
+                 Please try logging in with your {{provider}} account.",
+            ),
+            EmailTemplateKind::EmailReset => (
+                "Verify your new email",
+                "Hi {{firstname}},\n\n\
+                 Follow this link to verify your new email address:\n\n\
+                 {{url}}\n\n\
+                 The link expires in an hour.",
+            ),
+            EmailTemplateKind::JigReport => (
+                "URGENT: JIG Report '{{report_type}}'",
+                "{{reporter_name}} with email {{reporter_email}} has reported \"{{display_name}}\" for the following reason: \"{{report_type}}\".\n\n\
+                 URL: {{url}}\n\
+                 Created by: {{creator_name}}",
+            ),
+            EmailTemplateKind::ResourceReport => (
+                "URGENT: Resource Report '{{report_type}}'",
+                "{{reporter_name}} with email {{reporter_email}} has reported \"{{display_name}}\" for the following reason: \"{{report_type}}\".\n\n\
+                 URL: {{url}}\n\
+                 Created by: {{creator_name}}",
+            ),
+            EmailTemplateKind::NotificationDigest => (
+                "Your week on Jigzi",
+                "Hi {{firstname}},\n\n\
+                 Here's what happened while you were away:\n\n\
+                 {{summary}}\n\n\
+                 See all your notifications at {{url}}",
+            ),
+        };
+
+        let parts = match kind {
+            EmailTemplateKind::NotificationDigest => NotificationKind::ALL
+                .iter()
+                .map(|kind| (kind.as_str().to_owned(), digest_line(*kind).to_owned()))
+                .collect(),
+            _ => HashMap::new(),
+        };
+
+        Self {
+            subject: subject.to_owned(),
+            body: body.to_owned(),
+            parts,
+        }
+    }
+}
+
+/// The English line of the notification digest's summary for a kind of notification.
+fn digest_line(kind: NotificationKind) -> &'static str {
+    match kind {
+        NotificationKind::JigLiked => "New likes on your JIGs: {{count}}",
+        NotificationKind::JigPlayed => "New plays of your JIGs: {{count}}",
+        NotificationKind::Followed => "New followers: {{count}}",
+        NotificationKind::CircleJoined => "New members in your circles: {{count}}",
+        NotificationKind::CurationComment => "New curation comments on your JIGs: {{count}}",
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+/// Replaces each `{{name}}` in `template` with `value(name)`, passing the text around them
+/// through `literal`. Placeholders without a value are left as they are.
+fn substitute<'a>(
+    template: &'a str,
+    literal: impl Fn(&'a str) -> Cow<'a, str>,
+    value: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+
+        let end = start + 2 + len + 2;
+        let name = rest[start + 2..start + 2 + len].trim();
+
+        out.push_str(&literal(&rest[..start]));
+
+        match value(name) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(&literal(&rest[start..end])),
+        }
+
+        rest = &rest[end..];
+    }
+
+    out.push_str(&literal(rest));
+
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locale_for_language() {
+        assert_eq!(locale_for("he"), "he");
+        assert_eq!(locale_for("he-IL"), "he");
+        assert_eq!(locale_for("EN"), "en");
+        assert_eq!(locale_for("iw"), "he");
+        assert_eq!(locale_for("fr_FR"), "fr");
+        assert_eq!(locale_for(""), DEFAULT_LOCALE);
+    }
+
+    #[test]
+    fn render_substitutes_variables() {
+        let email = EmailTemplate::PasswordReset {
+            url: "https://jigzi.org/reset?a=1&b=2".to_owned(),
+            first_name: "<Sam>".to_owned(),
+        };
+
+        let strings = TemplateStrings {
+            subject: "Hi {{ firstname }}".to_owned(),
+            body: "Hi {{firstname}},\n\n{{url}}\n\n{{missing}}".to_owned(),
+            parts: HashMap::new(),
+        };
+
+        let rendered = email.render("en", &strings);
+
+        assert_eq!(rendered.subject, "Hi <Sam>");
+        assert_eq!(
+            rendered.text,
+            "Hi <Sam>,\n\nhttps://jigzi.org/reset?a=1&b=2\n\n{{missing}}"
+        );
+        assert!(rendered.html.contains("<p>Hi &lt;Sam&gt;,</p>"));
+        assert!(rendered.html.contains(
+            r#"<p><a href="https://jigzi.org/reset?a=1&amp;b=2">https://jigzi.org/reset?a=1&amp;b=2</a></p>"#
+        ));
+        assert!(rendered.html.contains("<p>{{missing}}</p>"));
+        assert!(rendered.html.contains(r#"dir="ltr""#));
+    }
+
+    #[test]
+    fn render_rtl() {
+        let email = EmailTemplate::sample(EmailTemplateKind::SignupVerify, "https://jigzi.org");
+
+        let rendered = email.render(
+            "he",
+            &TemplateStrings::default_for(EmailTemplateKind::SignupVerify),
+        );
+
+        assert!(rendered.html.contains(r#"<html lang="he" dir="rtl">"#));
+
+        let rendered = email.render(
+            "ar",
+            &TemplateStrings::default_for(EmailTemplateKind::SignupVerify),
+        );
+
+        assert!(rendered.html.contains(r#"<html lang="ar" dir="rtl">"#));
+    }
+
+    #[test]
+    fn render_notification_digest() {
+        let email = EmailTemplate::NotificationDigest {
+            url: "https://jigzi.org/notifications".to_owned(),
+            first_name: "Sam".to_owned(),
+            counts: vec![
+                (NotificationKind::JigLiked, 3),
+                (NotificationKind::Followed, 1),
+            ],
+        };
+
+        let rendered = email.render(
+            DEFAULT_LOCALE,
+            &TemplateStrings::default_for(EmailTemplateKind::NotificationDigest),
+        );
+
+        assert!(rendered
+            .text
+            .contains("New likes on your JIGs: 3\nNew followers: 1"));
+        assert!(rendered
+            .html
+            .contains("<p>New likes on your JIGs: 3<br>New followers: 1</p>"));
+
+        // lines come from the template's strings, in the email's locale
+        let strings = TemplateStrings {
+            subject: "השבוע שלך בג'יגזי".to_owned(),
+            body: "{{summary}}".to_owned(),
+            parts: HashMap::from([
+                ("jig-liked".to_owned(), "לייקים חדשים: {{count}}".to_owned()),
+                ("followed".to_owned(), "עוקבים חדשים: {{count}}".to_owned()),
+            ]),
+        };
+
+        let rendered = email.render("he", &strings);
+
+        assert_eq!(rendered.text, "לייקים חדשים: 3\nעוקבים חדשים: 1");
+    }
+
+    #[test]
+    fn defaults_only_use_known_variables() {
+        for kind in EmailTemplateKind::ALL {
+            let email = EmailTemplate::sample(*kind, "https://jigzi.org");
+            let rendered = email.render(DEFAULT_LOCALE, &TemplateStrings::default_for(*kind));
+
+            assert!(!rendered.subject.contains("{{"), "{}", kind.as_str());
+            assert!(!rendered.text.contains("{{"), "{}", kind.as_str());
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Billing through a payment provider.
+//!
+//! Handlers only talk to the provider through [`PaymentProvider`], so the subscription flows can
+//! run against Stripe in production and against [`fake::FakePaymentProvider`] in tests. The
+//! Stripe webhook is the exception, it's inherently Stripe specific.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use shared::{
+    domain::billing::{
+        AmountInCents, CustomerId, Invoice, StripeInvoiceId, StripePriceId, StripeSubscriptionId,
+        SubscriptionStatus, UpdateSubscriptionRecord,
+    },
+    error::{BillingError, ServiceKindError},
+};
+
+use super::Service;
+
+pub mod fake;
+pub mod stripe;
+
+/// ID of a promotion code, as opposed to the code customers enter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromotionCodeId(pub String);
+
+/// A subscription to create for a customer.
+#[derive(Debug, Clone)]
+pub struct NewSubscription {
+    pub customer_id: CustomerId,
+    pub price_id: StripePriceId,
+    pub promotion_code_id: Option<PromotionCodeId>,
+    /// Days of trial to give. The subscription is canceled at the end of the trial if the
+    /// customer hasn't added a payment method by then.
+    pub trial_period_days: Option<u32>,
+    pub collection: Collection,
+}
+
+/// How the invoices of a new subscription get paid.
+#[derive(Debug, Clone, Default)]
+pub enum Collection {
+    /// Charge the customer's default payment method.
+    #[default]
+    ChargeAutomatically,
+    /// Send each invoice to the customer to pay by the due date, against their purchase order.
+    /// The subscription starts straight away.
+    SendInvoice {
+        days_until_due: u32,
+        purchase_order_number: String,
+    },
+}
+
+/// A subscription as created by the provider.
+#[derive(Debug, Clone)]
+pub struct CreatedSubscription {
+    pub subscription_id: StripeSubscriptionId,
+    pub status: SubscriptionStatus,
+    pub current_period_end: DateTime<Utc>,
+    pub latest_invoice_id: Option<StripeInvoiceId>,
+    pub amount_due_in_cents: Option<AmountInCents>,
+    /// Price of the subscription without any discounts applied
+    pub price: AmountInCents,
+    /// Client secret of the payment intent to confirm, if the first invoice has to be paid before
+    /// the subscription starts.
+    pub client_secret: Option<String>,
+}
+
+/// A change to an existing subscription.
+#[derive(Debug, Clone)]
+pub enum SubscriptionChange {
+    /// Cancel the subscription at the end of the current period.
+    CancelAtPeriodEnd,
+    /// Keep a subscription which was set to cancel at the end of the current period.
+    RemoveCancellation,
+    /// Stop collecting payments.
+    Pause,
+    /// Start collecting payments again.
+    Resume,
+    /// Switch to another price, invoicing the prorated difference straight away.
+    ChangePrice {
+        price_id: StripePriceId,
+        promotion_code_id: Option<PromotionCodeId>,
+    },
+}
+
+#[async_trait]
+pub trait PaymentProvider: Send + Sync {
+    /// Creates a customer to bill.
+    async fn create_customer(&self, email: &str, name: &str) -> Result<CustomerId, BillingError>;
+
+    /// Updates a customer's contact details, leaving the ones which are `None` as they are.
+    async fn update_customer(
+        &self,
+        customer_id: &CustomerId,
+        email: Option<&str>,
+        name: Option<&str>,
+    ) -> Result<(), BillingError>;
+
+    /// Creates a setup intent for the customer to add a payment method with, returning its client
+    /// secret.
+    async fn create_setup_intent(&self, customer_id: &CustomerId) -> Result<String, BillingError>;
+
+    /// Makes the payment method added through a setup intent the customer's default.
+    ///
+    /// Errors with [`BillingError::InvalidSetupIntentId`] if there is no such setup intent.
+    async fn set_default_payment_method(
+        &self,
+        customer_id: &CustomerId,
+        setup_intent_id: &str,
+    ) -> Result<(), BillingError>;
+
+    /// Looks up an active promotion code.
+    ///
+    /// Errors with [`BillingError::InvalidPromotionCode`] if there is no such code.
+    async fn find_promotion_code(&self, code: &str) -> Result<PromotionCodeId, BillingError>;
+
+    async fn create_subscription(
+        &self,
+        subscription: NewSubscription,
+    ) -> Result<CreatedSubscription, BillingError>;
+
+    /// Changes a subscription, returning what needs to be saved as a result.
+    async fn update_subscription(
+        &self,
+        subscription_id: &StripeSubscriptionId,
+        change: SubscriptionChange,
+    ) -> Result<UpdateSubscriptionRecord, BillingError>;
+
+    /// Lists all of the customer's invoices, newest first.
+    async fn list_invoices(&self, customer_id: &CustomerId) -> Result<Vec<Invoice>, BillingError>;
+
+    /// Marks an open invoice as paid outside of the provider, e.g. by bank transfer.
+    async fn mark_invoice_paid(
+        &self,
+        invoice_id: &StripeInvoiceId,
+    ) -> Result<Invoice, BillingError>;
+
+    /// Creates a link to the customer's billing portal.
+    async fn create_portal_link(&self, customer_id: &CustomerId) -> Result<String, BillingError>;
+}
+
+impl Service for dyn PaymentProvider {
+    const DISABLED_ERROR: ServiceKindError = ServiceKindError::Stripe;
+}
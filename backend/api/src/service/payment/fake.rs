@@ -0,0 +1,471 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use shared::{
+    domain::{
+        billing::{
+            AmountInCents, CollectionMethod, CustomerId, Invoice, InvoiceNumber, InvoiceStatus,
+            StripeInvoiceId, StripeSubscriptionId, SubscriptionStatus, UpdateSubscriptionRecord,
+        },
+        UpdateNonNullable,
+    },
+    error::BillingError,
+};
+
+use super::{
+    Collection, CreatedSubscription, NewSubscription, PaymentProvider, PromotionCodeId,
+    SubscriptionChange,
+};
+
+/// A customer held by [`FakePaymentProvider`].
+#[derive(Debug, Clone)]
+pub struct FakeCustomer {
+    pub email: String,
+    pub name: String,
+    pub default_payment_method: Option<String>,
+}
+
+/// A subscription held by [`FakePaymentProvider`].
+#[derive(Debug, Clone)]
+pub struct FakeSubscription {
+    pub customer_id: String,
+    pub price_id: String,
+    pub promotion_code_id: Option<String>,
+    pub status: SubscriptionStatus,
+    pub is_trial: bool,
+    pub cancel_at_period_end: bool,
+    pub paused: bool,
+    /// Set if the subscription's invoices are sent to the customer instead of charged
+    pub purchase_order_number: Option<String>,
+}
+
+#[derive(Default)]
+struct State {
+    last_id: u64,
+    prices: HashMap<String, i64>,
+    promotion_codes: HashMap<String, String>,
+    customers: HashMap<String, FakeCustomer>,
+    /// Customer each setup intent was created for
+    setup_intents: HashMap<String, String>,
+    subscriptions: HashMap<String, FakeSubscription>,
+    /// Invoices and the customer each is for, oldest first
+    invoices: Vec<(String, Invoice)>,
+}
+
+impl State {
+    fn next_id(&mut self, prefix: &str) -> String {
+        self.last_id += 1;
+        format!("{}_fake_{}", prefix, self.last_id)
+    }
+
+    fn price(&self, price_id: &str) -> Result<i64, BillingError> {
+        self.prices
+            .get(price_id)
+            .copied()
+            .ok_or_else(|| anyhow!("No such price: {}", price_id).into())
+    }
+
+    fn invoice_mut(&mut self, invoice_id: &StripeInvoiceId) -> Result<&mut Invoice, BillingError> {
+        self.invoices
+            .iter_mut()
+            .map(|(_, invoice)| invoice)
+            .find(|invoice| invoice.id.as_str() == invoice_id.as_str())
+            .ok_or_else(|| anyhow!("No such invoice: {}", invoice_id).into())
+    }
+
+    fn promotion_code_exists(&self, id: &Option<PromotionCodeId>) -> Result<(), BillingError> {
+        match id {
+            Some(id) if !self.promotion_codes.values().any(|it| *it == id.0) => {
+                Err(anyhow!("No such promotion code: {}", id.0).into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// An in-memory [`PaymentProvider`] for tests.
+///
+/// Nothing is charged, and IDs are numbered in the order things are created so runs are
+/// repeatable. Prices and promotion codes have to be added before they can be used, and the
+/// current period of every subscription ends at [`FakePaymentProvider::PERIOD_END`].
+///
+/// Setup intents complete as soon as they're created, and the ID of one is its client secret up to
+/// `_secret`, as with Stripe.
+///
+/// Every new subscription gets a first invoice. It's paid straight away if there is a trial or a
+/// payment method to charge, and otherwise stays open until it's paid.
+#[derive(Default)]
+pub struct FakePaymentProvider {
+    state: Mutex<State>,
+}
+
+impl FakePaymentProvider {
+    /// When the current period of every subscription ends, 2030-01-01.
+    pub const PERIOD_END: i64 = 1_893_456_000;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a price for subscriptions, in cents.
+    pub fn with_price(self, price_id: &str, amount_in_cents: i64) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .prices
+            .insert(price_id.to_owned(), amount_in_cents);
+
+        self
+    }
+
+    /// Adds an active promotion code.
+    pub fn with_promotion_code(self, code: &str) -> Self {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id("promo");
+        state.promotion_codes.insert(code.to_owned(), id);
+        drop(state);
+
+        self
+    }
+
+    pub fn customer(&self, customer_id: &CustomerId) -> Option<FakeCustomer> {
+        self.state
+            .lock()
+            .unwrap()
+            .customers
+            .get(customer_id.as_str())
+            .cloned()
+    }
+
+    pub fn invoice(&self, invoice_id: &StripeInvoiceId) -> Option<Invoice> {
+        self.state
+            .lock()
+            .unwrap()
+            .invoice_mut(invoice_id)
+            .ok()
+            .cloned()
+    }
+
+    pub fn subscription(&self, subscription_id: &StripeSubscriptionId) -> Option<FakeSubscription> {
+        self.state
+            .lock()
+            .unwrap()
+            .subscriptions
+            .get(subscription_id.as_str())
+            .cloned()
+    }
+
+    fn period_end() -> DateTime<Utc> {
+        Utc.timestamp_opt(Self::PERIOD_END, 0).unwrap()
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for FakePaymentProvider {
+    async fn create_customer(&self, email: &str, name: &str) -> Result<CustomerId, BillingError> {
+        let mut state = self.state.lock().unwrap();
+        let id = state.next_id("cus");
+
+        state.customers.insert(
+            id.clone(),
+            FakeCustomer {
+                email: email.to_owned(),
+                name: name.to_owned(),
+                default_payment_method: None,
+            },
+        );
+
+        Ok(id.into())
+    }
+
+    async fn update_customer(
+        &self,
+        customer_id: &CustomerId,
+        email: Option<&str>,
+        name: Option<&str>,
+    ) -> Result<(), BillingError> {
+        let mut state = self.state.lock().unwrap();
+        let customer = state
+            .customers
+            .get_mut(customer_id.as_str())
+            .ok_or_else(|| anyhow!("No such customer: {}", customer_id))?;
+
+        if let Some(email) = email {
+            customer.email = email.to_owned();
+        }
+
+        if let Some(name) = name {
+            customer.name = name.to_owned();
+        }
+
+        Ok(())
+    }
+
+    async fn create_setup_intent(&self, customer_id: &CustomerId) -> Result<String, BillingError> {
+        let mut state = self.state.lock().unwrap();
+
+        if !state.customers.contains_key(customer_id.as_str()) {
+            return Err(anyhow!("No such customer: {}", customer_id).into());
+        }
+
+        let id = state.next_id("seti");
+        state
+            .setup_intents
+            .insert(id.clone(), customer_id.as_str().to_owned());
+
+        Ok(format!("{}_secret", id))
+    }
+
+    async fn set_default_payment_method(
+        &self,
+        customer_id: &CustomerId,
+        setup_intent_id: &str,
+    ) -> Result<(), BillingError> {
+        let mut state = self.state.lock().unwrap();
+
+        if state.setup_intents.get(setup_intent_id).map(String::as_str)
+            != Some(customer_id.as_str())
+        {
+            return Err(BillingError::InvalidSetupIntentId);
+        }
+
+        let payment_method_id = state.next_id("pm");
+        let customer = state
+            .customers
+            .get_mut(customer_id.as_str())
+            .ok_or_else(|| anyhow!("No such customer: {}", customer_id))?;
+
+        customer.default_payment_method = Some(payment_method_id);
+
+        Ok(())
+    }
+
+    async fn find_promotion_code(&self, code: &str) -> Result<PromotionCodeId, BillingError> {
+        self.state
+            .lock()
+            .unwrap()
+            .promotion_codes
+            .get(code)
+            .cloned()
+            .map(PromotionCodeId)
+            .ok_or_else(|| BillingError::InvalidPromotionCode(code.to_owned()))
+    }
+
+    async fn create_subscription(
+        &self,
+        subscription: NewSubscription,
+    ) -> Result<CreatedSubscription, BillingError> {
+        let mut state = self.state.lock().unwrap();
+        let price_id = String::from(subscription.price_id);
+        let price = state.price(&price_id)?;
+        state.promotion_code_exists(&subscription.promotion_code_id)?;
+
+        let has_payment_method = state
+            .customers
+            .get(subscription.customer_id.as_str())
+            .ok_or_else(|| anyhow!("No such customer: {}", subscription.customer_id))?
+            .default_payment_method
+            .is_some();
+
+        let id = state.next_id("sub");
+        let invoice_id = state.next_id("in");
+        let is_trial = subscription.trial_period_days.is_some();
+        let now = Utc::now();
+
+        let mut invoice = Invoice {
+            id: invoice_id.clone().into(),
+            number: Some(InvoiceNumber::from(format!(
+                "FAKE-{:04}",
+                state.invoices.len() + 1
+            ))),
+            status: InvoiceStatus::Open,
+            collection_method: CollectionMethod::ChargeAutomatically,
+            amount_due: AmountInCents::new(price),
+            amount_paid: AmountInCents::new(0),
+            created_at: now,
+            due_at: None,
+            paid_at: None,
+            pdf_url: Some(format!(
+                "https://billing.example.com/invoice/{}.pdf",
+                invoice_id
+            )),
+            hosted_invoice_url: Some(format!(
+                "https://billing.example.com/invoice/{}",
+                invoice_id
+            )),
+        };
+
+        let purchase_order_number = match subscription.collection {
+            Collection::ChargeAutomatically => None,
+            Collection::SendInvoice {
+                days_until_due,
+                purchase_order_number,
+            } => {
+                invoice.collection_method = CollectionMethod::SendInvoice;
+                invoice.due_at = Some(now + Duration::days(days_until_due.into()));
+                Some(purchase_order_number)
+            }
+        };
+
+        // Without a trial the first invoice is due straight away, and the subscription stays
+        // incomplete until it's paid unless there is a payment method to charge. Invoices which
+        // are sent to the customer don't hold up the subscription.
+        let (status, amount_due, client_secret) = if purchase_order_number.is_some() {
+            (SubscriptionStatus::Active, price, None)
+        } else if is_trial || has_payment_method {
+            invoice.status = InvoiceStatus::Paid;
+            invoice.amount_due = AmountInCents::new(0);
+            invoice.paid_at = Some(now);
+            if !is_trial {
+                invoice.amount_paid = AmountInCents::new(price);
+            }
+
+            (SubscriptionStatus::Active, 0, None)
+        } else {
+            let payment_intent_id = state.next_id("pi");
+            (
+                SubscriptionStatus::Inactive,
+                price,
+                Some(format!("{}_secret", payment_intent_id)),
+            )
+        };
+
+        state.subscriptions.insert(
+            id.clone(),
+            FakeSubscription {
+                customer_id: subscription.customer_id.as_str().to_owned(),
+                price_id,
+                promotion_code_id: subscription.promotion_code_id.map(|id| id.0),
+                status,
+                is_trial,
+                cancel_at_period_end: false,
+                paused: false,
+                purchase_order_number,
+            },
+        );
+
+        state
+            .invoices
+            .push((subscription.customer_id.as_str().to_owned(), invoice));
+
+        Ok(CreatedSubscription {
+            subscription_id: id.into(),
+            status,
+            current_period_end: Self::period_end(),
+            latest_invoice_id: Some(invoice_id.into()),
+            amount_due_in_cents: Some(AmountInCents::new(amount_due)),
+            price: AmountInCents::new(price),
+            client_secret,
+        })
+    }
+
+    async fn update_subscription(
+        &self,
+        subscription_id: &StripeSubscriptionId,
+        change: SubscriptionChange,
+    ) -> Result<UpdateSubscriptionRecord, BillingError> {
+        let mut state = self.state.lock().unwrap();
+
+        let new_price = match &change {
+            SubscriptionChange::ChangePrice {
+                price_id,
+                promotion_code_id,
+            } => {
+                let price_id = String::from(price_id.clone());
+                let price = state.price(&price_id)?;
+                state.promotion_code_exists(promotion_code_id)?;
+                Some((price_id, price))
+            }
+            _ => None,
+        };
+
+        let subscription = state
+            .subscriptions
+            .get_mut(subscription_id.as_str())
+            .ok_or_else(|| anyhow!("No such subscription: {}", subscription_id.as_str()))?;
+
+        match change {
+            SubscriptionChange::CancelAtPeriodEnd => subscription.cancel_at_period_end = true,
+            SubscriptionChange::RemoveCancellation => subscription.cancel_at_period_end = false,
+            SubscriptionChange::Pause => subscription.paused = true,
+            SubscriptionChange::Resume => subscription.paused = false,
+            SubscriptionChange::ChangePrice {
+                promotion_code_id, ..
+            } => {
+                subscription.price_id = new_price.as_ref().unwrap().0.clone();
+                subscription.promotion_code_id = promotion_code_id.map(|id| id.0);
+            }
+        }
+
+        // Same precedence as when converting a Stripe subscription
+        let status = if subscription.cancel_at_period_end {
+            SubscriptionStatus::Canceled
+        } else if subscription.paused {
+            SubscriptionStatus::Paused
+        } else {
+            subscription.status
+        };
+
+        let mut record = UpdateSubscriptionRecord::new(subscription_id.clone());
+        record.status = UpdateNonNullable::Change(status);
+        record.is_trial = UpdateNonNullable::Change(subscription.is_trial);
+        record.current_period_end = UpdateNonNullable::Change(Self::period_end());
+
+        if let Some((_, price)) = new_price {
+            record.price = UpdateNonNullable::Change(AmountInCents::new(price));
+        }
+
+        Ok(record)
+    }
+
+    async fn list_invoices(&self, customer_id: &CustomerId) -> Result<Vec<Invoice>, BillingError> {
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .invoices
+            .iter()
+            .rev()
+            .filter(|(invoice_customer_id, _)| invoice_customer_id == customer_id.as_str())
+            .map(|(_, invoice)| invoice.clone())
+            .collect())
+    }
+
+    async fn mark_invoice_paid(
+        &self,
+        invoice_id: &StripeInvoiceId,
+    ) -> Result<Invoice, BillingError> {
+        let mut state = self.state.lock().unwrap();
+        let invoice = state.invoice_mut(invoice_id)?;
+
+        if invoice.status != InvoiceStatus::Open {
+            return Err(anyhow!("Invoice {} is not open", invoice_id).into());
+        }
+
+        invoice.status = InvoiceStatus::Paid;
+        invoice.amount_paid = invoice.amount_due;
+        invoice.amount_due = AmountInCents::new(0);
+        invoice.paid_at = Some(Utc::now());
+
+        Ok(invoice.clone())
+    }
+
+    async fn create_portal_link(&self, customer_id: &CustomerId) -> Result<String, BillingError> {
+        if !self
+            .state
+            .lock()
+            .unwrap()
+            .customers
+            .contains_key(customer_id.as_str())
+        {
+            return Err(anyhow!("No such customer: {}", customer_id).into());
+        }
+
+        Ok(format!(
+            "https://billing.example.com/session/{}",
+            customer_id
+        ))
+    }
+}
@@ -0,0 +1,370 @@
+use std::str::FromStr;
+
+use ::stripe::{
+    generated::billing::subscription::SubscriptionProrationBehavior, BillingPortalSession, Client,
+    CollectionMethod, CreateBillingPortalSession, CreateCustomer, CreateSetupIntent,
+    CreateSubscription as CreateStripeSubscription, CreateSubscriptionItems,
+    CreateSubscriptionTrialSettings, CreateSubscriptionTrialSettingsEndBehavior,
+    CreateSubscriptionTrialSettingsEndBehaviorMissingPaymentMethod, Customer,
+    CustomerInvoiceSettings, Invoice as StripeInvoice, List, ListInvoices, ListPromotionCodes,
+    Metadata, PromotionCode, SetupIntent, SetupIntentId, Subscription, SubscriptionId,
+    SubscriptionPaymentBehavior, UpdateCustomer, UpdateSubscription, UpdateSubscriptionItems,
+    UpdateSubscriptionPauseCollection, UpdateSubscriptionPauseCollectionBehavior,
+};
+use anyhow::anyhow;
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use serde::Serialize;
+use shared::{
+    domain::billing::{
+        AmountInCents, CustomerId, Invoice, StripeInvoiceId, StripeSubscriptionId,
+        SubscriptionStatus, UpdateSubscriptionRecord,
+    },
+    error::{BillingError, IntoAnyhow},
+};
+use tracing::instrument;
+
+use super::{
+    Collection, CreatedSubscription, NewSubscription, PaymentProvider, PromotionCodeId,
+    SubscriptionChange,
+};
+
+/// Bills customers through Stripe.
+pub struct StripePaymentProvider {
+    client: Client,
+}
+
+impl StripePaymentProvider {
+    pub fn new(secret_key: &str) -> Self {
+        Self {
+            client: Client::new(secret_key),
+        }
+    }
+}
+
+/// Parameters for paying an invoice, which async-stripe doesn't have.
+#[derive(Serialize)]
+struct PayInvoice {
+    paid_out_of_band: bool,
+}
+
+fn stripe_promotion_code_id(
+    id: Option<PromotionCodeId>,
+) -> anyhow::Result<Option<::stripe::PromotionCodeId>> {
+    id.map(|id| ::stripe::PromotionCodeId::from_str(&id.0).into_anyhow())
+        .transpose()
+}
+
+#[async_trait]
+impl PaymentProvider for StripePaymentProvider {
+    #[instrument(skip(self))]
+    async fn create_customer(&self, email: &str, name: &str) -> Result<CustomerId, BillingError> {
+        let create_customer = CreateCustomer {
+            email: Some(email),
+            name: Some(name),
+            ..Default::default()
+        };
+
+        Ok(Customer::create(&self.client, create_customer)
+            .await?
+            .id
+            .into())
+    }
+
+    #[instrument(skip(self))]
+    async fn update_customer(
+        &self,
+        customer_id: &CustomerId,
+        email: Option<&str>,
+        name: Option<&str>,
+    ) -> Result<(), BillingError> {
+        let update_customer = UpdateCustomer {
+            email,
+            name,
+            ..Default::default()
+        };
+
+        Customer::update(&self.client, &customer_id.clone().into(), update_customer).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn create_setup_intent(&self, customer_id: &CustomerId) -> Result<String, BillingError> {
+        let create_setup_intent = CreateSetupIntent {
+            customer: Some(customer_id.clone().into()),
+            payment_method_types: Some(vec!["card".into(), "link".into()]),
+            // TODO need to set `automatic_payment_methods` but it isn't available in async-stripe?
+            ..Default::default()
+        };
+
+        let setup_intent = SetupIntent::create(&self.client, create_setup_intent).await?;
+
+        Ok(setup_intent
+            .client_secret
+            .ok_or(anyhow!("Missing client secret"))?)
+    }
+
+    #[instrument(skip(self))]
+    async fn set_default_payment_method(
+        &self,
+        customer_id: &CustomerId,
+        setup_intent_id: &str,
+    ) -> Result<(), BillingError> {
+        let setup_intent_id = SetupIntentId::from_str(setup_intent_id)
+            .map_err(|_| BillingError::InvalidSetupIntentId)?;
+        let setup_intent = SetupIntent::retrieve(&self.client, &setup_intent_id, &[])
+            .await
+            .map_err(|_| BillingError::InvalidSetupIntentId)?;
+
+        let payment_method_id = setup_intent
+            .payment_method
+            .ok_or(BillingError::InternalServerError(
+                anyhow!("Missing payment_method from SetupIntent").into(),
+            ))?
+            .id()
+            .to_string();
+
+        let update_customer = UpdateCustomer {
+            invoice_settings: Some(CustomerInvoiceSettings {
+                default_payment_method: Some(payment_method_id),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        Customer::update(&self.client, &customer_id.clone().into(), update_customer).await?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn find_promotion_code(&self, code: &str) -> Result<PromotionCodeId, BillingError> {
+        let list_params = ListPromotionCodes {
+            active: Some(true),
+            code: Some(code),
+            ..Default::default()
+        };
+
+        let List {
+            data: mut codes, ..
+        } = PromotionCode::list(&self.client, &list_params).await?;
+
+        if codes.len() != 1 {
+            return Err(BillingError::InvalidPromotionCode(code.to_string()));
+        }
+
+        Ok(PromotionCodeId(codes.pop().unwrap().id.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn create_subscription(
+        &self,
+        subscription: NewSubscription,
+    ) -> Result<CreatedSubscription, BillingError> {
+        let mut params = CreateStripeSubscription::new(subscription.customer_id.into());
+        params.items = Some(vec![CreateSubscriptionItems {
+            price: Some(subscription.price_id.into()),
+            ..Default::default()
+        }]);
+
+        params.promotion_code = stripe_promotion_code_id(subscription.promotion_code_id)?;
+
+        // This will mark the subscription as incomplete until the payment intent has been
+        // confirmed.
+        params.payment_behavior = Some(SubscriptionPaymentBehavior::AllowIncomplete);
+        params.expand = &["latest_invoice.payment_intent"];
+
+        if let Collection::SendInvoice {
+            days_until_due,
+            purchase_order_number,
+        } = subscription.collection
+        {
+            params.collection_method = Some(CollectionMethod::SendInvoice);
+            params.days_until_due = Some(days_until_due);
+            params.metadata = Some(Metadata::from([(
+                "purchase_order_number".to_owned(),
+                purchase_order_number,
+            )]));
+        }
+
+        if let Some(trial_period_days) = subscription.trial_period_days {
+            params.trial_period_days = Some(trial_period_days);
+            params.trial_settings = Some(CreateSubscriptionTrialSettings {
+                end_behavior: CreateSubscriptionTrialSettingsEndBehavior {
+                    missing_payment_method:
+                        CreateSubscriptionTrialSettingsEndBehaviorMissingPaymentMethod::Cancel,
+                },
+            });
+        }
+
+        let stripe_subscription = Subscription::create(&self.client, params).await?;
+
+        let latest_invoice = stripe_subscription
+            .latest_invoice
+            .as_ref()
+            .and_then(|invoice| invoice.as_object());
+
+        let latest_invoice_id = stripe_subscription
+            .latest_invoice
+            .as_ref()
+            .map(|invoice| StripeInvoiceId::from(&invoice.id()));
+
+        let amount_due_in_cents = latest_invoice
+            .and_then(|invoice| invoice.amount_remaining)
+            .map(AmountInCents::new);
+
+        // The client secret is needed to confirm the payment if the user doesn't get a trial, and
+        // needs to add a payment method so that the subscription can be completed.
+        let client_secret = latest_invoice
+            .and_then(|invoice| invoice.payment_intent.as_ref())
+            .and_then(|payment_intent| payment_intent.as_object())
+            .and_then(|payment_intent| payment_intent.client_secret.clone());
+
+        let price = AmountInCents::from(
+            stripe_subscription
+                .items
+                .data
+                .get(0)
+                .map(|item| item.clone())
+                .ok_or(anyhow!("Missing plan data"))?
+                .plan
+                .ok_or(anyhow!("Missing stripe subscription plan"))?
+                .amount
+                .ok_or(anyhow!("Missing subscription plan amount"))?,
+        );
+
+        Ok(CreatedSubscription {
+            subscription_id: stripe_subscription.id.into(),
+            status: SubscriptionStatus::from(stripe_subscription.status),
+            current_period_end: Utc
+                .timestamp_opt(stripe_subscription.current_period_end, 0)
+                .latest()
+                .ok_or(anyhow!("Invalid timestamp"))?,
+            latest_invoice_id,
+            amount_due_in_cents,
+            price,
+            client_secret,
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn update_subscription(
+        &self,
+        subscription_id: &StripeSubscriptionId,
+        change: SubscriptionChange,
+    ) -> Result<UpdateSubscriptionRecord, BillingError> {
+        let stripe_id = SubscriptionId::try_from(subscription_id.clone())?;
+
+        let update_subscription = match change {
+            SubscriptionChange::CancelAtPeriodEnd => UpdateSubscription {
+                cancel_at_period_end: Some(true),
+                ..Default::default()
+            },
+            SubscriptionChange::RemoveCancellation => UpdateSubscription {
+                cancel_at_period_end: Some(false),
+                ..Default::default()
+            },
+            SubscriptionChange::Pause => UpdateSubscription {
+                pause_collection: Some(UpdateSubscriptionPauseCollection {
+                    behavior: UpdateSubscriptionPauseCollectionBehavior::Void,
+                    resumes_at: None,
+                }),
+                ..Default::default()
+            },
+            SubscriptionChange::Resume => UpdateSubscription {
+                pause_collection: Some(UpdateSubscriptionPauseCollection {
+                    behavior: UpdateSubscriptionPauseCollectionBehavior::Void,
+                    // Resume right now. There is no way to remove the pause_collection using this crate,
+                    // so instead we just set the resumes_at time to right now.
+                    resumes_at: Some(Utc::now().timestamp()),
+                }),
+                ..Default::default()
+            },
+            SubscriptionChange::ChangePrice {
+                price_id,
+                promotion_code_id,
+            } => {
+                let stripe_subscription =
+                    Subscription::retrieve(&self.client, &stripe_id, &[]).await?;
+
+                let price_item = stripe_subscription.items.data.first().ok_or(anyhow!(
+                    "Expected exactly one subscription item for subscription {stripe_id}"
+                ))?;
+
+                UpdateSubscription {
+                    items: Some(vec![UpdateSubscriptionItems {
+                        id: Some(price_item.id.to_string()),
+                        price: Some(price_id.into()),
+                        ..Default::default()
+                    }]),
+                    promotion_code: stripe_promotion_code_id(promotion_code_id)?,
+                    proration_behavior: Some(SubscriptionProrationBehavior::AlwaysInvoice),
+                    ..Default::default()
+                }
+            }
+        };
+
+        let updated_subscription =
+            Subscription::update(&self.client, &stripe_id, update_subscription).await?;
+
+        Ok(UpdateSubscriptionRecord::try_from(updated_subscription)?)
+    }
+
+    #[instrument(skip(self))]
+    async fn list_invoices(&self, customer_id: &CustomerId) -> Result<Vec<Invoice>, BillingError> {
+        let mut list_params = ListInvoices {
+            customer: Some(customer_id.clone().into()),
+            // The most Stripe returns in one go.
+            limit: Some(100),
+            ..Default::default()
+        };
+
+        let mut invoices = Vec::new();
+
+        loop {
+            let List { data, has_more, .. } =
+                StripeInvoice::list(&self.client, &list_params).await?;
+
+            let last_id = data.last().map(|invoice| invoice.id.clone());
+
+            for invoice in data {
+                invoices.push(Invoice::try_from(invoice)?);
+            }
+
+            match last_id {
+                Some(last_id) if has_more => list_params.starting_after = Some(last_id),
+                _ => break,
+            }
+        }
+
+        Ok(invoices)
+    }
+
+    #[instrument(skip(self))]
+    async fn mark_invoice_paid(
+        &self,
+        invoice_id: &StripeInvoiceId,
+    ) -> Result<Invoice, BillingError> {
+        let invoice: StripeInvoice = self
+            .client
+            .post_form(
+                &format!("/invoices/{invoice_id}/pay"),
+                PayInvoice {
+                    paid_out_of_band: true,
+                },
+            )
+            .await?;
+
+        Ok(Invoice::try_from(invoice)?)
+    }
+
+    #[instrument(skip(self))]
+    async fn create_portal_link(&self, customer_id: &CustomerId) -> Result<String, BillingError> {
+        let create_session = CreateBillingPortalSession::new(customer_id.clone().into());
+
+        let session = BillingPortalSession::create(&self.client, create_session).await?;
+
+        Ok(session.url)
+    }
+}
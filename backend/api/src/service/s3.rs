@@ -1,14 +1,22 @@
 use anyhow::Context;
 use aws_credential_types::Credentials;
-use aws_sdk_s3::{config::Region, primitives::ByteStream, Client as S3Client};
+use aws_sdk_s3::{
+    config::Region, operation::get_object::GetObjectError, primitives::ByteStream, types::Object,
+    Client as S3Client,
+};
 use ji_core::settings::S3Settings;
-use shared::media::{self, media_key, FileKind, MediaLibrary, PngImageFile};
+use shared::media::{self, media_key, media_prefix, FileKind, MediaLibrary};
 use tracing::instrument;
 use uuid::Uuid;
 
+/// Prefix of the keys of quarantined media in the processing bucket.
+const QUARANTINE_PREFIX: &str = "quarantine/";
+
 #[derive(Clone)]
 pub struct Client {
     media_bucket: String,
+    /// Bucket which isn't served by the CDN, quarantined media is kept in it.
+    processing_bucket: String,
     client: S3Client,
 }
 
@@ -17,9 +25,9 @@ impl Client {
         let S3Settings {
             endpoint,
             media_bucket,
+            processing_bucket,
             access_key_id,
             secret_access_key,
-            ..
         } = s3_settings;
 
         let credentials = Credentials::new(access_key_id, secret_access_key, None, None, "static");
@@ -36,56 +44,186 @@ impl Client {
 
         Ok(Self {
             media_bucket,
+            processing_bucket,
             client,
         })
     }
 
-    #[instrument(skip(self, library, original, resized, thumbnail))]
-    pub async fn upload_png_images(
+    /// Uploads every file generated for an image, see
+    /// [`generate_images`](crate::image_ops::generate_images).
+    #[instrument(skip(self, library, files))]
+    pub async fn upload_images(
         &self,
         library: MediaLibrary,
         image: Uuid,
-        original: Vec<u8>,
-        resized: Vec<u8>,
-        thumbnail: Vec<u8>,
+        files: Vec<(FileKind, Vec<u8>)>,
     ) -> anyhow::Result<()> {
-        self.upload_media(
-            original,
-            library,
-            image,
-            FileKind::ImagePng(PngImageFile::Original),
-        )
-        .await?;
-        self.upload_media(
-            resized,
-            library,
-            image,
-            FileKind::ImagePng(PngImageFile::Resized),
+        for (file_kind, data) in files {
+            self.upload_media(data, library, image, file_kind).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Downloads a stored file, `None` if there's no such file.
+    pub async fn download_media(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        let object = match self
+            .client
+            .get_object()
+            .bucket(&self.media_bucket)
+            .key(media_key(library, id, file_kind))
+            .send()
+            .await
+        {
+            Ok(object) => object,
+            Err(err)
+                if err
+                    .as_service_error()
+                    .map_or(false, GetObjectError::is_no_such_key) =>
+            {
+                return Ok(None)
+            }
+            Err(err) => return Err(err).context("failed to download object from s3"),
+        };
+
+        let data = object
+            .body
+            .collect()
+            .await
+            .context("failed to read object from s3")?;
+
+        Ok(Some(data.into_bytes().to_vec()))
+    }
+
+    /// Total size in bytes of every file stored for some media, in any format.
+    pub async fn stored_size(&self, library: MediaLibrary, id: Uuid) -> anyhow::Result<u64> {
+        let objects = self
+            .list_objects(&self.media_bucket, &media_prefix(library, id))
+            .await?;
+
+        Ok(objects
+            .iter()
+            .filter_map(|object| object.size())
+            .sum::<i64>() as u64)
+    }
+
+    /// Moves every file stored for some media to the processing bucket, so that it isn't served
+    /// while the media is quarantined.
+    ///
+    /// Copies of the files which the CDN cached before are served until they expire.
+    pub async fn quarantine(&self, library: MediaLibrary, id: Uuid) -> anyhow::Result<()> {
+        let prefix = media_prefix(library, id);
+        let quarantined = format!("{QUARANTINE_PREFIX}{prefix}");
+
+        self.move_objects(
+            (&self.media_bucket, &prefix),
+            (&self.processing_bucket, &quarantined),
         )
-        .await?;
-        self.upload_media(
-            thumbnail,
-            library,
-            image,
-            FileKind::ImagePng(PngImageFile::Thumbnail),
+        .await
+    }
+
+    /// Moves the files of media which was released from quarantine back to the media bucket, see
+    /// [`quarantine`](Self::quarantine).
+    pub async fn release(&self, library: MediaLibrary, id: Uuid) -> anyhow::Result<()> {
+        let prefix = media_prefix(library, id);
+        let quarantined = format!("{QUARANTINE_PREFIX}{prefix}");
+
+        self.move_objects(
+            (&self.processing_bucket, &quarantined),
+            (&self.media_bucket, &prefix),
         )
-        .await?;
+        .await
+    }
+
+    /// Deletes the files of deleted media which was quarantined, see
+    /// [`quarantine`](Self::quarantine).
+    pub async fn delete_quarantined(&self, library: MediaLibrary, id: Uuid) {
+        let prefix = format!("{QUARANTINE_PREFIX}{}", media_prefix(library, id));
+
+        let res = async {
+            for object in self.list_objects(&self.processing_bucket, &prefix).await? {
+                if let Some(key) = object.key() {
+                    self.try_delete(&self.processing_bucket, key.to_owned())
+                        .await?;
+                }
+            }
+
+            anyhow::Ok(())
+        };
+
+        if let Err(err) = res.await {
+            log::warn!("failed to delete {} from s3: {}", prefix, err);
+        }
+    }
+
+    /// Moves every object whose key starts with the `from` prefix to the `to` prefix, replacing it.
+    async fn move_objects(&self, from: (&str, &str), to: (&str, &str)) -> anyhow::Result<()> {
+        let (from_bucket, from_prefix) = from;
+        let (to_bucket, to_prefix) = to;
+
+        for object in self.list_objects(from_bucket, from_prefix).await? {
+            let Some(key) = object.key() else { continue };
+
+            self.client
+                .copy_object()
+                .copy_source(format!("{from_bucket}/{key}"))
+                .bucket(to_bucket)
+                .key(format!("{to_prefix}{}", &key[from_prefix.len()..]))
+                .send()
+                .await
+                .context("failed to copy object in s3")?;
+
+            self.try_delete(from_bucket, key.to_owned()).await?;
+        }
 
         Ok(())
     }
 
+    async fn list_objects(&self, bucket: &str, prefix: &str) -> anyhow::Result<Vec<Object>> {
+        let mut pages = self
+            .client
+            .list_objects_v2()
+            .bucket(bucket)
+            .prefix(prefix)
+            .into_paginator()
+            .send();
+
+        let mut objects = Vec::new();
+        while let Some(page) = pages.next().await {
+            let page = page.context("failed to list objects in s3")?;
+            objects.extend_from_slice(page.contents());
+        }
+
+        Ok(objects)
+    }
+
+    /// Deletes every file which may be stored for an image, in any format.
+    pub async fn delete_images(&self, library: MediaLibrary, image: Uuid) {
+        futures::future::join_all(
+            FileKind::image_files()
+                .into_iter()
+                .map(|file_kind| self.delete_media(library, file_kind, image)),
+        )
+        .await;
+    }
+
     pub async fn delete_media(&self, library: MediaLibrary, file: FileKind, id: Uuid) {
         let key = media_key(library, id, file);
-        if let Err(err) = self.try_delete(key.clone()).await {
+        if let Err(err) = self.try_delete(&self.media_bucket, key.clone()).await {
             log::warn!("failed to delete {} from s3: {}", key, err);
         }
     }
 
     // note: does nothing if object doesn't exist.
-    async fn try_delete(&self, key: String) -> anyhow::Result<()> {
+    async fn try_delete(&self, bucket: &str, key: String) -> anyhow::Result<()> {
         self.client
             .delete_object()
-            .bucket(&self.media_bucket)
+            .bucket(bucket)
             .key(key)
             .send()
             .await
@@ -1,7 +1,18 @@
 use ji_core::config::{
     ANIMATION_BODY_SIZE_LIMIT, AUDIO_BODY_SIZE_LIMIT, IMAGE_BODY_SIZE_LIMIT, PDF_BODY_SIZE_LIMIT,
 };
-use shared::media::{FileKind, PngImageFile};
+use serde::Serialize;
+use shared::{
+    domain::{
+        billing::PlanTier,
+        user::{UserId, UserStorageUsage},
+    },
+    media::{FileKind, MediaLibrary, PngImageFile},
+};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::{db, error, service::s3};
 
 pub struct Client;
 
@@ -16,3 +27,116 @@ impl Client {
         }
     }
 }
+
+/// The user's storage usage, with the quota of their account's plan tier.
+///
+/// `excluding` is a file which is about to be replaced, and doesn't count towards the usage.
+pub async fn usage(
+    db: &PgPool,
+    user_id: UserId,
+    excluding: Option<Uuid>,
+) -> anyhow::Result<UserStorageUsage> {
+    let plan_tier = db::account::get_user_account_summary(db, &user_id)
+        .await?
+        .map_or(PlanTier::Free, |summary| summary.plan_tier);
+
+    let used_bytes = db::user::media::storage_used(db, user_id, excluding).await?;
+
+    Ok(UserStorageUsage {
+        used_bytes,
+        quota_bytes: plan_tier.storage_quota_bytes(),
+        plan_tier,
+    })
+}
+
+/// Checks that the user has room for an upload of `size` bytes (replacing `replacing`, if any),
+/// before it's processed.
+///
+/// Returns the user's [`Quota`], which is enforced again once the stored size of the upload, with
+/// all of its renditions, is known.
+pub async fn check_quota(
+    db: &PgPool,
+    user_id: UserId,
+    replacing: Option<Uuid>,
+    size: usize,
+) -> Result<Quota, error::Upload> {
+    let usage = usage(db, user_id, replacing).await?;
+
+    if usage.used_bytes + size as u64 > usage.quota_bytes {
+        return Err(error::Upload::QuotaExceeded {
+            used: usage.used_bytes,
+            quota: usage.quota_bytes,
+        });
+    }
+
+    Ok(Quota {
+        user_id,
+        quota_bytes: usage.quota_bytes,
+    })
+}
+
+/// A user's storage quota, see [`check_quota`].
+pub struct Quota {
+    user_id: UserId,
+    quota_bytes: u64,
+}
+
+impl Quota {
+    /// Checks that the upload `id` fits in the quota once it's stored as `size_bytes`, as part of
+    /// the transaction which stores it.
+    ///
+    /// The user is locked until the transaction ends, so that their concurrent uploads are counted
+    /// one after the other.
+    pub async fn reserve(
+        &self,
+        txn: &mut PgConnection,
+        id: Uuid,
+        size_bytes: usize,
+    ) -> Result<(), error::Upload> {
+        db::user::media::lock_storage(txn, self.user_id).await?;
+
+        let used = db::user::media::storage_used(&mut *txn, self.user_id, Some(id)).await?;
+
+        if used + size_bytes as u64 > self.quota_bytes {
+            return Err(error::Upload::QuotaExceeded {
+                used,
+                quota: self.quota_bytes,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// The uploads whose size was counted in a run of [`backfill_sizes`].
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeReport {
+    pub counted: Vec<Uuid>,
+    /// Bytes stored for the counted uploads.
+    pub bytes: u64,
+}
+
+/// Counts the stored size of up to `limit` uploads which were stored before sizes were counted,
+/// from the sizes of their files in S3.
+///
+/// Errors from S3 end the run, the uploads which weren't counted yet are tried again in the next
+/// one.
+pub async fn backfill_sizes(
+    db: &PgPool,
+    s3: &s3::Client,
+    limit: i64,
+) -> anyhow::Result<SizeReport> {
+    let mut report = SizeReport::default();
+
+    for (id, kind) in db::user::media::list_uncounted(db, limit).await? {
+        let size_bytes = s3.stored_size(MediaLibrary::User, id).await?;
+
+        db::user::media::set_counted_size(db, id, kind, size_bytes).await?;
+
+        report.counted.push(id);
+        report.bytes += size_bytes;
+    }
+
+    Ok(report)
+}
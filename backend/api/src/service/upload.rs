@@ -1,16 +1,23 @@
 use shared::{
     domain::{
-        animation::AnimationKind,
         image::{ImageFileKind, ImageSize},
+        moderation::FlaggedContentKind,
+        user::UserMediaKind,
     },
     media::{FileKind, MediaLibrary},
 };
 use sqlx::{Postgres, Transaction};
 use uuid::Uuid;
 
-use crate::{error, service};
+use crate::{
+    content_safety::ContentSafety, db, error, image_ops::MediaKind, service,
+    service::storage::Quota,
+};
 
 pub mod cleaner;
+pub mod orphans;
+pub mod references;
+pub mod renditions;
 
 pub async fn process_image_bytes(
     txn: &mut Transaction<'_, Postgres>,
@@ -26,10 +33,7 @@ pub async fn process_image_bytes(
     .await
     .unwrap()?;
 
-    if matches!(
-        kind.to_shared(),
-        shared::media::MediaKind::Animation(AnimationKind::Gif)
-    ) {
+    if matches!(kind, MediaKind::GifAnimation) {
         if size != ImageSize::Sticker {
             return Err(error::Upload::InvalidMedia);
         }
@@ -47,21 +51,36 @@ pub async fn process_image_bytes(
         return Ok(());
     }
 
+    let is_svg = matches!(kind, MediaKind::SvgStickerImage);
+    if is_svg && size != ImageSize::Sticker {
+        return Err(error::Upload::InvalidMedia);
+    }
+
     let processed = tokio::task::spawn_blocking(move || -> Result<_, error::Upload> {
-        let original = image::load_from_memory(&file).map_err(|_| error::Upload::InvalidMedia)?;
+        if is_svg {
+            let (svg, raster) = crate::image_ops::load_svg(&file, size)?;
+            return Ok(crate::image_ops::generate_svg_images(svg, &raster, size)?);
+        }
+
+        let original = crate::image_ops::load_image(&file)?;
         Ok(crate::image_ops::generate_images(&original, size)?)
     })
     .await
     .unwrap()?;
 
-    let (original, resized, thumbnail) = processed;
-    s3.upload_png_images(MediaLibrary::Global, id, original, resized, thumbnail)
+    let file_kind = if is_svg {
+        ImageFileKind::Svg
+    } else {
+        ImageFileKind::Png
+    };
+
+    s3.upload_images(MediaLibrary::Global, id, processed)
         .await?;
 
     sqlx::query!(
-        "update image_upload set uploaded_at = now(), processed_at = now(), processing_result = true, kind = $2 where image_id = $1",
+        "update image_upload set uploaded_at = now(), processed_at = now(), processing_result = true, kind = $2, renditions_at = now() where image_id = $1",
         id,
-        ImageFileKind::Png as i16,
+        file_kind as i16,
     )
     .execute(&mut *txn)
     .await?;
@@ -69,9 +88,35 @@ pub async fn process_image_bytes(
     Ok(())
 }
 
+/// Processes and stores an image uploaded to a user's library, quarantining it if it's flagged by
+/// the content safety checks.
 pub async fn process_user_image_bytes(
     txn: &mut Transaction<'_, Postgres>,
     s3: &service::s3::Client,
+    safety: &ContentSafety,
+    quota: &Quota,
+    id: Uuid,
+    size: ImageSize,
+    file: Vec<u8>,
+) -> Result<(), error::Upload> {
+    let reasons = safety.check_image(&file).await;
+
+    store_user_image_bytes(txn, s3, quota, id, size, file).await?;
+
+    db::moderation::flag(txn, FlaggedContentKind::UserImage, id, &reasons).await?;
+
+    // also when replacing an image which was already quarantined
+    if db::moderation::is_quarantined(txn, FlaggedContentKind::UserImage, id).await? {
+        s3.quarantine(MediaLibrary::User, id).await?;
+    }
+
+    Ok(())
+}
+
+async fn store_user_image_bytes(
+    txn: &mut Transaction<'_, Postgres>,
+    s3: &service::s3::Client,
+    quota: &Quota,
     id: Uuid,
     size: ImageSize,
     file: Vec<u8>,
@@ -83,10 +128,10 @@ pub async fn process_user_image_bytes(
     .await
     .unwrap()?;
 
-    if matches!(
-        kind.to_shared(),
-        shared::media::MediaKind::Animation(AnimationKind::Gif)
-    ) {
+    if matches!(kind, MediaKind::GifAnimation) {
+        let size_bytes = file.len();
+        quota.reserve(txn, id, size_bytes).await?;
+
         process_uploaded_gif(
             txn,
             s3,
@@ -97,24 +142,58 @@ pub async fn process_user_image_bytes(
             "image_id",
         )
         .await?;
+
+        sqlx::query!(
+            "update user_image_library set size_bytes = $2 where id = $1",
+            id,
+            size_bytes as i64,
+        )
+        .execute(&mut *txn)
+        .await?;
+
         return Ok(());
     }
 
+    let is_svg = matches!(kind, MediaKind::SvgStickerImage);
+    if is_svg && size != ImageSize::Sticker {
+        return Err(error::Upload::InvalidMedia);
+    }
+
     let processed = tokio::task::spawn_blocking(move || -> Result<_, error::Upload> {
-        let original = image::load_from_memory(&file).map_err(|_| error::Upload::InvalidMedia)?;
+        if is_svg {
+            let (svg, raster) = crate::image_ops::load_svg(&file, size)?;
+            return Ok(crate::image_ops::generate_svg_images(svg, &raster, size)?);
+        }
+
+        let original = crate::image_ops::load_image(&file)?;
         Ok(crate::image_ops::generate_images(&original, size)?)
     })
     .await
     .unwrap()?;
 
-    let (original, resized, thumbnail) = processed;
-    s3.upload_png_images(MediaLibrary::User, id, original, resized, thumbnail)
-        .await?;
+    let file_kind = if is_svg {
+        ImageFileKind::Svg
+    } else {
+        ImageFileKind::Png
+    };
+
+    let size_bytes: usize = processed.iter().map(|(_, data)| data.len()).sum();
+    quota.reserve(txn, id, size_bytes).await?;
+
+    s3.upload_images(MediaLibrary::User, id, processed).await?;
 
     sqlx::query!(
-        "update user_image_upload set uploaded_at = now(), processed_at = now(), processing_result = true, kind = $2 where image_id = $1",
+        "update user_image_library set size_bytes = $2 where id = $1",
         id,
-        ImageFileKind::Png as i16,
+        size_bytes as i64,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    sqlx::query!(
+        "update user_image_upload set uploaded_at = now(), processed_at = now(), processing_result = true, kind = $2, renditions_at = now() where image_id = $1",
+        id,
+        file_kind as i16,
     )
     .execute(&mut *txn)
     .await?;
@@ -149,13 +228,11 @@ async fn process_uploaded_gif(
 }
 
 async fn validate_gif(file: Vec<u8>) -> Result<(), error::Upload> {
-    tokio::task::spawn_blocking(move || -> Result<(), error::Upload> {
-        image::load_from_memory_with_format(&file, image::ImageFormat::Gif)
-            .map_err(|_| error::Upload::InvalidMedia)?;
-        Ok(())
-    })
-    .await
-    .unwrap()
+    tokio::task::spawn_blocking(move || crate::image_ops::validate_gif(&file))
+        .await
+        .unwrap()?;
+
+    Ok(())
 }
 
 pub async fn process_animation_bytes(
@@ -182,12 +259,30 @@ pub async fn process_animation_bytes(
 pub async fn process_user_audio_bytes(
     txn: &mut Transaction<'_, Postgres>,
     s3: &service::s3::Client,
+    quota: &Quota,
     id: Uuid,
     file: Vec<u8>,
 ) -> Result<(), error::Upload> {
-    s3.upload_media(file, MediaLibrary::User, id, FileKind::AudioMp3)
+    let processed = crate::audio_ops::process_audio(file).await?;
+    let size_bytes = processed.mp3.len() + processed.opus.len();
+    quota.reserve(txn, id, size_bytes).await?;
+
+    s3.upload_media(processed.mp3, MediaLibrary::User, id, FileKind::AudioMp3)
+        .await?;
+
+    s3.upload_media(processed.opus, MediaLibrary::User, id, FileKind::AudioOpus)
         .await?;
 
+    sqlx::query!(
+        "update user_audio_library set duration_ms = $2, waveform = $3, size_bytes = $4 where id = $1",
+        id,
+        processed.duration_ms as i32,
+        &processed.peaks,
+        size_bytes as i64,
+    )
+    .execute(&mut *txn)
+    .await?;
+
     sqlx::query!(
         "update user_audio_upload set uploaded_at = now(), processed_at = now(), processing_result = true where audio_id = $1",
         id
@@ -198,15 +293,76 @@ pub async fn process_user_audio_bytes(
     Ok(())
 }
 
+/// Processes and stores a pdf uploaded to a user's library, quarantining it if its text or first
+/// page are flagged by the content safety checks.
 pub async fn process_user_pdf_bytes(
     txn: &mut Transaction<'_, Postgres>,
     s3: &service::s3::Client,
+    safety: &ContentSafety,
+    quota: &Quota,
     id: Uuid,
     file: Vec<u8>,
 ) -> Result<(), error::Upload> {
+    let processed = crate::pdf_ops::process_pdf(&file).await?;
+
+    let mut reasons = safety.check_text(&[("text", processed.text.as_str())]);
+    if let Some(first_page) = processed.previews.first() {
+        reasons.extend(safety.check_image(first_page).await);
+    }
+    let size_bytes = file.len() + processed.previews.iter().map(Vec::len).sum::<usize>();
+    quota.reserve(txn, id, size_bytes).await?;
+
     s3.upload_media(file, MediaLibrary::User, id, FileKind::DocumentPdf)
         .await?;
 
+    let previous_page_count = sqlx::query!(
+        "select preview_page_count from user_pdf_library where id = $1",
+        id
+    )
+    .fetch_one(&mut *txn)
+    .await?
+    .preview_page_count as u32;
+
+    let preview_page_count = processed.previews.len() as u32;
+    for (page, preview) in (1..).zip(processed.previews) {
+        s3.upload_media(
+            preview,
+            MediaLibrary::User,
+            id,
+            FileKind::DocumentPdfPage(page),
+        )
+        .await?;
+    }
+
+    // a replaced pdf may have had more pages
+    for page in preview_page_count + 1..=previous_page_count {
+        s3.delete_media(MediaLibrary::User, FileKind::DocumentPdfPage(page), id)
+            .await;
+    }
+
+    sqlx::query!(
+        "update user_pdf_library set page_count = $2, preview_page_count = $3, content_text = $4, size_bytes = $5 where id = $1",
+        id,
+        processed.page_count as i32,
+        preview_page_count as i32,
+        processed.text,
+        size_bytes as i64,
+    )
+    .execute(&mut *txn)
+    .await?;
+
+    // resources linking to the pdf get re-indexed with its text
+    sqlx::query!(
+        r#"
+update resource_data
+set last_synced_at = null
+where id in (select resource_data_id from resource_data_resource where resource_content ->> 'pdfId' = $1::text)
+"#,
+        id.to_string(),
+    )
+    .execute(&mut *txn)
+    .await?;
+
     sqlx::query!(
         "update user_pdf_upload set uploaded_at = now(), processed_at = now(), processing_result = true where pdf_id = $1",
         id
@@ -214,5 +370,61 @@ pub async fn process_user_pdf_bytes(
     .execute(&mut *txn)
     .await?;
 
+    db::moderation::flag(txn, FlaggedContentKind::UserPdf, id, &reasons).await?;
+
+    // also when replacing a pdf which was already quarantined
+    if db::moderation::is_quarantined(txn, FlaggedContentKind::UserPdf, id).await? {
+        s3.quarantine(MediaLibrary::User, id).await?;
+    }
+
     Ok(())
 }
+
+/// Deletes the stored files of uploads which were deleted from the user libraries, where `pdfs`
+/// are the deleted pdfs with their page preview counts, as returned by
+/// [`db::user::media::delete`](crate::db::user::media::delete).
+pub async fn delete_user_media_files(
+    s3: &service::s3::Client,
+    media: &[(Uuid, UserMediaKind)],
+    pdfs: &[(Uuid, u32)],
+) {
+    for (id, kind) in media {
+        match kind {
+            UserMediaKind::Image => {
+                s3.delete_images(MediaLibrary::User, *id).await;
+                s3.delete_media(MediaLibrary::User, FileKind::AnimationGif, *id)
+                    .await;
+                s3.delete_quarantined(MediaLibrary::User, *id).await;
+            }
+            UserMediaKind::Audio => {
+                s3.delete_media(MediaLibrary::User, FileKind::AudioMp3, *id)
+                    .await;
+                s3.delete_media(MediaLibrary::User, FileKind::AudioOpus, *id)
+                    .await;
+            }
+            // deleted below, with their page previews
+            UserMediaKind::Pdf => {}
+        }
+    }
+
+    for (id, preview_page_count) in pdfs {
+        for file_kind in FileKind::pdf_files(*preview_page_count) {
+            s3.delete_media(MediaLibrary::User, file_kind, *id).await;
+        }
+        s3.delete_quarantined(MediaLibrary::User, *id).await;
+    }
+}
+
+/// Deletes the stored files of media which was deleted from the web media library.
+pub async fn delete_web_media_files(s3: &service::s3::Client, id: Uuid, kind: MediaKind) {
+    match kind {
+        MediaKind::PngStickerImage | MediaKind::SvgStickerImage => {
+            s3.delete_images(MediaLibrary::Web, id).await;
+        }
+        MediaKind::GifAnimation => {
+            s3.delete_media(MediaLibrary::Web, FileKind::AnimationGif, id)
+                .await;
+        }
+        kind => log::warn!("web media {} has unsupported kind {:?}", id, kind),
+    }
+}
@@ -0,0 +1,289 @@
+//! Deleting uploaded media which nothing references any more.
+//!
+//! Media in the web media library is deleted once no content references it (see [`references`])
+//! and it hasn't been added for [`ORPHANED_MEDIA_GRACE_PERIOD_DAYS`]. Uploads to the user
+//! libraries are only cleaned when asked for, since users keep media in their library to use
+//! later. The global image and animation libraries are curated, so they're never cleaned.
+//!
+//! Content may start using media while the references are being scanned, so before deleting,
+//! the orphans are locked and checked again in the transaction which deletes them.
+
+use std::collections::{HashMap, HashSet};
+
+use ji_core::config::ORPHANED_MEDIA_GRACE_PERIOD_DAYS;
+use serde::Serialize;
+use shared::domain::user::{UserId, UserMediaKind};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::references;
+use crate::{db, image_ops::MediaKind, service::s3};
+
+/// The media which was found to be orphaned, and deleted unless it was a dry run.
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanReport {
+    pub dry_run: bool,
+    pub user_images: Vec<Uuid>,
+    pub user_audio: Vec<Uuid>,
+    pub user_pdfs: Vec<Uuid>,
+    pub web_media: Vec<Uuid>,
+    /// Bytes stored for the orphaned user uploads.
+    pub user_bytes: u64,
+}
+
+/// Orphaned media which has been deleted from the database, but whose files haven't been deleted
+/// yet.
+pub struct Orphans {
+    pub report: OrphanReport,
+    user_media: Vec<(Uuid, UserMediaKind)>,
+    pdfs: Vec<(Uuid, u32)>,
+    web_media: Vec<(Uuid, MediaKind)>,
+}
+
+impl Orphans {
+    /// Deletes the files of the orphaned media.
+    pub async fn delete_files(self, s3: &s3::Client) -> OrphanReport {
+        super::delete_user_media_files(s3, &self.user_media, &self.pdfs).await;
+
+        for (id, kind) in self.web_media {
+            super::delete_web_media_files(s3, id, kind).await;
+        }
+
+        self.report
+    }
+}
+
+struct UserUpload {
+    id: Uuid,
+    user_id: UserId,
+    kind: UserMediaKind,
+    size_bytes: u64,
+}
+
+/// Finds orphaned media, and deletes it from the database unless `dry_run` is set.
+///
+/// Uploads to the user libraries are only included if `include_user_library` is set.
+pub async fn clean(
+    db: &PgPool,
+    dry_run: bool,
+    include_user_library: bool,
+) -> anyhow::Result<Orphans> {
+    let mut orphans = Orphans {
+        report: OrphanReport {
+            dry_run,
+            ..OrphanReport::default()
+        },
+        user_media: Vec::new(),
+        pdfs: Vec::new(),
+        web_media: Vec::new(),
+    };
+
+    let (mut user_uploads, mut web_media) =
+        find(&mut *db.acquire().await?, include_user_library).await?;
+
+    if dry_run {
+        add_to_report(&mut orphans.report, &user_uploads, &web_media);
+        return Ok(orphans);
+    }
+
+    if user_uploads.is_empty() && web_media.is_empty() {
+        return Ok(orphans);
+    }
+
+    let mut txn = db.begin().await?;
+
+    let ids: Vec<_> = user_uploads
+        .iter()
+        .map(|upload| upload.id)
+        .chain(web_media.iter().map(|(id, _)| *id))
+        .collect();
+
+    let locked = lock_expired(&mut txn, &ids).await?;
+    let live = references::scan(&mut txn).await?;
+
+    let still_orphaned = |id: &Uuid| locked.contains(id) && !live.contains(id);
+    user_uploads.retain(|upload| still_orphaned(&upload.id));
+    web_media.retain(|(id, _)| still_orphaned(id));
+
+    add_to_report(&mut orphans.report, &user_uploads, &web_media);
+
+    let mut by_user: HashMap<UserId, Vec<Uuid>> = HashMap::new();
+    for upload in user_uploads {
+        by_user.entry(upload.user_id).or_default().push(upload.id);
+        orphans.user_media.push((upload.id, upload.kind));
+    }
+
+    for (user_id, ids) in by_user {
+        let pdfs = db::user::media::delete(&mut txn, user_id, &ids).await?;
+        orphans.pdfs.extend(pdfs);
+    }
+
+    orphans.web_media = db::media::delete(&mut txn, &orphans.report.web_media).await?;
+
+    txn.commit().await?;
+
+    Ok(orphans)
+}
+
+/// Finds the media which is past the grace period and isn't referenced.
+async fn find(
+    conn: &mut PgConnection,
+    include_user_library: bool,
+) -> anyhow::Result<(Vec<UserUpload>, Vec<(Uuid, MediaKind)>)> {
+    // candidates are found before scanning, so that media added during the scan isn't included.
+    let user_uploads = match include_user_library {
+        true => expired_user_uploads(&mut *conn).await?,
+        false => Vec::new(),
+    };
+    let web_media = expired_web_media(&mut *conn).await?;
+
+    let live = references::scan(&mut *conn).await?;
+
+    log::info!(
+        "Found {} referenced IDs, checking {} user uploads and {} web media",
+        live.len(),
+        user_uploads.len(),
+        web_media.len()
+    );
+
+    let user_uploads = user_uploads
+        .into_iter()
+        .filter(|upload| !live.contains(&upload.id))
+        .collect();
+
+    let web_media = web_media
+        .into_iter()
+        .filter(|(id, _)| !live.contains(id))
+        .collect();
+
+    Ok((user_uploads, web_media))
+}
+
+fn add_to_report(
+    report: &mut OrphanReport,
+    user_uploads: &[UserUpload],
+    web_media: &[(Uuid, MediaKind)],
+) {
+    report.web_media = web_media.iter().map(|(id, _)| *id).collect();
+    report.user_bytes = user_uploads.iter().map(|upload| upload.size_bytes).sum();
+
+    for upload in user_uploads {
+        match upload.kind {
+            UserMediaKind::Image => report.user_images.push(upload.id),
+            UserMediaKind::Audio => report.user_audio.push(upload.id),
+            UserMediaKind::Pdf => report.user_pdfs.push(upload.id),
+        }
+    }
+}
+
+/// Locks the media in `ids` which is still past the grace period, so that it can't be replaced or
+/// added again before it's deleted.
+async fn lock_expired(txn: &mut PgConnection, ids: &[Uuid]) -> sqlx::Result<HashSet<Uuid>> {
+    let ids = sqlx::query_scalar!(
+        //language=SQL
+        r#"
+with image as (select id
+               from user_image_library
+               left join user_image_upload on image_id = id
+               where id = any($1)
+                 and greatest(created_at, uploaded_at) < now() - make_interval(days => $2)
+               for update of user_image_library),
+     audio as (select id
+               from user_audio_library
+               left join user_audio_upload on audio_id = id
+               where id = any($1)
+                 and greatest(created_at, uploaded_at) < now() - make_interval(days => $2)
+               for update of user_audio_library),
+     pdf as (select id
+             from user_pdf_library
+             left join user_pdf_upload on pdf_id = id
+             where id = any($1)
+               and greatest(created_at, uploaded_at) < now() - make_interval(days => $2)
+             for update of user_pdf_library),
+     web as (select id
+             from web_media_library
+             where id = any($1)
+               and greatest(created_at,
+                            updated_at,
+                            (select max(created_at) from web_media_library_url where media_id = id))
+                 < now() - make_interval(days => $2)
+             for update of web_media_library)
+select id as "id!" from image
+union all
+select id from audio
+union all
+select id from pdf
+union all
+select id from web
+"#,
+        ids,
+        ORPHANED_MEDIA_GRACE_PERIOD_DAYS,
+    )
+    .fetch_all(&mut *txn)
+    .await?;
+
+    Ok(ids.into_iter().collect())
+}
+
+/// Uploads to the user libraries which haven't been added or replaced within the grace period.
+async fn expired_user_uploads(conn: &mut PgConnection) -> sqlx::Result<Vec<UserUpload>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select id                as "id!",
+       user_id           as "user_id!: UserId",
+       kind              as "kind!",
+       size_bytes        as "size_bytes!"
+from (select id, user_id, 'image' as kind, size_bytes, greatest(created_at, uploaded_at) as changed_at
+      from user_image_library
+      left join user_image_upload on image_id = id
+      union all
+      select id, user_id, 'audio' as kind, size_bytes, greatest(created_at, uploaded_at) as changed_at
+      from user_audio_library
+      left join user_audio_upload on audio_id = id
+      union all
+      select id, user_id, 'pdf' as kind, size_bytes, greatest(created_at, uploaded_at) as changed_at
+      from user_pdf_library
+      left join user_pdf_upload on pdf_id = id) as media
+where changed_at < now() - make_interval(days => $1)
+"#,
+        ORPHANED_MEDIA_GRACE_PERIOD_DAYS,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| UserUpload {
+            id: row.id,
+            user_id: row.user_id,
+            kind: match row.kind.as_str() {
+                "image" => UserMediaKind::Image,
+                "audio" => UserMediaKind::Audio,
+                _ => UserMediaKind::Pdf,
+            },
+            size_bytes: row.size_bytes as u64,
+        })
+        .collect())
+}
+
+/// Web media which hasn't been added from any url within the grace period.
+async fn expired_web_media(conn: &mut PgConnection) -> sqlx::Result<Vec<(Uuid, MediaKind)>> {
+    let rows = sqlx::query!(
+        //language=SQL
+        r#"
+select id, kind as "kind: MediaKind"
+from web_media_library
+where greatest(created_at,
+               updated_at,
+               (select max(created_at) from web_media_library_url where media_id = id))
+          < now() - make_interval(days => $1)
+"#,
+        ORPHANED_MEDIA_GRACE_PERIOD_DAYS,
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| (row.id, row.kind)).collect())
+}
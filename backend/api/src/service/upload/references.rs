@@ -0,0 +1,162 @@
+//! Finding which uploaded media is still referenced.
+//!
+//! Module bodies (covers included), additional resources, course units, profile images and
+//! circle images are walked for every ID they contain, in drafts and live data alike. IDs are
+//! collected wherever they appear, rather than only where an `Image`, `Audio` or `PdfId` is
+//! expected, so that media used by a field the scanner doesn't know about yet is never taken to
+//! be unused.
+
+use std::collections::HashSet;
+
+use futures::TryStreamExt;
+use serde_json::Value;
+use sqlx::PgConnection;
+use uuid::Uuid;
+
+/// IDs of all media referenced by content or profiles, which may be an `ImageId`, `AudioId`,
+/// `AnimationId` or `PdfId`.
+#[derive(Default, Debug)]
+pub struct LiveMedia(HashSet<Uuid>);
+
+impl LiveMedia {
+    /// The IDs referenced by a single piece of content.
+    pub fn of(content: &Value) -> Self {
+        let mut live = Self::default();
+        live.add(content);
+        live
+    }
+
+    pub fn contains(&self, id: &Uuid) -> bool {
+        self.0.contains(id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Uuid> {
+        self.0.iter()
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn add(&mut self, value: &Value) {
+        match value {
+            Value::String(s) => self.0.extend(find_uuids(s)),
+            Value::Array(values) => values.iter().for_each(|value| self.add(value)),
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    self.0.extend(find_uuids(key));
+                    self.add(value);
+                }
+            }
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+        }
+    }
+}
+
+/// Walks all content for the media it references.
+pub async fn scan(conn: &mut PgConnection) -> anyhow::Result<LiveMedia> {
+    let mut live = LiveMedia::default();
+
+    let mut rows = sqlx::query_scalar!(
+        //language=SQL
+        r#"
+select contents as "content!" from jig_data_module
+union all
+select resource_content from jig_data_additional_resource
+union all
+select contents from resource_data_module
+union all
+select resource_content from resource_data_resource
+union all
+select contents from playlist_data_module
+union all
+select resource_content from playlist_data_resource
+union all
+select contents from course_data_module
+union all
+select resource_content from course_data_resource
+union all
+select value from course_data_unit
+union all
+select to_jsonb(profile_image_id) from user_profile where profile_image_id is not null
+union all
+select to_jsonb(profile_image_id) from school where profile_image_id is not null
+union all
+select to_jsonb(image) from circle
+union all
+select to_jsonb(image_id) from user_recent_image
+"#
+    )
+    .fetch(&mut *conn);
+
+    while let Some(content) = rows.try_next().await? {
+        live.add(&content);
+    }
+
+    Ok(live)
+}
+
+/// Finds every UUID in `s`, whether it's the whole string or part of one, like a URL.
+fn find_uuids(s: &str) -> impl Iterator<Item = Uuid> + '_ {
+    const LEN: usize = 36;
+
+    let bytes = s.as_bytes();
+
+    (0..(bytes.len() + 1).saturating_sub(LEN)).filter_map(move |start| {
+        let candidate = &bytes[start..start + LEN];
+
+        let is_uuid = candidate.iter().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => *b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        });
+
+        // the candidate is all ascii, so this is a char boundary
+        is_uuid
+            .then(|| Uuid::parse_str(&s[start..start + LEN]).ok())
+            .flatten()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const IMAGE: &str = "8d7e1d02-e4a2-11eb-8176-2b0d5e4d4e21";
+    const AUDIO: &str = "a641fd6e-e41b-11eb-8176-57df101c2201";
+    const PDF: &str = "1f241e1b-b537-493f-a230-075cb16315be";
+
+    #[test]
+    fn finds_uuids_in_strings() {
+        let s = format!("https://example.com/{IMAGE}/thumb.png?v={AUDIO}");
+
+        assert_eq!(
+            vec![IMAGE.parse::<Uuid>().unwrap(), AUDIO.parse().unwrap()],
+            find_uuids(&s).collect::<Vec<_>>()
+        );
+
+        assert_eq!(0, find_uuids("not-a-uuid").count());
+        assert_eq!(0, find_uuids(&IMAGE[1..]).count());
+    }
+
+    #[test]
+    fn collects_ids_from_content() {
+        let mut live = LiveMedia::default();
+
+        live.add(&json!({
+            "content": {
+                "stickers": [{"Sprite": {"image": {"id": IMAGE, "lib": "User", "kind": "Png"}}}],
+                "audio": null,
+                "playSettings": {"hint": "None", "count": 3},
+            }
+        }));
+        live.add(&json!({ "audioId": AUDIO }));
+        live.add(&json!(PDF));
+
+        assert_eq!(3, live.len());
+        for id in [IMAGE, AUDIO, PDF] {
+            assert!(live.contains(&id.parse().unwrap()));
+        }
+    }
+}
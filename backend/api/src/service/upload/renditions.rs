@@ -0,0 +1,177 @@
+//! Storing the WebP and AVIF renditions of PNG images which were uploaded before renditions were
+//! stored.
+//!
+//! The renditions are generated from the stored original PNG. Images whose original is missing or
+//! can't be decoded are marked as done too, `img-ji` falls back to the PNG for them.
+
+use serde::Serialize;
+use shared::{
+    domain::image::ImageSize,
+    media::{FileKind, MediaLibrary, PngImageFile},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::service::s3;
+
+/// The images whose renditions were stored in a run of [`backfill`].
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RenditionReport {
+    pub backfilled: Vec<Uuid>,
+    /// Images whose original is missing or couldn't be decoded, which won't be tried again.
+    pub failed: Vec<Uuid>,
+    /// Bytes stored for the renditions.
+    pub bytes: u64,
+}
+
+/// Stores the renditions of up to `limit` images which are missing them.
+///
+/// Other errors from S3 end the run, the images which weren't done yet are tried again in the next
+/// one.
+pub async fn backfill(db: &PgPool, s3: &s3::Client, limit: i64) -> anyhow::Result<RenditionReport> {
+    let images = sqlx::query!(
+        //language=SQL
+        r#"
+select image_id as "id!", $2::int2 as "library!: MediaLibrary", image_metadata.size as "size!: ImageSize"
+from image_upload
+         inner join image_metadata on image_metadata.id = image_upload.image_id
+where image_upload.renditions_at is null
+  and image_upload.processing_result is true
+  and image_upload.kind = 0
+union all
+select image_id, $3::int2, user_image_library.size
+from user_image_upload
+         inner join user_image_library on user_image_library.id = user_image_upload.image_id
+where user_image_upload.renditions_at is null
+  and user_image_upload.processing_result is true
+  and user_image_upload.kind = 0
+union all
+select media_id, $4::int2, $5::int2
+from web_media_upload
+         inner join web_media_library on web_media_library.id = web_media_upload.media_id
+where web_media_upload.renditions_at is null
+  and web_media_upload.processing_result is true
+  and web_media_library.kind = 0
+limit $1
+"#,
+        limit,
+        MediaLibrary::Global as i16,
+        MediaLibrary::User as i16,
+        MediaLibrary::Web as i16,
+        ImageSize::Sticker as i16,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut report = RenditionReport::default();
+
+    for image in images {
+        let library = image.library;
+
+        let original = s3
+            .download_media(
+                library,
+                image.id,
+                FileKind::ImagePng(PngImageFile::Original),
+            )
+            .await?;
+
+        let renditions = match original {
+            Some(original) => {
+                let size = image.size;
+                tokio::task::spawn_blocking(move || -> anyhow::Result<_> {
+                    let original =
+                        crate::image_ops::load_image(&original).map_err(anyhow::Error::msg)?;
+                    let files = crate::image_ops::generate_images(&original, size)?;
+
+                    Ok(files
+                        .into_iter()
+                        .filter(|(kind, _)| {
+                            matches!(kind, FileKind::ImageWebp(_) | FileKind::ImageAvif(_))
+                        })
+                        .collect::<Vec<_>>())
+                })
+                .await?
+            }
+            None => Err(anyhow::anyhow!("the original isn't stored")),
+        };
+
+        let size_bytes = match renditions {
+            Ok(renditions) => {
+                let size_bytes: usize = renditions.iter().map(|(_, data)| data.len()).sum();
+                s3.upload_images(library, image.id, renditions).await?;
+
+                report.backfilled.push(image.id);
+                report.bytes += size_bytes as u64;
+
+                size_bytes
+            }
+            Err(e) => {
+                log::warn!(
+                    "failed to generate the renditions of {} image {}: {:?}",
+                    library.to_str(),
+                    image.id,
+                    e
+                );
+
+                report.failed.push(image.id);
+
+                0
+            }
+        };
+
+        mark_done(db, library, image.id, size_bytes).await?;
+    }
+
+    Ok(report)
+}
+
+/// Marks the renditions of an image as stored, counting them towards the storage used by users.
+async fn mark_done(
+    db: &PgPool,
+    library: MediaLibrary,
+    id: Uuid,
+    size_bytes: usize,
+) -> sqlx::Result<()> {
+    match library {
+        MediaLibrary::Global => {
+            sqlx::query!(
+                "update image_upload set renditions_at = now() where image_id = $1",
+                id
+            )
+            .execute(db)
+            .await?;
+        }
+        MediaLibrary::User => {
+            let mut txn = db.begin().await?;
+
+            sqlx::query!(
+                "update user_image_upload set renditions_at = now() where image_id = $1",
+                id
+            )
+            .execute(&mut txn)
+            .await?;
+
+            sqlx::query!(
+                "update user_image_library set size_bytes = size_bytes + $2 where id = $1",
+                id,
+                size_bytes as i64,
+            )
+            .execute(&mut txn)
+            .await?;
+
+            txn.commit().await?;
+        }
+        MediaLibrary::Web => {
+            sqlx::query!(
+                "update web_media_upload set renditions_at = now() where media_id = $1",
+                id
+            )
+            .execute(db)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+//! Scratch directories for the files external tools (`ffmpeg`, `poppler`) read and write.
+
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+/// A scratch directory, removed with everything in it when dropped.
+pub struct TempDir {
+    pub path: PathBuf,
+}
+
+impl TempDir {
+    /// Creates a new, empty directory in the system temp dir, named `{prefix}-{uuid}`.
+    pub async fn new(prefix: &str) -> std::io::Result<Self> {
+        let path = std::env::temp_dir().join(format!("{}-{}", prefix, Uuid::new_v4()));
+        tokio::fs::create_dir(&path).await?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.path) {
+            log::warn!("failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
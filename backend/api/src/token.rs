@@ -5,6 +5,7 @@ use paseto::{PasetoBuilder, TimeBackend};
 use rand::Rng;
 use serde_json::json;
 use shared::domain::{
+    class::StudentId,
     session::AUTH_COOKIE_NAME,
     user::{UserId, UserScope},
 };
@@ -16,6 +17,8 @@ use crate::error::{self, BasicError};
 
 const AUTHORIZED_FOOTER: &str = "authorized";
 
+const STUDENT_FOOTER: &str = "student";
+
 pub struct SessionClaims {
     pub user_id: UserId,
     pub token: String,
@@ -210,6 +213,42 @@ pub fn create_player_session_instance_token(
         .map_err(|err| anyhow::anyhow!("failed to create player session instance token: {}", err))
 }
 
+pub fn create_student_token(
+    token_secret: &[u8; 32],
+    valid_duration: Duration,
+    student_id: StudentId,
+    now: DateTime<Utc>,
+) -> anyhow::Result<String> {
+    PasetoBuilder::new()
+        .set_expiration(&(now + valid_duration))
+        .set_not_before(&now)
+        .set_issued_at(Some(now))
+        .set_encryption_key(token_secret)
+        .set_subject(&student_id.to_string())
+        .set_footer(STUDENT_FOOTER)
+        .build()
+        .map_err(|err| anyhow::anyhow!("failed to create student token: {}", err))
+}
+
+/// Validates a token created by [`create_student_token`], returning the student it was created for.
+pub fn validate_student_token(
+    token_string: &str,
+    token_key: &[u8; 32],
+) -> Result<StudentId, actix_web::Error> {
+    #[derive(serde::Deserialize)]
+    struct StudentTokenClaims {
+        sub: StudentId,
+    }
+
+    let token = validate_token(token_string, Some(STUDENT_FOOTER), token_key)?;
+
+    let claims: StudentTokenClaims = serde_json::from_value(token)
+        .map_err(Into::into)
+        .map_err(error::ise)?;
+
+    Ok(claims.sub)
+}
+
 #[instrument(skip_all)]
 pub fn create_update_email_token(
     token_secret: &[u8; 32],
@@ -1,1046 +1,621 @@
+//! Translating the names and descriptions of images and published assets into every language the
+//! site is available in, and copies of JIGs into other languages.
+//!
+//! Translations go through a [`TranslationProvider`], picked by [`provider`] from the keys which
+//! are configured. Terms in the [`Glossary`] are kept out of the provider's hands.
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
 use anyhow::Context;
-use futures::TryStreamExt;
+use async_trait::async_trait;
 use ji_core::settings::RuntimeSettings;
-use reqwest::{self};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use shared::domain::jig::{JigTranslationId, TextDirection};
 use sqlx::{PgConnection, PgPool};
-use std::collections::HashMap;
-
-use shared::domain::image::ImageId;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct TranslateTextRequest {
-    q: String,
-    source: String,
-    target: String,
-    format: String,
-}
+use self::glossary::{Glossary, Protected};
+use crate::{db, error};
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct DetectedLanguageResponse {
-    pub language: String,
-}
+pub mod deepl;
+pub mod glossary;
+pub mod google;
+pub mod local;
+pub mod module;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct DetectLanguageRequest {
-    pub q: String,
-}
+const LANGUAGES: &'static [&str] = &[
+    "en", "iw", "es", "pt", "ru", "fr", "nl", "sv", "ar", "de", "hu", "it", "yi",
+];
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct TranslateTextResponse {
-    data: TranslateTextResponseList,
-}
+/// Languages which are written right to left.
+const RTL_LANGUAGES: &'static [&str] = &["iw", "ar", "yi"];
 
-// Response from google translate detection
-////////////////////////////////////////////////////////////
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct DetectLanguageResponse {
-    data: Detection,
-}
+/// A machine translation service.
+///
+/// Languages are given as Google Translate's codes, which is `iw` for Hebrew.
+#[async_trait]
+pub trait TranslationProvider: Send + Sync {
+    /// Name of the provider, for logs.
+    fn name(&self) -> &'static str;
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct Detection {
-    detections: Vec<Vec<DetectLanguage>>,
-}
+    /// Detects the language `text` is written in, `None` if it can't tell.
+    async fn detect_language(&self, text: &str) -> anyhow::Result<Option<String>>;
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct DetectLanguage {
-    language: String,
-}
+    async fn translate(&self, text: &str, source: &str, target: &str) -> anyhow::Result<String>;
 
-////////////////////////////////////////////////////////////
+    /// Whether the provider can translate into `language`.
+    fn supports(&self, _language: &str) -> bool {
+        true
+    }
+}
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TranslateTextResponseList {
-    translations: Vec<Translation>,
+/// Picks the provider to translate with: DeepL or Google Translate, depending on which key is
+/// configured, or [`local::LocalProvider`] when running locally without either.
+pub fn provider(settings: &RuntimeSettings) -> Option<Arc<dyn TranslationProvider>> {
+    if let Some(api_key) = &settings.deepl_api_key {
+        Some(Arc::new(deepl::DeepL::new(api_key.clone())))
+    } else if let Some(api_key) = &settings.google_api_key {
+        Some(Arc::new(google::GoogleTranslate::new(api_key.clone())))
+    } else if settings.is_local() {
+        Some(Arc::new(local::LocalProvider))
+    } else {
+        None
+    }
 }
 
-#[derive(Debug, Deserialize)]
+/// Translation status of one field of an image or asset, stored by field in its
+/// `translation_status`.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
 #[serde(rename_all = "camelCase")]
-struct Translation {
-    translated_text: String,
-}
+pub enum TranslationStatus {
+    /// The provider couldn't detect the text's language
+    Undefined,
 
-const LANGUAGES: &'static [&str] = &[
-    "en", "iw", "es", "pt", "ru", "fr", "nl", "sv", "ar", "de", "hu", "it", "yi",
-];
+    /// The provider couldn't translate the text into any language
+    NoTranslation,
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ImageTranslate {
-    image_id: ImageId,
-    text: String,
+    /// Successful Translation
+    Success,
+
+    /// The text was translated into some of the languages, the others kept the original text
+    Partial,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct JigTranslate {
-    jig_data_id: Uuid,
-    text: String,
+/// The fields which get translated.
+#[derive(Copy, Clone, Debug)]
+enum Field {
+    Name,
+    Description,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ResourceTranslate {
-    resource_data_id: Uuid,
-    text: String,
+impl Field {
+    /// Key of the field in `translation_status`, and suffix of its `translated_` column.
+    fn key(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Description => "description",
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct PlaylistTranslate {
-    playlist_data_id: Uuid,
-    text: String,
+/// What gets translated. Assets are translated once they're published, from their live data.
+#[derive(Copy, Clone, Debug)]
+enum Translatable {
+    Image,
+    Jig,
+    Playlist,
+    Resource,
+    Course,
 }
 
-/// Translation status for assets
-#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
-#[cfg_attr(feature = "backend", derive(sqlx::Type))]
-#[serde(rename_all = "camelCase")]
-#[repr(i8)]
-pub enum TranslationStatus {
-    /// Google Translation response for strings that can't detect a language
-    Undefined = 0,
+impl Translatable {
+    const ALL: [Self; 5] = [
+        Self::Image,
+        Self::Jig,
+        Self::Playlist,
+        Self::Resource,
+        Self::Course,
+    ];
+
+    fn table(self) -> &'static str {
+        match self {
+            Self::Image => "image_metadata",
+            Self::Jig => "jig",
+            Self::Playlist => "playlist",
+            Self::Resource => "resource",
+            Self::Course => "course",
+        }
+    }
 
-    /// API cannot translate language
-    NoTranslation = 1,
+    /// How many texts of each field get translated per run.
+    fn batch_size(self) -> usize {
+        match self {
+            Self::Image | Self::Resource => 10,
+            Self::Playlist | Self::Course => 20,
+            Self::Jig => 30,
+        }
+    }
 
-    /// Successful Translation
-    Success = 2,
+    /// The table holding the texts, and the column of `field`.
+    fn text_column(self, field: Field) -> (String, &'static str) {
+        match self {
+            Self::Image => ("image_metadata".to_owned(), field.key()),
+            _ => (
+                format!("{}_data", self.table()),
+                match field {
+                    Field::Name => "display_name",
+                    Field::Description => "description",
+                },
+            ),
+        }
+    }
+
+    /// Selects a batch of untranslated texts, with the ID of the image or the asset's live data.
+    ///
+    /// Rows aren't locked, since translating them takes a while. See [`Self::lock_unchanged`].
+    fn untranslated_query(self, field: Field) -> String {
+        let key = field.key();
+        let limit = self.batch_size();
+
+        match self {
+            Self::Image => format!(
+                r#"
+select id, {key} as text
+from image_metadata
+     inner join image_upload on id = image_id
+where {key} <> '' and translated_{key} = '{{}}'
+and processed_at is not null
+and not translation_status ? '{key}'
+order by coalesce(updated_at, created_at) desc
+limit {limit}
+"#
+            ),
+            _ => {
+                let table = self.table();
+                let (_, column) = self.text_column(field);
+
+                format!(
+                    r#"
+select {table}_data.id, {column} as text
+from {table}_data
+inner join {table} on live_id = {table}_data.id
+where {column} <> '' and translated_{key} = '{{}}'
+and published_at is not null
+and not {table}.translation_status ? '{key}'
+order by coalesce(updated_at, created_at) desc
+limit {limit}
+"#
+                )
+            }
+        }
+    }
+
+    /// Locks the row of a text, returning whether it's still `text`.
+    ///
+    /// Texts which were edited while they were being translated are skipped, editing them resets
+    /// their status so they get translated again.
+    async fn lock_unchanged(
+        self,
+        conn: &mut PgConnection,
+        field: Field,
+        id: Uuid,
+        text: &str,
+    ) -> sqlx::Result<bool> {
+        let (table, column) = self.text_column(field);
+
+        sqlx::query(&format!(
+            "select 1 from {table} where id = $1 and {column} = $2 for no key update"
+        ))
+        .bind(id)
+        .bind(text)
+        .fetch_optional(conn)
+        .await
+        .map(|row| row.is_some())
+    }
+
+    async fn save_translations(
+        self,
+        conn: &mut PgConnection,
+        field: Field,
+        id: Uuid,
+        translations: &HashMap<String, String>,
+    ) -> sqlx::Result<()> {
+        let (table, _) = self.text_column(field);
+
+        sqlx::query(&format!(
+            "update {} set translated_{} = $2, last_synced_at = null where id = $1",
+            table,
+            field.key()
+        ))
+        .bind(id)
+        .bind(json!(translations))
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn save_status(
+        self,
+        conn: &mut PgConnection,
+        field: Field,
+        id: Uuid,
+        status: TranslationStatus,
+    ) -> sqlx::Result<()> {
+        let id_column = match self {
+            Self::Image => "id",
+            _ => "live_id",
+        };
+
+        sqlx::query(&format!(
+            "update {} set translation_status = translation_status || $2 where {} = $1",
+            self.table(),
+            id_column
+        ))
+        .bind(id)
+        .bind(json!({ field.key(): status }))
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
-pub struct GoogleTranslate {
+pub struct Translator {
     pub db: PgPool,
-    pub api_key: String,
+    pub provider: Arc<dyn TranslationProvider>,
 }
 
-impl GoogleTranslate {
+impl Translator {
     pub fn new(db: PgPool, runtime_settings: &RuntimeSettings) -> anyhow::Result<Option<Self>> {
-        let api_key = match runtime_settings.google_api_key.to_owned() {
-            Some(key) => key,
-            None => return Ok(None),
+        let provider = match provider(runtime_settings) {
+            Some(provider) => provider,
+            None => {
+                log::warn!("No translation provider is configured, translating will be disabled");
+                return Ok(None);
+            }
         };
 
-        Ok(Some(Self { db, api_key }))
+        log::info!("Translating with {}", provider.name());
+
+        Ok(Some(Self { db, provider }))
     }
 
     pub async fn spawn_cron_jobs(&self) -> anyhow::Result<()> {
         log::debug!("reached description translation cron job");
 
-        for count in 0..4 {
-            let res = match count {
-                0 => self
-                    .update_image_translations()
-                    .await
-                    .context("update image translation task errored"),
-                1 => self
-                    .update_jig_translations()
-                    .await
-                    .context("update jig translation task errored"),
-                2 => self
-                    .update_playlist_translations()
-                    .await
-                    .context("update playlist translation task errored"),
-                3 => self
-                    .update_resource_translations()
+        let glossary = Glossary::load(&self.db)
+            .await
+            .context("couldn't load the translation glossary")?;
+
+        for translatable in Translatable::ALL {
+            for field in [Field::Description, Field::Name] {
+                let res = self
+                    .update_translations(translatable, field, &glossary)
                     .await
-                    .context("update resource translation task errored"),
-                _ => continue,
-            };
+                    .with_context(|| {
+                        format!(
+                            "update {} {} translation task errored",
+                            translatable.table(),
+                            field.key()
+                        )
+                    });
 
-            match res {
-                Ok(true) => {}
-                Ok(false) => {
-                    log::info!("exiting translation api");
-                }
-                Err(e) => {
+                if let Err(e) = res {
                     log::error!("{:?}", e);
                 }
             }
         }
+
+        // translated copies of JIGs which were interrupted, e.g. by a restart
+        for id in db::jig::translation::list_pending(&self.db).await? {
+            self.translate_jig(id).await;
+        }
+
         Ok(())
     }
 
-    async fn update_image_translations(&self) -> anyhow::Result<bool> {
-        log::info!("reached update images translation");
-        let mut txn = self.db.begin().await?;
+    /// Translates a batch of texts. Each text is saved in its own transaction once it's
+    /// translated, so that no rows stay locked while the provider is called.
+    async fn update_translations(
+        &self,
+        translatable: Translatable,
+        field: Field,
+        glossary: &Glossary,
+    ) -> anyhow::Result<()> {
+        let texts: Vec<(Uuid, String)> = sqlx::query_as(&translatable.untranslated_query(field))
+            .fetch_all(&self.db)
+            .await?;
+
+        if texts.is_empty() {
+            return Ok(());
+        }
 
-        let descriptions: Vec<_> = sqlx::query!(
-            //language=SQL
-            r#"
-select id                               as "id!: ImageId",
-       description
-from image_metadata
-     inner join image_upload on id = image_id
-where description <> '' and translated_description = '{}'
-and processed_at is not null
-and description_translate_status is null
-order by coalesce(updated_at, created_at) desc
-limit 10 for no key update skip locked;
- "#
-        )
-        .fetch(&mut txn)
-        .map_ok(|row| ImageTranslate {
-            image_id: row.id,
-            text: row.description,
-        })
-        .try_collect()
-        .await?;
+        for (id, text) in texts {
+            let result = self.translate_all(&text, glossary).await;
 
-        let names: Vec<_> = sqlx::query!(
-            //language=SQL
-            r#"
-select id                               as "id!: ImageId",
-       name
-from image_metadata
-     inner join image_upload on id = image_id
-where name <> '' and translated_name = '{}'
-and processed_at is not null
-and name_translate_status is null
-order by coalesce(updated_at, created_at) desc
-limit 10 for no key update skip locked;
- "#
-        )
-        .fetch(&mut txn)
-        .map_ok(|row| ImageTranslate {
-            image_id: row.id,
-            text: row.name,
-        })
-        .try_collect()
-        .await?;
+            let mut txn = self.db.begin().await?;
 
-        if descriptions.is_empty() && names.is_empty() {
-            return Ok(true);
-        }
+            if !translatable
+                .lock_unchanged(&mut txn, field, id, &text)
+                .await?
+            {
+                log::debug!(
+                    "{} of {} {} changed while it was translated",
+                    field.key(),
+                    translatable.table(),
+                    id
+                );
+                continue;
+            }
 
-        for t in descriptions {
-            let descriptions: anyhow::Result<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await;
-
-            match descriptions {
-                Ok(descriptions) => {
-                    if let Some(descriptions) = descriptions {
-                        sqlx::query!(
-                            r#"
-                                update image_metadata
-                                set translated_description = $2,
-                                    last_synced_at = null
-                                where id = $1
-                            "#,
-                            t.image_id.0,
-                            json!(descriptions)
-                        )
-                        .execute(&mut txn)
-                        .await?;
-                        update_image_translation_status(
-                            &mut txn,
-                            "image_metadata",
-                            "description",
-                            t.image_id.0,
-                            TranslationStatus::Success,
-                        )
+            let status = match result {
+                Ok(Some((status, translations))) => {
+                    translatable
+                        .save_translations(&mut txn, field, id, &translations)
                         .await?;
-                    } else {
-                        log::warn!("Empty translation list for image_id: {}", t.image_id.0,);
-                        update_image_translation_status(
-                            &mut txn,
-                            "image_metadata",
-                            "description",
-                            t.image_id.0,
-                            TranslationStatus::Undefined,
-                        )
-                        .await?;
-                        continue;
-                    };
+                    status
                 }
-                Err(error) => {
-                    log::error!(
-                        "Could not translate description for image_id: {}, error: {}",
-                        t.image_id.0,
-                        error,
+                Ok(None) => {
+                    log::debug!(
+                        "Couldn't detect the language of {} {}",
+                        translatable.table(),
+                        id
                     );
-                    update_image_translation_status(
-                        &mut txn,
-                        "image_metadata",
-                        "description",
-                        t.image_id.0,
-                        TranslationStatus::NoTranslation,
-                    )
-                    .await?;
-
-                    continue;
-                }
-            }
-        }
-
-        for t in names {
-            let names: anyhow::Result<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await;
-
-            match names {
-                Ok(names) => {
-                    if let Some(names) = names {
-                        sqlx::query!(
-                            r#"
-                                update image_metadata
-                                set translated_name = $2,
-                                    last_synced_at = null
-                                where id = $1
-                                "#,
-                            t.image_id.0,
-                            json!(names)
-                        )
-                        .execute(&mut txn)
-                        .await?;
-                        update_image_translation_status(
-                            &mut txn,
-                            "image_metadata",
-                            "name",
-                            t.image_id.0,
-                            TranslationStatus::Success,
-                        )
-                        .await?;
-                    } else {
-                        log::warn!("Empty translation list for image_id: {}", t.image_id.0,);
-                        update_image_translation_status(
-                            &mut txn,
-                            "image_metadata",
-                            "name",
-                            t.image_id.0,
-                            TranslationStatus::Undefined,
-                        )
-                        .await?;
-                        continue;
-                    };
+                    TranslationStatus::Undefined
                 }
                 Err(error) => {
-                    log::error!(
-                        "Could not translate name for image_id: {}, error: {}",
-                        t.image_id.0,
+                    log::warn!(
+                        "Could not translate {} for {} {}, error: {:?}",
+                        field.key(),
+                        translatable.table(),
+                        id,
                         error,
                     );
-                    update_image_translation_status(
-                        &mut txn,
-                        "image_metadata",
-                        "name",
-                        t.image_id.0,
-                        TranslationStatus::NoTranslation,
-                    )
-                    .await?;
-
-                    continue;
+                    TranslationStatus::NoTranslation
                 }
-            }
-        }
-
-        txn.commit().await?;
-
-        log::info!("completed update image translations");
-
-        Ok(true)
-    }
-
-    async fn update_jig_translations(&self) -> anyhow::Result<bool> {
-        log::info!("reached update JIG translation");
-        let mut txn = self.db.begin().await?;
-
-        let descriptions: Vec<_> = sqlx::query!(
-            //language=SQL
-            r#"
-select jig_data.id ,
-       description
-from jig_data
-inner join jig on live_id = jig_data.id
-where description <> '' and translated_description = '{}'
-and published_at is not null
-and description_translate_status is null
-order by coalesce(updated_at, created_at) desc
-limit 30 for no key update skip locked;
- "#
-        )
-        .fetch(&mut txn)
-        .map_ok(|row| JigTranslate {
-            jig_data_id: row.id,
-            text: row.description,
-        })
-        .try_collect()
-        .await?;
-
-        let display_names: Vec<_> = sqlx::query!(
-            //language=SQL
-            r#"
-select jig_data.id,
-       display_name
-from jig_data
-inner join jig on live_id = jig_data.id
-where display_name <> '' and translated_name = '{}'
-and published_at is not null
-and name_translate_status is null
-order by coalesce(updated_at, created_at) desc
-limit 30 for no key update skip locked;
-         "#
-        )
-        .fetch(&mut txn)
-        .map_ok(|row| JigTranslate {
-            jig_data_id: row.id,
-            text: row.display_name,
-        })
-        .try_collect()
-        .await?;
-
-        if descriptions.is_empty() && display_names.is_empty() {
-            return Ok(true);
-        }
-
-        for t in descriptions {
-            let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
-
-            if let Some(res) = res {
-                if let Some(res) = res {
-                    sqlx::query!(
-                        r#"
-                            update jig_data
-                            set translated_description = $2,
-                                last_synced_at = null
-                            where id = $1
-                            "#,
-                        &t.jig_data_id,
-                        json!(res)
-                    )
-                    .execute(&mut txn)
-                    .await?;
-                    update_asset_translation_status(
-                        &mut txn,
-                        "jig",
-                        "description",
-                        t.jig_data_id,
-                        TranslationStatus::Success,
-                    )
-                    .await?;
-                } else {
-                    log::debug!("Empty translation list for jig_data_id: {}", t.jig_data_id);
-                    update_asset_translation_status(
-                        &mut txn,
-                        "jig",
-                        "description",
-                        t.jig_data_id,
-                        TranslationStatus::Undefined,
-                    )
-                    .await?;
-                    continue;
-                };
-            } else {
-                log::debug!(
-                    "Could not translate description for jig_data_id: {}, string: {}",
-                    t.jig_data_id,
-                    t.text
-                );
-                update_asset_translation_status(
-                    &mut txn,
-                    "jig",
-                    "description",
-                    t.jig_data_id,
-                    TranslationStatus::NoTranslation,
-                )
-                .await?;
-
-                continue;
             };
-        }
 
-        for t in display_names {
-            let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
-
-            if let Some(res) = res {
-                if let Some(res) = res {
-                    sqlx::query!(
-                        r#"
-                            update jig_data
-                            set translated_name = $2,
-                                last_synced_at = null
-                            where id = $1
-                            "#,
-                        &t.jig_data_id,
-                        json!(res)
-                    )
-                    .execute(&mut txn)
-                    .await?;
-                    update_asset_translation_status(
-                        &mut txn,
-                        "jig",
-                        "name",
-                        t.jig_data_id,
-                        TranslationStatus::Success,
-                    )
-                    .await?;
-                } else {
-                    log::debug!("Empty translation list for jig_data_id: {}", t.jig_data_id);
-                    update_asset_translation_status(
-                        &mut txn,
-                        "jig",
-                        "name",
-                        t.jig_data_id,
-                        TranslationStatus::Undefined,
-                    )
-                    .await?;
-
-                    continue;
-                };
-            } else {
-                log::debug!(
-                    "Could not translate name for jig_data_id: {}, string: {}",
-                    t.jig_data_id,
-                    t.text
-                );
-                update_asset_translation_status(
-                    &mut txn,
-                    "jig",
-                    "name",
-                    t.jig_data_id,
-                    TranslationStatus::NoTranslation,
-                )
+            translatable
+                .save_status(&mut txn, field, id, status)
                 .await?;
 
-                continue;
-            };
+            txn.commit().await?;
         }
 
-        txn.commit().await?;
-
-        log::info!("completed update jig translations");
+        log::info!("completed update {} translations", translatable.table());
 
-        Ok(true)
+        Ok(())
     }
 
-    async fn update_resource_translations(&self) -> anyhow::Result<bool> {
-        log::info!("reached update Resource translation");
-        let mut txn = self.db.begin().await?;
-
-        let descriptions: Vec<_> = sqlx::query!(
-            //language=SQL
-            r#"
-select resource_data.id,
-       description
-from resource_data
-inner join resource on live_id = resource_data.id
-where description <> '' and translated_description = '{}'
-and published_at is not null
-and description_translate_status is null
-order by coalesce(updated_at, created_at) desc
-limit 10 for no key update skip locked;
- "#
-        )
-        .fetch(&mut txn)
-        .map_ok(|row| ResourceTranslate {
-            resource_data_id: row.id,
-            text: row.description,
-        })
-        .try_collect()
-        .await?;
-
-        let display_names: Vec<_> = sqlx::query!(
-            //language=SQL
-            r#"
-select resource_data.id,
-       display_name
-from resource_data
-inner join resource on live_id = resource_data.id
-where display_name <> '' and translated_name = '{}'
-and published_at is not null
-and name_translate_status is null
-order by coalesce(updated_at, created_at) desc
-limit 10 for no key update skip locked;
-         "#
-        )
-        .fetch(&mut txn)
-        .map_ok(|row| ResourceTranslate {
-            resource_data_id: row.id,
-            text: row.display_name,
-        })
-        .try_collect()
-        .await?;
+    /// Translates `text` into every language in [`LANGUAGES`], returning `None` if its language
+    /// couldn't be detected. Languages it can't be translated into get the original text.
+    async fn translate_all(
+        &self,
+        text: &str,
+        glossary: &Glossary,
+    ) -> anyhow::Result<Option<(TranslationStatus, HashMap<String, String>)>> {
+        let source = match self.provider.detect_language(text).await? {
+            Some(language) => normalize_language(&language),
+            None => return Ok(None),
+        };
 
-        if descriptions.is_empty() && display_names.is_empty() {
-            return Ok(true);
-        }
+        let protected = glossary.protect(text, &source);
 
-        for t in descriptions {
-            let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
-
-            if let Some(res) = res {
-                if let Some(res) = res {
-                    sqlx::query!(
-                        r#"
-                            update resource_data
-                            set translated_description = $2,
-                                last_synced_at = null
-                            where id = $1
-                            "#,
-                        &t.resource_data_id,
-                        json!(res)
-                    )
-                    .execute(&mut txn)
-                    .await?;
-                    update_asset_translation_status(
-                        &mut txn,
-                        "resource",
-                        "description",
-                        t.resource_data_id,
-                        TranslationStatus::Success,
-                    )
-                    .await?;
-                } else {
-                    log::debug!(
-                        "Empty description translation list for resource_data_id: {}",
-                        t.resource_data_id
-                    );
-                    update_asset_translation_status(
-                        &mut txn,
-                        "resource",
-                        "description",
-                        t.resource_data_id,
-                        TranslationStatus::Undefined,
-                    )
-                    .await?;
-                    continue;
-                };
-            } else {
-                log::debug!(
-                    "Could not translate description for resource_data_id: {}, string: {}",
-                    t.resource_data_id,
-                    t.text
-                );
-                update_asset_translation_status(
-                    &mut txn,
-                    "resource",
-                    "description",
-                    t.resource_data_id,
-                    TranslationStatus::NoTranslation,
-                )
-                .await?;
+        let mut translations = HashMap::new();
+        let mut attempted = 0;
+        let mut failed = 0;
 
+        for &target in LANGUAGES {
+            if target == source {
+                translations.insert(target.to_owned(), text.to_owned());
                 continue;
-            };
-        }
+            }
 
-        for t in display_names {
-            let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
-
-            if let Some(res) = res {
-                if let Some(res) = res {
-                    sqlx::query!(
-                        r#"
-                            update resource_data
-                            set translated_name = $2,
-                                last_synced_at = null
-                            where id = $1
-                            "#,
-                        &t.resource_data_id,
-                        json!(res)
-                    )
-                    .execute(&mut txn)
-                    .await?;
-
-                    update_asset_translation_status(
-                        &mut txn,
-                        "resource",
-                        "name",
-                        t.resource_data_id,
-                        TranslationStatus::Success,
-                    )
-                    .await?;
-                } else {
-                    log::debug!(
-                        "Empty name translation list for resource_data_id: {}",
-                        t.resource_data_id
-                    );
-                    update_asset_translation_status(
-                        &mut txn,
-                        "resource",
-                        "name",
-                        t.resource_data_id,
-                        TranslationStatus::Undefined,
-                    )
-                    .await?;
-
-                    continue;
-                };
+            attempted += 1;
+
+            let translation = if self.provider.supports(target) {
+                self.translate_with_glossary(text, &protected, &source, target)
+                    .await
+                    .map_err(|error| {
+                        log::debug!("Could not translate into {}: {:?}", target, error);
+                    })
+                    .ok()
             } else {
-                log::debug!(
-                    "Could not translate name for resource_data_id: {}, string: {}",
-                    t.resource_data_id,
-                    t.text
-                );
+                None
+            };
 
-                update_asset_translation_status(
-                    &mut txn,
-                    "resource",
-                    "name",
-                    t.resource_data_id,
-                    TranslationStatus::NoTranslation,
-                )
-                .await?;
+            if translation.is_none() {
+                failed += 1;
+            }
 
-                continue;
-            };
+            translations.insert(
+                target.to_owned(),
+                translation.unwrap_or_else(|| text.to_owned()),
+            );
         }
 
-        txn.commit().await?;
-
-        log::info!("completed update resource translations");
+        let status = match failed {
+            0 => TranslationStatus::Success,
+            failed if failed == attempted => anyhow::bail!("no language could be translated into"),
+            _ => TranslationStatus::Partial,
+        };
 
-        Ok(true)
+        Ok(Some((status, translations)))
     }
 
-    async fn update_playlist_translations(&self) -> anyhow::Result<bool> {
-        log::info!("reached update Playlist translation");
-        let mut txn = self.db.begin().await?;
-
-        let descriptions: Vec<_> = sqlx::query!(
-            //language=SQL
-            r#"
-select playlist_data.id,
-       description
-from playlist_data
-inner join playlist on live_id = playlist_data.id
-where description <> ''
-      and translated_description = '{}'
-      and published_at is not null
-      and description_translate_status is null
-order by coalesce(updated_at, created_at) desc
-limit 20 for no key update skip locked;
- "#
-        )
-        .fetch(&mut txn)
-        .map_ok(|row| PlaylistTranslate {
-            playlist_data_id: row.id,
-            text: row.description,
-        })
-        .try_collect()
-        .await?;
-
-        let names: Vec<_> = sqlx::query!(
-            //language=SQL
-            r#"
-select playlist_data.id,
-       display_name
-from playlist_data
-inner join playlist on live_id = playlist_data.id
-where display_name <> ''
-      and translated_name = '{}'
-      and published_at is not null
-      and name_translate_status is null
-order by coalesce(updated_at, created_at) desc
-limit 20 for no key update skip locked;
- "#
-        )
-        .fetch(&mut txn)
-        .map_ok(|row| PlaylistTranslate {
-            playlist_data_id: row.id,
-            text: row.display_name,
-        })
-        .try_collect()
-        .await?;
-
-        if descriptions.is_empty() && names.is_empty() {
-            return Ok(true);
+    /// Translates each of `texts` from `source` into `target`, returning the translation of each
+    /// text which could be translated.
+    pub async fn translate_texts(
+        &self,
+        texts: &HashSet<String>,
+        source: &str,
+        target: &str,
+    ) -> anyhow::Result<HashMap<String, String>> {
+        let glossary = Glossary::load(&self.db)
+            .await
+            .context("couldn't load the translation glossary")?;
+
+        let mut translations = HashMap::new();
+
+        for text in texts {
+            let protected = glossary.protect(text, source);
+
+            match self
+                .translate_with_glossary(text, &protected, source, target)
+                .await
+            {
+                Ok(translation) => {
+                    translations.insert(text.clone(), translation);
+                }
+                Err(error) => {
+                    log::debug!("Could not translate into {}: {:?}", target, error);
+                }
+            }
         }
 
-        for t in descriptions {
-            let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
-
-            if let Some(res) = res {
-                if let Some(res) = res {
-                    sqlx::query!(
-                        r#"
-                            update playlist_data
-                            set translated_description = $2,
-                                last_synced_at = null
-                            where id = $1
-                            "#,
-                        &t.playlist_data_id,
-                        json!(res),
-                    )
-                    .execute(&mut txn)
-                    .await?;
-
-                    update_asset_translation_status(
-                        &mut txn,
-                        "playlist",
-                        "description",
-                        t.playlist_data_id,
-                        TranslationStatus::Success,
-                    )
-                    .await?;
-                } else {
-                    log::debug!(
-                        "Empty translation list for playlist_id: {}",
-                        t.playlist_data_id
-                    );
-                    update_asset_translation_status(
-                        &mut txn,
-                        "playlist",
-                        "description",
-                        t.playlist_data_id,
-                        TranslationStatus::Undefined,
-                    )
-                    .await?;
-
-                    continue;
-                };
-            } else {
-                log::debug!(
-                    "Could not translate description for playlist_id: {}, string: {}",
-                    t.playlist_data_id,
-                    t.text
-                );
+        Ok(translations)
+    }
 
-                update_asset_translation_status(
-                    &mut txn,
-                    "playlist",
-                    "description",
-                    t.playlist_data_id,
-                    TranslationStatus::NoTranslation,
-                )
-                .await?;
+    /// Makes the translated copy of a JIG which was queued with
+    /// [`db::jig::translation::create`], unless it's already being made.
+    pub async fn translate_jig(&self, id: JigTranslationId) {
+        if let Err(e) = self.try_translate_jig(id).await {
+            log::error!("Couldn't make the translated JIG {}: {:?}", id.0, e);
 
-                continue;
-            };
+            if let Err(e) = db::jig::translation::fail(&self.db, id).await {
+                log::error!("Couldn't save that translation {} failed: {:?}", id.0, e);
+            }
         }
+    }
 
-        for t in names {
-            let res: Option<Option<HashMap<String, String>>> =
-                multi_translation(&t.text, &self.api_key).await.ok();
-
-            if let Some(res) = res {
-                if let Some(res) = res {
-                    sqlx::query!(
-                        r#"
-                            update playlist_data
-                            set translated_name = $2,
-                                last_synced_at = null
-                            where id = $1
-                            "#,
-                        &t.playlist_data_id,
-                        json!(res)
-                    )
-                    .execute(&mut txn)
-                    .await?;
-
-                    update_asset_translation_status(
-                        &mut txn,
-                        "playlist",
-                        "name",
-                        t.playlist_data_id,
-                        TranslationStatus::Success,
-                    )
-                    .await?;
-                } else {
-                    log::debug!(
-                        "Empty translation list for playlist_id: {}",
-                        t.playlist_data_id
-                    );
-                    update_asset_translation_status(
-                        &mut txn,
-                        "playlist",
-                        "name",
-                        t.playlist_data_id,
-                        TranslationStatus::Undefined,
-                    )
-                    .await?;
-                    continue;
-                };
-            } else {
-                log::debug!(
-                    "Could not translate name for playlist_id: {}, string: {}",
-                    t.playlist_data_id,
-                    t.text
-                );
+    async fn try_translate_jig(&self, id: JigTranslationId) -> anyhow::Result<()> {
+        let (jig_id, user_id, language) = match db::jig::translation::start(&self.db, id).await? {
+            Some(translation) => translation,
+            None => return Ok(()),
+        };
 
-                update_asset_translation_status(
-                    &mut txn,
-                    "playlist",
-                    "name",
-                    t.playlist_data_id,
-                    TranslationStatus::NoTranslation,
-                )
-                .await?;
-                continue;
-            };
-        }
-        txn.commit().await?;
+        let target = translation_language(&language)
+            .with_context(|| format!("can't translate into {}", language))?;
 
-        log::info!("completed update Playlist translations");
+        // JIGs without a language are assumed to be in English
+        let source = db::jig::language(&self.db, jig_id)
+            .await?
+            .and_then(|source| translation_language(&source))
+            .unwrap_or_else(|| "en".to_owned());
 
-        Ok(true)
-    }
-}
+        let texts = db::jig::texts(&self.db, jig_id).await?;
+        let translations = self.translate_texts(&texts, &source, &target).await?;
 
-pub async fn translate_text(
-    query: &str,
-    target: &str,
-    source: &str,
-    api_key: &str,
-) -> anyhow::Result<Option<String>> {
-    //https://cloud.google.com/translate/docs/languages
-    //https://cloud.google.com/translate/docs/reference/rest/v2/translate
-    let res = reqwest::Client::new()
-        .post("https://translation.googleapis.com/language/translate/v2")
-        .query(&[("key", &api_key.to_owned())])
-        .json(&TranslateTextRequest {
-            q: query.to_string(),
-            target: target.to_string(),
-            source: source.to_string(),
-            format: "text".to_string(),
-        })
-        .send()
-        .await?
-        .error_for_status()?
-        .json::<TranslateTextResponse>()
-        .await?;
+        let mut txn = self.db.begin().await?;
 
-    let res = TranslateTextResponseList {
-        translations: res
-            .data
-            .translations
-            .into_iter()
-            .map(|it| Translation {
-                translated_text: it.translated_text,
-            })
-            .collect(),
-    };
+        let (translated_jig_id, untranslated) = db::jig::clone_translated_jig(
+            &mut txn,
+            jig_id,
+            user_id,
+            &language,
+            text_direction(&target),
+            &translations,
+        )
+        .await
+        .map_err(|e| match e {
+            error::CloneDraft::InternalServerError(e) => e,
+            _ => anyhow::anyhow!("couldn't copy JIG {}", jig_id.0),
+        })?;
 
-    let translate = res.translations[0].translated_text.clone();
+        db::jig::translation::finish(&mut txn, id, translated_jig_id, &untranslated).await?;
 
-    Ok(Some(translate))
-}
+        txn.commit().await?;
 
-pub async fn multi_translation(
-    query: &str,
-    api_key: &str,
-) -> anyhow::Result<Option<HashMap<String, String>>> {
-    //https://cloud.google.com/translate/docs/languages
-    //https://cloud.google.com/translate/docs/reference/rest/v2/translate
-    let res = reqwest::Client::new()
-        .post("https://translation.googleapis.com/language/translate/v2/detect")
-        .query(&[("key", &api_key.to_owned())])
-        .json(&DetectLanguageRequest {
-            q: query.to_string(),
-        })
-        .send()
-        .await?
-        .json::<DetectLanguageResponse>()
-        .await?;
+        Ok(())
+    }
 
-    let language_list: Vec<_> = res.data.detections.into_iter().collect();
+    async fn translate_with_glossary(
+        &self,
+        text: &str,
+        protected: &Protected<'_>,
+        source: &str,
+        target: &str,
+    ) -> anyhow::Result<String> {
+        if !protected.is_empty() {
+            let translated = self
+                .provider
+                .translate(&protected.text, source, target)
+                .await?;
 
-    let v: Vec<String> = {
-        let mut s: Vec<String> = Vec::new();
-        for lang in language_list {
-            for l in lang {
-                s.push(l.language.to_string().to_owned());
+            if let Some(restored) = protected.restore(&translated, target) {
+                return Ok(restored);
             }
+
+            log::debug!(
+                "{} dropped glossary placeholders, translating without the glossary",
+                self.provider.name()
+            );
         }
-        s
-    };
 
-    if &v[0] == "und" {
-        return Ok(None);
+        self.provider.translate(text, source, target).await
     }
+}
 
-    let src = &v[0][..2];
-
-    let mut translation_list = HashMap::new();
-
-    for l in LANGUAGES {
-        let text = if l != &src {
-            let text: Option<String> = translate_text(query, l, src, &api_key.to_owned()).await?;
-            text
-        } else {
-            None
-        };
-
-        if let Some(text) = text {
-            translation_list.insert(l.to_owned().to_owned(), text);
-        } else {
-            translation_list.insert(l.to_owned().to_owned(), query.to_owned());
-        };
-    }
+/// Turns a JIG language into the code it's translated with, `None` if it can't be translated.
+pub fn translation_language(language: &str) -> Option<String> {
+    let language = normalize_language(language);
 
-    Ok(Some(translation_list))
+    LANGUAGES.contains(&language.as_str()).then_some(language)
 }
 
-async fn update_asset_translation_status(
-    conn: &mut PgConnection,
-    table: &str,
-    field: &str,
-    id: Uuid,
-    status: TranslationStatus,
-) -> sqlx::Result<()> {
-    sqlx::query(&format!(
-        "update {0} set {1}_translate_status = $2 where {0}.live_id = $1",
-        table, field
-    ))
-    .bind(id)
-    .bind(status as i16)
-    .execute(&mut *conn)
-    .await?;
-
-    Ok(())
+/// The direction `language` (one of our codes) is written in.
+pub fn text_direction(language: &str) -> TextDirection {
+    if RTL_LANGUAGES.contains(&language) {
+        TextDirection::RightToLeft
+    } else {
+        TextDirection::LeftToRight
+    }
 }
 
-async fn update_image_translation_status(
-    conn: &mut PgConnection,
-    table: &str,
-    field: &str,
-    id: Uuid,
-    status: TranslationStatus,
-) -> sqlx::Result<()> {
-    sqlx::query(&format!(
-        "update {0} set {1}_translate_status = $2 where {0}.id = $1",
-        table, field
-    ))
-    .bind(id)
-    .bind(status as i16)
-    .execute(&mut *conn)
-    .await?;
-
-    Ok(())
+/// Turns a detected language into one of our codes, e.g. `zh-CN` into `zh` and `he` into `iw`.
+fn normalize_language(language: &str) -> String {
+    let language = language
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+
+    match language.as_str() {
+        "he" => "iw".to_owned(),
+        _ => language,
+    }
 }
 
-// #[test_service(setup = "setup_service", fixtures(""))]
-// async fn test_translated_description() -> anyhow::Result<()> {
-//     let api_key: &str = "";
-
-//     let this_arr: Vec<&str> = vec!["yes", "...", "passed", "", "nothing", ":?", ",", "Good"];
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-//     for t in this_arr {
-//         let translated_description: Option<Option<HashMap<String, String>>> =
-//             multi_translation(t, api_key).await.ok();
+    #[test]
+    fn normalizes_languages() {
+        assert_eq!("iw", normalize_language("he"));
+        assert_eq!("iw", normalize_language("iw"));
+        assert_eq!("zh", normalize_language("zh-CN"));
+        assert_eq!("pt", normalize_language("PT-BR"));
+    }
 
-//         if let Some(translation) = translated_description {
-//             println!("result {:?}", translation);
-//             translation
-//         } else {
-//             continue;
-//         };
-//     }
+    #[test]
+    fn maps_jig_languages() {
+        assert_eq!(Some("iw".to_owned()), translation_language("he"));
+        assert_eq!(Some("fr".to_owned()), translation_language("fr"));
+        assert_eq!(None, translation_language("xx"));
+        assert_eq!(None, translation_language(""));
+    }
 
-//     Ok(())
-// }
+    #[test]
+    fn knows_rtl_languages() {
+        assert_eq!(TextDirection::RightToLeft, text_direction("iw"));
+        assert_eq!(TextDirection::RightToLeft, text_direction("ar"));
+        assert_eq!(TextDirection::LeftToRight, text_direction("es"));
+    }
+}
@@ -0,0 +1,116 @@
+//! [DeepL](https://developers.deepl.com/docs/api-reference/translate).
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::TranslationProvider;
+
+/// The languages DeepL can translate into, of the ones we translate into (it has no Yiddish).
+const SUPPORTED_LANGUAGES: &[&str] = &[
+    "en", "iw", "es", "pt", "ru", "fr", "nl", "sv", "ar", "de", "hu", "it",
+];
+
+#[derive(Debug, Serialize)]
+struct TranslateRequest<'a> {
+    text: [&'a str; 1],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_lang: Option<String>,
+    target_lang: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranslateResponse {
+    translations: Vec<Translation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Translation {
+    detected_source_language: String,
+    text: String,
+}
+
+pub struct DeepL {
+    api_key: String,
+}
+
+impl DeepL {
+    pub fn new(api_key: String) -> Self {
+        Self { api_key }
+    }
+
+    /// Keys of free accounts end in `:fx`, and only work with the free API.
+    fn url(&self) -> &'static str {
+        if self.api_key.ends_with(":fx") {
+            "https://api-free.deepl.com/v2/translate"
+        } else {
+            "https://api.deepl.com/v2/translate"
+        }
+    }
+
+    async fn request(
+        &self,
+        text: &str,
+        source: Option<&str>,
+        target: &str,
+    ) -> anyhow::Result<Translation> {
+        let res = reqwest::Client::new()
+            .post(self.url())
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("DeepL-Auth-Key {}", self.api_key),
+            )
+            .json(&TranslateRequest {
+                text: [text],
+                source_lang: source.map(source_language),
+                target_lang: target_language(target),
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<TranslateResponse>()
+            .await?;
+
+        res.translations
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("deepl returned no translations"))
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for DeepL {
+    fn name(&self) -> &'static str {
+        "DeepL"
+    }
+
+    /// DeepL has no detection endpoint, the source language comes with any translation.
+    async fn detect_language(&self, text: &str) -> anyhow::Result<Option<String>> {
+        let translation = self.request(text, None, "en").await?;
+
+        Ok(Some(translation.detected_source_language.to_lowercase()))
+    }
+
+    async fn translate(&self, text: &str, source: &str, target: &str) -> anyhow::Result<String> {
+        Ok(self.request(text, Some(source), target).await?.text)
+    }
+
+    fn supports(&self, language: &str) -> bool {
+        SUPPORTED_LANGUAGES.contains(&language)
+    }
+}
+
+fn source_language(language: &str) -> String {
+    match language {
+        "iw" => "HE".to_owned(),
+        language => language.to_uppercase(),
+    }
+}
+
+/// English and Portuguese need a variant as a target.
+fn target_language(language: &str) -> String {
+    match language {
+        "en" => "EN-US".to_owned(),
+        "pt" => "PT-BR".to_owned(),
+        language => source_language(language),
+    }
+}
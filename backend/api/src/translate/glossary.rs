@@ -0,0 +1,241 @@
+//! Terms which translation providers tend to get wrong, like the names of Jewish holidays.
+//!
+//! Before text is sent to a provider, the terms found in it are swapped for placeholders, which
+//! are replaced with the term's spelling in the target language afterwards.
+
+use std::collections::HashMap;
+
+use futures::TryStreamExt;
+use sqlx::{types::Json, PgPool};
+
+/// One term, with its spellings in each language it has been written down for. The first
+/// spelling of a language is the one used in translations.
+#[derive(Debug, Clone)]
+pub struct Term {
+    spellings: HashMap<String, Vec<String>>,
+}
+
+impl Term {
+    pub fn new(spellings: HashMap<String, Vec<String>>) -> Self {
+        Self { spellings }
+    }
+
+    fn spelling(&self, language: &str) -> Option<&str> {
+        self.spellings
+            .get(language)
+            .and_then(|spellings| spellings.first())
+            .map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Glossary {
+    terms: Vec<Term>,
+}
+
+/// Text with the glossary's terms swapped for placeholders, see [`Glossary::protect`].
+#[derive(Debug)]
+pub struct Protected<'a> {
+    pub text: String,
+    /// The term behind each placeholder, with the text it replaced.
+    terms: Vec<(&'a Term, String)>,
+}
+
+impl Glossary {
+    pub fn new(terms: Vec<Term>) -> Self {
+        Self { terms }
+    }
+
+    pub async fn load(db: &PgPool) -> sqlx::Result<Self> {
+        let terms = sqlx::query!(
+            r#"select terms as "terms!: Json<HashMap<String, Vec<String>>>" from translation_glossary"#
+        )
+        .fetch(db)
+        .map_ok(|row| Term::new(row.terms.0))
+        .try_collect()
+        .await?;
+
+        Ok(Self { terms })
+    }
+
+    /// Swaps every whole-word, case-insensitive occurrence of a term's `source` spelling in `text`
+    /// for a placeholder. Longer spellings are swapped first, so that "Simchat Torah" isn't
+    /// swapped as "Torah".
+    pub fn protect<'a>(&'a self, text: &str, source: &str) -> Protected<'a> {
+        let mut spellings: Vec<_> = self
+            .terms
+            .iter()
+            .filter_map(|term| Some((term, term.spellings.get(source)?)))
+            .flat_map(|(term, spellings)| spellings.iter().map(move |spelling| (term, spelling)))
+            .filter(|(_, spelling)| !spelling.is_empty())
+            .collect();
+        spellings.sort_by_key(|(_, spelling)| std::cmp::Reverse(spelling.chars().count()));
+
+        let mut protected = Protected {
+            text: text.to_owned(),
+            terms: Vec::new(),
+        };
+
+        for (term, spelling) in spellings {
+            while let Some((start, end)) = find_word(&protected.text, spelling) {
+                let placeholder = placeholder(protected.terms.len());
+                let original = protected.text[start..end].to_owned();
+
+                protected.text.replace_range(start..end, &placeholder);
+                protected.terms.push((term, original));
+            }
+        }
+
+        protected
+    }
+}
+
+impl Protected<'_> {
+    /// Puts the terms back into a translation of the protected text, spelled for `target` if the
+    /// glossary knows how, or else as they were written.
+    ///
+    /// Returns `None` if the provider lost any of the placeholders.
+    pub fn restore(&self, translated: &str, target: &str) -> Option<String> {
+        let mut restored = translated.to_owned();
+
+        for (index, (term, original)) in self.terms.iter().enumerate() {
+            let placeholder = placeholder(index);
+            let start = restored.find(&placeholder)?;
+
+            let spelling = term.spelling(target).unwrap_or(original);
+            restored.replace_range(start..start + placeholder.len(), spelling);
+        }
+
+        Some(restored)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+/// Brackets which are unlikely to be in the text, and which providers leave as they are.
+fn placeholder(index: usize) -> String {
+    format!("⟦{}⟧", index)
+}
+
+/// Finds the first case-insensitive occurrence of `word` in `text` which isn't part of a longer
+/// word, returning its byte range.
+fn find_word(text: &str, word: &str) -> Option<(usize, usize)> {
+    let mut previous: Option<char> = None;
+
+    for (start, c) in text.char_indices() {
+        if !previous.is_some_and(char::is_alphanumeric) {
+            if let Some(end) = match_at(&text[start..], word) {
+                let end = start + end;
+                let is_word_end = !text[end..]
+                    .chars()
+                    .next()
+                    .is_some_and(char::is_alphanumeric);
+
+                if is_word_end {
+                    return Some((start, end));
+                }
+            }
+        }
+
+        previous = Some(c);
+    }
+
+    None
+}
+
+/// If `text` starts with `word` (ignoring case), the byte length of the match.
+fn match_at(text: &str, word: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+
+    for expected in word.chars() {
+        let (_, c) = chars.next()?;
+        if !c.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+
+    Some(chars.next().map_or(text.len(), |(index, _)| index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(spellings: &[(&str, &[&str])]) -> Term {
+        Term::new(
+            spellings
+                .iter()
+                .map(|(language, spellings)| {
+                    let spellings = spellings.iter().map(|it| it.to_string()).collect();
+                    (language.to_string(), spellings)
+                })
+                .collect(),
+        )
+    }
+
+    fn glossary() -> Glossary {
+        Glossary::new(vec![
+            term(&[
+                ("en", &["Hanukkah", "Chanukah"]),
+                ("iw", &["חנוכה"]),
+                ("es", &["Janucá"]),
+            ]),
+            term(&[("en", &["Torah"]), ("iw", &["תורה"])]),
+            term(&[("en", &["Simchat Torah"]), ("iw", &["שמחת תורה"])]),
+        ])
+    }
+
+    #[test]
+    fn protects_and_restores_terms() {
+        let glossary = glossary();
+        let protected = glossary.protect("Lighting candles on chanukah", "en");
+
+        assert_eq!("Lighting candles on ⟦0⟧", protected.text);
+        assert_eq!(
+            Some("Encendiendo velas en Janucá".to_owned()),
+            protected.restore("Encendiendo velas en ⟦0⟧", "es")
+        );
+    }
+
+    #[test]
+    fn keeps_the_original_spelling_without_a_target_spelling() {
+        let glossary = glossary();
+        let protected = glossary.protect("Chanukah songs", "en");
+
+        assert_eq!(
+            Some("Chansons de Chanukah".to_owned()),
+            protected.restore("Chansons de ⟦0⟧", "fr")
+        );
+    }
+
+    #[test]
+    fn prefers_longer_terms() {
+        let glossary = glossary();
+        let protected = glossary.protect("Dancing with the Torah on Simchat Torah", "en");
+
+        assert_eq!("Dancing with the ⟦1⟧ on ⟦0⟧", protected.text);
+        assert_eq!(
+            Some("רוקדים עם התורה בשמחת תורה".to_owned()),
+            protected.restore("רוקדים עם ה⟦1⟧ ב⟦0⟧", "iw")
+        );
+    }
+
+    #[test]
+    fn only_matches_whole_words() {
+        let glossary = glossary();
+
+        assert!(glossary.protect("Torahs and Torahless", "en").is_empty());
+        assert!(glossary.protect("חנוכה", "en").is_empty());
+        assert_eq!("⟦0⟧", glossary.protect("חנוכה", "iw").text);
+    }
+
+    #[test]
+    fn detects_lost_placeholders() {
+        let glossary = glossary();
+        let protected = glossary.protect("Hanukkah", "en");
+
+        assert_eq!(None, protected.restore("Hanoucca", "fr"));
+    }
+}
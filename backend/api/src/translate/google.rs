@@ -0,0 +1,123 @@
+//! [Google Translate](https://cloud.google.com/translate/docs/reference/rest/v2/translate).
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::TranslationProvider;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateTextRequest<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateTextResponse {
+    data: TranslateTextResponseList,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateTextResponseList {
+    translations: Vec<Translation>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Translation {
+    translated_text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DetectLanguageRequest<'a> {
+    q: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DetectLanguageResponse {
+    data: Detection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Detection {
+    detections: Vec<Vec<DetectLanguage>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DetectLanguage {
+    language: String,
+}
+
+pub struct GoogleTranslate {
+    api_key: String,
+}
+
+impl GoogleTranslate {
+    pub fn new(api_key: String) -> Self {
+        Self { api_key }
+    }
+}
+
+#[async_trait]
+impl TranslationProvider for GoogleTranslate {
+    fn name(&self) -> &'static str {
+        "Google Translate"
+    }
+
+    async fn detect_language(&self, text: &str) -> anyhow::Result<Option<String>> {
+        let res = reqwest::Client::new()
+            .post("https://translation.googleapis.com/language/translate/v2/detect")
+            .query(&[("key", &self.api_key)])
+            .json(&DetectLanguageRequest { q: text })
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<DetectLanguageResponse>()
+            .await?;
+
+        let language = res
+            .data
+            .detections
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|detection| detection.language)
+            // google's response for strings it can't detect a language in
+            .filter(|language| language != "und");
+
+        Ok(language)
+    }
+
+    async fn translate(&self, text: &str, source: &str, target: &str) -> anyhow::Result<String> {
+        //https://cloud.google.com/translate/docs/languages
+        let res = reqwest::Client::new()
+            .post("https://translation.googleapis.com/language/translate/v2")
+            .query(&[("key", &self.api_key)])
+            .json(&TranslateTextRequest {
+                q: text,
+                source,
+                target,
+                format: "text",
+            })
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<TranslateTextResponse>()
+            .await?;
+
+        res.data
+            .translations
+            .into_iter()
+            .next()
+            .map(|translation| translation.translated_text)
+            .ok_or_else(|| anyhow::anyhow!("google returned no translations"))
+    }
+}
@@ -0,0 +1,51 @@
+//! An offline stand-in for a translation provider, for running locally without any API keys.
+
+use async_trait::async_trait;
+
+use super::TranslationProvider;
+
+/// Detects languages by their script and "translates" by returning the text as it is, so only
+/// the glossary's terms get translated.
+pub struct LocalProvider;
+
+#[async_trait]
+impl TranslationProvider for LocalProvider {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    async fn detect_language(&self, text: &str) -> anyhow::Result<Option<String>> {
+        Ok(detect_script(text).map(ToOwned::to_owned))
+    }
+
+    async fn translate(&self, text: &str, _source: &str, _target: &str) -> anyhow::Result<String> {
+        Ok(text.to_owned())
+    }
+}
+
+/// The language of the first letter's script, assuming Hebrew script is Hebrew and Latin script
+/// is English.
+fn detect_script(text: &str) -> Option<&'static str> {
+    text.chars()
+        .filter(|c| c.is_alphabetic())
+        .find_map(|c| match c {
+            '\u{0590}'..='\u{05FF}' => Some("iw"),
+            '\u{0400}'..='\u{04FF}' => Some("ru"),
+            '\u{0600}'..='\u{06FF}' => Some("ar"),
+            c if c.is_ascii_alphabetic() => Some("en"),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_scripts() {
+        assert_eq!(Some("iw"), detect_script("123 חנוכה שמח"));
+        assert_eq!(Some("ru"), detect_script("Ханука"));
+        assert_eq!(Some("en"), detect_script("... Happy Hanukkah"));
+        assert_eq!(None, detect_script("123 :?"));
+    }
+}
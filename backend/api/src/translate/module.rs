@@ -0,0 +1,312 @@
+//! Finding the text in module bodies, for translating copies of JIGs.
+//!
+//! Text is found in:
+//! * the instructions and feedback
+//! * text cards
+//! * text stickers, whose value is the text editor's JSON with the text in the `text` of each leaf
+//! * the text of traces
+//! * the title and text of find-the-answer questions
+//!
+//! Legacy modules are left as they are.
+
+use serde_json::Value;
+use shared::domain::module::{
+    body::{
+        _groups::{
+            cards::{self, CardContent, CardPair},
+            design::{self, Sticker, Trace},
+        },
+        ModuleAssist,
+    },
+    ModuleBody,
+};
+
+/// Calls `f` with every text in `body`, which it can change.
+pub fn visit_texts(body: &mut ModuleBody, f: &mut impl FnMut(&mut String)) {
+    match body {
+        ModuleBody::MemoryGame(data) => {
+            if let Some(content) = &mut data.content {
+                visit_cards(&mut content.base, f);
+            }
+        }
+        ModuleBody::Matching(data) => {
+            if let Some(content) = &mut data.content {
+                visit_cards(&mut content.base, f);
+            }
+        }
+        ModuleBody::Flashcards(data) => {
+            if let Some(content) = &mut data.content {
+                visit_cards(&mut content.base, f);
+            }
+        }
+        ModuleBody::CardQuiz(data) => {
+            if let Some(content) = &mut data.content {
+                visit_cards(&mut content.base, f);
+            }
+        }
+        ModuleBody::Poster(data) => {
+            if let Some(content) = &mut data.content {
+                visit_design(&mut content.base, f);
+            }
+        }
+        ModuleBody::Video(data) => {
+            if let Some(content) = &mut data.content {
+                visit_design(&mut content.base, f);
+            }
+        }
+        ModuleBody::Embed(data) => {
+            if let Some(content) = &mut data.content {
+                visit_design(&mut content.base, f);
+            }
+        }
+        ModuleBody::Cover(data) => {
+            if let Some(content) = &mut data.content {
+                visit_design(&mut content.base, f);
+            }
+        }
+        ModuleBody::ResourceCover(data) => {
+            if let Some(content) = &mut data.content {
+                visit_design(&mut content.base, f);
+            }
+        }
+        ModuleBody::TappingBoard(data) => {
+            if let Some(content) = &mut data.content {
+                visit_design(&mut content.base, f);
+                content
+                    .traces
+                    .iter_mut()
+                    .for_each(|trace| visit_trace(trace, f));
+            }
+        }
+        ModuleBody::DragDrop(data) => {
+            if let Some(content) = &mut data.content {
+                visit_assist(&mut content.instructions, f);
+                visit_assist(&mut content.feedback, f);
+                content
+                    .items
+                    .iter_mut()
+                    .for_each(|item| visit_sticker(&mut item.sticker, f));
+                content
+                    .target_areas
+                    .iter_mut()
+                    .for_each(|area| visit_trace(&mut area.trace, f));
+            }
+        }
+        ModuleBody::FindAnswer(data) => {
+            if let Some(content) = &mut data.content {
+                visit_design(&mut content.base, f);
+
+                for question in &mut content.questions {
+                    f(&mut question.title);
+                    f(&mut question.question_text);
+                    question
+                        .traces
+                        .iter_mut()
+                        .for_each(|trace| visit_trace(trace, f));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The non-blank texts in `body`.
+pub fn texts(body: &ModuleBody) -> Vec<String> {
+    let mut body = body.clone();
+    let mut texts = Vec::new();
+
+    visit_texts(&mut body, &mut |text| {
+        if !text.trim().is_empty() {
+            texts.push(text.clone());
+        }
+    });
+
+    texts
+}
+
+fn visit_assist(assist: &mut ModuleAssist, f: &mut impl FnMut(&mut String)) {
+    if let Some(text) = &mut assist.text {
+        f(text);
+    }
+}
+
+fn visit_cards(base: &mut cards::BaseContent, f: &mut impl FnMut(&mut String)) {
+    visit_assist(&mut base.instructions, f);
+    visit_assist(&mut base.feedback, f);
+
+    for CardPair(first, second) in &mut base.pairs {
+        for card in [first, second] {
+            if let CardContent::Text(text) = &mut card.card_content {
+                f(text);
+            }
+        }
+    }
+}
+
+fn visit_design(base: &mut design::BaseContent, f: &mut impl FnMut(&mut String)) {
+    visit_assist(&mut base.instructions, f);
+    visit_assist(&mut base.feedback, f);
+    base.stickers
+        .iter_mut()
+        .for_each(|sticker| visit_sticker(sticker, f));
+}
+
+fn visit_sticker(sticker: &mut Sticker, f: &mut impl FnMut(&mut String)) {
+    if let Sticker::Text(text) = sticker {
+        visit_rich_text(&mut text.value, f);
+    }
+}
+
+fn visit_trace(trace: &mut Trace, f: &mut impl FnMut(&mut String)) {
+    if let Some(text) = &mut trace.text {
+        f(text);
+    }
+}
+
+/// Visits the leaves of a text sticker's value, which is left as it is if it isn't valid JSON.
+fn visit_rich_text(rich_text: &mut String, f: &mut impl FnMut(&mut String)) {
+    let mut value: Value = match serde_json::from_str(rich_text) {
+        Ok(value) => value,
+        Err(_) => return,
+    };
+
+    let mut changed = false;
+    visit_leaves(&mut value, &mut |text: &mut String| {
+        let before = text.clone();
+        f(text);
+        changed |= *text != before;
+    });
+
+    if changed {
+        *rich_text = value.to_string();
+    }
+}
+
+fn visit_leaves(value: &mut Value, f: &mut impl FnMut(&mut String)) {
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(|value| visit_leaves(value, f)),
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                match value {
+                    Value::String(text) if key == "text" => f(text),
+                    value => visit_leaves(value, f),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::domain::module::body::{
+        _groups::{
+            cards::Card,
+            design::{Text, TraceKind, TraceShape},
+        },
+        find_answer::{self, Question},
+        memory, tapping_board, BodyExt, ThemeId, Transform,
+    };
+
+    use super::*;
+
+    fn card(text: &str) -> Card {
+        Card {
+            audio: None,
+            card_content: CardContent::Text(text.to_owned()),
+        }
+    }
+
+    fn trace(text: &str) -> Trace {
+        Trace {
+            transform: Transform::identity(),
+            shape: TraceShape::Rect(1.0, 1.0),
+            kind: TraceKind::Regular,
+            audio: None,
+            text: Some(text.to_owned()),
+        }
+    }
+
+    #[test]
+    fn finds_card_texts() {
+        let mut data =
+            memory::ModuleData::new_with_mode_and_theme(cards::Mode::Duplicate, ThemeId::default());
+        let base = &mut data.content.as_mut().unwrap().base;
+        base.instructions.text = Some("Match the pairs".to_owned());
+        base.pairs = vec![
+            CardPair(card("dog"), card("dog")),
+            CardPair(card("cat"), card(" ")),
+        ];
+
+        assert_eq!(
+            vec!["Match the pairs", "dog", "dog", "cat"],
+            texts(&ModuleBody::MemoryGame(data))
+        );
+    }
+
+    #[test]
+    fn finds_design_texts() {
+        let mut data = tapping_board::ModuleData::new_with_mode_and_theme(
+            tapping_board::Mode::Words,
+            ThemeId::default(),
+        );
+        let content = data.content.as_mut().unwrap();
+        content.base.stickers = vec![Sticker::Text(Text::from_str("Shalom"))];
+        content.traces = vec![trace("Look here")];
+
+        assert_eq!(
+            vec!["Shalom", "Look here"],
+            texts(&ModuleBody::TappingBoard(data))
+        );
+
+        let mut data = find_answer::ModuleData::new_with_mode_and_theme(
+            find_answer::Mode::Text,
+            ThemeId::default(),
+        );
+        let content = data.content.as_mut().unwrap();
+        content.base.stickers = Vec::new();
+        content.questions = vec![Question {
+            title: "Question 1".to_owned(),
+            question_text: "Where is the dog?".to_owned(),
+            ..Default::default()
+        }];
+
+        assert_eq!(
+            vec!["Question 1", "Where is the dog?"],
+            texts(&ModuleBody::FindAnswer(data))
+        );
+    }
+
+    #[test]
+    fn replaces_texts() {
+        let mut data = tapping_board::ModuleData::new_with_mode_and_theme(
+            tapping_board::Mode::Words,
+            ThemeId::default(),
+        );
+        let content = data.content.as_mut().unwrap();
+        content.base.instructions.text = Some("Hello".to_owned());
+        content.base.stickers = vec![Sticker::Text(Text::from_str("Hello"))];
+
+        let mut body = ModuleBody::TappingBoard(data);
+        visit_texts(&mut body, &mut |text| {
+            if text == "Hello" {
+                *text = "שלום".to_owned();
+            }
+        });
+
+        let content = match body {
+            ModuleBody::TappingBoard(data) => data.content.unwrap(),
+            _ => unreachable!(),
+        };
+        assert_eq!(Some("שלום".to_owned()), content.base.instructions.text);
+
+        let value = match &content.base.stickers[..] {
+            [Sticker::Text(text)] => serde_json::from_str::<Value>(&text.value).unwrap(),
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            serde_json::from_str::<Value>(&Text::value_from_str("שלום")).unwrap(),
+            value
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::admin::{InviteFailedReason, InviteSchoolUsersResponse};
+use shared::domain::billing::{
+    AccountIfAuthorized, AccountSeats, GetSchoolAccountResponse, InviteSchoolMembersResponse,
+    ListSchoolInvitesResponse, ListUserSchoolInvitesResponse,
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+const SCHOOL_ID: &str = "c4b2f80c-6e5f-11ee-b962-0242ac120002";
+const INVITING_SCHOOL_ID: &str = "d0e1a4f6-6e5f-11ee-b962-0242ac120002";
+
+async fn get_school(port: u16, school_id: &str) -> anyhow::Result<GetSchoolAccountResponse> {
+    let resp = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}/v1/schools/{}", port, school_id))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(resp.json().await?)
+}
+
+async fn get_seats(port: u16) -> anyhow::Result<AccountSeats> {
+    match get_school(port, SCHOOL_ID).await?.account {
+        AccountIfAuthorized::Authorized(account) => {
+            Ok(account.seats.expect("school accounts have seats"))
+        }
+        AccountIfAuthorized::Unauthorized => anyhow::bail!("expected to be authorized"),
+    }
+}
+
+async fn list_school_invites(port: u16) -> anyhow::Result<ListSchoolInvitesResponse> {
+    let resp = reqwest::Client::new()
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/schools/{}/invites",
+            port, SCHOOL_ID
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(resp.json().await?)
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan", "Fixture::School")
+)]
+async fn school_seats(port: u16) -> anyhow::Result<()> {
+    let seats = get_seats(port).await?;
+
+    assert_eq!(seats.members, 2);
+    assert_eq!(seats.pending_invites, 2);
+    assert_eq!(seats.available(), Some(1));
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan", "Fixture::School")
+)]
+async fn invite_school_members(port: u16) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/schools/{}/invites",
+            port, SCHOOL_ID
+        ))
+        .login()
+        .json(&json!({
+            "emails": [
+                "fooodoosfakeemail@testing238EE.test",
+                "pending1@test.test",
+                "new1@test.test",
+                "new2@test.test",
+            ],
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: InviteSchoolMembersResponse = resp.json().await?;
+
+    assert_eq!(body.invites.len(), 1);
+    assert_eq!(body.invites[0].email, "new1@test.test");
+
+    let reasons: Vec<_> = body
+        .failures
+        .iter()
+        .map(|failure| (failure.email.as_str(), failure.reason.clone()))
+        .collect();
+
+    assert!(matches!(
+        reasons.as_slice(),
+        [
+            (
+                "fooodoosfakeemail@testing238EE.test",
+                InviteFailedReason::AlreadyMember
+            ),
+            ("pending1@test.test", InviteFailedReason::AlreadyInvited),
+            ("new2@test.test", InviteFailedReason::NoSeatsAvailable),
+        ]
+    ));
+
+    let seats = get_seats(port).await?;
+    assert_eq!(seats.pending_invites, 3);
+    assert!(seats.is_full());
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan", "Fixture::School")
+)]
+async fn admin_invite_respects_seats(port: u16) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .post(&format!("http://0.0.0.0:{}/v1/admin/invite-users", port))
+        .login()
+        .json(&json!({
+            "school_id": SCHOOL_ID,
+            "data": "fooodoosfakeemail23@teting28FE.test",
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: InviteSchoolUsersResponse = resp.json().await?;
+    assert!(body.failures.is_empty());
+
+    let seats = get_seats(port).await?;
+    assert_eq!(seats.members, 3);
+    assert!(seats.is_full());
+
+    let resp = reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/schools/{}/invites",
+            port, SCHOOL_ID
+        ))
+        .login()
+        .json(&json!({ "emails": ["new1@test.test"] }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: InviteSchoolMembersResponse = resp.json().await?;
+
+    assert!(body.invites.is_empty());
+    assert!(matches!(
+        body.failures[0].reason,
+        InviteFailedReason::NoSeatsAvailable
+    ));
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan", "Fixture::School")
+)]
+async fn revoke_school_invite(port: u16) -> anyhow::Result<()> {
+    let url = format!(
+        "http://0.0.0.0:{}/v1/schools/{}/invites/c4b2fa64-6e5f-11ee-b962-0242ac120002",
+        port, SCHOOL_ID
+    );
+
+    let resp = reqwest::Client::new().delete(&url).login().send().await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let invites = list_school_invites(port).await?.invites;
+    assert_eq!(invites.len(), 1);
+    assert_eq!(invites[0].email, "pending2@test.test");
+
+    let resp = reqwest::Client::new().delete(&url).login().send().await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan", "Fixture::School")
+)]
+async fn remove_school_member_with_replacement(port: u16) -> anyhow::Result<()> {
+    // Take the last seat so that the replacement can only get in through the freed up seat.
+    reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/schools/{}/invites",
+            port, SCHOOL_ID
+        ))
+        .login()
+        .json(&json!({ "emails": ["new1@test.test"] }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let resp = reqwest::Client::new()
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/schools/{}/members/7b96a41c-e406-11eb-8176-efd86dd7f444",
+            port, SCHOOL_ID
+        ))
+        .login()
+        .json(&json!({ "replacement_email": "replacement@test.test" }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let school = get_school(port, SCHOOL_ID).await?;
+    assert_eq!(school.users.len(), 1);
+
+    let invites = list_school_invites(port).await?.invites;
+    assert!(invites
+        .iter()
+        .any(|invite| invite.email == "replacement@test.test"));
+
+    let seats = get_seats(port).await?;
+    assert_eq!(seats.members, 1);
+    assert_eq!(seats.pending_invites, 4);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan", "Fixture::School")
+)]
+async fn remove_last_school_admin(port: u16) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/schools/{}/members/1f241e1b-b537-493f-a230-075cb16315be",
+            port, SCHOOL_ID
+        ))
+        .login()
+        .json(&json!({}))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan", "Fixture::SchoolInvite")
+)]
+async fn accept_school_invite(port: u16) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/user/me/school-invites",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: ListUserSchoolInvitesResponse = resp.json().await?;
+
+    assert_eq!(body.invites.len(), 1);
+    assert_eq!(body.invites[0].school_name, "Inviting School");
+
+    let resp = reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/user/me/school-invites/{}/accept",
+            port, body.invites[0].id
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let school = get_school(port, INVITING_SCHOOL_ID).await?;
+    assert!(school
+        .users
+        .iter()
+        .any(|user| user.user.email == "test@test.test" && !user.is_admin));
+
+    match school.account {
+        AccountIfAuthorized::Authorized(account) => {
+            let seats = account.seats.expect("school accounts have seats");
+            assert_eq!(seats.members, 2);
+            assert_eq!(seats.pending_invites, 0);
+        }
+        AccountIfAuthorized::Unauthorized => anyhow::bail!("expected to be authorized"),
+    }
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan", "Fixture::SchoolInvite")
+)]
+async fn decline_school_invite(port: u16) -> anyhow::Result<()> {
+    let url = format!(
+        "http://0.0.0.0:{}/v1/user/me/school-invites/d0e1a74e-6e5f-11ee-b962-0242ac120002",
+        port
+    );
+
+    let resp = reqwest::Client::new().delete(&url).login().send().await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = reqwest::Client::new()
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/user/me/school-invites",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: ListUserSchoolInvitesResponse = resp.json().await?;
+    assert!(body.invites.is_empty());
+
+    let resp = reqwest::Client::new().delete(&url).login().send().await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,631 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac, NewMac};
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use sha2::Sha256;
+use shared::domain::billing::{
+    AccountIfAuthorized, CollectionMethod, CreateSubscriptionResponse, GetSchoolAccountResponse,
+    IndividualAccountResponse, Invoice, InvoiceStatus, ListInvoicesResponse,
+    ListWebhookEventsQuery, ListWebhookEventsResponse, PlanType, Subscription, SubscriptionStatus,
+    WebhookEvent, WebhookEventStatus,
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt, STRIPE_WEBHOOK_SECRET},
+};
+
+const PAYMENT_METHOD_ATTACHED: &str =
+    include_str!("../../fixtures/stripe/payment_method_attached.json");
+const BILLING_PORTAL_SESSION_CREATED: &str =
+    include_str!("../../fixtures/stripe/billing_portal_session_created.json");
+const SUBSCRIPTION_UPDATED_TIERED: &str =
+    include_str!("../../fixtures/stripe/customer_subscription_updated_tiered.json");
+
+/// Signs `payload` the way Stripe does, see <https://stripe.com/docs/webhooks#verify-manually>.
+fn stripe_signature(payload: &str) -> String {
+    let timestamp = Utc::now().timestamp();
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(STRIPE_WEBHOOK_SECRET.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.{}", timestamp, payload).as_bytes());
+
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+async fn send_webhook(port: u16, payload: &str) -> anyhow::Result<StatusCode> {
+    let resp = reqwest::Client::new()
+        .post(&format!("http://0.0.0.0:{}/v1/stripe-webhook", port))
+        .header("Stripe-Signature", stripe_signature(payload))
+        .body(payload.to_owned())
+        .send()
+        .await?;
+
+    Ok(resp.status())
+}
+
+async fn list_webhook_events(
+    port: u16,
+    status: Option<WebhookEventStatus>,
+) -> anyhow::Result<ListWebhookEventsResponse> {
+    let resp = reqwest::Client::new()
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/admin/billing/webhook-events",
+            port
+        ))
+        .query(&ListWebhookEventsQuery {
+            status,
+            ..Default::default()
+        })
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(resp.json().await?)
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Billing"))]
+async fn webhook_redelivery_is_skipped(port: u16) -> anyhow::Result<()> {
+    assert_eq!(
+        send_webhook(port, PAYMENT_METHOD_ATTACHED).await?,
+        StatusCode::OK
+    );
+
+    let resp = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/account", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: IndividualAccountResponse = resp.json().await?;
+
+    assert!(body
+        .account
+        .and_then(|account| account.payment_method)
+        .is_some());
+
+    // Stripe redelivers the event, e.g. because our response timed out
+    assert_eq!(
+        send_webhook(port, PAYMENT_METHOD_ATTACHED).await?,
+        StatusCode::OK
+    );
+
+    let body = list_webhook_events(port, None).await?;
+
+    assert_eq!(body.events.len(), 1);
+    assert_eq!(body.events[0].id.as_str(), "evt_1O4TkZ2eZvKYlo2CMFPqvB3c");
+    assert_eq!(body.events[0].event_type, "payment_method.attached");
+    assert_eq!(body.events[0].status, WebhookEventStatus::Processed);
+    assert_eq!(body.events[0].attempts, 1);
+
+    // handled events can't be replayed
+    let resp = reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/admin/billing/webhook-events/evt_1O4TkZ2eZvKYlo2CMFPqvB3c/replay",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Billing"))]
+async fn webhook_unsupported_event_is_logged(port: u16) -> anyhow::Result<()> {
+    assert_eq!(
+        send_webhook(port, BILLING_PORTAL_SESSION_CREATED).await?,
+        StatusCode::OK
+    );
+
+    let body = list_webhook_events(port, Some(WebhookEventStatus::Ignored)).await?;
+
+    assert_eq!(body.events.len(), 1);
+    assert_eq!(body.events[0].event_type, "billing_portal.session.created");
+    assert!(body.events[0].error.is_some());
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Billing"))]
+async fn webhook_bad_signature_is_not_logged(port: u16) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .post(&format!("http://0.0.0.0:{}/v1/stripe-webhook", port))
+        .header("Stripe-Signature", "t=1697673600,v1=deadbeef")
+        .body(PAYMENT_METHOD_ATTACHED)
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = list_webhook_events(port, None).await?;
+
+    assert!(body.events.is_empty());
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Billing"))]
+async fn webhook_failed_event_replay(port: u16) -> anyhow::Result<()> {
+    // tiered prices don't have a flat amount, which we can't handle
+    assert_eq!(
+        send_webhook(port, SUBSCRIPTION_UPDATED_TIERED).await?,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+
+    // failed events are handled again when Stripe retries them
+    assert_eq!(
+        send_webhook(port, SUBSCRIPTION_UPDATED_TIERED).await?,
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+
+    let body = list_webhook_events(port, Some(WebhookEventStatus::Failed)).await?;
+
+    assert_eq!(body.events.len(), 1);
+    assert_eq!(body.events[0].attempts, 2);
+    assert!(body.events[0]
+        .error
+        .as_deref()
+        .unwrap_or_default()
+        .contains("Missing subscription plan amount"));
+
+    let resp = reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/admin/billing/webhook-events/evt_1O4Tp82eZvKYlo2CdS0hWn4L/replay",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: WebhookEvent = resp.json().await?;
+
+    assert_eq!(body.status, WebhookEventStatus::Failed);
+    assert_eq!(body.attempts, 3);
+
+    let resp = reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/admin/billing/webhook-events/evt_unknown/replay",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+async fn subscribe(port: u16, body: serde_json::Value) -> anyhow::Result<reqwest::Response> {
+    Ok(reqwest::Client::new()
+        .post(&format!("http://0.0.0.0:{}/v1/billing/subscribe", port))
+        .json(&body)
+        .login()
+        .send()
+        .await?)
+}
+
+async fn current_subscription(port: u16) -> anyhow::Result<Subscription> {
+    let resp = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/account", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: IndividualAccountResponse = resp.json().await?;
+
+    body.account
+        .and_then(|account| account.subscription)
+        .ok_or_else(|| anyhow::anyhow!("no subscription"))
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan")
+)]
+async fn subscribe_with_trial(port: u16) -> anyhow::Result<()> {
+    let resp = subscribe(port, json!({ "plan_type": "individual-basic-monthly" })).await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // nothing to pay during the trial
+    let body: Option<CreateSubscriptionResponse> = resp.json().await?;
+    assert!(body.is_none());
+
+    let subscription = current_subscription(port).await?;
+
+    assert_eq!(
+        subscription.subscription_plan_type,
+        PlanType::IndividualBasicMonthly
+    );
+    assert!(matches!(subscription.status, SubscriptionStatus::Active));
+    assert_eq!(subscription.price.inner(), 1799);
+
+    // only one subscription at a time
+    let resp = subscribe(port, json!({ "plan_type": "individual-pro-monthly" })).await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan")
+)]
+async fn subscribe_with_setup_intent(port: u16) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/billing/payment-method",
+            port
+        ))
+        .json(&json!({ "plan_type": "individual-basic-monthly" }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let client_secret: String = resp.json().await?;
+    let setup_intent_id = client_secret.trim_end_matches("_secret");
+
+    let resp = subscribe(
+        port,
+        json!({ "plan_type": "individual-basic-monthly", "setup_intent_id": "seti_unknown" }),
+    )
+    .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = subscribe(
+        port,
+        json!({ "plan_type": "individual-basic-monthly", "setup_intent_id": setup_intent_id }),
+    )
+    .await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let subscription = current_subscription(port).await?;
+
+    assert!(matches!(subscription.status, SubscriptionStatus::Active));
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan")
+)]
+async fn subscription_upgrade(port: u16) -> anyhow::Result<()> {
+    let resp = subscribe(port, json!({ "plan_type": "individual-basic-monthly" })).await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let upgrade = |body: serde_json::Value| {
+        reqwest::Client::new()
+            .post(&format!(
+                "http://0.0.0.0:{}/v1/billing/subscription/upgrade",
+                port
+            ))
+            .json(&body)
+            .login()
+            .send()
+    };
+
+    let resp =
+        upgrade(json!({ "plan_type": "individual-pro-monthly", "promotion_code": "NOPE" })).await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp =
+        upgrade(json!({ "plan_type": "individual-pro-monthly", "promotion_code": "WELCOME" }))
+            .await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let subscription = current_subscription(port).await?;
+
+    assert_eq!(
+        subscription.subscription_plan_type,
+        PlanType::IndividualProMonthly
+    );
+    assert_eq!(subscription.price.inner(), 2999);
+
+    // can't go back down
+    let resp = upgrade(json!({ "plan_type": "individual-basic-monthly" })).await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan")
+)]
+async fn subscription_cancel(port: u16) -> anyhow::Result<()> {
+    let resp = subscribe(port, json!({ "plan_type": "individual-basic-monthly" })).await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let cancel = |status: &str| {
+        reqwest::Client::new()
+            .patch(&format!(
+                "http://0.0.0.0:{}/v1/billing/subscription/cancel",
+                port
+            ))
+            .json(&json!({ "status": status }))
+            .login()
+            .send()
+    };
+
+    let resp = cancel("remove").await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = cancel("period-end").await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let subscription = current_subscription(port).await?;
+
+    assert!(matches!(subscription.status, SubscriptionStatus::Canceled));
+
+    let resp = cancel("remove").await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let subscription = current_subscription(port).await?;
+
+    assert!(matches!(subscription.status, SubscriptionStatus::Active));
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::SubscriptionPlan")
+)]
+async fn subscription_pause(port: u16) -> anyhow::Result<()> {
+    let resp = subscribe(port, json!({ "plan_type": "individual-basic-monthly" })).await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let pause = |paused: bool| {
+        reqwest::Client::new()
+            .patch(&format!(
+                "http://0.0.0.0:{}/v1/billing/subscription/pause",
+                port
+            ))
+            .json(&json!({ "paused": paused }))
+            .login()
+            .send()
+    };
+
+    let resp = pause(true).await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let subscription = current_subscription(port).await?;
+
+    assert!(matches!(subscription.status, SubscriptionStatus::Paused));
+
+    // paused subscriptions can't be canceled
+    let resp = reqwest::Client::new()
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/billing/subscription/cancel",
+            port
+        ))
+        .json(&json!({ "status": "period-end" }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = pause(false).await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let subscription = current_subscription(port).await?;
+
+    assert!(matches!(subscription.status, SubscriptionStatus::Active));
+
+    Ok(())
+}
+
+const UNSUBSCRIBED_SCHOOL_ID: &str = "e2a7c5a8-6e5f-11ee-b962-0242ac120002";
+
+async fn create_invoiced_subscription(
+    port: u16,
+    body: serde_json::Value,
+) -> anyhow::Result<reqwest::Response> {
+    Ok(reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/admin/schools/{}/invoiced-subscription",
+            port, UNSUBSCRIBED_SCHOOL_ID
+        ))
+        .json(&body)
+        .login()
+        .send()
+        .await?)
+}
+
+async fn school_subscription(port: u16) -> anyhow::Result<Subscription> {
+    let resp = reqwest::Client::new()
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/schools/{}",
+            port, UNSUBSCRIBED_SCHOOL_ID
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: GetSchoolAccountResponse = resp.json().await?;
+
+    match body.account {
+        AccountIfAuthorized::Authorized(account) => account
+            .subscription
+            .ok_or_else(|| anyhow::anyhow!("no subscription")),
+        AccountIfAuthorized::Unauthorized => anyhow::bail!("expected to be authorized"),
+    }
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::User",
+        "Fixture::SubscriptionPlan",
+        "Fixture::UnsubscribedSchool"
+    )
+)]
+async fn invoiced_school_subscription(port: u16) -> anyhow::Result<()> {
+    let resp = create_invoiced_subscription(
+        port,
+        json!({
+            "plan_type": "school-level-1-monthly",
+            "purchase_order_number": "PO-1234",
+        }),
+    )
+    .await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // the school gets the plan while the invoice is open
+    let subscription = school_subscription(port).await?;
+
+    assert!(matches!(subscription.status, SubscriptionStatus::Active));
+    assert_eq!(
+        subscription.subscription_plan_type,
+        PlanType::SchoolLevel1Monthly
+    );
+
+    let purchase_order = subscription.purchase_order.expect("purchase order");
+    assert_eq!(purchase_order.purchase_order_number, "PO-1234");
+    assert_eq!(purchase_order.days_until_due, 30);
+
+    let resp = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}/v1/billing/invoices", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: ListInvoicesResponse = resp.json().await?;
+
+    assert_eq!(body.invoices.len(), 1);
+
+    let invoice = &body.invoices[0];
+    assert_eq!(invoice.status, InvoiceStatus::Open);
+    assert_eq!(invoice.collection_method, CollectionMethod::SendInvoice);
+    assert_eq!(invoice.amount_due.inner(), 11500);
+    assert_eq!(
+        invoice
+            .due_at
+            .map(|due_at| (due_at - invoice.created_at).num_days()),
+        Some(30)
+    );
+    assert!(invoice.pdf_url.is_some());
+
+    let resp = reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/admin/billing/invoices/{}/paid",
+            port, invoice.id
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let paid: Invoice = resp.json().await?;
+
+    assert_eq!(paid.status, InvoiceStatus::Paid);
+    assert_eq!(paid.amount_paid.inner(), 11500);
+    assert!(paid.paid_at.is_some());
+
+    let resp = reqwest::Client::new()
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/admin/schools/{}/invoices",
+            port, UNSUBSCRIBED_SCHOOL_ID
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: ListInvoicesResponse = resp.json().await?;
+
+    assert_eq!(body.invoices.len(), 1);
+    assert_eq!(body.invoices[0].status, InvoiceStatus::Paid);
+
+    let subscription = school_subscription(port).await?;
+
+    assert_eq!(
+        subscription.amount_due_in_cents.map(|due| due.inner()),
+        Some(0)
+    );
+
+    // only one subscription at a time
+    let resp = create_invoiced_subscription(
+        port,
+        json!({
+            "plan_type": "school-level-1-monthly",
+            "purchase_order_number": "PO-5678",
+        }),
+    )
+    .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::User",
+        "Fixture::SubscriptionPlan",
+        "Fixture::UnsubscribedSchool"
+    )
+)]
+async fn invoiced_subscription_requires_school_plan(port: u16) -> anyhow::Result<()> {
+    let resp = create_invoiced_subscription(
+        port,
+        json!({
+            "plan_type": "individual-basic-monthly",
+            "purchase_order_number": "PO-1234",
+            "days_until_due": 60,
+        }),
+    )
+    .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // nothing was invoiced
+    let resp = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}/v1/billing/invoices", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: ListInvoicesResponse = resp.json().await?;
+
+    assert!(body.invoices.is_empty());
+
+    Ok(())
+}
@@ -1,8 +1,14 @@
 use http::StatusCode;
 use macros::test_service;
 use serde_json::json;
-use shared::domain::category::{
-    CategoryTreeScope, CreateCategoryRequest, GetCategoryRequest, NewCategoryResponse,
+use shared::domain::{
+    asset::AssetId,
+    category::{
+        CategoryId, CategoryResponse, CategoryTreeScope, CategoryUsage, CategoryUsageResponse,
+        CreateCategoryRequest, GetCategoryRequest, MergeCategoryRequest, NewCategoryResponse,
+        RecategorizeRequest, RecategorizeResponse,
+    },
+    jig::JigId,
 };
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use uuid::Uuid;
@@ -385,3 +391,217 @@ async fn update_rename(port: u16) -> anyhow::Result<()> {
     )
     .await
 }
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::CategoryOrdering")
+)]
+async fn update_translated_name(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/category/81c4796a-e883-11ea-93f0-df2484ab6b11",
+            port
+        ))
+        .json(&json!({"translated_name": {"he": "שלוש", "es": "Tres"}}))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let body: CategoryResponse = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/category?ids=81c4796a-e883-11ea-93f0-df2484ab6b11",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let category = &body.categories[0];
+    assert_eq!(category.name, "Three");
+    assert_eq!(category.translated_name["he"], "שלוש");
+    assert_eq!(category.translated_name["es"], "Tres");
+
+    Ok(())
+}
+
+async fn usage_of(port: u16, id: CategoryId) -> anyhow::Result<CategoryUsage> {
+    let body: CategoryUsageResponse = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}/v1/category/usage", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(body
+        .usage
+        .into_iter()
+        .find(|usage| usage.id == id)
+        .expect("category is missing from usage"))
+}
+
+async fn recategorize(
+    port: u16,
+    req: &RecategorizeRequest,
+) -> anyhow::Result<RecategorizeResponse> {
+    let resp = reqwest::Client::new()
+        .post(&format!("http://0.0.0.0:{}/v1/category/recategorize", port))
+        .json(req)
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(resp.json().await?)
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Jig",
+        "Fixture::CategoryOrdering"
+    )
+)]
+async fn recategorize_preview(port: u16) -> anyhow::Result<()> {
+    let jig_id: JigId = "0cc084bc-7c83-11eb-9f77-e3218dffb008".parse()?;
+    let one: CategoryId = "7e0cc6e2-e883-11ea-93f0-cb82f9b3da95".parse()?;
+    let two: CategoryId = "7fe19326-e883-11ea-93f0-5343493c17c4".parse()?;
+
+    let mut req = RecategorizeRequest {
+        assets: vec![jig_id.into()],
+        add: vec![one],
+        preview: true,
+        ..RecategorizeRequest::default()
+    };
+
+    let body = recategorize(port, &req).await?;
+
+    assert_eq!(body.changed.len(), 1);
+    assert_eq!(body.changed[0].asset_id, AssetId::from(jig_id));
+    assert_eq!(body.changed[0].added, vec![one]);
+    assert_eq!(usage_of(port, one).await?.jigs, 0);
+
+    req.preview = false;
+    recategorize(port, &req).await?;
+
+    assert_eq!(usage_of(port, one).await?.jigs, 1);
+
+    // everything in `one` is moved to `two`.
+    let body = recategorize(
+        port,
+        &RecategorizeRequest {
+            in_category: Some(one),
+            add: vec![two],
+            remove: vec![one],
+            ..RecategorizeRequest::default()
+        },
+    )
+    .await?;
+
+    assert_eq!(body.changed.len(), 1);
+    assert_eq!(body.changed[0].added, vec![two]);
+    assert_eq!(body.changed[0].removed, vec![one]);
+    assert_eq!(usage_of(port, one).await?.jigs, 0);
+    assert_eq!(usage_of(port, two).await?.jigs, 1);
+
+    // nothing is left to change.
+    req.preview = true;
+    req.add = vec![two];
+    assert!(recategorize(port, &req).await?.changed.is_empty());
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Jig",
+        "Fixture::CategoryOrdering"
+    )
+)]
+async fn merge(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let jig_id: JigId = "0cc084bc-7c83-11eb-9f77-e3218dffb008".parse()?;
+    let two: CategoryId = "7fe19326-e883-11ea-93f0-5343493c17c4".parse()?;
+    let three: CategoryId = "81c4796a-e883-11ea-93f0-df2484ab6b11".parse()?;
+
+    recategorize(
+        port,
+        &RecategorizeRequest {
+            assets: vec![jig_id.into()],
+            add: vec![two, three],
+            ..RecategorizeRequest::default()
+        },
+    )
+    .await?;
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/category/{}/merge",
+            port, two
+        ))
+        .json(&MergeCategoryRequest { into: three })
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    assert_eq!(usage_of(port, three).await?.jigs, 1);
+
+    let body: CategoryResponse = client
+        .get(&format!("http://0.0.0.0:{}/v1/category", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let names: Vec<_> = body
+        .categories
+        .iter()
+        .map(|category| category.name.as_str())
+        .collect();
+
+    assert_eq!(names, vec!["One", "Three"]);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::CategoryNesting")
+)]
+async fn merge_into_descendant(port: u16) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/category/afbce03c-e90f-11ea-8281-cfde02f6b582/merge",
+            port
+        ))
+        .json(&json!({"into": "01cff7d8-e910-11ea-8281-7f86c625a156"}))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    Ok(())
+}
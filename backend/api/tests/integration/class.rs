@@ -0,0 +1,347 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::{
+    class::{
+        Class, ClassId, ClassLoginRosterResponse, Student, StudentDataResponse,
+        StudentLoginResponse,
+    },
+    CreateResponse,
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn student_data_export_and_delete(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/class", port))
+        .json(&json!({ "displayName": "Grade 2" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let class_id = resp.json::<CreateResponse<ClassId>>().await?.id;
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/class/{}/students",
+            port, class_id.0
+        ))
+        .json(&json!({ "displayName": "Noa" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let student: Student = resp.json().await?;
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/class/login", port))
+        .json(&json!({ "code": { "code": student.login_code.0 } }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let login: StudentLoginResponse = resp.json().await?;
+
+    client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/codes/instance", port))
+        .json(&json!({
+            "code": 123456,
+            "student_token": login.token,
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let data_url = format!(
+        "http://0.0.0.0:{}/v1/class/{}/students/{}/data",
+        port, class_id.0, student.id.0
+    );
+
+    let resp = client
+        .get(&data_url)
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let data: serde_json::Value = resp.json().await?;
+
+    // the export can be passed on, so it leaves out what the student logs in with
+    assert!(data["student"].get("loginCode").is_none());
+    assert!(data["student"].get("picturePassword").is_none());
+
+    let data: StudentDataResponse = serde_json::from_value(data)?;
+
+    assert_eq!(data.student.display_name, "Noa");
+    assert_eq!(data.sessions.len(), 1);
+    assert_eq!(data.sessions[0].code.0, 123456);
+
+    let resp = client.delete(&data_url).login().send().await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client.get(&data_url).login().send().await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/codes/123456/report",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let report: serde_json::Value = resp.json().await?;
+
+    assert_eq!(report["players"], json!([]));
+
+    Ok(())
+}
+
+/// Creates a class with a student named `name`, returning the class and the student.
+async fn create_class_with_student(
+    client: &reqwest::Client,
+    port: u16,
+    name: &str,
+) -> anyhow::Result<(Class, Student)> {
+    let class_id = client
+        .post(&format!("http://0.0.0.0:{}/v1/class", port))
+        .json(&json!({ "displayName": "Grade 2" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<CreateResponse<ClassId>>()
+        .await?
+        .id;
+
+    let student: Student = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/class/{}/students",
+            port, class_id.0
+        ))
+        .json(&json!({ "displayName": name }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let class: Class = client
+        .get(&format!("http://0.0.0.0:{}/v1/class/{}", port, class_id.0))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok((class, student))
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User")
+)]
+async fn class_names_are_trimmed(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/class", port))
+        .json(&json!({ "displayName": "   " }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let class_id = client
+        .post(&format!("http://0.0.0.0:{}/v1/class", port))
+        .json(&json!({ "displayName": "  Grade 3 " }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<CreateResponse<ClassId>>()
+        .await?
+        .id;
+
+    let class_url = format!("http://0.0.0.0:{}/v1/class/{}", port, class_id.0);
+
+    let class: Class = client
+        .get(&class_url)
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_eq!(class.display_name, "Grade 3");
+
+    let resp = client
+        .patch(&class_url)
+        .json(&json!({ "displayName": "" }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User")
+)]
+async fn roster_shows_short_names(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let (class, student) = create_class_with_student(&client, port, "Noa Levi").await?;
+
+    let roster: ClassLoginRosterResponse = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/class/login/{}",
+            port, class.login_code.0
+        ))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_eq!(roster.display_name, "Grade 2");
+    assert_eq!(roster.students.len(), 1);
+    assert_eq!(roster.students[0].id, student.id);
+    assert_eq!(roster.students[0].display_name, "Noa L.");
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User")
+)]
+async fn roster_lookups_are_throttled(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let (class, _) = create_class_with_student(&client, port, "Noa Levi").await?;
+
+    let roster_url = |code: &str| format!("http://0.0.0.0:{}/v1/class/login/{}", port, code);
+
+    for _ in 0..30 {
+        let resp = client.get(&roster_url("ZZZZZZ")).send().await?;
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    let resp = client.get(&roster_url(&class.login_code.0)).send().await?;
+
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User")
+)]
+async fn picture_login_locks_out_after_failures(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let (class, student) = create_class_with_student(&client, port, "Noa Levi").await?;
+
+    let login = |picture_password: &[i16]| {
+        client
+            .post(&format!("http://0.0.0.0:{}/v1/class/login", port))
+            .json(&json!({
+                "picture": {
+                    "classCode": class.login_code.0,
+                    "studentId": student.id,
+                    "picturePassword": picture_password,
+                }
+            }))
+            .send()
+    };
+
+    let mut wrong_password = student.picture_password.0.clone();
+    wrong_password[0] = (wrong_password[0] + 1) % 9;
+
+    for _ in 0..5 {
+        let resp = login(&wrong_password).await?;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // locked out, even with the right password
+    let resp = login(&student.picture_password.0).await?;
+
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // logging in with the student's own code still works
+    client
+        .post(&format!("http://0.0.0.0:{}/v1/class/login", port))
+        .json(&json!({ "code": { "code": student.login_code.0 } }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Jig",
+        "Fixture::OtherUserJig"
+    )
+)]
+async fn assign_only_accessible_jigs(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let (class, _) = create_class_with_student(&client, port, "Noa Levi").await?;
+
+    let assign = |jig_id: &str| {
+        client
+            .post(&format!(
+                "http://0.0.0.0:{}/v1/class/{}/assignments",
+                port, class.id.0
+            ))
+            .json(&json!({ "assetId": { "jigId": jig_id } }))
+            .login()
+            .send()
+    };
+
+    let resp = assign("3a71522a-cd77-11eb-8dc1-af3e35f7c743").await?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = assign("7b5c23e4-3a54-11ef-9c0c-0b7f3c0e8a64").await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = assign("00000000-0000-0000-0000-000000000000").await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
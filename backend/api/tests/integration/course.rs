@@ -9,6 +9,7 @@ use crate::{
     helpers::{setup_service, LoginExt},
 };
 
+pub mod progress;
 pub mod unit;
 
 #[test_service(
@@ -0,0 +1,81 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::{json, Value};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+const COURSE: &str = "47b3c062-906c-11ed-b4f6-9b0c5b1939a1";
+const UNIT: &str = "09225312-906d-11ed-b4f6-afb0e90115b3";
+
+async fn get(port: u16, path: &str) -> anyhow::Result<Value> {
+    let resp = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}{}", port, path))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(resp.json().await?)
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Course")
+)]
+async fn course_progress(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+    let progress_path = format!("/v1/course/{}/progress", COURSE);
+
+    let body = get(port, &progress_path).await?;
+
+    assert_eq!(body["unitCount"], json!(1));
+    assert_eq!(body["continueUnitId"], json!(UNIT));
+    assert_eq!(body["lastPlayedAt"], json!(null));
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/course/{}/unit/{}/progress",
+            port, COURSE, UNIT
+        ))
+        .login()
+        .json(&json!({"status": "completed"}))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let body = get(port, &progress_path).await?;
+
+    assert_eq!(body["completedCount"], json!(1));
+    assert_eq!(body["percentComplete"], json!(100));
+    assert_eq!(body["continueUnitId"], json!(null));
+    assert_eq!(body["units"][0]["unitId"], json!(UNIT));
+    assert_eq!(body["units"][0]["status"], json!("completed"));
+
+    let body = get(port, "/v1/course/in-progress?includeCompleted=true").await?;
+
+    assert_eq!(body["courses"].as_array().unwrap().len(), 1);
+    assert_eq!(body["courses"][0]["course"]["id"], json!(COURSE));
+
+    // a unit of another course
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/course/{}/unit/e0984370-906c-11ed-b4f6-3f864931e86f/progress",
+            port, COURSE
+        ))
+        .login()
+        .json(&json!({"status": "started"}))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
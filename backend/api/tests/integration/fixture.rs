@@ -21,6 +21,13 @@ pub enum Fixture {
     Playlist,
     Resource,
     Course,
+    Billing,
+    SubscriptionPlan,
+    School,
+    SchoolInvite,
+    UnsubscribedSchool,
+    WebMedia,
+    OtherUserJig,
 }
 
 impl Fixture {
@@ -47,6 +54,15 @@ impl Fixture {
             Self::Playlist => include_str!("../../fixtures/20_playlist.sql"),
             Self::Resource => include_str!("../../fixtures/21_resource.sql"),
             Self::Course => include_str!("../../fixtures/22_course.sql"),
+            Self::Billing => include_str!("../../fixtures/23_billing.sql"),
+            Self::SubscriptionPlan => include_str!("../../fixtures/24_subscription_plan.sql"),
+            Self::School => include_str!("../../fixtures/25_school.sql"),
+            Self::SchoolInvite => include_str!("../../fixtures/26_school_invite.sql"),
+            Self::UnsubscribedSchool => {
+                include_str!("../../fixtures/27_unsubscribed_school.sql")
+            }
+            Self::WebMedia => include_str!("../../fixtures/28_web_media.sql"),
+            Self::OtherUserJig => include_str!("../../fixtures/29_other_user_jig.sql"),
         }
     }
 }
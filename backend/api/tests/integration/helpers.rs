@@ -1,12 +1,15 @@
 use actix_web::dev::ServerHandle;
 use chrono::{Duration, Utc};
+use ji_cloud_api::content_safety::{image::StubImageClassifier, ContentSafety};
 use ji_cloud_api::http::Application;
+use ji_cloud_api::service::payment::fake::FakePaymentProvider;
 use ji_core::settings::{JwkAudiences, RuntimeSettings};
 use rand::Rng;
 use shared::config::RemoteTarget;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::{postgres::PgConnectOptions, Executor, PgPool};
 use std::panic::{self, UnwindSafe};
+use std::sync::Arc;
 
 use crate::fixture::Fixture;
 use crate::service::{Service, TestServicesSettings};
@@ -56,6 +59,20 @@ pub fn generate_paseto_key() -> [u8; 32] {
     arr
 }
 
+/// Payment provider the test server bills with. It knows the prices of the plans in the
+/// `SubscriptionPlan` fixture.
+fn payment_provider() -> FakePaymentProvider {
+    FakePaymentProvider::new()
+        .with_price("price_test_basic_monthly", 1799)
+        .with_price("price_test_pro_monthly", 2999)
+        .with_price("price_test_pro_annually", 29999)
+        .with_price("price_test_school_level_1_monthly", 11500)
+        .with_promotion_code("WELCOME")
+}
+
+/// Secret the test server checks Stripe webhook signatures with.
+pub const STRIPE_WEBHOOK_SECRET: &str = "whsec_test";
+
 pub static PASETO_KEY: once_cell::sync::Lazy<Box<[u8; 32]>> =
     once_cell::sync::Lazy::new(|| Box::new(generate_paseto_key()));
 
@@ -120,10 +137,13 @@ pub async fn initialize_server_and_get_db(
         None,
         None,
         None,
-        PASETO_KEY.clone(),
         None,
         None,
+        PASETO_KEY.clone(),
+        Some(Box::new(generate_paseto_key())),
+        None,
         None,
+        Some(STRIPE_WEBHOOK_SECRET.to_owned()),
     );
 
     let app = ji_cloud_api::http::build(
@@ -138,6 +158,8 @@ pub async fn initialize_server_and_get_db(
         None,
         None,
         None,
+        Some(Arc::new(payment_provider())),
+        Arc::new(ContentSafety::new(Arc::new(StubImageClassifier::default()))),
     )
     .expect("failed to initialize server");
 
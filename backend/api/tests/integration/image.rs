@@ -5,7 +5,10 @@ mod user;
 use http::StatusCode;
 use macros::test_service;
 use serde_json::json;
-use shared::domain::{image::ImageId, CreateResponse};
+use shared::domain::{
+    image::{bulk::ImageBulkUpdateResponse, ImageId},
+    CreateResponse,
+};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use uuid::Uuid;
 
@@ -354,6 +357,175 @@ async fn browse(port: u16) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::User",
+        "Fixture::Image",
+        "Fixture::MetaKinds",
+        "Fixture::MetaImage"
+    )
+)]
+async fn bulk_update_and_undo(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/image/bulk", port))
+        .json(&json!({
+            "query": { "styles": ["6389eaa0-de76-11ea-b7ab-0399bcf84df2"] },
+            "changes": {
+                "addStyles": ["6389ff7c-de76-11ea-b7ab-9b5661dd4f70"],
+                "removeStyles": ["6389eaa0-de76-11ea-b7ab-0399bcf84df2"],
+                "addTags": [0],
+                "isPremium": true,
+            },
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: ImageBulkUpdateResponse = resp.json().await?;
+
+    assert_eq!(body.image_count, 2);
+
+    let metadata = get_metadata_json("3095d05e-f2c7-11ea-89c3-3b621dd74a1f", port).await?;
+
+    assert_eq!(
+        metadata["styles"],
+        json!(["6389ff7c-de76-11ea-b7ab-9b5661dd4f70"])
+    );
+    assert_eq!(metadata["tags"], json!([0]));
+    assert_eq!(metadata["is_premium"], json!(true));
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/image/bulk/{}/undo",
+            port, body.id
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let metadata = get_metadata_json("3095d05e-f2c7-11ea-89c3-3b621dd74a1f", port).await?;
+
+    assert_eq!(
+        metadata["styles"],
+        json!(["6389eaa0-de76-11ea-b7ab-0399bcf84df2"])
+    );
+    assert_eq!(metadata["tags"], json!([]));
+    assert_eq!(metadata["is_premium"], json!(false));
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/image/bulk/{}/undo",
+            port, body.id
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::User",
+        "Fixture::Image",
+        "Fixture::MetaKinds",
+        "Fixture::MetaImage"
+    )
+)]
+async fn bulk_undo_keeps_later_edits(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/image/bulk", port))
+        .json(&json!({
+            "query": { "styles": ["6389eaa0-de76-11ea-b7ab-0399bcf84df2"] },
+            "changes": { "publishAt": { "At": "2030-01-01T00:00:00Z" } },
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: ImageBulkUpdateResponse = resp.json().await?;
+
+    assert_eq!(body.image_count, 2);
+
+    client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/image/3095d05e-f2c7-11ea-89c3-3b621dd74a1f",
+            port
+        ))
+        .json(&json!({ "publish_at": { "At": "2031-01-01T00:00:00Z" } }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/image/bulk/{}/undo",
+            port, body.id
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let metadata = get_metadata_json("3095d05e-f2c7-11ea-89c3-3b621dd74a1f", port).await?;
+
+    assert_eq!(metadata["publish_at"], json!("2031-01-01T00:00:00Z"));
+
+    let metadata = get_metadata_json("8cca6f3a-c4bb-11eb-8edf-13c75672da8f", port).await?;
+
+    assert_eq!(metadata["publish_at"], json!(null));
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Image"))]
+async fn bulk_update_empty_query(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/image/bulk", port))
+        .json(&json!({
+            "query": {},
+            "changes": { "isPremium": true },
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+async fn get_metadata_json(id: &str, port: u16) -> anyhow::Result<serde_json::Value> {
+    let resp = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}/v1/image/{}", port, id))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    Ok(body["metadata"].clone())
+}
+
 // https://cloud.google.com/storage/docs/performing-resumable-uploads#single-chunk-upload
 // #[ignore]
 // #[test_service(
@@ -202,3 +202,309 @@ async fn session_instance_play_count_flow(port: u16) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test_service(
+    setup = "setup_service",
+    fixtures(
+        "Fixture::MetaKinds",
+        "Fixture::User",
+        "Fixture::Jig",
+        "Fixture::SubscriptionPlan",
+        "Fixture::School"
+    )
+)]
+async fn child_privacy_mode_pseudonymizes_players(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/schools/c4b2f80c-6e5f-11ee-b962-0242ac120002",
+            port
+        ))
+        .json(&serde_json::json!({
+            "child_privacy_mode": true,
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/codes/instance", port))
+        .json(&serde_json::json!({
+            "code": 123456,
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: PlayerSessionInstanceResponse = resp.json().await?;
+
+    let pseudonym = body.pseudonym.clone().expect("players get a pseudonym");
+    let player_id = body.player_id;
+
+    assert!(pseudonym.starts_with("Player "));
+
+    client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/codes/instance/complete",
+            port
+        ))
+        .json(&serde_json::json!({
+            "token": body.token,
+            "session": {
+                "modules": []
+            },
+            "players_name": "Dana Levi"
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/codes/123456/sessions",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["sessions"][0]["players_name"], pseudonym);
+
+    // players on the same network are told apart by their device
+    let other: PlayerSessionInstanceResponse = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/codes/instance", port))
+        .json(&serde_json::json!({
+            "code": 123456,
+        }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_ne!(other.pseudonym, Some(pseudonym.clone()));
+
+    let again: PlayerSessionInstanceResponse = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/codes/instance", port))
+        .json(&serde_json::json!({
+            "code": 123456,
+            "player_id": player_id,
+        }))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_eq!(again.pseudonym, Some(pseudonym));
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn max_attempts_per_player(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/codes", port))
+        .json(&serde_json::json!({
+            "jigId": "3a71522a-cd77-11eb-8dc1-af3e35f7c743",
+            "settings": {
+                "direction": "ltr",
+                "maxAttempts": 40000,
+            }
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/codes", port))
+        .json(&serde_json::json!({
+            "jigId": "3a71522a-cd77-11eb-8dc1-af3e35f7c743",
+            "settings": {
+                "direction": "ltr",
+                "dueAt": "2030-01-01T00:00:00Z",
+                "maxAttempts": 1,
+            }
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let code: JigCodeResponse = resp.json().await?;
+
+    let start = |player_id: Option<uuid::Uuid>| {
+        client
+            .post(&format!("http://0.0.0.0:{}/v1/jig/codes/instance", port))
+            .json(&serde_json::json!({
+                "code": code.index.0,
+                "player_id": player_id,
+            }))
+            .send()
+    };
+
+    let first: PlayerSessionInstanceResponse =
+        start(None).await?.error_for_status()?.json().await?;
+
+    let resp = start(Some(first.player_id)).await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // another player on the same network still gets their attempt
+    let second: PlayerSessionInstanceResponse =
+        start(None).await?.error_for_status()?.json().await?;
+
+    assert_ne!(first.player_id, second.player_id);
+
+    let code_url = format!("http://0.0.0.0:{}/v1/jig/codes/{}", port, code.index.0);
+
+    let resp = client
+        .patch(&code_url)
+        .json(&serde_json::json!({ "maxAttempts": 40000 }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // settings which aren't sent are left as they are
+    client
+        .patch(&code_url)
+        .json(&serde_json::json!({
+            "name": "Homework",
+            "settings": {
+                "direction": "rtl",
+            }
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let updated: JigCodeResponse = client
+        .get(&code_url)
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_eq!(updated.name.as_deref(), Some("Homework"));
+    assert!(updated.settings.direction.is_rtl());
+    assert_eq!(updated.settings.due_at, code.settings.due_at);
+    assert_eq!(updated.settings.max_attempts, Some(1));
+
+    client
+        .patch(&code_url)
+        .json(&serde_json::json!({ "maxAttempts": null }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    start(Some(first.player_id)).await?.error_for_status()?;
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn report_tells_anonymous_players_apart(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let code: JigCodeResponse = client
+        .post(&format!("http://0.0.0.0:{}/v1/jig/codes", port))
+        .json(&serde_json::json!({
+            "jigId": "3a71522a-cd77-11eb-8dc1-af3e35f7c743",
+            "settings": {
+                "direction": "ltr",
+            }
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let play = |player_id: Option<uuid::Uuid>, name: &'static str| {
+        let client = &client;
+
+        async move {
+            let session: PlayerSessionInstanceResponse = client
+                .post(&format!("http://0.0.0.0:{}/v1/jig/codes/instance", port))
+                .json(&serde_json::json!({
+                    "code": code.index.0,
+                    "player_id": player_id,
+                }))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            client
+                .post(&format!(
+                    "http://0.0.0.0:{}/v1/jig/codes/instance/complete",
+                    port
+                ))
+                .json(&serde_json::json!({
+                    "token": session.token,
+                    "session": {
+                        "modules": []
+                    },
+                    "players_name": name
+                }))
+                .send()
+                .await?
+                .error_for_status()?;
+
+            anyhow::Ok(session.player_id)
+        }
+    };
+
+    let first = play(None, "Noa").await?;
+    // another player who typed the same name
+    play(None, "noa").await?;
+    // the first player again, with another name
+    play(Some(first), "Noa K").await?;
+
+    let report: serde_json::Value = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/codes/{}/report",
+            port, code.index.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let players = report["players"].as_array().unwrap();
+
+    assert_eq!(players.len(), 2);
+    assert_eq!(players[0]["playersName"], "Noa K");
+    assert_eq!(players[0]["attempts"], 2);
+    assert_eq!(players[1]["playersName"], "noa");
+    assert_eq!(players[1]["attempts"], 1);
+
+    Ok(())
+}
@@ -146,8 +146,10 @@ async fn create(port: u16) -> anyhow::Result<()> {
             bundle_id: "8359a48a-7c67-11eb-a0d7-0fd74777a62c".parse()?,
             section: None,
             item_kind_id: None,
-            english: Some("Just another test entry".to_owned()),
-            hebrew: None,
+            key: Some("another-test-entry".to_owned()),
+            translations: [("en".to_owned(), "Just another test entry".to_owned())]
+                .into_iter()
+                .collect(),
             status: EntryStatus::Discuss,
             zeplin_reference: None,
             comments: None,
@@ -201,3 +203,60 @@ async fn update_in_app(port: u16) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Locale"))]
+async fn create_unknown_language(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/locale/entry", port))
+        .json(&json!({
+            "bundleId": "8359a48a-7c67-11eb-a0d7-0fd74777a62c",
+            "key": "unknown-language",
+            "translations": { "xx": "Unknown" },
+            "status": "discuss",
+            "inApp": false,
+            "inElement": false,
+            "inMock": false,
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Locale"))]
+async fn export_fluent(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let export = |language: &'static str| {
+        client
+            .get(&format!(
+                "http://0.0.0.0:{}/v1/locale/bundle/7c11d04e-7c67-11eb-a0d7-9b5fb8f29de9/fluent",
+                port
+            ))
+            .query(&[("language", language)])
+            .send()
+    };
+
+    let resp = export("en").await?.error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.text().await?,
+        "## A\n\nentry-1 = Entry #1\n\nentry-3 = Entry #3\n\n## B\n\nentry-b-1 = Entry #B-1\n"
+    );
+
+    let resp = export("he").await?.error_for_status()?;
+
+    assert_eq!(resp.text().await?, "## B\n\nentry-b-1 = רשומה #B-1\n");
+
+    let resp = export("xx").await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -2,18 +2,24 @@ use crate::helpers::setup_service;
 use macros::test_service;
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 
+mod account;
 mod animation;
 mod audio;
 mod auth;
+mod billing;
 mod category;
 mod circle;
+mod class;
 mod course;
 mod fixture;
 mod helpers;
 mod image;
 mod jig;
 mod locale;
+mod media;
 mod meta;
+mod moderation;
+mod notification;
 mod playlist;
 mod resource;
 mod service;
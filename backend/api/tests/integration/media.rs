@@ -0,0 +1,151 @@
+use http::StatusCode;
+use ji_cloud_api::service::upload::orphans;
+use macros::test_service;
+use serde_json::Value;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use uuid::Uuid;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server_and_get_db, setup_service, LoginExt},
+};
+
+async fn list(port: u16, query: &str) -> anyhow::Result<Value> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/media?{}", port, query))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(resp.json().await?)
+}
+
+fn ids(body: &Value) -> Vec<&str> {
+    body["media"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|media| media["id"].as_str().unwrap())
+        .collect()
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::WebMedia")
+)]
+async fn list_all(port: u16) -> anyhow::Result<()> {
+    let body = list(port, "").await?;
+
+    assert_eq!(
+        vec![
+            "8d7e1d04-e4a2-11eb-8176-2b0d5e4d4e21",
+            "8d7e1d03-e4a2-11eb-8176-2b0d5e4d4e21",
+            "8d7e1d02-e4a2-11eb-8176-2b0d5e4d4e21",
+        ],
+        ids(&body)
+    );
+    assert_eq!(3, body["total_count"]);
+
+    // both urls which had the same bytes are listed on the one media.
+    assert_eq!(
+        serde_json::json!([
+            "https://example.com/apple.png",
+            "https://example.org/apple.png"
+        ]),
+        body["media"][2]["urls"]
+    );
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::User", "Fixture::WebMedia")
+)]
+async fn list_filtered(port: u16) -> anyhow::Result<()> {
+    let body = list(port, "kind=animation").await?;
+    assert_eq!(vec!["8d7e1d03-e4a2-11eb-8176-2b0d5e4d4e21"], ids(&body));
+
+    let body = list(
+        port,
+        "created_by=1f241e1b-b537-493f-a230-075cb16315be&from_date=2021-07-02T00:00:00Z",
+    )
+    .await?;
+    assert_eq!(vec!["8d7e1d03-e4a2-11eb-8176-2b0d5e4d4e21"], ids(&body));
+
+    let body = list(port, "page=1&page_limit=2").await?;
+    assert_eq!(vec!["8d7e1d02-e4a2-11eb-8176-2b0d5e4d4e21"], ids(&body));
+    assert_eq!(2, body["pages"]);
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn orphan_clean_keeps_used_media(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+) -> anyhow::Result<()> {
+    let (_, db) = initialize_server_and_get_db(
+        &[
+            Fixture::MetaKinds,
+            Fixture::User,
+            Fixture::Image,
+            Fixture::Jig,
+            Fixture::WebMedia,
+        ],
+        &[],
+        pool_opts,
+        conn_opts,
+    )
+    .await;
+
+    // the png sticker is used by a module, the gif and svg aren't used anywhere.
+    sqlx::query(
+        r#"
+update jig_data_module
+set contents = '{"content": {"stickers": [{"Sprite": {"image": {"id": "8d7e1d02-e4a2-11eb-8176-2b0d5e4d4e21", "lib": "Web"}}}]}}'
+where id = 'a6b248f8-1dd7-11ec-8426-975953035335'
+"#,
+    )
+    .execute(&db)
+    .await?;
+
+    let user_images = "select count(*) from user_image_library";
+    let library_count: i64 = sqlx::query_scalar(user_images).fetch_one(&db).await?;
+
+    // user uploads are only reported when they're asked for.
+    let report = orphans::clean(&db, true, true).await?.report;
+    assert!(report.dry_run);
+    assert!(!report.user_images.is_empty());
+
+    let report = orphans::clean(&db, false, false).await?.report;
+
+    let mut web_media = report.web_media.clone();
+    web_media.sort();
+    assert_eq!(
+        vec![
+            "8d7e1d03-e4a2-11eb-8176-2b0d5e4d4e21".parse::<Uuid>()?,
+            "8d7e1d04-e4a2-11eb-8176-2b0d5e4d4e21".parse()?,
+        ],
+        web_media
+    );
+    assert!(report.user_images.is_empty());
+
+    let remaining: Vec<Uuid> = sqlx::query_scalar("select id from web_media_library")
+        .fetch_all(&db)
+        .await?;
+    assert_eq!(
+        vec!["8d7e1d02-e4a2-11eb-8176-2b0d5e4d4e21".parse::<Uuid>()?],
+        remaining
+    );
+
+    let count: i64 = sqlx::query_scalar(user_images).fetch_one(&db).await?;
+    assert_eq!(library_count, count);
+
+    Ok(())
+}
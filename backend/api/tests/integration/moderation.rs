@@ -0,0 +1,225 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::json;
+use shared::domain::{circle::CircleId, CreateResponse};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Image"))]
+async fn flagged_circle_is_quarantined_until_approved(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/circle", port))
+        .json(&json!({
+            "displayName": "sh1t circle",
+            "description": "write to me at kid@example.com",
+            "image": "8a473dd6-ffaa-11eb-86a5-dba3538e5a15"
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: CreateResponse<CircleId> = resp.json().await?;
+    let circle_id = body.id.0;
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/circle/browse", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["totalCircleCount"], 0);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/admin/content-flags?status=Pending",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["totalCount"], 1);
+
+    let flag = &body["flags"][0];
+
+    assert_eq!(flag["kind"], "Circle");
+    assert_eq!(flag["itemId"], circle_id.to_string());
+    assert_eq!(
+        flag["reasons"],
+        json!([
+            {
+                "kind": "profanity",
+                "field": "displayName",
+                "language": "en",
+                "term": "shit"
+            },
+            {
+                "kind": "personalInfo",
+                "field": "description",
+                "info": "Email"
+            }
+        ])
+    );
+
+    let flag_id = flag["id"].as_str().unwrap().to_owned();
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/admin/content-flags/{}/review",
+            port, flag_id
+        ))
+        .json(&json!({ "decision": "Approve" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/circle/browse", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["totalCircleCount"], 1);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/admin/content-flags/{}/review",
+            port, flag_id
+        ))
+        .json(&json!({ "decision": "Reject" }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    Ok(())
+}
+
+#[test_service(setup = "setup_service", fixtures("Fixture::User", "Fixture::Image"))]
+async fn clean_circle_is_not_flagged(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    client
+        .post(&format!("http://0.0.0.0:{}/v1/circle", port))
+        .json(&json!({
+            "displayName": "Hebrew club",
+            "description": "Learning the alphabet together",
+            "image": "8a473dd6-ffaa-11eb-86a5-dba3538e5a15"
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/admin/content-flags", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["totalCount"], 0);
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn reviewing_a_jig_flag_keeps_admin_block(port: u16) -> anyhow::Result<()> {
+    let client = reqwest::Client::new();
+
+    let jig_url = format!(
+        "http://0.0.0.0:{}/v1/jig/19becb2b-bff7-4c1b-bb2c-16f2e098d3d3",
+        port
+    );
+
+    client
+        .patch(&format!("{}/admin", jig_url))
+        .json(&json!({ "blocked": true }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    client
+        .patch(&jig_url)
+        .json(&json!({ "displayName": "sh1t jig" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    client
+        .put(&format!("{}/draft/publish", jig_url))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/admin/content-flags?status=Pending",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["totalCount"], 1);
+    assert_eq!(body["flags"][0]["kind"], "Jig");
+
+    let flag_id = body["flags"][0]["id"].as_str().unwrap().to_owned();
+
+    client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/admin/content-flags/{}/review",
+            port, flag_id
+        ))
+        .json(&json!({ "decision": "Approve" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let resp = client
+        .get(&format!("{}/live", jig_url))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["adminData"]["blocked"], true);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use http::StatusCode;
+use macros::test_service;
+use serde_json::{json, Value};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{setup_service, LoginExt},
+};
+
+const JIG: &str = "0cc084bc-7c83-11eb-9f77-e3218dffb008";
+
+async fn play(port: u16) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .put(&format!("http://0.0.0.0:{}/v1/jig/{}/play", port, JIG))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    Ok(())
+}
+
+async fn list(port: u16, query: &str) -> anyhow::Result<Value> {
+    let resp = reqwest::Client::new()
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/notification?{}",
+            port, query
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(resp.json().await?)
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn jig_plays_are_grouped(port: u16) -> anyhow::Result<()> {
+    play(port).await?;
+    play(port).await?;
+
+    let body = list(port, "").await?;
+
+    assert_eq!(body["unreadCount"], json!(1));
+    assert_eq!(body["notifications"][0]["kind"], json!("jigPlayed"));
+    assert_eq!(body["notifications"][0]["jigId"], json!(JIG));
+    assert_eq!(body["notifications"][0]["count"], json!(2));
+
+    let resp = reqwest::Client::new()
+        .post(&format!("http://0.0.0.0:{}/v1/notification/read", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // plays after the notification was read start a new one
+    play(port).await?;
+
+    let body = list(port, "unreadOnly=true").await?;
+
+    assert_eq!(body["totalCount"], json!(1));
+    assert_eq!(body["notifications"][0]["count"], json!(1));
+
+    let body = list(port, "").await?;
+
+    assert_eq!(body["totalCount"], json!(2));
+
+    Ok(())
+}
+
+#[test_service(
+    setup = "setup_service",
+    fixtures("Fixture::MetaKinds", "Fixture::User", "Fixture::Jig")
+)]
+async fn preferences_hide_notifications(port: u16) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/notification/preferences",
+            port
+        ))
+        .login()
+        .json(&json!({
+            "preferences": [{"kind": "jigPlayed", "inApp": false, "email": true}]
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    play(port).await?;
+
+    let body = list(port, "").await?;
+
+    assert_eq!(body["totalCount"], json!(0));
+    assert_eq!(body["unreadCount"], json!(0));
+
+    let resp = reqwest::Client::new()
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/notification/preferences",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: Value = resp.json().await?;
+    let preferences = body["preferences"].as_array().unwrap();
+
+    // every kind is listed, the ones which weren't changed with the defaults
+    assert_eq!(preferences.len(), 5);
+    assert!(preferences.contains(&json!({"kind": "jigPlayed", "inApp": false, "email": true})));
+    assert!(preferences.contains(&json!({"kind": "followed", "inApp": true, "email": true})));
+
+    Ok(())
+}
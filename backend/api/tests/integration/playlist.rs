@@ -8,6 +8,8 @@ use crate::{
     helpers::{setup_service, LoginExt},
 };
 
+pub mod progress;
+
 #[test_service(
     setup = "setup_service",
     fixtures(
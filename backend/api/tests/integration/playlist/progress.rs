@@ -0,0 +1,131 @@
+use http::StatusCode;
+use serde_json::{json, Value};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use uuid::Uuid;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server_and_get_db, LoginExt},
+};
+
+const PLAYLIST: &str = "3a6a3660-f3ec-11ec-b8ef-071747fa2a0d";
+const PLAYLIST_LIVE_DATA: &str = "566ed564-f3ec-11ec-b8ef-93b44835e7e4";
+const FIRST_JIG: &str = "0cc084bc-7c83-11eb-9f77-e3218dffb008";
+const SECOND_JIG: &str = "3a71522a-cd77-11eb-8dc1-af3e35f7c743";
+
+async fn update(port: u16, jig_id: &str, body: Value) -> anyhow::Result<()> {
+    let resp = reqwest::Client::new()
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/playlist/{}/jig/{}/progress",
+            port, PLAYLIST, jig_id
+        ))
+        .login()
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    Ok(())
+}
+
+async fn get(port: u16, path: &str) -> anyhow::Result<Value> {
+    let resp = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}{}", port, path))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(resp.json().await?)
+}
+
+#[sqlx::test]
+async fn playlist_progress(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+) -> anyhow::Result<()> {
+    let (app, db) = initialize_server_and_get_db(
+        &[
+            Fixture::MetaKinds,
+            Fixture::User,
+            Fixture::Jig,
+            Fixture::Playlist,
+        ],
+        &[],
+        pool_opts,
+        conn_opts,
+    )
+    .await;
+
+    sqlx::query(
+        "update playlist_data_jig set index = case when jig_id = $2 then 0 else 1 end where playlist_data_id = $1",
+    )
+    .bind(PLAYLIST_LIVE_DATA.parse::<Uuid>()?)
+    .bind(FIRST_JIG.parse::<Uuid>()?)
+    .execute(&db)
+    .await?;
+
+    let port = app.port();
+    let _join_handle = tokio::spawn(app.run_until_stopped());
+
+    let progress_path = format!("/v1/playlist/{}/progress", PLAYLIST);
+
+    let body = get(port, &progress_path).await?;
+
+    assert_eq!(body["itemCount"], json!(2));
+    assert_eq!(body["completedCount"], json!(0));
+    assert_eq!(body["continueJigId"], json!(FIRST_JIG));
+    assert_eq!(body["items"], json!([]));
+
+    update(port, FIRST_JIG, json!({"status": "started"})).await?;
+    update(
+        port,
+        FIRST_JIG,
+        json!({"status": "completed", "pointsEarned": {"available": 4.0, "earned": 3.0}}),
+    )
+    .await?;
+
+    // playing a completed JIG again doesn't undo its completion or score
+    update(port, FIRST_JIG, json!({"status": "started"})).await?;
+
+    let body = get(port, &progress_path).await?;
+
+    assert_eq!(body["completedCount"], json!(1));
+    assert_eq!(body["percentComplete"], json!(50));
+    assert_eq!(body["continueJigId"], json!(SECOND_JIG));
+    assert_eq!(body["items"][0]["status"], json!("completed"));
+    assert_eq!(
+        body["items"][0]["pointsEarned"],
+        json!({"available": 4.0, "earned": 3.0})
+    );
+
+    let body = get(port, "/v1/playlist/in-progress").await?;
+
+    assert_eq!(body["playlists"].as_array().unwrap().len(), 1);
+    assert_eq!(body["playlists"][0]["playlist"]["id"], json!(PLAYLIST));
+    assert_eq!(body["playlists"][0]["progress"]["completedCount"], json!(1));
+
+    update(port, SECOND_JIG, json!({"status": "completed"})).await?;
+
+    // completed playlists are only listed when asked for
+    let body = get(port, "/v1/playlist/in-progress").await?;
+
+    assert_eq!(body["playlists"], json!([]));
+
+    let body = get(port, "/v1/playlist/in-progress?includeCompleted=true").await?;
+
+    assert_eq!(
+        body["playlists"][0]["progress"]["percentComplete"],
+        json!(100)
+    );
+    assert_eq!(
+        body["playlists"][0]["progress"]["continueJigId"],
+        json!(null)
+    );
+
+    Ok(())
+}
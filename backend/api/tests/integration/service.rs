@@ -210,6 +210,7 @@ impl TestServicesSettings {
             password_reset_template,
             email_reset_template,
             welcome_jigzi_template,
+            localized_templates: Default::default(),
         };
 
         let client = mail::Client::new(settings);
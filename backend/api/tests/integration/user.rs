@@ -10,6 +10,7 @@ use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 
 mod color;
 mod font;
+mod media;
 mod public_user;
 
 #[test_service(setup = "setup_service", fixtures("Fixture::User"))]
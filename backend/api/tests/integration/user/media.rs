@@ -0,0 +1,125 @@
+use ji_cloud_api::{error, service::storage};
+use shared::domain::{
+    jig::JigId,
+    user::{UserId, UserMediaListResponse},
+};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use uuid::Uuid;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server_and_get_db, LoginExt},
+};
+
+const USER: &str = "1f241e1b-b537-493f-a230-075cb16315be";
+
+#[sqlx::test]
+async fn list_media_references(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+) -> anyhow::Result<()> {
+    let (app, db) = initialize_server_and_get_db(
+        &[
+            Fixture::MetaKinds,
+            Fixture::User,
+            Fixture::Image,
+            Fixture::Jig,
+        ],
+        &[],
+        pool_opts,
+        conn_opts,
+    )
+    .await;
+
+    // one image is used by a module of the user's JIG, another one by their profile
+    sqlx::query(
+        r#"
+update jig_data_module
+set contents = '{"content": {"stickers": [{"Sprite": {"image": {"id": "89125d88-ffaa-11eb-86a5-9fd50ab8d8df", "lib": "User"}}}]}}'
+where id = 'a6b248f8-1dd7-11ec-8426-975953035335'
+"#,
+    )
+    .execute(&db)
+    .await?;
+
+    sqlx::query(
+        "update user_profile set profile_image_id = '89fa4c10-ffaa-11eb-86a5-870d6a01dc37' where user_id = $1",
+    )
+    .bind(USER.parse::<Uuid>()?)
+    .execute(&db)
+    .await?;
+
+    let port = app.port();
+    let _join_handle = tokio::spawn(app.run_until_stopped());
+
+    let resp: UserMediaListResponse = reqwest::Client::new()
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/media", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let media = |id: &str| {
+        let id: Uuid = id.parse().unwrap();
+        resp.media.iter().find(|media| media.id == id).unwrap()
+    };
+
+    let in_jig = media("89125d88-ffaa-11eb-86a5-9fd50ab8d8df");
+    assert_eq!(
+        vec![JigId("0cc084bc-7c83-11eb-9f77-e3218dffb008".parse()?)],
+        in_jig.referenced_by
+    );
+    assert!(!in_jig.used_elsewhere);
+
+    let in_profile = media("89fa4c10-ffaa-11eb-86a5-870d6a01dc37");
+    assert!(in_profile.referenced_by.is_empty());
+    assert!(in_profile.used_elsewhere);
+
+    assert!(media("8a2469b4-ffaa-11eb-86a5-330f321d2a3f").is_unreferenced());
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn quota_counts_stored_size(
+    pool_opts: PgPoolOptions,
+    conn_opts: PgConnectOptions,
+) -> anyhow::Result<()> {
+    let (_, db) =
+        initialize_server_and_get_db(&[Fixture::User, Fixture::Image], &[], pool_opts, conn_opts)
+            .await;
+
+    let user_id = UserId(USER.parse()?);
+    let replaced: Uuid = "89125d88-ffaa-11eb-86a5-9fd50ab8d8df".parse()?;
+    let uploaded: Uuid = "89fa4c10-ffaa-11eb-86a5-870d6a01dc37".parse()?;
+
+    let quota = storage::check_quota(&db, user_id, None, 1024)
+        .await
+        .map_err(|_| anyhow::anyhow!("the upload should fit in the quota"))?;
+
+    // all but 1 KB of the quota is used by another image
+    let quota_bytes = storage::usage(&db, user_id, None).await?.quota_bytes;
+    sqlx::query("update user_image_library set size_bytes = $2 where id = $1")
+        .bind(replaced)
+        .bind((quota_bytes - 1024) as i64)
+        .execute(&db)
+        .await?;
+
+    let mut txn = db.begin().await?;
+
+    // the renditions of an upload are counted, not just the uploaded file
+    assert!(quota.reserve(&mut txn, uploaded, 1024).await.is_ok());
+    assert!(matches!(
+        quota.reserve(&mut txn, uploaded, 1025).await,
+        Err(error::Upload::QuotaExceeded { .. })
+    ));
+
+    // the stored size of a file doesn't count towards the file replacing it
+    assert!(quota.reserve(&mut txn, replaced, 2048).await.is_ok());
+
+    txn.rollback().await?;
+
+    Ok(())
+}
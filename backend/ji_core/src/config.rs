@@ -37,6 +37,17 @@ pub const ANIMATION_BODY_SIZE_LIMIT: usize = 1024 * 1024 * 40;
 pub const AUDIO_BODY_SIZE_LIMIT: usize = 1024 * 1024 * 30;
 /// PDF file size limit. 10 MB
 pub const PDF_BODY_SIZE_LIMIT: usize = 1024 * 1024 * 10;
+/// Largest width or height of an uploaded image, in pixels.
+pub const IMAGE_MAX_DIMENSION: u32 = 8192;
+/// Largest number of pixels in an uploaded image. 40 megapixels
+/// Checked before decoding, so small files that decode to huge images are rejected early.
+pub const IMAGE_MAX_PIXELS: u64 = 40_000_000;
+/// Longest uploaded audio, in seconds. 30 minutes
+pub const AUDIO_MAX_DURATION_SECS: u32 = 30 * 60;
+/// Most pages of an uploaded PDF which get a preview image, starting from the first.
+pub const PDF_MAX_PREVIEW_PAGES: u32 = 20;
+/// Longest text extracted from an uploaded PDF for search, in bytes. 256 KB
+pub const PDF_MAX_TEXT_LEN: usize = 256 * 1024;
 /// JSON body size limit for both requests and responses. 256 KB
 pub const JSON_BODY_LIMIT: u64 = 1024 * 256;
 /// Allowed CORS origins
@@ -63,3 +74,6 @@ pub const EXPIRED_UPLOAD_CLEANUP_PERIOD: u64 = 60 * 60 * 6;
 /// Any media created at least this old will be deleted from the database if it has not successfully
 /// completed processing by the time the cleaning task has
 pub const UPLOAD_EXPIRY_TIME: u64 = 60 * 60 * 24 * 3;
+/// Uploaded media which nothing references is only deleted once it hasn't been added or replaced
+/// for this many days, so that files uploaded for content which isn't saved yet are kept.
+pub const ORPHANED_MEDIA_GRACE_PERIOD_DAYS: i32 = 30;
@@ -37,11 +37,9 @@ pub mod s3 {
 }
 
 pub mod email {
-    /// Disable email client locally (avoiding the warnings for missing secrets)
-    /// If specified in a way that maps to `true` (currently "true", "1", "y"), all mailing related services,
-    /// such as email verification, password resetting, and email changing(1) will be disabled.
-    /// all related routes will return "501 - Not Implemented"
-    /// (1): currently not implemented.
+    /// Disable the SendGrid email client locally (avoiding the warnings for missing secrets)
+    /// If specified in a way that maps to `true` (currently "true", "1", "y"), emails, such as for email verification,
+    /// password resetting, and email changing, will be rendered and logged by the API instead of being sent.
     pub const DISABLE: &str = "SENDGRID_DISABLE_LOCAL";
 
     /// Sendgrid / email client api key.
@@ -78,6 +76,19 @@ pub mod email {
     /// Is optional. If missing, email resetting will be disabled,
     /// all related routes will return "501 - Not Implemented" and a warning will be emitted.
     pub const EMAIL_WELCOME_JIGZI_TEMPLATE: &str = "EMAIL_WELCOME_JIGZI_TEMPLATE";
+
+    /// Templates which can be sent through SendGrid, keyed the way the API names them.
+    pub const TEMPLATES: &[(&str, &str)] = &[
+        ("signup-verify", SIGNUP_VERIFY_TEMPLATE),
+        ("password-reset", PASSWORD_RESET_TEMPLATE),
+        ("email-reset", EMAIL_RESET_TEMPLATE),
+        ("welcome-jigzi", EMAIL_WELCOME_JIGZI_TEMPLATE),
+    ];
+
+    /// Locales, other than English, which can have their own SendGrid template for each of [`TEMPLATES`].
+    /// The template ID is read from the template's key suffixed with the upper-cased locale, e.g. `EMAIL_SIGNUP_VERIFY_TEMPLATE_HE`.
+    /// Is optional. If missing, emails in that locale will be rendered by the API instead.
+    pub const TEMPLATE_LOCALES: &[&str] = &["he"];
 }
 
 #[cfg(feature = "db")]
@@ -155,6 +166,11 @@ pub mod algolia {
 /// Must be 32 bytes of hex
 pub const TOKEN_SECRET: &str = "TOKEN_SECRET";
 
+/// Key for deriving the pseudonyms of players in child privacy mode, kept apart from the token
+/// secret so that either can be rotated on its own.
+/// Must be 32 bytes of hex
+pub const CHILD_PRIVACY_SECRET: &str = "CHILD_PRIVACY_SECRET";
+
 /// How long *login* tokens are valid for (measured in seconds).
 /// This environment variable can only be set on `local`
 /// This environment variable is optional, if missing it will use the server's compiled default (an indeterminate but reasonable amount of time)
@@ -162,6 +178,10 @@ pub const LOGIN_TOKEN_VALID_DURATION: &str = "LOGIN_TOKEN_VALID_DURATION";
 
 pub const PIXABAY_SEARCH_KEY: &str = "PIXABAY_SEARCH_KEY";
 
+/// Access key for the Unsplash API.
+/// Is optional. If missing, Unsplash is left out of web image searches.
+pub const UNSPLASH_ACCESS_KEY: &str = "UNSPLASH_ACCESS_KEY";
+
 /// ID of the google oauth client.
 /// Is optional. If missing, all google-oauth related services will be disabled,
 /// all related routes will return "501 - Not Implemented" and a warning will be emitted.
@@ -177,6 +197,10 @@ pub const GOOGLE_OAUTH_SECRET: &str = "GOOGLE_OAUTH_SECRET";
 /// all related routes will return "501 - Not Implemented" and a warning will be emitted.
 pub const GOOGLE_API_KEY: &str = "GOOGLE_API_KEY";
 
+/// Secret for the DeepL translation API.
+/// Is optional. If present, it's used for translations instead of Google Translate.
+pub const DEEPL_API_KEY: &str = "DEEPL_API_KEY";
+
 /// Secret for Stripe API
 pub const STRIPE_SECRET_KEY: &str = "STRIPE_SECRET_KEY";
 
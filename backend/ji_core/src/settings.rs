@@ -18,6 +18,7 @@ use anyhow::Context;
 use chrono::{DateTime, Utc};
 use shared::config::RemoteTarget;
 use std::{
+    collections::HashMap,
     convert::TryInto,
     env::VarError,
     str::FromStr,
@@ -96,9 +97,19 @@ pub struct RuntimeSettings {
     /// if missing / disabled, related routes will return `501 - Not Implemented`
     pub google_api_key: Option<String>,
 
+    /// Key for translating via DeepL, which is preferred over Google Translate when set.
+    pub deepl_api_key: Option<String>,
+
+    /// Access key for searching images on Unsplash, which is skipped when missing.
+    pub unsplash_access_key: Option<String>,
+
     /// Secret for signing/encrypting tokens.
     pub token_secret: Box<[u8; 32]>,
 
+    /// Key for deriving the pseudonyms of players in child privacy mode.
+    /// if missing, anonymous players can't start sessions of codes in child privacy mode.
+    pub child_privacy_secret: Option<Box<[u8; 32]>>,
+
     /// How long *login* tokens are valid for (measured in seconds).
     /// * can only be set on `local`
     /// * optional, if missing it will use the server's compiled default (an indeterminate but reasonable amount of time)
@@ -119,9 +130,12 @@ impl RuntimeSettings {
         pages_port: u16,
         media_watch_port: u16,
         pixabay_search_key: Option<String>,
+        unsplash_access_key: Option<String>,
         google_api_key: Option<String>,
+        deepl_api_key: Option<String>,
         google_oauth: Option<GoogleOAuth>,
         token_secret: Box<[u8; 32]>,
+        child_privacy_secret: Option<Box<[u8; 32]>>,
         login_token_valid_duration: Option<chrono::Duration>,
         stripe_secret_key: Option<String>,
         stripe_webhook_secret: Option<String>,
@@ -135,9 +149,12 @@ impl RuntimeSettings {
             epoch: get_epoch(),
             remote_target,
             pixabay_search_key,
+            unsplash_access_key,
             google_oauth,
             google_api_key,
+            deepl_api_key,
             token_secret,
+            child_privacy_secret,
             login_token_valid_duration,
             stripe_secret_key,
             stripe_webhook_secret,
@@ -147,9 +164,12 @@ impl RuntimeSettings {
     pub(crate) fn with_env(
         remote_target: RemoteTarget,
         pixabay_search_key: Option<String>,
+        unsplash_access_key: Option<String>,
         google_api_key: Option<String>,
+        deepl_api_key: Option<String>,
         google_oauth: Option<GoogleOAuth>,
         token_secret: Box<[u8; 32]>,
+        child_privacy_secret: Option<Box<[u8; 32]>>,
         login_token_valid_duration: Option<chrono::Duration>,
         stripe_secret_key: Option<String>,
         stripe_webhook_secret: Option<String>,
@@ -173,9 +193,12 @@ impl RuntimeSettings {
             epoch: get_epoch(),
             remote_target,
             pixabay_search_key,
+            unsplash_access_key,
             google_oauth,
             google_api_key,
+            deepl_api_key,
             token_secret,
+            child_privacy_secret,
             login_token_valid_duration,
             stripe_secret_key,
             stripe_webhook_secret,
@@ -310,6 +333,10 @@ pub struct EmailClientSettings {
     /// Is optional. If missing, email resetting will be disabled,
     /// all related routes will return "501 - Not Implemented" and a warning will be emitted.
     pub welcome_jigzi_template: Option<String>,
+
+    /// Email client template IDs for locales other than English, keyed by `{template}:{locale}`, e.g. `signup-verify:he`.
+    /// Emails in a locale without a template are rendered by the API instead.
+    pub localized_templates: HashMap<String, String>,
 }
 
 // TODO: unify google services clients' auth tokens and project_id requirements
@@ -643,6 +670,18 @@ impl SettingsManager {
             .get_varying_secret(keys::email::EMAIL_WELCOME_JIGZI_TEMPLATE)
             .await?;
 
+        let mut localized_templates = HashMap::new();
+
+        for (template, key) in keys::email::TEMPLATES {
+            for locale in keys::email::TEMPLATE_LOCALES {
+                let key = format!("{}_{}", key, locale.to_uppercase());
+
+                if let Some(template_id) = self.get_optional_secret(&key).await? {
+                    localized_templates.insert(format!("{}:{}", template, locale), template_id);
+                }
+            }
+        }
+
         let (api_key, sender_email, jigzi_info_email) =
             match (api_key, sender_email, jigzi_info_email) {
                 (Some(api_key), Some(sender_email), Some(jigzi_info_email)) => {
@@ -659,6 +698,7 @@ impl SettingsManager {
             password_reset_template,
             email_reset_template,
             welcome_jigzi_template,
+            localized_templates,
         }))
     }
 
@@ -732,26 +772,20 @@ impl SettingsManager {
 
     /// Load the `RuntimeSettings`.
     pub async fn runtime_settings(&self) -> anyhow::Result<RuntimeSettings> {
-        let token_secret = self
-            .get_secret(keys::TOKEN_SECRET)
-            .await
-            .and_then(|secret| {
-                let secret = hex::decode(secret)?;
-
-                let secret: [u8; 32] = secret.try_into().map_err(|s: Vec<u8>| {
-                    anyhow::anyhow!(
-                        "token secret must be 32 bytes long, it was: {} bytes long",
-                        s.len()
-                    )
-                })?;
+        let token_secret = self.get_key_secret(keys::TOKEN_SECRET).await?;
 
-                Ok(Box::new(secret))
-            })?;
+        let child_privacy_secret = self
+            .get_varying_key_secret(keys::CHILD_PRIVACY_SECRET)
+            .await?;
 
         let pixabay_search_key = self.get_optional_secret(keys::PIXABAY_SEARCH_KEY).await?;
 
+        let unsplash_access_key = self.get_optional_secret(keys::UNSPLASH_ACCESS_KEY).await?;
+
         let google_api_key = self.get_optional_secret(keys::GOOGLE_API_KEY).await?;
 
+        let deepl_api_key = self.get_optional_secret(keys::DEEPL_API_KEY).await?;
+
         let login_token_valid_duration = match self.remote_target {
             RemoteTarget::Local => self
                 .get_optional_secret(keys::LOGIN_TOKEN_VALID_DURATION)
@@ -771,15 +805,31 @@ impl SettingsManager {
         RuntimeSettings::with_env(
             self.remote_target,
             pixabay_search_key,
+            unsplash_access_key,
             google_api_key,
+            deepl_api_key,
             google_oauth,
             token_secret,
+            child_privacy_secret,
             login_token_valid_duration,
             self.stripe_secret_key().await?,
             self.stripe_webhook_secret().await?,
         )
     }
 
+    /// Load a 32 byte key stored as hex.
+    async fn get_key_secret(&self, key: &str) -> anyhow::Result<Box<[u8; 32]>> {
+        decode_key_secret(key, &self.get_secret(key).await?)
+    }
+
+    /// Load a 32 byte key stored as hex, warning if it's missing.
+    async fn get_varying_key_secret(&self, key: &str) -> anyhow::Result<Option<Box<[u8; 32]>>> {
+        self.get_varying_secret(key)
+            .await?
+            .map(|secret| decode_key_secret(key, &secret))
+            .transpose()
+    }
+
     /// Load the Stripe secret key
     async fn stripe_secret_key(&self) -> anyhow::Result<Option<String>> {
         self.get_varying_secret(keys::STRIPE_SECRET_KEY).await
@@ -796,3 +846,17 @@ fn get_epoch() -> std::time::Duration {
         .duration_since(UNIX_EPOCH)
         .expect("Time went backwards")
 }
+
+fn decode_key_secret(key: &str, secret: &str) -> anyhow::Result<Box<[u8; 32]>> {
+    let secret = hex::decode(secret)?;
+
+    let secret: [u8; 32] = secret.try_into().map_err(|s: Vec<u8>| {
+        anyhow::anyhow!(
+            "{} must be 32 bytes long, it was: {} bytes long",
+            key,
+            s.len()
+        )
+    })?;
+
+    Ok(Box::new(secret))
+}
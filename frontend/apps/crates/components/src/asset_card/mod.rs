@@ -1,14 +1,20 @@
 use std::rc::Rc;
 
-use dominator::{html, Dom, EventOptions};
+use dominator::{html, Dom, DomBuilder, EventOptions};
 use futures_signals::signal::{from_future, SignalExt};
-use shared::domain::asset::{Asset, DraftOrLive, PrivacyLevel};
+use shared::domain::{
+    additional_resource::ResourceContent,
+    asset::{Asset, DraftOrLive, PrivacyLevel},
+};
 use utils::ages::AgeRangeVecExt;
 use utils::events;
 use utils::metadata::{get_age_ranges, get_resource_types};
 use utils::routes::{CommunityMembersRoute, CommunityRoute, Route};
 
+use web_sys::HtmlElement;
+
 use crate::module::_common::thumbnail::{ModuleThumbnail, ThumbnailFallback};
+use crate::pdf::preview::first_page_signal;
 use crate::share_asset::ShareAsset;
 
 #[derive(Clone, Default)]
@@ -29,6 +35,37 @@ pub enum AssetCardBottomIndicator {
     Status,
 }
 
+/// Resources without a cover show the first page of their pdf instead, if they have one.
+fn render_thumbnail(dom: DomBuilder<HtmlElement>, asset: &Asset) -> DomBuilder<HtmlElement> {
+    let (asset_id, cover) = (asset.id(), asset.cover().cloned());
+    let render_module = move || {
+        ModuleThumbnail::new(
+            asset_id,
+            cover.clone(),
+            ThumbnailFallback::Asset,
+            DraftOrLive::Live,
+        )
+        .render(Some("image"))
+    };
+
+    let pdf_id = match asset {
+        Asset::Resource(resource) if asset.cover().is_none() => resource
+            .resource_data
+            .additional_resources
+            .first()
+            .and_then(|resource| match resource.resource_content {
+                ResourceContent::PdfId(pdf_id) => Some(pdf_id),
+                _ => None,
+            }),
+        _ => None,
+    };
+
+    match pdf_id {
+        Some(pdf_id) => dom.child_signal(first_page_signal(pdf_id, Some("image"), render_module)),
+        None => dom.child(render_module()),
+    }
+}
+
 pub fn render_asset_card(asset: &Asset, config: AssetCardConfig) -> Dom {
     // let liked = Mutable::new(false);
     html!("asset-card", {
@@ -53,12 +90,7 @@ pub fn render_asset_card(asset: &Asset, config: AssetCardConfig) -> Dom {
                 None => dom,
             }
         })
-        .child(ModuleThumbnail::new(
-            asset.id(),
-            asset.cover().cloned(),
-            ThumbnailFallback::Asset,
-            DraftOrLive::Live
-        ).render(Some("image")))
+        .apply(|dom| render_thumbnail(dom, asset))
         // TODO: enable like
         // .apply_if(config.likeable, |dom| {
         // .apply_if(false, |dom| {
@@ -2,7 +2,11 @@ use super::{
     super::upload::upload_audio,
     state::{AudioInput, AudioInputMode},
 };
-use shared::{domain::module::body::Audio, media::MediaLibrary};
+use shared::{
+    api::endpoints,
+    domain::{audio::user::UserAudioWaveformPath, module::body::Audio},
+    media::MediaLibrary,
+};
 use std::rc::Rc;
 use utils::prelude::*;
 use web_sys::File;
@@ -13,6 +17,8 @@ impl AudioInput {
     pub(super) fn set_audio(&self, audio: Option<Audio>) {
         let previous_mode = self.mode.get_cloned();
 
+        self.load_waveform(audio.as_ref());
+
         //Change the mutable for affecting all DOM rendering stuff
         //with _eventual consistency_
         self.mode.set_neq(match audio.clone() {
@@ -49,11 +55,44 @@ impl AudioInput {
     //It's safe and idiomatic to set the external signal from callbacks too
     //(e.g. the external signal can be driven by a combo of history, current audio, and initial audio)
     pub(super) fn set_audio_ext(&self, audio: Option<Audio>) {
+        if mode_audio(&self.mode.lock_ref()) != audio.as_ref() {
+            self.load_waveform(audio.as_ref());
+        }
+
         self.mode.set_neq(match audio {
             Some(audio) => AudioInputMode::Stopped(audio),
             None => AudioInputMode::Empty,
         });
     }
+
+    //Only audio in the user library is processed on upload, so nothing else has a waveform
+    fn load_waveform(&self, audio: Option<&Audio>) {
+        self.waveform.set(None);
+
+        let id = match audio {
+            Some(audio) if audio.lib == MediaLibrary::User => audio.id,
+            _ => return,
+        };
+
+        let waveform = self.waveform.clone();
+        self.waveform_loader.load(async move {
+            let res =
+                endpoints::audio::user::Waveform::api_with_auth(UserAudioWaveformPath(id), None)
+                    .await;
+
+            // audio uploaded before processing was added has no waveform
+            if let Ok(res) = res {
+                waveform.set(Some(Rc::new(res)));
+            }
+        });
+    }
+}
+
+fn mode_audio(mode: &AudioInputMode) -> Option<&Audio> {
+    match mode {
+        AudioInputMode::Playing(audio) | AudioInputMode::Stopped(audio) => Some(audio),
+        AudioInputMode::Empty | AudioInputMode::Recording | AudioInputMode::Uploading => None,
+    }
 }
 
 pub async fn file_change(state: Rc<AudioInput>, file: File) {
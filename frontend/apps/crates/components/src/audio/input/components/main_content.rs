@@ -14,7 +14,7 @@ const STR_UPLOADING_TEXT: &str = "Uploading... please wait.";
 pub fn render(state: Rc<AudioInput>, mode: AudioInputMode, add_method: AudioInputAddMethod) -> Dom {
     match mode {
         AudioInputMode::Playing(audio) => player::dom::render(state, audio),
-        AudioInputMode::Stopped(_) => render_input_icon("success"),
+        AudioInputMode::Stopped(_) => render_stopped(state),
         AudioInputMode::Empty => render_start(state, add_method),
         AudioInputMode::Recording => render_recording(),
         AudioInputMode::Uploading => render_uploading(),
@@ -43,6 +43,18 @@ fn render_start(state: Rc<AudioInput>, add_method: AudioInputAddMethod) -> Dom {
     }
 }
 
+fn render_stopped(state: Rc<AudioInput>) -> Dom {
+    html!("empty-fragment", {
+        .prop("slot", "main-content")
+        .child_signal(state.waveform.signal_cloned().map(|waveform| {
+            Some(match waveform {
+                Some(waveform) => player::dom::render_waveform(&waveform, None),
+                None => render_input_icon("success"),
+            })
+        }))
+    })
+}
+
 fn render_recording() -> Dom {
     html!("audio-input-recording", {
         .prop("slot", "main-content")
@@ -1,28 +1,71 @@
 use crate::audio::input::state::AudioInput;
 use dominator::{clone, html, with_node, Dom};
 use futures_signals::signal::Mutable;
-use shared::domain::module::body::Audio;
+use shared::{
+    domain::{audio::user::UserAudioWaveformResponse, module::body::Audio},
+    media::MediaLibrary,
+};
 use std::rc::Rc;
-use utils::{path::audio_lib_url, prelude::*};
+use utils::{
+    path::{audio_lib_opus_url, audio_lib_url},
+    prelude::*,
+};
 use web_sys::HtmlAudioElement;
 
 pub fn render(state: Rc<AudioInput>, audio: Audio) -> Dom {
     let current_time = Mutable::new(0);
-    html!("progress-bar", {
-        .prop("slot", "main-content")
-        .prop("color", "green")
-        .prop_signal("progress", current_time.signal())
-        .child(html!("audio" => HtmlAudioElement, {
-            .prop("autoplay", true)
-            .prop("src", audio_lib_url(audio.lib, audio.id))
-            .with_node!(elem => {
-                .event(clone!(current_time => move |_:events::TimeUpdate| {
-                    super::actions::on_time_update(&elem, &current_time);
-                }))
-            })
-            .event(clone!(state, audio => move |_:events::Ended| {
-                super::actions::on_ended(&state, audio.clone());
+
+    let player = html!("audio" => HtmlAudioElement, {
+        .prop("autoplay", true)
+        .apply(|dom| match audio.lib {
+            // prefer the smaller Opus file, falling back to the MP3 for browsers without Opus
+            // support and for audio uploaded before it was transcoded
+            MediaLibrary::User => dom.children(&mut [
+                html!("source", {
+                    .prop("src", audio_lib_opus_url(audio.lib, audio.id))
+                    .prop("type", "audio/ogg; codecs=opus")
+                }),
+                html!("source", {
+                    .prop("src", audio_lib_url(audio.lib, audio.id))
+                    .prop("type", "audio/mpeg")
+                }),
+            ]),
+            _ => dom.prop("src", audio_lib_url(audio.lib, audio.id)),
+        })
+        .with_node!(elem => {
+            .event(clone!(current_time => move |_:events::TimeUpdate| {
+                super::actions::on_time_update(&elem, &current_time);
             }))
+        })
+        .event(clone!(state, audio => move |_:events::Ended| {
+            super::actions::on_ended(&state, audio.clone());
         }))
+    });
+
+    match state.waveform.get_cloned() {
+        Some(waveform) => html!("empty-fragment", {
+            .prop("slot", "main-content")
+            .child(render_waveform(&waveform, Some(&current_time)))
+            .child(player)
+        }),
+        None => html!("progress-bar", {
+            .prop("slot", "main-content")
+            .prop("color", "green")
+            .prop_signal("progress", current_time.signal())
+            .child(player)
+        }),
+    }
+}
+
+/// `progress` is the percent played, if the audio is playing.
+pub fn render_waveform(
+    waveform: &UserAudioWaveformResponse,
+    progress: Option<&Mutable<i32>>,
+) -> Dom {
+    html!("audio-input-waveform", {
+        .prop("peaks", js_sys::Uint8Array::from(&waveform.peaks[..]))
+        .apply_if(progress.is_some(), |dom| {
+            dom.prop_signal("progress", progress.unwrap_ji().signal())
+        })
     })
 }
@@ -1,9 +1,10 @@
 use super::callbacks::AudioInputCallbacks;
 use super::options::*;
 use super::recorder::AudioRecorder;
+use awsm_web::loaders::helpers::AsyncLoader;
 use dominator::clone;
 use futures_signals::signal::{Mutable, Signal, SignalExt};
-use shared::domain::module::body::Audio;
+use shared::domain::{audio::user::UserAudioWaveformResponse, module::body::Audio};
 use std::cell::RefCell;
 use std::rc::Rc;
 use utils::prelude::*;
@@ -30,6 +31,9 @@ pub struct AudioInput {
     pub add_method: Mutable<AudioInputAddMethod>,
     pub recorder: AudioRecorder,
     pub aborter: RefCell<AbortController>,
+    /// Waveform of the current audio, if it was processed on upload.
+    pub waveform: Mutable<Option<Rc<UserAudioWaveformResponse>>>,
+    pub waveform_loader: AsyncLoader,
     ext_audio_handle: RefCell<Option<FutureHandle>>,
 }
 
@@ -44,6 +48,8 @@ impl AudioInput {
             recorder: AudioRecorder::new(),
             add_method: Mutable::new(AudioInputAddMethod::Record),
             aborter: RefCell::new(AbortController::new()),
+            waveform: Mutable::new(None),
+            waveform_loader: AsyncLoader::new(),
             ext_audio_handle: RefCell::new(None),
         });
 
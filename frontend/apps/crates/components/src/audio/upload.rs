@@ -10,6 +10,8 @@ use shared::{
 use thiserror::Error;
 use utils::prelude::*;
 
+use crate::image::upload::STR_STORAGE_FULL;
+
 use web_sys::File;
 
 const STR_AUDIO_IS_TOO_LARGE: &str = "Audio is too large, limit is 30MB";
@@ -20,6 +22,8 @@ pub enum UploadError {
     Aborted,
     #[error("TooLarge")]
     TooLarge,
+    #[error("QuotaExceeded")]
+    QuotaExceeded,
     #[error("awsm_web error")]
     Other(awsm_web::errors::Error),
 }
@@ -37,6 +41,10 @@ impl UploadError {
     pub fn is_too_large(&self) -> bool {
         matches!(self, Self::TooLarge)
     }
+
+    pub fn is_quota_exceeded(&self) -> bool {
+        matches!(self, Self::QuotaExceeded)
+    }
 }
 
 /*
@@ -82,6 +90,11 @@ pub async fn upload_audio(
                 .unwrap_ji()
                 .alert_with_message(STR_AUDIO_IS_TOO_LARGE);
             Err(UploadError::TooLarge)
+        } else if status == 507 {
+            let _ = web_sys::window()
+                .unwrap_ji()
+                .alert_with_message(STR_STORAGE_FULL);
+            Err(UploadError::QuotaExceeded)
         } else {
             resp.map_err(UploadError::Other)
         }
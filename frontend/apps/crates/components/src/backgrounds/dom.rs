@@ -9,7 +9,11 @@ use futures_signals::{
 };
 
 use super::state::*;
-use shared::domain::module::body::{_groups::design::Backgrounds as RawBackgrounds, Background};
+use shared::domain::{
+    image::ImageSize,
+    module::body::{_groups::design::Backgrounds as RawBackgrounds, Background},
+};
+use utils::image::resized_srcset;
 
 pub fn render_backgrounds(bg: Rc<Backgrounds>, slot: Option<&str>) -> Dom {
     let children = map_ref! {
@@ -140,6 +144,8 @@ fn render_bg(bg: &Background) -> Option<Dom> {
             .prop("id", image.id.0.to_string())
             .prop("lib", image.lib.to_str())
             .prop("size", "full")
+            .prop("srcset", resized_srcset(image.lib, image.id, ImageSize::Canvas.size().0))
+            .prop("sizes", "100vw")
         })),
     }
 }
@@ -1,5 +1,6 @@
 use shared::domain::{asset::AssetId, module::ModuleId};
 use std::sync::atomic::{AtomicUsize, Ordering};
+use utils::routes::is_kids_route;
 use wasm_bindgen::prelude::*;
 
 static GLOBAL_SCREENSHOT_LISTENER_COUNT: AtomicUsize = AtomicUsize::new(0);
@@ -30,10 +31,16 @@ pub fn listen_for_screenshot_updates(
     module_id: &ModuleId,
     on_update: impl FnMut() + 'static,
 ) -> ScreenshotListener {
-    init();
-
     let listener = ScreenshotListener::new(on_update);
 
+    // firebase starts google analytics, which mustn't run for kids. The screenshot is then only
+    // updated when it's loaded again.
+    if is_kids_route() {
+        return listener;
+    }
+
+    init();
+
     listenForScreenshotUpdates(
         &asset_id.uuid().to_string(),
         &module_id.0.to_string(),
@@ -12,7 +12,7 @@ use shared::domain::image::recent::{UserRecentImageListRequest, UserRecentImageU
 use shared::domain::image::{ImageFileKind, ImageId, ImageSearchPath};
 use shared::domain::media::{MediaCreatePath, WebMediaUrlCreateRequest};
 use shared::domain::meta::{GetMetadataPath, ImageTagIndex};
-use shared::domain::search::{WebImageSearchPath, WebImageSearchQuery};
+use shared::domain::search::{WebImageSearchItem, WebImageSearchPath, WebImageSearchQuery};
 use shared::domain::user::GetProfilePath;
 use shared::{
     api::endpoints,
@@ -38,6 +38,7 @@ pub async fn web_to_image(url: Url) -> Result<Image, ()> {
 
     let kind = match res.kind {
         MediaKind::Image => ImageFileKind::Png,
+        MediaKind::SvgImage => ImageFileKind::Svg,
         MediaKind::Animation(AnimationKind::Gif) => ImageFileKind::Gif,
         kind => unreachable!("Only images here but found: {:?}", kind),
     };
@@ -63,12 +64,18 @@ impl ImageSearch {
         }
     }
 
-    pub fn on_web_image_click(self: &Rc<Self>, url: Url) {
+    pub fn on_web_image_click(self: &Rc<Self>, image: WebImageSearchItem) {
         let state = self;
         state.loader.load(clone!(state => async move {
 
-            let image = web_to_image(url).await.expect_ji("Couldn't upload image");
-            state.set_selected(image);
+            let selected = web_to_image(image.url.clone()).await.expect_ji("Couldn't upload image");
+            state.set_selected(selected);
+
+            if image.attribution.required {
+                if let Some(on_attribution) = state.callbacks.on_attribution.as_ref() {
+                    on_attribution(image.attribution.text(image.source));
+                }
+            }
 
         }));
     }
@@ -308,11 +315,7 @@ impl ImageSearch {
     pub async fn upload_file(self: &Rc<Self>, file: File) {
         match upload_user_image(ImageSize::Sticker, &file, None).await {
             Ok(id) => {
-                let kind = if file.type_() == "image/gif" {
-                    ImageFileKind::Gif
-                } else {
-                    ImageFileKind::Png
-                };
+                let kind = ImageFileKind::from_content_type(&file.type_());
 
                 self.set_selected(Image {
                     id,
@@ -2,12 +2,20 @@ use shared::domain::module::body::Image;
 
 pub struct ImageSearchCallbacks {
     pub on_select: Option<Box<dyn Fn(Option<Image>)>>,
+    /// Called with the credit for a web image whose license requires it, after it's selected.
+    pub on_attribution: Option<Box<dyn Fn(String)>>,
 }
 
 impl ImageSearchCallbacks {
     pub fn new(on_select: Option<impl Fn(Option<Image>) + 'static>) -> Self {
         Self {
             on_select: on_select.map(|f| Box::new(f) as _),
+            on_attribution: None,
         }
     }
+
+    pub fn with_on_attribution(mut self, on_attribution: impl Fn(String) + 'static) -> Self {
+        self.on_attribution = Some(Box::new(on_attribution));
+        self
+    }
 }
@@ -194,8 +194,9 @@ impl ImageSearch {
             .prop("size", "thumb")
             .prop("src", &image.thumbnail_url.to_string())
             .prop("loading", "lazy")
+            .prop("title", image.attribution.text(image.source))
             .event(clone!(state, image => move |_: events::Click| {
-                state.on_web_image_click(image.url.clone());
+                state.on_web_image_click(image.clone());
             }))
             .event(clone!(image => move |evt: events::DragStart| {
                 if let Some(data_transfer) = evt.data_transfer() {
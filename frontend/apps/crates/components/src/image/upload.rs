@@ -14,6 +14,8 @@ use utils::prelude::*;
 use web_sys::File;
 
 const STR_IMAGE_TOO_LARGE: &str = "Image is too large, limit is 30MB";
+pub(crate) const STR_STORAGE_FULL: &str =
+    "Your storage is full. Delete unused files in My media, or upgrade your plan, to upload more.";
 
 #[derive(Debug, Error)]
 pub enum UploadError {
@@ -21,6 +23,8 @@ pub enum UploadError {
     Aborted,
     #[error("TooLarge")]
     TooLarge,
+    #[error("QuotaExceeded")]
+    QuotaExceeded,
     #[error("awsm_web error")]
     Other(awsm_web::errors::Error),
 }
@@ -38,6 +42,10 @@ impl UploadError {
     pub fn is_too_large(&self) -> bool {
         matches!(self, Self::TooLarge)
     }
+
+    pub fn is_quota_exceeded(&self) -> bool {
+        matches!(self, Self::QuotaExceeded)
+    }
 }
 
 /*
@@ -91,6 +99,11 @@ pub async fn upload_image(
                 .unwrap_ji()
                 .alert_with_message(STR_IMAGE_TOO_LARGE);
             Err(UploadError::TooLarge)
+        } else if status == 507 {
+            let _ = web_sys::window()
+                .unwrap_ji()
+                .alert_with_message(STR_STORAGE_FULL);
+            Err(UploadError::QuotaExceeded)
         } else {
             resp.map_err(UploadError::Other)
         }
@@ -129,6 +142,11 @@ pub async fn upload_user_image(
                 .unwrap_ji()
                 .alert_with_message(STR_IMAGE_TOO_LARGE);
             Err(UploadError::TooLarge)
+        } else if status == 507 {
+            let _ = web_sys::window()
+                .unwrap_ji()
+                .alert_with_message(STR_STORAGE_FULL);
+            Err(UploadError::QuotaExceeded)
         } else {
             resp.map_err(UploadError::Other)
         }
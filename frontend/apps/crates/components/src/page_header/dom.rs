@@ -34,6 +34,7 @@ const STR_MY_PLAYLISTS: &str = "My playlists";
 const STR_MY_RESOURCES: &str = "My resources";
 const STR_CLASSES: &str = "My classes";
 const STR_LIKES: &str = "My likes";
+const STR_MEDIA: &str = "My media";
 
 impl PageHeader {
     pub fn render(self: Rc<PageHeader>) -> Dom {
@@ -220,6 +221,15 @@ fn render_logged_in(state: Rc<PageHeader>, user: &UserProfile) -> Vec<Dom> {
             }))
             .text(STR_LIKES)
         }))
+        .child(html!("a", {
+            .prop("slot", "setting-links")
+            .prop("href", Route::User(UserRoute::Media).to_string())
+            .prop("target", "_top")
+            .child(html!("fa-icon", {
+                .prop("icon", "fa-light fa-photo-film")
+            }))
+            .text(STR_MEDIA)
+        }))
         .child(html!("a", {
             .prop("slot", "setting-links")
             .prop("href",  Route::Community(CommunityRoute::Members(CommunityMembersRoute::Member(user.id))).to_string())
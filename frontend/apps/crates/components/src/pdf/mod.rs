@@ -1 +1,2 @@
+pub mod preview;
 pub mod upload;
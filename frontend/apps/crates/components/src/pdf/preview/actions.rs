@@ -0,0 +1,20 @@
+use std::rc::Rc;
+
+use shared::{api::endpoints, domain::pdf::user::UserPdfGetPath};
+use utils::prelude::*;
+
+use super::state::PdfPreview;
+
+impl PdfPreview {
+    pub(super) fn load(self: &Rc<Self>) {
+        let state = Rc::clone(self);
+
+        self.loader.load(async move {
+            match endpoints::pdf::user::Get::api_with_auth(UserPdfGetPath(state.pdf_id), None).await
+            {
+                Ok(res) => state.pdf.set(Some(res.metadata)),
+                Err(err) => log::error!("failed to load pdf {}: {:?}", state.pdf_id.0, err),
+            }
+        });
+    }
+}
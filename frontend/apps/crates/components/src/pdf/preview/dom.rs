@@ -0,0 +1,95 @@
+use std::rc::Rc;
+
+use dominator::{clone, html, Dom, DomBuilder};
+use futures_signals::signal::{Mutable, Signal, SignalExt};
+use shared::{
+    domain::pdf::{user::UserPdf, PdfId},
+    media::MediaLibrary,
+};
+use utils::{
+    component::Component,
+    events,
+    path::{pdf_lib_page_url, pdf_lib_url},
+};
+use web_sys::{HtmlElement, ShadowRoot};
+
+use super::state::PdfPreview;
+
+impl Component<PdfPreview> for Rc<PdfPreview> {
+    fn styles() -> &'static str {
+        include_str!("./styles.css")
+    }
+
+    fn apply_on_host(&self, dom: DomBuilder<HtmlElement>) -> DomBuilder<HtmlElement> {
+        dom.class("pdf-preview")
+    }
+
+    fn dom(&self, dom: DomBuilder<ShadowRoot>) -> DomBuilder<ShadowRoot> {
+        let state = Rc::clone(self);
+
+        dom.child_signal(
+            state
+                .pdf
+                .signal_ref(move |pdf| pdf.as_ref().map(|pdf| render_pages(pdf))),
+        )
+    }
+}
+
+fn render_pages(pdf: &UserPdf) -> Dom {
+    let url = pdf_lib_url(MediaLibrary::User, pdf.id);
+
+    html!("div", {
+        .child(html!("div", {
+            .class("pages")
+            .children((1..=pdf.preview_page_count).map(|page| {
+                html!("a", {
+                    .prop("href", format!("{}#page={}", url, page))
+                    .prop("target", "_blank")
+                    .child(html!("img", {
+                        .prop("src", pdf_lib_page_url(MediaLibrary::User, pdf.id, page))
+                        .prop("alt", format!("Page {}", page))
+                        .prop("loading", "lazy")
+                    }))
+                })
+            }))
+        }))
+        .apply(|dom| match pdf.page_count {
+            Some(1) => dom.child(html!("p", { .class("page-count").text("1 page") })),
+            Some(count) => dom.child(html!("p", {
+                .class("page-count")
+                .text(&format!("{} pages", count))
+            })),
+            None => dom,
+        })
+    })
+}
+
+/// The preview of the first page of a pdf, or whatever `fallback` renders if there isn't one
+/// (pdfs uploaded before previews were generated, or which failed to render).
+pub fn first_page_signal(
+    pdf_id: PdfId,
+    slot: Option<&'static str>,
+    fallback: impl Fn() -> Dom + 'static,
+) -> impl Signal<Item = Option<Dom>> {
+    let failed = Mutable::new(false);
+
+    failed.signal().map(clone!(failed => move |is_failed| {
+        Some(if is_failed {
+            fallback()
+        } else {
+            html!("img", {
+                .apply(|dom| match slot {
+                    Some(slot) => dom.prop("slot", slot),
+                    None => dom,
+                })
+                .prop("src", pdf_lib_page_url(MediaLibrary::User, pdf_id, 1))
+                .style("object-fit", "cover")
+                .style("object-position", "top")
+                .style("width", "100%")
+                .event(clone!(failed => move |_: events::LoadError| {
+                    failed.set(true);
+                }))
+            })
+        })
+    }))
+}
@@ -0,0 +1,6 @@
+mod actions;
+mod dom;
+mod state;
+
+pub use dom::first_page_signal;
+pub use state::*;
@@ -0,0 +1,26 @@
+use std::rc::Rc;
+
+use dominator_helpers::futures::AsyncLoader;
+use futures_signals::signal::Mutable;
+use shared::domain::pdf::{user::UserPdf, PdfId};
+
+/// Previews of the first pages of a pdf in the user library, linking to the pages themselves.
+pub struct PdfPreview {
+    pub pdf_id: PdfId,
+    pub(super) pdf: Mutable<Option<UserPdf>>,
+    pub(super) loader: AsyncLoader,
+}
+
+impl PdfPreview {
+    pub fn new(pdf_id: PdfId) -> Rc<Self> {
+        let state = Rc::new(Self {
+            pdf_id,
+            pdf: Mutable::new(None),
+            loader: AsyncLoader::new(),
+        });
+
+        state.load();
+
+        state
+    }
+}
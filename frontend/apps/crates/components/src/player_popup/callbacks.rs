@@ -1,11 +1,23 @@
+use shared::domain::jig::codes::PointsEarned;
+
 pub struct PreviewPopupCallbacks {
     pub close: Box<dyn Fn()>,
+    pub jig_finished: Option<Box<dyn Fn(Option<PointsEarned>)>>,
 }
 
 impl PreviewPopupCallbacks {
     pub fn new(close: impl Fn() + 'static) -> Self {
         Self {
             close: Box::new(close),
+            jig_finished: None,
         }
     }
+
+    pub fn with_jig_finished(
+        mut self,
+        jig_finished: impl Fn(Option<PointsEarned>) + 'static,
+    ) -> Self {
+        self.jig_finished = Some(Box::new(jig_finished));
+        self
+    }
 }
@@ -80,6 +80,11 @@ impl PlayerPopup {
                                         AssetPlayerToPlayerPopup::CloseButtonShown(shown) => {
                                             state.close_button_shown.set_neq(shown);
                                         },
+                                        AssetPlayerToPlayerPopup::JigFinished(points_earned) => {
+                                            if let Some(jig_finished) = &state.callbacks.jig_finished {
+                                                jig_finished(points_earned);
+                                            }
+                                        },
                                     }
                                 }
                             }))
@@ -1,5 +1,6 @@
 use std::rc::Rc;
 
+use chrono::{DateTime, TimeZone, Utc};
 use dominator::clone;
 use futures_signals::signal::Mutable;
 use gloo_timers::callback::Timeout;
@@ -8,6 +9,7 @@ use shared::{
     domain::jig::{codes::JigPlayerSessionCreatePath, JigPlayerSettings},
 };
 use utils::{bail_on_err, prelude::*};
+use wasm_bindgen::JsValue;
 
 use crate::qr_dialog::{QrDialog, QrDialogCallbacks};
 
@@ -25,6 +27,10 @@ impl ShareAsset {
                 settings: JigPlayerSettings {
                     direction: state.direction.get(),
                     scoring: state.scoring.get(),
+                    opens_at: state.opens_at.get(),
+                    due_at: state.due_at.get(),
+                    max_attempts: state.max_attempts.get(),
+                    hide_score: state.hide_score.get(),
                     ..Default::default()
                 },
             };
@@ -35,6 +41,19 @@ impl ShareAsset {
         }));
     }
 
+    /// Sets a date from the value of a `datetime-local` input, which is in the user's timezone.
+    pub(super) fn set_date(date: &Mutable<Option<DateTime<Utc>>>, value: &str) {
+        if value.is_empty() {
+            date.set(None);
+            return;
+        }
+
+        let millis = js_sys::Date::new(&JsValue::from_str(value)).get_time();
+        if !millis.is_nan() {
+            date.set(Utc.timestamp_millis_opt(millis as i64).single());
+        }
+    }
+
     pub fn set_copied_mutable(copied: Mutable<bool>) {
         copied.set(true);
         let timeout = Timeout::new(
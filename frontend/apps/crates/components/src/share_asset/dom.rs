@@ -4,7 +4,10 @@ use dominator::{clone, html, with_node, Dom, EventOptions};
 use futures_signals::signal::{Mutable, SignalExt};
 use js_sys::{encode_uri_component, Function, Reflect};
 use serde::{Deserialize, Serialize};
-use shared::{config::JIG_PLAYER_SESSION_VALID_DURATION_SECS, domain::jig::TextDirection};
+use shared::{
+    config::JIG_PLAYER_SESSION_VALID_DURATION_SECS,
+    domain::jig::{player::MAX_ATTEMPTS_LIMIT, TextDirection},
+};
 use utils::{
     clipboard,
     component::Component,
@@ -40,6 +43,10 @@ const STR_COPY_LABEL_1: &str = "Copy ";
 const STR_COPY_LABEL_2: &str = " link";
 const STR_COPIED_LABEL: &str = " link copied";
 const STR_SCORING: &str = "Scoring & Assessment";
+const STR_OPENS_AT: &str = "Opens";
+const STR_DUE_AT: &str = "Due";
+const STR_MAX_ATTEMPTS: &str = "Attempts per student";
+const STR_HIDE_SCORE: &str = "Hide score from students";
 
 impl ShareAsset {
     pub fn render(self: Rc<Self>, anchor: Dom, slot: Option<&str>) -> Dom {
@@ -336,6 +343,61 @@ impl ShareAsset {
                         }))
                     })
                 }),
+                html!("div", {
+                    .prop("slot", "assignment-settings")
+                    .child(html!("label", {
+                        .text(STR_OPENS_AT)
+                        .child(html!("input" => HtmlInputElement, {
+                            .with_node!(elem => {
+                                .prop("type", "datetime-local")
+                                .event(clone!(state => move |_: events::Input| {
+                                    ShareAsset::set_date(&state.opens_at, &elem.value());
+                                }))
+                            })
+                        }))
+                    }))
+                    .child(html!("label", {
+                        .text(STR_DUE_AT)
+                        .child(html!("input" => HtmlInputElement, {
+                            .with_node!(elem => {
+                                .prop("type", "datetime-local")
+                                .event(clone!(state => move |_: events::Input| {
+                                    ShareAsset::set_date(&state.due_at, &elem.value());
+                                }))
+                            })
+                        }))
+                    }))
+                    .child(html!("label", {
+                        .text(STR_MAX_ATTEMPTS)
+                        .child(html!("input" => HtmlInputElement, {
+                            .with_node!(elem => {
+                                .prop("type", "number")
+                                .prop("min", "1")
+                                .prop("max", MAX_ATTEMPTS_LIMIT.to_string())
+                                .prop("placeholder", "Unlimited")
+                                .event(clone!(state => move |_: events::Input| {
+                                    let max_attempts = elem
+                                        .value()
+                                        .parse()
+                                        .ok()
+                                        .filter(|max| (1..=MAX_ATTEMPTS_LIMIT).contains(max));
+                                    state.max_attempts.set(max_attempts);
+                                }))
+                            })
+                        }))
+                    }))
+                    .apply_if(state.scoring.get(), |dom| {
+                        dom.child(html!("label", {
+                            .child(html!("input-switch", {
+                                .prop_signal("enabled", state.hide_score.signal())
+                                .event(clone!(state => move |evt: events::CustomToggle| {
+                                    state.hide_score.set(evt.value());
+                                }))
+                            }))
+                            .text(STR_HIDE_SCORE)
+                        }))
+                    })
+                }),
                 html!("share-jig-gen-code-button", {
                     .prop("slot", "gen-code-button")
                     .prop_signal("disabled", state.student_code.signal_ref(|x| x.is_some()))
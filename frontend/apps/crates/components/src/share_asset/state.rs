@@ -1,6 +1,7 @@
 use std::rc::Rc;
 
 use awsm_web::loaders::helpers::AsyncLoader;
+use chrono::{DateTime, Utc};
 use futures_signals::signal::Mutable;
 use shared::domain::asset::Asset;
 use shared::domain::jig::codes::JigCode;
@@ -28,6 +29,11 @@ pub struct ShareAsset {
     pub code_name: Mutable<Option<String>>,
     pub direction: Mutable<TextDirection>,
     pub scoring: Mutable<bool>,
+    // assignment settings
+    pub opens_at: Mutable<Option<DateTime<Utc>>>,
+    pub due_at: Mutable<Option<DateTime<Utc>>>,
+    pub max_attempts: Mutable<Option<u16>>,
+    pub hide_score: Mutable<bool>,
 }
 
 impl ShareAsset {
@@ -53,6 +59,10 @@ impl ShareAsset {
             code_name: Mutable::new(None),
             direction: Mutable::new(direction),
             scoring: Mutable::new(scoring),
+            opens_at: Mutable::new(None),
+            due_at: Mutable::new(None),
+            max_attempts: Mutable::new(None),
+            hide_score: Mutable::new(false),
         })
     }
 
@@ -1,5 +1,5 @@
 use utils::image_effects::ImageEffect;
-use utils::path::module_image_url;
+use utils::path::{image_lib_svg_url, module_image_url};
 use utils::prelude::UnwrapJiExt;
 
 use super::state::Sprite;
@@ -32,8 +32,12 @@ impl Sprite {
 }
 
 pub async fn load_and_render(image: Image, effects: &[SpriteEffect]) -> (String, f64, f64) {
-    if image.kind == ImageFileKind::Gif && effects.is_empty() {
-        let url = module_image_url(image.lib, image.kind, PngImageFile::Resized, image.id);
+    // without effects, animations and vector images are shown as they are, not drawn to a canvas
+    if image.kind != ImageFileKind::Png && effects.is_empty() {
+        let url = match image.kind {
+            ImageFileKind::Svg => image_lib_svg_url(image.lib, image.id),
+            _ => module_image_url(image.lib, image.kind, PngImageFile::Resized, image.id),
+        };
         let img = awsm_web::loaders::image::load(url.clone())
             .await
             .unwrap_ji();
@@ -7,3 +7,4 @@ mod wysiwyg_types;
 
 pub use callbacks::*;
 pub use state::*;
+pub use wysiwyg_types::{plain_text_value, ElementType};
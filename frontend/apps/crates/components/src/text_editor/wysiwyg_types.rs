@@ -161,6 +161,20 @@ impl WysiwygControlsChange {
     }
 }
 
+/// The editor's value for a single line of `text`, as created by the editor's `createValue`.
+pub fn plain_text_value(text: &str, element: ElementType) -> String {
+    serde_json::json!({
+        "version": "0.1.0",
+        "content": [{
+            "children": [{
+                "text": text,
+                "element": element,
+            }],
+        }],
+    })
+    .to_string()
+}
+
 pub fn enum_variant_to_string<T: ?Sized>(v: &T) -> String
 where
     T: Serialize,
@@ -1,8 +1,10 @@
+use crate::pdf::preview::first_page_signal;
 use crate::stickers::embed::types::ParseUrlExt;
 
 use super::state::*;
 use dominator::{html, Dom};
 use shared::domain::image::ImageId;
+use shared::domain::pdf::PdfId;
 use shared::domain::{course::unit::CourseUnitValue, module::body::_groups::design::YoutubeEmbed};
 use std::rc::Rc;
 use utils::prelude::*;
@@ -21,7 +23,7 @@ impl UnitThumbnail {
                         CourseUnitValue::ImageId(image) => render_image_thumbnail(image),
                         CourseUnitValue::AudioId(_) => render_audio_thumbnail(),
                         CourseUnitValue::Link(_) => render_link_thumbnail(),
-                        CourseUnitValue::PdfId(_) => render_pdf_thumbnail(),
+                        CourseUnitValue::PdfId(pdf_id) => render_pdf_thumbnail(*pdf_id),
                         CourseUnitValue::Video(youtube) => render_youtube_thumbnail(youtube),
                     }
                 } else {
@@ -45,10 +47,15 @@ fn render_youtube_thumbnail(youtube: &YoutubeEmbed) -> Option<Dom> {
     }))
 }
 
-fn render_pdf_thumbnail() -> Option<Dom> {
-    Some(html!("img-ui", {
-        .prop("path", "entry/pro-dev/thumbnail/thumbnail-pdf.svg")
-        .style("position", "absolute")
+fn render_pdf_thumbnail(pdf_id: PdfId) -> Option<Dom> {
+    Some(html!("div", {
+        .style("display", "contents")
+        .child_signal(first_page_signal(pdf_id, None, || {
+            html!("img-ui", {
+                .prop("path", "entry/pro-dev/thumbnail/thumbnail-pdf.svg")
+                .style("position", "absolute")
+            })
+        }))
     }))
 }
 
@@ -1 +1,11 @@
+use std::rc::Rc;
 
+use super::UnitValueView;
+
+impl UnitValueView {
+    pub fn ended(self: &Rc<Self>) {
+        if let Some(on_ended) = &self.on_ended {
+            on_ended();
+        }
+    }
+}
@@ -12,6 +12,7 @@ use shared::{
 };
 use utils::{
     component::Component,
+    events,
     path::{audio_lib_url, pdf_lib_url},
     unwrap::UnwrapJiExt,
 };
@@ -67,10 +68,14 @@ impl UnitValueView {
     }
 
     fn render_active_video(self: &Rc<Self>, video: YoutubeEmbed) -> Dom {
+        let state = self;
         html!("div", {
             .class("video-wrapper")
             .child(html!("video-youtube-player" => HtmlElement, {
                 .prop("videoId", video.url.get_id())
+                .event(clone!(state => move |_: events::YoutubeEnded| {
+                    state.ended();
+                }))
                 .apply(|mut dom| {
                     if let Some(start_at) = video.start_at {
                         dom = dom.prop("start", start_at);
@@ -106,10 +111,14 @@ impl UnitValueView {
     }
 
     fn render_active_audio(self: &Rc<Self>, audio_id: AudioId) -> Dom {
+        let state = self;
         let resp = audio_lib_url(MediaLibrary::User, audio_id);
         html!("audio", {
             .prop("src", resp)
             .prop("controls", true)
+            .event(clone!(state => move |_: events::Ended| {
+                state.ended();
+            }))
         })
     }
 }
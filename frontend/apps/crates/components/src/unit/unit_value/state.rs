@@ -4,12 +4,25 @@ use std::rc::Rc;
 
 pub struct UnitValueView {
     pub unit_value: Mutable<Option<CourseUnitValue>>,
+    /// Called when an audio or video unit was played all the way through
+    pub on_ended: Option<Box<dyn Fn()>>,
 }
 
 impl UnitValueView {
     pub fn new(unit_value: Option<CourseUnitValue>) -> Rc<Self> {
         Rc::new(Self {
             unit_value: Mutable::new(unit_value),
+            on_ended: None,
+        })
+    }
+
+    pub fn new_with_on_ended(
+        unit_value: Option<CourseUnitValue>,
+        on_ended: impl Fn() + 'static,
+    ) -> Rc<Self> {
+        Rc::new(Self {
+            unit_value: Mutable::new(unit_value),
+            on_ended: Some(Box::new(on_ended)),
         })
     }
 }
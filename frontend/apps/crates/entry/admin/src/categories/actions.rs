@@ -3,15 +3,28 @@ use futures_signals::signal_vec::{MutableVecLockMut, MutableVecLockRef};
 use shared::{
     api::endpoints::{self},
     domain::category::{
-        CategoryTreeScope, CreateCategoryPath, CreateCategoryRequest, DeleteCategoryPath,
-        GetCategoryPath, GetCategoryRequest, UpdateCategoryPath, UpdateCategoryRequest,
+        CategoryTreeScope, CategoryUsagePath, CreateCategoryPath, CreateCategoryRequest,
+        DeleteCategoryPath, GetCategoryPath, GetCategoryRequest, MergeCategoryPath,
+        MergeCategoryRequest, RecategorizePath, RecategorizeRequest, UpdateCategoryPath,
+        UpdateCategoryRequest,
     },
 };
-use std::rc::Rc;
+use std::{collections::HashMap, rc::Rc};
 use utils::{prelude::ApiEndpointExt, unwrap::UnwrapJiExt};
 
+use components::confirm::Confirm;
 use dominator::clone;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+
+const STR_MERGE_TITLE: &str = "Merge categories";
+const STR_MERGE_CONTENT: &str = "Everything in \"{from}\" will be moved to \"{into}\", along with its subcategories, and \"{from}\" will be deleted. Are you sure you want to merge these categories?";
+const STR_MERGE_CONFIRM: &str = "Yes, merge";
+const STR_RECATEGORIZE_TITLE: &str = "Move assets";
+const STR_RECATEGORIZE_CONTENT: &str =
+    "{count} assets will be moved from \"{from}\" to \"{into}\". Are you sure you want to move them?";
+const STR_RECATEGORIZE_CONFIRM: &str = "Yes, move";
+const STR_CANCEL: &str = "Cancel";
 
 pub fn toggle_expand_all(cat: &Rc<Category>, flag: bool) {
     cat.expanded.set(flag);
@@ -22,27 +35,46 @@ pub fn toggle_expand_all(cat: &Rc<Category>, flag: bool) {
 }
 pub fn load_categories(state: Rc<State>) {
     state.loader.load(clone!(state => async move {
-        let req = GetCategoryRequest {
-            ids: Vec::new(),
-            scope: Some(CategoryTreeScope::Descendants)
-        };
+        fetch_categories(&state).await;
+    }));
+}
 
-        match endpoints::category::Get::api_with_auth(GetCategoryPath(), Some(req)).await {
-            Ok(resp) => {
-                let categories:Vec<Rc<Category>> = resp.categories
-                    .into_iter()
-                    .map(Category::from)
-                    .map(Rc::new)
-                    .collect();
+async fn fetch_categories(state: &State) {
+    let req = GetCategoryRequest {
+        ids: Vec::new(),
+        scope: Some(CategoryTreeScope::Descendants),
+    };
 
-                state.categories.lock_mut().replace_cloned(categories);
+    match endpoints::category::Get::api_with_auth(GetCategoryPath(), Some(req)).await {
+        Ok(resp) => {
+            let categories: Vec<Rc<Category>> = resp
+                .categories
+                .into_iter()
+                .map(Category::from)
+                .map(Rc::new)
+                .collect();
 
-            },
-            Err(_) => {
-                log::info!("err!")
-            }
+            state.categories.lock_mut().replace_cloned(categories);
         }
-    }));
+        Err(_) => {
+            log::info!("err!")
+        }
+    }
+
+    match endpoints::category::Usage::api_with_auth(CategoryUsagePath(), None).await {
+        Ok(resp) => {
+            let usage = resp
+                .usage
+                .into_iter()
+                .map(|usage| (usage.id, usage))
+                .collect();
+
+            state.usage.set(usage);
+        }
+        Err(_) => {
+            log::info!("err!")
+        }
+    }
 }
 
 impl ContentState {
@@ -157,6 +189,7 @@ pub fn move_category(content_state: Rc<ContentState>, dir: Direction) {
 
         let req = UpdateCategoryRequest {
             name: None,
+            translated_name: None,
             parent_id: None,
             index: Some(target_index as u16),
             user_scopes: None,
@@ -202,6 +235,7 @@ pub fn rename_category(cat: &Rc<Category>, state: Rc<State>, name: String) {
     state.loader.load(async move {
         let req = UpdateCategoryRequest {
             name: Some(name),
+            translated_name: None,
             parent_id: None,
             index: None,
             user_scopes: None,
@@ -215,3 +249,157 @@ pub fn rename_category(cat: &Rc<Category>, state: Rc<State>, name: String) {
         }
     });
 }
+
+pub fn save_translated_name(
+    content_state: Rc<ContentState>,
+    translated_name: HashMap<String, String>,
+) {
+    content_state.state.translating.set(None);
+    content_state
+        .cat
+        .translated_name
+        .set(translated_name.clone());
+
+    let id = content_state.cat.id;
+
+    content_state.state.loader.load(async move {
+        let req = UpdateCategoryRequest {
+            translated_name: Some(translated_name),
+            ..Default::default()
+        };
+
+        match endpoints::category::Update::api_with_auth(UpdateCategoryPath(id), Some(req)).await {
+            Ok(_) => {}
+            Err(_) => {
+                log::info!("err!")
+            }
+        }
+    });
+}
+
+pub fn start_picking(content_state: Rc<ContentState>, action: PickAction) {
+    content_state.close_menu();
+    content_state.state.picking.set(Some(Picking {
+        action,
+        source: content_state.clone(),
+    }));
+}
+
+/// Finishes picking with `target`, which is `None` for the top level.
+pub fn pick(state: Rc<State>, target: Option<Rc<Category>>) {
+    let picking = match state.picking.replace(None) {
+        Some(picking) => picking,
+        None => return,
+    };
+
+    let source = picking.source.cat.clone();
+
+    if target.as_ref().map(|target| target.id) == Some(source.id) {
+        return;
+    }
+
+    match (picking.action, target) {
+        (PickAction::Move, target) => move_to_parent(state, source, target),
+        (PickAction::Merge, Some(target)) => merge_category(state, source, target),
+        (PickAction::Recategorize, Some(target)) => recategorize(state, source, target),
+        // only moving can be done to the top level.
+        (_, None) => {}
+    }
+}
+
+fn move_to_parent(state: Rc<State>, source: Rc<Category>, parent: Option<Rc<Category>>) {
+    state.loader.load(clone!(state => async move {
+        let req = UpdateCategoryRequest {
+            parent_id: Some(parent.map(|parent| parent.id)),
+            ..Default::default()
+        };
+
+        match endpoints::category::Update::api_with_auth(UpdateCategoryPath(source.id), Some(req)).await {
+            Ok(_) => fetch_categories(&state).await,
+            Err(_) => {
+                log::info!("err!")
+            }
+        }
+    }));
+}
+
+fn merge_category(state: Rc<State>, source: Rc<Category>, into: Rc<Category>) {
+    spawn_local(clone!(state => async move {
+        let confirmed = Confirm {
+            title: STR_MERGE_TITLE.to_string(),
+            message: STR_MERGE_CONTENT
+                .replace("{from}", &source.name.get_cloned())
+                .replace("{into}", &into.name.get_cloned()),
+            confirm_text: STR_MERGE_CONFIRM.to_string(),
+            cancel_text: STR_CANCEL.to_string(),
+        }
+        .confirm()
+        .await;
+
+        if !confirmed {
+            return;
+        }
+
+        state.loader.load(clone!(state => async move {
+            let req = MergeCategoryRequest { into: into.id };
+
+            match endpoints::category::Merge::api_with_auth(MergeCategoryPath(source.id), Some(req)).await {
+                Ok(_) => fetch_categories(&state).await,
+                Err(_) => {
+                    log::info!("err!")
+                }
+            }
+        }));
+    }));
+}
+
+fn recategorize(state: Rc<State>, source: Rc<Category>, into: Rc<Category>) {
+    spawn_local(clone!(state => async move {
+        let req = |preview| RecategorizeRequest {
+            in_category: Some(source.id),
+            add: vec![into.id],
+            remove: vec![source.id],
+            preview,
+            ..Default::default()
+        };
+
+        let preview = match endpoints::category::Recategorize::api_with_auth(RecategorizePath(), Some(req(true))).await {
+            Ok(resp) => resp,
+            Err(_) => {
+                log::info!("err!");
+                return;
+            }
+        };
+
+        if preview.changed.is_empty() {
+            return;
+        }
+
+        let confirmed = Confirm {
+            title: STR_RECATEGORIZE_TITLE.to_string(),
+            message: STR_RECATEGORIZE_CONTENT
+                .replace("{count}", &preview.changed.len().to_string())
+                .replace("{from}", &source.name.get_cloned())
+                .replace("{into}", &into.name.get_cloned()),
+            confirm_text: STR_RECATEGORIZE_CONFIRM.to_string(),
+            cancel_text: STR_CANCEL.to_string(),
+        }
+        .confirm()
+        .await;
+
+        if !confirmed {
+            return;
+        }
+
+        let req = req(false);
+
+        state.loader.load(clone!(state => async move {
+            match endpoints::category::Recategorize::api_with_auth(RecategorizePath(), Some(req)).await {
+                Ok(_) => fetch_categories(&state).await,
+                Err(_) => {
+                    log::info!("err!")
+                }
+            }
+        }));
+    }));
+}
@@ -1,16 +1,27 @@
-use dominator::{clone, html, Dom};
+use dominator::{clone, html, with_node, Dom};
 use futures_signals::signal::{Mutable, SignalExt};
 use futures_signals::signal_vec::SignalVecExt;
+use shared::domain::category::CategoryUsage;
 use std::rc::Rc;
+use web_sys::HtmlInputElement;
 
 use super::{actions, state::*};
 
-use utils::events;
+use utils::{events, languages::JIG_LANGUAGES};
 
 const STR_DELETE_TITLE: &str = "Warning";
 const STR_DELETE_CONTENT: &str = "Deleting the category \"{category}\" will also remove it from any images or JIGs associated with it. Are you sure you want to delete this category?";
 const STR_DELETE_CONFIRM: &str = "Yes, delete";
 const STR_DELETE_CANCEL: &str = "Don't delete";
+const STR_PICK_MOVE: &str = "Choose where to move \"{category}\"";
+const STR_PICK_MERGE: &str = "Choose the category to merge \"{category}\" into";
+const STR_PICK_RECATEGORIZE: &str = "Choose the category to move the assets in \"{category}\" to";
+const STR_PICK_HERE: &str = "Choose";
+const STR_PICK_TOP_LEVEL: &str = "Move to top level";
+const STR_PICK_CANCEL: &str = "Cancel";
+const STR_TRANSLATIONS_TITLE: &str = "Translations of \"{category}\"";
+const STR_TRANSLATIONS_SAVE: &str = "Save";
+const STR_UNUSED: &str = "Unused";
 
 pub struct CategoriesPage {}
 
@@ -36,6 +47,12 @@ impl CategoriesPage {
                         }))
                     }))
             })))
+            .child_signal(state.translating.signal_cloned().map(|translating| {
+                translating.map(TranslationsDom::render)
+            }))
+            .child_signal(state.picking.signal_cloned().map(clone!(state => move |picking| {
+                picking.map(|picking| PickingDom::render(state.clone(), picking))
+            })))
             .child(
                 html!("category-page", {
                     .children(&mut [
@@ -136,6 +153,27 @@ impl ContentLineDom {
             }))
         })];
 
+        children.push(html!("span", {
+            .prop("slot", "content")
+            .text_signal(content_state.state.usage_signal(content_state.cat.id).map(|usage| {
+                usage.map(usage_label).unwrap_or_default()
+            }))
+        }));
+
+        children.push(html!("empty-fragment", {
+            .prop("slot", "content")
+            .child_signal(content_state.state.picking.signal_ref(clone!(content_state => move |picking| {
+                picking.as_ref().map(|_| html!("button-rect", {
+                    .prop("kind", "text")
+                    .prop("color", "blue")
+                    .text(STR_PICK_HERE)
+                    .event(clone!(content_state => move |_evt: events::Click| {
+                        actions::pick(content_state.state.clone(), Some(content_state.cat.clone()));
+                    }))
+                }))
+            })))
+        }));
+
         if content_state.parent.is_none() {
             children.push(html!("button-expand", {
                 .prop("slot", "content")
@@ -184,6 +222,43 @@ impl MenuDom {
                         actions::move_category(content_state.clone(), actions::Direction::Down);
                     }))
                 }),
+                html!("button-rect", {
+                    .prop("kind", "text")
+                    .prop("color", "darkGray")
+                    .prop("hoverColor", "blue")
+                    .text("Move to…")
+                    .event(clone!(content_state => move |_evt: events::Click| {
+                        actions::start_picking(content_state.clone(), PickAction::Move);
+                    }))
+                }),
+                html!("button-rect", {
+                    .prop("kind", "text")
+                    .prop("color", "darkGray")
+                    .prop("hoverColor", "blue")
+                    .text("Merge into…")
+                    .event(clone!(content_state => move |_evt: events::Click| {
+                        actions::start_picking(content_state.clone(), PickAction::Merge);
+                    }))
+                }),
+                html!("button-rect", {
+                    .prop("kind", "text")
+                    .prop("color", "darkGray")
+                    .prop("hoverColor", "blue")
+                    .text("Move assets to…")
+                    .event(clone!(content_state => move |_evt: events::Click| {
+                        actions::start_picking(content_state.clone(), PickAction::Recategorize);
+                    }))
+                }),
+                html!("button-rect", {
+                    .prop("kind", "text")
+                    .prop("color", "darkGray")
+                    .prop("hoverColor", "blue")
+                    .text("Translations")
+                    .event(clone!(content_state => move |_evt: events::Click| {
+                        content_state.close_menu();
+                        content_state.state.translating.set(Some(content_state.clone()));
+                    }))
+                }),
                 html!("button-rect", {
                     .prop("kind", "text")
                     .prop("color", "darkGray")
@@ -209,3 +284,129 @@ impl MenuDom {
         })
     }
 }
+
+pub struct PickingDom {}
+
+impl PickingDom {
+    pub fn render(state: Rc<State>, picking: Picking) -> Dom {
+        let message = match picking.action {
+            PickAction::Move => STR_PICK_MOVE,
+            PickAction::Merge => STR_PICK_MERGE,
+            PickAction::Recategorize => STR_PICK_RECATEGORIZE,
+        }
+        .replace("{category}", &picking.source.cat.name.get_cloned());
+
+        html!("div", {
+            .style("display", "flex")
+            .style("align-items", "center")
+            .style("gap", "16px")
+            .style("padding", "8px 16px")
+            .child(html!("span", {
+                .text(&message)
+            }))
+            .apply_if(picking.action == PickAction::Move, |dom| {
+                dom.child(html!("button-rect", {
+                    .prop("kind", "text")
+                    .prop("color", "blue")
+                    .text(STR_PICK_TOP_LEVEL)
+                    .event(clone!(state => move |_evt: events::Click| {
+                        actions::pick(state.clone(), None);
+                    }))
+                }))
+            })
+            .child(html!("button-rect", {
+                .prop("kind", "text")
+                .prop("color", "darkGray")
+                .text(STR_PICK_CANCEL)
+                .event(clone!(state => move |_evt: events::Click| {
+                    state.picking.set(None);
+                }))
+            }))
+        })
+    }
+}
+
+pub struct TranslationsDom {}
+
+impl TranslationsDom {
+    pub fn render(content_state: Rc<ContentState>) -> Dom {
+        let translated_name = Mutable::new(content_state.cat.translated_name.get_cloned());
+
+        html!("dialog-overlay", {
+            .prop("open", true)
+            .prop("autoClose", false)
+            .event(clone!(content_state => move |_evt: events::Close| {
+                content_state.state.translating.set(None);
+            }))
+            .child(html!("popup-body", {
+                .child(html!("fa-button", {
+                    .prop("slot", "close")
+                    .prop("icon", "fa-regular fa-xmark")
+                    .event(clone!(content_state => move |_evt: events::Click| {
+                        content_state.state.translating.set(None);
+                    }))
+                }))
+                .child(html!("h3", {
+                    .prop("slot", "heading")
+                    .text(&STR_TRANSLATIONS_TITLE.replace("{category}", &content_state.cat.name.get_cloned()))
+                }))
+                .child(html!("div", {
+                    .prop("slot", "body")
+                    .style("display", "grid")
+                    .style("gap", "12px")
+                    .style("padding", "0 12px 12px")
+                    .children(JIG_LANGUAGES.iter().filter(|language| language.0 != "en").map(clone!(translated_name => move |language| {
+                        let code = language.0;
+
+                        html!("input-wrapper", {
+                            .prop("label", language.1)
+                            .child(html!("input" => HtmlInputElement, {
+                                .with_node!(elem => {
+                                    .prop("dir", "auto")
+                                    .prop("value", translated_name.lock_ref().get(code).cloned().unwrap_or_default())
+                                    .event(clone!(translated_name => move |_: events::Input| {
+                                        let value = elem.value();
+                                        let mut translated_name = translated_name.lock_mut();
+                                        if value.trim().is_empty() {
+                                            translated_name.remove(code);
+                                        } else {
+                                            translated_name.insert(code.to_string(), value);
+                                        }
+                                    }))
+                                })
+                            }))
+                        })
+                    })))
+                    .child(html!("button-rect", {
+                        .text(STR_TRANSLATIONS_SAVE)
+                        .event(clone!(content_state, translated_name => move |_evt: events::Click| {
+                            actions::save_translated_name(content_state.clone(), translated_name.get_cloned());
+                        }))
+                    }))
+                }))
+            }))
+        })
+    }
+}
+
+fn usage_label(usage: CategoryUsage) -> String {
+    let counts = [
+        (usage.jigs, "JIGs"),
+        (usage.resources, "resources"),
+        (usage.playlists, "playlists"),
+        (usage.courses, "courses"),
+        (usage.images, "images"),
+    ];
+
+    let used: Vec<_> = counts
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{count} {label}"))
+        .collect();
+
+    if used.is_empty() {
+        STR_UNUSED.to_string()
+    } else {
+        used.join(", ")
+    }
+}
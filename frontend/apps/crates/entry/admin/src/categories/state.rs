@@ -2,31 +2,60 @@ use dominator_helpers::futures::AsyncLoader;
 use futures_signals::signal::{Mutable, Signal, SignalExt};
 use futures_signals::signal_vec::{MutableVec, SignalVecExt};
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::rc::Rc;
 
 use super::debug;
-use shared::domain::category::{Category as DbCategory, CategoryId};
+use shared::domain::category::{Category as DbCategory, CategoryId, CategoryUsage};
 
 use web_sys::HtmlElement;
 
 pub struct State {
     pub categories: MutableVec<Rc<Category>>,
+    pub usage: Mutable<HashMap<CategoryId, CategoryUsage>>,
     pub deleting: Mutable<Option<Rc<ContentState>>>,
+    pub translating: Mutable<Option<Rc<ContentState>>>,
+    /// Set while choosing the category to move, merge or recategorize into.
+    pub picking: Mutable<Option<Picking>>,
     pub loader: AsyncLoader,
 }
 impl State {
     pub fn new() -> Self {
         Self {
             categories: MutableVec::new(),
+            usage: Mutable::new(HashMap::new()),
             deleting: Mutable::new(None),
+            translating: Mutable::new(None),
+            picking: Mutable::new(None),
             loader: AsyncLoader::new(),
         }
     }
+
+    pub fn usage_signal(&self, id: CategoryId) -> impl Signal<Item = Option<CategoryUsage>> {
+        self.usage.signal_ref(move |usage| usage.get(&id).copied())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PickAction {
+    /// Move the category and its children under the picked category.
+    Move,
+    /// Merge the category into the picked category.
+    Merge,
+    /// Move the category's assets to the picked category.
+    Recategorize,
+}
+
+#[derive(Clone)]
+pub struct Picking {
+    pub action: PickAction,
+    pub source: Rc<ContentState>,
 }
 
 pub struct Category {
     pub id: CategoryId,
     pub name: Mutable<String>,
+    pub translated_name: Mutable<HashMap<String, String>>,
     pub children: MutableVec<Rc<Category>>,
     pub expanded: Mutable<bool>,
     pub editing: Mutable<bool>,
@@ -38,21 +67,28 @@ impl Category {
     }
 
     pub fn new(id: CategoryId, name: String, editing: bool) -> Self {
-        Self::new_internal(id, name, None, editing)
+        Self::new_internal(id, name, HashMap::new(), None, editing)
     }
-    pub fn new_with_children(id: CategoryId, name: String, children: Vec<Rc<Self>>) -> Self {
-        Self::new_internal(id, name, Some(children), false)
+    pub fn new_with_children(
+        id: CategoryId,
+        name: String,
+        translated_name: HashMap<String, String>,
+        children: Vec<Rc<Self>>,
+    ) -> Self {
+        Self::new_internal(id, name, translated_name, Some(children), false)
     }
 
     fn new_internal(
         id: CategoryId,
         name: String,
+        translated_name: HashMap<String, String>,
         children: Option<Vec<Rc<Self>>>,
         editing: bool,
     ) -> Self {
         Self {
             id,
             name: Mutable::new(name),
+            translated_name: Mutable::new(translated_name),
             children: match children {
                 Some(children) => MutableVec::new_with_values(children),
                 None => MutableVec::new(),
@@ -72,7 +108,7 @@ impl From<DbCategory> for Category {
             .map(Rc::new)
             .collect();
 
-        Self::new_with_children(cat.id, cat.name, children)
+        Self::new_with_children(cat.id, cat.name, cat.translated_name, children)
     }
 }
 
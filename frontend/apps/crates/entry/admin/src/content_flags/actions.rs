@@ -0,0 +1,66 @@
+use std::rc::Rc;
+
+use dominator::clone;
+use shared::{
+    api::endpoints,
+    domain::moderation::{
+        ContentFlagDecision, ContentFlagId, ContentFlagListPath, ContentFlagListQuery,
+        ContentFlagReviewPath, ContentFlagReviewRequest,
+    },
+};
+use utils::prelude::ApiEndpointExt;
+
+use super::ContentFlags;
+
+impl ContentFlags {
+    pub fn load_data(self: &Rc<Self>) {
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            state.load_flags().await;
+        }));
+    }
+
+    async fn load_flags(self: &Rc<Self>) {
+        let query = ContentFlagListQuery {
+            status: self.status.get(),
+            kind: self.kind.get(),
+            page: self.active_page.get().into(),
+            ..Default::default()
+        };
+
+        match endpoints::moderation::List::api_with_auth(ContentFlagListPath(), Some(query)).await {
+            Ok(res) => {
+                self.flags
+                    .lock_mut()
+                    .replace_cloned(res.flags.into_iter().map(Rc::new).collect());
+                self.total_pages.set(Some(res.pages.into()));
+                self.total_count.set(Some(res.total_count.into()));
+            }
+            Err(_) => log::error!("Couldn't load the content flags"),
+        }
+    }
+
+    /// Reloads the first page, after a filter has changed.
+    pub fn apply_filters(self: &Rc<Self>) {
+        self.active_page.set(0);
+        self.load_data();
+    }
+
+    pub fn go_to_page(self: &Rc<Self>, page: usize) {
+        self.active_page.set(page);
+        self.load_data();
+    }
+
+    /// Approves or rejects a flag, and reloads the current page.
+    pub fn review(self: &Rc<Self>, id: ContentFlagId, decision: ContentFlagDecision) {
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            let req = ContentFlagReviewRequest { decision };
+
+            match endpoints::moderation::Review::api_with_auth(ContentFlagReviewPath(id), Some(req)).await {
+                Ok(_) => state.load_flags().await,
+                Err(_) => log::error!("Couldn't review content flag {}", id.0),
+            }
+        }));
+    }
+}
@@ -0,0 +1,300 @@
+use super::ContentFlags;
+use dominator::{clone, html, Dom, DomBuilder};
+use futures_signals::{
+    map_ref,
+    signal::{Signal, SignalExt},
+    signal_vec::SignalVecExt,
+};
+use shared::{
+    domain::{
+        image::ImageFileKind,
+        moderation::{
+            ContentFlag, ContentFlagDecision, ContentFlagStatus, FlagReason, FlaggedContentKind,
+            PersonalInfoKind,
+        },
+    },
+    media::MediaLibrary,
+};
+use std::rc::Rc;
+use utils::{
+    component::Component,
+    events,
+    routes::{AdminRoute, AdminUsersRoute, Route},
+};
+use web_sys::ShadowRoot;
+
+const STATUSES: [ContentFlagStatus; 3] = [
+    ContentFlagStatus::Pending,
+    ContentFlagStatus::Approved,
+    ContentFlagStatus::Rejected,
+];
+
+const KINDS: [FlaggedContentKind; 4] = [
+    FlaggedContentKind::UserImage,
+    FlaggedContentKind::UserPdf,
+    FlaggedContentKind::Jig,
+    FlaggedContentKind::Circle,
+];
+
+impl Component<ContentFlags> for Rc<ContentFlags> {
+    fn styles() -> &'static str {
+        include_str!("./styles.css")
+    }
+
+    fn dom(&self, dom: DomBuilder<ShadowRoot>) -> DomBuilder<ShadowRoot> {
+        let state = self;
+
+        state.load_data();
+
+        dom.child(html!("admin-table-content-flags", {
+            .child(html!("input-select", {
+                .prop("slot", "controls")
+                .prop("label", "Status")
+                .prop("multiple", false)
+                .prop_signal("value", state.status.signal().map(|status| {
+                    status.map(status_label).unwrap_or("All")
+                }))
+                .child(html!("input-select-option", {
+                    .text("All")
+                    .prop_signal("selected", state.status.signal().map(|status| status.is_none()))
+                    .event(clone!(state => move |_: events::CustomSelectedChange| {
+                        state.status.set(None);
+                        state.apply_filters();
+                    }))
+                }))
+                .children(STATUSES.iter().map(|status| {
+                    let status = *status;
+                    html!("input-select-option", {
+                        .text(status_label(status))
+                        .prop_signal("selected", state.status.signal().map(move |current| current == Some(status)))
+                        .event(clone!(state => move |_: events::CustomSelectedChange| {
+                            state.status.set(Some(status));
+                            state.apply_filters();
+                        }))
+                    })
+                }))
+            }))
+            .child(html!("input-select", {
+                .prop("slot", "controls")
+                .prop("label", "Kind")
+                .prop("multiple", false)
+                .prop_signal("value", state.kind.signal().map(|kind| {
+                    kind.map(kind_label).unwrap_or("All")
+                }))
+                .child(html!("input-select-option", {
+                    .text("All")
+                    .prop_signal("selected", state.kind.signal().map(|kind| kind.is_none()))
+                    .event(clone!(state => move |_: events::CustomSelectedChange| {
+                        state.kind.set(None);
+                        state.apply_filters();
+                    }))
+                }))
+                .children(KINDS.iter().map(|kind| {
+                    let kind = *kind;
+                    html!("input-select-option", {
+                        .text(kind_label(kind))
+                        .prop_signal("selected", state.kind.signal().map(move |current| current == Some(kind)))
+                        .event(clone!(state => move |_: events::CustomSelectedChange| {
+                            state.kind.set(Some(kind));
+                            state.apply_filters();
+                        }))
+                    })
+                }))
+            }))
+            .child(html!("span", {
+                .prop("slot", "controls")
+                .text_signal(state.total_count.signal().map(|total_count| {
+                    total_count.map(|total_count| format!("{total_count} found")).unwrap_or_default()
+                }))
+            }))
+            .child(state.render_pagination())
+            .children_signal_vec(state.flags.signal_vec_cloned().map(clone!(state => move |flag| {
+                state.render_flag(&flag)
+            })))
+        }))
+    }
+}
+
+impl ContentFlags {
+    fn render_pagination(self: &Rc<Self>) -> Dom {
+        let state = self;
+        html!("table-pagination-jig", {
+            .prop("slot", "controls")
+            .child(html!("fa-button", {
+                .prop("slot", "back")
+                .prop("title", "Previous")
+                .prop("icon", "fa-solid fa-chevron-left")
+                .prop_signal("disabled", state.active_page.signal().map(|active_page| {
+                    active_page == 0
+                }))
+                .event(clone!(state => move |_: events::Click| {
+                    let active_page = state.active_page.get();
+                    state.go_to_page(active_page - 1);
+                }))
+            }))
+            .child(html!("fa-button", {
+                .prop("slot", "next")
+                .prop("title", "Next")
+                .prop("icon", "fa-solid fa-chevron-right")
+                .prop_signal("disabled", state.is_last_page_signal())
+                .event(clone!(state => move |_: events::Click| {
+                    let active_page = state.active_page.get();
+                    state.go_to_page(active_page + 1);
+                }))
+            }))
+            .child_signal(state.total_pages.signal().map(clone!(state => move |total_pages| {
+                total_pages.map(|total_pages| {
+                    html!("input-select", {
+                        .style("width", "150px")
+                        .prop_signal("value", state.active_page.signal().map(|active_page| {
+                            format!("{}", active_page + 1)
+                        }))
+                        .children((0..total_pages).map(|page| {
+                            html!("input-select-option", {
+                                .text(&format!("{}", page + 1))
+                                .prop_signal("selected", state.active_page.signal().map(move |active_page| {
+                                    page == active_page
+                                }))
+                                .event(clone!(state => move |evt: events::CustomSelectedChange| {
+                                    if evt.selected() {
+                                        state.go_to_page(page);
+                                    }
+                                }))
+                            })
+                        }))
+                    })
+                })
+            })))
+        })
+    }
+
+    fn is_last_page_signal(&self) -> impl Signal<Item = bool> {
+        map_ref! {
+            let total_pages = self.total_pages.signal(),
+            let active_page = self.active_page.signal() => {
+                match total_pages {
+                    None => true,
+                    // active_page is 0 indexed
+                    Some(total_pages) => *active_page + 1 >= *total_pages,
+                }
+            }
+        }
+    }
+
+    fn render_flag(self: &Rc<Self>, flag: &ContentFlag) -> Dom {
+        let state = self;
+        let id = flag.id;
+
+        html!("admin-table-line", {
+            .children(&mut [
+                html!("div", {
+                    .class("img-wrapper")
+                    .apply(|dom| match flag.kind {
+                        FlaggedContentKind::UserImage => dom.child(html!("img-ji", {
+                            .prop("size", "thumb")
+                            .prop("lib", MediaLibrary::User.to_str())
+                            .prop("id", flag.item_id.to_string())
+                            .prop("kind", ImageFileKind::Png.to_str())
+                        })),
+                        _ => dom,
+                    })
+                }),
+                html!("span", {
+                    .text(kind_label(flag.kind))
+                    .child(html!("span", {
+                        .text(&flag.item_id.to_string())
+                    }))
+                }),
+                html!("span", {
+                    .children(flag.reasons.iter().map(|reason| {
+                        html!("span", {
+                            .text(&reason_label(reason))
+                        })
+                    }))
+                }),
+                html!("span", {
+                    .apply(|dom| match flag.owner_id {
+                        Some(user_id) => dom.child(html!("a", {
+                            .prop("href", Route::Admin(AdminRoute::Users(AdminUsersRoute::User(user_id))).to_string())
+                            .text(&user_id.to_string())
+                        })),
+                        None => dom.text("-"),
+                    })
+                }),
+                html!("span", {
+                    .text(&flag.created_at.format("%Y-%m-%d %H:%M").to_string())
+                }),
+                html!("span", {
+                    .text(status_label(flag.status))
+                    .apply(|dom| match flag.reviewed_at {
+                        Some(reviewed_at) => dom.child(html!("span", {
+                            .text(&reviewed_at.format("%Y-%m-%d %H:%M").to_string())
+                        })),
+                        None => dom,
+                    })
+                }),
+                html!("span", {
+                    .apply(|dom| match flag.status {
+                        ContentFlagStatus::Pending => dom.children(&mut [
+                            html!("button-rect", {
+                                .prop("kind", "text")
+                                .prop("color", "blue")
+                                .text("Approve")
+                                .event(clone!(state => move |_: events::Click| {
+                                    state.review(id, ContentFlagDecision::Approve);
+                                }))
+                            }),
+                            html!("button-rect", {
+                                .prop("kind", "text")
+                                .prop("color", "red")
+                                .text("Reject")
+                                .event(clone!(state => move |_: events::Click| {
+                                    state.review(id, ContentFlagDecision::Reject);
+                                }))
+                            }),
+                        ]),
+                        _ => dom,
+                    })
+                }),
+            ])
+        })
+    }
+}
+
+fn kind_label(kind: FlaggedContentKind) -> &'static str {
+    match kind {
+        FlaggedContentKind::UserImage => "User image",
+        FlaggedContentKind::UserPdf => "User PDF",
+        FlaggedContentKind::Jig => "JIG",
+        FlaggedContentKind::Circle => "Circle",
+    }
+}
+
+fn status_label(status: ContentFlagStatus) -> &'static str {
+    match status {
+        ContentFlagStatus::Pending => "Pending",
+        ContentFlagStatus::Approved => "Approved",
+        ContentFlagStatus::Rejected => "Rejected",
+    }
+}
+
+fn reason_label(reason: &FlagReason) -> String {
+    match reason {
+        FlagReason::Profanity {
+            field,
+            language,
+            term,
+        } => format!("{field}: \"{term}\" ({language})"),
+        FlagReason::PersonalInfo { field, info } => {
+            let info = match info {
+                PersonalInfoKind::Email => "email address",
+                PersonalInfoKind::PhoneNumber => "phone number",
+                PersonalInfoKind::CardNumber => "card number",
+            };
+            format!("{field}: {info}")
+        }
+        FlagReason::Image { label, confidence } => {
+            format!("image: {label} ({:.0}%)", confidence * 100.0)
+        }
+    }
+}
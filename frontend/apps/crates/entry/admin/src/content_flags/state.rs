@@ -0,0 +1,29 @@
+use dominator_helpers::futures::AsyncLoader;
+use futures_signals::{signal::Mutable, signal_vec::MutableVec};
+use shared::domain::moderation::{ContentFlag, ContentFlagStatus, FlaggedContentKind};
+use std::rc::Rc;
+
+pub struct ContentFlags {
+    pub loader: AsyncLoader,
+    pub flags: MutableVec<Rc<ContentFlag>>,
+    pub status: Mutable<Option<ContentFlagStatus>>,
+    pub kind: Mutable<Option<FlaggedContentKind>>,
+    pub active_page: Mutable<usize>,
+    pub total_pages: Mutable<Option<usize>>,
+    pub total_count: Mutable<Option<usize>>,
+}
+
+impl ContentFlags {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self {
+            loader: AsyncLoader::new(),
+            flags: MutableVec::new(),
+            // the review queue
+            status: Mutable::new(Some(ContentFlagStatus::Pending)),
+            kind: Mutable::new(None),
+            active_page: Mutable::new(0),
+            total_pages: Mutable::new(None),
+            total_count: Mutable::new(None),
+        })
+    }
+}
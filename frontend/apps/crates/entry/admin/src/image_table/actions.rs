@@ -4,8 +4,8 @@ use dominator::clone;
 use shared::{
     api::endpoints,
     domain::image::{
-        ImageBrowsePath, ImageBrowseQuery, ImageGetPath, ImageId, ImageResponse, ImageSearchPath,
-        ImageSearchQuery,
+        bulk::ImageBulkUndoPath, ImageBrowsePath, ImageBrowseQuery, ImageGetPath, ImageId,
+        ImageResponse, ImageSearchPath, ImageSearchQuery,
     },
 };
 use utils::{prelude::ApiEndpointExt, unwrap::UnwrapJiExt};
@@ -147,6 +147,23 @@ impl ImageTable {
         }
     }
 
+    pub fn undo_last_bulk_update(self: &Rc<Self>) {
+        let state = self;
+        let id = match state.last_bulk_update.replace(None) {
+            Some(id) => id,
+            None => return,
+        };
+        state.loader.load(clone!(state => async move {
+            match endpoints::image::bulk::Undo::api_with_auth(ImageBulkUndoPath(id), None).await {
+                Ok(_) => state.load_images().await,
+                Err(_err) => {
+                    log::error!("couldn't undo!");
+                    state.last_bulk_update.set(Some(id));
+                }
+            }
+        }));
+    }
+
     pub fn go_to_page(self: &Rc<Self>, page: u32) {
         let state = self;
         state.loader.load(clone!(state => async move {
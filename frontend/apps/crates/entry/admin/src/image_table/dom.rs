@@ -76,6 +76,20 @@ impl Component<ImageTable> for Rc<ImageTable> {
                     state.mass_editing.set(true);
                 }))
             }))
+            .child_signal(state.last_bulk_update.signal_ref(clone!(state => move |last_bulk_update| {
+                last_bulk_update.as_ref().map(|_| {
+                    html!("button-rect", {
+                        .prop("slot", "controls")
+                        .prop("color", "blue")
+                        .prop("size", "regular")
+                        .prop("kind", "outline")
+                        .text("Undo mass edit")
+                        .event(clone!(state => move |_: events::Click| {
+                            state.undo_last_bulk_update();
+                        }))
+                    })
+                })
+            })))
             .child(html!("table-pagination-jig", {
                 .prop("slot", "controls")
                 .child(html!("fa-button", {
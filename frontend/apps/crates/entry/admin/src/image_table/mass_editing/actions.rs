@@ -1,7 +1,13 @@
 use std::rc::Rc;
 
 use dominator::clone;
-use futures::future::join_all;
+use shared::{
+    api::endpoints,
+    domain::image::bulk::{
+        ImageBulkChanges, ImageBulkQuery, ImageBulkUpdatePath, ImageBulkUpdateRequest,
+    },
+};
+use utils::prelude::ApiEndpointExt;
 use wasm_bindgen_futures::spawn_local;
 
 use crate::image_table::editable_image::EditableImage;
@@ -13,8 +19,9 @@ impl MassEditing {
         let state = self;
         spawn_local(clone!(state => async move {
             let selected = state.get_selected();
-            state.apply_changes_on_images(&selected);
-            state.save_all_images(&selected).await;
+            if state.save_bulk_update(&selected).await {
+                state.apply_changes_on_images(&selected);
+            }
             state.images_state.mass_editing.set(false);
             state.images_state.clear_selected();
         }));
@@ -92,7 +99,48 @@ impl MassEditing {
         };
     }
 
-    async fn save_all_images(self: &Rc<Self>, selected: &Vec<Rc<EditableImage>>) {
-        join_all(selected.iter().map(|image| image.save())).await;
+    /// Saves the changes to every selected image in one request. Returns whether it worked.
+    async fn save_bulk_update(self: &Rc<Self>, selected: &Vec<Rc<EditableImage>>) -> bool {
+        let styles: Vec<_> = self.styles.lock_ref().iter().cloned().collect();
+        let tags: Vec<_> = self.tags.lock_ref().iter().cloned().collect();
+        let ages: Vec<_> = self.ages.lock_ref().iter().cloned().collect();
+        let affiliations: Vec<_> = self.affiliations.lock_ref().iter().cloned().collect();
+
+        let changes = match &*self.mode.lock_ref() {
+            Mode::Add => ImageBulkChanges {
+                add_styles: styles,
+                add_tags: tags,
+                add_age_ranges: ages,
+                add_affiliations: affiliations,
+                ..Default::default()
+            },
+            Mode::Remove => ImageBulkChanges {
+                remove_styles: styles,
+                remove_tags: tags,
+                remove_age_ranges: ages,
+                remove_affiliations: affiliations,
+                ..Default::default()
+            },
+        };
+
+        let req = ImageBulkUpdateRequest {
+            query: ImageBulkQuery {
+                ids: selected.iter().map(|image| image.id).collect(),
+                ..Default::default()
+            },
+            changes,
+        };
+
+        match endpoints::image::bulk::Update::api_with_auth(ImageBulkUpdatePath(), Some(req)).await
+        {
+            Ok(res) => {
+                self.images_state.last_bulk_update.set(Some(res.id));
+                true
+            }
+            Err(_err) => {
+                log::error!("couldn't save!");
+                false
+            }
+        }
     }
 }
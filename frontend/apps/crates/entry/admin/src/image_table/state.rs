@@ -1,6 +1,9 @@
 use dominator_helpers::futures::AsyncLoader;
 use futures_signals::{signal::Mutable, signal_vec::MutableVec};
-use shared::domain::{asset::OrderBy, image::ImageId};
+use shared::domain::{
+    asset::OrderBy,
+    image::{bulk::ImageBulkUpdateId, ImageId},
+};
 use std::{cell::RefCell, collections::HashSet, rc::Rc};
 
 use super::editable_image::EditableImage;
@@ -14,6 +17,8 @@ pub struct ImageTable {
     pub active_page: Mutable<u32>,
     pub total_pages: Mutable<Option<u32>>,
     pub order_by: Mutable<OrderBy>,
+    /// The last mass edit, which can be undone.
+    pub last_bulk_update: Mutable<Option<ImageBulkUpdateId>>,
 }
 
 impl ImageTable {
@@ -27,6 +32,7 @@ impl ImageTable {
             active_page: Mutable::new(0),
             total_pages: Mutable::new(None),
             order_by: Mutable::new(OrderBy::PublishedAt),
+            last_bulk_update: Mutable::new(None),
         })
     }
 
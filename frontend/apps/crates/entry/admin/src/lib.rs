@@ -5,6 +5,7 @@
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 mod categories;
+mod content_flags;
 mod course_curation;
 mod export;
 mod featured_jigs;
@@ -19,6 +20,7 @@ mod schools;
 mod sidebar;
 mod strings;
 mod users;
+mod web_media;
 
 use router::Router;
 use wasm_bindgen::prelude::*;
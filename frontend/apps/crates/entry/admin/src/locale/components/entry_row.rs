@@ -3,7 +3,7 @@ use crate::locale::state::{Column, DisplayableEntry, Section, State};
 use dominator::{clone, events, html, with_node, Dom};
 use futures_signals::signal::Mutable;
 use futures_signals::signal_vec::SignalVecExt;
-use shared::domain::locale::EntryStatus;
+use shared::domain::locale::{is_valid_key, EntryStatus};
 use std::clone::Clone;
 use std::rc::Rc;
 use url::Url;
@@ -112,30 +112,33 @@ impl EntryRow {
                                 }))
                             })
                         },
-                        Column::English => {
+                        Column::Key => {
                             html!("locale-cell", {
-                                .child(html!("textarea" => HtmlTextAreaElement, {
+                                .child(html!("input" => HtmlInputElement, {
                                     .with_node!(elem => {
-                                        .text(&entry_ref.english)
+                                        .prop("value", &entry_ref.key)
                                         .event(clone!(state, entry => move |_: events::Input| {
                                             let value: String = elem.value();
                                             let mut entry = entry.lock_mut();
-                                            entry.english = value;
-                                            Self::save_entry(state.clone(), entry.clone());
+                                            entry.key = value;
+                                            // keys are the ids of Fluent messages, so only valid ones are saved
+                                            if entry.key.is_empty() || is_valid_key(&entry.key) {
+                                                Self::save_entry(state.clone(), entry.clone());
+                                            }
                                         }))
                                     })
                                 }))
                             })
                         },
-                        Column::Hebrew => {
+                        Column::Translation(language) => {
                             html!("locale-cell", {
                                 .child(html!("textarea" => HtmlTextAreaElement, {
                                     .with_node!(elem => {
-                                        .text(&entry_ref.hebrew)
+                                        .text(entry_ref.translations.get(&language.code).map(String::as_str).unwrap_or_default())
                                         .event(clone!(state, entry => move |_: events::Input| {
                                             let value: String = elem.value();
                                             let mut entry = entry.lock_mut();
-                                            entry.hebrew = value;
+                                            entry.translations.insert(language.code.clone(), value);
                                             Self::save_entry(state.clone(), entry.clone());
                                         }))
                                     })
@@ -87,15 +87,17 @@ impl LocaleOuterDom {
                                     entries.sort_by(|a, b| {
                                         let a = a.lock_ref();
                                         let b = b.lock_ref();
-                                        let mut ord = match sort.column {
+                                        let mut ord = match &sort.column {
                                             SortKind::Section => a.section.cmp(&b.section),
                                             SortKind::ItemKind => {
                                                 let a = Self::get_item_kind_name_by_id(&state.item_kind_options, a.item_kind_id);
                                                 let b = Self::get_item_kind_name_by_id(&state.item_kind_options, b.item_kind_id);
                                                 a.cmp(&b)
                                             }
-                                            SortKind::English => a.english.cmp(&b.english),
-                                            SortKind::Hebrew => a.hebrew.cmp(&b.hebrew),
+                                            SortKind::Key => a.key.cmp(&b.key),
+                                            SortKind::Translation(language) => {
+                                                a.translations.get(language).cmp(&b.translations.get(language))
+                                            }
                                             SortKind::Status => a.status.to_string().cmp(&b.status.to_string()),
                                             SortKind::Comments => a.comments.cmp(&b.comments),
                                         };
@@ -115,26 +115,27 @@ impl TableHeaderDom {
                                 ])
                             })
                         }
-                        Column::English => {
+                        Column::Key => {
                             html!("locale-cell-header", {
-                                .prop("label", Column::English.to_string())
+                                .prop("label", Column::Key.to_string())
                                 .child(html!("locale-sort-button", {
                                     .prop("slot", "actions")
-                                    .prop_signal("sorted", state.sort.signal_ref(|sort| sort.column == SortKind::English))
+                                    .prop_signal("sorted", state.sort.signal_ref(|sort| sort.column == SortKind::Key))
                                     .event(clone!(state => move |_event: events::Click| {
-                                        state.sort_clicked(SortKind::English);
+                                        state.sort_clicked(SortKind::Key);
                                     }))
                                 }))
                             })
                         }
-                        Column::Hebrew => {
+                        Column::Translation(language) => {
+                            let sort_kind = SortKind::Translation(language.code.clone());
                             html!("locale-cell-header", {
-                                .prop("label", Column::Hebrew.to_string())
+                                .prop("label", &language.display_name)
                                 .child(html!("locale-sort-button", {
                                     .prop("slot", "actions")
-                                    .prop_signal("sorted", state.sort.signal_ref(|sort| sort.column == SortKind::Hebrew))
+                                    .prop_signal("sorted", state.sort.signal_ref(clone!(sort_kind => move |sort| sort.column == sort_kind)))
                                     .event(clone!(state => move |_event: events::Click| {
-                                        state.sort_clicked(SortKind::Hebrew);
+                                        state.sort_clicked(sort_kind.clone());
                                     }))
                                 }))
                             })
@@ -4,10 +4,11 @@ use shared::{
     api::endpoints,
     domain::locale::{
         Bundle, CreateEntryPath, CreateEntryRequest, DeleteEntryPath, Entry, EntryStatus, ItemKind,
-        ListBundlePath, ListEntryGroupBy, ListEntryPath, ListEntryQuery, ListEntryResponse,
-        ListItemKindPath, UpdateEntryPath, UpdateEntryRequest,
+        Language, ListBundlePath, ListEntryGroupBy, ListEntryPath, ListEntryQuery,
+        ListEntryResponse, ListItemKindPath, ListLanguagePath, UpdateEntryPath, UpdateEntryRequest,
     },
 };
+use std::collections::BTreeMap;
 use utils::{prelude::ApiEndpointExt, unwrap::UnwrapJiExt};
 use uuid::Uuid;
 
@@ -25,6 +26,13 @@ pub async fn get_item_kind() -> Vec<ItemKind> {
         .item_kinds
 }
 
+pub async fn get_languages() -> Vec<Language> {
+    endpoints::locale::language::List::api_with_auth(ListLanguagePath(), None)
+        .await
+        .unwrap_ji()
+        .languages
+}
+
 pub async fn get_entries(bundles: Vec<Uuid>) -> Vec<DisplayableEntry> {
     let query = ListEntryQuery {
         bundles,
@@ -51,8 +59,14 @@ pub async fn clone_entry(entry: &DisplayableEntry) -> DisplayableEntry {
         bundle_id: entry.bundle_id,
         section: entry.section.clone(),
         item_kind_id: None,
-        english: Some(entry.english.clone()),
-        hebrew: Some(entry.hebrew.clone()),
+        // keys are unique within a bundle
+        key: None,
+        translations: entry
+            .translations
+            .iter()
+            .filter(|(_, text)| !text.is_empty())
+            .map(|(language, text)| (language.clone(), text.clone()))
+            .collect(),
         status: entry.status,
         zeplin_reference: {
             entry
@@ -73,6 +87,7 @@ pub async fn clone_entry(entry: &DisplayableEntry) -> DisplayableEntry {
 
     let mut new_entry = entry.clone();
     new_entry.id = res.id;
+    new_entry.key = String::new();
     new_entry
 }
 
@@ -81,8 +96,8 @@ pub async fn create_entry(bundle_id: Uuid) -> DisplayableEntry {
         bundle_id,
         section: None,
         item_kind_id: None,
-        english: None,
-        hebrew: None,
+        key: None,
+        translations: BTreeMap::new(),
         status: EntryStatus::Approved,
         zeplin_reference: None,
         comments: None,
@@ -120,8 +135,8 @@ pub async fn delete_entry(entry_id: u32) {
 fn new_entry_with_id(id: u32, bundle_id: Uuid) -> DisplayableEntry {
     DisplayableEntry {
         id,
-        english: String::new(),
-        hebrew: String::new(),
+        key: String::new(),
+        translations: BTreeMap::new(),
         section: None,
         item_kind_id: None,
         status: EntryStatus::Discuss,
@@ -5,11 +5,14 @@ use dominator_helpers::futures::AsyncLoader;
 use futures_signals::signal::Mutable;
 use futures_signals::signal_vec::MutableVec;
 use serde_derive::{Deserialize, Serialize};
-use shared::domain::locale::{Bundle, Entry, EntryStatus, ItemKind, UpdateEntryRequest};
+use shared::domain::locale::{
+    Bundle, Entry, EntryStatus, ItemKind, Language, UpdateEntryRequest, FALLBACK_LANGUAGE,
+};
 use std::clone::Clone;
 use std::cmp::Ord;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::fmt;
 use std::rc::Rc;
 use std::str::FromStr;
 use strum_macros::Display;
@@ -46,6 +49,7 @@ pub struct State {
 
     pub item_kind_filter: Mutable<HashMap<Option<Uuid>, bool>>,
     pub item_kind_options: Vec<ItemKind>,
+    pub languages: Vec<Language>,
     pub section_options: Mutable<BTreeMap<Section, bool>>,
     pub status_options: Mutable<BTreeMap<EntryStatus, bool>>,
 
@@ -61,6 +65,7 @@ impl State {
             .collect();
 
         let item_kind_options: Vec<ItemKind> = db_interface::get_item_kind().await;
+        let languages: Vec<Language> = db_interface::get_languages().await;
 
         let mut item_kind_filter: HashMap<Option<Uuid>, bool> = item_kind_options
             .iter()
@@ -92,8 +97,13 @@ impl State {
         columns.insert("ID".to_string(), true);
         columns.insert("Section".to_string(), true);
         columns.insert("Item Kind".to_string(), true);
-        columns.insert("English".to_string(), true);
-        columns.insert("Hebrew".to_string(), false);
+        columns.insert("Key".to_string(), true);
+        for language in &languages {
+            columns.insert(
+                language.display_name.clone(),
+                language.code == FALLBACK_LANGUAGE,
+            );
+        }
         columns.insert("Status".to_string(), true);
         columns.insert("Zeplin reference".to_string(), true);
         columns.insert("Comments".to_string(), true);
@@ -102,11 +112,14 @@ impl State {
         columns.insert("Mock".to_string(), true);
         columns.insert("Actions".to_string(), true);
 
-        let visible_columns = Rc::new(MutableVec::new_with_values(vec![
-            Column::ID,
-            Column::Section,
-            Column::ItemKind,
-            Column::English,
+        let (fallback_languages, other_languages): (Vec<_>, Vec<_>) = languages
+            .iter()
+            .cloned()
+            .partition(|language| language.code == FALLBACK_LANGUAGE);
+
+        let mut visible_columns = vec![Column::ID, Column::Section, Column::ItemKind, Column::Key];
+        visible_columns.extend(fallback_languages.into_iter().map(Column::Translation));
+        visible_columns.extend([
             Column::Status,
             Column::ZeplinReference,
             Column::Comments,
@@ -114,11 +127,15 @@ impl State {
             Column::Element,
             Column::Mock,
             Column::Actions,
-        ]));
-        let hidden_columns = Rc::new(MutableVec::new_with_values(vec![
-            Column::Hebrew,
-            Column::Bundle,
-        ]));
+        ]);
+        let visible_columns = Rc::new(MutableVec::new_with_values(visible_columns));
+
+        let mut hidden_columns: Vec<Column> = other_languages
+            .into_iter()
+            .map(Column::Translation)
+            .collect();
+        hidden_columns.push(Column::Bundle);
+        let hidden_columns = Rc::new(MutableVec::new_with_values(hidden_columns));
 
         Self {
             bundles: Mutable::new(bundles),
@@ -129,6 +146,7 @@ impl State {
             saving_loader: Rc::new(AsyncLoader::new()),
 
             item_kind_options,
+            languages,
             item_kind_filter: Mutable::new(item_kind_filter),
             section_options: Mutable::new(section_options),
             status_options: Mutable::new(status_options),
@@ -286,8 +304,9 @@ pub struct DisplayableEntry {
     pub id: u32,
     pub section: Option<Section>,
     pub item_kind_id: Option<Uuid>,
-    pub english: String,
-    pub hebrew: String,
+    pub key: String,
+    /// Text by language code, where an empty text hasn't been translated.
+    pub translations: BTreeMap<String, String>,
     pub status: EntryStatus,
     pub zeplin_reference: Mutable<Option<Url>>,
     pub comments: String,
@@ -303,8 +322,8 @@ impl From<Entry> for DisplayableEntry {
             id: entry.id,
             section: entry.section.clone(),
             item_kind_id: entry.item_kind_id,
-            english: entry.english.clone().unwrap_or_default(),
-            hebrew: entry.hebrew.clone().unwrap_or_default(),
+            key: entry.key.clone().unwrap_or_default(),
+            translations: entry.translations.clone(),
             status: entry.status,
             zeplin_reference: {
                 let v = entry
@@ -327,8 +346,13 @@ impl From<DisplayableEntry> for Entry {
             id: displayable_entry.id,
             section: displayable_entry.section.clone(),
             item_kind_id: displayable_entry.item_kind_id,
-            english: Some(displayable_entry.english.clone()),
-            hebrew: Some(displayable_entry.hebrew.clone()),
+            key: Some(displayable_entry.key.clone()).filter(|key| !key.is_empty()),
+            translations: displayable_entry
+                .translations
+                .iter()
+                .filter(|(_, text)| !text.is_empty())
+                .map(|(language, text)| (language.clone(), text.clone()))
+                .collect(),
             status: displayable_entry.status,
             zeplin_reference: {
                 displayable_entry
@@ -352,8 +376,15 @@ impl From<DisplayableEntry> for UpdateEntryRequest {
             bundle_id: Some(entry.bundle_id),
             section: Some(entry.section.clone()),
             item_kind_id: entry.item_kind_id,
-            english: Some(entry.english.clone()),
-            hebrew: Some(entry.hebrew.clone()),
+            key: Some(Some(entry.key.clone()).filter(|key| !key.is_empty())),
+            translations: entry
+                .translations
+                .iter()
+                .map(|(language, text)| {
+                    let text = Some(text.clone()).filter(|text| !text.is_empty());
+                    (language.clone(), text)
+                })
+                .collect(),
             status: Some(entry.status),
             zeplin_reference: {
                 let v = entry
@@ -377,12 +408,13 @@ pub struct Sort {
     pub column: SortKind,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum SortKind {
     Section,
     ItemKind,
-    English,
-    Hebrew,
+    Key,
+    /// Sorts by the text in the language with this code.
+    Translation(String),
     Status,
     Comments,
 }
@@ -396,16 +428,14 @@ pub enum SortOrder {
     Desc,
 }
 
-#[derive(Clone, PartialEq, Display)]
+#[derive(Clone, PartialEq)]
 pub enum Column {
     ID,
     Section,
-    #[strum(serialize = "Item Kind")]
     ItemKind,
-    English,
-    Hebrew,
+    Key,
+    Translation(Language),
     Status,
-    #[strum(serialize = "Zeplin Reference")]
     ZeplinReference,
     Comments,
     App,
@@ -414,3 +444,23 @@ pub enum Column {
     Actions,
     Bundle,
 }
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ID => f.write_str("ID"),
+            Self::Section => f.write_str("Section"),
+            Self::ItemKind => f.write_str("Item Kind"),
+            Self::Key => f.write_str("Key"),
+            Self::Translation(language) => f.write_str(&language.display_name),
+            Self::Status => f.write_str("Status"),
+            Self::ZeplinReference => f.write_str("Zeplin Reference"),
+            Self::Comments => f.write_str("Comments"),
+            Self::App => f.write_str("App"),
+            Self::Element => f.write_str("Element"),
+            Self::Mock => f.write_str("Mock"),
+            Self::Actions => f.write_str("Actions"),
+            Self::Bundle => f.write_str("Bundle"),
+        }
+    }
+}
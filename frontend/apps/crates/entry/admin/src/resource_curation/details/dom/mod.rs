@@ -1,10 +1,12 @@
 use super::state::ResourceDetails;
+use components::pdf::preview::PdfPreview;
 use dominator::{clone, html, with_node, Dom};
 use futures_signals::signal::SignalExt;
-use shared::domain::resource::ResourceRating;
+use shared::domain::{additional_resource::ResourceContent, resource::ResourceRating};
 use std::rc::Rc;
 use utils::{
-    asset::ResourceContentExt, events, routes::AdminResourceCurationRoute, unwrap::UnwrapJiExt,
+    asset::ResourceContentExt, component::Component, events, routes::AdminResourceCurationRoute,
+    unwrap::UnwrapJiExt,
 };
 use web_sys::{HtmlInputElement, HtmlTextAreaElement};
 
@@ -148,6 +150,15 @@ impl ResourceDetails {
                     ])
                 }),
             ])
+            .child_signal(state.resource.resource_signal().map(|resource| {
+                match resource?.resource_content {
+                    ResourceContent::PdfId(pdf_id) => Some(html!("div", {
+                        .prop("slot", "preview")
+                        .child(PdfPreview::new(pdf_id).render())
+                    })),
+                    _ => None,
+                }
+            }))
             .child(html!("fa-button", {
                 .prop("slot", "block")
                 .style_signal("color", state.resource.blocked.signal().map(|blocked| {
@@ -19,6 +19,7 @@ use futures_signals::{
 use crate::playlist_curation::PlaylistCuration;
 use crate::{
     categories::dom::CategoriesPage,
+    content_flags::ContentFlags,
     export::Export,
     image_table::ImageTable,
     images::{
@@ -31,6 +32,7 @@ use crate::{
     schools::Schools,
     sidebar::Sidebar,
     users::Users,
+    web_media::WebMedia,
 };
 use crate::{course_curation::CourseCuration, featured_jigs::FeaturedJigs};
 use std::cell::RefCell;
@@ -125,6 +127,8 @@ impl Router {
                                                 AdminRoute::Schools(schools_route) => Some(state.with_child(route, Schools::new(schools_route).render())),
                                                 AdminRoute::Images => Some(state.with_child(route, ImageTable::new().render())),
                                                 AdminRoute::Export => Some(state.with_child(route, Export::new().render())),
+                                                AdminRoute::WebMedia => Some(state.with_child(route, WebMedia::new().render())),
+                                                AdminRoute::ContentFlags => Some(state.with_child(route, ContentFlags::new().render())),
                                             }
                                         }
                                     }
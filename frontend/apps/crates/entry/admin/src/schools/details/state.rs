@@ -103,6 +103,7 @@ impl From<EditableAdminSchool> for UpdateSchoolAccountRequest {
             profile_image: value.profile_image.into(),
             website: value.website.into(),
             organization_type: value.organization_type.into(),
+            ..Default::default()
         }
     }
 }
@@ -64,6 +64,8 @@ impl Sidebar {
                     SidebarItem::new(AdminRoute::Categories, profile, &curr_route),
                     SidebarItem::new(AdminRoute::Locale, profile, &curr_route),
                     SidebarItem::new(AdminRoute::Export, profile, &curr_route),
+                    SidebarItem::new(AdminRoute::WebMedia, profile, &curr_route),
+                    SidebarItem::new(AdminRoute::ContentFlags, profile, &curr_route),
                 ],
             })
             .to_signal_vec()
@@ -99,6 +101,8 @@ impl SidebarItem {
             AdminRoute::Schools(_) => "schools",
             AdminRoute::Images => "images",
             AdminRoute::Export => "export",
+            AdminRoute::WebMedia => "web-media",
+            AdminRoute::ContentFlags => "content-flags",
             AdminRoute::Landing => "",
         };
 
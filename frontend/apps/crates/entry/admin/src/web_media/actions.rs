@@ -0,0 +1,84 @@
+use std::rc::Rc;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use dominator::clone;
+use shared::{
+    api::endpoints,
+    domain::media::{MediaIdDeletePath, MediaListPath, WebMediaListQuery},
+};
+use utils::prelude::ApiEndpointExt;
+
+use super::WebMedia;
+
+impl WebMedia {
+    pub fn load_data(self: &Rc<Self>) {
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            state.load_media().await;
+        }));
+    }
+
+    async fn load_media(self: &Rc<Self>) {
+        let query = WebMediaListQuery {
+            kind: self.kind.get(),
+            from_date: self.from_date.get(),
+            to_date: self.to_date.get(),
+            created_by: self.created_by.get(),
+            page: self.active_page.get().into(),
+            ..Default::default()
+        };
+
+        match endpoints::media::List::api_with_auth(MediaListPath(), Some(query)).await {
+            Ok(res) => {
+                self.media
+                    .lock_mut()
+                    .replace_cloned(res.media.into_iter().map(Rc::new).collect());
+                self.total_pages.set(Some(res.pages.into()));
+                self.total_count.set(Some(res.total_count.into()));
+            }
+            Err(_) => log::error!("Couldn't load the web media library"),
+        }
+    }
+
+    /// Reloads the first page, after a filter has changed.
+    pub fn apply_filters(self: &Rc<Self>) {
+        self.active_page.set(0);
+        self.load_data();
+    }
+
+    pub fn go_to_page(self: &Rc<Self>, page: usize) {
+        self.active_page.set(page);
+        self.load_data();
+    }
+
+    /// Filters by media added on or after the date of a date input, or clears it when empty.
+    pub fn set_from_date(self: &Rc<Self>, value: &str) {
+        self.from_date.set(parse_date(value));
+        self.apply_filters();
+    }
+
+    /// Filters by media added on or before the date of a date input, or clears it when empty.
+    pub fn set_to_date(self: &Rc<Self>, value: &str) {
+        self.to_date
+            .set(parse_date(value).map(|date| date + Duration::days(1)));
+        self.apply_filters();
+    }
+
+    /// Deletes the media along with its files and urls, and reloads the current page.
+    pub fn purge(self: &Rc<Self>, id: uuid::Uuid) {
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            match endpoints::media::DeleteId::api_with_auth(MediaIdDeletePath(id), None).await {
+                Ok(_) => state.load_media().await,
+                Err(_) => log::error!("Couldn't delete web media {id}"),
+            }
+        }));
+    }
+}
+
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|date| date.and_utc())
+}
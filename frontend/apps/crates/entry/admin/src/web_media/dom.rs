@@ -0,0 +1,278 @@
+use super::WebMedia;
+use dominator::{clone, html, with_node, Dom, DomBuilder};
+use futures_signals::{
+    map_ref,
+    signal::{Signal, SignalExt},
+    signal_vec::SignalVecExt,
+};
+use shared::{
+    domain::{
+        image::ImageFileKind,
+        media::{WebMediaKind, WebMediaMetadataResponse},
+        user::UserId,
+    },
+    media::{AnimationKind, MediaKind, MediaLibrary},
+};
+use std::rc::Rc;
+use strum::IntoEnumIterator;
+use utils::{
+    component::Component,
+    events,
+    routes::{AdminRoute, AdminUsersRoute, Route},
+};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, ShadowRoot};
+
+impl Component<WebMedia> for Rc<WebMedia> {
+    fn styles() -> &'static str {
+        include_str!("./styles.css")
+    }
+
+    fn dom(&self, dom: DomBuilder<ShadowRoot>) -> DomBuilder<ShadowRoot> {
+        let state = self;
+
+        state.load_data();
+
+        dom.child(html!("admin-table-web-media", {
+            .child(html!("input-select", {
+                .prop("slot", "controls")
+                .prop("label", "Kind")
+                .prop("multiple", false)
+                .prop_signal("value", state.kind.signal().map(|kind| {
+                    kind.map(|kind| kind.to_string()).unwrap_or_else(|| "All".to_string())
+                }))
+                .child(html!("input-select-option", {
+                    .text("All")
+                    .prop_signal("selected", state.kind.signal().map(|kind| kind.is_none()))
+                    .event(clone!(state => move |_: events::CustomSelectedChange| {
+                        state.kind.set(None);
+                        state.apply_filters();
+                    }))
+                }))
+                .children(WebMediaKind::iter().map(|kind| {
+                    html!("input-select-option", {
+                        .text(&kind.to_string())
+                        .prop_signal("selected", state.kind.signal().map(move |current| current == Some(kind)))
+                        .event(clone!(state => move |_: events::CustomSelectedChange| {
+                            state.kind.set(Some(kind));
+                            state.apply_filters();
+                        }))
+                    })
+                }))
+            }))
+            .child(html!("input-wrapper", {
+                .prop("slot", "controls")
+                .prop("label", "From date")
+                .child(html!("input" => HtmlInputElement, {
+                    .prop("type", "date")
+                    .with_node!(input => {
+                        .event(clone!(state => move |_: events::Change| {
+                            state.set_from_date(&input.value());
+                        }))
+                    })
+                }))
+            }))
+            .child(html!("input-wrapper", {
+                .prop("slot", "controls")
+                .prop("label", "To date")
+                .child(html!("input" => HtmlInputElement, {
+                    .prop("type", "date")
+                    .with_node!(input => {
+                        .event(clone!(state => move |_: events::Change| {
+                            state.set_to_date(&input.value());
+                        }))
+                    })
+                }))
+            }))
+            .child(html!("input-wrapper", {
+                .prop("slot", "controls")
+                .prop("label", "Added by (user ID)")
+                .child(html!("input" => HtmlInputElement, {
+                    .prop_signal("value", state.created_by.signal().map(|created_by| {
+                        created_by.map(|id| id.to_string()).unwrap_or_default()
+                    }))
+                    .with_node!(input => {
+                        .event(clone!(state => move |_: events::Change| {
+                            let created_by = input.value().trim().parse::<UserId>().ok();
+                            state.created_by.set(created_by);
+                            state.apply_filters();
+                        }))
+                    })
+                }))
+            }))
+            .child(html!("span", {
+                .prop("slot", "controls")
+                .text_signal(state.total_count.signal().map(|total_count| {
+                    total_count.map(|total_count| format!("{total_count} found")).unwrap_or_default()
+                }))
+            }))
+            .child(state.render_pagination())
+            .children_signal_vec(state.media.signal_vec_cloned().map(clone!(state => move |media| {
+                state.render_media(&media)
+            })))
+        }))
+    }
+}
+
+impl WebMedia {
+    fn render_pagination(self: &Rc<Self>) -> Dom {
+        let state = self;
+        html!("table-pagination-jig", {
+            .prop("slot", "controls")
+            .child(html!("fa-button", {
+                .prop("slot", "back")
+                .prop("title", "Previous")
+                .prop("icon", "fa-solid fa-chevron-left")
+                .prop_signal("disabled", state.active_page.signal().map(|active_page| {
+                    active_page == 0
+                }))
+                .event(clone!(state => move |_: events::Click| {
+                    let active_page = state.active_page.get();
+                    state.go_to_page(active_page - 1);
+                }))
+            }))
+            .child(html!("fa-button", {
+                .prop("slot", "next")
+                .prop("title", "Next")
+                .prop("icon", "fa-solid fa-chevron-right")
+                .prop_signal("disabled", state.is_last_page_signal())
+                .event(clone!(state => move |_: events::Click| {
+                    let active_page = state.active_page.get();
+                    state.go_to_page(active_page + 1);
+                }))
+            }))
+            .child_signal(state.total_pages.signal().map(clone!(state => move |total_pages| {
+                total_pages.map(|total_pages| {
+                    html!("input-select", {
+                        .style("width", "150px")
+                        .prop_signal("value", state.active_page.signal().map(|active_page| {
+                            format!("{}", active_page + 1)
+                        }))
+                        .children((0..total_pages).map(|page| {
+                            html!("input-select-option", {
+                                .text(&format!("{}", page + 1))
+                                .prop_signal("selected", state.active_page.signal().map(move |active_page| {
+                                    page == active_page
+                                }))
+                                .event(clone!(state => move |evt: events::CustomSelectedChange| {
+                                    if evt.selected() {
+                                        state.go_to_page(page);
+                                    }
+                                }))
+                            })
+                        }))
+                    })
+                })
+            })))
+        })
+    }
+
+    fn is_last_page_signal(&self) -> impl Signal<Item = bool> {
+        map_ref! {
+            let total_pages = self.total_pages.signal(),
+            let active_page = self.active_page.signal() => {
+                match total_pages {
+                    None => true,
+                    // active_page is 0 indexed
+                    Some(total_pages) => *active_page + 1 >= *total_pages,
+                }
+            }
+        }
+    }
+
+    fn render_media(self: &Rc<Self>, media: &WebMediaMetadataResponse) -> Dom {
+        let state = self;
+        let id = media.id;
+
+        html!("admin-table-line", {
+            .children(&mut [
+                html!("div", {
+                    .class("img-wrapper")
+                    .apply(|dom| match image_file_kind(media.kind) {
+                        Some(kind) => dom.child(html!("img-ji", {
+                            .prop("size", "thumb")
+                            .prop("lib", MediaLibrary::Web.to_str())
+                            .prop("id", id.to_string())
+                            .prop("kind", kind.to_str())
+                        })),
+                        None => dom,
+                    })
+                }),
+                html!("span", {
+                    .text(kind_label(media.kind))
+                }),
+                html!("span", {
+                    .children(media.urls.iter().map(|url| {
+                        html!("a", {
+                            .prop("href", url.as_str())
+                            .prop("target", "_blank")
+                            .text(url.as_str())
+                        })
+                    }))
+                }),
+                html!("span", {
+                    .text(&media.created_at.format("%Y-%m-%d %H:%M").to_string())
+                }),
+                html!("span", {
+                    .apply(|dom| match media.created_by {
+                        Some(user_id) => dom
+                            .child(html!("a", {
+                                .prop("href", Route::Admin(AdminRoute::Users(AdminUsersRoute::User(user_id))).to_string())
+                                .text(&user_id.to_string())
+                            }))
+                            .child(html!("button-rect", {
+                                .prop("kind", "text")
+                                .prop("color", "blue")
+                                .text("Filter")
+                                .event(clone!(state => move |_: events::Click| {
+                                    state.created_by.set(Some(user_id));
+                                    state.apply_filters();
+                                }))
+                            })),
+                        None => dom.text("-"),
+                    })
+                }),
+                html!("span", {
+                    .child(html!("button-rect", {
+                        .prop("kind", "text")
+                        .prop("color", "red")
+                        .text("Purge")
+                        .event(clone!(state => move |_: events::Click| {
+                            spawn_local(clone!(state => async move {
+                                let confirmed = components::confirm::Confirm {
+                                    title: "Purge media".to_string(),
+                                    message: "This deletes the media and its files for every URL it was added from. Any content still using it will show a broken image.".to_string(),
+                                    confirm_text: "Purge".to_string(),
+                                    cancel_text: "Cancel".to_string(),
+                                }.confirm().await;
+
+                                if confirmed {
+                                    state.purge(id);
+                                }
+                            }));
+                        }))
+                    }))
+                }),
+            ])
+        })
+    }
+}
+
+fn image_file_kind(kind: MediaKind) -> Option<ImageFileKind> {
+    match kind {
+        MediaKind::Image => Some(ImageFileKind::Png),
+        MediaKind::SvgImage => Some(ImageFileKind::Svg),
+        MediaKind::Animation(AnimationKind::Gif) => Some(ImageFileKind::Gif),
+        _ => None,
+    }
+}
+
+fn kind_label(kind: MediaKind) -> &'static str {
+    match kind {
+        MediaKind::Image => "Image",
+        MediaKind::SvgImage => "SVG image",
+        MediaKind::Animation(_) => "Animation",
+        MediaKind::Audio(_) => "Audio",
+        MediaKind::Pdf => "PDF",
+    }
+}
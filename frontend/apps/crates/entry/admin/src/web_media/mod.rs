@@ -0,0 +1,5 @@
+mod actions;
+mod dom;
+mod state;
+
+pub use state::*;
@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+use dominator_helpers::futures::AsyncLoader;
+use futures_signals::{signal::Mutable, signal_vec::MutableVec};
+use shared::domain::{
+    media::{WebMediaKind, WebMediaMetadataResponse},
+    user::UserId,
+};
+use std::rc::Rc;
+
+pub struct WebMedia {
+    pub loader: AsyncLoader,
+    pub media: MutableVec<Rc<WebMediaMetadataResponse>>,
+    pub kind: Mutable<Option<WebMediaKind>>,
+    pub from_date: Mutable<Option<DateTime<Utc>>>,
+    pub to_date: Mutable<Option<DateTime<Utc>>>,
+    pub created_by: Mutable<Option<UserId>>,
+    pub active_page: Mutable<usize>,
+    pub total_pages: Mutable<Option<usize>>,
+    pub total_count: Mutable<Option<usize>>,
+}
+
+impl WebMedia {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self {
+            loader: AsyncLoader::new(),
+            media: MutableVec::new(),
+            kind: Mutable::new(None),
+            from_date: Mutable::new(None),
+            to_date: Mutable::new(None),
+            created_by: Mutable::new(None),
+            active_page: Mutable::new(0),
+            total_pages: Mutable::new(None),
+            total_count: Mutable::new(None),
+        })
+    }
+}
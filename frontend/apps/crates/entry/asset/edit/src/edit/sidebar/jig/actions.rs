@@ -85,6 +85,7 @@ pub fn get_player_options(settings_state: Rc<SettingsState>) -> JigPlayerOptions
         play_token: None,
         quota: false,
         players_name: None,
+        hide_score: false,
     }
 }
 
@@ -34,6 +34,7 @@ impl JigSettings {
             direction: self.jig.direction.get(),
             scoring: self.jig.scoring.get(),
             drag_assist: self.jig.drag_assist.get(),
+            ..Default::default()
         }
     }
     fn get_audio_effects(&self) -> AudioEffects {
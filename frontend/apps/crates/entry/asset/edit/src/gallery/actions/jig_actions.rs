@@ -1,11 +1,13 @@
 use super::super::state::Gallery;
+use gloo::timers::future::TimeoutFuture;
 use shared::{
     api::endpoints::{self},
     domain::{
         asset::{Asset, DraftOrLive, UserOrMe},
         jig::{
             JigBrowsePath, JigBrowseQuery, JigClonePath, JigDeletePath, JigGetDraftPath, JigId,
-            JigSearchPath, JigSearchQuery,
+            JigSearchPath, JigSearchQuery, JigTranslatePath, JigTranslateRequest,
+            JigTranslationPath, JigTranslationStatus, UntranslatedText,
         },
     },
     error::IntoAnyhow,
@@ -13,6 +15,9 @@ use shared::{
 use std::rc::Rc;
 use utils::prelude::*;
 
+/// Milliseconds between checking whether a translated copy has been made.
+const TRANSLATION_POLL_INTERVAL: u32 = 2_000;
+
 pub async fn load_jigs(
     state: &Rc<Gallery>,
     is_published: Option<bool>,
@@ -61,6 +66,46 @@ pub async fn copy_jig(jig_id: JigId) -> Result<Asset, ()> {
     }
 }
 
+pub async fn translate_jig(
+    jig_id: JigId,
+    language: String,
+) -> anyhow::Result<(Asset, Vec<UntranslatedText>)> {
+    let req = JigTranslateRequest { language };
+    let id = endpoints::jig::Translate::api_with_auth(JigTranslatePath(jig_id), Some(req))
+        .await
+        .into_anyhow()?
+        .id;
+
+    // the copy is made in the background
+    let translation = loop {
+        TimeoutFuture::new(TRANSLATION_POLL_INTERVAL).await;
+
+        let translation =
+            endpoints::jig::GetTranslation::api_with_auth(JigTranslationPath(id), None)
+                .await
+                .into_anyhow()?;
+
+        if translation.status != JigTranslationStatus::Pending {
+            break translation;
+        }
+    };
+
+    let translated_jig_id = match translation.translated_jig_id {
+        Some(translated_jig_id) if translation.status == JigTranslationStatus::Finished => {
+            translated_jig_id
+        }
+        _ => anyhow::bail!("couldn't translate jig {}", jig_id.0),
+    };
+
+    let asset: Asset =
+        endpoints::jig::GetDraft::api_with_auth(JigGetDraftPath(translated_jig_id), None)
+            .await
+            .into_anyhow()?
+            .into();
+
+    Ok((asset, translation.untranslated))
+}
+
 pub async fn delete_jig(jig_id: JigId) -> anyhow::Result<()> {
     endpoints::jig::Delete::api_with_auth(JigDeletePath(jig_id), None)
         .await
@@ -5,17 +5,22 @@ use shared::{
     api::endpoints,
     domain::{
         asset::{AssetId, AssetType},
+        jig::JigId,
         meta::GetMetadataPath,
     },
 };
 use std::rc::Rc;
-use utils::{asset, paywall, prelude::*};
+use utils::{asset, paywall, prelude::*, toasts};
 
 mod course_actions;
 mod jig_actions;
 mod playlist_actions;
 mod resource_actions;
 
+const STR_TRANSLATE_ERROR: &str = "Couldn't translate the JIG";
+const STR_UNTRANSLATED: &str =
+    "Some of the text couldn't be translated and was copied as it was. Please review it:";
+
 impl Gallery {
     pub fn load_data(self: &Rc<Self>) {
         let state = self;
@@ -169,6 +174,27 @@ impl Gallery {
         }));
     }
 
+    pub fn translate_jig(self: &Rc<Self>, jig_id: JigId, language: &'static str) {
+        let state = Rc::clone(self);
+        state.loader.load(clone!(state => async move {
+            let (asset, untranslated) = match jig_actions::translate_jig(jig_id, language.to_owned()).await {
+                Ok(translated) => translated,
+                Err(_) => {
+                    toasts::error(STR_TRANSLATE_ERROR);
+                    return;
+                }
+            };
+            state.assets.lock_mut().insert_cloned(0, asset);
+
+            if !untranslated.is_empty() {
+                let texts: Vec<_> = untranslated.into_iter().map(|it| it.text).collect();
+                let _ = web_sys::window()
+                    .unwrap_ji()
+                    .alert_with_message(&format!("{}\n\n{}", STR_UNTRANSLATED, texts.join("\n")));
+            }
+        }));
+    }
+
     pub fn delete_asset(self: &Rc<Self>, asset_id: AssetId) {
         let state = self;
         state.loader.load(clone!(state => async move {
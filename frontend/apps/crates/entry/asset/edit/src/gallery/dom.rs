@@ -18,6 +18,7 @@ use strum::IntoEnumIterator;
 use utils::asset::{AssetPlayerOptions, ResourceContentExt};
 use utils::init::analytics;
 use utils::init::user::with_user;
+use utils::languages::{Language, JIG_LANGUAGES};
 use utils::paywall;
 use utils::prelude::*;
 
@@ -26,6 +27,7 @@ const STR_PLAY: &str = "Play";
 const STR_VIEW: &str = "View";
 // const STR_SHARE: &str = "Share";
 const STR_DUPLICATE: &str = "Duplicate";
+const STR_TRANSLATE: &str = "Translate a copy";
 const STR_DELETE: &str = "Delete";
 const STR_SEARCH: &str = "Search";
 const STR_SHOW_ALL: &str = "Show all";
@@ -40,6 +42,13 @@ const STR_DELETE_CONTENT_WARNING_3: &str = " and cannot be undone.";
 const STR_DELETE_CONFIRM: &str = "Yes, delete";
 const STR_DELETE_CANCEL: &str = "Don't delete";
 
+const STR_TRANSLATE_TITLE: &str = "Translate a copy";
+const STR_TRANSLATE_CONTENT: &str =
+    "A copy of this JIG will be made, with its text translated into:";
+const STR_TRANSLATE_LANGUAGE: &str = "Language";
+const STR_TRANSLATE_CONFIRM: &str = "Translate";
+const STR_TRANSLATE_CANCEL: &str = "Cancel";
+
 const STR_LOAD_MORE: &str = "See more";
 
 impl Gallery {
@@ -104,6 +113,48 @@ impl Gallery {
                     })
                 })
             })))
+            .child_signal(state.confirm_translate.signal().map(clone!(state => move |confirm_translate| {
+                confirm_translate.map(|jig_id| {
+                    html!("modal-confirm", {
+                        .prop("title", STR_TRANSLATE_TITLE)
+                        .prop("cancel_text", STR_TRANSLATE_CANCEL)
+                        .prop("confirm_text", STR_TRANSLATE_CONFIRM)
+                        .child(html!("div", {
+                            .prop("slot", "content")
+                            .child(html!("p", {
+                                .text(STR_TRANSLATE_CONTENT)
+                            }))
+                            .child(html!("input-select", {
+                                .prop("label", STR_TRANSLATE_LANGUAGE)
+                                .prop_signal("value", state.translate_language.signal().map(|language| {
+                                    language.map(Language::code_to_display_name).unwrap_or_default()
+                                }))
+                                .children(JIG_LANGUAGES.iter().map(|language| {
+                                    let code = language.code();
+                                    html!("input-select-option", {
+                                        .text(language.display_name())
+                                        .prop_signal("selected", state.translate_language.signal().map(move |selected| {
+                                            selected == Some(code)
+                                        }))
+                                        .event(clone!(state => move |evt: events::CustomSelectedChange| {
+                                            if evt.selected() {
+                                                state.translate_language.set(Some(code));
+                                            }
+                                        }))
+                                    })
+                                }))
+                            }))
+                        }))
+                        .event(clone!(state => move |_evt: events::CustomCancel| state.confirm_translate.set_neq(None)))
+                        .event(clone!(state => move |_evt: events::CustomConfirm| {
+                            state.confirm_translate.set_neq(None);
+                            if let Some(language) = state.translate_language.get() {
+                                state.translate_jig(jig_id, language);
+                            }
+                        }))
+                    })
+                })
+            })))
             .child(
                 html!("asset-gallery", {
                     .prop("kind", state.asset_type.as_str())
@@ -252,6 +303,23 @@ impl Gallery {
                                                         state.copy_asset(asset_id);
                                                     }))
                                                 }),
+                                            ])
+                                            .apply(clone!(state => move |dom| {
+                                                match asset_id {
+                                                    AssetId::JigId(jig_id) => {
+                                                        dom.child(html!("menu-line", {
+                                                            .prop("icon", "text")
+                                                            .text(STR_TRANSLATE)
+                                                            .event(clone!(state => move |_: events::Click| {
+                                                                state.translate_language.set(None);
+                                                                state.confirm_translate.set(Some(jig_id));
+                                                            }))
+                                                        }))
+                                                    },
+                                                    _ => dom,
+                                                }
+                                            }))
+                                            .children(&mut [
                                                 html!("menu-line", {
                                                     .prop("icon", "delete")
                                                     .text(STR_DELETE)
@@ -295,11 +363,9 @@ impl Gallery {
                         None,
                         None,
                         AssetPlayerOptions::default_from_id_draft(&asset_id),
-                        PreviewPopupCallbacks {
-                            close: Box::new(clone!(state => move|| {
-                                state.play_asset.set(None);
-                            }))
-                        },
+                        PreviewPopupCallbacks::new(clone!(state => move|| {
+                            state.play_asset.set(None);
+                        })),
                     ).render(None)
                 })
             })))
@@ -4,6 +4,7 @@ use dominator_helpers::futures::AsyncLoader;
 use futures_signals::{signal::Mutable, signal_vec::MutableVec};
 use shared::domain::{
     asset::{Asset, AssetId, AssetType},
+    jig::JigId,
     meta::AgeRange,
 };
 use strum_macros::{Display, EnumIter, EnumString};
@@ -42,6 +43,9 @@ pub struct Gallery {
     pub age_ranges: Mutable<Vec<AgeRange>>,
     pub confirm_delete: Mutable<Option<AssetId>>,
     pub play_asset: Mutable<Option<AssetId>>,
+    /// JIG to make a translated copy of, and the language picked for it
+    pub confirm_translate: Mutable<Option<JigId>>,
+    pub translate_language: Mutable<Option<&'static str>>,
 }
 
 impl Gallery {
@@ -56,6 +60,8 @@ impl Gallery {
             age_ranges: Mutable::new(vec![]),
             confirm_delete: Mutable::new(None),
             play_asset: Mutable::new(None),
+            confirm_translate: Mutable::new(None),
+            translate_language: Mutable::new(None),
         })
     }
 
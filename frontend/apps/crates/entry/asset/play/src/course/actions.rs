@@ -1,13 +1,25 @@
 use dominator::clone;
+use futures::Future;
+use futures_signals::signal::SignalExt;
 use shared::{
     api::endpoints::course,
     domain::{
-        asset::DraftOrLive,
-        course::{CourseGetDraftPath, CourseGetLivePath},
+        asset::{DraftOrLive, ProgressStatus},
+        course::{
+            progress::{
+                CourseProgressPath, CourseUnitProgressPath, CourseUnitProgressUpdateRequest,
+            },
+            unit::CourseUnitValue,
+            CourseGetDraftPath, CourseGetLivePath,
+        },
     },
 };
 use std::rc::Rc;
-use utils::{paywall, prelude::ApiEndpointExt};
+use utils::{
+    bail_on_err, paywall,
+    prelude::{is_user_set, ApiEndpointExt},
+};
+use wasm_bindgen_futures::spawn_local;
 
 use super::state::CoursePlayer;
 
@@ -45,14 +57,14 @@ impl CoursePlayer {
                         return;
                     }
 
-                    if let Some(start_unit_id) = state.start_unit_id {
-                        if let Some((index, _)) = course.course_data.units.iter().enumerate().find(|unit| {
-                            unit.1.id == start_unit_id
-                        }) {
-                            state.active_unit.set_neq(Some(index));
-                        };
-                    }
+                    let start_unit = state.start_unit_id.and_then(|start_unit_id| {
+                        course.course_data.units.iter().position(|unit| unit.id == start_unit_id)
+                    });
                     state.course.set(Some(Rc::new(course)));
+                    if let Some(index) = start_unit {
+                        state.active_unit.set_neq(Some(index));
+                    }
+                    state.load_progress().await;
                 },
                 Err(_) => {
                     todo!();
@@ -60,4 +72,106 @@ impl CoursePlayer {
             }
         }));
     }
+
+    pub async fn load_progress(self: &Rc<Self>) {
+        if !is_user_set() {
+            return;
+        }
+
+        let progress =
+            course::progress::Get::api_with_auth(CourseProgressPath(self.course_id), None).await;
+        let progress = bail_on_err!(progress);
+
+        if let Some(course) = &*self.course.lock_ref() {
+            let mut played_units = self.played_units.lock_mut();
+            for (index, unit) in course.course_data.units.iter().enumerate() {
+                let completed = progress
+                    .units
+                    .iter()
+                    .any(|progress| progress.unit_id == unit.id && progress.status.is_completed());
+                if completed {
+                    played_units.insert(index);
+                }
+            }
+        }
+
+        self.progress.set(Some(progress));
+    }
+
+    /// Index of the unit to continue from, falls back to the first unit.
+    pub fn continue_unit_index(&self) -> usize {
+        let continue_unit_id = self
+            .progress
+            .lock_ref()
+            .as_ref()
+            .and_then(|progress| progress.continue_unit_id);
+
+        match (continue_unit_id, &*self.course.lock_ref()) {
+            (Some(unit_id), Some(course)) => course
+                .course_data
+                .units
+                .iter()
+                .position(|unit| unit.id == unit_id)
+                .unwrap_or(0),
+            _ => 0,
+        }
+    }
+
+    /// Marks each unit which is opened as started, or as completed if there's nothing to play
+    /// through. Audio and video units are completed when they end, see [`Self::unit_ended`].
+    pub fn track_progress(self: &Rc<Self>) -> impl Future<Output = ()> {
+        let state = Rc::clone(self);
+        self.active_unit.signal().for_each(move |active_unit| {
+            if let Some(index) = active_unit {
+                let is_played = match &*state.course.lock_ref() {
+                    Some(course) => course.course_data.units.get(index).map_or(false, |unit| {
+                        matches!(
+                            unit.value,
+                            CourseUnitValue::AudioId(_) | CourseUnitValue::Video(_)
+                        )
+                    }),
+                    None => false,
+                };
+
+                let status = if is_played {
+                    ProgressStatus::Started
+                } else {
+                    ProgressStatus::Completed
+                };
+                state.update_unit_progress(index, status);
+            }
+            async {}
+        })
+    }
+
+    /// Marks an audio or video unit as completed once it was played all the way through.
+    pub fn unit_ended(self: &Rc<Self>, index: usize) {
+        self.update_unit_progress(index, ProgressStatus::Completed);
+    }
+
+    fn update_unit_progress(self: &Rc<Self>, index: usize, status: ProgressStatus) {
+        if !is_user_set() || self.player_options.draft_or_live.is_draft() {
+            return;
+        }
+
+        let unit_id = match &*self.course.lock_ref() {
+            Some(course) => match course.course_data.units.get(index) {
+                Some(unit) => unit.id,
+                None => return,
+            },
+            None => return,
+        };
+
+        let state = self;
+        spawn_local(clone!(state => async move {
+            let req = CourseUnitProgressUpdateRequest { status };
+            let res = course::progress::UpdateUnit::api_with_auth(
+                CourseUnitProgressPath(state.course_id, unit_id),
+                Some(req),
+            )
+            .await;
+            bail_on_err!(res);
+            state.load_progress().await;
+        }));
+    }
 }
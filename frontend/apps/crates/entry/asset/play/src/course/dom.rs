@@ -14,5 +14,6 @@ impl Component<CoursePlayer> for Rc<CoursePlayer> {
     fn dom(&self, dom: DomBuilder<ShadowRoot>) -> DomBuilder<ShadowRoot> {
         self.load_data();
         dom.child(PlayerMain::new(self).render())
+            .future(self.track_progress())
     }
 }
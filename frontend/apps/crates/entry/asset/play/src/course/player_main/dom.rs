@@ -67,6 +67,9 @@ impl PlayerMain {
             .prop("itemsCount", course.course_data.units.len())
             .prop("itemType", "Units")
             .prop("hasAdditionalResources", !course.course_data.additional_resources.is_empty())
+            .prop_signal("progress", state.player_state.progress.signal_ref(|progress| {
+                progress.as_ref().map(|progress| progress.percent_complete as i32).unwrap_or(-1)
+            }))
             .child(
                 ModuleThumbnail::new_hight_res(
                     course.id.into(),
@@ -105,8 +108,9 @@ impl PlayerMain {
                 }))
                 .event(clone!(state => move |_: events::Click| {
                     state.render_popup.set(false);
-                    state.player_state.active_unit.set(Some(0));
-                    state.player_state.played_units.lock_mut().insert(0);
+                    let index = state.player_state.continue_unit_index();
+                    state.player_state.active_unit.set(Some(index));
+                    state.player_state.played_units.lock_mut().insert(index);
                 }))
             }))
             .child(ShareAsset::new(course.as_ref().clone().into()).render(
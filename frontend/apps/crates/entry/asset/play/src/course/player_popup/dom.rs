@@ -295,9 +295,16 @@ impl PlayerPopup {
     }
 
     fn render_active_unit(self: &Rc<Self>, unit: CourseUnit) -> Dom {
+        let state = self;
+        let index = state.player_state.active_unit.get();
+        let on_ended = clone!(state => move || {
+            if let Some(index) = index {
+                state.player_state.unit_ended(index);
+            }
+        });
         html!("div", {
             .class("unit-play")
-            .child(UnitValueView::new(Some(unit.value)).render())
+            .child(UnitValueView::new_with_on_ended(Some(unit.value), on_ended).render())
         })
     }
 }
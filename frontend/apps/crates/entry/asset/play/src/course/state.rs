@@ -2,7 +2,9 @@ use std::{collections::HashSet, rc::Rc};
 
 use awsm_web::loaders::helpers::AsyncLoader;
 use futures_signals::signal::Mutable;
-use shared::domain::course::{unit::CourseUnitId, CourseId, CourseResponse};
+use shared::domain::course::{
+    progress::CourseProgressResponse, unit::CourseUnitId, CourseId, CourseResponse,
+};
 use utils::asset::CoursePlayerOptions;
 
 pub struct CoursePlayer {
@@ -15,6 +17,8 @@ pub struct CoursePlayer {
     pub current_page: Mutable<Option<usize>>, // TODO: what is this??
     pub start_unit_id: Option<CourseUnitId>,
     pub player_options: CoursePlayerOptions,
+    /// Only loaded for logged in users
+    pub progress: Mutable<Option<CourseProgressResponse>>,
 }
 
 impl CoursePlayer {
@@ -32,6 +36,7 @@ impl CoursePlayer {
             start_unit_id: unit_id,
             current_page: Mutable::new(None),
             player_options,
+            progress: Mutable::new(None),
         })
     }
 }
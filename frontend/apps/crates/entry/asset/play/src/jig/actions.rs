@@ -18,8 +18,11 @@ use shared::{
         asset::DraftOrLive,
         category::GetCategoryPath,
         jig::{
-            codes::instance::{
-                PlayerSessionInstanceCompletePath, PlayerSessionInstanceCompleteRequest,
+            codes::{
+                instance::{
+                    PlayerSessionInstanceCompletePath, PlayerSessionInstanceCompleteRequest,
+                },
+                JigPlaySessionModuleGetPointsEarned,
             },
             player::{ModuleConfig, PlayerNavigationHandler, Seconds},
             AudioBackground, JigGetDraftPath, JigGetLivePath, JigLikedPath, JigPlayPath,
@@ -38,6 +41,7 @@ use utils::{
         AssetPlayerToPlayerPopup, IframeAction, IframeMessageExt, JigToModulePlayerMessage,
         ModuleToJigPlayerMessage,
     },
+    js_wrappers::is_iframe,
     keyboard::{Key, KeyEvent},
     paywall,
     prelude::{is_user_set, ApiEndpointExt, SETTINGS},
@@ -104,6 +108,14 @@ impl JigPlayer {
         let state = self;
         state.done.set(true);
         algolia_events::finished_jig(self.jig_id);
+        if is_iframe() {
+            let points_earned = {
+                let session_info = state.session_info.borrow();
+                (!session_info.modules.is_empty()).then(|| session_info.get_points_earned())
+            };
+            let _ = IframeAction::new(AssetPlayerToPlayerPopup::JigFinished(points_earned))
+                .try_post_message_to_parent();
+        }
         if let Some(token) = state.play_token.clone() {
             spawn_local(clone!(state => async move {
                 let req = PlayerSessionInstanceCompleteRequest {
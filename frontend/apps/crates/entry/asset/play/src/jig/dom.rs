@@ -162,7 +162,7 @@ impl JigPlayer {
                 }
             })
             .child_signal(state.scoring.signal().map(clone!(state => move |scoring| {
-                scoring.then(|| {
+                (scoring && !state.hide_score).then(|| {
                     html!("div", {
                         .prop("slot", "indicators")
                         .style("display", "contents")
@@ -572,7 +572,7 @@ impl JigPlayer {
                         .prop("autoClose", false)
                         .child(html!("jig-play-done-popup", {
                             .apply(|mut dom| {
-                                if state.scoring.get() {
+                                if state.scoring.get() && !state.hide_score {
                                     dom = dom.prop_signal("score", state.points.signal().map(|p| p * 100));
                                     dom = dom.prop_signal("percentage", map_ref! {
                                         let points = state.points.signal(),
@@ -66,6 +66,7 @@ pub struct JigPlayer {
     pub url_scoring: Option<bool>,
     pub scoring: Mutable<bool>,
     pub drag_assist: Mutable<bool>,
+    pub hide_score: bool,
 }
 
 impl JigPlayer {
@@ -121,6 +122,7 @@ impl JigPlayer {
             url_scoring: player_options.scoring,
             scoring: Mutable::new(player_options.scoring.unwrap_or_default()),
             drag_assist: Mutable::new(player_options.drag_assist.unwrap_or_default()),
+            hide_score: player_options.hide_score,
         })
     }
 }
@@ -6,19 +6,26 @@ use futures::join;
 use shared::{
     api::endpoints,
     domain::{
-        asset::DraftOrLive,
-        jig::{JigGetLivePath, JigId, JigResponse},
+        asset::{DraftOrLive, ProgressStatus},
+        jig::{codes::PointsEarned, JigGetLivePath, JigId, JigResponse},
         meta::GetMetadataPath,
-        playlist::{PlaylistGetDraftPath, PlaylistGetLivePath},
+        playlist::{
+            progress::{
+                PlaylistItemProgressPath, PlaylistItemProgressUpdateRequest, PlaylistProgressPath,
+            },
+            PlaylistGetDraftPath, PlaylistGetLivePath,
+        },
     },
 };
 use utils::{
+    bail_on_err,
     iframe::{AssetPlayerToPlayerPopup, IframeAction, IframeMessageExt},
     js_wrappers::is_iframe,
     paywall,
-    prelude::ApiEndpointExt,
+    prelude::{is_user_set, ApiEndpointExt},
     unwrap::UnwrapJiExt,
 };
+use wasm_bindgen_futures::spawn_local;
 
 use super::state::PlaylistPlayer;
 
@@ -29,6 +36,7 @@ impl PlaylistPlayer {
             join!(
                 state.load_playlist(),
                 state.load_resource_types(),
+                state.load_progress(),
             );
         }));
     }
@@ -83,6 +91,55 @@ impl PlaylistPlayer {
         };
     }
 
+    async fn load_progress(self: &Rc<Self>) {
+        if !is_user_set() {
+            return;
+        }
+
+        let progress = endpoints::playlist::progress::Get::api_with_auth(
+            PlaylistProgressPath(self.playlist_id),
+            None,
+        )
+        .await;
+        let progress = bail_on_err!(progress);
+
+        self.jigs_done.lock_mut().extend(
+            progress
+                .items
+                .iter()
+                .filter(|item| item.status.is_completed())
+                .map(|item| item.jig_id),
+        );
+        self.progress.set(Some(progress));
+    }
+
+    /// Records progress for logged in users and refreshes the playlist progress.
+    pub fn update_progress(
+        self: &Rc<Self>,
+        jig_id: JigId,
+        status: ProgressStatus,
+        points_earned: Option<PointsEarned>,
+    ) {
+        if !is_user_set() || self.player_options.draft_or_live.is_draft() {
+            return;
+        }
+
+        let state = self;
+        spawn_local(clone!(state => async move {
+            let req = PlaylistItemProgressUpdateRequest {
+                status,
+                points_earned,
+            };
+            let res = endpoints::playlist::progress::UpdateItem::api_with_auth(
+                PlaylistItemProgressPath(state.playlist_id, jig_id),
+                Some(req),
+            )
+            .await;
+            bail_on_err!(res);
+            state.load_progress().await;
+        }));
+    }
+
     async fn load_jigs(self: &Rc<Self>, jig_ids: Vec<JigId>) {
         let jigs = try_join_all(jig_ids.iter().map(|jig_id| self.load_jig(jig_id)))
             .await
@@ -99,12 +156,18 @@ impl PlaylistPlayer {
 
     pub fn play_jig(self: &Rc<Self>, jig_id: JigId) {
         self.active_jig.set(Some(jig_id));
+        self.update_progress(jig_id, ProgressStatus::Started, None);
         if is_iframe() {
             let _ = IframeAction::new(AssetPlayerToPlayerPopup::CloseButtonShown(false))
                 .try_post_message_to_parent();
         }
     }
 
+    pub fn finished_jig(self: &Rc<Self>, jig_id: JigId, points_earned: Option<PointsEarned>) {
+        self.jigs_done.lock_mut().insert(jig_id);
+        self.update_progress(jig_id, ProgressStatus::Completed, points_earned);
+    }
+
     pub fn done_playing_jig(self: &Rc<Self>) {
         self.active_jig.set(None);
         if is_iframe() {
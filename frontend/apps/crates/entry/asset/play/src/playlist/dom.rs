@@ -37,12 +37,15 @@ impl PlaylistPlayer {
                         quota: true,
                         ..Default::default()
                     });
+                    let jig_finished = clone!(state => move |points_earned| {
+                        state.finished_jig(jig_id, points_earned);
+                    });
                     PlayerPopup::new(
                         jig_id.into(),
                         None,
                         None,
                         options,
-                        PreviewPopupCallbacks::new(close)
+                        PreviewPopupCallbacks::new(close).with_jig_finished(jig_finished)
                     ).render(None)
                 })
             })))
@@ -60,6 +63,9 @@ impl PlaylistPlayer {
             .prop("itemsCount", playlist.playlist_data.items.len())
             .prop("itemType", "JIGs")
             .prop("hasAdditionalResources", !playlist.playlist_data.additional_resources.is_empty())
+            .prop_signal("progress", state.progress.signal_ref(|progress| {
+                progress.as_ref().map(|progress| progress.percent_complete as i32).unwrap_or(-1)
+            }))
             .child(
                 ModuleThumbnail::new_hight_res(
                     playlist.id.into(),
@@ -90,10 +96,15 @@ impl PlaylistPlayer {
                 .prop("slot", "play")
                 .prop("icon", "fa-solid fa-circle-play")
                 .event(clone!(state => move |_: events::Click| {
-                    let jigs = state.jigs.lock_mut();
-                    let jig_id = jigs.first().map(|jig| jig.id);
-                    state.active_jig.set(jig_id);
-
+                    let continue_jig_id = state
+                        .progress
+                        .lock_ref()
+                        .as_ref()
+                        .and_then(|progress| progress.continue_jig_id);
+                    let jig_id = continue_jig_id.or_else(|| state.jigs.lock_ref().first().map(|jig| jig.id));
+                    if let Some(jig_id) = jig_id {
+                        state.play_jig(jig_id);
+                    }
                 }))
             }))
             .child(ShareAsset::new(playlist.clone().into()).render(
@@ -5,7 +5,7 @@ use futures_signals::signal::Mutable;
 use shared::domain::{
     jig::{JigId, JigResponse},
     meta::ResourceType,
-    playlist::{PlaylistId, PlaylistResponse},
+    playlist::{progress::PlaylistProgressResponse, PlaylistId, PlaylistResponse},
 };
 use utils::asset::PlaylistPlayerOptions;
 
@@ -18,6 +18,8 @@ pub struct PlaylistPlayer {
     pub player_options: PlaylistPlayerOptions,
     pub active_jig: Mutable<Option<JigId>>,
     pub resource_types: Mutable<Vec<ResourceType>>,
+    /// Only loaded for logged in users
+    pub progress: Mutable<Option<PlaylistProgressResponse>>,
 }
 
 impl PlaylistPlayer {
@@ -31,6 +33,7 @@ impl PlaylistPlayer {
             player_options,
             active_jig: Mutable::new(None),
             resource_types: Default::default(),
+            progress: Mutable::new(None),
         })
     }
 }
@@ -0,0 +1,46 @@
+use std::rc::Rc;
+
+use dominator::clone;
+use shared::{
+    api::endpoints,
+    domain::class::{ClassCreatePath, ClassCreateRequest, ClassListPath},
+};
+use utils::{
+    bail_on_err,
+    error_ext::ErrorExt,
+    prelude::ApiEndpointExt,
+    routes::{ClassroomRoute, Route},
+};
+use wasm_bindgen_futures::spawn_local;
+
+use super::Classes;
+
+impl Classes {
+    pub fn load_data(self: &Rc<Self>) {
+        let state = self;
+        spawn_local(clone!(state => async move {
+            let res = endpoints::class::List::api_with_auth(ClassListPath(), None).await;
+            let res = bail_on_err!(res);
+            state.classes.set(Some(res.classes));
+        }));
+    }
+
+    pub fn create_class(self: &Rc<Self>) {
+        let display_name = self.new_name.get_cloned().trim().to_string();
+        if display_name.is_empty() {
+            return;
+        }
+
+        spawn_local(async move {
+            let req = ClassCreateRequest {
+                display_name,
+                account_id: None,
+            };
+            let res = endpoints::class::Create::api_with_auth(ClassCreatePath(), Some(req))
+                .await
+                .toast_on_err();
+            let res = bail_on_err!(res);
+            Route::Classroom(ClassroomRoute::Class(res.id)).go_to();
+        });
+    }
+}
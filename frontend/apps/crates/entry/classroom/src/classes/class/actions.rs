@@ -0,0 +1,182 @@
+use std::rc::Rc;
+
+use dominator::clone;
+use futures::join;
+use shared::{
+    api::endpoints,
+    domain::{
+        class::{
+            ClassAssignmentCreatePath, ClassAssignmentCreateRequest, ClassAssignmentDeletePath,
+            ClassAssignmentId, ClassAssignmentListPath, ClassGetPath, Student, StudentCreatePath,
+            StudentCreateRequest, StudentDeletePath, StudentId, StudentImportPath,
+            StudentImportRequest, StudentListPath, StudentUpdatePath, StudentUpdateRequest,
+        },
+        jig::{codes::JigsWithCodesPath, JigId},
+    },
+};
+use utils::{bail_on_err, error_ext::ErrorExt, prelude::ApiEndpointExt};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::File;
+
+use super::ClassPage;
+
+impl ClassPage {
+    pub fn load_data(self: &Rc<Self>) {
+        let state = self;
+        spawn_local(clone!(state => async move {
+            join!(
+                state.load_class(),
+                state.load_students(),
+                state.load_assignments(),
+                state.load_jigs(),
+            );
+        }));
+    }
+
+    async fn load_class(self: &Rc<Self>) {
+        let res = endpoints::class::Get::api_with_auth(ClassGetPath(self.class_id), None)
+            .await
+            .toast_on_err();
+        let res = bail_on_err!(res);
+        self.class.set(Some(res));
+    }
+
+    async fn load_students(self: &Rc<Self>) {
+        let res =
+            endpoints::class::student::List::api_with_auth(StudentListPath(self.class_id), None)
+                .await;
+        let res = bail_on_err!(res);
+        self.students.lock_mut().replace_cloned(res.students);
+    }
+
+    async fn load_assignments(self: &Rc<Self>) {
+        let res = endpoints::class::assignment::List::api_with_auth(
+            ClassAssignmentListPath(self.class_id),
+            None,
+        )
+        .await;
+        let res = bail_on_err!(res);
+        self.assignments.lock_mut().replace_cloned(res.assignments);
+    }
+
+    async fn load_jigs(self: &Rc<Self>) {
+        let res =
+            endpoints::jig::codes::JigsWithCodes::api_with_auth(JigsWithCodesPath(), None).await;
+        let res = bail_on_err!(res);
+        self.jigs.set(res.jigs);
+    }
+
+    pub fn add_student(self: &Rc<Self>) {
+        let state = self;
+        let display_name = state.new_student_name.get_cloned().trim().to_string();
+        if display_name.is_empty() {
+            return;
+        }
+
+        spawn_local(clone!(state => async move {
+            let req = StudentCreateRequest { display_name };
+            let res = endpoints::class::student::Create::api_with_auth(
+                StudentCreatePath(state.class_id),
+                Some(req),
+            )
+            .await
+            .toast_on_err();
+            let student = bail_on_err!(res);
+            state.new_student_name.set(String::new());
+            state.students.lock_mut().push_cloned(student);
+        }));
+    }
+
+    pub fn import_students(self: &Rc<Self>, file: File) {
+        let state = self;
+        spawn_local(clone!(state => async move {
+            let csv = wasm_bindgen_futures::JsFuture::from(file.text())
+                .await
+                .ok()
+                .and_then(|text| text.as_string())
+                .unwrap_or_default();
+            let req = StudentImportRequest { csv };
+            let res = endpoints::class::student::Import::api_with_auth(
+                StudentImportPath(state.class_id),
+                Some(req),
+            )
+            .await
+            .toast_on_err();
+            let res = bail_on_err!(res);
+            state.skipped.set(res.skipped);
+            state.students.lock_mut().extend(res.students);
+        }));
+    }
+
+    /// Gives the student a new login code and picture password, e.g. when they shared theirs.
+    pub fn reset_student_login(self: &Rc<Self>, student: &Student) {
+        let state = self;
+        let student_id = student.id;
+        spawn_local(clone!(state => async move {
+            let req = StudentUpdateRequest {
+                display_name: None,
+                reset_login: true,
+            };
+            let res = endpoints::class::student::Update::api_with_auth(
+                StudentUpdatePath(state.class_id, student_id),
+                Some(req),
+            )
+            .await
+            .toast_on_err();
+            bail_on_err!(res);
+            state.load_students().await;
+        }));
+    }
+
+    pub fn delete_student(self: &Rc<Self>, student_id: StudentId) {
+        let state = self;
+        spawn_local(clone!(state => async move {
+            let res = endpoints::class::student::Delete::api_with_auth(
+                StudentDeletePath(state.class_id, student_id),
+                None,
+            )
+            .await
+            .toast_on_err();
+            bail_on_err!(res);
+            state
+                .students
+                .lock_mut()
+                .retain(|student| student.id != student_id);
+        }));
+    }
+
+    pub fn assign_jig(self: &Rc<Self>, jig_id: JigId) {
+        let state = self;
+        spawn_local(clone!(state => async move {
+            let req = ClassAssignmentCreateRequest {
+                asset_id: jig_id.into(),
+                due_at: None,
+            };
+            let res = endpoints::class::assignment::Create::api_with_auth(
+                ClassAssignmentCreatePath(state.class_id),
+                Some(req),
+            )
+            .await
+            .toast_on_err();
+            bail_on_err!(res);
+            state.load_assignments().await;
+        }));
+    }
+
+    pub fn delete_assignment(self: &Rc<Self>, assignment_id: ClassAssignmentId) {
+        let state = self;
+        spawn_local(clone!(state => async move {
+            let res = endpoints::class::assignment::Delete::api_with_auth(
+                ClassAssignmentDeletePath(state.class_id, assignment_id),
+                None,
+            )
+            .await
+            .toast_on_err();
+            bail_on_err!(res);
+            state
+                .assignments
+                .lock_mut()
+                .retain(|assignment| assignment.id != assignment_id);
+        }));
+    }
+}
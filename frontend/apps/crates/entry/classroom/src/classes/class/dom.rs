@@ -0,0 +1,292 @@
+use std::rc::Rc;
+
+use dominator::{clone, html, with_node, Dom, DomBuilder};
+use futures_signals::{
+    signal::{Mutable, SignalExt},
+    signal_vec::SignalVecExt,
+};
+use shared::domain::{
+    asset::AssetId,
+    class::{ClassAssignment, Student},
+    jig::JigId,
+};
+use utils::{
+    class::PICTURE_PASSWORD_ICONS,
+    component::Component,
+    date_formatters, events, link,
+    routes::{ClassroomCodesRoute, ClassroomRoute, Route},
+    unwrap::UnwrapJiExt,
+};
+use wasm_bindgen::JsValue;
+use web_sys::{HtmlInputElement, HtmlSelectElement, ShadowRoot};
+
+use super::ClassPage;
+
+impl Component<ClassPage> for Rc<ClassPage> {
+    fn styles() -> &'static str {
+        include_str!("./styles.css")
+    }
+
+    fn dom(&self, dom: DomBuilder<ShadowRoot>) -> DomBuilder<ShadowRoot> {
+        let state = self;
+
+        state.load_data();
+
+        dom.child_signal(state.class.signal_cloned().map(|class| {
+            class.map(|class| {
+                html!("header", {
+                    .child(html!("h2", {
+                        .text(&class.display_name)
+                    }))
+                    .child(html!("p", {
+                        .text("Class code: ")
+                        .child(html!("span", {
+                            .class("login-code")
+                            .text(&class.login_code.0)
+                        }))
+                    }))
+                })
+            })
+        }))
+        .child(state.render_students())
+        .child(state.render_assignments())
+    }
+}
+
+impl ClassPage {
+    fn render_students(self: &Rc<Self>) -> Dom {
+        let state = self;
+        html!("section", {
+            .child(html!("h3", {
+                .text("Students")
+            }))
+            .child(html!("div", {
+                .class("controls")
+                .child(html!("input" => HtmlInputElement, {
+                    .with_node!(elem => {
+                        .prop("placeholder", "Student name")
+                        .prop_signal("value", state.new_student_name.signal_cloned())
+                        .event(clone!(state => move |_: events::Input| {
+                            state.new_student_name.set(elem.value());
+                        }))
+                    })
+                }))
+                .child(html!("button-rect", {
+                    .prop("kind", "outline")
+                    .prop_signal("disabled", state.new_student_name.signal_ref(|name| name.trim().is_empty()))
+                    .text("Add student")
+                    .event(clone!(state => move |_: events::Click| {
+                        state.add_student();
+                    }))
+                }))
+                .child(html!("input-wrapper", {
+                    .prop("label", "Import roster (CSV)")
+                    .child(html!("input", {
+                        .prop("type", "file")
+                        .prop("accept", "text/csv")
+                        .event(clone!(state => move |evt: events::Change| {
+                            let target: JsValue = evt.target().unwrap_ji().into();
+                            let element: HtmlInputElement = target.into();
+
+                            if let Some(file) = element.files().unwrap_ji().get(0) {
+                                state.import_students(file);
+                            }
+                        }))
+                    }))
+                }))
+            }))
+            .child_signal(state.skipped.signal_ref(|skipped| {
+                (!skipped.is_empty()).then(|| {
+                    html!("p", {
+                        .class("skipped")
+                        .text(&format!("Already in the class: {}", skipped.join(", ")))
+                    })
+                })
+            }))
+            .child(html!("div", {
+                .class("table")
+                .class("students")
+                .child(html!("div", {
+                    .class("header")
+                    .child(html!("span", {
+                        .class("cell")
+                        .text("Name")
+                    }))
+                    .child(html!("span", {
+                        .class("cell")
+                        .text("Login code")
+                    }))
+                    .child(html!("span", {
+                        .class("cell")
+                        .text("Picture password")
+                    }))
+                    .child(html!("span", {
+                        .class("cell")
+                    }))
+                }))
+                .children_signal_vec(state.students.signal_vec_cloned().map(clone!(state => move |student| {
+                    state.render_student(student)
+                })))
+            }))
+        })
+    }
+
+    fn render_student(self: &Rc<Self>, student: Student) -> Dom {
+        let state = self;
+        html!("div", {
+            .class("row")
+            .child(html!("span", {
+                .class("cell")
+                .text(&student.display_name)
+            }))
+            .child(html!("span", {
+                .class("cell")
+                .class("login-code")
+                .text(&student.login_code.0)
+            }))
+            .child(html!("span", {
+                .class("cell")
+                .class("pictures")
+                .children(student.picture_password.0.iter().filter_map(|picture| {
+                    PICTURE_PASSWORD_ICONS.get(*picture as usize).map(|icon| {
+                        html!("fa-icon", {
+                            .prop("icon", *icon)
+                        })
+                    })
+                }))
+            }))
+            .child(html!("span", {
+                .class("cell")
+                .class("actions")
+                .child(html!("fa-button", {
+                    .prop("icon", "fa-regular fa-arrows-rotate")
+                    .prop("title", "Reset login")
+                    .event(clone!(state, student => move |_: events::Click| {
+                        state.reset_student_login(&student);
+                    }))
+                }))
+                .child(html!("fa-button", {
+                    .prop("icon", "fa-regular fa-trash-can")
+                    .prop("title", "Remove")
+                    .event(clone!(state => move |_: events::Click| {
+                        state.delete_student(student.id);
+                    }))
+                }))
+            }))
+        })
+    }
+
+    fn render_assignments(self: &Rc<Self>) -> Dom {
+        let state = self;
+        let selected: Mutable<Option<JigId>> = Mutable::new(None);
+
+        html!("section", {
+            .child(html!("h3", {
+                .text("Assignments")
+            }))
+            .child(html!("div", {
+                .class("controls")
+                .child(html!("select" => HtmlSelectElement, {
+                    .with_node!(elem => {
+                        .child(html!("option", {
+                            .prop("value", "")
+                            .text("Pick a JIG to assign")
+                        }))
+                        .children_signal_vec(state.jigs.signal_cloned().map(|jigs| {
+                            jigs.into_iter().map(|jig| {
+                                html!("option", {
+                                    .prop("value", jig.jig.id.0.to_string())
+                                    .text(&jig.jig.jig_data.display_name)
+                                })
+                            }).collect()
+                        }).to_signal_vec())
+                        .event(clone!(selected => move |_: events::Change| {
+                            selected.set(elem.value().parse::<JigId>().ok());
+                        }))
+                    })
+                }))
+                .child(html!("button-rect", {
+                    .prop("kind", "outline")
+                    .prop_signal("disabled", selected.signal_ref(Option::is_none))
+                    .text("Assign")
+                    .event(clone!(state, selected => move |_: events::Click| {
+                        if let Some(jig_id) = selected.get() {
+                            state.assign_jig(jig_id);
+                        }
+                    }))
+                }))
+            }))
+            .child(html!("div", {
+                .class("table")
+                .class("assignments")
+                .child(html!("div", {
+                    .class("header")
+                    .child(html!("span", {
+                        .class("cell")
+                        .text("Assigned")
+                    }))
+                    .child(html!("span", {
+                        .class("cell")
+                        .text("Due")
+                    }))
+                    .child(html!("span", {
+                        .class("cell")
+                        .text("Codes")
+                    }))
+                    .child(html!("span", {
+                        .class("cell")
+                    }))
+                }))
+                .children_signal_vec(state.assignments.signal_vec_cloned().map(clone!(state => move |assignment| {
+                    state.render_assignment(assignment)
+                })))
+            }))
+        })
+    }
+
+    fn render_assignment(self: &Rc<Self>, assignment: ClassAssignment) -> Dom {
+        let state = self;
+        let assignment_id = assignment.id;
+        let asset_id = assignment.asset_id;
+        let name_signal = state.jigs.signal_ref(move |jigs| match asset_id {
+            AssetId::JigId(jig_id) => jigs
+                .iter()
+                .find(|jig| jig.jig.id == jig_id)
+                .map(|jig| jig.jig.jig_data.display_name.clone())
+                .unwrap_or_default(),
+            _ => "Playlist".to_string(),
+        });
+
+        html!("div", {
+            .class("row")
+            .child(html!("span", {
+                .class("cell")
+                .text_signal(name_signal)
+            }))
+            .child(html!("span", {
+                .class("cell")
+                .text(&assignment.due_at.map(|due_at| date_formatters::year_month_day(&due_at)).unwrap_or_default())
+            }))
+            .child(html!("span", {
+                .class("cell")
+                .class("codes")
+                .children(assignment.codes.iter().map(|code| {
+                    link!(Route::Classroom(ClassroomRoute::Codes(ClassroomCodesRoute::JigCodeSession(code.jig_id, code.code))), {
+                        .text(&code.code.to_string())
+                    })
+                }))
+            }))
+            .child(html!("span", {
+                .class("cell")
+                .class("actions")
+                .child(html!("fa-button", {
+                    .prop("icon", "fa-regular fa-trash-can")
+                    .prop("title", "Remove")
+                    .event(clone!(state => move |_: events::Click| {
+                        state.delete_assignment(assignment_id);
+                    }))
+                }))
+            }))
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use std::rc::Rc;
+
+use futures_signals::{signal::Mutable, signal_vec::MutableVec};
+use shared::domain::{
+    class::{Class, ClassAssignment, ClassId, Student},
+    jig::codes::JigWithCodes,
+};
+
+pub struct ClassPage {
+    pub class_id: ClassId,
+    pub class: Mutable<Option<Class>>,
+    pub students: MutableVec<Student>,
+    pub assignments: MutableVec<ClassAssignment>,
+    /// The teacher's JIGs, which can be assigned to the class.
+    pub jigs: Mutable<Vec<JigWithCodes>>,
+    pub new_student_name: Mutable<String>,
+    /// Names skipped by the last roster import because they were already in the class.
+    pub skipped: Mutable<Vec<String>>,
+}
+
+impl ClassPage {
+    pub fn new(class_id: ClassId) -> Rc<Self> {
+        Rc::new(Self {
+            class_id,
+            class: Default::default(),
+            students: Default::default(),
+            assignments: Default::default(),
+            jigs: Default::default(),
+            new_student_name: Default::default(),
+            skipped: Default::default(),
+        })
+    }
+}
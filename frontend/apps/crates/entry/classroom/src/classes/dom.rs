@@ -0,0 +1,99 @@
+use std::rc::Rc;
+
+use dominator::{clone, html, with_node, Dom, DomBuilder};
+use futures_signals::signal::SignalExt;
+use shared::domain::class::Class;
+use utils::{
+    component::Component,
+    events, link,
+    routes::{ClassroomRoute, Route},
+};
+use web_sys::{HtmlInputElement, ShadowRoot};
+
+use super::{class::ClassPage, Classes};
+
+impl Component<Classes> for Rc<Classes> {
+    fn styles() -> &'static str {
+        include_str!("./styles.css")
+    }
+
+    fn dom(&self, dom: DomBuilder<ShadowRoot>) -> DomBuilder<ShadowRoot> {
+        let state = self;
+
+        dom.child(html!("h1", {
+            .child(link!(Route::Classroom(ClassroomRoute::Classes), {
+                .text("My classes")
+            }))
+        }))
+        .child(html!("div", {
+            .class("width-holder")
+            .child(match state.class_id {
+                Some(class_id) => ClassPage::new(class_id).render(),
+                None => state.render_list(),
+            })
+        }))
+    }
+}
+
+impl Classes {
+    fn render_list(self: &Rc<Self>) -> Dom {
+        let state = self;
+
+        state.load_data();
+
+        html!("div", {
+            .child(state.render_create())
+            .child_signal(state.classes.signal_cloned().map(clone!(state => move |classes| {
+                Some(match classes {
+                    Some(classes) => state.render_loaded(classes),
+                    None => html!("progress"),
+                })
+            })))
+        })
+    }
+
+    fn render_create(self: &Rc<Self>) -> Dom {
+        let state = self;
+        html!("div", {
+            .class("create")
+            .child(html!("input" => HtmlInputElement, {
+                .with_node!(elem => {
+                    .prop("placeholder", "New class name")
+                    .prop_signal("value", state.new_name.signal_cloned())
+                    .event(clone!(state => move |_: events::Input| {
+                        state.new_name.set(elem.value());
+                    }))
+                })
+            }))
+            .child(html!("button-rect", {
+                .prop_signal("disabled", state.new_name.signal_ref(|name| name.trim().is_empty()))
+                .text("Create class")
+                .event(clone!(state => move |_: events::Click| {
+                    state.create_class();
+                }))
+            }))
+        })
+    }
+
+    fn render_loaded(self: &Rc<Self>, classes: Vec<Class>) -> Dom {
+        html!("div", {
+            .class("classes")
+            .children(classes.into_iter().map(|class| {
+                link!(Route::Classroom(ClassroomRoute::Class(class.id)), {
+                    .class("class-card")
+                    .child(html!("h3", {
+                        .text(&class.display_name)
+                    }))
+                    .child(html!("span", {
+                        .class("student-count")
+                        .text(&format!("{} students", class.student_count))
+                    }))
+                    .child(html!("span", {
+                        .class("login-code")
+                        .text(&class.login_code.0)
+                    }))
+                })
+            }))
+        })
+    }
+}
@@ -0,0 +1,6 @@
+mod actions;
+pub mod class;
+mod dom;
+mod state;
+
+pub use state::*;
@@ -0,0 +1,20 @@
+use std::rc::Rc;
+
+use futures_signals::signal::Mutable;
+use shared::domain::class::{Class, ClassId};
+
+pub struct Classes {
+    pub class_id: Option<ClassId>,
+    pub classes: Mutable<Option<Vec<Class>>>,
+    pub new_name: Mutable<String>,
+}
+
+impl Classes {
+    pub fn new(class_id: Option<ClassId>) -> Rc<Self> {
+        Rc::new(Self {
+            class_id,
+            classes: Default::default(),
+            new_name: Default::default(),
+        })
+    }
+}
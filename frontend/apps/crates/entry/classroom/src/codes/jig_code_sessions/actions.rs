@@ -9,7 +9,7 @@ use shared::{
         asset::AssetType,
         jig::{
             codes::{
-                JigCode, JigCodePath, JigCodeSessionsPath, JigPlaySessionModule,
+                JigCode, JigCodePath, JigCodeReportPath, JigCodeSessionsPath, JigPlaySessionModule,
                 JigPlaySessionModuleGetPointsEarned,
             },
             JigGetLivePath,
@@ -34,6 +34,7 @@ impl CodeSessions {
                 state.load_code(),
                 state.load_jig(),
                 state.load_report(),
+                state.load_completion(),
             );
         }));
     }
@@ -80,6 +81,15 @@ impl CodeSessions {
         self.infos.lock_mut().extend(res.sessions);
     }
 
+    async fn load_completion(self: &Rc<Self>) {
+        let res =
+            endpoints::jig::codes::JigCodeReport::api_with_auth(JigCodeReportPath(self.code), None)
+                .await
+                .toast_on_err();
+        let res = bail_on_err!(res);
+        self.completion.set(Some(res));
+    }
+
     pub fn show_qr_code(self: &Rc<Self>) {
         let state = self;
         if let Some(code_response) = state.code_response.lock_ref().as_ref() {
@@ -10,7 +10,8 @@ use futures_signals::{
 use shared::domain::{
     asset::DraftOrLive,
     jig::codes::{
-        JigCodeSessionResponse, JigPlaySessionModule, JigPlaySessionModuleGetPointsEarned,
+        JigCodeCompletionStatus, JigCodeReportResponse, JigCodeSessionResponse,
+        JigPlaySessionModule, JigPlaySessionModuleGetPointsEarned,
     },
     module::{ModuleBody, ModuleResponse, StableModuleId},
 };
@@ -127,6 +128,9 @@ impl Component<CodeSessions> for Rc<CodeSessions> {
                 })
             }),
         ))
+        .child_signal(state.completion.signal_cloned().map(clone!(state => move |completion| {
+            completion.map(|completion| state.render_completion(completion))
+        })))
         .child_signal(
             state
                 .preview_open
@@ -155,6 +159,73 @@ impl Component<CodeSessions> for Rc<CodeSessions> {
 }
 
 impl CodeSessions {
+    fn render_completion(self: &Rc<Self>, completion: JigCodeReportResponse) -> Dom {
+        let count = |status| {
+            completion
+                .players
+                .iter()
+                .filter(|player| player.status == status)
+                .count()
+        };
+
+        html!("section", {
+            .class("completion")
+            .child(html!("h3", {
+                .text("Completion")
+                .apply(|dom| match completion.due_at {
+                    Some(due_at) => dom.text(&format!(
+                        " (due {})",
+                        date_formatters::year_month_day_hour_minute(&due_at)
+                    )),
+                    None => dom,
+                })
+            }))
+            .child(html!("p", {
+                .class("summary")
+                .text(&format!(
+                    "On time: {} · Late: {} · Not completed: {}",
+                    count(JigCodeCompletionStatus::OnTime),
+                    count(JigCodeCompletionStatus::Late),
+                    count(JigCodeCompletionStatus::NotCompleted),
+                ))
+            }))
+            .child(html!("div", {
+                .class("players")
+                .children(completion.players.iter().map(|player| {
+                    let (status_class, status) = match player.status {
+                        JigCodeCompletionStatus::OnTime => ("on-time", "On time"),
+                        JigCodeCompletionStatus::Late => ("late", "Late"),
+                        JigCodeCompletionStatus::NotCompleted => ("not-completed", "Not completed"),
+                    };
+                    html!("div", {
+                        .class("player")
+                        .child(html!("span", {
+                            .text(player.players_name.as_deref().unwrap_or("Anonymous"))
+                        }))
+                        .child(html!("span", {
+                            .class("status")
+                            .class(status_class)
+                            .text(status)
+                        }))
+                        .child(html!("span", {
+                            .text(&player.completed_at.map(|completed_at| {
+                                date_formatters::year_month_day_hour_minute(&completed_at)
+                            }).unwrap_or_default())
+                        }))
+                        .child(html!("span", {
+                            .text(&format!("{} attempts", player.attempts))
+                        }))
+                        .child(html!("span", {
+                            .text(&player.points.as_ref().map(|points| {
+                                format!("{}%", points.percent())
+                            }).unwrap_or_default())
+                        }))
+                    })
+                }))
+            }))
+        })
+    }
+
     fn render_loaded(
         self: &Rc<Self>,
         jig: JigWithModules,
@@ -4,7 +4,7 @@ use components::qr_dialog::QrDialog;
 use futures_signals::signal::Mutable;
 use shared::domain::{
     jig::{
-        codes::{JigCode, JigCodeResponse, JigCodeSessionResponse},
+        codes::{JigCode, JigCodeReportResponse, JigCodeResponse, JigCodeSessionResponse},
         JigId, JigResponse,
     },
     module::{ModuleResponse, StableModuleId},
@@ -16,6 +16,7 @@ pub struct CodeSessions {
     pub(super) jig_id: JigId,
     pub(super) jig: Mutable<Option<JigWithModules>>,
     pub(super) infos: Mutable<Vec<JigCodeSessionResponse>>,
+    pub(super) completion: Mutable<Option<JigCodeReportResponse>>,
     pub(super) preview_open: Mutable<bool>,
     pub(super) qr_dialog: Mutable<Option<Rc<QrDialog>>>,
 }
@@ -28,6 +29,7 @@ impl CodeSessions {
             jig_id,
             jig: Default::default(),
             infos: Default::default(),
+            completion: Default::default(),
             preview_open: Default::default(),
             qr_dialog: Default::default(),
         })
@@ -46,7 +46,7 @@ impl JigCodes {
         spawn_local(async move {
             let req = JigCodeUpdateRequest {
                 name: Some(Some(new_name)),
-                settings: None,
+                ..Default::default()
             };
             let _ = endpoints::jig::codes::Update::api_with_auth(JigCodePath(code), Some(req))
                 .await
@@ -11,7 +11,7 @@ use dominator::{html, Dom, DomBuilder};
 use futures_signals::signal::{Signal, SignalExt};
 use web_sys::ShadowRoot;
 
-use crate::{codes::Codes, state::Classroom};
+use crate::{classes::Classes, codes::Codes, state::Classroom};
 
 impl Component<Classroom> for Rc<Classroom> {
     fn styles() -> &'static str {
@@ -37,6 +37,8 @@ impl Classroom {
         self.route_signal().map(|route| match route {
             Route::Classroom(route) => Some(match route {
                 ClassroomRoute::Codes(route) => Codes::new(route).render(),
+                ClassroomRoute::Classes => Classes::new(None).render(),
+                ClassroomRoute::Class(class_id) => Classes::new(Some(class_id)).render(),
             }),
             _ => None,
         })
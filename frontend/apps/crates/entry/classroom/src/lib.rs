@@ -4,6 +4,7 @@
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
+mod classes;
 mod codes;
 mod dom;
 mod state;
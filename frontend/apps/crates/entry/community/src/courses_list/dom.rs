@@ -140,11 +140,9 @@ impl Component<CoursesList> for Rc<CoursesList> {
                 play_course.map(|course_id| {
                     PlayerPopup::new_default_player_options(
                         course_id.into(),
-                        PreviewPopupCallbacks {
-                            close: Box::new(clone!(state => move|| {
-                                state.play_course.set(None);
-                            }))
-                        },
+                        PreviewPopupCallbacks::new(clone!(state => move|| {
+                            state.play_course.set(None);
+                        })),
                     ).render(None)
                 })
             }),
@@ -185,11 +185,9 @@ impl Component<CommunityLanding> for Rc<CommunityLanding> {
                 play_course.map(|course_id| {
                     PlayerPopup::new_default_player_options(
                         course_id.into(),
-                        PreviewPopupCallbacks {
-                            close: Box::new(clone!(state => move|| {
-                                state.play_course.set(None);
-                            }))
-                        },
+                        PreviewPopupCallbacks::new(clone!(state => move|| {
+                            state.play_course.set(None);
+                        })),
                     ).render(None)
                 })
             })))
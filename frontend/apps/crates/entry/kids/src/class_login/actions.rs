@@ -0,0 +1,183 @@
+use std::rc::Rc;
+
+use dominator::clone;
+use futures_signals::{signal::Mutable, signal_vec::MutableVec};
+use shared::{
+    api::endpoints::class,
+    config::PICTURE_PASSWORD_LENGTH,
+    domain::{
+        class::{
+            ClassAssignmentId, ClassLoginRosterPath, LoginCode, PicturePassword,
+            StudentAssignmentListPath, StudentAssignmentListRequest, StudentAssignmentPlayPath,
+            StudentAssignmentPlayRequest, StudentLoginPath, StudentLoginRequest, StudentProfile,
+        },
+        jig::JigId,
+    },
+};
+use utils::{
+    class::{delete_student_login, save_student_login},
+    prelude::*,
+};
+
+use super::state::{ClassLogin, Roster, Step};
+use crate::student_code::state::PlayJig;
+
+impl ClassLogin {
+    /// A code is either a student's own code, which logs them in directly, or their class's code,
+    /// which lets them pick their name and enter their picture password.
+    pub fn submit_code(self: &Rc<Self>, code: String) {
+        let state = self;
+        let code = LoginCode::normalized(&code);
+        if code.0.is_empty() {
+            return;
+        }
+
+        state.loader.load(clone!(state => async move {
+            let (res, _) = class::login::Roster::api_no_auth_status(
+                ClassLoginRosterPath(code.clone()),
+                None,
+            )
+            .await;
+
+            match res {
+                Ok(roster) => {
+                    state.error.set_neq(false);
+                    state.step.set(Step::Name(Rc::new(Roster {
+                        class_code: code,
+                        roster,
+                    })));
+                },
+                Err(_) => {
+                    state.log_in(StudentLoginRequest::Code { code }).await;
+                },
+            }
+        }));
+    }
+
+    pub fn select_student(self: &Rc<Self>, roster: Rc<Roster>, student: StudentProfile) {
+        self.error.set_neq(false);
+        self.step
+            .set(Step::Picture(roster, student, Default::default()));
+    }
+
+    pub fn add_picture(
+        self: &Rc<Self>,
+        roster: &Rc<Roster>,
+        student: &StudentProfile,
+        pictures: &Rc<MutableVec<i16>>,
+        picture: i16,
+    ) {
+        let state = self;
+        pictures.lock_mut().push(picture);
+
+        let picture_password = pictures.lock_ref().to_vec();
+        if picture_password.len() < PICTURE_PASSWORD_LENGTH {
+            return;
+        }
+
+        let req = StudentLoginRequest::Picture {
+            class_code: roster.class_code.clone(),
+            student_id: student.id,
+            picture_password: PicturePassword(picture_password),
+        };
+
+        state.loader.load(clone!(state, pictures => async move {
+            state.log_in(req).await;
+            // let the student try again after a wrong password
+            pictures.lock_mut().clear();
+        }));
+    }
+
+    async fn log_in(self: &Rc<Self>, req: StudentLoginRequest) {
+        let (res, _) = class::login::Login::api_no_auth_status(StudentLoginPath(), Some(req)).await;
+
+        match res {
+            Ok(login) => {
+                save_student_login(&login);
+                self.error.set_neq(false);
+                self.login.set(Some(login));
+                self.step.set(Step::Assignments);
+                self.load_assignments();
+            }
+            Err(_) => {
+                self.error.set(true);
+            }
+        }
+    }
+
+    pub fn load_assignments(self: &Rc<Self>) {
+        let state = self;
+        let token = match &*state.login.lock_ref() {
+            Some(login) => login.token.clone(),
+            None => return,
+        };
+
+        state.loader.load(clone!(state => async move {
+            let req = StudentAssignmentListRequest { token };
+            let (res, _) = class::login::Assignments::api_no_auth_status(
+                StudentAssignmentListPath(),
+                Some(req),
+            )
+            .await;
+
+            match res {
+                Ok(res) => {
+                    state.assignments.lock_mut().replace_cloned(res.assignments);
+                },
+                Err(_) => {
+                    // the token expired or the student was removed from the class
+                    state.logout();
+                },
+            }
+        }));
+    }
+
+    pub fn play(self: &Rc<Self>, assignment_id: ClassAssignmentId, jig_id: JigId) {
+        let state = self;
+        let login = match state.login.get_cloned() {
+            Some(login) => login,
+            None => return,
+        };
+
+        state.loader.load(clone!(state => async move {
+            let req = StudentAssignmentPlayRequest {
+                token: login.token,
+                jig_id,
+            };
+            let (res, _) = class::login::Play::api_no_auth_status(
+                StudentAssignmentPlayPath(assignment_id),
+                Some(req),
+            )
+            .await;
+
+            match res {
+                Ok(res) => {
+                    state.error.set_neq(false);
+                    state.play_jig.set(Some(PlayJig {
+                        id: res.jig_id,
+                        settings: res.settings,
+                        token: res.token,
+                        name: Mutable::new(Some(login.student.display_name)),
+                    }));
+                },
+                Err(_) => {
+                    state.error.set(true);
+                },
+            }
+        }));
+    }
+
+    pub fn close_jig(self: &Rc<Self>) {
+        self.play_jig.set(None);
+        // refresh which JIGs were completed
+        self.load_assignments();
+    }
+
+    pub fn logout(self: &Rc<Self>) {
+        delete_student_login();
+        self.login.set(None);
+        self.assignments.lock_mut().clear();
+        self.error.set_neq(false);
+        self.step.set(Step::Code);
+    }
+}
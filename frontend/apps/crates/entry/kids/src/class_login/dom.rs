@@ -0,0 +1,287 @@
+use std::rc::Rc;
+
+use components::player_popup::{PlayerPopup, PreviewPopupCallbacks};
+use dominator::{clone, html, with_node, Dom};
+use futures_signals::{
+    signal::{Mutable, SignalExt},
+    signal_vec::{MutableVec, SignalVecExt},
+};
+use shared::domain::{
+    asset::DraftOrLive,
+    class::{StudentAssignment, StudentProfile},
+};
+use utils::{
+    asset::JigPlayerOptions,
+    class::PICTURE_PASSWORD_ICONS,
+    date_formatters, events,
+    routes::{KidsRoute, Route},
+};
+use web_sys::HtmlInputElement;
+
+use super::state::{ClassLogin, Roster, Step};
+use crate::student_code::state::PlayJig;
+
+const STR_CODE_HEADING: &str = "Type in your code";
+const STR_CODE_PLACEHOLDER: &str = "Class or student code";
+const STR_CODE_ERROR: &str = "We couldn't find that code, try again";
+const STR_GO: &str = "Go";
+const STR_BACK: &str = "Back";
+const STR_NAME_HEADING: &str = "Who are you?";
+const STR_PICTURE_HEADING: &str = "Pick your pictures";
+const STR_PICTURE_ERROR: &str = "Those aren't your pictures, try again";
+const STR_ASSIGNMENTS_HEADING_PREFIX: &str = "Hi";
+const STR_NO_ASSIGNMENTS: &str = "Nothing to play yet";
+const STR_PLAY_ERROR: &str = "Couldn't start this JIG, try again";
+const STR_DUE: &str = "Due";
+const STR_LOGOUT: &str = "Log out";
+const STR_JIG_CODE: &str = "I have a JIG code";
+
+impl ClassLogin {
+    pub fn render(self: Rc<Self>) -> Dom {
+        let state = self;
+        if state.login.lock_ref().is_some() {
+            state.load_assignments();
+        }
+
+        html!("div", {
+            .child_signal(state.play_jig.signal_cloned().map(clone!(state => move |play_jig| {
+                Some(match play_jig {
+                    Some(play_jig) => state.render_jig(play_jig),
+                    None => html!("div", {
+                        .child_signal(state.step.signal_cloned().map(clone!(state => move |step| {
+                            Some(match step {
+                                Step::Code => state.render_code(),
+                                Step::Name(roster) => state.render_names(roster),
+                                Step::Picture(roster, student, pictures) => {
+                                    state.render_pictures(roster, student, pictures)
+                                },
+                                Step::Assignments => state.render_assignments(),
+                            })
+                        })))
+                    }),
+                })
+            })))
+        })
+    }
+
+    fn render_code(self: &Rc<Self>) -> Dom {
+        let state = self;
+        let code = Mutable::new(String::new());
+
+        html!("kids-class-login", {
+            .prop("heading", STR_CODE_HEADING)
+            .prop_signal("error", state.error.signal())
+            .child(html!("input" => HtmlInputElement, {
+                .with_node!(elem => {
+                    .prop("slot", "content")
+                    .prop("placeholder", STR_CODE_PLACEHOLDER)
+                    .prop("autocomplete", "off")
+                    .event(clone!(code => move |_: events::Input| {
+                        code.set(elem.value());
+                    }))
+                    .event(clone!(state, code => move |evt: events::KeyDown| {
+                        if evt.key() == "Enter" {
+                            state.submit_code(code.get_cloned());
+                        }
+                    }))
+                })
+            }))
+            .child(html!("p", {
+                .prop("slot", "error")
+                .text(STR_CODE_ERROR)
+            }))
+            .child(html!("button-rect", {
+                .prop("slot", "action")
+                .prop("color", "red")
+                .prop("size", "large")
+                .prop_signal("disabled", code.signal_ref(|code| code.trim().is_empty()))
+                .text(STR_GO)
+                .event(clone!(state, code => move |_: events::Click| {
+                    state.submit_code(code.get_cloned());
+                }))
+            }))
+            .child(html!("button-rect", {
+                .prop("slot", "action")
+                .prop("kind", "text")
+                .prop("color", "blue")
+                .prop("href", Route::Kids(KidsRoute::StudentCode(None)).to_string())
+                .text(STR_JIG_CODE)
+            }))
+        })
+    }
+
+    fn render_names(self: &Rc<Self>, roster: Rc<Roster>) -> Dom {
+        let state = self;
+
+        html!("kids-class-login", {
+            .prop("heading", STR_NAME_HEADING)
+            .child(html!("p", {
+                .prop("slot", "content")
+                .text(&roster.roster.display_name)
+            }))
+            .children(roster.roster.students.iter().map(clone!(state, roster => move |student| {
+                html!("kids-class-login-option", {
+                    .prop("slot", "option")
+                    .prop("label", &student.display_name)
+                    .event(clone!(state, roster, student => move |_: events::Click| {
+                        state.select_student(Rc::clone(&roster), student.clone());
+                    }))
+                })
+            })))
+            .child(state.render_back())
+        })
+    }
+
+    fn render_pictures(
+        self: &Rc<Self>,
+        roster: Rc<Roster>,
+        student: StudentProfile,
+        pictures: Rc<MutableVec<i16>>,
+    ) -> Dom {
+        let state = self;
+
+        html!("kids-class-login", {
+            .prop("heading", STR_PICTURE_HEADING)
+            .prop_signal("error", state.error.signal())
+            .child(html!("div", {
+                .prop("slot", "content")
+                .child(html!("p", {
+                    .text(&student.display_name)
+                }))
+                .children_signal_vec(pictures.signal_vec().map(|picture| {
+                    html!("fa-icon", {
+                        .prop("icon", PICTURE_PASSWORD_ICONS[picture as usize])
+                    })
+                }))
+            }))
+            .children(PICTURE_PASSWORD_ICONS.iter().enumerate().map(|(picture, icon)| {
+                html!("kids-class-login-option", {
+                    .prop("slot", "option")
+                    .child(html!("fa-icon", {
+                        .prop("icon", *icon)
+                    }))
+                    .event(clone!(state, roster, student, pictures => move |_: events::Click| {
+                        state.add_picture(&roster, &student, &pictures, picture as i16);
+                    }))
+                })
+            }))
+            .child(html!("p", {
+                .prop("slot", "error")
+                .text(STR_PICTURE_ERROR)
+            }))
+            .child(state.render_back())
+        })
+    }
+
+    fn render_back(self: &Rc<Self>) -> Dom {
+        let state = self;
+
+        html!("button-rect", {
+            .prop("slot", "action")
+            .prop("kind", "text")
+            .prop("color", "blue")
+            .text(STR_BACK)
+            .event(clone!(state => move |_: events::Click| {
+                state.error.set_neq(false);
+                state.step.set(Step::Code);
+            }))
+        })
+    }
+
+    fn render_assignments(self: &Rc<Self>) -> Dom {
+        let state = self;
+        let heading = match &*state.login.lock_ref() {
+            Some(login) => format!(
+                "{} {}!",
+                STR_ASSIGNMENTS_HEADING_PREFIX, login.student.display_name
+            ),
+            None => String::new(),
+        };
+
+        html!("kids-class-login", {
+            .prop("heading", heading)
+            .prop_signal("error", state.error.signal())
+            .child(html!("button-rect", {
+                .prop("slot", "logout")
+                .prop("kind", "text")
+                .prop("color", "blue")
+                .text(STR_LOGOUT)
+                .event(clone!(state => move |_: events::Click| {
+                    state.logout();
+                }))
+            }))
+            .child_signal(state.assignments.signal_vec_cloned().is_empty().map(|empty| {
+                empty.then(|| {
+                    html!("p", {
+                        .prop("slot", "content")
+                        .text(STR_NO_ASSIGNMENTS)
+                    })
+                })
+            }))
+            .children_signal_vec(state.assignments.signal_vec_cloned().map(clone!(state => move |assignment| {
+                state.render_assignment(assignment)
+            })))
+            .child(html!("p", {
+                .prop("slot", "error")
+                .text(STR_PLAY_ERROR)
+            }))
+        })
+    }
+
+    fn render_assignment(self: &Rc<Self>, assignment: StudentAssignment) -> Dom {
+        let state = self;
+        let assignment_id = assignment.assignment.id;
+        let due = assignment
+            .assignment
+            .due_at
+            .map(|due_at| format!("{} {}", STR_DUE, date_formatters::year_month_day(&due_at)));
+
+        html!("div", {
+            .prop("slot", "option")
+            .children(assignment.jigs.into_iter().map(clone!(state, due => move |jig| {
+                html!("kids-class-login-option", {
+                    .prop("label", &jig.display_name)
+                    .prop("subLabel", due.clone().unwrap_or_default())
+                    .prop("completed", jig.completed)
+                    .child(html!("fa-icon", {
+                        .prop("icon", match jig.completed {
+                            true => "fa-solid fa-circle-check",
+                            false => "fa-solid fa-play",
+                        })
+                    }))
+                    .event(clone!(state => move |_: events::Click| {
+                        state.play(assignment_id, jig.jig_id);
+                    }))
+                })
+            })))
+        })
+    }
+
+    fn render_jig(self: &Rc<Self>, play_jig: PlayJig) -> Dom {
+        let state = self;
+        let close = clone!(state => move || {
+            state.close_jig();
+        });
+
+        let player_options = JigPlayerOptions {
+            draft_or_live: DraftOrLive::Live,
+            play_token: Some(play_jig.token),
+            players_name: play_jig.name.get_cloned(),
+            is_student: true,
+            quota: false,
+            direction: Some(play_jig.settings.direction),
+            scoring: Some(play_jig.settings.scoring),
+            drag_assist: Some(play_jig.settings.drag_assist),
+            hide_score: play_jig.settings.hide_score,
+        };
+
+        PlayerPopup::new(
+            play_jig.id.into(),
+            None,
+            None,
+            player_options.into(),
+            PreviewPopupCallbacks::new(close),
+        )
+        .render(None)
+    }
+}
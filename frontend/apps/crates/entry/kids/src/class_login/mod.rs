@@ -0,0 +1,3 @@
+pub(super) mod actions;
+pub mod dom;
+pub mod state;
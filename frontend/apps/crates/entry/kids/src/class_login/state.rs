@@ -0,0 +1,55 @@
+use std::rc::Rc;
+
+use awsm_web::loaders::helpers::AsyncLoader;
+use futures_signals::{signal::Mutable, signal_vec::MutableVec};
+use shared::domain::class::{
+    ClassLoginRosterResponse, LoginCode, StudentAssignment, StudentLoginResponse, StudentProfile,
+};
+use utils::class::load_student_login;
+
+use crate::student_code::state::PlayJig;
+
+pub struct ClassLogin {
+    pub loader: AsyncLoader,
+    pub error: Mutable<bool>,
+    pub(super) step: Mutable<Step>,
+    pub(super) login: Mutable<Option<StudentLoginResponse>>,
+    pub(super) assignments: MutableVec<StudentAssignment>,
+    pub(super) play_jig: Mutable<Option<PlayJig>>,
+}
+
+impl ClassLogin {
+    pub fn new() -> Rc<Self> {
+        let login = load_student_login();
+        let step = match login {
+            Some(_) => Step::Assignments,
+            None => Step::Code,
+        };
+
+        Rc::new(Self {
+            loader: AsyncLoader::new(),
+            error: Mutable::new(false),
+            step: Mutable::new(step),
+            login: Mutable::new(login),
+            assignments: MutableVec::new(),
+            play_jig: Mutable::new(None),
+        })
+    }
+}
+
+#[derive(Clone)]
+pub(super) enum Step {
+    /// Typing in a student or class code.
+    Code,
+    /// Picking a name from the class roster.
+    Name(Rc<Roster>),
+    /// Picking the pictures of the picked student's password.
+    Picture(Rc<Roster>, StudentProfile, Rc<MutableVec<i16>>),
+    /// Logged in, picking an assignment to play.
+    Assignments,
+}
+
+pub(super) struct Roster {
+    pub class_code: LoginCode,
+    pub roster: ClassLoginRosterResponse,
+}
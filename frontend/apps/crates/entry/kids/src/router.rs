@@ -1,4 +1,4 @@
-use crate::student_code::state::StudentCode;
+use crate::{class_login::state::ClassLogin, student_code::state::StudentCode};
 
 use dominator::{html, Dom};
 use futures_signals::signal::Signal;
@@ -19,6 +19,7 @@ impl Router {
             match route {
                 Route::Kids(route) => match route {
                     KidsRoute::StudentCode(code) => Some(StudentCode::new().render(code)),
+                    KidsRoute::ClassLogin => Some(ClassLogin::new().render()),
                 },
                 _ => None,
             }
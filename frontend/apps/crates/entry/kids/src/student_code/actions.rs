@@ -12,7 +12,11 @@ use shared::{
         JigCode,
     },
 };
-use utils::prelude::*;
+use utils::{
+    class::load_student_login,
+    prelude::*,
+    storage::{load_player_id, save_player_id},
+};
 
 use super::state::{PlayJig, StudentCode};
 
@@ -28,11 +32,16 @@ impl StudentCode {
                 },
                 Ok(res) => {
                     state.error.set_neq(false);
+                    // students logged in to their class don't need to type their name, and
+                    // players of schools in child privacy mode aren't asked for it
+                    let name = res
+                        .pseudonym
+                        .or_else(|| load_student_login().map(|login| login.student.display_name));
                     state.play_jig.set(Some(PlayJig {
                         id: res.jig_id,
                         settings: res.settings,
                         token: res.token,
-                        name: Mutable::new(None),
+                        name: Mutable::new(name),
                     }));
                 },
             }
@@ -43,9 +52,18 @@ impl StudentCode {
 async fn code_to_jig_id(number: String) -> Result<PlayerSessionInstanceResponse, ()> {
     let number = number.parse::<i32>().map_err(|_| ())?;
     let code = JigCode(number);
-    let req = PlayerSessionInstanceCreateRequest { code };
+    let req = PlayerSessionInstanceCreateRequest {
+        code,
+        student_token: load_student_login().map(|login| login.token),
+        player_id: load_player_id(),
+    };
+
+    let res =
+        jig::codes::instance::Create::api_no_auth(PlayerSessionInstanceCreatePath(), Some(req))
+            .await
+            .map_err(|_| ())?;
+
+    save_player_id(res.player_id);
 
-    jig::codes::instance::Create::api_no_auth(PlayerSessionInstanceCreatePath(), Some(req))
-        .await
-        .map_err(|_| ())
+    Ok(res)
 }
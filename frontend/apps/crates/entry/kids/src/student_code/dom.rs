@@ -4,13 +4,18 @@ use components::player_popup::{PlayerPopup, PreviewPopupCallbacks};
 use dominator::{clone, html, with_node, Dom};
 use futures_signals::signal::{Mutable, SignalExt};
 use shared::domain::asset::DraftOrLive;
-use utils::{asset::JigPlayerOptions, events};
+use utils::{
+    asset::JigPlayerOptions,
+    events,
+    routes::{KidsRoute, Route},
+};
 use web_sys::HtmlInputElement;
 
 use super::state::{PlayJig, StudentCode};
 
 const STR_TRY_AGAIN: &str = "Try again";
 const STR_HELP: &str = "Ask for help";
+const STR_CLASS_LOGIN: &str = "Log in to my class";
 
 impl StudentCode {
     pub fn render(self: Rc<Self>, code: Option<String>) -> Dom {
@@ -52,6 +57,13 @@ impl StudentCode {
                     state.submit_code(evt.value());
                 }))
             }))
+            .child(html!("button-rect", {
+                .prop("slot", "class-login")
+                .prop("kind", "text")
+                .prop("color", "blue")
+                .prop("href", Route::Kids(KidsRoute::ClassLogin).to_string())
+                .text(STR_CLASS_LOGIN)
+            }))
             .child(html!("kids-student-code-jigzi", {
                 .prop("slot", "jigzi")
                 .prop_signal("mode", state.error.signal().map(|error| {
@@ -138,6 +150,7 @@ impl StudentCode {
             direction: Some(play_jig.settings.direction),
             scoring: Some(play_jig.settings.scoring),
             drag_assist: Some(play_jig.settings.drag_assist),
+            hide_score: play_jig.settings.hide_score,
         };
 
         PlayerPopup::new(
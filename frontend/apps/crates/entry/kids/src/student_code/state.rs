@@ -21,7 +21,7 @@ impl StudentCode {
 }
 
 #[derive(Clone)]
-pub(super) struct PlayJig {
+pub(crate) struct PlayJig {
     pub id: JigId,
     pub settings: JigPlayerSettings,
     pub token: String,
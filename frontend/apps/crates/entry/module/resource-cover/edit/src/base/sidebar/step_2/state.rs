@@ -4,6 +4,7 @@ use components::{
     image::search::{ImageSearch, ImageSearchCallbacks, ImageSearchKind, ImageSearchOptions},
     stickers::state::Stickers,
     tabs::MenuTabKind,
+    text_editor::{plain_text_value, ElementType},
 };
 use dominator::clone;
 use futures_signals::signal::Mutable;
@@ -49,7 +50,11 @@ impl Tab {
                         let image = image.expect_ji("ImageSearchKind::Sticker should never call on_select with `None`");
                         Stickers::add_sprite(base.stickers.clone(), image);
                     }),
-                ));
+                ))
+                .with_on_attribution(clone!(base => move |attribution: String| {
+                    let value = plain_text_value(&attribution, ElementType::P2);
+                    Stickers::add_text(base.stickers.clone(), value);
+                }));
                 let state = ImageSearch::new(opts, callbacks);
 
                 Self::Image(state)
@@ -9,12 +9,14 @@ mod email;
 mod email_handler;
 mod likes;
 mod login;
+mod media;
 mod oauth;
 mod password_handler;
 mod register;
 mod reset_password;
 mod router;
 mod school_end;
+mod school_members;
 mod school_start;
 mod settings;
 mod strings;
@@ -0,0 +1,74 @@
+use std::rc::Rc;
+
+use components::confirm::Confirm;
+use dominator::clone;
+use shared::{
+    api::endpoints,
+    domain::user::{UserMediaDeletePath, UserMediaDeleteRequest, UserMediaListPath},
+};
+use utils::{bail_on_err, error_ext::ErrorExt, prelude::ApiEndpointExt};
+use uuid::Uuid;
+
+use super::MediaLibrary;
+
+const STR_DELETE_TITLE: &str = "Delete unused files";
+const STR_DELETE_MESSAGE: &str =
+    "These files aren't used in any of your JIGs, and will be permanently deleted.";
+
+impl MediaLibrary {
+    pub(super) fn load_data(self: &Rc<Self>) {
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            let res = endpoints::user::ListMedia::api_with_auth(UserMediaListPath(), None)
+                .await
+                .toast_on_err();
+            let res = bail_on_err!(res);
+
+            state
+                .media
+                .lock_mut()
+                .replace_cloned(res.media.into_iter().map(Rc::new).collect());
+            state.storage.set(Some(res.storage));
+        }));
+    }
+
+    pub(super) fn delete_unused(self: &Rc<Self>) {
+        let ids: Vec<Uuid> = self
+            .media
+            .lock_ref()
+            .iter()
+            .filter(|media| media.is_unreferenced())
+            .map(|media| media.id)
+            .collect();
+
+        self.delete(ids);
+    }
+
+    pub(super) fn delete(self: &Rc<Self>, ids: Vec<Uuid>) {
+        if ids.is_empty() {
+            return;
+        }
+
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            let confirmed = Confirm::new(STR_DELETE_TITLE.to_string(), STR_DELETE_MESSAGE.to_string())
+                .confirm()
+                .await;
+            if !confirmed {
+                return;
+            }
+
+            let req = UserMediaDeleteRequest { ids };
+            let res = endpoints::user::DeleteMedia::api_with_auth(UserMediaDeletePath(), Some(req))
+                .await
+                .toast_on_err();
+            let res = bail_on_err!(res);
+
+            state
+                .media
+                .lock_mut()
+                .retain(|media| !res.deleted.contains(&media.id));
+            state.storage.set(Some(res.storage));
+        }));
+    }
+}
@@ -0,0 +1,182 @@
+use std::rc::Rc;
+
+use components::page_header::PageHeader;
+use dominator::{clone, html, Dom};
+use futures_signals::{map_ref, signal::SignalExt, signal_vec::SignalVecExt};
+use shared::{
+    domain::{
+        image::ImageId,
+        user::{UserMedia, UserMediaKind, UserStorageUsage},
+    },
+    media::{MediaLibrary as Library, PngImageFile},
+};
+use utils::{events, path::image_lib_url, routes::*};
+
+use super::state::MediaLibrary;
+
+const STR_TITLE: &str = "My media";
+const STR_DELETE_UNUSED: &str = "Delete unused files";
+const STR_DELETE: &str = "Delete";
+const STR_UNUSED: &str = "Not used";
+const STR_USED_IN: &str = "Used in";
+const STR_USED_ELSEWHERE: &str = "Used in a playlist, resource, course or profile";
+const STR_EMPTY: &str = "You haven't uploaded any files yet.";
+
+impl MediaLibrary {
+    pub fn render(self: Rc<Self>) -> Dom {
+        let state = self;
+
+        state.load_data();
+
+        html!("div", {
+            .child(PageHeader::new(Default::default()).render())
+            .child(html!("h1", {
+                .style("height", "86px")
+                .style("background-color", "var(--light-blue-6)")
+                .style("margin", "0px")
+                .style("margin-bottom", "20px")
+                .style("display", "grid")
+                .style("align-items", "center")
+                .style("justify-content", "start")
+                .style("padding-inline", "40px")
+                .style("font-size", "29px")
+                .style("font-weight", "900")
+                .style("color", "var(--main-yellow)")
+                .text(STR_TITLE)
+            }))
+            .child(html!("div", {
+                .style("display", "grid")
+                .style("gap", "20px")
+                .style("padding-inline", "40px")
+                .child_signal(state.storage.signal_cloned().map(|storage| {
+                    storage.map(|storage| render_storage(&storage))
+                }))
+                .child(html!("button-rect", {
+                    .style("justify-self", "start")
+                    .prop("color", "red")
+                    .prop("kind", "outline")
+                    .prop_signal("disabled", state.unused_count_signal().map(|count| count == 0))
+                    .text(STR_DELETE_UNUSED)
+                    .event(clone!(state => move |_: events::Click| {
+                        state.delete_unused();
+                    }))
+                }))
+                .child_signal(map_ref! {
+                    let is_empty = state.media.signal_vec_cloned().is_empty(),
+                    let is_loading = state.loader.is_loading() => move {
+                        (*is_empty && !*is_loading).then(|| {
+                            html!("p", {
+                                .text(STR_EMPTY)
+                            })
+                        })
+                    }
+                })
+                .child(html!("div", {
+                    .style("display", "grid")
+                    .style("grid-template-columns", "64px 1fr auto auto auto")
+                    .style("align-items", "center")
+                    .style("gap", "12px 24px")
+                    .children_signal_vec(state.media.signal_vec_cloned().map(clone!(state => move |media| {
+                        state.render_media(&media)
+                    })))
+                }))
+            }))
+        })
+    }
+
+    fn render_media(self: &Rc<Self>, media: &UserMedia) -> Dom {
+        let state = self;
+        let id = media.id;
+
+        // the rows lay out their cells in the list's grid
+        html!("div", {
+            .style("display", "contents")
+            .children(&mut [
+                render_preview(media),
+                html!("div", {
+                    .apply(|dom| {
+                        if !media.referenced_by.is_empty() {
+                            dom.text(STR_USED_IN)
+                        } else if media.used_elsewhere {
+                            dom.text(STR_USED_ELSEWHERE)
+                        } else {
+                            dom.text(STR_UNUSED)
+                        }
+                    })
+                    .children(media.referenced_by.iter().enumerate().map(|(index, jig_id)| {
+                        html!("a", {
+                            .style("margin-inline-start", "6px")
+                            .prop("href", Route::Asset(AssetRoute::Edit(AssetEditRoute::Jig(
+                                *jig_id,
+                                JigEditRoute::Landing,
+                            ))).to_string())
+                            .prop("target", "_top")
+                            .text(&format!("JIG {}", index + 1))
+                        })
+                    }))
+                }),
+                html!("span", {
+                    .text(&format_size(media.size_bytes))
+                }),
+                html!("span", {
+                    .text(&media.created_at.format("%b %e, %Y").to_string())
+                }),
+                html!("button-rect", {
+                    .prop("color", "red")
+                    .prop("kind", "text")
+                    .prop("disabled", !media.is_unreferenced())
+                    .text(STR_DELETE)
+                    .event(clone!(state => move |_: events::Click| {
+                        state.delete(vec![id]);
+                    }))
+                }),
+            ])
+        })
+    }
+}
+
+fn render_storage(storage: &UserStorageUsage) -> Dom {
+    html!("div", {
+        .child(html!("progress", {
+            .style("width", "100%")
+            .prop("max", storage.quota_bytes as f64)
+            .prop("value", storage.used_bytes.min(storage.quota_bytes) as f64)
+        }))
+        .child(html!("p", {
+            .style("margin", "0")
+            .text(&format!(
+                "{} of {} used",
+                format_size(storage.used_bytes),
+                format_size(storage.quota_bytes)
+            ))
+        }))
+    })
+}
+
+fn render_preview(media: &UserMedia) -> Dom {
+    match media.kind {
+        UserMediaKind::Image => html!("img", {
+            .style("width", "64px")
+            .style("height", "64px")
+            .style("object-fit", "contain")
+            .prop("src", image_lib_url(Library::User, PngImageFile::Thumbnail, ImageId(media.id)))
+        }),
+        UserMediaKind::Audio => html!("fa-icon", {
+            .prop("icon", "fa-light fa-file-audio")
+        }),
+        UserMediaKind::Pdf => html!("fa-icon", {
+            .prop("icon", "fa-light fa-file-pdf")
+        }),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const MB: f64 = 1024.0 * 1024.0;
+
+    let mb = bytes as f64 / MB;
+    if mb >= 1024.0 {
+        format!("{:.1} GB", mb / 1024.0)
+    } else {
+        format!("{:.1} MB", mb)
+    }
+}
@@ -0,0 +1,31 @@
+use std::rc::Rc;
+
+use dominator_helpers::futures::AsyncLoader;
+use futures_signals::{
+    signal::{Mutable, Signal},
+    signal_vec::{MutableVec, SignalVecExt},
+};
+use shared::domain::user::{UserMedia, UserStorageUsage};
+
+pub struct MediaLibrary {
+    pub loader: AsyncLoader,
+    pub media: MutableVec<Rc<UserMedia>>,
+    pub storage: Mutable<Option<UserStorageUsage>>,
+}
+
+impl MediaLibrary {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self {
+            loader: AsyncLoader::new(),
+            media: MutableVec::new(),
+            storage: Mutable::new(None),
+        })
+    }
+
+    pub fn unused_count_signal(&self) -> impl Signal<Item = usize> {
+        self.media
+            .signal_vec_cloned()
+            .filter(|media| media.is_unreferenced())
+            .len()
+    }
+}
@@ -5,10 +5,12 @@ use crate::{
     email::{confirmation::SendEmailConfirmationPage, verify::VerifyEmailPage},
     likes::Likes,
     login::LoginPage,
+    media::MediaLibrary,
     oauth::dom::OauthPage,
     register::{dom::RegisterPage, state::Step},
     reset_password::PasswordResetPage,
     school_end::SchoolEnd,
+    school_members::SchoolMembers,
     school_start::SchoolStart,
     settings::state::SettingsPage,
     subscribe1::Subscribe1,
@@ -42,6 +44,7 @@ impl Router {
                 UserRoute::Login(query) => Some(LoginPage::new(query).render()),
                 UserRoute::Settings => Some(SettingsPage::new().render()),
                 UserRoute::Likes => Some(Likes::new().render()),
+                UserRoute::Media => Some(MediaLibrary::new().render()),
                 UserRoute::ContinueRegistration(oauth_profile) => Some(RegisterPage::render(
                     Some(Step::One(oauth_profile)),
                     Default::default(),
@@ -59,6 +62,7 @@ impl Router {
                     SchoolStart::new(plan_type, promo).map(|state| state.render())
                 }
                 UserRoute::SchoolEnd => Some(SchoolEnd::new().render()),
+                UserRoute::SchoolMembers => Some(SchoolMembers::new().render()),
                 UserRoute::Subscribe1(plan_type, promo) => {
                     Some(Subscribe1::new(plan_type, promo).render())
                 }
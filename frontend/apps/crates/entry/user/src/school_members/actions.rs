@@ -0,0 +1,139 @@
+use dominator::clone;
+use futures::join;
+use shared::{
+    api::endpoints,
+    domain::{
+        billing::{
+            AccountIfAuthorized, InviteSchoolMembersRequest, RemoveSchoolMemberRequest,
+            SchoolAccountPath, SchoolInviteId, SchoolInvitePath, SchoolInvitesPath,
+            SchoolMemberPath, UpdateSchoolAccountRequest,
+        },
+        user::UserId,
+        UpdateNonNullable,
+    },
+};
+use std::rc::Rc;
+use utils::{bail_on_err, prelude::*, toasts};
+
+use super::state::*;
+
+impl SchoolMembers {
+    pub fn load(self: &Rc<Self>) {
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            state.reload().await;
+        }));
+    }
+
+    async fn reload(self: &Rc<Self>) {
+        let state = self;
+        let (account, invites) = join!(
+            endpoints::account::GetSchoolAccount::api_with_auth(
+                SchoolAccountPath(state.school_id),
+                None,
+            ),
+            endpoints::account::ListSchoolInvites::api_with_auth(
+                SchoolInvitesPath(state.school_id),
+                None,
+            ),
+        );
+
+        let account = bail_on_err!(account.toast_on_err());
+        let invites = bail_on_err!(invites.toast_on_err());
+
+        if let AccountIfAuthorized::Authorized(school_account) = account.account {
+            state.seats.set(school_account.seats);
+        }
+        state
+            .child_privacy_mode
+            .set(account.school.child_privacy_mode);
+        state
+            .session_retention_days
+            .set(account.school.session_retention_days);
+        state.members.lock_mut().replace_cloned(account.users);
+        state.invites.lock_mut().replace_cloned(invites.invites);
+    }
+
+    pub fn send_invites(self: &Rc<Self>) {
+        let state = self;
+        let emails: Vec<String> = state
+            .invite_emails
+            .get_cloned()
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|email| !email.is_empty())
+            .map(String::from)
+            .collect();
+
+        if emails.is_empty() {
+            return;
+        }
+
+        state.loader.load(clone!(state => async move {
+            let res = endpoints::account::InviteSchoolMembers::api_with_auth(
+                SchoolInvitesPath(state.school_id),
+                Some(InviteSchoolMembersRequest { emails }),
+            )
+            .await
+            .toast_on_err();
+            let res = bail_on_err!(res);
+
+            if !res.invites.is_empty() {
+                toasts::success_string(format!("Sent {} invite(s)", res.invites.len()));
+            }
+            state.invite_emails.set(String::new());
+            state.invite_failures.lock_mut().replace_cloned(res.failures);
+
+            state.reload().await;
+        }));
+    }
+
+    pub fn save_privacy(self: &Rc<Self>) {
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            let req = UpdateSchoolAccountRequest {
+                child_privacy_mode: UpdateNonNullable::Change(state.child_privacy_mode.get()),
+                session_retention_days: state.session_retention_days.get().into(),
+                ..Default::default()
+            };
+            let res = endpoints::account::UpdateSchoolAccount::api_with_auth(
+                SchoolAccountPath(state.school_id),
+                Some(req),
+            )
+            .await
+            .toast_on_err();
+            let _ = bail_on_err!(res);
+
+            toasts::success("Privacy settings saved");
+        }));
+    }
+
+    pub fn revoke_invite(self: &Rc<Self>, invite_id: SchoolInviteId) {
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            let res = endpoints::account::RevokeSchoolInvite::api_with_auth(
+                SchoolInvitePath(state.school_id, invite_id),
+                None,
+            )
+            .await
+            .toast_on_err();
+            let _ = bail_on_err!(res);
+
+            state.reload().await;
+        }));
+    }
+
+    pub fn remove_member(self: &Rc<Self>, user_id: UserId) {
+        let state = self;
+        state.loader.load(clone!(state => async move {
+            let res = endpoints::account::RemoveSchoolMember::api_with_auth(
+                SchoolMemberPath(state.school_id, user_id),
+                Some(RemoveSchoolMemberRequest::default()),
+            )
+            .await
+            .toast_on_err();
+            let _ = bail_on_err!(res);
+
+            state.reload().await;
+        }));
+    }
+}
@@ -0,0 +1,204 @@
+use super::state::SchoolMembers;
+use components::page_header::PageHeader;
+use dominator::{clone, html, with_node, DomBuilder};
+use futures_signals::{
+    map_ref,
+    signal::{Signal, SignalExt},
+    signal_vec::SignalVecExt,
+};
+use shared::{config::CHILD_PRIVACY_SESSION_RETENTION_DAYS, domain::billing::AccountSeats};
+use std::rc::Rc;
+use utils::{component::Component, events, gap};
+use web_sys::{HtmlInputElement, HtmlTextAreaElement, ShadowRoot};
+
+impl Component<SchoolMembers> for Rc<SchoolMembers> {
+    fn styles() -> &'static str {
+        include_str!("./styles.css")
+    }
+
+    fn dom(&self, dom: DomBuilder<ShadowRoot>) -> DomBuilder<ShadowRoot> {
+        let state = self;
+        state.load();
+
+        dom.child(PageHeader::new(Default::default()).render())
+            .child(html!("main", {
+                .child(html!("h1", {
+                    .text("School members")
+                }))
+                .child(html!("p", {
+                    .class("seats")
+                    .text_signal(state.seats.signal().map(|seats| {
+                        seats.map(seats_text).unwrap_or_default()
+                    }))
+                }))
+                .child(gap!(24))
+                .child(html!("h3", {
+                    .text("Teachers")
+                }))
+                .child(html!("ul", {
+                    .children_signal_vec(state.members.signal_vec_cloned().map(clone!(state => move |member| {
+                        html!("li", {
+                            .child(html!("span", {
+                                .class("name")
+                                .text(&format!("{} {}", member.user.given_name, member.user.family_name))
+                            }))
+                            .child(html!("span", {
+                                .class("email")
+                                .text(&member.user.email)
+                            }))
+                            .apply(|dom| {
+                                if member.is_admin {
+                                    dom.child(html!("span", {
+                                        .class("admin")
+                                        .text("Admin")
+                                    }))
+                                } else {
+                                    dom.child(html!("button-rect", {
+                                        .prop("kind", "text")
+                                        .prop("color", "red")
+                                        .prop_signal("disabled", state.loader.is_loading())
+                                        .text("Remove")
+                                        .event(clone!(state => move |_: events::Click| {
+                                            state.remove_member(member.user.id);
+                                        }))
+                                    }))
+                                }
+                            })
+                        })
+                    })))
+                }))
+                .child(gap!(24))
+                .child(html!("h3", {
+                    .text("Pending invites")
+                }))
+                .child(html!("ul", {
+                    .children_signal_vec(state.invites.signal_vec_cloned().map(clone!(state => move |invite| {
+                        html!("li", {
+                            .child(html!("span", {
+                                .class("email")
+                                .text(&invite.email)
+                            }))
+                            .child(html!("button-rect", {
+                                .prop("kind", "text")
+                                .prop("color", "red")
+                                .prop_signal("disabled", state.loader.is_loading())
+                                .text("Revoke")
+                                .event(clone!(state => move |_: events::Click| {
+                                    state.revoke_invite(invite.id);
+                                }))
+                            }))
+                        })
+                    })))
+                }))
+                .child(gap!(24))
+                .child(html!("input-wrapper", {
+                    .prop("label", "Invite teachers")
+                    .child(html!("textarea" => HtmlTextAreaElement, {
+                        .with_node!(elem => {
+                            .prop("placeholder", "One email address per line")
+                            .prop_signal("value", state.invite_emails.signal_cloned())
+                            .event(clone!(state => move |_evt: events::Input| {
+                                state.invite_emails.set(elem.value());
+                            }))
+                        })
+                    }))
+                }))
+                .child(html!("ul", {
+                    .class("failures")
+                    .children_signal_vec(state.invite_failures.signal_vec_cloned().map(|failure| {
+                        html!("li", {
+                            .text(&format!("{}: {}", failure.email, failure.reason))
+                        })
+                    }))
+                }))
+                .child(html!("div", {
+                    .class("actions")
+                    .child(html!("button-rect", {
+                        .text("Send invites")
+                        .prop("color", "red")
+                        .prop("kind", "filled")
+                        .prop_signal("disabled", invite_disabled_signal(state))
+                        .event(clone!(state => move |_: events::Click| {
+                            state.send_invites();
+                        }))
+                    }))
+                }))
+                .child(gap!(24))
+                .child(html!("h3", {
+                    .text("Student privacy")
+                }))
+                .child(html!("input-checkbox", {
+                    .prop("label", "Child privacy mode")
+                    .prop_signal("checked", state.child_privacy_mode.signal())
+                    .event(clone!(state => move |evt: events::CustomToggle| {
+                        state.child_privacy_mode.set(evt.value());
+                    }))
+                }))
+                .child(html!("p", {
+                    .class("hint")
+                    .text("Players of your teachers' JIG codes are shown under a pseudonym, \
+                        and their names and IP addresses aren't stored.")
+                }))
+                .child(html!("input-wrapper", {
+                    .prop("label", "Delete play sessions after (days)")
+                    .child(html!("input" => HtmlInputElement, {
+                        .with_node!(elem => {
+                            .prop("type", "number")
+                            .prop("min", 1)
+                            .prop_signal("placeholder", state.child_privacy_mode.signal().map(|child_privacy_mode| {
+                                match child_privacy_mode {
+                                    true => CHILD_PRIVACY_SESSION_RETENTION_DAYS.to_string(),
+                                    false => "Keep forever".to_string(),
+                                }
+                            }))
+                            .prop_signal("value", state.session_retention_days.signal().map(|days| {
+                                days.map(|days| days.to_string()).unwrap_or_default()
+                            }))
+                            .event(clone!(state => move |_: events::Input| {
+                                let days = elem.value().parse::<u16>().ok().filter(|days| *days > 0);
+                                state.session_retention_days.set(days);
+                            }))
+                        })
+                    }))
+                }))
+                .child(html!("div", {
+                    .class("actions")
+                    .child(html!("button-rect", {
+                        .text("Save")
+                        .prop("color", "blue")
+                        .prop("kind", "filled")
+                        .prop_signal("disabled", state.loader.is_loading())
+                        .event(clone!(state => move |_: events::Click| {
+                            state.save_privacy();
+                        }))
+                    }))
+                }))
+            }))
+    }
+}
+
+/// Invites can't be sent while loading, or once every seat is taken.
+fn invite_disabled_signal(state: &Rc<SchoolMembers>) -> impl Signal<Item = bool> {
+    map_ref! {
+        let loading = state.loader.is_loading(),
+        let seats = state.seats.signal() => {
+            *loading || seats.map_or(false, |seats| seats.is_full())
+        }
+    }
+}
+
+fn seats_text(seats: AccountSeats) -> String {
+    match seats.limit {
+        Some(limit) => format!(
+            "{} of {} seats used ({} teachers, {} pending invites)",
+            seats.used(),
+            limit.get(),
+            seats.members,
+            seats.pending_invites,
+        ),
+        None => format!(
+            "{} teachers, {} pending invites",
+            seats.members, seats.pending_invites,
+        ),
+    }
+}
@@ -0,0 +1,37 @@
+use dominator_helpers::futures::AsyncLoader;
+use futures_signals::{signal::Mutable, signal_vec::MutableVec};
+use shared::domain::{
+    admin::InviteSchoolUserFailure,
+    billing::{AccountSeats, AccountUser, SchoolId, SchoolInvite},
+};
+use std::rc::Rc;
+use utils::{prelude::get_school_id, unwrap::UnwrapJiExt};
+
+pub struct SchoolMembers {
+    pub school_id: SchoolId,
+    pub loader: AsyncLoader,
+    pub seats: Mutable<Option<AccountSeats>>,
+    pub members: MutableVec<AccountUser>,
+    pub invites: MutableVec<SchoolInvite>,
+    /// Emails to invite, one per line
+    pub invite_emails: Mutable<String>,
+    /// Emails which couldn't be invited the last time invites were sent
+    pub invite_failures: MutableVec<InviteSchoolUserFailure>,
+    pub child_privacy_mode: Mutable<bool>,
+    pub session_retention_days: Mutable<Option<u16>>,
+}
+impl SchoolMembers {
+    pub fn new() -> Rc<Self> {
+        Rc::new(Self {
+            school_id: get_school_id().unwrap_ji(),
+            loader: AsyncLoader::new(),
+            seats: Mutable::new(None),
+            members: MutableVec::new(),
+            invites: MutableVec::new(),
+            invite_emails: Mutable::new(String::new()),
+            invite_failures: MutableVec::new(),
+            child_privacy_mode: Mutable::new(false),
+            session_retention_days: Mutable::new(None),
+        })
+    }
+}
@@ -9,7 +9,12 @@ use shared::domain::billing::{
 use std::rc::Rc;
 use strum::IntoEnumIterator;
 use utils::prelude::get_user_cloned;
-use utils::{events, js_object, prelude::plan_type_signal, unwrap::UnwrapJiExt};
+use utils::{
+    events, js_object,
+    prelude::plan_type_signal,
+    routes::{Route, UserRoute},
+    unwrap::UnwrapJiExt,
+};
 use wasm_bindgen::JsValue;
 use wasm_bindgen_futures::spawn_local;
 
@@ -53,6 +58,16 @@ impl SettingsPage {
                     None
                 }
             })))
+            .apply_if(is_school_admin(plan_info), |dom| {
+                dom.child(html!("button-rect", {
+                    .style("align-self", "center")
+                    .style("margin-left", "auto")
+                    .prop("kind", "text")
+                    .prop("color", "blue")
+                    .prop("href", Route::User(UserRoute::SchoolMembers).to_string())
+                    .text("Manage teachers")
+                }))
+            })
         })];
 
         plan_items.append(&mut vec![
@@ -473,3 +488,10 @@ fn number_as_price(cents: u32) -> String {
         .as_string()
         .unwrap_ji()
 }
+
+fn is_school_admin(plan_info: &PlanSectionInfo) -> bool {
+    plan_info.individual_or_school.is_school()
+        && get_user_cloned()
+            .and_then(|user| user.account_summary)
+            .map_or(false, |summary| summary.is_admin)
+}
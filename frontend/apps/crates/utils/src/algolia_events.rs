@@ -3,7 +3,9 @@ use serde::Serialize;
 use shared::domain::{jig::JigId, user::UserId};
 use wasm_bindgen_futures::spawn_local;
 
-use crate::{prelude::get_user_id, storage::get_session_storage, unwrap::UnwrapJiExt};
+use crate::{
+    prelude::get_user_id, routes::is_kids_route, storage::get_session_storage, unwrap::UnwrapJiExt,
+};
 
 #[cfg(feature = "release")]
 const JIG_INDEX: &str = "release_jig";
@@ -15,6 +17,11 @@ const ALGOLIA_EVENTS_URL: &str = "https://insights.algolia.io/1/events";
 const UNAUTHENTICATED: &str = "unauthenticated-token";
 
 fn send_event(data: impl Serialize + 'static) {
+    // kids are never tracked
+    if is_kids_route() {
+        return;
+    }
+
     spawn_local(async move {
         fetch_with_headers_and_data(
             ALGOLIA_EVENTS_URL,
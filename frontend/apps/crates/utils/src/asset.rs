@@ -206,6 +206,10 @@ pub struct JigPlayerOptions {
 
     #[serde(default)]
     pub drag_assist: Option<bool>,
+
+    /// Record the score without showing it, see `JigPlayerSettings::hide_score`.
+    #[serde(default)]
+    pub hide_score: bool,
 }
 
 impl Default for JigPlayerOptions {
@@ -219,6 +223,7 @@ impl Default for JigPlayerOptions {
             direction: None,
             scoring: None,
             drag_assist: None,
+            hide_score: false,
         }
     }
 }
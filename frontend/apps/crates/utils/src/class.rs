@@ -0,0 +1,43 @@
+use shared::{config::PICTURE_PASSWORD_CHOICES, domain::class::StudentLoginResponse};
+
+use crate::{storage::get_local_storage, unwrap::UnwrapJiExt};
+
+const STUDENT_LOGIN_STORAGE_NAME: &str = "student-login";
+
+/// Icons shown for each picture of a picture password, indexed by the picture.
+pub const PICTURE_PASSWORD_ICONS: [&str; PICTURE_PASSWORD_CHOICES as usize] = [
+    "fa-solid fa-cat",
+    "fa-solid fa-dog",
+    "fa-solid fa-fish",
+    "fa-solid fa-apple-whole",
+    "fa-solid fa-star",
+    "fa-solid fa-sun",
+    "fa-solid fa-moon",
+    "fa-solid fa-tree",
+    "fa-solid fa-car",
+];
+
+/// The student logged in to their class on this device, if any.
+pub fn load_student_login() -> Option<StudentLoginResponse> {
+    get_local_storage()
+        .unwrap_ji()
+        .get(STUDENT_LOGIN_STORAGE_NAME)
+        .unwrap_ji()
+        .and_then(|login| serde_json::from_str(&login).ok())
+}
+
+pub fn save_student_login(login: &StudentLoginResponse) {
+    let login = serde_json::to_string(login).unwrap_ji();
+
+    get_local_storage()
+        .unwrap_ji()
+        .set(STUDENT_LOGIN_STORAGE_NAME, &login)
+        .unwrap_ji()
+}
+
+pub fn delete_student_login() {
+    get_local_storage()
+        .unwrap_ji()
+        .remove_item(STUDENT_LOGIN_STORAGE_NAME)
+        .unwrap_ji()
+}
@@ -220,6 +220,7 @@ impl EditableJig {
                 direction: self.direction.get(),
                 scoring: self.scoring.get(),
                 drag_assist: self.drag_assist.get(),
+                ..Default::default()
             }),
             other_keywords: Some(self.other_keywords.get_cloned()),
             ..Default::default()
@@ -251,6 +251,9 @@ impl ImageLoad {
 
 temp_make_event!(ImageError, "image-error" => web_sys::Event);
 
+// the native error event, e.g. of an <img> which failed to load
+temp_make_event!(LoadError, "error" => web_sys::Event);
+
 // Custom Direction
 #[derive(Deserialize, Debug)]
 pub struct CustomDirectionData {
@@ -0,0 +1,127 @@
+//! UI strings from the locale service's bundles, as [Fluent](https://projectfluent.org) messages.
+//!
+//! Bundles are loaded with [`load_bundle`] in the user's app language, along with English which
+//! is used for messages that haven't been translated. Messages are then looked up by their key with
+//! [`text`] or [`text_with_args`], which fall back to the key itself if the message isn't found.
+
+use std::cell::RefCell;
+
+use awsm_web::loaders::fetch::fetch_url;
+use fluent::{FluentBundle, FluentResource};
+use shared::api::PathParts;
+use shared::domain::locale::{ExportBundlePath, FALLBACK_LANGUAGE};
+use unic_langid::LanguageIdentifier;
+use uuid::Uuid;
+
+use crate::init::{settings::SETTINGS, user::get_user_cloned};
+use crate::unwrap::UnwrapJiExt;
+
+pub use fluent::{fluent_args, FluentArgs};
+
+thread_local! {
+    /// The bundle in the user's language, if it isn't the fallback language.
+    static USER_BUNDLE: RefCell<Option<FluentBundle<FluentResource>>> = const { RefCell::new(None) };
+    static FALLBACK_BUNDLE: RefCell<FluentBundle<FluentResource>> =
+        RefCell::new(new_bundle(FALLBACK_LANGUAGE));
+}
+
+fn new_bundle(language: &str) -> FluentBundle<FluentResource> {
+    let language: LanguageIdentifier = language.parse().unwrap_or_default();
+
+    let mut bundle = FluentBundle::new(vec![language]);
+    // isolation marks end up as visible characters in inputs and attributes.
+    bundle.set_use_isolating(false);
+    bundle
+}
+
+/// The code of the language the UI is shown in.
+pub fn language() -> String {
+    get_user_cloned()
+        .map(|user| user.language_app)
+        .filter(|language| !language.is_empty())
+        .unwrap_or_else(|| FALLBACK_LANGUAGE.to_owned())
+}
+
+/// Loads the messages of a locale bundle, in the user's language and in the fallback language.
+///
+/// Bundles which fail to load are logged and skipped, since the messages' keys are shown instead.
+pub async fn load_bundle(bundle_id: Uuid) {
+    let language = language();
+
+    if language != FALLBACK_LANGUAGE {
+        if let Some(resource) = fetch_resource(bundle_id, &language).await {
+            USER_BUNDLE.with(|bundle| {
+                bundle
+                    .borrow_mut()
+                    .get_or_insert_with(|| new_bundle(&language))
+                    .add_resource_overriding(resource);
+            });
+        }
+    }
+
+    if let Some(resource) = fetch_resource(bundle_id, FALLBACK_LANGUAGE).await {
+        FALLBACK_BUNDLE.with(|bundle| bundle.borrow_mut().add_resource_overriding(resource));
+    }
+}
+
+async fn fetch_resource(bundle_id: Uuid, language: &str) -> Option<FluentResource> {
+    let url = format!(
+        "{}{}?language={}",
+        SETTINGS.get().unwrap_ji().remote_target.api_url(),
+        ExportBundlePath(bundle_id).get_filled(),
+        language
+    );
+
+    let resp = match fetch_url(&url).await {
+        Ok(resp) if resp.status() == 200 => resp,
+        _ => {
+            log::error!("Couldn't load locale bundle {bundle_id} in {language}");
+            return None;
+        }
+    };
+
+    let ftl = resp.text().await.ok()?;
+
+    match FluentResource::try_new(ftl) {
+        Ok(resource) => Some(resource),
+        Err((resource, errors)) => {
+            log::warn!("Locale bundle {bundle_id} in {language} has errors: {errors:?}");
+            Some(resource)
+        }
+    }
+}
+
+fn format(
+    bundle: &FluentBundle<FluentResource>,
+    id: &str,
+    args: Option<&FluentArgs>,
+) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+
+    let mut errors = Vec::new();
+    let text = bundle.format_pattern(pattern, args, &mut errors);
+
+    if !errors.is_empty() {
+        log::warn!("Couldn't format message {id}: {errors:?}");
+    }
+
+    Some(text.into_owned())
+}
+
+/// The message with the key `id`, formatted with `args`.
+pub fn text_with_args(id: &str, args: Option<&FluentArgs>) -> String {
+    USER_BUNDLE
+        .with(|bundle| {
+            bundle
+                .borrow()
+                .as_ref()
+                .and_then(|bundle| format(bundle, id, args))
+        })
+        .or_else(|| FALLBACK_BUNDLE.with(|bundle| format(&bundle.borrow(), id, args)))
+        .unwrap_or_else(|| id.to_owned())
+}
+
+/// The message with the key `id`.
+pub fn text(id: &str) -> String {
+    text_with_args(id, None)
+}
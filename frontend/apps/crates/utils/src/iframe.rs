@@ -2,7 +2,7 @@ use crate::{keyboard::KeyEvent, unwrap::UnwrapJiExt};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use shared::domain::{
     jig::{
-        codes::JigPlaySessionModule,
+        codes::{JigPlaySessionModule, PointsEarned},
         player::{ModuleConfig, Seconds},
     },
     module::{
@@ -241,4 +241,6 @@ pub enum AssetPlayerToPlayerPopup {
     Close,
     // used for playlist player to hide close button when jig is playing since the jig has it's own button
     CloseButtonShown(bool),
+    // sent by the jig player once the jig was played all the way through, with the session score if it was scored
+    JigFinished(Option<PointsEarned>),
 }
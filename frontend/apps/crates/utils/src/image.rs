@@ -1,6 +1,13 @@
-use crate::unwrap::UnwrapJiExt;
+use crate::{path::image_lib_webp_url, unwrap::UnwrapJiExt};
 use dominator::{html, Dom};
-use shared::domain::module::body::Image;
+use itertools::Itertools;
+use shared::{
+    domain::{
+        image::{ImageFileKind, ImageId},
+        module::body::Image,
+    },
+    media::{ImageWidth, MediaLibrary, PngImageFile, RenditionSize},
+};
 
 pub trait ImageExt {
     fn render(&self, slot: Option<&str>) -> Dom;
@@ -12,9 +19,38 @@ impl ImageExt for Image {
             .prop("id", self.id.0.to_string())
             .prop("lib", self.lib.to_str())
             .prop("kind", self.kind.to_str())
+            .apply_if(self.kind == ImageFileKind::Png, |dom| {
+                // Same size as the PNG so that the image's natural size doesn't change.
+                dom.prop("srcset", image_lib_webp_url(self.lib, RenditionSize::Png(PngImageFile::Resized), self.id))
+            })
             .apply_if(slot.is_some(), |dom| {
                 dom.prop("slot", slot.unwrap_ji())
             })
         })
     }
 }
+
+/// `srcset` for `img-ji` which lets the browser pick the narrowest WebP rendition of the resized
+/// image that's wide enough, e.g. for backgrounds.
+///
+/// `width` is the width of the resized image, see
+/// [`ImageSize::size`](shared::domain::image::ImageSize::size). `img-ji` falls back to the PNG for
+/// images uploaded before the WebP renditions were stored, until they're backfilled.
+pub fn resized_srcset(lib: MediaLibrary, id: ImageId, width: u32) -> String {
+    ImageWidth::ALL
+        .into_iter()
+        .filter(|narrower| narrower.pixels() < width)
+        .map(|narrower| {
+            format!(
+                "{} {}w",
+                image_lib_webp_url(lib, RenditionSize::ResizedWidth(narrower), id),
+                narrower.pixels()
+            )
+        })
+        .chain(std::iter::once(format!(
+            "{} {}w",
+            image_lib_webp_url(lib, RenditionSize::Png(PngImageFile::Resized), id),
+            width
+        )))
+        .join(", ")
+}
@@ -6,6 +6,7 @@ pub mod api_helpers;
 pub mod asset;
 pub mod block_context_menu;
 pub mod callback_future;
+pub mod class;
 pub mod clipboard;
 pub mod colors;
 pub mod component;
@@ -19,6 +20,7 @@ pub mod events;
 pub mod fetch;
 pub mod firebase;
 pub mod fonts;
+pub mod i18n;
 pub mod iframe;
 pub mod image;
 pub mod image_effects;
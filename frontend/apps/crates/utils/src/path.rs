@@ -5,7 +5,7 @@ use shared::{
         image::{ImageFileKind, ImageId},
         pdf::PdfId,
     },
-    media::{media_key, FileKind, MediaLibrary, PngImageFile},
+    media::{media_key, FileKind, MediaLibrary, PngImageFile, RenditionSize},
 };
 
 use crate::unwrap::UnwrapJiExt;
@@ -36,12 +36,31 @@ pub fn audio_lib_url(library_kind: MediaLibrary, id: AudioId) -> String {
     uploads_url(&path)
 }
 
+/// Only audio uploaded to the user library since it started being processed has an Opus rendition.
+pub fn audio_lib_opus_url(library_kind: MediaLibrary, id: AudioId) -> String {
+    let path = media_key(library_kind, id.0, FileKind::AudioOpus);
+
+    uploads_url(&path)
+}
+
 pub fn image_lib_url(library_kind: MediaLibrary, img_kind: PngImageFile, id: ImageId) -> String {
     let path = media_key(library_kind, id.0, FileKind::ImagePng(img_kind));
 
     uploads_url(&path)
 }
 
+pub fn image_lib_webp_url(library_kind: MediaLibrary, size: RenditionSize, id: ImageId) -> String {
+    let path = media_key(library_kind, id.0, FileKind::ImageWebp(size));
+
+    uploads_url(&path)
+}
+
+pub fn image_lib_svg_url(library_kind: MediaLibrary, id: ImageId) -> String {
+    let path = media_key(library_kind, id.0, FileKind::ImageSvg);
+
+    uploads_url(&path)
+}
+
 pub fn module_image_url(
     library_kind: MediaLibrary,
     file_kind: ImageFileKind,
@@ -49,7 +68,10 @@ pub fn module_image_url(
     id: ImageId,
 ) -> String {
     let path = match file_kind {
-        ImageFileKind::Png => media_key(library_kind, id.0, FileKind::ImagePng(img_kind)),
+        // the PNG renditions of SVGs are what can be drawn to a canvas for effects
+        ImageFileKind::Png | ImageFileKind::Svg => {
+            media_key(library_kind, id.0, FileKind::ImagePng(img_kind))
+        }
         ImageFileKind::Gif => media_key(library_kind, id.0, FileKind::AnimationGif),
     };
 
@@ -62,6 +84,16 @@ pub fn pdf_lib_url(library_kind: MediaLibrary, id: PdfId) -> String {
     uploads_url(&path)
 }
 
+/// Preview image of a page of a pdf, `page` starts from 1.
+///
+/// Only the first [`UserPdf::preview_page_count`](shared::domain::pdf::user::UserPdf::preview_page_count)
+/// pages have a preview.
+pub fn pdf_lib_page_url(library_kind: MediaLibrary, id: PdfId, page: u32) -> String {
+    let path = media_key(library_kind, id.0, FileKind::DocumentPdfPage(page));
+
+    uploads_url(&path)
+}
+
 pub fn uploads_url(path: &str) -> String {
     format!(
         "{}/{}",
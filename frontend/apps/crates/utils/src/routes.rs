@@ -7,6 +7,7 @@ use shared::domain::{
     asset::{AssetId, AssetType, DraftOrLive},
     category::CategoryId,
     circle::CircleId,
+    class::ClassId,
     course::{unit::CourseUnitId, CourseId},
     image::{ImageId, ImageSearchQuery},
     jig::JigId,
@@ -134,6 +135,7 @@ pub enum UserRoute {
     NoAuth,
     Settings,
     Likes,
+    Media,
     RegisterOauth(OauthData),
     LoginOauth(OauthData),
     Login(LoginQuery),
@@ -144,6 +146,7 @@ pub enum UserRoute {
     PasswordReset(String),         //the token
     SchoolStart(PlanType, Option<PromotionCode>),
     SchoolEnd,
+    SchoolMembers,
     Subscribe1(PlanType, Option<PromotionCode>),
     Subscribe2(
         PlanType,
@@ -184,11 +187,14 @@ pub enum CommunityCirclesRoute {
 #[derive(Debug, Clone)]
 pub enum KidsRoute {
     StudentCode(Option<String>),
+    ClassLogin,
 }
 
 #[derive(Debug, Clone)]
 pub enum ClassroomRoute {
     Codes(ClassroomCodesRoute),
+    Classes,
+    Class(ClassId),
 }
 
 #[derive(Debug, Clone)]
@@ -216,6 +222,8 @@ pub enum AdminRoute {
     ImageTags,
     ImageMeta(ImageId, bool), //flag is for if it's a new image
     Export,
+    WebMedia,
+    ContentFlags,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -282,6 +290,8 @@ impl AdminRoute {
                 scopes.contains(&UserScope::ManageImage)
             }
             Self::Export => scopes.contains(&UserScope::Admin),
+            Self::WebMedia => scopes.contains(&UserScope::Admin),
+            Self::ContentFlags => scopes.contains(&UserScope::Admin),
         }
     }
 }
@@ -450,7 +460,13 @@ impl Route {
             ["home", "plan", "pro"] => Self::Home(HomeRoute::Plan(HomePlanRoute::Pro)),
             ["home", "plan", "school"] => Self::Home(HomeRoute::Plan(HomePlanRoute::School)),
             ["kids"] => Self::Kids(KidsRoute::StudentCode(None)),
+            ["kids", "class"] => Self::Kids(KidsRoute::ClassLogin),
             ["kids", code] => Self::Kids(KidsRoute::StudentCode(Some(code.to_string()))),
+            ["classroom", "classes"] => Self::Classroom(ClassroomRoute::Classes),
+            ["classroom", "classes", class_id] => {
+                let class_id = ClassId::from_str(class_id).unwrap_ji();
+                Self::Classroom(ClassroomRoute::Class(class_id))
+            }
             ["classroom", "codes"] => {
                 Self::Classroom(ClassroomRoute::Codes(ClassroomCodesRoute::Jigs))
             }
@@ -500,6 +516,7 @@ impl Route {
             ["community", "courses"] => Self::Community(CommunityRoute::Courses),
             ["user", "settings"] => Self::User(UserRoute::Settings),
             ["user", "likes"] => Self::User(UserRoute::Likes),
+            ["user", "media"] => Self::User(UserRoute::Media),
             ["user", "login"] => {
                 let query = serde_qs::from_str(&params_string).unwrap_ji();
                 Self::User(UserRoute::Login(query))
@@ -550,6 +567,7 @@ impl Route {
                 Self::User(UserRoute::SchoolStart(plan_type, promo))
             }
             ["user", "school-end"] => Self::User(UserRoute::SchoolEnd),
+            ["user", "school-members"] => Self::User(UserRoute::SchoolMembers),
             ["user", "subscribe-1", plan_type] => {
                 let promo = params_map.get("promo");
                 let plan_type = (*plan_type).try_into().unwrap_ji();
@@ -634,6 +652,8 @@ impl Route {
                 Self::Admin(AdminRoute::ImageMeta(id, bool::from_str(flag).unwrap_ji()))
             }
             ["admin", "export"] => Self::Admin(AdminRoute::Export),
+            ["admin", "web-media"] => Self::Admin(AdminRoute::WebMedia),
+            ["admin", "content-flags"] => Self::Admin(AdminRoute::ContentFlags),
             ["admin"] => Self::Admin(AdminRoute::Landing),
             // ["jig", "edit", path] => Self::Asset(AssetRoute::RedirectToJig(path.to_string())),
             ["asset", "edit", "studio"] => Self::Asset(AssetRoute::Studio),
@@ -882,6 +902,7 @@ impl From<&Route> for String {
                     Some(code) => format!("/kids/{}", code),
                     None => "/kids".to_string(),
                 },
+                KidsRoute::ClassLogin => "/kids/class".to_string(),
             },
             Route::Classroom(route) => match route {
                 ClassroomRoute::Codes(route) => match route {
@@ -899,6 +920,8 @@ impl From<&Route> for String {
                         )
                     }
                 },
+                ClassroomRoute::Classes => "/classroom/classes".to_string(),
+                ClassroomRoute::Class(class_id) => format!("/classroom/classes/{}", class_id),
             },
             Route::Community(route) => match route {
                 CommunityRoute::Landing => "/community".to_string(),
@@ -927,6 +950,7 @@ impl From<&Route> for String {
             Route::User(route) => match route {
                 UserRoute::Settings => "/user/settings".to_string(),
                 UserRoute::Likes => "/user/likes".to_string(),
+                UserRoute::Media => "/user/media".to_string(),
                 UserRoute::ContinueRegistration(oauth_profile) => match oauth_profile {
                     None => "/user/continue-registration".to_string(),
                     Some(oauth_profile) => {
@@ -961,6 +985,7 @@ impl From<&Route> for String {
                     None => format!("/user/school-start/{}", plan_type.as_str()),
                 },
                 UserRoute::SchoolEnd => "/user/school-end".to_string(),
+                UserRoute::SchoolMembers => "/user/school-members".to_string(),
                 UserRoute::Subscribe1(plan_type, promo) => match promo {
                     Some(promo) => {
                         format!("/user/subscribe-1/{}?promo={}", plan_type.as_str(), promo)
@@ -1029,6 +1054,8 @@ impl From<&Route> for String {
                     format!("/admin/image-meta/{}/{}", id.0, is_new)
                 }
                 AdminRoute::Export => "/admin/export".to_string(),
+                AdminRoute::WebMedia => "/admin/web-media".to_string(),
+                AdminRoute::ContentFlags => "/admin/content-flags".to_string(),
             },
             Route::Asset(route) => match route {
                 AssetRoute::Studio => "/asset/edit/studio".to_string(),
@@ -1137,6 +1164,21 @@ impl From<&Route> for String {
     }
 }
 
+/// Whether the page is part of the kids app, or is framed by it like the JIG player. Third-party
+/// services such as analytics must not be used on these pages.
+pub fn is_kids_route() -> bool {
+    fn is_kids(window: &web_sys::Window) -> bool {
+        // reading the location of a cross-origin frame fails
+        match window.location().href() {
+            Ok(href) => matches!(Route::from_url(&href), Route::Kids(_)),
+            Err(_) => false,
+        }
+    }
+
+    let window = window();
+    is_kids(&window) || matches!(window.top(), Ok(Some(top)) if is_kids(&top))
+}
+
 //todo - rename to get_* for consistency
 pub fn is_param_bool(param: &str) -> bool {
     match get_param(param) {
@@ -1,9 +1,12 @@
 use crate::unwrap::UnwrapJiExt;
+use uuid::Uuid;
 use wasm_bindgen::prelude::*;
 use web_sys::{window, Storage};
 
 pub const CSRF_STORAGE_NAME: &str = "X-CSRF";
 
+const PLAYER_ID_STORAGE_NAME: &str = "jig-code-player-id";
+
 pub fn load_csrf_token() -> Option<String> {
     let res = get_local_storage()
         .unwrap_ji()
@@ -29,6 +32,22 @@ pub fn delete_csrf_token() -> Result<(), JsValue> {
     local_storage.remove_item(CSRF_STORAGE_NAME)
 }
 
+/// Id this device plays JIG codes under when not logged in as a student, if it has one yet.
+pub fn load_player_id() -> Option<Uuid> {
+    get_local_storage()
+        .unwrap_ji()
+        .get(PLAYER_ID_STORAGE_NAME)
+        .unwrap_ji()
+        .and_then(|id| id.parse().ok())
+}
+
+pub fn save_player_id(id: Uuid) {
+    get_local_storage()
+        .unwrap_ji()
+        .set(PLAYER_ID_STORAGE_NAME, &id.to_string())
+        .unwrap_ji()
+}
+
 pub fn get_local_storage() -> Result<Storage, JsValue> {
     window()
         .unwrap_ji()
@@ -2,6 +2,7 @@ import "@elements/module/_common/edit/widgets/audio-input/audio-input-recording"
 import "@elements/module/_common/edit/widgets/audio-input/audio-input-action";
 import "@elements/module/_common/edit/widgets/audio-input/audio-input-delete";
 import "@elements/module/_common/edit/widgets/audio-input/audio-input-icon";
+import "@elements/module/_common/edit/widgets/audio-input/audio-input-waveform";
 import "@elements/module/_common/edit/widgets/audio-input/audio-input";
 import "@elements/core/progress-bar/progress-bar";
 import "@elements/core/inputs/primitives/file";
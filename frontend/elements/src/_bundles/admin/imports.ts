@@ -39,6 +39,8 @@ import "@elements/entry/admin/schools/school-table";
 import "@elements/entry/admin/schools/school-details";
 import "@elements/entry/admin/schools/school-user-table";
 import "@elements/entry/admin/export/export";
+import "@elements/entry/admin/web-media/table";
+import "@elements/entry/admin/content-flags/table";
 import "@elements/entry/home/home/search-results/search-results";
 import "@elements/entry/home/home/search-results/search-results-section";
 import "@elements/core/inputs/wrapper";
@@ -8,3 +8,5 @@ import "@elements/entry/kids/student-code/jigzi";
 import "@elements/entry/kids/student-code/name";
 import "@elements/core/player-popup/player-popup";
 import "@elements/core/buttons/fa-button";
+import "@elements/entry/kids/class-login/class-login";
+import "@elements/entry/kids/class-login/option";
@@ -79,6 +79,17 @@ export class _ extends LitElement {
     @property()
     kind: MediaKindOptions = "png";
 
+    // WebP renditions to prefer over the PNG, e.g. from `utils::image::resized_srcset`
+    @property()
+    srcset: string = "";
+
+    @property()
+    sizes: string = "";
+
+    // images uploaded before WebP renditions were stored don't have any, so fall back to the PNG
+    @property({ type: Boolean })
+    srcsetFailed: boolean = false;
+
     firstUpdated() {
         this.style.setProperty('--border-radius', this.borderRadius);
     }
@@ -90,6 +101,9 @@ export class _ extends LitElement {
         if (changedProperties.has('id') || changedProperties.has('lib') || changedProperties.has('size') || changedProperties.has('kind')) {
             this.fallbackVisible = false;
         }
+        if (changedProperties.has('id') || changedProperties.has('lib') || changedProperties.has('srcset')) {
+            this.srcsetFailed = false;
+        }
     }
 
 
@@ -108,6 +122,11 @@ export class _ extends LitElement {
     }
 
     onError(_evt: Event) {
+        if (this.useSrcset()) {
+            this.srcsetFailed = true;
+            return;
+        }
+
         this.dispatchEvent(
             new Event("image-error", {
                 bubbles: true,
@@ -117,10 +136,16 @@ export class _ extends LitElement {
         this.fallbackVisible = true;
     }
 
+    useSrcset(): boolean {
+        return this.srcset !== "" && this.kind === "png" && !this.srcsetFailed;
+    }
+
     render_image() {
         const { lib, size, id, kind, fallbackVisible, cacheBust, draggable } = this;
 
         let src = imageLib({ lib, size, id, kind });
+        const srcset = this.useSrcset() ? this.srcset : "";
+        const sizes = this.useSrcset() ? this.sizes : "";
 
         if (cacheBust) {
             src += `?cb=${Date.now()}`;
@@ -134,6 +159,8 @@ export class _ extends LitElement {
             if (sameOrigin(src)) {
                 return html`<img
                     .draggable=${draggable}
+                    .srcset="${srcset}"
+                    .sizes="${sizes}"
                     .src="${src}"
                     @error=${this.onError}
                     @load="${this.onLoad}"
@@ -141,6 +168,8 @@ export class _ extends LitElement {
             } else {
                 return html`<img
                     .draggable=${draggable}
+                    .srcset="${srcset}"
+                    .sizes="${sizes}"
                     .src="${src}"
                     crossorigin="anonymous"
                     @error=${this.onError}
@@ -59,6 +59,12 @@ export class _ extends LitElement {
                     display: grid;
                     font-weight: 14px;
                 }
+                ::slotted([slot=assignment-settings]) {
+                    display: grid;
+                    row-gap: 8px;
+                    font-size: 14px;
+                    color: var(--dark-gray-6);
+                }
                 label strong {
                     font-weight: 600;
                 }
@@ -163,6 +169,7 @@ export class _ extends LitElement {
                 <div slot="body" class="body">
                     ${ !this.code ? html`
                         <slot name="name-input"></slot>
+                        <slot name="assignment-settings"></slot>
                         <slot name="gen-code-button"></slot>
                     ` : html`
                         <div class="field-code">
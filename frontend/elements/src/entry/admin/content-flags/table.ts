@@ -0,0 +1,51 @@
+import { LitElement, html, css, customElement, property } from "lit-element";
+
+@customElement("admin-table-content-flags")
+export class _ extends LitElement {
+    static styles = [
+        css`
+            .controls {
+                display: flex;
+                flex-wrap: wrap;
+                align-items: end;
+                grid-gap: 18px;
+                padding: 10px;
+            }
+            .table {
+                display: grid;
+                font-family: sans-serif;
+                border: solid 1px #c4d9f7;
+            }
+            .header-cell {
+                background-color: #f3f8fe;
+                border: solid 1px #eaebef;
+                padding: 5px;
+                color: #2565d5;
+            }
+        `,
+    ];
+
+    @property({ attribute: false })
+    headers: string[] = ["Image", "Item", "Reasons", "Owner", "Flagged", "Status", ""];
+
+    render() {
+        return html`
+            <style>
+                .table {
+                    grid-template-columns: repeat(${this.headers.length}, auto);
+                }
+            </style>
+            <div class="controls">
+                <slot name="controls"></slot>
+            </div>
+            <div class="table">
+                <admin-table-line>
+                    ${this.headers.map(
+                        (header) => html`<div class="header-cell">${header}</div>`
+                    )}
+                </admin-table-line>
+                <slot></slot>
+            </div>
+        `;
+    }
+}
@@ -70,6 +70,7 @@ export class _ extends LitElement {
                 <slot name="open"></slot>
                 <slot name="rating"></slot>
                 <slot name="block"></slot>
+                <slot name="preview"></slot>
             </div>
             <slot name="loader"></slot>
         `;
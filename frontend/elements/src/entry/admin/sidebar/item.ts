@@ -15,7 +15,9 @@ export type ID =
     | "category"
     | "image-tags"
     | "featured-jigs"
-    | "export";
+    | "export"
+    | "web-media"
+    | "content-flags";
 
 const STR_LABEL_LOOKUP: { [key in ID]: string } = {
     "image-add": "Add image",
@@ -32,6 +34,8 @@ const STR_LABEL_LOOKUP: { [key in ID]: string } = {
     "locale": "Localization",
     "featured-jigs": "Featured jigs",
     "export": "Export",
+    "web-media": "Web media",
+    "content-flags": "Content flags",
 };
 
 @customElement("admin-sidebar-item")
@@ -1,4 +1,5 @@
 import { css, customElement, html, LitElement, property } from "lit-element";
+import { nothing } from "lit-html";
 
 @customElement("jig-play-playlist-main")
 export class _ extends LitElement {
@@ -124,6 +125,22 @@ export class _ extends LitElement {
                     place-content: center;
                     font-size: 50px;
                 }
+                .progress {
+                    display: grid;
+                    row-gap: 4px;
+                    color: var(--dark-gray-6);
+                    font-size: 14px;
+                }
+                .progress-bar {
+                    height: 8px;
+                    border-radius: 4px;
+                    background-color: var(--light-blue-3);
+                    overflow: hidden;
+                }
+                .progress-bar-fill {
+                    height: 100%;
+                    background-color: var(--main-blue);
+                }
                 .items-header ::slotted([slot=share]) {
                     color: var(--main-blue);
                     font-size: 18px;
@@ -156,6 +173,24 @@ export class _ extends LitElement {
     @property()
    itemType: string = "";
 
+    // percentage of items completed, negative when progress isn't tracked
+    @property({ type: Number })
+    progress: number = -1;
+
+    renderProgress() {
+        if (this.progress < 0) {
+            return nothing;
+        }
+        return html`
+            <div class="progress">
+                <span>${this.progress}% complete</span>
+                <div class="progress-bar">
+                    <div class="progress-bar-fill" style="width: ${this.progress}%"></div>
+                </div>
+            </div>
+        `;
+    }
+
     render() {
         return html`
             <div class="playlist-info-side">
@@ -177,6 +212,7 @@ export class _ extends LitElement {
                         Created by: ${this.author}
                     </span>
                 </div>
+                ${this.renderProgress()}
                 <p class="description" dir="auto">${this.description}</p>
                 <div class="additional-resources">
                     <h3>Teacher resources</h3>
@@ -0,0 +1,107 @@
+import { LitElement, html, css, customElement, property } from "lit-element";
+
+@customElement("kids-class-login")
+export class _ extends LitElement {
+    static get styles() {
+        return [
+            css`
+                :host {
+                    display: grid;
+                    justify-content: center;
+                    background-color: var(--green-3);
+                    min-height: 100dvh;
+                    width: 100vw;
+                    box-sizing: border-box;
+                }
+                main {
+                    display: grid;
+                    row-gap: 45px;
+                    width: 100vw;
+                    align-content: start;
+                }
+                header {
+                    background-color: white;
+                    height: 88px;
+                    display: grid;
+                    grid-template-columns: auto auto;
+                    align-items: center;
+                    justify-content: space-between;
+                    padding: 0 25px;
+                }
+                h1 {
+                    margin: 0;
+                    margin-top: 80px;
+                    font-size: 32px;
+                    font-weight: 900;
+                    color: var(--dark-blue-4);
+                    text-align: center;
+                }
+                .center {
+                    width: 900px;
+                    max-width: 90vw;
+                    display: grid;
+                    row-gap: 32px;
+                    justify-items: center;
+                    margin: auto;
+                }
+                .options {
+                    display: flex;
+                    flex-wrap: wrap;
+                    justify-content: center;
+                    gap: 24px;
+                }
+                ::slotted(input[slot=content]) {
+                    width: 420px;
+                    max-width: 80vw;
+                    height: 64px;
+                    padding: 0 24px;
+                    box-sizing: border-box;
+                    border-radius: 14px;
+                    border: solid 2px var(--main-blue);
+                    font-size: 32px;
+                    text-align: center;
+                    text-transform: uppercase;
+                }
+                ::slotted([slot=content]) {
+                    font-size: 24px;
+                    color: var(--dark-blue-4);
+                }
+                :host([error]) ::slotted([slot=error]) {
+                    display: block;
+                }
+                ::slotted([slot=error]) {
+                    display: none;
+                    color: var(--dark-red-1);
+                    font-size: 20px;
+                    font-weight: 600;
+                }
+            `,
+        ];
+    }
+
+    @property()
+    heading: string = "";
+
+    @property({ type: Boolean, reflect: true })
+    error: boolean = false;
+
+    render() {
+        return html`
+            <main>
+                <header>
+                    <img-ui path="core/page-header/logo.svg"></img-ui>
+                    <slot name="logout"></slot>
+                </header>
+                <h1>${this.heading}</h1>
+                <div class="center">
+                    <slot name="content"></slot>
+                    <div class="options">
+                        <slot name="option"></slot>
+                    </div>
+                    <slot name="error"></slot>
+                    <slot name="action"></slot>
+                </div>
+            </main>
+        `;
+    }
+}
@@ -0,0 +1,65 @@
+import { LitElement, html, css, customElement, property } from "lit-element";
+import { nothing } from "lit-html";
+
+@customElement("kids-class-login-option")
+export class _ extends LitElement {
+    static get styles() {
+        return [
+            css`
+                :host {
+                    display: grid;
+                    justify-items: center;
+                    align-content: center;
+                    row-gap: 8px;
+                    min-width: 140px;
+                    min-height: 120px;
+                    padding: 16px;
+                    box-sizing: border-box;
+                    border-radius: 14px;
+                    border: solid 2px var(--main-blue);
+                    background-color: var(--white);
+                    cursor: pointer;
+                }
+                :host(:hover) {
+                    border-color: var(--dark-blue-2);
+                }
+                :host([completed]) {
+                    background-color: var(--light-blue-3);
+                }
+                ::slotted(fa-icon) {
+                    font-size: 48px;
+                    color: var(--main-blue);
+                }
+                .label {
+                    font-size: 22px;
+                    font-weight: 600;
+                    color: var(--dark-blue-4);
+                    text-align: center;
+                }
+                .sub-label {
+                    font-size: 16px;
+                    color: var(--dark-gray-6);
+                }
+            `,
+        ];
+    }
+
+    @property()
+    label: string = "";
+
+    @property()
+    subLabel: string = "";
+
+    @property({ type: Boolean, reflect: true })
+    completed: boolean = false;
+
+    render() {
+        return html`
+            <slot></slot>
+            ${this.label ? html`<span class="label">${this.label}</span>` : nothing}
+            ${this.subLabel
+                ? html`<span class="sub-label">${this.subLabel}</span>`
+                : nothing}
+        `;
+    }
+}
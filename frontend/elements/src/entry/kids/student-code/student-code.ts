@@ -44,6 +44,9 @@ export class _ extends LitElement {
                     justify-self: center;
                     width: fit-content;
                 }
+                ::slotted([slot=class-login]) {
+                    justify-self: center;
+                }
                 .jigzi-wrapper {
                     justify-self: center;
                     grid-column: 1;
@@ -65,6 +68,7 @@ export class _ extends LitElement {
                 </header>
                 <h1>${STR_TYPE_THE_CODE}</h1>
                 <slot name="input"></slot>
+                <slot name="class-login"></slot>
             </main>
             <div class="jigzi-wrapper">
                 <slot name="jigzi"></slot>
@@ -0,0 +1,63 @@
+import { LitElement, html, svg, css, customElement, property } from "lit-element";
+
+// peaks are scaled from 0 to 255, the bars are drawn centered in a box this tall
+const HEIGHT = 256;
+const MIN_BAR_HEIGHT = 8;
+
+@customElement("audio-input-waveform")
+export class _ extends LitElement {
+    static get styles() {
+        return [
+            css`
+                :host {
+                    display: block;
+                    width: 75%;
+                    height: 96px;
+                }
+                svg {
+                    display: block;
+                    width: 100%;
+                    height: 100%;
+                }
+                rect {
+                    fill: var(--light-blue-4);
+                }
+                rect.played {
+                    fill: var(--main-blue);
+                }
+            `,
+        ];
+    }
+
+    @property({ attribute: false })
+    peaks: ArrayLike<number> = [];
+
+    // percent of the audio played so far
+    @property({ type: Number })
+    progress: number = 0;
+
+    render() {
+        const peaks = Array.from(this.peaks);
+        const played = (peaks.length * this.progress) / 100;
+
+        return html`
+            <svg
+                viewBox="0 0 ${Math.max(peaks.length, 1)} ${HEIGHT}"
+                preserveAspectRatio="none"
+            >
+                ${peaks.map((peak, i) => {
+                    const height = Math.max(peak, MIN_BAR_HEIGHT);
+                    return svg`
+                        <rect
+                            class=${i < played ? "played" : ""}
+                            x=${i + 0.15}
+                            y=${(HEIGHT - height) / 2}
+                            width="0.7"
+                            height=${height}
+                        ></rect>
+                    `;
+                })}
+            </svg>
+        `;
+    }
+}
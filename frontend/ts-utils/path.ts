@@ -14,7 +14,7 @@ const mediaUploads = (path: string): string => `${MEDIA_UPLOADS}/${path}`;
 
 export type MediaLibOptions = "global" | "user" | "web" | "screenshot" | "mock";
 export type MediaSizeOptions = "original" | "full" | "thumb";
-export type MediaKindOptions = "png" | "gif";
+export type MediaKindOptions = "png" | "gif" | "svg";
 
 const imagePrefix = (lib: MediaLibOptions): string => {
     switch (lib) {
@@ -99,6 +99,11 @@ export const imageLib = ({
         return mediaUploads(`${prefix}/${id}/animation.gif`);
     }
 
+    // SVGs have PNG renditions too, which are lighter for thumbnails
+    if (kind === "svg" && size !== "thumb") {
+        return mediaUploads(`${prefix}/${id}/image.svg`);
+    }
+
     return lib === "mock"
         ? mediaUi(`${prefix}/${variant}/${id}`)
         : mediaUploads(`${prefix}/${id}/${variant + suffix}`);
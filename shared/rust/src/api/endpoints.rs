@@ -77,6 +77,9 @@ pub mod resource;
 /// Circle endpoints
 pub mod circle;
 
+/// Class endpoints
+pub mod class;
+
 /// Module endpoints
 pub mod module;
 
@@ -89,6 +92,12 @@ pub mod billing;
 /// Account endpoints
 pub mod account;
 
+/// Notification endpoints
+pub mod notification;
+
+/// Content moderation endpoints
+pub mod moderation;
+
 /// Item that can be part of PathParts
 pub trait PathPart {
     /// string value to replace placeholder with
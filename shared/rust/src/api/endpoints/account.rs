@@ -1,9 +1,12 @@
 use super::ApiEndpoint;
 use crate::api::Method;
 use crate::domain::billing::{
-    CreateSchoolAccountPath, CreateSchoolAccountRequest, GetSchoolAccountResponse,
-    IndividualAccountPath, IndividualAccountResponse, SchoolAccountPath, SchoolId,
-    UpdateSchoolAccountRequest,
+    AcceptSchoolInvitePath, CreateSchoolAccountPath, CreateSchoolAccountRequest,
+    GetSchoolAccountResponse, IndividualAccountPath, IndividualAccountResponse,
+    InviteSchoolMembersRequest, InviteSchoolMembersResponse, ListSchoolInvitesResponse,
+    ListUserSchoolInvitesResponse, RemoveSchoolMemberRequest, SchoolAccountPath, SchoolId,
+    SchoolInvitePath, SchoolInvitesPath, SchoolMemberPath, UpdateSchoolAccountRequest,
+    UserSchoolInvitePath, UserSchoolInvitesPath,
 };
 use crate::error::AccountError;
 
@@ -56,3 +59,122 @@ impl ApiEndpoint for GetIndividualAccount {
     type Err = AccountError;
     const METHOD: Method = Method::Get;
 }
+
+/// Invite teachers to a school.
+///
+/// Each invite holds one of the seats allowed by the school's plan until it's accepted, declined
+/// or revoked. Emails which can't be invited are returned as failures, including once the school
+/// runs out of seats.
+///
+/// # Authorization
+///
+/// * Account admin or system admin
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the school doesn't exist.
+/// * [`Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't an admin of the school.
+pub struct InviteSchoolMembers;
+impl ApiEndpoint for InviteSchoolMembers {
+    type Path = SchoolInvitesPath;
+    type Req = InviteSchoolMembersRequest;
+    type Res = InviteSchoolMembersResponse;
+    type Err = AccountError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List the pending invites to a school.
+///
+/// # Authorization
+///
+/// * Account admin or system admin
+pub struct ListSchoolInvites;
+impl ApiEndpoint for ListSchoolInvites {
+    type Path = SchoolInvitesPath;
+    type Req = ();
+    type Res = ListSchoolInvitesResponse;
+    type Err = AccountError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Revoke a pending invite to a school, freeing up its seat.
+///
+/// # Authorization
+///
+/// * Account admin or system admin
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if there is no such pending invite.
+pub struct RevokeSchoolInvite;
+impl ApiEndpoint for RevokeSchoolInvite {
+    type Path = SchoolInvitePath;
+    type Req = ();
+    type Res = ();
+    type Err = AccountError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// Remove a teacher from a school, freeing up their seat. The seat can be handed to another
+/// teacher straight away with [`RemoveSchoolMemberRequest::replacement_email`].
+///
+/// # Authorization
+///
+/// * Account admin or system admin
+/// * Any member, to leave the school themselves
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the user isn't a member of the school.
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if the user is the school's last admin, or the
+/// replacement can't be invited.
+pub struct RemoveSchoolMember;
+impl ApiEndpoint for RemoveSchoolMember {
+    type Path = SchoolMemberPath;
+    type Req = RemoveSchoolMemberRequest;
+    type Res = ();
+    type Err = AccountError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// List the pending school invites for the logged in user.
+pub struct ListUserSchoolInvites;
+impl ApiEndpoint for ListUserSchoolInvites {
+    type Path = UserSchoolInvitesPath;
+    type Req = ();
+    type Res = ListUserSchoolInvitesResponse;
+    type Err = AccountError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Accept an invite to a school, making the logged in user a member of it.
+///
+/// An individual account without a valid subscription is replaced by the school account.
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if there is no such pending invite for the user.
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if the user already belongs to a school, has
+/// an individual subscription, hasn't completed their profile, or the school has no seats left.
+pub struct AcceptSchoolInvite;
+impl ApiEndpoint for AcceptSchoolInvite {
+    type Path = AcceptSchoolInvitePath;
+    type Req = ();
+    type Res = ();
+    type Err = AccountError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Decline an invite to a school, freeing up its seat.
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if there is no such pending invite for the user.
+pub struct DeclineSchoolInvite;
+impl ApiEndpoint for DeclineSchoolInvite {
+    type Path = UserSchoolInvitePath;
+    type Req = ();
+    type Res = ();
+    type Err = AccountError;
+    const METHOD: Method = Method::Delete;
+}
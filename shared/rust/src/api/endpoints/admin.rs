@@ -8,6 +8,10 @@ use crate::domain::admin::{
     SetInternalSchoolNamePath, UpdateSchoolNamePath, VerifySchoolRequest,
 };
 use crate::domain::billing::{PlanTier, SchoolName, SchoolNameId, SchoolNameValue};
+use crate::domain::email::{
+    EmailOutboxPath, EmailPreviewPath, EmailPreviewQuery, EmailPreviewResponse,
+    ListEmailOutboxQuery, ListEmailOutboxResponse, RetryEmailPath,
+};
 use crate::domain::user::UserId;
 use crate::domain::UpdateNullable;
 use crate::error::AccountError;
@@ -212,3 +216,42 @@ impl ApiEndpoint for RemoveUserFromSchool {
     type Err = AccountError;
     const METHOD: Method = Method::Post;
 }
+
+/// Render an email template with sample data.
+///
+/// # Errors
+///
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the locale isn't one of the added languages.
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the template doesn't exist.
+pub struct PreviewEmail;
+impl ApiEndpoint for PreviewEmail {
+    type Path = EmailPreviewPath;
+    type Req = EmailPreviewQuery;
+    type Res = EmailPreviewResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// List emails in the outbox, newest first.
+pub struct ListEmailOutbox;
+impl ApiEndpoint for ListEmailOutbox {
+    type Path = EmailOutboxPath;
+    type Req = ListEmailOutboxQuery;
+    type Res = ListEmailOutboxResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Queue an email in the outbox to be sent again, resetting its attempts.
+///
+/// # Errors
+///
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the email doesn't exist or was already sent.
+pub struct RetryEmail;
+impl ApiEndpoint for RetryEmail {
+    type Path = RetryEmailPath;
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
@@ -7,6 +7,7 @@ pub mod user {
                 user::{
                     UserAudioCreatePath, UserAudioDeletePath, UserAudioGetPath, UserAudioListPath,
                     UserAudioListResponse, UserAudioResponse, UserAudioUploadPath,
+                    UserAudioWaveformPath, UserAudioWaveformResponse,
                 },
                 AudioId,
             },
@@ -34,7 +35,24 @@ pub mod user {
         type Err = EmptyError;
         const METHOD: Method = Method::Get;
     }
+    /// Get the waveform peaks of an audio file.
+    ///
+    /// # Errors
+    ///
+    /// * [`NotFound`](http::StatusCode::NOT_FOUND) if the audio file doesn't exist or hasn't been processed yet.
+    pub struct Waveform;
+    impl ApiEndpoint for Waveform {
+        type Path = UserAudioWaveformPath;
+        type Req = ();
+        type Res = UserAudioWaveformResponse;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Get;
+    }
+
     /// Create an audio file.
+    ///
+    /// Audio may be uploaded as MP3, WAV, Ogg, WebM or M4A. It's trimmed of leading and trailing
+    /// silence, normalized to a consistent loudness and stored as MP3 and Opus.
     pub struct Create;
     impl ApiEndpoint for Create {
         type Path = UserAudioCreatePath;
@@ -44,7 +62,7 @@ pub mod user {
         const METHOD: Method = Method::Post;
     }
 
-    /// Upload raw audio bytes.
+    /// Upload raw audio bytes, processed the same way as [`Create`].
     pub struct Upload;
     impl ApiEndpoint for Upload {
         type Path = UserAudioUploadPath;
@@ -1,10 +1,13 @@
 use super::ApiEndpoint;
 use crate::domain::billing::{
-    AdminUpgradeSubscriptionPlanPath, AdminUpgradeSubscriptionPlanRequest,
-    CreateCustomerPortalLinkPath, CreateSetupIntentPath, CreateSetupIntentRequest,
-    SubscriptionCancellationStatusRequest, SubscriptionPauseRequest,
+    AdminCreateInvoicedSubscriptionPath, AdminCreateInvoicedSubscriptionRequest,
+    AdminListSchoolInvoicesPath, AdminMarkInvoicePaidPath, AdminUpgradeSubscriptionPlanPath,
+    AdminUpgradeSubscriptionPlanRequest, CreateCustomerPortalLinkPath, CreateSetupIntentPath,
+    CreateSetupIntentRequest, Invoice, ListInvoicesPath, ListInvoicesResponse,
+    ListWebhookEventsPath, ListWebhookEventsQuery, ListWebhookEventsResponse,
+    ReplayWebhookEventPath, SubscriptionCancellationStatusRequest, SubscriptionPauseRequest,
     UpdateSubscriptionCancellationPath, UpdateSubscriptionPausedPath, UpgradeSubscriptionPlanPath,
-    UpgradeSubscriptionPlanRequest,
+    UpgradeSubscriptionPlanRequest, WebhookEvent,
 };
 use crate::error::BillingError;
 use crate::{
@@ -83,3 +86,111 @@ impl ApiEndpoint for CreateCustomerPortalLink {
     type Err = BillingError;
     const METHOD: Method = Method::Get;
 }
+
+/// List the invoices of the user's account, newest first.
+///
+/// # Authorization
+///
+/// * Any user with an individual account, or an admin of a school account
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the user has no account.
+/// * [`Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't an admin of the school.
+pub struct ListInvoices;
+impl ApiEndpoint for ListInvoices {
+    type Path = ListInvoicesPath;
+    type Req = ();
+    type Res = ListInvoicesResponse;
+    type Err = BillingError;
+    const METHOD: Method = Method::Get;
+}
+
+/// List the invoices of a school, newest first.
+///
+/// # Authorization
+///
+/// * Admin
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the school doesn't exist.
+pub struct AdminListSchoolInvoices;
+impl ApiEndpoint for AdminListSchoolInvoices {
+    type Path = AdminListSchoolInvoicesPath;
+    type Req = ();
+    type Res = ListInvoicesResponse;
+    type Err = BillingError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Subscribe a school which pays by invoice against a purchase order instead of by card.
+///
+/// An invoice is sent to the school at the start of every period, and the subscription is active
+/// straight away. Once the school has paid, an admin marks the invoice as paid with
+/// [`AdminMarkInvoicePaid`].
+///
+/// # Authorization
+///
+/// * Admin
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the school or plan doesn't exist.
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if the plan isn't a school plan, or the school
+///   already has a subscription.
+pub struct AdminCreateInvoicedSubscription;
+impl ApiEndpoint for AdminCreateInvoicedSubscription {
+    type Path = AdminCreateInvoicedSubscriptionPath;
+    type Req = AdminCreateInvoicedSubscriptionRequest;
+    type Res = ();
+    type Err = BillingError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Mark an invoice as paid outside of Stripe, e.g. by bank transfer or check.
+///
+/// # Authorization
+///
+/// * Admin
+pub struct AdminMarkInvoicePaid;
+impl ApiEndpoint for AdminMarkInvoicePaid {
+    type Path = AdminMarkInvoicePaidPath;
+    type Req = ();
+    type Res = Invoice;
+    type Err = BillingError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List Stripe webhook events, newest first.
+///
+/// # Authorization
+///
+/// * Admin
+pub struct ListWebhookEvents;
+impl ApiEndpoint for ListWebhookEvents {
+    type Path = ListWebhookEventsPath;
+    type Req = ListWebhookEventsQuery;
+    type Res = ListWebhookEventsResponse;
+    type Err = BillingError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Handle a Stripe webhook event again, e.g. once whatever made it fail has been fixed.
+///
+/// # Authorization
+///
+/// * Admin
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the event was never received.
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if the event neither failed nor was ignored.
+pub struct ReplayWebhookEvent;
+impl ApiEndpoint for ReplayWebhookEvent {
+    type Path = ReplayWebhookEventPath;
+    type Req = ();
+    type Res = WebhookEvent;
+    type Err = BillingError;
+    const METHOD: Method = Method::Post;
+}
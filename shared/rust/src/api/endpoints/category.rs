@@ -2,9 +2,10 @@ use super::ApiEndpoint;
 use crate::{
     api::method::Method,
     domain::category::{
-        CategoryResponse, CreateCategoryPath, CreateCategoryRequest, DeleteCategoryPath,
-        GetCategoryPath, GetCategoryRequest, NewCategoryResponse, UpdateCategoryPath,
-        UpdateCategoryRequest,
+        CategoryResponse, CategoryUsagePath, CategoryUsageResponse, CreateCategoryPath,
+        CreateCategoryRequest, DeleteCategoryPath, GetCategoryPath, GetCategoryRequest,
+        MergeCategoryPath, MergeCategoryRequest, NewCategoryResponse, RecategorizePath,
+        RecategorizeRequest, RecategorizeResponse, UpdateCategoryPath, UpdateCategoryRequest,
     },
     error::EmptyError,
 };
@@ -60,3 +61,53 @@ impl ApiEndpoint for Delete {
     type Err = EmptyError;
     const METHOD: Method = Method::Delete;
 }
+
+/// Get how many assets use each category.
+///
+/// # Authorization
+/// Standard + [`UserScope::ManageCategory`](crate::domain::user::UserScope).
+pub struct Usage;
+impl ApiEndpoint for Usage {
+    type Path = CategoryUsagePath;
+    type Req = ();
+    type Res = CategoryUsageResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Merge a category into another one, then delete it.
+///
+/// # Authorization
+/// Standard + [`UserScope::ManageCategory`](crate::domain::user::UserScope).
+///
+/// # Errors
+///
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if either category doesn't exist.
+/// * [`UnprocessableEntity`](http::StatusCode::UNPROCESSABLE_ENTITY) if the category would be
+/// merged into itself or one of its descendants.
+pub struct Merge;
+impl ApiEndpoint for Merge {
+    type Path = MergeCategoryPath;
+    type Req = MergeCategoryRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Add and remove categories on many assets at once, or preview doing so.
+///
+/// # Authorization
+/// Standard + [`UserScope::ManageCategory`](crate::domain::user::UserScope).
+///
+/// # Errors
+///
+/// * [`BadRequest`](http::StatusCode::BAD_REQUEST) if a category is both added and removed.
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if a category to add doesn't exist.
+pub struct Recategorize;
+impl ApiEndpoint for Recategorize {
+    type Path = RecategorizePath;
+    type Req = RecategorizeRequest;
+    type Res = RecategorizeResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
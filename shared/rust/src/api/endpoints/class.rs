@@ -0,0 +1,370 @@
+use crate::{
+    api::Method,
+    domain::{
+        class::{
+            Class, ClassCreatePath, ClassCreateRequest, ClassDeletePath, ClassGetPath, ClassId,
+            ClassListPath, ClassListResponse, ClassUpdatePath, ClassUpdateRequest,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// Create a Class owned by the current user.
+///
+/// # Authorization
+/// * TokenUser
+/// * When an `account_id` is given, the user must be a member of the account.
+///
+/// # Errors
+/// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the request is malformed.
+/// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the user isn't a member of the account.
+pub struct Create;
+impl ApiEndpoint for Create {
+    type Req = ClassCreateRequest;
+    type Res = CreateResponse<ClassId>;
+    type Path = ClassCreatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List the current user's Classes.
+///
+/// # Authorization
+/// * TokenUser
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ();
+    type Res = ClassListResponse;
+    type Path = ClassListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Get a Class.
+///
+/// # Authorization
+/// * Owner of the Class
+///
+/// # Errors
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Class doesn't exist or isn't owned by the user.
+pub struct Get;
+impl ApiEndpoint for Get {
+    type Req = ();
+    type Res = Class;
+    type Path = ClassGetPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Update a Class.
+///
+/// # Authorization
+/// * Owner of the Class
+///
+/// # Errors
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Class doesn't exist or isn't owned by the user.
+pub struct Update;
+impl ApiEndpoint for Update {
+    type Req = ClassUpdateRequest;
+    type Res = ();
+    type Path = ClassUpdatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Patch;
+}
+
+/// Delete a Class along with its students and assignments.
+///
+/// Sessions played by the students are kept, but are no longer linked to them.
+///
+/// # Authorization
+/// * Owner of the Class
+///
+/// # Errors
+/// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Class doesn't exist or isn't owned by the user.
+pub struct Delete;
+impl ApiEndpoint for Delete {
+    type Req = ();
+    type Res = ();
+    type Path = ClassDeletePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Delete;
+}
+
+/// Endpoints for managing the students of a Class.
+pub mod student {
+    use crate::{
+        api::{ApiEndpoint, Method},
+        domain::class::{
+            Student, StudentCreatePath, StudentCreateRequest, StudentDataPath, StudentDataResponse,
+            StudentDeletePath, StudentImportPath, StudentImportRequest, StudentImportResponse,
+            StudentListPath, StudentListResponse, StudentUpdatePath, StudentUpdateRequest,
+        },
+        error::EmptyError,
+    };
+
+    /// List the students of a Class.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    pub struct List;
+    impl ApiEndpoint for List {
+        type Req = ();
+        type Res = StudentListResponse;
+        type Path = StudentListPath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Get;
+    }
+
+    /// Add a student to a Class, generating their login code and picture password.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    pub struct Create;
+    impl ApiEndpoint for Create {
+        type Req = StudentCreateRequest;
+        type Res = Student;
+        type Path = StudentCreatePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+
+    /// Add students to a Class from a CSV roster.
+    ///
+    /// Names already in the Class are skipped, so the same roster can be imported again after
+    /// students are added to it.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    ///
+    /// # Errors
+    /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the CSV can't be parsed or has no name column.
+    pub struct Import;
+    impl ApiEndpoint for Import {
+        type Req = StudentImportRequest;
+        type Res = StudentImportResponse;
+        type Path = StudentImportPath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+
+    /// Update a student, optionally resetting their login.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    pub struct Update;
+    impl ApiEndpoint for Update {
+        type Req = StudentUpdateRequest;
+        type Res = ();
+        type Path = StudentUpdatePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Patch;
+    }
+
+    /// Remove a student from a Class.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    pub struct Delete;
+    impl ApiEndpoint for Delete {
+        type Req = ();
+        type Res = ();
+        type Path = StudentDeletePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Delete;
+    }
+
+    /// Export everything stored about a student, e.g. for a parent's access request.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    pub struct ExportData;
+    impl ApiEndpoint for ExportData {
+        type Req = ();
+        type Res = StudentDataResponse;
+        type Path = StudentDataPath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Get;
+    }
+
+    /// Remove a student from a Class along with the JIG code sessions they played, e.g. for a
+    /// parent's deletion request. [`Delete`] keeps their sessions, without linking them to the
+    /// student.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    pub struct DeleteData;
+    impl ApiEndpoint for DeleteData {
+        type Req = ();
+        type Res = ();
+        type Path = StudentDataPath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Delete;
+    }
+}
+
+/// Endpoints for assigning JIGs and Playlists to a Class.
+pub mod assignment {
+    use crate::{
+        api::{ApiEndpoint, Method},
+        domain::{
+            class::{
+                ClassAssignmentCreatePath, ClassAssignmentCreateRequest, ClassAssignmentDeletePath,
+                ClassAssignmentId, ClassAssignmentListPath, ClassAssignmentListResponse,
+                ClassAssignmentUpdatePath, ClassAssignmentUpdateRequest,
+            },
+            CreateResponse,
+        },
+        error::EmptyError,
+    };
+
+    /// List the assignments of a Class.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    pub struct List;
+    impl ApiEndpoint for List {
+        type Req = ();
+        type Res = ClassAssignmentListResponse;
+        type Path = ClassAssignmentListPath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Get;
+    }
+
+    /// Assign a JIG or Playlist to a Class.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    ///
+    /// # Errors
+    /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the asset isn't a JIG or Playlist.
+    /// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the asset isn't the user's own and
+    ///   isn't published for others to use.
+    /// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the Class or asset doesn't exist.
+    pub struct Create;
+    impl ApiEndpoint for Create {
+        type Req = ClassAssignmentCreateRequest;
+        type Res = CreateResponse<ClassAssignmentId>;
+        type Path = ClassAssignmentCreatePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+
+    /// Update an assignment's due date.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    pub struct Update;
+    impl ApiEndpoint for Update {
+        type Req = ClassAssignmentUpdateRequest;
+        type Res = ();
+        type Path = ClassAssignmentUpdatePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Patch;
+    }
+
+    /// Remove an assignment from a Class.
+    ///
+    /// # Authorization
+    /// * Owner of the Class
+    pub struct Delete;
+    impl ApiEndpoint for Delete {
+        type Req = ();
+        type Res = ();
+        type Path = ClassAssignmentDeletePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Delete;
+    }
+}
+
+/// Endpoints for students logging in to the kids app.
+pub mod login {
+    use crate::{
+        api::{ApiEndpoint, Method},
+        domain::{
+            class::{
+                ClassLoginRosterPath, ClassLoginRosterResponse, StudentAssignmentListPath,
+                StudentAssignmentListRequest, StudentAssignmentListResponse,
+                StudentAssignmentPlayPath, StudentAssignmentPlayRequest, StudentLoginPath,
+                StudentLoginRequest, StudentLoginResponse,
+            },
+            jig::codes::instance::PlayerSessionInstanceResponse,
+        },
+        error::EmptyError,
+    };
+
+    /// Look up a Class by its login code, returning the names students pick from.
+    ///
+    /// # Auth
+    /// * No auth
+    ///
+    /// # Errors
+    /// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if no Class has the code.
+    /// * [`429 - TooManyRequests`](http::StatusCode::TOO_MANY_REQUESTS) if too many unknown codes
+    ///   or failed logins came from the same IP address recently.
+    pub struct Roster;
+    impl ApiEndpoint for Roster {
+        type Req = ();
+        type Res = ClassLoginRosterResponse;
+        type Path = ClassLoginRosterPath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Get;
+    }
+
+    /// Log a student in with their own code, or with the class code and their picture password.
+    ///
+    /// # Auth
+    /// * No auth
+    /// * Returns a token to cache and pass along when starting JIG sessions.
+    ///
+    /// # Errors
+    /// * [`401 - Unauthorized`](http::StatusCode::UNAUTHORIZED) if the code or picture password is wrong.
+    /// * [`429 - TooManyRequests`](http::StatusCode::TOO_MANY_REQUESTS) if too many logins failed
+    ///   from the same IP address, or with the same student's picture password, recently.
+    pub struct Login;
+    impl ApiEndpoint for Login {
+        type Req = StudentLoginRequest;
+        type Res = StudentLoginResponse;
+        type Path = StudentLoginPath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+
+    /// List the assignments of a logged in student.
+    ///
+    /// This is a `POST` so that the student's token isn't part of the URL.
+    ///
+    /// # Auth
+    /// * Requires the token returned in [`Login`]
+    pub struct Assignments;
+    impl ApiEndpoint for Assignments {
+        type Req = StudentAssignmentListRequest;
+        type Res = StudentAssignmentListResponse;
+        type Path = StudentAssignmentListPath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+
+    /// Start a session of one of an assignment's JIGs for a logged in student.
+    ///
+    /// The session is recorded under the assignment's code for the JIG, which is created on first
+    /// play, and is linked to the student.
+    ///
+    /// # Auth
+    /// * Requires the token returned in [`Login`]
+    ///
+    /// # Errors
+    /// * [`404 - NotFound`](http::StatusCode::NOT_FOUND) if the assignment isn't given to the
+    /// student's Class, or the JIG isn't part of it.
+    pub struct Play;
+    impl ApiEndpoint for Play {
+        type Req = StudentAssignmentPlayRequest;
+        type Res = PlayerSessionInstanceResponse;
+        type Path = StudentAssignmentPlayPath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
+}
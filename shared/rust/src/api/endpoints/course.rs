@@ -15,6 +15,7 @@ use crate::{
     error::{EmptyError, MetadataNotFound},
 };
 
+pub mod progress;
 pub mod unit;
 
 use super::ApiEndpoint;
@@ -0,0 +1,67 @@
+//! Endpoints for tracking a learner's progress through a Course
+use crate::{
+    api::Method,
+    domain::course::progress::{
+        CourseListInProgressPath, CourseListInProgressRequest, CourseListInProgressResponse,
+        CourseProgressPath, CourseProgressResponse, CourseUnitProgressPath,
+        CourseUnitProgressUpdateRequest,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// Get the current user's progress through a Course.
+///
+/// Includes the completion percentage and the unit to continue from.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+///
+/// * [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the Course doesn't exist.
+pub struct Get;
+impl ApiEndpoint for Get {
+    type Req = ();
+    type Res = CourseProgressResponse;
+    type Path = CourseProgressPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Record that the current user started or completed a Course Unit.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+///
+/// * [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the Course Unit doesn't belong to the Course.
+pub struct UpdateUnit;
+impl ApiEndpoint for UpdateUnit {
+    type Req = CourseUnitProgressUpdateRequest;
+    type Res = ();
+    type Path = CourseUnitProgressPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Put;
+}
+
+/// List the Courses the current user has started, most recently played first.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+///
+/// * [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+pub struct ListInProgress;
+impl ApiEndpoint for ListInProgress {
+    type Req = CourseListInProgressRequest;
+    type Res = CourseListInProgressResponse;
+    type Path = CourseListInProgressPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
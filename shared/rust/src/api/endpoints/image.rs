@@ -12,6 +12,7 @@ use crate::{
     },
 };
 
+pub mod bulk;
 pub mod recent;
 pub mod tag;
 pub mod user;
@@ -0,0 +1,80 @@
+//! Routes to edit many images at once
+
+use super::super::ApiEndpoint;
+use crate::{
+    api::Method,
+    domain::image::bulk::{
+        ImageBulkListPath, ImageBulkListQuery, ImageBulkListResponse, ImageBulkUndoPath,
+        ImageBulkUndoResponse, ImageBulkUpdatePath, ImageBulkUpdateRequest,
+        ImageBulkUpdateResponse,
+    },
+    error::{EmptyError, MetadataNotFound},
+};
+
+/// Apply the same changes to every image matching a query, in one transaction.
+///
+/// The images are queued to be reindexed, and what they looked like before is kept so the
+/// update can be undone with [`Undo`].
+///
+/// # Authorization
+/// Standard + [`UserScope::ManageImage`](crate::domain::user::UserScope)
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+///
+/// [`BadRequest`](http::StatusCode::BAD_REQUEST) if the query has no filters set.
+///
+/// [`UnprocessableEntity`](http::StatusCode::UNPROCESSABLE_ENTITY) if any of the metadata to add doesn't exist.
+pub struct Update;
+
+impl ApiEndpoint for Update {
+    type Path = ImageBulkUpdatePath;
+    type Req = ImageBulkUpdateRequest;
+    type Res = ImageBulkUpdateResponse;
+    type Err = MetadataNotFound;
+    const METHOD: Method = Method::Post;
+}
+
+/// Restore the images edited by a bulk update to what they were before it.
+///
+/// # Authorization
+/// Standard + [`UserScope::ManageImage`](crate::domain::user::UserScope)
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+///
+/// [`NotFound`](http::StatusCode::NOT_FOUND) if the bulk update doesn't exist.
+///
+/// [`Conflict`](http::StatusCode::CONFLICT) if the bulk update was already undone.
+pub struct Undo;
+
+impl ApiEndpoint for Undo {
+    type Path = ImageBulkUndoPath;
+    type Req = ();
+    type Res = ImageBulkUndoResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// List bulk updates, most recent first.
+///
+/// # Authorization
+/// Standard + [`UserScope::ManageImage`](crate::domain::user::UserScope)
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+pub struct List;
+
+impl ApiEndpoint for List {
+    type Path = ImageBulkListPath;
+    type Req = ImageBulkListQuery;
+    type Res = ImageBulkListResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
@@ -9,10 +9,11 @@ use crate::{
             JigFeaturedResponse, JigFeaturedUpdateRequest, JigGetDraftPath, JigGetLivePath, JigId,
             JigLikePath, JigLikedPath, JigLikedResponse, JigPlayPath, JigPublishPath, JigResponse,
             JigSearchPath, JigSearchQuery, JigSearchResponse, JigTransferAdminPath,
-            JigTrendingPath, JigTrendingResponse, JigUnlikePath, JigUpdateAdminDataRequest,
-            JigUpdateDraftDataPath, JigUpdateDraftDataRequest, JigUserPlayPath, ListLikedPath,
-            ListLikedRequest, ListLikedResponse, ListPlayedPath, ListPlayedRequest,
-            ListPlayedResponse,
+            JigTranslatePath, JigTranslateRequest, JigTranslation, JigTranslationId,
+            JigTranslationPath, JigTrendingPath, JigTrendingResponse, JigUnlikePath,
+            JigUpdateAdminDataRequest, JigUpdateDraftDataPath, JigUpdateDraftDataRequest,
+            JigUserPlayPath, ListLikedPath, ListLikedRequest, ListLikedResponse, ListPlayedPath,
+            ListPlayedRequest, ListPlayedResponse,
         },
         CreateResponse,
     },
@@ -215,6 +216,48 @@ impl ApiEndpoint for Clone {
     const METHOD: Method = Method::Post;
 }
 
+/// Clone a JIG, translating the copy into another language.
+///
+/// The copy is made in the background, and its progress can be followed with [`GetTranslation`].
+/// The name, description and the text in each module are translated, and the copy's language and
+/// text direction are set for the target language. Texts which can't be translated are copied
+/// as they were, and returned for review.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminAsset`, or `ManageSelfAsset`
+///
+/// # Errors
+/// * [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+/// * ['NotFound'](http::StatusCode::NOT_FOUND) if the jig does not exist.
+/// * ['BadRequest'](http::StatusCode::BAD_REQUEST) if the language isn't supported, or is the JIG's own language.
+/// * ['NotImplemented'](http::StatusCode::NOT_IMPLEMENTED) if translating is disabled.
+pub struct Translate;
+impl ApiEndpoint for Translate {
+    type Req = JigTranslateRequest;
+    type Res = CreateResponse<JigTranslationId>;
+    type Path = JigTranslatePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Get a translated copy of a JIG, which was started with [`Translate`].
+///
+/// # Authorization
+/// * Only the user who started the translation
+///
+/// # Errors
+/// * [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * ['NotFound'](http::StatusCode::NOT_FOUND) if the translation does not exist, or was started by another user.
+pub struct GetTranslation;
+impl ApiEndpoint for GetTranslation {
+    type Req = ();
+    type Res = JigTranslation;
+    type Path = JigTranslationPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
 /// Delete a JIG.
 ///
 /// # Authorization
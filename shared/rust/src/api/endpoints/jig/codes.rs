@@ -1,10 +1,10 @@
 use crate::{
     api::{ApiEndpoint, Method},
     domain::jig::codes::{
-        JigCodeListPath, JigCodeListRequest, JigCodeListResponse, JigCodePath, JigCodeResponse,
-        JigCodeSessionsListResponse, JigCodeSessionsPath, JigCodeUpdateRequest,
-        JigPlayerSessionCreatePath, JigPlayerSessionCreateRequest, JigPlayerSessionCreateResponse,
-        JigsWithCodesPath, JigsWithCodesResponse,
+        JigCodeListPath, JigCodeListRequest, JigCodeListResponse, JigCodePath, JigCodeReportPath,
+        JigCodeReportResponse, JigCodeResponse, JigCodeSessionsListResponse, JigCodeSessionsPath,
+        JigCodeUpdateRequest, JigPlayerSessionCreatePath, JigPlayerSessionCreateRequest,
+        JigPlayerSessionCreateResponse, JigsWithCodesPath, JigsWithCodesResponse,
     },
     error::EmptyError,
 };
@@ -101,6 +101,23 @@ impl ApiEndpoint for JigCodeSessions {
     const METHOD: Method = Method::Get;
 }
 
+/// Report who completed a code on time, late, or not at all.
+///
+/// Players are told apart by the student they're logged in as, or otherwise by the name they
+/// typed in. Students of classes the code is assigned to are listed even if they never played.
+///
+/// # Errors
+///
+/// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the code doesn't exist or isn't the user's.
+pub struct JigCodeReport;
+impl ApiEndpoint for JigCodeReport {
+    type Path = JigCodeReportPath;
+    type Req = ();
+    type Res = JigCodeReportResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
 /// Endpoints for unauthed users to access jig player sessions.
 pub mod instance {
     use crate::{
@@ -122,6 +139,8 @@ pub mod instance {
     /// # Errors
     ///
     /// * [`400 - BadRequest`](http::StatusCode::BAD_REQUEST) if the request is malformed.
+    /// * [`403 - Forbidden`](http::StatusCode::FORBIDDEN) if the code isn't open yet, or the player
+    /// has used all their attempts.
     /// * ['404 - NotFound'](http::StatusCode::NOT_FOUND) if the jig player session does not exist.
     pub struct Create;
     impl ApiEndpoint for Create {
@@ -84,7 +84,7 @@ pub mod item_kind {
 pub mod bundle {
     use crate::{
         api::{endpoints::ApiEndpoint, Method},
-        domain::locale::{ListBundlePath, ListBundleResponse},
+        domain::locale::{ExportBundlePath, ExportBundleQuery, ListBundlePath, ListBundleResponse},
         error::EmptyError,
     };
 
@@ -97,4 +97,48 @@ pub mod bundle {
         type Err = EmptyError;
         const METHOD: Method = Method::Get;
     }
+
+    /// Export a [`Bundle`](crate::domain::locale::Bundle)'s entries in a language, as a Fluent
+    /// (`.ftl`) file.
+    ///
+    /// Only entries with a key and a translation in the language are exported.
+    pub struct Export;
+    impl ApiEndpoint for Export {
+        type Req = ExportBundleQuery;
+        type Res = ();
+        type Path = ExportBundlePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Get;
+    }
+}
+
+/// [`Language`](crate::domain::locale::Language) endpoints
+pub mod language {
+    use crate::{
+        api::{endpoints::ApiEndpoint, Method},
+        domain::locale::{
+            CreateLanguagePath, CreateLanguageRequest, ListLanguagePath, ListLanguageResponse,
+        },
+        error::EmptyError,
+    };
+
+    /// List [`Language`](crate::domain::locale::Language)s
+    pub struct List;
+    impl ApiEndpoint for List {
+        type Req = ();
+        type Res = ListLanguageResponse;
+        type Path = ListLanguagePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Get;
+    }
+
+    /// Add a [`Language`](crate::domain::locale::Language)
+    pub struct Create;
+    impl ApiEndpoint for Create {
+        type Req = CreateLanguageRequest;
+        type Res = ();
+        type Path = CreateLanguagePath;
+        type Err = EmptyError;
+        const METHOD: Method = Method::Post;
+    }
 }
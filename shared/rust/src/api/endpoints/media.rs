@@ -1,8 +1,9 @@
 use crate::{
     api::Method,
     domain::media::{
-        MediaCreatePath, MediaIdDeletePath, MediaIdGetPath, MediaUrlDeletePath, MediaUrlGetPath,
-        UrlCreatedResponse, WebMediaMetadataResponse, WebMediaUrlCreateRequest,
+        MediaCreatePath, MediaIdDeletePath, MediaIdGetPath, MediaListPath, MediaUrlDeletePath,
+        MediaUrlGetPath, UrlCreatedResponse, WebMediaListQuery, WebMediaListResponse,
+        WebMediaMetadataResponse, WebMediaUrlCreateRequest,
     },
     error::EmptyError,
 };
@@ -40,6 +41,20 @@ impl ApiEndpoint for GetId {
     const METHOD: Method = Method::Get;
 }
 
+/// List the web media library, filtered by kind, date added and uploader.
+///
+/// # Authorization
+///
+/// * Admin
+pub struct List;
+impl ApiEndpoint for List {
+    type Path = MediaListPath;
+    type Req = WebMediaListQuery;
+    type Res = WebMediaListResponse;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
 /// Remove a URL from the web media library.
 pub struct DeleteUrl;
 impl ApiEndpoint for DeleteUrl {
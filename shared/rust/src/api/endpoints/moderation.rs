@@ -0,0 +1,40 @@
+use crate::{
+    api::Method,
+    domain::moderation::{
+        ContentFlagListPath, ContentFlagListQuery, ContentFlagListResponse, ContentFlagReviewPath,
+        ContentFlagReviewRequest,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// List items flagged by the content safety checks, most recent first.
+///
+/// # Authorization
+/// * Admin
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ContentFlagListQuery;
+    type Res = ContentFlagListResponse;
+    type Path = ContentFlagListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Approve or reject a flagged item. Approving releases the item from quarantine.
+///
+/// # Authorization
+/// * Admin
+///
+/// # Errors
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the flag doesn't exist.
+/// * [`Conflict`](http::StatusCode::CONFLICT) if the flag was already reviewed.
+pub struct Review;
+impl ApiEndpoint for Review {
+    type Req = ContentFlagReviewRequest;
+    type Res = ();
+    type Path = ContentFlagReviewPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
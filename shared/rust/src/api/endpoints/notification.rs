@@ -0,0 +1,95 @@
+use crate::{
+    api::Method,
+    domain::notification::{
+        ListNotificationsPath, ListNotificationsQuery, ListNotificationsResponse,
+        MarkAllNotificationsReadPath, MarkNotificationReadPath, NotificationPreferences,
+        NotificationPreferencesPath, UnreadNotificationsCountPath,
+        UnreadNotificationsCountResponse,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// List the current user's notifications, latest first.
+///
+/// Kinds of notifications the user has turned off in app are left out.
+///
+/// # Authorization
+/// * TokenUser
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ListNotificationsQuery;
+    type Res = ListNotificationsResponse;
+    type Path = ListNotificationsPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Count the current user's unread notifications.
+///
+/// # Authorization
+/// * TokenUser
+pub struct UnreadCount;
+impl ApiEndpoint for UnreadCount {
+    type Req = ();
+    type Res = UnreadNotificationsCountResponse;
+    type Path = UnreadNotificationsCountPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Mark one of the current user's notifications as read.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the notification doesn't exist or belongs to someone else.
+pub struct MarkRead;
+impl ApiEndpoint for MarkRead {
+    type Req = ();
+    type Res = ();
+    type Path = MarkNotificationReadPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Mark all of the current user's notifications as read.
+///
+/// # Authorization
+/// * TokenUser
+pub struct MarkAllRead;
+impl ApiEndpoint for MarkAllRead {
+    type Req = ();
+    type Res = ();
+    type Path = MarkAllNotificationsReadPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
+
+/// Get the current user's notification preferences.
+///
+/// # Authorization
+/// * TokenUser
+pub struct GetPreferences;
+impl ApiEndpoint for GetPreferences {
+    type Req = ();
+    type Res = NotificationPreferences;
+    type Path = NotificationPreferencesPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Update the current user's notification preferences.
+///
+/// # Authorization
+/// * TokenUser
+pub struct UpdatePreferences;
+impl ApiEndpoint for UpdatePreferences {
+    type Req = NotificationPreferences;
+    type Res = ();
+    type Path = NotificationPreferencesPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Put;
+}
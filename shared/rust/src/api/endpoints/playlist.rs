@@ -18,6 +18,8 @@ use crate::{
 
 use super::ApiEndpoint;
 
+pub mod progress;
+
 /// Create a Playlist and it's draft and live data copies.
 ///
 /// * New Playlists are all set to `PrivacyLevel::Unlisted` by default
@@ -0,0 +1,67 @@
+//! Endpoints for tracking a learner's progress through a Playlist
+use crate::{
+    api::Method,
+    domain::playlist::progress::{
+        PlaylistItemProgressPath, PlaylistItemProgressUpdateRequest, PlaylistListInProgressPath,
+        PlaylistListInProgressRequest, PlaylistListInProgressResponse, PlaylistProgressPath,
+        PlaylistProgressResponse,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// Get the current user's progress through a Playlist.
+///
+/// Includes the completion percentage and the JIG to continue from.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+///
+/// * [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the Playlist doesn't exist.
+pub struct Get;
+impl ApiEndpoint for Get {
+    type Req = ();
+    type Res = PlaylistProgressResponse;
+    type Path = PlaylistProgressPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Record that the current user started or completed a JIG within a Playlist.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+///
+/// * [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the JIG isn't an item of the Playlist.
+pub struct UpdateItem;
+impl ApiEndpoint for UpdateItem {
+    type Req = PlaylistItemProgressUpdateRequest;
+    type Res = ();
+    type Path = PlaylistItemProgressPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Put;
+}
+
+/// List the Playlists the current user has started, most recently played first.
+///
+/// # Authorization
+/// * TokenUser
+///
+/// # Errors
+///
+/// * [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+pub struct ListInProgress;
+impl ApiEndpoint for ListInProgress {
+    type Req = PlaylistListInProgressRequest;
+    type Res = PlaylistListInProgressResponse;
+    type Path = PlaylistListInProgressPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
@@ -18,6 +18,7 @@ use crate::{
 
 mod colors;
 mod fonts;
+mod media;
 mod profile;
 mod public_user;
 
@@ -29,6 +30,8 @@ pub use fonts::{
     Create as CreateFont, Delete as DeleteFont, Get as GetFonts, Update as UpdateFont,
 };
 
+pub use media::{Delete as DeleteMedia, List as ListMedia};
+
 pub use profile::{
     Create as CreateProfile, Get as Profile, Patch as PatchProfile, PatchProfileAdminData,
 };
@@ -0,0 +1,34 @@
+use super::ApiEndpoint;
+
+use crate::{
+    api::method::Method,
+    domain::user::{
+        UserMediaDeletePath, UserMediaDeleteRequest, UserMediaDeleteResponse, UserMediaListPath,
+        UserMediaListResponse,
+    },
+    error::EmptyError,
+};
+
+/// List every file the user uploaded, with its size and the JIGs using it, and the user's
+/// storage usage.
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ();
+    type Res = UserMediaListResponse;
+    type Path = UserMediaListPath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Get;
+}
+
+/// Delete several of the user's files at once.
+///
+/// Files which are still used by a JIG or anything else are skipped, and returned in
+/// [`in_use`](UserMediaDeleteResponse::in_use).
+pub struct Delete;
+impl ApiEndpoint for Delete {
+    type Req = UserMediaDeleteRequest;
+    type Res = UserMediaDeleteResponse;
+    type Path = UserMediaDeletePath;
+    type Err = EmptyError;
+    const METHOD: Method = Method::Post;
+}
@@ -11,6 +11,19 @@ pub const JIG_PLAYER_SESSION_VALID_DURATION_SECS: u32 = 60 * 60 * 24 * 365;
 /// means 0-9999 are possible. If this is changed then the DB's check constraint must also be updated.
 pub const JIG_PLAYER_SESSION_CODE_MAX: i32 = 999999;
 
+/// Length of the login codes given to classes and students.
+pub const CLASS_LOGIN_CODE_LENGTH: usize = 6;
+
+/// Number of pictures a student picks when logging in with a picture password.
+pub const PICTURE_PASSWORD_LENGTH: usize = 3;
+
+/// Number of pictures a picture password is chosen from. The kids app must have an image for each.
+pub const PICTURE_PASSWORD_CHOICES: u8 = 9;
+
+/// Days JIG code sessions are kept for schools in child privacy mode which haven't set their own
+/// retention period.
+pub const CHILD_PRIVACY_SESSION_RETENTION_DAYS: u16 = 365;
+
 /// Minimum amount of words which should be added to a list for a game.
 pub const MIN_LIST_WORDS: usize = 2;
 
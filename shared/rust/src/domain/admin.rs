@@ -147,6 +147,15 @@ pub enum InviteFailedReason {
     /// The user hasn't completed setting up their profile
     #[strum(serialize = "Incomplete profile")]
     IncompleteProfile,
+    /// The user is already a member of this school
+    #[strum(serialize = "Already a member")]
+    AlreadyMember,
+    /// The user has already been invited to this school
+    #[strum(serialize = "Already invited")]
+    AlreadyInvited,
+    /// The school's plan has no seats left
+    #[strum(serialize = "No seats available")]
+    NoSeatsAvailable,
 }
 
 make_path_parts!(SchoolNamesPath => "/v1/admin/school-names");
@@ -798,3 +798,130 @@ impl From<bool> for DraftOrLive {
         }
     }
 }
+
+/// A learner's progress through a single item of a Course or Playlist.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum ProgressStatus {
+    /// The item was opened but not finished
+    Started = 0,
+    /// The item was played all the way through
+    Completed = 1,
+}
+
+impl ProgressStatus {
+    /// Returns `true` for a [`Self::Completed`] value.
+    pub fn is_completed(&self) -> bool {
+        matches!(*self, ProgressStatus::Completed)
+    }
+}
+
+/// Computes the percentage of completed items, rounded down.
+pub fn progress_percent(completed: u32, total: u32) -> u16 {
+    if total == 0 {
+        return 0;
+    }
+
+    ((completed.min(total) as u64 * 100) / total as u64) as u16
+}
+
+/// Picks the item to continue from: the most recently played item if it isn't completed yet,
+/// otherwise the first incomplete item after it, wrapping around to the start.
+///
+/// Returns the first item if nothing has been played, and `None` once every item is completed.
+pub fn continue_from<T: PartialEq + Copy>(
+    items: &[T],
+    last_played: Option<T>,
+    is_completed: impl Fn(&T) -> bool,
+) -> Option<T> {
+    let start = last_played
+        .and_then(|last_played| items.iter().position(|item| *item == last_played))
+        .unwrap_or(0);
+
+    items[start..]
+        .iter()
+        .chain(items[..start].iter())
+        .find(|item| !is_completed(item))
+        .copied()
+}
+
+/// Progress on a single item of a Course or Playlist, which is summarized by [`ProgressSummary`].
+pub trait ItemProgress {
+    /// ID of the items
+    type Id: Copy + Eq + std::hash::Hash;
+
+    /// The item this progress is for
+    fn item_id(&self) -> Self::Id;
+
+    /// Whether the item has been started or completed
+    fn status(&self) -> ProgressStatus;
+
+    /// When the progress was last updated
+    fn updated_at(&self) -> DateTime<Utc>;
+}
+
+/// A learner's progress through the items of a Course or Playlist.
+#[derive(Debug, Clone)]
+pub struct ProgressSummary<P: ItemProgress> {
+    /// Progress on each item the learner has started, in item order.
+    pub items: Vec<P>,
+
+    /// Number of items
+    pub item_count: u32,
+
+    /// Number of items the learner has completed
+    pub completed_count: u32,
+
+    /// Percentage of items completed, `0..=100`
+    pub percent_complete: u16,
+
+    /// Item to continue from, see [`continue_from`].
+    pub continue_from: Option<P::Id>,
+
+    /// When the learner last made progress
+    pub last_played_at: Option<DateTime<Utc>>,
+}
+
+impl<P: ItemProgress> ProgressSummary<P> {
+    /// Summarizes the `progress` on `items`, which are in order. Progress on items which aren't in
+    /// `items` any more, e.g. because they were removed when the asset was published, is ignored
+    /// apart from when it was made.
+    #[must_use]
+    pub fn new(items: &[P::Id], progress: Vec<P>) -> Self {
+        let last_played_at = progress.iter().map(|item| item.updated_at()).max();
+
+        let mut progress: HashMap<P::Id, P> = progress
+            .into_iter()
+            .map(|item| (item.item_id(), item))
+            .collect();
+
+        let last_played = items
+            .iter()
+            .filter_map(|id| progress.get(id))
+            .max_by_key(|item| item.updated_at())
+            .map(|item| item.item_id());
+
+        let is_completed = |id: &P::Id| {
+            progress
+                .get(id)
+                .map_or(false, |item| item.status().is_completed())
+        };
+
+        let continue_from = continue_from(items, last_played, is_completed);
+
+        let completed_count = items.iter().filter(|id| is_completed(id)).count() as u32;
+
+        let item_count = items.len() as u32;
+
+        Self {
+            items: items.iter().filter_map(|id| progress.remove(id)).collect(),
+            item_count,
+            completed_count,
+            percent_complete: progress_percent(completed_count, item_count),
+            continue_from,
+            last_played_at,
+        }
+    }
+}
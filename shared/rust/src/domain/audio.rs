@@ -40,7 +40,24 @@ pub mod user {
     pub struct UserAudio {
         /// The audio file's ID.
         pub id: AudioId,
-        // more fields to be added
+
+        /// Length of the audio in milliseconds, after leading and trailing silence was trimmed.
+        ///
+        /// `None` until the audio has been processed.
+        #[serde(default)]
+        pub duration_ms: Option<u32>,
+    }
+
+    make_path_parts!(UserAudioWaveformPath => "/v1/user/me/audio/{}/waveform" => AudioId);
+
+    /// Precomputed peaks for drawing an audio file's waveform.
+    #[derive(Serialize, Deserialize, Debug, Clone)]
+    pub struct UserAudioWaveformResponse {
+        /// How many peaks there are for each second of audio.
+        pub peaks_per_second: u32,
+
+        /// The loudest sample of each slice of audio, scaled so that the loudest slice is 255.
+        pub peaks: Vec<u8>,
     }
 
     make_path_parts!(UserAudioUploadPath => "/v1/user/me/audio/{}/raw" => AudioId);
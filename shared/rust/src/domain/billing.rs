@@ -11,9 +11,10 @@ use const_format::formatcp;
 use serde_json::Value;
 
 use crate::api::endpoints::PathPart;
+use crate::domain::admin::InviteSchoolUserFailure;
 use crate::domain::image::ImageId;
 use crate::domain::user::{UserId, UserProfile};
-use crate::domain::{Percent, UpdateNonNullable, UpdateNullable};
+use crate::domain::{ItemCount, Page, PageLimit, Percent, UpdateNonNullable, UpdateNullable};
 
 /// ### Shared billing constants.
 /// (please keep all constants in same place, so that we don't end up duplicates)
@@ -63,6 +64,9 @@ pub const PLAN_PRICE_MONTHLY_SCHOOL_UNLIMITED: u32 = 300_00;
 /// Plan price annual-school-unlimited
 pub const PLAN_PRICE_ANNUAL_SCHOOL_UNLIMITED: u32 = 3_000_00;
 
+/// Days a school has to pay an invoice for a purchase order subscription (net-30)
+pub const PURCHASE_ORDER_DAYS_UNTIL_DUE: u32 = 30;
+
 /// Stripe customer ID
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type), sqlx(transparent))]
@@ -83,6 +87,12 @@ impl From<CustomerId> for stripe::CustomerId {
     }
 }
 
+impl From<String> for CustomerId {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
 impl CustomerId {
     /// Obtain a reference to the inner string
     #[cfg(feature = "backend")]
@@ -250,6 +260,19 @@ impl From<stripe::SubscriptionId> for StripeSubscriptionId {
     }
 }
 
+impl From<String> for StripeSubscriptionId {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl StripeSubscriptionId {
+    /// Obtain a reference to the inner string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
 #[cfg(feature = "backend")]
 impl TryFrom<StripeSubscriptionId> for stripe::SubscriptionId {
     type Error = anyhow::Error;
@@ -269,6 +292,29 @@ impl StripeInvoiceId {
     pub fn inner(&self) -> String {
         self.0.clone()
     }
+
+    /// Obtain a reference to the inner string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for StripeInvoiceId {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for StripeInvoiceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PathPart for StripeInvoiceId {
+    fn get_path_string(&self) -> String {
+        self.0.clone()
+    }
 }
 
 #[cfg(feature = "backend")]
@@ -440,6 +486,11 @@ pub struct Subscription {
     pub price: AmountInCents,
     /// A coupon which may have been applied to the subscription
     pub applied_coupon: Option<AppliedCoupon>,
+    /// The purchase order the subscription is invoiced against, if the school pays by invoice
+    /// instead of by card
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purchase_order: Option<PurchaseOrder>,
     /// When the subscription was originally created.
     pub created_at: DateTime<Utc>,
     /// When the subscription was last updated.
@@ -448,6 +499,15 @@ pub struct Subscription {
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+/// Terms of a subscription which is paid by invoice against a purchase order
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PurchaseOrder {
+    /// The school's purchase order number, shown on every invoice
+    pub purchase_order_number: String,
+    /// Days the school has to pay each invoice
+    pub days_until_due: u32,
+}
+
 /// Details of a coupon applied to a subscription
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AppliedCoupon {
@@ -484,6 +544,8 @@ pub struct CreateSubscriptionRecord {
     pub amount_due_in_cents: Option<AmountInCents>,
     /// Price of the subscription without any discounts applied
     pub price: AmountInCents,
+    /// The purchase order the subscription is invoiced against, if any
+    pub purchase_order: Option<PurchaseOrder>,
 }
 
 /// Data used to update a new subscription record
@@ -653,6 +715,14 @@ impl From<i64> for AccountLimit {
     }
 }
 
+impl AccountLimit {
+    /// The number of accounts allowed
+    #[must_use]
+    pub const fn get(&self) -> i64 {
+        self.0
+    }
+}
+
 /// The type of subscription
 #[derive(Debug, Display, Serialize, Deserialize, Clone, Copy)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type))]
@@ -691,6 +761,20 @@ pub enum PlanTier {
     Pro = 2,
 }
 
+impl PlanTier {
+    /// Most bytes of media (images, audio and pdfs) a user on this tier can store.
+    #[must_use]
+    pub const fn storage_quota_bytes(self) -> u64 {
+        const MB: u64 = 1024 * 1024;
+
+        match self {
+            Self::Free => 250 * MB,
+            Self::Basic => 2 * 1024 * MB,
+            Self::Pro => 20 * 1024 * MB,
+        }
+    }
+}
+
 /// Possible individual subscription plans
 #[derive(
     Debug, Serialize, Deserialize, Clone, Copy, Eq, Ord, PartialOrd, PartialEq, Hash, EnumIter,
@@ -1240,6 +1324,18 @@ impl From<SubscriptionType> for AccountType {
 #[cfg_attr(feature = "backend", derive(sqlx::Type), sqlx(transparent))]
 pub struct InvoiceNumber(String);
 
+impl From<String> for InvoiceNumber {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl Display for InvoiceNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Represents an amount in cents
 #[derive(Debug, Serialize, Deserialize, Clone, Copy)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type), sqlx(transparent))]
@@ -1308,6 +1404,126 @@ pub struct Charge {
     pub amount_in_cents: AmountInCents,
 }
 
+/// Status of an invoice
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum InvoiceStatus {
+    /// Not finalized yet, so it can still change
+    Draft,
+    /// Finalized and waiting to be paid
+    Open,
+    /// Paid, either by card or marked as paid by an admin
+    Paid,
+    /// Unlikely to be paid
+    Uncollectible,
+    /// Canceled
+    Void,
+}
+
+#[cfg(feature = "backend")]
+impl From<stripe::InvoiceStatus> for InvoiceStatus {
+    fn from(value: stripe::InvoiceStatus) -> Self {
+        match value {
+            stripe::InvoiceStatus::Draft => Self::Draft,
+            stripe::InvoiceStatus::Open => Self::Open,
+            stripe::InvoiceStatus::Paid => Self::Paid,
+            stripe::InvoiceStatus::Uncollectible => Self::Uncollectible,
+            stripe::InvoiceStatus::Void => Self::Void,
+        }
+    }
+}
+
+/// How an invoice gets paid
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CollectionMethod {
+    /// The customer's default payment method is charged
+    ChargeAutomatically,
+    /// The invoice is sent to the customer to pay, e.g. against a purchase order
+    SendInvoice,
+}
+
+#[cfg(feature = "backend")]
+impl From<stripe::CollectionMethod> for CollectionMethod {
+    fn from(value: stripe::CollectionMethod) -> Self {
+        match value {
+            stripe::CollectionMethod::ChargeAutomatically => Self::ChargeAutomatically,
+            stripe::CollectionMethod::SendInvoice => Self::SendInvoice,
+        }
+    }
+}
+
+/// An invoice issued to an account
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Invoice {
+    /// Stripe's ID for the invoice
+    pub id: StripeInvoiceId,
+    /// Invoice number, assigned once the invoice is finalized
+    pub number: Option<InvoiceNumber>,
+    /// Status of the invoice
+    pub status: InvoiceStatus,
+    /// How the invoice gets paid
+    pub collection_method: CollectionMethod,
+    /// Amount still to be paid
+    pub amount_due: AmountInCents,
+    /// Amount paid so far
+    pub amount_paid: AmountInCents,
+    /// When the invoice was created
+    pub created_at: DateTime<Utc>,
+    /// When payment is due, for invoices which are sent to the customer
+    pub due_at: Option<DateTime<Utc>>,
+    /// When the invoice was paid
+    pub paid_at: Option<DateTime<Utc>>,
+    /// Link to download the invoice as a PDF, once it's finalized
+    pub pdf_url: Option<String>,
+    /// Link to the page where the invoice can be viewed and paid, once it's finalized
+    pub hosted_invoice_url: Option<String>,
+}
+
+#[cfg(feature = "backend")]
+impl TryFrom<stripe::Invoice> for Invoice {
+    type Error = anyhow::Error;
+
+    fn try_from(value: stripe::Invoice) -> Result<Self, Self::Error> {
+        use chrono::TimeZone;
+
+        let timestamp = |timestamp: stripe::Timestamp| {
+            Utc.timestamp_opt(timestamp, 0)
+                .latest()
+                .ok_or(anyhow::anyhow!("Invalid timestamp"))
+        };
+
+        Ok(Self {
+            id: StripeInvoiceId::from(&value.id),
+            number: value.number.map(InvoiceNumber::from),
+            status: value
+                .status
+                .ok_or(anyhow::anyhow!("Missing invoice status"))?
+                .into(),
+            collection_method: value
+                .collection_method
+                .unwrap_or(stripe::CollectionMethod::ChargeAutomatically)
+                .into(),
+            amount_due: AmountInCents::new(value.amount_remaining.unwrap_or_default()),
+            amount_paid: AmountInCents::new(value.amount_paid.unwrap_or_default()),
+            created_at: timestamp(
+                value
+                    .created
+                    .ok_or(anyhow::anyhow!("Missing invoice creation time"))?,
+            )?,
+            due_at: value.due_date.map(timestamp).transpose()?,
+            paid_at: value
+                .status_transitions
+                .and_then(|transitions| transitions.paid_at)
+                .map(timestamp)
+                .transpose()?,
+            pdf_url: value.invoice_pdf,
+            hosted_invoice_url: value.hosted_invoice_url,
+        })
+    }
+}
+
 wrap_uuid! {
     /// Local subscription plan ID
     pub struct PlanId
@@ -1412,6 +1628,45 @@ pub struct Account {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub updated_at: Option<DateTime<Utc>>,
+    /// Seats taken on the account, for school accounts
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seats: Option<AccountSeats>,
+}
+
+/// Seats taken on a school account, out of the seats allowed by its plan.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+pub struct AccountSeats {
+    /// Number of users associated with the account, including admins
+    pub members: i64,
+    /// Number of invites which haven't been accepted yet. Each of these holds a seat.
+    pub pending_invites: i64,
+    /// The number of seats allowed. [None] means unlimited.
+    ///
+    /// An account without a valid subscription has no seats, unless its tier has been overridden.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<AccountLimit>,
+}
+
+impl AccountSeats {
+    /// Number of seats which are taken by members or held by pending invites
+    #[must_use]
+    pub const fn used(&self) -> i64 {
+        self.members + self.pending_invites
+    }
+
+    /// Number of seats which are still available. [None] means unlimited.
+    #[must_use]
+    pub fn available(&self) -> Option<i64> {
+        self.limit.map(|limit| (limit.get() - self.used()).max(0))
+    }
+
+    /// Whether no more users can be invited to the account
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.available() == Some(0)
+    }
 }
 
 /// Summary of the user's account. This could be a school account that a user is a member of.
@@ -1488,6 +1743,19 @@ pub struct School {
     /// The school's account ID
     pub account_id: AccountId,
 
+    /// Whether players of the school's JIG codes are only stored under a pseudonym, and their
+    /// sessions are purged after [`CHILD_PRIVACY_SESSION_RETENTION_DAYS`] unless
+    /// `session_retention_days` is set.
+    ///
+    /// [`CHILD_PRIVACY_SESSION_RETENTION_DAYS`]: crate::config::CHILD_PRIVACY_SESSION_RETENTION_DAYS
+    #[serde(default)]
+    pub child_privacy_mode: bool,
+
+    /// Days after which sessions of the school's JIG codes are deleted.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_retention_days: Option<u16>,
+
     /// When the school was created.
     pub created_at: DateTime<Utc>,
 
@@ -1707,8 +1975,104 @@ pub struct UpdateSchoolAccountRequest {
     /// Organization type
     #[serde(default, skip_serializing_if = "UpdateNullable::is_keep")]
     pub organization_type: UpdateNullable<String>,
+
+    /// See [`School::child_privacy_mode`].
+    #[serde(default, skip_serializing_if = "UpdateNonNullable::is_keep")]
+    pub child_privacy_mode: UpdateNonNullable<bool>,
+
+    /// See [`School::session_retention_days`].
+    #[serde(default, skip_serializing_if = "UpdateNullable::is_keep")]
+    pub session_retention_days: UpdateNullable<u16>,
+}
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents the ID of an invite to a school.
+    pub struct SchoolInviteId
+}
+
+/// An invite to join a school which hasn't been accepted yet.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SchoolInvite {
+    /// The invite's ID
+    pub id: SchoolInviteId,
+    /// Email address the invite was sent to
+    pub email: String,
+    /// The user who sent the invite, if they still exist
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invited_by: Option<UserId>,
+    /// When the invite was sent
+    pub created_at: DateTime<Utc>,
+}
+
+make_path_parts!(SchoolInvitesPath => "/v1/schools/{}/invites" => SchoolId);
+
+/// Request to invite teachers to a school. Each invite holds a seat until it's accepted, declined
+/// or revoked.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InviteSchoolMembersRequest {
+    /// Emails of the teachers to invite. They don't need to have signed up yet.
+    pub emails: Vec<String>,
+}
+
+/// Response for inviting teachers to a school
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InviteSchoolMembersResponse {
+    /// Invites which were sent
+    pub invites: Vec<SchoolInvite>,
+    /// Emails which couldn't be invited and the reasons
+    pub failures: Vec<InviteSchoolUserFailure>,
+}
+
+/// Pending invites to a school
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListSchoolInvitesResponse {
+    /// Invites which haven't been accepted yet, oldest first
+    pub invites: Vec<SchoolInvite>,
+}
+
+make_path_parts!(SchoolInvitePath => "/v1/schools/{}/invites/{}" => SchoolId, SchoolInviteId);
+
+make_path_parts!(SchoolMemberPath => "/v1/schools/{}/members/{}" => SchoolId, UserId);
+
+/// Request to remove a teacher from a school.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct RemoveSchoolMemberRequest {
+    /// Email of a teacher to invite into the seat which is freed up.
+    ///
+    /// The seat is handed over as part of the removal, so this works even when the school has no
+    /// other seats available.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_email: Option<String>,
+}
+
+/// An invite to join a school, as seen by the invited user.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UserSchoolInvite {
+    /// The invite's ID
+    pub id: SchoolInviteId,
+    /// The school the user has been invited to
+    pub school_id: SchoolId,
+    /// Name of the school
+    pub school_name: String,
+    /// When the invite was sent
+    pub created_at: DateTime<Utc>,
+}
+
+make_path_parts!(UserSchoolInvitesPath => "/v1/user/me/school-invites");
+
+/// Pending invites for the current user
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListUserSchoolInvitesResponse {
+    /// Invites which haven't been accepted yet, oldest first
+    pub invites: Vec<UserSchoolInvite>,
 }
 
+make_path_parts!(UserSchoolInvitePath => "/v1/user/me/school-invites/{}" => SchoolInviteId);
+
+make_path_parts!(AcceptSchoolInvitePath => "/v1/user/me/school-invites/{}/accept" => SchoolInviteId);
+
 make_path_parts!(IndividualAccountPath => "/v1/user/me/account");
 
 /// Individual account response
@@ -1773,3 +2137,143 @@ make_path_parts!(UpgradeSubscriptionPlanPath => "/v1/billing/subscription/upgrad
 make_path_parts!(AdminUpgradeSubscriptionPlanPath => "/v1/admin/billing/subscription/upgrade");
 
 make_path_parts!(CreateCustomerPortalLinkPath => "/v1/billing/customer-portal");
+
+make_path_parts!(ListInvoicesPath => "/v1/billing/invoices");
+
+/// Invoices for an account, newest first
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListInvoicesResponse {
+    /// The invoices
+    pub invoices: Vec<Invoice>,
+}
+
+make_path_parts!(AdminListSchoolInvoicesPath => "/v1/admin/schools/{}/invoices" => SchoolId);
+
+make_path_parts!(AdminCreateInvoicedSubscriptionPath => "/v1/admin/schools/{}/invoiced-subscription" => SchoolId);
+
+/// Request to create a subscription for a school which pays by invoice against a purchase order
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AdminCreateInvoicedSubscriptionRequest {
+    /// The school plan to subscribe to
+    pub plan_type: PlanType,
+    /// The school's purchase order number
+    pub purchase_order_number: String,
+    /// Days the school has to pay each invoice. Defaults to [`PURCHASE_ORDER_DAYS_UNTIL_DUE`].
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub days_until_due: Option<u32>,
+}
+
+make_path_parts!(AdminMarkInvoicePaidPath => "/v1/admin/billing/invoices/{}/paid" => StripeInvoiceId);
+
+/// Stripe event ID
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type), sqlx(transparent))]
+pub struct StripeEventId(String);
+
+impl StripeEventId {
+    /// Obtain a reference to the inner string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for StripeEventId {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(feature = "backend")]
+impl From<&stripe::EventId> for StripeEventId {
+    fn from(value: &stripe::EventId) -> Self {
+        Self(value.as_str().to_owned())
+    }
+}
+
+impl fmt::Display for StripeEventId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PathPart for StripeEventId {
+    fn get_path_string(&self) -> String {
+        self.0.clone()
+    }
+}
+
+/// Processing status of a Stripe webhook event
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[repr(i16)]
+pub enum WebhookEventStatus {
+    /// Received but not handled yet
+    Pending = 0,
+    /// Handled successfully. Redeliveries of the event are skipped.
+    Processed = 1,
+    /// Handling failed. The event is handled again if Stripe redelivers it or an admin replays it.
+    Failed = 2,
+    /// Not an event we handle. Redeliveries of the event are skipped.
+    Ignored = 3,
+}
+
+/// A Stripe webhook event in the event log
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookEvent {
+    /// Stripe's ID for the event
+    pub id: StripeEventId,
+    /// Type of the event, e.g. `customer.subscription.updated`
+    pub event_type: String,
+    /// The event as Stripe sent it
+    pub payload: Value,
+    /// Processing status
+    pub status: WebhookEventStatus,
+    /// Number of times the event has been handled
+    pub attempts: u16,
+    /// Error returned the last time handling the event failed
+    pub error: Option<String>,
+    /// When the event was first received
+    pub created_at: DateTime<Utc>,
+    /// When the event was last handled
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(ListWebhookEventsPath => "/v1/admin/billing/webhook-events");
+
+/// Query for listing webhook events
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhookEventsQuery {
+    /// Only list events with this status
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<WebhookEventStatus>,
+    /// Only list events of this type
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    /// Current page of results
+    #[serde(default)]
+    pub page: Page,
+    /// Events per page to return
+    #[serde(default)]
+    pub page_limit: PageLimit,
+}
+
+/// Webhook events, newest first
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhookEventsResponse {
+    /// Events on this page
+    pub events: Vec<WebhookEvent>,
+    /// Count of pages
+    pub pages: ItemCount,
+    /// Total count of events for this query
+    pub total_count: ItemCount,
+}
+
+make_path_parts!(ReplayWebhookEventPath => "/v1/admin/billing/webhook-events/{}/replay" => StripeEventId);
@@ -1,11 +1,16 @@
 //! Types for categories.
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{api::endpoints::PathPart, domain::user::UserScope};
+use crate::{
+    api::endpoints::PathPart,
+    domain::{asset::AssetId, user::UserScope},
+};
 
 wrap_uuid! {
     /// Wrapper type around [`Uuid`], represents the ID of a category.
@@ -33,6 +38,11 @@ pub struct Category {
     /// The category's name.
     pub name: String,
 
+    /// The category's name in other languages, keyed by language code.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub translated_name: HashMap<String, String>,
+
     /// The category's children, if any.
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -140,7 +150,12 @@ pub struct UpdateCategoryRequest {
     /// If [`Some`] change the category's name to this name
     pub name: Option<String>,
 
-    /// If [`Some`], change the parent to the given `Option<CategoryId>`.
+    /// If [`Some`] replace the category's names in other languages, keyed by language code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub translated_name: Option<HashMap<String, String>>,
+
+    /// If [`Some`], change the parent to the given `Option<CategoryId>`, moving the category's whole subtree.
     ///
     /// Specifically, if [`None`], don't update.
     /// If `Some(None)`, set the parent to [`None`].
@@ -162,3 +177,96 @@ pub struct UpdateCategoryRequest {
 }
 
 make_path_parts!(DeleteCategoryPath => "/v1/category/{}" => CategoryId);
+
+make_path_parts!(CategoryUsagePath => "/v1/category/usage");
+
+#[derive(Serialize, Deserialize, Debug)]
+/// The response returned when a request for category usage is successful.
+pub struct CategoryUsageResponse {
+    /// Usage of every category, in no particular order.
+    pub usage: Vec<CategoryUsage>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+/// How many assets use a category.
+///
+/// An asset is counted if either its draft or its live version has the category.
+pub struct CategoryUsage {
+    /// The category's id.
+    pub id: CategoryId,
+
+    /// Number of JIGs in the category.
+    pub jigs: u64,
+
+    /// Number of resources in the category.
+    pub resources: u64,
+
+    /// Number of playlists in the category.
+    pub playlists: u64,
+
+    /// Number of courses in the category.
+    pub courses: u64,
+
+    /// Number of library images in the category.
+    pub images: u64,
+}
+
+make_path_parts!(MergeCategoryPath => "/v1/category/{}/merge" => CategoryId);
+
+#[derive(Serialize, Deserialize, Debug)]
+/// Request to merge a category into another one.
+///
+/// Every asset and image in the merged category is moved to `into`, its children are appended to
+/// `into`'s children, and then it's deleted.
+pub struct MergeCategoryRequest {
+    /// The category to merge into.
+    pub into: CategoryId,
+}
+
+make_path_parts!(RecategorizePath => "/v1/category/recategorize");
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+/// Request to add and remove categories on many assets at once.
+///
+/// Changes are made to both the draft and live versions of each asset.
+pub struct RecategorizeRequest {
+    /// The assets to recategorize.
+    #[serde(default)]
+    pub assets: Vec<AssetId>,
+
+    /// If [`Some`], also recategorize every asset in this category.
+    #[serde(default)]
+    pub in_category: Option<CategoryId>,
+
+    /// Categories to add to the assets.
+    #[serde(default)]
+    pub add: Vec<CategoryId>,
+
+    /// Categories to remove from the assets.
+    #[serde(default)]
+    pub remove: Vec<CategoryId>,
+
+    /// If `true`, only report what would change.
+    #[serde(default)]
+    pub preview: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+/// Response for recategorizing assets.
+pub struct RecategorizeResponse {
+    /// The assets whose categories changed, or would change for a preview.
+    pub changed: Vec<RecategorizedAsset>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+/// The categories added to and removed from an asset.
+pub struct RecategorizedAsset {
+    /// The asset's id.
+    pub asset_id: AssetId,
+
+    /// Categories added to the draft or live version of the asset.
+    pub added: Vec<CategoryId>,
+
+    /// Categories removed from the draft or live version of the asset.
+    pub removed: Vec<CategoryId>,
+}
@@ -0,0 +1,537 @@
+//! Types for classes, their student rosters and assignments.
+//!
+//! A class is owned by a teacher, optionally under a school [`Account`](super::billing::Account).
+//! Students in a class log in to the kids app with either their own [`LoginCode`], or with the
+//! class's code followed by their [`PicturePassword`]. JIG sessions played by a logged in student
+//! are linked to their [`StudentId`] instead of a typed name.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::endpoints::PathPart,
+    config::{PICTURE_PASSWORD_CHOICES, PICTURE_PASSWORD_LENGTH},
+};
+
+use super::{
+    asset::AssetId,
+    billing::AccountId,
+    jig::{
+        codes::{JigCode, JigCodeSessionResponse},
+        JigId,
+    },
+    user::UserId,
+    UpdateNullable,
+};
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents the ID of a Class.
+    pub struct ClassId
+}
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents the ID of a Student in a Class.
+    pub struct StudentId
+}
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents the ID of an assignment given to a Class.
+    pub struct ClassAssignmentId
+}
+
+/// Short code used to log in to the kids app.
+///
+/// Every class has one, which lets students pick their name and enter their picture password.
+/// Every student also has their own, which logs them in directly.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, PathPart)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[cfg_attr(feature = "backend", sqlx(transparent))]
+pub struct LoginCode(pub String);
+
+impl LoginCode {
+    /// Characters login codes are made of. Letters and digits which are easily confused when
+    /// read aloud or written by hand are left out.
+    pub const ALPHABET: &'static [u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+    /// Normalizes a code typed in by a student, ignoring case and surrounding whitespace.
+    pub fn normalized(code: &str) -> Self {
+        Self(code.trim().to_uppercase())
+    }
+}
+
+/// A sequence of pictures a student picks to log in, stored as indices into the pictures shown by
+/// the kids app.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PicturePassword(pub Vec<i16>);
+
+impl PicturePassword {
+    /// Whether the password has the expected length and only uses available pictures.
+    pub fn is_valid(&self) -> bool {
+        self.0.len() == PICTURE_PASSWORD_LENGTH
+            && self
+                .0
+                .iter()
+                .all(|picture| (0..PICTURE_PASSWORD_CHOICES as i16).contains(picture))
+    }
+}
+
+/// A class owned by a teacher.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Class {
+    /// The ID of the Class.
+    pub id: ClassId,
+
+    /// The Class's name.
+    pub display_name: String,
+
+    /// The teacher who owns the Class.
+    pub owner_id: UserId,
+
+    /// The school account the Class belongs to, if any.
+    pub account_id: Option<AccountId>,
+
+    /// Code students enter before picking their name and picture password.
+    pub login_code: LoginCode,
+
+    /// Number of students in the Class.
+    pub student_count: u32,
+
+    /// When the Class was created.
+    pub created_at: DateTime<Utc>,
+
+    /// When the Class was last updated.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(ClassCreatePath => "/v1/class");
+
+/// Request to create a new Class.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassCreateRequest {
+    /// The Class's name.
+    pub display_name: String,
+
+    /// The school account to create the Class under. The teacher must be a member of the account.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<AccountId>,
+}
+
+make_path_parts!(ClassListPath => "/v1/class");
+
+/// Response for listing the current user's Classes.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassListResponse {
+    /// The classes, most recently created first.
+    pub classes: Vec<Class>,
+}
+
+make_path_parts!(ClassGetPath => "/v1/class/{}" => ClassId);
+
+make_path_parts!(ClassUpdatePath => "/v1/class/{}" => ClassId);
+
+/// Request to update a Class.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassUpdateRequest {
+    /// The Class's name.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+
+    /// Generate a new login code for the Class.
+    #[serde(default)]
+    pub reset_login_code: bool,
+}
+
+make_path_parts!(ClassDeletePath => "/v1/class/{}" => ClassId);
+
+/// A student in a Class.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Student {
+    /// The ID of the Student.
+    pub id: StudentId,
+
+    /// The Class the Student is in.
+    pub class_id: ClassId,
+
+    /// The Student's name as shown to the teacher and on the class login screen.
+    pub display_name: String,
+
+    /// Code the Student can log in with directly.
+    pub login_code: LoginCode,
+
+    /// Pictures the Student picks to log in after entering the class code.
+    pub picture_password: PicturePassword,
+
+    /// When the Student was added.
+    pub created_at: DateTime<Utc>,
+}
+
+make_path_parts!(StudentListPath => "/v1/class/{}/students" => ClassId);
+
+/// Response for listing the Students in a Class.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentListResponse {
+    /// The students, ordered by name.
+    pub students: Vec<Student>,
+}
+
+make_path_parts!(StudentCreatePath => "/v1/class/{}/students" => ClassId);
+
+/// Request to add a Student to a Class. The login code and picture password are generated.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentCreateRequest {
+    /// The Student's name.
+    pub display_name: String,
+}
+
+make_path_parts!(StudentImportPath => "/v1/class/{}/students/import" => ClassId);
+
+/// Request to add Students to a Class from a CSV roster.
+///
+/// See [`parse_roster_csv`] for the expected format.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentImportRequest {
+    /// Contents of the CSV file.
+    pub csv: String,
+}
+
+/// Response for importing Students from a CSV roster.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentImportResponse {
+    /// The students that were added.
+    pub students: Vec<Student>,
+
+    /// Names from the roster that were skipped because a student with the same name is already
+    /// in the class.
+    pub skipped: Vec<String>,
+}
+
+make_path_parts!(StudentUpdatePath => "/v1/class/{}/students/{}" => ClassId, StudentId);
+
+/// Request to update a Student.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentUpdateRequest {
+    /// The Student's name.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+
+    /// Generate a new login code and picture password for the Student.
+    #[serde(default)]
+    pub reset_login: bool,
+}
+
+make_path_parts!(StudentDeletePath => "/v1/class/{}/students/{}" => ClassId, StudentId);
+
+make_path_parts!(StudentDataPath => "/v1/class/{}/students/{}/data" => ClassId, StudentId);
+
+/// A Student as included in their exported data, without the credentials they log in with.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentRecord {
+    /// The ID of the Student.
+    pub id: StudentId,
+
+    /// The Class the Student is in.
+    pub class_id: ClassId,
+
+    /// The Student's name as shown to the teacher and on the class login screen.
+    pub display_name: String,
+
+    /// When the Student was added.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Everything stored about a Student, exported for their teacher or parents.
+///
+/// Their login code and picture password are left out, as the export may be passed on.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentDataResponse {
+    /// The Student.
+    pub student: StudentRecord,
+
+    /// The JIG code sessions the Student played while logged in to their class, oldest first.
+    pub sessions: Vec<JigCodeSessionResponse>,
+}
+
+/// Errors from parsing a CSV roster.
+#[derive(Debug, thiserror::Error)]
+pub enum RosterCsvError {
+    /// The file could not be read as CSV.
+    #[error("Invalid CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    /// None of the header columns hold a student's name.
+    #[error("Missing a `name` column, or `first name` and `last name` columns")]
+    MissingNameColumn,
+}
+
+/// Parses student names from a CSV roster.
+///
+/// The first row is a header. Names are read from a `name` (or `student name`, `display name`)
+/// column, or otherwise from `first name` (`given name`) and `last name` (`family name`) columns.
+/// Headers are matched ignoring case, and other columns are ignored. Rows without a name are
+/// skipped.
+pub fn parse_roster_csv(csv: &str) -> Result<Vec<String>, RosterCsvError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(csv.as_bytes());
+
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|header| header.to_lowercase().replace(['_', '-'], " "))
+        .collect();
+
+    let column = |names: &[&str]| {
+        headers
+            .iter()
+            .position(|header| names.contains(&header.as_str()))
+    };
+
+    let name = column(&["name", "student name", "display name", "full name"]);
+    let given_name = column(&["first name", "given name", "first"]);
+    let family_name = column(&["last name", "family name", "surname", "last"]);
+
+    if name.is_none() && given_name.is_none() {
+        return Err(RosterCsvError::MissingNameColumn);
+    }
+
+    let mut names = Vec::new();
+
+    for record in reader.records() {
+        let record = record?;
+        let field = |index: Option<usize>| index.and_then(|index| record.get(index)).unwrap_or("");
+
+        let display_name = match name {
+            Some(_) => field(name).to_owned(),
+            None => format!("{} {}", field(given_name), field(family_name))
+                .trim()
+                .to_owned(),
+        };
+
+        if !display_name.is_empty() {
+            names.push(display_name);
+        }
+    }
+
+    Ok(names)
+}
+
+/// A JIG or Playlist assigned to a Class.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassAssignment {
+    /// The ID of the assignment.
+    pub id: ClassAssignmentId,
+
+    /// The Class the assignment was given to.
+    pub class_id: ClassId,
+
+    /// The assigned JIG or Playlist.
+    pub asset_id: AssetId,
+
+    /// When the assignment is due, if it has a due date.
+    pub due_at: Option<DateTime<Utc>>,
+
+    /// Codes the students' sessions are recorded under, one for each JIG that has been played.
+    pub codes: Vec<ClassAssignmentCode>,
+
+    /// When the assignment was created.
+    pub created_at: DateTime<Utc>,
+}
+
+make_path_parts!(ClassAssignmentListPath => "/v1/class/{}/assignments" => ClassId);
+
+/// Response for listing a Class's assignments.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassAssignmentListResponse {
+    /// The assignments, ordered by due date.
+    pub assignments: Vec<ClassAssignment>,
+}
+
+make_path_parts!(ClassAssignmentCreatePath => "/v1/class/{}/assignments" => ClassId);
+
+/// Request to assign a JIG or Playlist to a Class.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassAssignmentCreateRequest {
+    /// The JIG or Playlist to assign. Other kinds of assets can't be assigned.
+    pub asset_id: AssetId,
+
+    /// When the assignment is due.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(ClassAssignmentUpdatePath => "/v1/class/{}/assignments/{}" => ClassId, ClassAssignmentId);
+
+/// Request to update an assignment.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassAssignmentUpdateRequest {
+    /// When the assignment is due.
+    #[serde(default, skip_serializing_if = "UpdateNullable::is_keep")]
+    pub due_at: UpdateNullable<DateTime<Utc>>,
+}
+
+make_path_parts!(ClassAssignmentDeletePath => "/v1/class/{}/assignments/{}" => ClassId, ClassAssignmentId);
+
+/// A Student as shown on the class login screen.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentProfile {
+    /// The ID of the Student.
+    pub id: StudentId,
+
+    /// The Student's name. Shortened to their first name and last initial in the class roster,
+    /// unless that would be ambiguous.
+    pub display_name: String,
+}
+
+make_path_parts!(ClassLoginRosterPath => "/v1/class/login/{}" => LoginCode);
+
+/// Response for looking up a Class by its login code, so that students can pick their name.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassLoginRosterResponse {
+    /// The Class's name.
+    pub display_name: String,
+
+    /// The students in the Class, ordered by name.
+    pub students: Vec<StudentProfile>,
+}
+
+make_path_parts!(StudentLoginPath => "/v1/class/login");
+
+/// Request for a Student to log in to the kids app.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum StudentLoginRequest {
+    /// Log in with the Student's own code.
+    #[serde(rename_all = "camelCase")]
+    Code {
+        /// The Student's login code.
+        code: LoginCode,
+    },
+
+    /// Log in with the Class's code and the Student's picture password.
+    #[serde(rename_all = "camelCase")]
+    Picture {
+        /// The Class's login code.
+        class_code: LoginCode,
+
+        /// The Student picked from the class login screen.
+        student_id: StudentId,
+
+        /// The pictures the Student picked.
+        picture_password: PicturePassword,
+    },
+}
+
+/// Response for a Student logging in.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentLoginResponse {
+    /// Token identifying the Student. It's passed along when starting a JIG session so that the
+    /// session is linked to the Student.
+    pub token: String,
+
+    /// The Student that logged in.
+    pub student: StudentProfile,
+
+    /// The Student's Class.
+    pub class_id: ClassId,
+
+    /// The Student's Class's name.
+    pub class_name: String,
+}
+
+make_path_parts!(StudentAssignmentListPath => "/v1/class/student/assignments");
+
+/// Request for the assignments of a logged in Student.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentAssignmentListRequest {
+    /// Token returned when the Student logged in.
+    pub token: String,
+}
+
+/// An assignment as shown to a Student.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentAssignment {
+    /// The assignment.
+    pub assignment: ClassAssignment,
+
+    /// Name of the assigned JIG or Playlist.
+    pub display_name: String,
+
+    /// JIGs of the assignment, in order. A JIG assignment has a single JIG.
+    pub jigs: Vec<StudentAssignmentJig>,
+}
+
+/// A JIG of an assignment as shown to a Student.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentAssignmentJig {
+    /// The JIG.
+    pub jig_id: JigId,
+
+    /// Name of the JIG.
+    pub display_name: String,
+
+    /// Whether the Student has finished playing the JIG for this assignment.
+    pub completed: bool,
+}
+
+/// Response for the assignments of a logged in Student.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentAssignmentListResponse {
+    /// The Student's assignments, ordered by due date.
+    pub assignments: Vec<StudentAssignment>,
+}
+
+make_path_parts!(StudentAssignmentPlayPath => "/v1/class/student/assignments/{}/play" => ClassAssignmentId);
+
+/// Request for a logged in Student to play a JIG of an assignment.
+///
+/// Returns a [`PlayerSessionInstanceResponse`](super::jig::codes::instance::PlayerSessionInstanceResponse).
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StudentAssignmentPlayRequest {
+    /// Token returned when the Student logged in.
+    pub token: String,
+
+    /// The JIG to play. Must be one of the assignment's [`jigs`](StudentAssignment::jigs).
+    pub jig_id: JigId,
+}
+
+/// Code the sessions of an assignment's JIG are recorded under. Sessions played from the
+/// assignment show up in the teacher's reports for this code.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClassAssignmentCode {
+    /// The JIG.
+    pub jig_id: JigId,
+
+    /// The code.
+    pub code: JigCode,
+}
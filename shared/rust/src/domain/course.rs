@@ -18,6 +18,7 @@ use super::{
     user::UserId,
 };
 
+pub mod progress;
 pub mod unit;
 
 wrap_uuid! {
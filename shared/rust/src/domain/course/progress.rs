@@ -0,0 +1,132 @@
+//! Types for tracking a learner's progress through a Course
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::endpoints::PathPart,
+    domain::asset::{ItemProgress, ProgressStatus},
+};
+
+use super::{unit::CourseUnitId, CourseId, CourseResponse};
+
+make_path_parts!(CourseProgressPath => "/v1/course/{}/progress" => CourseId);
+
+make_path_parts!(CourseUnitProgressPath => "/v1/course/{}/unit/{}/progress" => CourseId, CourseUnitId);
+
+/// Request to record the current user's progress on a Course Unit.
+///
+/// [`course::progress::UpdateUnit`](crate::api::endpoints::course::progress::UpdateUnit)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseUnitProgressUpdateRequest {
+    /// Whether the unit has been started or completed.
+    ///
+    /// A completed unit stays completed when it is started again.
+    pub status: ProgressStatus,
+}
+
+/// Progress of the current user on a single Course Unit.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseUnitProgress {
+    /// The unit this progress is for
+    pub unit_id: CourseUnitId,
+
+    /// Whether the unit has been started or completed
+    pub status: ProgressStatus,
+
+    /// When the unit was first started
+    pub started_at: DateTime<Utc>,
+
+    /// When the unit was first completed
+    pub completed_at: Option<DateTime<Utc>>,
+
+    /// When the progress was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ItemProgress for CourseUnitProgress {
+    type Id = CourseUnitId;
+
+    fn item_id(&self) -> CourseUnitId {
+        self.unit_id
+    }
+
+    fn status(&self) -> ProgressStatus {
+        self.status
+    }
+
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
+/// The current user's progress through a Course.
+///
+/// [`course::progress::Get`](crate::api::endpoints::course::progress::Get)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseProgressResponse {
+    /// ID of the Course
+    pub course_id: CourseId,
+
+    /// Progress on each unit the user has started, in unit order.
+    pub units: Vec<CourseUnitProgress>,
+
+    /// Number of units in the Course
+    pub unit_count: u32,
+
+    /// Number of units the user has completed
+    pub completed_count: u32,
+
+    /// Percentage of units completed, `0..=100`
+    pub percent_complete: u16,
+
+    /// Unit to continue from, `None` if every unit has been completed.
+    pub continue_unit_id: Option<CourseUnitId>,
+
+    /// When the user last made progress in the Course
+    pub last_played_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(CourseListInProgressPath => "/v1/course/in-progress");
+
+/// Request for the list of Courses the current user has started.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseListInProgressRequest {
+    /// The page number of the courses to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// The hits per page to be returned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+
+    /// Include courses that have been completed.
+    #[serde(default)]
+    pub include_completed: bool,
+}
+
+/// A Course the user has started, with their progress.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseInProgress {
+    /// The Course
+    pub course: CourseResponse,
+
+    /// The user's progress through the Course
+    pub progress: CourseProgressResponse,
+}
+
+/// Response for the list of Courses the current user has started, most recently played first.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CourseListInProgressResponse {
+    /// The courses returned.
+    pub courses: Vec<CourseInProgress>,
+}
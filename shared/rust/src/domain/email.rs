@@ -0,0 +1,209 @@
+//! Types for transactional emails.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::api::endpoints::PathPart;
+
+use super::{ItemCount, Page, PageLimit};
+
+/// A transactional email sent by Jigzi.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[repr(i16)]
+pub enum EmailTemplateKind {
+    /// Verify an email address at signup.
+    SignupVerify = 0,
+    /// Welcome a user once they've finished signing up.
+    WelcomeJigzi = 1,
+    /// Reset a password.
+    PasswordReset = 2,
+    /// Reset a password for a user who signed up through OAuth.
+    OAuthPasswordReset = 3,
+    /// Verify a new email address.
+    EmailReset = 4,
+    /// Let admins know a JIG was reported.
+    JigReport = 5,
+    /// Let admins know a resource was reported.
+    ResourceReport = 6,
+    /// Weekly summary of a user's unread notifications.
+    NotificationDigest = 7,
+}
+
+impl EmailTemplateKind {
+    /// Every template.
+    pub const ALL: &'static [Self] = &[
+        Self::SignupVerify,
+        Self::WelcomeJigzi,
+        Self::PasswordReset,
+        Self::OAuthPasswordReset,
+        Self::EmailReset,
+        Self::JigReport,
+        Self::ResourceReport,
+        Self::NotificationDigest,
+    ];
+
+    /// Key of the template, used in paths, settings and as the locale section of its strings.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::SignupVerify => "signup-verify",
+            Self::WelcomeJigzi => "welcome-jigzi",
+            Self::PasswordReset => "password-reset",
+            Self::OAuthPasswordReset => "oauth-password-reset",
+            Self::EmailReset => "email-reset",
+            Self::JigReport => "jig-report",
+            Self::ResourceReport => "resource-report",
+            Self::NotificationDigest => "notification-digest",
+        }
+    }
+}
+
+impl FromStr for EmailTemplateKind {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|kind| kind.as_str() == s)
+            .copied()
+            .ok_or(())
+    }
+}
+
+impl PathPart for EmailTemplateKind {
+    fn get_path_string(&self) -> String {
+        self.as_str().to_string()
+    }
+}
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents the ID of an email in the outbox.
+    pub struct EmailOutboxId
+}
+
+/// Delivery status of an email in the outbox.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[repr(i16)]
+pub enum EmailOutboxStatus {
+    /// Waiting to be sent, either for the first time or to be retried.
+    Pending = 0,
+    /// Handed off to the mail provider.
+    Sent = 1,
+    /// Gave up after too many failed attempts.
+    Failed = 2,
+}
+
+/// An email in the outbox.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailOutboxEntry {
+    /// ID of the email
+    pub id: EmailOutboxId,
+
+    /// Template the email was rendered from
+    pub template: EmailTemplateKind,
+
+    /// Recipient's email address
+    pub to_email: String,
+
+    /// Locale the email was rendered in
+    pub locale: String,
+
+    /// Rendered subject line
+    pub subject: String,
+
+    /// Delivery status
+    pub status: EmailOutboxStatus,
+
+    /// Number of delivery attempts made so far
+    pub attempts: u16,
+
+    /// Error returned by the last failed attempt
+    pub last_error: Option<String>,
+
+    /// When the email was queued
+    pub created_at: DateTime<Utc>,
+
+    /// When the email was sent
+    pub sent_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(EmailPreviewPath => "/v1/admin/email/preview/{}" => EmailTemplateKind);
+
+/// Query for previewing an email template.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailPreviewQuery {
+    /// Locale to render the template in. Defaults to English.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+}
+
+/// An email template rendered with sample data.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailPreviewResponse {
+    /// Locale the template was rendered in
+    pub locale: String,
+
+    /// Rendered subject line
+    pub subject: String,
+
+    /// Rendered HTML body
+    pub html: String,
+
+    /// Rendered plain text body
+    pub text: String,
+
+    /// SendGrid template which is used instead of the rendered body when sending, if one is
+    /// configured for this template and locale.
+    pub sendgrid_template_id: Option<String>,
+}
+
+make_path_parts!(EmailOutboxPath => "/v1/admin/email/outbox");
+
+/// Query for listing the outbox.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEmailOutboxQuery {
+    /// Only list emails with this status
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<EmailOutboxStatus>,
+
+    /// Only list emails sent to this address
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_email: Option<String>,
+
+    /// Current page of results
+    #[serde(default)]
+    pub page: Page,
+
+    /// Emails per page to return
+    #[serde(default)]
+    pub page_limit: PageLimit,
+}
+
+/// Emails in the outbox, newest first.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEmailOutboxResponse {
+    /// Emails on this page
+    pub emails: Vec<EmailOutboxEntry>,
+
+    /// Count of pages
+    pub pages: ItemCount,
+
+    /// Total count of emails for this query
+    pub total_count: ItemCount,
+}
+
+make_path_parts!(RetryEmailPath => "/v1/admin/email/outbox/{}/retry" => EmailOutboxId);
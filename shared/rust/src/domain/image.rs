@@ -1,5 +1,6 @@
 //! Types for images.
 
+pub mod bulk;
 pub mod recent;
 pub mod tag;
 pub mod user;
@@ -69,6 +70,8 @@ pub enum ImageFileKind {
     Png = 0,
     /// Animated GIF file.
     Gif = 1,
+    /// Sanitized SVG file, with PNG renditions for thumbnails and effects.
+    Svg = 2,
 }
 
 impl Default for ImageFileKind {
@@ -84,6 +87,17 @@ impl ImageFileKind {
         match self {
             Self::Png => "png",
             Self::Gif => "gif",
+            Self::Svg => "svg",
+        }
+    }
+
+    /// The kind an uploaded file will be stored as, from its content type.
+    #[must_use]
+    pub fn from_content_type(content_type: &str) -> Self {
+        match content_type {
+            "image/gif" => Self::Gif,
+            "image/svg+xml" => Self::Svg,
+            _ => Self::Png,
         }
     }
 }
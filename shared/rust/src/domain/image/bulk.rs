@@ -0,0 +1,226 @@
+//! Types to edit many images at once.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::endpoints::PathPart,
+    domain::{
+        category::CategoryId,
+        meta::{AffiliationId, AgeRangeId, ImageStyleId, ImageTagIndex},
+        user::UserId,
+        ItemCount, Page, PageLimit, Publish,
+    },
+};
+
+use super::{ImageId, ImageSize};
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`](uuid::Uuid), represents the ID of a bulk update.
+    pub struct ImageBulkUpdateId
+}
+
+/// Which images a bulk update applies to.
+///
+/// Every filter which is set must match. Filters on lists match images which have any of the
+/// given items.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageBulkQuery {
+    /// Only these images.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ids: Vec<ImageId>,
+
+    /// Only images whose name contains this text, ignoring case.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub q: Option<String>,
+
+    /// Only images which are, or aren't, published.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_published: Option<bool>,
+
+    /// Only images which are, or aren't, premium.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_premium: Option<bool>,
+
+    /// Only images of this size.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<ImageSize>,
+
+    /// Only images with any of these styles.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub styles: Vec<ImageStyleId>,
+
+    /// Only images with any of these age ranges.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub age_ranges: Vec<AgeRangeId>,
+
+    /// Only images with any of these affiliations.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub affiliations: Vec<AffiliationId>,
+
+    /// Only images in any of these categories.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<CategoryId>,
+
+    /// Only images with any of these tags.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<ImageTagIndex>,
+}
+
+impl ImageBulkQuery {
+    /// Whether no filter is set, which would match the whole library.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// The changes a bulk update makes to each image.
+///
+/// Items which an image already has aren't added again, and items it doesn't have are ignored
+/// when removing.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageBulkChanges {
+    /// Tags to add.
+    #[serde(default)]
+    pub add_tags: Vec<ImageTagIndex>,
+
+    /// Tags to remove.
+    #[serde(default)]
+    pub remove_tags: Vec<ImageTagIndex>,
+
+    /// Styles to add.
+    #[serde(default)]
+    pub add_styles: Vec<ImageStyleId>,
+
+    /// Styles to remove.
+    #[serde(default)]
+    pub remove_styles: Vec<ImageStyleId>,
+
+    /// Age ranges to add.
+    #[serde(default)]
+    pub add_age_ranges: Vec<AgeRangeId>,
+
+    /// Age ranges to remove.
+    #[serde(default)]
+    pub remove_age_ranges: Vec<AgeRangeId>,
+
+    /// Affiliations to add.
+    #[serde(default)]
+    pub add_affiliations: Vec<AffiliationId>,
+
+    /// Affiliations to remove.
+    #[serde(default)]
+    pub remove_affiliations: Vec<AffiliationId>,
+
+    /// If `Some` mark the images as premium or not.
+    #[serde(default)]
+    pub is_premium: Option<bool>,
+
+    /// If `Some`, change the `publish_at` of the images.
+    ///
+    /// If `Some(None)`, unpublish them. Otherwise publish them at the given [`Publish`].
+    #[serde(deserialize_with = "crate::domain::deserialize_optional_field")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub publish_at: Option<Option<Publish>>,
+}
+
+make_path_parts!(ImageBulkUpdatePath => "/v1/image/bulk");
+
+/// Request to edit every image matching a query.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageBulkUpdateRequest {
+    /// The images to edit.
+    pub query: ImageBulkQuery,
+
+    /// The changes to make.
+    pub changes: ImageBulkChanges,
+}
+
+/// Response for a bulk update.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageBulkUpdateResponse {
+    /// The bulk update's ID, which can be used to undo it.
+    pub id: ImageBulkUpdateId,
+
+    /// How many images were edited.
+    pub image_count: u64,
+}
+
+make_path_parts!(ImageBulkUndoPath => "/v1/image/bulk/{}/undo" => ImageBulkUpdateId);
+
+/// Response for undoing a bulk update.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageBulkUndoResponse {
+    /// How many images were restored. Images deleted since the update aren't counted.
+    pub image_count: u64,
+}
+
+make_path_parts!(ImageBulkListPath => "/v1/image/bulk");
+
+/// Query for listing bulk updates, most recent first.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageBulkListQuery {
+    /// Current page of results
+    #[serde(default)]
+    pub page: Page,
+
+    /// Total bulk updates per page to return
+    #[serde(default)]
+    pub page_limit: PageLimit,
+}
+
+/// A bulk update which was made.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageBulkUpdate {
+    /// The bulk update's ID.
+    pub id: ImageBulkUpdateId,
+
+    /// Who made the bulk update, if they still exist.
+    pub created_by: Option<UserId>,
+
+    /// The changes which were made.
+    pub changes: ImageBulkChanges,
+
+    /// How many images were edited.
+    pub image_count: u64,
+
+    /// When the bulk update was made.
+    pub created_at: DateTime<Utc>,
+
+    /// When the bulk update was undone, if it has been.
+    pub undone_at: Option<DateTime<Utc>>,
+}
+
+/// Response for listing bulk updates.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageBulkListResponse {
+    /// The bulk updates on this page.
+    pub updates: Vec<ImageBulkUpdate>,
+
+    /// Count of pages
+    pub pages: ItemCount,
+
+    /// Total count of bulk updates
+    pub total_count: ItemCount,
+}
@@ -19,7 +19,7 @@ use super::{
     asset::{DraftOrLive, OrderBy, PrivacyLevel, UserOrMe},
     category::CategoryId,
     meta::{AffiliationId, AgeRangeId, ResourceTypeId},
-    module::LiteModule,
+    module::{LiteModule, ModuleId},
     playlist::PlaylistResponse,
     user::UserId,
 };
@@ -846,6 +846,73 @@ pub struct JigIdResponse {
 
 make_path_parts!(JigClonePath => "/v1/jig/{}/clone" => JigId);
 
+make_path_parts!(JigTranslatePath => "/v1/jig/{}/translate" => JigId);
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents the ID of a translation of a JIG.
+    pub struct JigTranslationId
+}
+
+/// Request to make a copy of a JIG with its content translated into another language.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JigTranslateRequest {
+    /// Language to translate into, one of the JIG languages, e.g. `he`.
+    pub language: String,
+}
+
+make_path_parts!(JigTranslationPath => "/v1/jig/translation/{}" => JigTranslationId);
+
+/// A translated copy of a JIG, which is made in the background.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct JigTranslation {
+    /// The ID of the translation.
+    pub id: JigTranslationId,
+
+    /// The JIG which is translated.
+    pub jig_id: JigId,
+
+    /// Language the copy is translated into.
+    pub language: String,
+
+    /// Whether the copy has been made yet.
+    pub status: JigTranslationStatus,
+
+    /// The ID of the translated copy, once it's been made.
+    pub translated_jig_id: Option<JigId>,
+
+    /// Texts which couldn't be translated, and were copied as they were.
+    ///
+    /// These should be reviewed by the creator.
+    pub untranslated: Vec<UntranslatedText>,
+}
+
+/// Whether a translated copy of a JIG has been made.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum JigTranslationStatus {
+    /// The copy is still being made.
+    Pending = 0,
+    /// The copy has been made.
+    Finished = 1,
+    /// The copy couldn't be made.
+    Failed = 2,
+}
+
+/// A text of a translated JIG which couldn't be translated.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UntranslatedText {
+    /// The draft module of the copy the text is in, `None` for the JIG's name and description.
+    pub module_id: Option<ModuleId>,
+
+    /// The text, as it was written.
+    pub text: String,
+}
+
 make_path_parts!(JigDeletePath => "/v1/jig/{}" => JigId);
 
 make_path_parts!(JigDeleteAllPath => "/v1/jig");
@@ -6,7 +6,10 @@ use chrono::{DateTime, Utc};
 use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
 
-use crate::{api::endpoints::PathPart, domain::module::StableModuleId};
+use crate::{
+    api::endpoints::PathPart,
+    domain::{class::StudentId, module::StableModuleId},
+};
 
 use super::{JigId, JigPlayerSettings, JigResponse};
 
@@ -55,8 +58,30 @@ pub struct JigCodeUpdateRequest {
     /// Display name
     pub name: Option<Option<String>>,
 
-    /// Settings for the session
+    /// Settings for the session.
+    ///
+    /// Its `opens_at`, `due_at` and `max_attempts` are ignored, they're updated by the fields
+    /// below so that they aren't cleared by requests which don't mean to change them.
     pub settings: Option<JigPlayerSettings>,
+
+    /// If `Some`, change when the code opens, or stop restricting it if `Some(None)`.
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::domain::deserialize_optional_field")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opens_at: Option<Option<DateTime<Utc>>>,
+
+    /// If `Some`, change when the code is due, or remove the due date if `Some(None)`.
+    #[serde(default)]
+    #[serde(deserialize_with = "crate::domain::deserialize_optional_field")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<Option<DateTime<Utc>>>,
+
+    /// If `Some`, change how many times each player can start the JIG, or allow any number of
+    /// attempts if `Some(None)`.
+    #[serde(default)]
+    #[serde(deserialize_with = "super::player::deserialize_optional_max_attempts")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<Option<u16>>,
 }
 
 /// Over-the-wire representation of a jig player session
@@ -134,6 +159,10 @@ pub struct JigCodeSessionResponse {
     pub code: JigCode,
     /// Playing's name
     pub players_name: Option<String>,
+    /// Student who played, if they were logged in to their class
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub student_id: Option<StudentId>,
     /// star time
     pub started_at: DateTime<Utc>,
     /// end time
@@ -142,6 +171,64 @@ pub struct JigCodeSessionResponse {
     pub info: Option<JigPlaySession>,
 }
 
+make_path_parts!(JigCodeReportPath => "/v1/jig/codes/{}/report" => JigCode);
+
+/// Report of which players completed a code on time.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JigCodeReportResponse {
+    /// code
+    pub code: JigCode,
+    /// When the code was due, see [`JigPlayerSettings::due_at`].
+    pub due_at: Option<DateTime<Utc>>,
+    /// One entry for each player, including students of classes the code is assigned to who
+    /// haven't played it.
+    pub players: Vec<JigCodePlayerReport>,
+}
+
+/// How a single player did on a code.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JigCodePlayerReport {
+    /// Playing's name
+    pub players_name: Option<String>,
+    /// Student who played, if they were logged in to their class
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub student_id: Option<StudentId>,
+    /// Whether and when the player completed the JIG
+    pub status: JigCodeCompletionStatus,
+    /// Number of sessions the player started
+    pub attempts: u32,
+    /// When the player first completed the JIG
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Best score of the player's completed sessions, if the JIG has scoring
+    pub points: Option<PointsEarned>,
+}
+
+/// Whether a player completed a code before it was due.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum JigCodeCompletionStatus {
+    /// Completed before the due date, or the code has no due date.
+    OnTime,
+    /// Completed after the due date.
+    Late,
+    /// Never completed.
+    NotCompleted,
+}
+
+impl JigCodeCompletionStatus {
+    /// Status of a player who first completed the JIG at `completed_at`.
+    pub fn new(completed_at: Option<DateTime<Utc>>, due_at: Option<DateTime<Utc>>) -> Self {
+        match (completed_at, due_at) {
+            (None, _) => Self::NotCompleted,
+            (Some(completed_at), Some(due_at)) if completed_at > due_at => Self::Late,
+            (Some(_), _) => Self::OnTime,
+        }
+    }
+}
+
 /// Play session
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct JigPlaySession {
@@ -411,6 +498,7 @@ pub struct JigPlaySessionFindAnswerItem {
 pub mod instance {
     use macros::make_path_parts;
     use serde::{Deserialize, Serialize};
+    use uuid::Uuid;
 
     use crate::domain::jig::{
         codes::{JigCode, JigPlaySession},
@@ -424,6 +512,20 @@ pub mod instance {
     pub struct PlayerSessionInstanceCreateRequest {
         /// Four-digit code identifying a JIG player session
         pub code: JigCode,
+
+        /// Token of a logged in student, linking the session to them.
+        ///
+        /// See [`StudentLoginResponse`](crate::domain::class::StudentLoginResponse).
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub student_token: Option<String>,
+
+        /// Random identifier of a player who isn't logged in as a student, which their device
+        /// keeps between plays so that their attempts are counted separately from other players
+        /// on the same network. A new one is returned if it's missing.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub player_id: Option<Uuid>,
     }
 
     /// Response for successfully creating an instance of a JIG player session. contains the token
@@ -438,6 +540,15 @@ pub mod instance {
 
         /// Token that will be passed to confirm a JIG was played all the way through
         pub token: String,
+
+        /// Identifier the player's device should send when starting another session.
+        pub player_id: Uuid,
+
+        /// Pseudonym the player is reported under when the code's school is in child privacy
+        /// mode. Players shouldn't be asked for their name in that case, as it isn't stored.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub pseudonym: Option<String>,
     }
 
     make_path_parts!(PlayerSessionInstanceCompletePath => "/v1/jig/codes/instance/complete");
@@ -2,6 +2,7 @@
 
 use std::ops::Deref;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Settings for the player session.
@@ -17,6 +18,54 @@ pub struct JigPlayerSettings {
     /// Whether or not to enable drag assist
     #[serde(default)]
     pub drag_assist: bool,
+    /// When the JIG can first be played. Only enforced for JIG codes.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opens_at: Option<DateTime<Utc>>,
+    /// When the JIG should be completed by. Players can still finish it afterwards, but are
+    /// reported as late. Only applies to JIG codes.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<DateTime<Utc>>,
+    /// How many times each player can start the JIG. Only enforced for JIG codes.
+    ///
+    /// Must be between 1 and [`MAX_ATTEMPTS_LIMIT`].
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_max_attempts")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u16>,
+    /// Record the score without showing it to the student.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub hide_score: bool,
+}
+
+/// The most attempts a JIG code can allow each player.
+pub const MAX_ATTEMPTS_LIMIT: u16 = i16::MAX as u16;
+
+/// Rejects a `max_attempts` of 0 or over [`MAX_ATTEMPTS_LIMIT`].
+pub(crate) fn deserialize_max_attempts<'de, D>(deserializer: D) -> Result<Option<u16>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let max_attempts = Option::<u16>::deserialize(deserializer)?;
+
+    match max_attempts {
+        Some(max) if max == 0 || max > MAX_ATTEMPTS_LIMIT => Err(serde::de::Error::custom(
+            format!("max_attempts must be between 1 and {}", MAX_ATTEMPTS_LIMIT),
+        )),
+        max_attempts => Ok(max_attempts),
+    }
+}
+
+/// Like [`deserialize_max_attempts`], for updates where a missing field is left as it is.
+pub(crate) fn deserialize_optional_max_attempts<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<u16>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    deserialize_max_attempts(deserializer).map(Some)
 }
 
 impl Default for JigPlayerSettings {
@@ -25,6 +74,10 @@ impl Default for JigPlayerSettings {
             direction: TextDirection::default(),
             scoring: false,
             drag_assist: false,
+            opens_at: None,
+            due_at: None,
+            max_attempts: None,
+            hide_score: false,
         }
     }
 }
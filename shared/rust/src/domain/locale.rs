@@ -18,6 +18,29 @@ pub struct Bundle {
     pub name: String,
 }
 
+/// A language UI strings are translated into.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Language {
+    /// The language's code, e.g. `he`.
+    pub code: String,
+
+    /// The language's name, in English.
+    pub display_name: String,
+}
+
+/// Code of the language which entries fall back to when they haven't been translated.
+pub const FALLBACK_LANGUAGE: &str = "en";
+
+/// Whether `key` can be used as an [`Entry`]'s key, which is the id of its message in Fluent
+/// files: a letter, followed by letters, digits, `-` and `_`.
+pub fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
 /// What kind of item an [`Entry`] is.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -46,8 +69,7 @@ pub enum EntryStatus {
     OnHold = 2,
 }
 
-// todo: an entry into the what?
-/// An entry into the ?
+/// A UI string, translated into any number of [`Language`]s.
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Entry {
@@ -63,11 +85,11 @@ pub struct Entry {
     /// This entry's [`ItemKind`]'s id.
     pub item_kind_id: Option<Uuid>,
 
-    /// The English version of this entry.
-    pub english: Option<String>,
+    /// The id of this entry's message in its bundle's Fluent file, see [`is_valid_key`].
+    pub key: Option<String>,
 
-    /// The hebrew version of this entry.
-    pub hebrew: Option<String>,
+    /// This entry's text in each [`Language`] it has been written in, by language code.
+    pub translations: BTreeMap<String, String>,
 
     /// This entry's current status.
     pub status: EntryStatus,
@@ -103,11 +125,12 @@ pub struct CreateEntryRequest {
     /// This entry's [`ItemKind`]'s id.
     pub item_kind_id: Option<Uuid>,
 
-    /// The English version of this entry.
-    pub english: Option<String>,
+    /// The id of this entry's message in its bundle's Fluent file, see [`is_valid_key`].
+    pub key: Option<String>,
 
-    /// The hebrew version of this entry.
-    pub hebrew: Option<String>,
+    /// This entry's text in each [`Language`] it has been written in, by language code.
+    #[serde(default)]
+    pub translations: BTreeMap<String, String>,
 
     /// This entry's current status.
     pub status: EntryStatus,
@@ -233,15 +256,17 @@ pub struct UpdateEntryRequest {
     #[serde(default)]
     pub item_kind_id: Option<Uuid>,
 
-    /// The English version of this entry.
+    /// The id of this entry's message in its bundle's Fluent file, see [`is_valid_key`].
+    #[serde(deserialize_with = "super::deserialize_optional_field")]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
-    pub english: Option<String>,
+    pub key: Option<Option<String>>,
 
-    /// The hebrew version of this entry.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Translations to change, by language code. `None` removes the language's translation, and
+    /// languages which aren't included are left as they are.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     #[serde(default)]
-    pub hebrew: Option<String>,
+    pub translations: BTreeMap<String, Option<String>>,
 
     /// This entry's current status.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -298,3 +323,36 @@ pub struct ListItemKindResponse {
     /// A list of item kinds
     pub item_kinds: Vec<ItemKind>,
 }
+
+make_path_parts!(ListLanguagePath => "/v1/locale/language");
+
+/// Response for listing languages
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListLanguageResponse {
+    /// A list of languages
+    pub languages: Vec<Language>,
+}
+
+make_path_parts!(CreateLanguagePath => "/v1/locale/language");
+
+/// Request for adding a language.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateLanguageRequest {
+    /// The language's code, e.g. `he`.
+    pub code: String,
+
+    /// The language's name, in English.
+    pub display_name: String,
+}
+
+make_path_parts!(ExportBundlePath => "/v1/locale/bundle/{}/fluent" => Uuid);
+
+/// Query for exporting a [`Bundle`] as a Fluent (`.ftl`) file.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportBundleQuery {
+    /// Code of the language to export the entries' translations in.
+    pub language: String,
+}
@@ -1,9 +1,11 @@
 //! Types for Media.
 
 use crate::api::endpoints::PathPart;
+use crate::domain::{user::UserId, ItemCount, Page, PageLimit};
 use chrono::{DateTime, Utc};
 use macros::make_path_parts;
 use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
 use url::Url;
 use uuid::Uuid;
 
@@ -49,8 +51,72 @@ pub struct WebMediaMetadataResponse {
 
     /// When this media was last updated, if ever.
     pub updated_at: Option<DateTime<Utc>>,
+
+    /// The user who first added this media, if they're known.
+    #[serde(default)]
+    pub created_by: Option<UserId>,
 }
 
 make_path_parts!(MediaUrlDeletePath => "/v1/media/url/{}" => Url);
 
 make_path_parts!(MediaIdDeletePath => "/v1/media/id/{}" => Uuid);
+
+make_path_parts!(MediaListPath => "/v1/media");
+
+/// Kinds of media to filter the web media library by.
+#[derive(Display, EnumIter, Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum WebMediaKind {
+    /// Still images
+    Image,
+    /// SVG images
+    #[strum(serialize = "SVG image")]
+    SvgImage,
+    /// Animations
+    Animation,
+}
+
+/// Query for listing the web media library, newest first.
+#[derive(Default, Serialize, Deserialize, Debug, Clone)]
+pub struct WebMediaListQuery {
+    /// Only list media of this kind.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<WebMediaKind>,
+
+    /// Only list media added at or after this time.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_date: Option<DateTime<Utc>>,
+
+    /// Only list media added before this time.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_date: Option<DateTime<Utc>>,
+
+    /// Only list media first added by this user.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<UserId>,
+
+    /// Current page of results
+    #[serde(default)]
+    pub page: Page,
+
+    /// Total media per page to return
+    #[serde(default)]
+    pub page_limit: PageLimit,
+}
+
+/// Response for listing the web media library.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WebMediaListResponse {
+    /// The media on this page.
+    pub media: Vec<WebMediaMetadataResponse>,
+
+    /// Count of pages
+    pub pages: ItemCount,
+
+    /// Total count of media for this query
+    pub total_count: ItemCount,
+}
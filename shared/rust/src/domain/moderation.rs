@@ -0,0 +1,180 @@
+//! Types for the review queue of content flagged by the content safety checks.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    api::endpoints::PathPart,
+    domain::{user::UserId, ItemCount, Page, PageLimit},
+};
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents the ID of a content flag.
+    pub struct ContentFlagId
+}
+
+/// The kinds of content which get checked.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[repr(i16)]
+pub enum FlaggedContentKind {
+    /// An image uploaded to a user's library.
+    UserImage = 0,
+    /// A PDF uploaded to a user's library.
+    UserPdf = 1,
+    /// The title or description of a published JIG.
+    Jig = 2,
+    /// The name or description of a circle.
+    Circle = 3,
+}
+
+/// Where a flag is in the review.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[repr(i16)]
+pub enum ContentFlagStatus {
+    /// Waiting for an admin, the item is quarantined.
+    Pending = 0,
+    /// An admin found the item to be fine, it's no longer quarantined.
+    Approved = 1,
+    /// An admin agreed with the flag, the item stays quarantined.
+    Rejected = 2,
+}
+
+/// Kinds of personal information which text is checked for.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PersonalInfoKind {
+    /// An email address.
+    Email,
+    /// A phone number.
+    PhoneNumber,
+    /// A payment card number.
+    CardNumber,
+}
+
+/// Why an item was flagged.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum FlagReason {
+    /// A term from the profanity list of `language` was found in `field`.
+    #[serde(rename_all = "camelCase")]
+    Profanity {
+        /// The field of the item, e.g. `description`.
+        field: String,
+        /// Language code of the list the term is from.
+        language: String,
+        /// The term which was found.
+        term: String,
+    },
+
+    /// Something which looks like personal information was found in `field`. The information
+    /// itself isn't kept.
+    #[serde(rename_all = "camelCase")]
+    PersonalInfo {
+        /// The field of the item, e.g. `description`.
+        field: String,
+        /// What kind of information was found.
+        info: PersonalInfoKind,
+    },
+
+    /// An image classifier labeled the image as unsafe.
+    #[serde(rename_all = "camelCase")]
+    Image {
+        /// The classifier's label, e.g. `adult`.
+        label: String,
+        /// How confident the classifier is, from 0 to 1.
+        confidence: f32,
+    },
+}
+
+/// An item in the review queue.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentFlag {
+    /// The flag's ID.
+    pub id: ContentFlagId,
+
+    /// What kind of item was flagged.
+    pub kind: FlaggedContentKind,
+
+    /// ID of the flagged item, of the kind given by `kind`.
+    pub item_id: Uuid,
+
+    /// Who the item belongs to, if they still exist.
+    pub owner_id: Option<UserId>,
+
+    /// Why the item was flagged.
+    pub reasons: Vec<FlagReason>,
+
+    /// Where the flag is in the review.
+    pub status: ContentFlagStatus,
+
+    /// The admin who reviewed the flag.
+    pub reviewed_by: Option<UserId>,
+
+    /// When the flag was reviewed.
+    pub reviewed_at: Option<DateTime<Utc>>,
+
+    /// When the item was first flagged.
+    pub created_at: DateTime<Utc>,
+}
+
+make_path_parts!(ContentFlagListPath => "/v1/admin/content-flags");
+
+/// Query for listing flags, most recent first.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentFlagListQuery {
+    /// Only flags with this status.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<ContentFlagStatus>,
+
+    /// Only flags on this kind of item.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<FlaggedContentKind>,
+
+    /// Current page of results
+    #[serde(default)]
+    pub page: Page,
+
+    /// Total flags per page to return
+    #[serde(default)]
+    pub page_limit: PageLimit,
+}
+
+/// Response for listing flags.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentFlagListResponse {
+    /// The flags on this page.
+    pub flags: Vec<ContentFlag>,
+
+    /// Count of pages
+    pub pages: ItemCount,
+
+    /// Total count of flags
+    pub total_count: ItemCount,
+}
+
+make_path_parts!(ContentFlagReviewPath => "/v1/admin/content-flags/{}/review" => ContentFlagId);
+
+/// An admin's decision on a flag.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ContentFlagDecision {
+    /// The item is fine, release it from quarantine.
+    Approve,
+    /// The item isn't fine, keep it quarantined.
+    Reject,
+}
+
+/// Request to review a flag.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentFlagReviewRequest {
+    /// The decision on the flag.
+    pub decision: ContentFlagDecision,
+}
@@ -0,0 +1,212 @@
+//! Types for notifications.
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::api::endpoints::PathPart;
+
+use super::{
+    circle::CircleId,
+    jig::{curation::CommentId, JigId},
+    user::UserId,
+    ItemCount, Page, PageLimit,
+};
+
+wrap_uuid! {
+    /// Wrapper type around [`Uuid`], represents the ID of a notification.
+    pub struct NotificationId
+}
+
+/// What a notification is about.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[repr(i16)]
+pub enum NotificationKind {
+    /// Someone liked one of the user's JIGs.
+    JigLiked = 0,
+    /// One of the user's JIGs was played. Plays are grouped into a single notification per JIG
+    /// until it's read.
+    JigPlayed = 1,
+    /// Someone followed the user.
+    Followed = 2,
+    /// Someone joined one of the user's circles.
+    CircleJoined = 3,
+    /// A curator commented on one of the user's JIGs.
+    CurationComment = 4,
+}
+
+impl NotificationKind {
+    /// Every kind of notification.
+    pub const ALL: &'static [Self] = &[
+        Self::JigLiked,
+        Self::JigPlayed,
+        Self::Followed,
+        Self::CircleJoined,
+        Self::CurationComment,
+    ];
+
+    /// Key of the kind, used as the item kind of its line in the notification digest email's
+    /// strings.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::JigLiked => "jig-liked",
+            Self::JigPlayed => "jig-played",
+            Self::Followed => "followed",
+            Self::CircleJoined => "circle-joined",
+            Self::CurationComment => "curation-comment",
+        }
+    }
+}
+
+/// A user who caused a notification.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationActor {
+    /// ID of the user
+    pub id: UserId,
+
+    /// The user's full name
+    pub name: String,
+}
+
+/// A notification in a user's feed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    /// ID of the notification
+    pub id: NotificationId,
+
+    /// What the notification is about
+    pub kind: NotificationKind,
+
+    /// Who caused the notification, if anyone
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<NotificationActor>,
+
+    /// JIG the notification is about
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jig_id: Option<JigId>,
+
+    /// Name of the JIG the notification is about
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jig_name: Option<String>,
+
+    /// Circle the notification is about
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circle_id: Option<CircleId>,
+
+    /// Name of the circle the notification is about
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub circle_name: Option<String>,
+
+    /// Curation comment the notification is about
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment_id: Option<CommentId>,
+
+    /// How many events are grouped into this notification
+    pub count: u32,
+
+    /// Whether the user has read the notification
+    pub read: bool,
+
+    /// When the first grouped event happened
+    pub created_at: DateTime<Utc>,
+
+    /// When the latest grouped event happened
+    pub updated_at: DateTime<Utc>,
+}
+
+make_path_parts!(ListNotificationsPath => "/v1/notification");
+
+/// Query for listing a user's notifications.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListNotificationsQuery {
+    /// Only list unread notifications
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub unread_only: bool,
+
+    /// Current page of results
+    #[serde(default)]
+    pub page: Page,
+
+    /// Notifications per page to return
+    #[serde(default)]
+    pub page_limit: PageLimit,
+}
+
+/// A user's notifications, latest first.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ListNotificationsResponse {
+    /// Notifications on this page
+    pub notifications: Vec<Notification>,
+
+    /// Count of pages
+    pub pages: ItemCount,
+
+    /// Total count of notifications for this query
+    pub total_count: ItemCount,
+
+    /// Count of all unread notifications
+    pub unread_count: ItemCount,
+}
+
+make_path_parts!(UnreadNotificationsCountPath => "/v1/notification/unread-count");
+
+/// Count of a user's unread notifications.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UnreadNotificationsCountResponse {
+    /// Count of unread notifications
+    pub unread_count: ItemCount,
+}
+
+make_path_parts!(MarkNotificationReadPath => "/v1/notification/{}/read" => NotificationId);
+
+make_path_parts!(MarkAllNotificationsReadPath => "/v1/notification/read");
+
+make_path_parts!(NotificationPreferencesPath => "/v1/notification/preferences");
+
+/// How a user wants to be notified of a kind of notification.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPreference {
+    /// Kind of notification
+    pub kind: NotificationKind,
+
+    /// Whether to show it in the notification feed
+    pub in_app: bool,
+
+    /// Whether to include it in the weekly digest email
+    pub email: bool,
+}
+
+impl NotificationPreference {
+    /// Users are notified of everything until they say otherwise.
+    pub fn default_for(kind: NotificationKind) -> Self {
+        Self {
+            kind,
+            in_app: true,
+            email: true,
+        }
+    }
+}
+
+/// A user's preferences for each kind of notification.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationPreferences {
+    /// Preferences by kind. When reading, every kind is listed. When updating, kinds which are
+    /// left out are unchanged.
+    pub preferences: Vec<NotificationPreference>,
+}
@@ -36,7 +36,18 @@ pub mod user {
     pub struct UserPdf {
         /// The Pdf file's ID.
         pub id: PdfId,
-        // more fields to be added
+
+        /// Number of pages in the Pdf.
+        ///
+        /// `None` until the Pdf has been processed.
+        #[serde(default)]
+        pub page_count: Option<u32>,
+
+        /// Number of pages, from the first, which have a preview image.
+        ///
+        /// See [`FileKind::DocumentPdfPage`](crate::media::FileKind::DocumentPdfPage).
+        #[serde(default)]
+        pub preview_page_count: u32,
     }
 
     make_path_parts!(UserPdfCreatePath => "/v1/user/me/pdf");
@@ -70,6 +81,10 @@ pub struct PdfMetadata {
     /// The description of the Pdf file.
     pub description: String,
 
+    /// Number of pages in the Pdf, if it has been processed.
+    #[serde(default)]
+    pub page_count: Option<u32>,
+
     /// When the Pdf should be considered published (if at all).
     pub publish_at: Option<DateTime<Utc>>,
 
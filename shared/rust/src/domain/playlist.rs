@@ -17,6 +17,8 @@ use super::{
     user::UserId,
 };
 
+pub mod progress;
+
 wrap_uuid! {
     /// Wrapper type around [`Uuid`], represents the ID of a Playlist.
     pub struct PlaylistId
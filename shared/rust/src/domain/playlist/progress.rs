@@ -0,0 +1,143 @@
+//! Types for tracking a learner's progress through a Playlist
+
+use chrono::{DateTime, Utc};
+use macros::make_path_parts;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api::endpoints::PathPart,
+    domain::{
+        asset::{ItemProgress, ProgressStatus},
+        jig::{codes::PointsEarned, JigId},
+    },
+};
+
+use super::{PlaylistId, PlaylistResponse};
+
+make_path_parts!(PlaylistProgressPath => "/v1/playlist/{}/progress" => PlaylistId);
+
+make_path_parts!(PlaylistItemProgressPath => "/v1/playlist/{}/jig/{}/progress" => PlaylistId, JigId);
+
+/// Request to record the current user's progress on a JIG within a Playlist.
+///
+/// [`playlist::progress::UpdateItem`](crate::api::endpoints::playlist::progress::UpdateItem)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistItemProgressUpdateRequest {
+    /// Whether the JIG has been started or completed.
+    ///
+    /// A completed JIG stays completed when it is started again.
+    pub status: ProgressStatus,
+
+    /// Score from the JIG session, if the JIG was scored.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub points_earned: Option<PointsEarned>,
+}
+
+/// Progress of the current user on a single JIG within a Playlist.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistItemProgress {
+    /// The JIG this progress is for
+    pub jig_id: JigId,
+
+    /// Whether the JIG has been started or completed
+    pub status: ProgressStatus,
+
+    /// Score from the last completed session of this JIG
+    pub points_earned: Option<PointsEarned>,
+
+    /// When the JIG was first started
+    pub started_at: DateTime<Utc>,
+
+    /// When the JIG was first completed
+    pub completed_at: Option<DateTime<Utc>>,
+
+    /// When the progress was last updated
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ItemProgress for PlaylistItemProgress {
+    type Id = JigId;
+
+    fn item_id(&self) -> JigId {
+        self.jig_id
+    }
+
+    fn status(&self) -> ProgressStatus {
+        self.status
+    }
+
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
+
+/// The current user's progress through a Playlist.
+///
+/// [`playlist::progress::Get`](crate::api::endpoints::playlist::progress::Get)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistProgressResponse {
+    /// ID of the Playlist
+    pub playlist_id: PlaylistId,
+
+    /// Progress on each JIG the user has started, in playlist order.
+    pub items: Vec<PlaylistItemProgress>,
+
+    /// Number of JIGs in the Playlist
+    pub item_count: u32,
+
+    /// Number of JIGs the user has completed
+    pub completed_count: u32,
+
+    /// Percentage of JIGs completed, `0..=100`
+    pub percent_complete: u16,
+
+    /// JIG to continue from, `None` if every JIG has been completed.
+    pub continue_jig_id: Option<JigId>,
+
+    /// When the user last made progress in the Playlist
+    pub last_played_at: Option<DateTime<Utc>>,
+}
+
+make_path_parts!(PlaylistListInProgressPath => "/v1/playlist/in-progress");
+
+/// Request for the list of Playlists the current user has started.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistListInProgressRequest {
+    /// The page number of the playlists to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// The hits per page to be returned
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_limit: Option<u32>,
+
+    /// Include playlists that have been completed.
+    #[serde(default)]
+    pub include_completed: bool,
+}
+
+/// A Playlist the user has started, with their progress.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistInProgress {
+    /// The Playlist
+    pub playlist: PlaylistResponse,
+
+    /// The user's progress through the Playlist
+    pub progress: PlaylistProgressResponse,
+}
+
+/// Response for the list of Playlists the current user has started, most recently played first.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistListInProgressResponse {
+    /// The playlists returned.
+    pub playlists: Vec<PlaylistInProgress>,
+}
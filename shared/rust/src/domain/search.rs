@@ -81,6 +81,79 @@ pub struct WebImageSearchItem {
     pub thumbnail_url: url::Url,
     /// A URL to the original image.
     pub url: url::Url,
+    /// Where the image was found.
+    pub source: WebImageSource,
+    /// Who made the image and what it's licensed under.
+    pub attribution: WebImageAttribution,
+}
+
+/// The stock image services which web images are searched on.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum WebImageSource {
+    /// [Pixabay](https://pixabay.com)
+    Pixabay,
+    /// [Openverse](https://openverse.org), openly licensed images from across the web
+    Openverse,
+    /// [Unsplash](https://unsplash.com)
+    Unsplash,
+}
+
+impl WebImageSource {
+    /// The source's name, as shown to users.
+    #[must_use]
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Pixabay => "Pixabay",
+            Self::Openverse => "Openverse",
+            Self::Unsplash => "Unsplash",
+        }
+    }
+}
+
+/// License and attribution of a web image.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct WebImageAttribution {
+    /// The image's title.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Name of the image's creator.
+    #[serde(default)]
+    pub creator: Option<String>,
+    /// The creator's page.
+    #[serde(default)]
+    pub creator_url: Option<url::Url>,
+    /// Name of the image's license, e.g. `CC BY 2.0`.
+    pub license: String,
+    /// The license's terms.
+    #[serde(default)]
+    pub license_url: Option<url::Url>,
+    /// The image's page on its source.
+    #[serde(default)]
+    pub source_url: Option<url::Url>,
+    /// Whether the license requires the image to be credited when it's used.
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl WebImageAttribution {
+    /// Credit for the image, e.g. `"Sunset" by Jane Doe on Openverse, CC BY 2.0`.
+    #[must_use]
+    pub fn text(&self, source: WebImageSource) -> String {
+        let mut text = match (&self.title, &self.creator) {
+            (Some(title), Some(creator)) => format!("\"{title}\" by {creator}"),
+            (Some(title), None) => format!("\"{title}\""),
+            (None, Some(creator)) => format!("Image by {creator}"),
+            (None, None) => "Image".to_owned(),
+        };
+
+        text.push_str(" on ");
+        text.push_str(source.display_name());
+        text.push_str(", ");
+        text.push_str(&self.license);
+
+        text
+    }
 }
 
 /// Response for successful search.
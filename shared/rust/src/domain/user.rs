@@ -5,6 +5,7 @@ use macros::make_path_parts;
 use serde::{Deserialize, Serialize, Serializer};
 use std::fmt;
 use strum_macros::Display;
+use uuid::Uuid;
 
 use crate::domain::billing::{
     AccountId, AmountInCents, PlanTier, PlanType, SchoolId, SubscriptionStatus, UserAccountSummary,
@@ -14,6 +15,7 @@ use crate::{
     domain::{
         circle::CircleId,
         image::ImageId,
+        jig::JigId,
         meta::{AffiliationId, AgeRangeId, SubjectId},
     },
 };
@@ -879,6 +881,106 @@ pub struct UserFontResponse {
 // i32 is font index
 make_path_parts!(UserFontDeletePath => "/v1/user/me/font/{}" => i32);
 
+// Media library
+
+make_path_parts!(UserMediaListPath => "/v1/user/me/media");
+
+/// Kinds of files in a user's media library.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum UserMediaKind {
+    /// An image or animation from the user image library.
+    Image,
+    /// Audio from the user audio library.
+    Audio,
+    /// A document from the user pdf library.
+    Pdf,
+}
+
+/// A file uploaded by the user.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserMedia {
+    /// The file's ID, an [`ImageId`], [`AudioId`](crate::domain::audio::AudioId) or
+    /// [`PdfId`](crate::domain::pdf::PdfId) depending on `kind`.
+    pub id: Uuid,
+
+    /// Which library the file is in.
+    pub kind: UserMediaKind,
+
+    /// Bytes stored for the file, including every rendition generated from it.
+    ///
+    /// `0` for files uploaded before storage was accounted for.
+    pub size_bytes: u64,
+
+    /// When the file was uploaded.
+    pub created_at: DateTime<Utc>,
+
+    /// JIGs created by the user which use the file, in their draft or live version.
+    pub referenced_by: Vec<JigId>,
+
+    /// Whether the file is used by anything other than a JIG, such as a playlist, resource,
+    /// course or profile image.
+    pub used_elsewhere: bool,
+}
+
+impl UserMedia {
+    /// Whether nothing uses the file, so it can be deleted.
+    #[must_use]
+    pub fn is_unreferenced(&self) -> bool {
+        self.referenced_by.is_empty() && !self.used_elsewhere
+    }
+}
+
+/// How much of their storage quota a user has used.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserStorageUsage {
+    /// Bytes stored for all of the user's files.
+    pub used_bytes: u64,
+
+    /// Most bytes the user can store, see [`PlanTier::storage_quota_bytes`].
+    pub quota_bytes: u64,
+
+    /// The plan tier the quota comes from.
+    pub plan_tier: PlanTier,
+}
+
+/// Response for [`ListMedia`](crate::api::endpoints::user::ListMedia).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserMediaListResponse {
+    /// Every file uploaded by the user, most recent first.
+    pub media: Vec<UserMedia>,
+
+    /// The user's storage usage.
+    pub storage: UserStorageUsage,
+}
+
+make_path_parts!(UserMediaDeletePath => "/v1/user/me/media/delete");
+
+/// Request for [`DeleteMedia`](crate::api::endpoints::user::DeleteMedia).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserMediaDeleteRequest {
+    /// IDs of the files to delete.
+    pub ids: Vec<Uuid>,
+}
+
+/// Response for [`DeleteMedia`](crate::api::endpoints::user::DeleteMedia).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UserMediaDeleteResponse {
+    /// IDs of the files which were deleted.
+    pub deleted: Vec<Uuid>,
+
+    /// IDs of the files which weren't deleted because something still uses them.
+    pub in_use: Vec<Uuid>,
+
+    /// The user's storage usage after the delete.
+    pub storage: UserStorageUsage,
+}
+
 //
 // Browse users
 //
@@ -35,6 +35,8 @@ pub enum AccountError {
     SchoolNameExists(SchoolNameValue),
     #[error("A school using a name with ID {0} already exists")]
     SchoolExists(SchoolNameId),
+    #[error("The school has no seats available")]
+    NoSeatsAvailable,
     #[error("{0}")]
     NotFound(String),
     #[error("Forbidden")]
@@ -54,6 +54,8 @@ pub enum BillingError {
     InvalidPromotionCode(String),
     #[error("Forbidden")]
     Forbidden,
+    #[error("Only failed or ignored webhook events can be replayed")]
+    WebhookEventNotReplayable,
     #[error("Cannot upgrade to {upgrade_to} from {upgrade_from}")]
     InvalidUpgradePlanType {
         upgrade_to: PlanType,
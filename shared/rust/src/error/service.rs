@@ -63,8 +63,8 @@ pub enum ServiceKindError {
     Mail,
     #[strum(serialize = "Media Upload Cleaner")]
     UploadCleaner,
-    #[strum(serialize = "Google Translate")]
-    GoogleTranslate,
+    #[strum(serialize = "Translation")]
+    Translate,
     #[strum(serialize = "Stripe")]
     Stripe,
 }
@@ -2,6 +2,7 @@
 
 use crate::domain::{animation::AnimationKind, audio::AudioKind};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use uuid::Uuid;
 
 /// Media Kinds
@@ -36,7 +37,7 @@ impl MediaGroupKind {
 }
 
 /// Image size Variants
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum PngImageFile {
     /// The original image
     Original,
@@ -48,6 +49,55 @@ pub enum PngImageFile {
     Thumbnail,
 }
 
+/// Sizes an image is stored at as WebP or AVIF.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RenditionSize {
+    /// The same size as one of the PNG files
+    Png(PngImageFile),
+
+    /// The resized image scaled down to a narrower width, for `srcset`s
+    ResizedWidth(ImageWidth),
+}
+
+impl RenditionSize {
+    /// Every size an image is stored at as WebP or AVIF.
+    pub const ALL: [Self; 5] = [
+        Self::Png(PngImageFile::Original),
+        Self::Png(PngImageFile::Resized),
+        Self::Png(PngImageFile::Thumbnail),
+        Self::ResizedWidth(ImageWidth::Small),
+        Self::ResizedWidth(ImageWidth::Medium),
+    ];
+}
+
+/// Narrower widths the resized image is stored at, so that small screens don't have to download
+/// the full size image.
+///
+/// Images are never scaled up, so if the resized image is already narrower than a width it's stored
+/// at its own width instead.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ImageWidth {
+    /// At most 480 pixels wide
+    Small,
+
+    /// At most 960 pixels wide
+    Medium,
+}
+
+impl ImageWidth {
+    /// Every width, narrowest first.
+    pub const ALL: [Self; 2] = [Self::Small, Self::Medium];
+
+    /// The most pixels wide the image is at this width.
+    #[must_use]
+    pub const fn pixels(self) -> u32 {
+        match self {
+            Self::Small => 480,
+            Self::Medium => 960,
+        }
+    }
+}
+
 /// Media Libraries
 #[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type))]
@@ -97,6 +147,9 @@ pub enum MediaKind {
     /// Media is an Image
     Image,
 
+    /// Media is an SVG Image, which also has PNG renditions
+    SvgImage,
+
     /// Media is audio
     Audio(AudioKind),
 
@@ -106,7 +159,7 @@ pub enum MediaKind {
 
 /// Kinds of media files
 /// FIXME: Really awkward
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum FileKind {
     /// File for an Animated Gif
     AnimationGif,
@@ -114,12 +167,27 @@ pub enum FileKind {
     /// Files for a PNG Image
     ImagePng(PngImageFile),
 
+    /// WebP renditions of a PNG Image
+    ImageWebp(RenditionSize),
+
+    /// AVIF renditions of a PNG Image, only stored when the API is built with AVIF support
+    ImageAvif(RenditionSize),
+
+    /// Sanitized SVG Image, stored alongside its PNG renditions
+    ImageSvg,
+
     // Spritesheet(Image,JSON),
     /// File for Mp3 audio
     AudioMp3,
 
+    /// Opus (in an Ogg container) rendition of processed audio
+    AudioOpus,
+
     /// File for pdf documents
     DocumentPdf,
+
+    /// JPEG preview of a page of a pdf document, numbered from 1
+    DocumentPdfPage(u32),
 }
 
 impl FileKind {
@@ -129,21 +197,64 @@ impl FileKind {
         match self {
             Self::AnimationGif => "image/gif",
             Self::ImagePng(_) => "image/png",
+            Self::ImageWebp(_) => "image/webp",
+            Self::ImageAvif(_) => "image/avif",
+            Self::ImageSvg => "image/svg+xml",
             Self::AudioMp3 => "audio/mp3",
+            Self::AudioOpus => "audio/ogg",
             Self::DocumentPdf => "application/pdf",
+            Self::DocumentPdfPage(_) => "image/jpeg",
         }
     }
 
+    /// Every file which may be stored for an image, in any format.
     #[must_use]
-    const fn suffix(self) -> &'static str {
-        match self {
+    pub fn image_files() -> Vec<Self> {
+        [
+            Self::ImagePng(PngImageFile::Original),
+            Self::ImagePng(PngImageFile::Resized),
+            Self::ImagePng(PngImageFile::Thumbnail),
+        ]
+        .into_iter()
+        .chain(RenditionSize::ALL.into_iter().map(Self::ImageWebp))
+        .chain(RenditionSize::ALL.into_iter().map(Self::ImageAvif))
+        .chain(std::iter::once(Self::ImageSvg))
+        .collect()
+    }
+
+    /// Every file which may be stored for a pdf with `preview_page_count` page previews.
+    #[must_use]
+    pub fn pdf_files(preview_page_count: u32) -> Vec<Self> {
+        std::iter::once(Self::DocumentPdf)
+            .chain((1..=preview_page_count).map(Self::DocumentPdfPage))
+            .collect()
+    }
+
+    #[must_use]
+    fn suffix(self) -> Cow<'static, str> {
+        let suffix = match self {
             Self::AnimationGif => "animation.gif",
             Self::ImagePng(PngImageFile::Original) => "original.png",
             Self::ImagePng(PngImageFile::Thumbnail) => "thumbnail.png",
             Self::ImagePng(PngImageFile::Resized) => "resized.png",
+            Self::ImageWebp(RenditionSize::Png(PngImageFile::Original)) => "original.webp",
+            Self::ImageWebp(RenditionSize::Png(PngImageFile::Thumbnail)) => "thumbnail.webp",
+            Self::ImageWebp(RenditionSize::Png(PngImageFile::Resized)) => "resized.webp",
+            Self::ImageWebp(RenditionSize::ResizedWidth(ImageWidth::Small)) => "resized-480.webp",
+            Self::ImageWebp(RenditionSize::ResizedWidth(ImageWidth::Medium)) => "resized-960.webp",
+            Self::ImageAvif(RenditionSize::Png(PngImageFile::Original)) => "original.avif",
+            Self::ImageAvif(RenditionSize::Png(PngImageFile::Thumbnail)) => "thumbnail.avif",
+            Self::ImageAvif(RenditionSize::Png(PngImageFile::Resized)) => "resized.avif",
+            Self::ImageAvif(RenditionSize::ResizedWidth(ImageWidth::Small)) => "resized-480.avif",
+            Self::ImageAvif(RenditionSize::ResizedWidth(ImageWidth::Medium)) => "resized-960.avif",
+            Self::ImageSvg => "image.svg",
             Self::AudioMp3 => "audio.mp3",
+            Self::AudioOpus => "audio.opus",
             Self::DocumentPdf => "document.pdf",
-        }
+            Self::DocumentPdfPage(page) => return Cow::Owned(format!("page-{page}.jpg")),
+        };
+
+        Cow::Borrowed(suffix)
     }
 }
 
@@ -156,9 +267,35 @@ impl std::str::FromStr for FileKind {
             "original.png" => Ok(Self::ImagePng(PngImageFile::Original)),
             "thumbnail.png" => Ok(Self::ImagePng(PngImageFile::Thumbnail)),
             "resized.png" => Ok(Self::ImagePng(PngImageFile::Resized)),
+            "original.webp" => Ok(Self::ImageWebp(RenditionSize::Png(PngImageFile::Original))),
+            "thumbnail.webp" => Ok(Self::ImageWebp(RenditionSize::Png(PngImageFile::Thumbnail))),
+            "resized.webp" => Ok(Self::ImageWebp(RenditionSize::Png(PngImageFile::Resized))),
+            "resized-480.webp" => Ok(Self::ImageWebp(RenditionSize::ResizedWidth(
+                ImageWidth::Small,
+            ))),
+            "resized-960.webp" => Ok(Self::ImageWebp(RenditionSize::ResizedWidth(
+                ImageWidth::Medium,
+            ))),
+            "original.avif" => Ok(Self::ImageAvif(RenditionSize::Png(PngImageFile::Original))),
+            "thumbnail.avif" => Ok(Self::ImageAvif(RenditionSize::Png(PngImageFile::Thumbnail))),
+            "resized.avif" => Ok(Self::ImageAvif(RenditionSize::Png(PngImageFile::Resized))),
+            "resized-480.avif" => Ok(Self::ImageAvif(RenditionSize::ResizedWidth(
+                ImageWidth::Small,
+            ))),
+            "resized-960.avif" => Ok(Self::ImageAvif(RenditionSize::ResizedWidth(
+                ImageWidth::Medium,
+            ))),
+            "image.svg" => Ok(Self::ImageSvg),
             "audio.mp3" => Ok(Self::AudioMp3),
+            "audio.opus" => Ok(Self::AudioOpus),
             "document.pdf" => Ok(Self::DocumentPdf),
-            _ => Err(anyhow::anyhow!("media type not recognized")),
+            _ => s
+                .strip_prefix("page-")
+                .and_then(|s| s.strip_suffix(".jpg"))
+                .and_then(|page| page.parse().ok())
+                .filter(|page| *page > 0)
+                .map(Self::DocumentPdfPage)
+                .ok_or_else(|| anyhow::anyhow!("media type not recognized")),
         }
     }
 }
@@ -182,10 +319,11 @@ pub struct MediaKey {
 /// this is *not* a full url, (for CDN it's missing the domain)
 #[must_use]
 pub fn media_key(library: MediaLibrary, id: Uuid, file_kind: FileKind) -> String {
-    format!(
-        "media/{}/{}/{}",
-        library.to_str(),
-        id.hyphenated(),
-        file_kind.suffix()
-    )
+    format!("{}{}", media_prefix(library, id), file_kind.suffix())
+}
+
+/// gives the prefix of the keys of every file stored for some media, see [`media_key`]
+#[must_use]
+pub fn media_prefix(library: MediaLibrary, id: Uuid) -> String {
+    format!("media/{}/{}/", library.to_str(), id.hyphenated())
 }